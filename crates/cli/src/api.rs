@@ -0,0 +1,289 @@
+use std::{env, path::PathBuf};
+
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::error::CliError;
+
+/// Client for the vibe-kanban HTTP API
+#[derive(Clone)]
+pub struct ApiClient {
+    client: Client,
+    base_url: String,
+    token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiResponseEnvelope {
+    success: bool,
+    data: Option<Value>,
+    message: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Task {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub title: String,
+    pub status: String,
+    #[serde(default)]
+    pub has_in_progress_attempt: bool,
+    #[serde(default)]
+    pub last_attempt_failed: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Repo {
+    pub id: Uuid,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GitBranch {
+    pub name: String,
+    pub is_current: bool,
+    pub is_remote: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Workspace {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub branch: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExecutionProcess {
+    pub id: Uuid,
+    pub run_reason: String,
+    pub status: String,
+    pub exit_code: Option<i64>,
+    #[serde(default)]
+    pub dropped: bool,
+    pub started_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RawLogSnapshot {
+    pub lines: Vec<RawLogLine>,
+    pub finished: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "stream", content = "content", rename_all = "snake_case")]
+pub enum RawLogLine {
+    Stdout(String),
+    Stderr(String),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuestionOption {
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Question {
+    pub question: String,
+    #[serde(default)]
+    pub options: Vec<QuestionOption>,
+    #[serde(default, rename = "multiSelect")]
+    pub multi_select: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PendingQuestion {
+    pub id: String,
+    pub execution_process_id: Uuid,
+    pub questions: Vec<Question>,
+    pub timeout_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PendingApproval {
+    pub id: String,
+    pub execution_process_id: Uuid,
+    pub tool_name: String,
+    pub tool_input: Value,
+    pub timeout_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuestionAnswer {
+    pub question_index: usize,
+    pub selected_options: Vec<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_text: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    Approved,
+    Denied { reason: Option<String> },
+}
+
+impl ApiClient {
+    pub fn new(base_url: String, token: Option<String>) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+        }
+    }
+
+    /// Resolve the server URL the same way the MCP server does: an explicit URL, then
+    /// HOST + BACKEND_PORT/PORT, then the port file written by a locally running server.
+    pub fn resolve_base_url(url: Option<String>) -> Result<String, CliError> {
+        if let Some(url) = url {
+            return Ok(url);
+        }
+        let host = env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+        let port = match env::var("BACKEND_PORT").or_else(|_| env::var("PORT")) {
+            Ok(port) => port,
+            Err(_) => std::fs::read_to_string(port_file_path())
+                .map_err(|_| CliError::ServerNotFound)?
+                .trim()
+                .to_string(),
+        };
+        let port: u16 = port
+            .parse()
+            .map_err(|_| CliError::InvalidArgument(format!("Invalid port value '{port}'")))?;
+        Ok(format!("http://{host}:{port}"))
+    }
+
+    fn request(&self, builder: RequestBuilder) -> RequestBuilder {
+        match &self.token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/api/{}", self.base_url, path.trim_start_matches('/'))
+    }
+
+    async fn send(&self, builder: RequestBuilder) -> Result<reqwest::Response, CliError> {
+        let response = self.request(builder).send().await?;
+        let status = response.status();
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(CliError::Unauthorized);
+        }
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = serde_json::from_str::<ApiResponseEnvelope>(&body)
+                .ok()
+                .and_then(|e| e.message)
+                .unwrap_or(body);
+            return Err(CliError::Api(format!("{status}: {message}")));
+        }
+        Ok(response)
+    }
+
+    /// Send a request to an endpoint that wraps its result in an `ApiResponse`.
+    async fn send_api(&self, builder: RequestBuilder) -> Result<Value, CliError> {
+        let envelope: ApiResponseEnvelope = self.send(builder).await?.json().await?;
+        if !envelope.success {
+            return Err(CliError::Api(
+                envelope
+                    .message
+                    .unwrap_or_else(|| "Unknown error".to_string()),
+            ));
+        }
+        Ok(envelope.data.unwrap_or(Value::Null))
+    }
+
+    pub async fn get(&self, path: &str) -> Result<Value, CliError> {
+        let url = self.url(path);
+        debug!("GET {url}");
+        self.send_api(self.client.get(&url)).await
+    }
+
+    pub async fn post<B: Serialize>(&self, path: &str, body: &B) -> Result<Value, CliError> {
+        let url = self.url(path);
+        debug!("POST {url}");
+        self.send_api(self.client.post(&url).json(body)).await
+    }
+
+    /// POST to an endpoint that returns its result directly rather than in an `ApiResponse`.
+    pub async fn post_raw<B: Serialize>(&self, path: &str, body: &B) -> Result<Value, CliError> {
+        let url = self.url(path);
+        debug!("POST {url}");
+        Ok(self
+            .send(self.client.post(&url).json(body))
+            .await?
+            .json()
+            .await?)
+    }
+
+    pub async fn get_as<T: DeserializeOwned>(&self, path: &str) -> Result<T, CliError> {
+        Ok(serde_json::from_value(self.get(path).await?)?)
+    }
+
+    pub async fn pending_questions(&self) -> Result<Vec<PendingQuestion>, CliError> {
+        self.get_as("/questions").await
+    }
+
+    pub async fn pending_approvals(&self) -> Result<Vec<PendingApproval>, CliError> {
+        self.get_as("/approvals").await
+    }
+
+    pub async fn answer_question(
+        &self,
+        question: &PendingQuestion,
+        answers: &[QuestionAnswer],
+    ) -> Result<Value, CliError> {
+        self.post_raw(
+            &format!("/questions/{}/respond", question.id),
+            &json!({
+                "execution_process_id": question.execution_process_id,
+                "answers": answers,
+            }),
+        )
+        .await
+    }
+
+    pub async fn respond_to_approval(
+        &self,
+        approval: &PendingApproval,
+        decision: ApprovalDecision,
+    ) -> Result<Value, CliError> {
+        self.post_raw(
+            &format!("/approvals/{}/respond", approval.id),
+            &json!({
+                "execution_process_id": approval.execution_process_id,
+                "status": decision,
+            }),
+        )
+        .await
+    }
+
+    pub async fn workspace_processes(
+        &self,
+        workspace_id: Uuid,
+    ) -> Result<Vec<ExecutionProcess>, CliError> {
+        self.get_as(&format!("/execution-processes?workspace_id={workspace_id}"))
+            .await
+    }
+
+    /// Open a server-sent events endpoint. The response body is left unread for the caller.
+    #[cfg(feature = "tui")]
+    pub async fn open_stream(&self, path: &str) -> Result<reqwest::Response, CliError> {
+        let url = self.url(path);
+        debug!("GET {url} (stream)");
+        self.send(
+            self.client
+                .get(&url)
+                .header(reqwest::header::ACCEPT, "text/event-stream"),
+        )
+        .await
+    }
+}
+
+fn port_file_path() -> PathBuf {
+    env::temp_dir().join("vibe-kanban").join("vibe-kanban.port")
+}
@@ -0,0 +1,77 @@
+use clap::Subcommand;
+
+use crate::{
+    api::{ApiClient, ApprovalDecision, PendingApproval},
+    error::CliError,
+    output::{OutputFormat, Table, truncate},
+};
+
+#[derive(Subcommand, Debug)]
+pub enum ApprovalCommand {
+    /// List tool calls waiting for approval
+    List,
+    /// Let the agent run the tool call
+    Approve { approval_id: String },
+    /// Reject the tool call, optionally telling the agent why
+    Deny {
+        approval_id: String,
+        #[arg(short, long)]
+        reason: Option<String>,
+    },
+}
+
+pub async fn run(
+    client: &ApiClient,
+    output: OutputFormat,
+    command: ApprovalCommand,
+) -> Result<(), CliError> {
+    let (approval_id, decision) = match command {
+        ApprovalCommand::List => {
+            let data = client.get("/approvals").await?;
+            let approvals: Vec<PendingApproval> = serde_json::from_value(data.clone())?;
+            output.print(&data, || approval_table(&approvals));
+            return Ok(());
+        }
+        ApprovalCommand::Approve { approval_id } => (approval_id, ApprovalDecision::Approved),
+        ApprovalCommand::Deny {
+            approval_id,
+            reason,
+        } => (approval_id, ApprovalDecision::Denied { reason }),
+    };
+
+    let approval = client
+        .pending_approvals()
+        .await?
+        .into_iter()
+        .find(|a| a.id == approval_id)
+        .ok_or_else(|| {
+            CliError::InvalidArgument(format!(
+                "Approval {approval_id} is not pending (already answered or timed out)"
+            ))
+        })?;
+
+    let data = client.respond_to_approval(&approval, decision).await?;
+    output.print(&data, || {
+        let mut table = Table::new(vec!["approval", "tool", "status"]);
+        table.row(vec![
+            approval_id.clone(),
+            approval.tool_name.clone(),
+            data["status"].as_str().unwrap_or_default().to_string(),
+        ]);
+        table
+    });
+    Ok(())
+}
+
+fn approval_table(approvals: &[PendingApproval]) -> Table {
+    let mut table = Table::new(vec!["id", "tool", "input", "timeout"]);
+    for approval in approvals {
+        table.row(vec![
+            approval.id.clone(),
+            approval.tool_name.clone(),
+            truncate(&approval.tool_input.to_string(), 60),
+            approval.timeout_at.format("%H:%M").to_string(),
+        ]);
+    }
+    table
+}
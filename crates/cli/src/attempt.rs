@@ -0,0 +1,297 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use chrono::Local;
+use clap::Subcommand;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        ApiClient, ExecutionProcess, GitBranch, RawLogLine, RawLogSnapshot, Repo, Task, Workspace,
+    },
+    error::CliError,
+    output::{OutputFormat, Table, truncate},
+};
+
+#[derive(Subcommand, Debug)]
+pub enum AttemptCommand {
+    /// Start a coding agent on a task
+    Start {
+        task_id: Uuid,
+        /// Coding agent to run, e.g. CLAUDE_CODE, CODEX, GEMINI, AMP
+        #[arg(short, long, default_value = "CLAUDE_CODE")]
+        executor: String,
+        /// Executor profile variant, e.g. PLAN
+        #[arg(long)]
+        variant: Option<String>,
+        /// Base branch per repository as REPO_ID=BRANCH. Defaults to each project repository's
+        /// checked-out branch.
+        #[arg(long = "repo", value_name = "REPO_ID=BRANCH")]
+        repos: Vec<String>,
+    },
+    /// Follow an attempt until its coding agent finishes
+    Watch {
+        workspace_id: Uuid,
+        /// Also print the processes' stdout and stderr
+        #[arg(long)]
+        logs: bool,
+        /// Seconds between polls
+        #[arg(long, default_value_t = 2)]
+        interval: u64,
+    },
+}
+
+pub async fn run(
+    client: &ApiClient,
+    output: OutputFormat,
+    command: AttemptCommand,
+) -> Result<(), CliError> {
+    match command {
+        AttemptCommand::Start {
+            task_id,
+            executor,
+            variant,
+            repos,
+        } => start(client, output, task_id, executor, variant, repos).await,
+        AttemptCommand::Watch {
+            workspace_id,
+            logs,
+            interval,
+        } => watch(client, output, workspace_id, logs, interval).await,
+    }
+}
+
+async fn start(
+    client: &ApiClient,
+    output: OutputFormat,
+    task_id: Uuid,
+    executor: String,
+    variant: Option<String>,
+    repos: Vec<String>,
+) -> Result<(), CliError> {
+    let repos = if repos.is_empty() {
+        default_repos(client, task_id).await?
+    } else {
+        repos
+            .iter()
+            .map(|spec| parse_repo(spec))
+            .collect::<Result<_, _>>()?
+    };
+
+    let data = client
+        .post(
+            "/task-attempts",
+            &json!({
+                "task_id": task_id,
+                "executor_profile_id": {
+                    "executor": executor.trim().replace('-', "_").to_ascii_uppercase(),
+                    "variant": variant,
+                },
+                "repos": repos
+                    .iter()
+                    .map(|(repo_id, branch)| json!({ "repo_id": repo_id, "target_branch": branch }))
+                    .collect::<Vec<_>>(),
+            }),
+        )
+        .await?;
+    let workspace: Workspace = serde_json::from_value(data.clone())?;
+
+    output.print(&data, || {
+        let mut table = Table::new(vec!["workspace", "task", "branch"]);
+        table.row(vec![
+            workspace.id.to_string(),
+            workspace.task_id.to_string(),
+            workspace.branch.clone(),
+        ]);
+        table
+    });
+    Ok(())
+}
+
+fn parse_repo(spec: &str) -> Result<(Uuid, String), CliError> {
+    let invalid = || CliError::InvalidArgument(format!("Expected REPO_ID=BRANCH, got '{spec}'"));
+    let (repo_id, branch) = spec.split_once('=').ok_or_else(invalid)?;
+    let repo_id = repo_id.trim().parse().map_err(|_| invalid())?;
+    if branch.trim().is_empty() {
+        return Err(invalid());
+    }
+    Ok((repo_id, branch.trim().to_string()))
+}
+
+/// Every repository of the task's project, based on its checked-out branch.
+async fn default_repos(client: &ApiClient, task_id: Uuid) -> Result<Vec<(Uuid, String)>, CliError> {
+    let task: Task = client.get_as(&format!("/tasks/{task_id}")).await?;
+    let repos: Vec<Repo> = client
+        .get_as(&format!("/projects/{}/repositories", task.project_id))
+        .await?;
+
+    let mut selected = Vec::new();
+    for repo in repos {
+        let branches: Vec<GitBranch> = client
+            .get_as(&format!("/repos/{}/branches", repo.id))
+            .await?;
+        let branch = branches
+            .into_iter()
+            .find(|b| b.is_current && !b.is_remote)
+            .ok_or_else(|| {
+                CliError::InvalidArgument(format!(
+                    "Repository {} has no checked-out branch; pass --repo {}=BRANCH",
+                    repo.name, repo.id
+                ))
+            })?;
+        selected.push((repo.id, branch.name));
+    }
+    Ok(selected)
+}
+
+async fn watch(
+    client: &ApiClient,
+    output: OutputFormat,
+    workspace_id: Uuid,
+    logs: bool,
+    interval: u64,
+) -> Result<(), CliError> {
+    let mut statuses: HashMap<Uuid, String> = HashMap::new();
+    let mut log_cursors: HashMap<Uuid, LogCursor> = HashMap::new();
+    let mut announced: HashSet<String> = HashSet::new();
+
+    loop {
+        let processes: Vec<ExecutionProcess> = client
+            .workspace_processes(workspace_id)
+            .await?
+            .into_iter()
+            .filter(|p| !p.dropped)
+            .collect();
+
+        for process in &processes {
+            if logs {
+                print_new_logs(client, output, process, &mut log_cursors).await?;
+            }
+            if statuses.get(&process.id) != Some(&process.status) {
+                statuses.insert(process.id, process.status.clone());
+                emit(
+                    output,
+                    json!({
+                        "event": "process",
+                        "id": process.id,
+                        "run_reason": process.run_reason,
+                        "status": process.status,
+                        "exit_code": process.exit_code,
+                    }),
+                    format!("{} {}", process.run_reason, process.status),
+                );
+            }
+        }
+
+        let process_ids: HashSet<Uuid> = processes.iter().map(|p| p.id).collect();
+        for question in client.pending_questions().await? {
+            if process_ids.contains(&question.execution_process_id)
+                && announced.insert(question.id.clone())
+            {
+                let text = question
+                    .questions
+                    .first()
+                    .map(|q| truncate(&q.question, 80))
+                    .unwrap_or_default();
+                emit(
+                    output,
+                    json!({ "event": "question", "id": question.id, "question": text }),
+                    format!(
+                        "question {id}: {text}\n  answer with `vibe question answer {id} --answer 0=<option>`",
+                        id = question.id
+                    ),
+                );
+            }
+        }
+        for approval in client.pending_approvals().await? {
+            if process_ids.contains(&approval.execution_process_id)
+                && announced.insert(approval.id.clone())
+            {
+                emit(
+                    output,
+                    json!({
+                        "event": "approval",
+                        "id": approval.id,
+                        "tool_name": approval.tool_name,
+                    }),
+                    format!(
+                        "approval {id} requested for {}\n  respond with `vibe approval approve {id}` or `vibe approval deny {id}`",
+                        approval.tool_name,
+                        id = approval.id
+                    ),
+                );
+            }
+        }
+
+        let latest_agent = processes
+            .iter()
+            .filter(|p| p.run_reason == "codingagent")
+            .max_by_key(|p| p.started_at);
+        let any_running = processes.iter().any(|p| p.status == "running");
+        if let Some(agent) = latest_agent
+            && !any_running
+        {
+            return match agent.status.as_str() {
+                "completed" => Ok(()),
+                status => Err(CliError::AttemptFailed(status.to_string())),
+            };
+        }
+
+        tokio::time::sleep(Duration::from_secs(interval.max(1))).await;
+    }
+}
+
+#[derive(Default)]
+struct LogCursor {
+    printed: usize,
+    /// Set once the logs of a finished process have been printed in full
+    done: bool,
+}
+
+async fn print_new_logs(
+    client: &ApiClient,
+    output: OutputFormat,
+    process: &ExecutionProcess,
+    log_cursors: &mut HashMap<Uuid, LogCursor>,
+) -> Result<(), CliError> {
+    let cursor = log_cursors.entry(process.id).or_default();
+    if cursor.done {
+        return Ok(());
+    }
+
+    let snapshot: RawLogSnapshot = client
+        .get_as(&format!("/execution-processes/{}/logs", process.id))
+        .await?;
+    for line in snapshot.lines.iter().skip(cursor.printed) {
+        let (stream, content) = match line {
+            RawLogLine::Stdout(content) => ("stdout", content),
+            RawLogLine::Stderr(content) => ("stderr", content),
+        };
+        match output {
+            OutputFormat::Json => println!(
+                "{}",
+                json!({
+                    "event": "log",
+                    "process_id": process.id,
+                    "stream": stream,
+                    "content": content,
+                })
+            ),
+            OutputFormat::Table => print!("{content}"),
+        }
+    }
+    cursor.printed = cursor.printed.max(snapshot.lines.len());
+    cursor.done = snapshot.finished;
+    Ok(())
+}
+
+/// Print a watch event as a JSON line or a timestamped message.
+fn emit(output: OutputFormat, event: serde_json::Value, message: String) {
+    match output {
+        OutputFormat::Json => println!("{event}"),
+        OutputFormat::Table => println!("[{}] {message}", Local::now().format("%H:%M:%S")),
+    }
+}
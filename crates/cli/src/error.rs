@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error(
+        "Could not find a running vibe-kanban server. Start it or pass --url / set VIBE_BACKEND_URL."
+    )]
+    ServerNotFound,
+
+    #[error("Failed to reach the server: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Unauthorized. Pass --token or set VIBE_TOKEN when multi-user mode is enabled.")]
+    Unauthorized,
+
+    #[error("API request failed: {0}")]
+    Api(String),
+
+    #[error("{0}")]
+    InvalidArgument(String),
+
+    #[error("Attempt finished with status {0}")]
+    AttemptFailed(String),
+
+    #[error("Failed to parse server response: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    #[cfg(feature = "tui")]
+    #[error("Terminal error: {0}")]
+    Terminal(#[from] std::io::Error),
+}
@@ -0,0 +1,103 @@
+mod api;
+mod approval;
+mod attempt;
+mod error;
+mod output;
+mod question;
+mod task;
+#[cfg(feature = "tui")]
+mod tui;
+
+use anyhow::Result;
+use api::ApiClient;
+use approval::ApprovalCommand;
+use attempt::AttemptCommand;
+use clap::{Parser, Subcommand};
+use error::CliError;
+use output::OutputFormat;
+use question::QuestionCommand;
+use task::TaskCommand;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Parser, Debug)]
+#[command(name = "vibe")]
+#[command(about = "Command-line client for a running vibe-kanban server")]
+#[command(version)]
+struct Args {
+    /// Server URL. Defaults to the locally running server.
+    #[arg(long, global = true, env = "VIBE_BACKEND_URL")]
+    url: Option<String>,
+
+    /// Session token, needed when the server runs in multi-user mode
+    #[arg(long, global = true, env = "VIBE_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+
+    /// Output format
+    #[arg(short, long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    output: OutputFormat,
+
+    /// Enable verbose output
+    #[arg(short, long, global = true, default_value_t = false)]
+    verbose: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Create and list tasks
+    #[command(subcommand)]
+    Task(TaskCommand),
+    /// Start and follow attempts
+    #[command(subcommand)]
+    Attempt(AttemptCommand),
+    /// List and answer agent questions
+    #[command(subcommand)]
+    Question(QuestionCommand),
+    /// List, approve and deny tool call approvals
+    #[command(subcommand)]
+    Approval(ApprovalCommand),
+    /// Interactive dashboard with the board, live attempt logs, questions and approvals
+    #[cfg(feature = "tui")]
+    Tui {
+        #[arg(long, env = "VIBE_PROJECT_ID")]
+        project_id: uuid::Uuid,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let filter = if args.verbose {
+        EnvFilter::new("debug")
+    } else {
+        EnvFilter::new("warn")
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .init();
+
+    if let Err(e) = run(args).await {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+async fn run(args: Args) -> Result<(), CliError> {
+    let client = ApiClient::new(ApiClient::resolve_base_url(args.url)?, args.token);
+    let output = args.output;
+
+    match args.command {
+        Command::Task(command) => task::run(&client, output, command).await,
+        Command::Attempt(command) => attempt::run(&client, output, command).await,
+        Command::Question(command) => question::run(&client, output, command).await,
+        Command::Approval(command) => approval::run(&client, output, command).await,
+        #[cfg(feature = "tui")]
+        Command::Tui { project_id } => tui::run(client, project_id).await,
+    }
+}
@@ -0,0 +1,98 @@
+use clap::ValueEnum;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+}
+
+impl OutputFormat {
+    /// Print the raw API data as JSON, or the table built from it.
+    pub fn print(self, data: &Value, table: impl FnOnce() -> Table) {
+        match self {
+            OutputFormat::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(data).unwrap_or_else(|_| data.to_string())
+            ),
+            OutputFormat::Table => print!("{}", table().render()),
+        }
+    }
+}
+
+/// Plain-text table with left-aligned columns sized to their widest cell.
+pub struct Table {
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: Vec<&'static str>) -> Self {
+        Self {
+            headers,
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    pub fn render(&self) -> String {
+        if self.rows.is_empty() {
+            return "(none)\n".to_string();
+        }
+
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let mut out = String::new();
+        let headers: Vec<String> = self.headers.iter().map(|h| h.to_uppercase()).collect();
+        for row in std::iter::once(&headers).chain(&self.rows) {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Shorten `text` to at most `max` characters for table cells.
+pub fn truncate(text: &str, max: usize) -> String {
+    let text = text.lines().next().unwrap_or_default();
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        let cut: String = text.chars().take(max.saturating_sub(1)).collect();
+        format!("{cut}…")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_aligned_columns() {
+        let mut table = Table::new(vec!["id", "title"]);
+        table.row(vec!["1".to_string(), "First".to_string()]);
+        table.row(vec!["22".to_string(), "Second".to_string()]);
+        assert_eq!(table.render(), "ID  TITLE\n1   First\n22  Second\n");
+    }
+
+    #[test]
+    fn truncates_long_cells() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("a longer title", 8), "a longe…");
+        assert_eq!(truncate("first line\nsecond", 20), "first line");
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+
+use clap::Subcommand;
+
+use crate::{
+    api::{ApiClient, PendingQuestion, QuestionAnswer},
+    error::CliError,
+    output::{OutputFormat, Table, truncate},
+};
+
+#[derive(Subcommand, Debug)]
+pub enum QuestionCommand {
+    /// List questions agents are waiting on
+    List,
+    /// Answer a pending question
+    Answer {
+        question_id: String,
+        /// Selected options as QUESTION=OPTION[,OPTION...], using 0-based indices from `list`
+        #[arg(long = "answer", value_name = "QUESTION=OPTIONS")]
+        answers: Vec<String>,
+        /// Free-form answer as QUESTION=TEXT
+        #[arg(long = "text", value_name = "QUESTION=TEXT")]
+        texts: Vec<String>,
+    },
+}
+
+pub async fn run(
+    client: &ApiClient,
+    output: OutputFormat,
+    command: QuestionCommand,
+) -> Result<(), CliError> {
+    match command {
+        QuestionCommand::List => {
+            let data = client.get("/questions").await?;
+            let questions: Vec<PendingQuestion> = serde_json::from_value(data.clone())?;
+            output.print(&data, || question_table(&questions));
+        }
+        QuestionCommand::Answer {
+            question_id,
+            answers,
+            texts,
+        } => {
+            let pending = client.pending_questions().await?;
+            let question = pending
+                .into_iter()
+                .find(|q| q.id == question_id)
+                .ok_or_else(|| {
+                    CliError::InvalidArgument(format!(
+                        "Question {question_id} is not pending (already answered or timed out)"
+                    ))
+                })?;
+
+            let answers = build_answers(&answers, &texts, question.questions.len())?;
+            let data = client.answer_question(&question, &answers).await?;
+            output.print(&data, || {
+                let mut table = Table::new(vec!["question", "answers"]);
+                table.row(vec![question_id.clone(), answers.len().to_string()]);
+                table
+            });
+        }
+    }
+    Ok(())
+}
+
+fn question_table(questions: &[PendingQuestion]) -> Table {
+    let mut table = Table::new(vec!["id", "#", "question", "options", "timeout"]);
+    for pending in questions {
+        for (index, question) in pending.questions.iter().enumerate() {
+            let options = question
+                .options
+                .iter()
+                .enumerate()
+                .map(|(i, o)| format!("{i}:{}", o.label))
+                .collect::<Vec<_>>()
+                .join(" | ");
+            let options = if question.multi_select {
+                format!("{options} (multi)")
+            } else {
+                options
+            };
+            table.row(vec![
+                pending.id.clone(),
+                index.to_string(),
+                truncate(&question.question, 60),
+                truncate(&options, 60),
+                pending.timeout_at.format("%H:%M").to_string(),
+            ]);
+        }
+    }
+    table
+}
+
+fn split_indexed(spec: &str) -> Result<(usize, &str), CliError> {
+    spec.split_once('=')
+        .and_then(|(index, rest)| Some((index.trim().parse().ok()?, rest)))
+        .ok_or_else(|| CliError::InvalidArgument(format!("Expected QUESTION=VALUE, got '{spec}'")))
+}
+
+fn answer_entry(
+    by_index: &mut BTreeMap<usize, QuestionAnswer>,
+    index: usize,
+) -> &mut QuestionAnswer {
+    by_index.entry(index).or_insert_with(|| QuestionAnswer {
+        question_index: index,
+        selected_options: Vec::new(),
+        custom_text: None,
+    })
+}
+
+fn build_answers(
+    answers: &[String],
+    texts: &[String],
+    question_count: usize,
+) -> Result<Vec<QuestionAnswer>, CliError> {
+    let mut by_index: BTreeMap<usize, QuestionAnswer> = BTreeMap::new();
+
+    for spec in answers {
+        let (index, options) = split_indexed(spec)?;
+        let options = options
+            .split(',')
+            .map(|o| o.trim().parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| {
+                CliError::InvalidArgument(format!("Options must be numbers, got '{spec}'"))
+            })?;
+        answer_entry(&mut by_index, index)
+            .selected_options
+            .extend(options);
+    }
+    for spec in texts {
+        let (index, text) = split_indexed(spec)?;
+        answer_entry(&mut by_index, index).custom_text = Some(text.to_string());
+    }
+
+    if by_index.is_empty() {
+        return Err(CliError::InvalidArgument(
+            "Provide at least one --answer or --text".to_string(),
+        ));
+    }
+    if let Some(index) = by_index.keys().find(|i| **i >= question_count) {
+        return Err(CliError::InvalidArgument(format!(
+            "Question index {index} out of range (this request has {question_count})"
+        )));
+    }
+    Ok(by_index.into_values().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_answers_from_flags() {
+        let answers = build_answers(
+            &["0=1".to_string(), "1=0,2".to_string()],
+            &["1=also this".to_string()],
+            2,
+        )
+        .unwrap();
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[0].selected_options, vec![1]);
+        assert_eq!(answers[1].selected_options, vec![0, 2]);
+        assert_eq!(answers[1].custom_text.as_deref(), Some("also this"));
+
+        assert!(build_answers(&[], &[], 1).is_err());
+        assert!(build_answers(&["2=0".to_string()], &[], 1).is_err());
+        assert!(build_answers(&["0=x".to_string()], &[], 1).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use clap::Subcommand;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    api::{ApiClient, Task},
+    error::CliError,
+    output::{OutputFormat, Table, truncate},
+};
+
+const TASK_STATUSES: [&str; 6] = [
+    "todo",
+    "inprogress",
+    "blocked",
+    "inreview",
+    "done",
+    "cancelled",
+];
+
+#[derive(Subcommand, Debug)]
+pub enum TaskCommand {
+    /// Create a task in a project
+    Create {
+        #[arg(long, env = "VIBE_PROJECT_ID")]
+        project_id: Uuid,
+        title: String,
+        #[arg(short, long)]
+        description: Option<String>,
+    },
+    /// List a project's tasks
+    List {
+        #[arg(long, env = "VIBE_PROJECT_ID")]
+        project_id: Uuid,
+        /// Only show tasks with this status (todo, inprogress, blocked, inreview, done, cancelled)
+        #[arg(long)]
+        status: Option<String>,
+    },
+}
+
+pub async fn run(
+    client: &ApiClient,
+    output: OutputFormat,
+    command: TaskCommand,
+) -> Result<(), CliError> {
+    match command {
+        TaskCommand::Create {
+            project_id,
+            title,
+            description,
+        } => {
+            let data = client
+                .post(
+                    "/tasks",
+                    &json!({
+                        "project_id": project_id,
+                        "title": title,
+                        "description": description,
+                    }),
+                )
+                .await?;
+            let task: Task = serde_json::from_value(data.clone())?;
+            output.print(&data, || task_table(std::slice::from_ref(&task)));
+        }
+        TaskCommand::List { project_id, status } => {
+            if let Some(status) = &status
+                && !TASK_STATUSES.contains(&status.as_str())
+            {
+                return Err(CliError::InvalidArgument(format!(
+                    "Invalid status '{status}'. Valid values: {}",
+                    TASK_STATUSES.join(", ")
+                )));
+            }
+
+            let mut data = client
+                .get(&format!("/tasks?project_id={project_id}"))
+                .await?;
+            if let (Some(status), Some(tasks)) = (&status, data.as_array_mut()) {
+                tasks.retain(|t| t["status"].as_str() == Some(status));
+            }
+            let tasks: Vec<Task> = serde_json::from_value(data.clone())?;
+            output.print(&data, || task_table(&tasks));
+        }
+    }
+    Ok(())
+}
+
+fn task_table(tasks: &[Task]) -> Table {
+    let mut table = Table::new(vec!["id", "status", "attempt", "title", "created"]);
+    for task in tasks {
+        let attempt = if task.has_in_progress_attempt {
+            "running"
+        } else if task.last_attempt_failed {
+            "failed"
+        } else {
+            ""
+        };
+        table.row(vec![
+            task.id.to_string(),
+            task.status.clone(),
+            attempt.to_string(),
+            truncate(&task.title, 60),
+            task.created_at.format("%Y-%m-%d %H:%M").to_string(),
+        ]);
+    }
+    table
+}
@@ -0,0 +1,727 @@
+use std::collections::VecDeque;
+
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use serde_json::Value;
+use tokio::{sync::mpsc::UnboundedSender, task::JoinHandle};
+use uuid::Uuid;
+
+use super::sse::{self, SseEvent};
+use crate::{
+    api::{
+        ApiClient, ApprovalDecision, ExecutionProcess, PendingApproval, PendingQuestion,
+        QuestionAnswer, Task, Workspace,
+    },
+    error::CliError,
+};
+
+/// Board columns as (task status, heading)
+pub const COLUMNS: [(&str, &str); 6] = [
+    ("todo", "To Do"),
+    ("inprogress", "In Progress"),
+    ("blocked", "Blocked"),
+    ("inreview", "In Review"),
+    ("done", "Done"),
+    ("cancelled", "Cancelled"),
+];
+
+/// Log lines kept for the selected attempt
+const LOG_TAIL_LINES: usize = 500;
+
+pub enum AppEvent {
+    Input(Event),
+    /// An event from the project's `/events/stream`
+    Entity(SseEvent),
+    /// An event from the raw log stream of an execution process
+    Log {
+        process_id: Uuid,
+        event: SseEvent,
+    },
+    StreamError(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    Board,
+    Inbox,
+}
+
+/// Something an agent is waiting on.
+pub enum InboxItem<'a> {
+    Question(&'a PendingQuestion),
+    Approval(&'a PendingApproval),
+}
+
+/// The latest attempt of the selected task.
+pub struct Attempt {
+    pub workspace: Workspace,
+    pub processes: Vec<ExecutionProcess>,
+}
+
+impl Attempt {
+    pub fn latest_process(&self) -> Option<&ExecutionProcess> {
+        self.processes.iter().max_by_key(|p| p.started_at)
+    }
+}
+
+/// The most recent output of one execution process.
+#[derive(Default)]
+pub struct LogTail {
+    pub process_id: Option<Uuid>,
+    pub lines: VecDeque<String>,
+    /// Output after the last newline
+    pub partial: String,
+    pub finished: bool,
+}
+
+impl LogTail {
+    fn reset(&mut self, process_id: Option<Uuid>) {
+        *self = LogTail {
+            process_id,
+            ..Default::default()
+        };
+    }
+
+    fn append(&mut self, content: &str) {
+        self.partial.push_str(content);
+        while let Some(end) = self.partial.find('\n') {
+            let line: String = self.partial.drain(..=end).collect();
+            self.lines
+                .push_back(line.trim_end_matches(['\n', '\r']).to_string());
+        }
+        while self.lines.len() > LOG_TAIL_LINES {
+            self.lines.pop_front();
+        }
+    }
+
+    /// The last `height` lines, including an unterminated one.
+    pub fn tail(&self, height: usize) -> Vec<&str> {
+        let partial = (!self.partial.is_empty()).then_some(self.partial.as_str());
+        let lines: Vec<&str> = self
+            .lines
+            .iter()
+            .map(String::as_str)
+            .chain(partial)
+            .collect();
+        lines[lines.len().saturating_sub(height)..].to_vec()
+    }
+}
+
+pub enum Dialog {
+    /// Answering the questions of a request one at a time
+    Question {
+        question: PendingQuestion,
+        index: usize,
+        cursor: usize,
+        selected: Vec<usize>,
+        /// Free-form answer being typed instead of picking options
+        text: Option<String>,
+        answers: Vec<QuestionAnswer>,
+    },
+    DenyReason {
+        approval: PendingApproval,
+        reason: String,
+    },
+}
+
+enum DialogOutcome {
+    Keep(Dialog),
+    Close,
+    Answer(PendingQuestion, Vec<QuestionAnswer>),
+    Deny(PendingApproval, Option<String>),
+}
+
+impl Dialog {
+    /// Open at the question `index`, typing straight away when it has no options.
+    fn question(question: PendingQuestion, index: usize, answers: Vec<QuestionAnswer>) -> Self {
+        let text = question.questions[index]
+            .options
+            .is_empty()
+            .then(String::new);
+        Dialog::Question {
+            question,
+            index,
+            cursor: 0,
+            selected: Vec::new(),
+            text,
+            answers,
+        }
+    }
+
+    fn handle_key(self, key: KeyEvent) -> DialogOutcome {
+        match self {
+            Dialog::Question {
+                question,
+                index,
+                cursor,
+                mut selected,
+                text: Some(mut text),
+                mut answers,
+            } => {
+                match key.code {
+                    KeyCode::Esc => {
+                        return DialogOutcome::Keep(Dialog::Question {
+                            question,
+                            index,
+                            cursor,
+                            selected,
+                            text: None,
+                            answers,
+                        });
+                    }
+                    KeyCode::Enter if !text.trim().is_empty() => {
+                        answers.push(QuestionAnswer {
+                            question_index: index,
+                            selected_options: std::mem::take(&mut selected),
+                            custom_text: Some(text.trim().to_string()),
+                        });
+                        return next_question(question, index, answers);
+                    }
+                    KeyCode::Backspace => {
+                        text.pop();
+                    }
+                    KeyCode::Char(c) => text.push(c),
+                    _ => {}
+                }
+                DialogOutcome::Keep(Dialog::Question {
+                    question,
+                    index,
+                    cursor,
+                    selected,
+                    text: Some(text),
+                    answers,
+                })
+            }
+            Dialog::Question {
+                question,
+                index,
+                mut cursor,
+                mut selected,
+                text: None,
+                mut answers,
+            } => {
+                let current = &question.questions[index];
+                let option_count = current.options.len();
+                match key.code {
+                    KeyCode::Esc => return DialogOutcome::Close,
+                    KeyCode::Up | KeyCode::Char('k') => cursor = cursor.saturating_sub(1),
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        cursor = (cursor + 1).min(option_count.saturating_sub(1))
+                    }
+                    KeyCode::Char(' ') if current.multi_select && option_count > 0 => {
+                        match selected.iter().position(|o| *o == cursor) {
+                            Some(position) => {
+                                selected.remove(position);
+                            }
+                            None => selected.push(cursor),
+                        }
+                    }
+                    KeyCode::Char('t') => {
+                        return DialogOutcome::Keep(Dialog::Question {
+                            question,
+                            index,
+                            cursor,
+                            selected,
+                            text: Some(String::new()),
+                            answers,
+                        });
+                    }
+                    KeyCode::Enter if option_count > 0 => {
+                        if !current.multi_select || selected.is_empty() {
+                            selected = vec![cursor];
+                        }
+                        selected.sort_unstable();
+                        answers.push(QuestionAnswer {
+                            question_index: index,
+                            selected_options: selected,
+                            custom_text: None,
+                        });
+                        return next_question(question, index, answers);
+                    }
+                    _ => {}
+                }
+                DialogOutcome::Keep(Dialog::Question {
+                    question,
+                    index,
+                    cursor,
+                    selected,
+                    text: None,
+                    answers,
+                })
+            }
+            Dialog::DenyReason {
+                approval,
+                mut reason,
+            } => {
+                match key.code {
+                    KeyCode::Esc => return DialogOutcome::Close,
+                    KeyCode::Enter => {
+                        let reason = Some(reason.trim().to_string()).filter(|r| !r.is_empty());
+                        return DialogOutcome::Deny(approval, reason);
+                    }
+                    KeyCode::Backspace => {
+                        reason.pop();
+                    }
+                    KeyCode::Char(c) => reason.push(c),
+                    _ => {}
+                }
+                DialogOutcome::Keep(Dialog::DenyReason { approval, reason })
+            }
+        }
+    }
+}
+
+/// Move on to the next question of a request, or submit once all are answered.
+fn next_question(
+    question: PendingQuestion,
+    index: usize,
+    answers: Vec<QuestionAnswer>,
+) -> DialogOutcome {
+    if index + 1 >= question.questions.len() {
+        return DialogOutcome::Answer(question, answers);
+    }
+    DialogOutcome::Keep(Dialog::question(question, index + 1, answers))
+}
+
+pub struct App {
+    client: ApiClient,
+    events: UnboundedSender<AppEvent>,
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub tasks: Vec<Task>,
+    pub focus: Focus,
+    pub column: usize,
+    pub rows: [usize; COLUMNS.len()],
+    pub attempt: Option<Attempt>,
+    pub log: LogTail,
+    log_stream: Option<JoinHandle<()>>,
+    pub questions: Vec<PendingQuestion>,
+    pub approvals: Vec<PendingApproval>,
+    pub inbox_row: usize,
+    pub dialog: Option<Dialog>,
+    pub status: Option<String>,
+    pub should_quit: bool,
+}
+
+impl App {
+    pub fn new(client: ApiClient, project_id: Uuid, events: UnboundedSender<AppEvent>) -> Self {
+        Self {
+            client,
+            events,
+            project_id,
+            project_name: String::new(),
+            tasks: Vec::new(),
+            focus: Focus::Board,
+            column: 0,
+            rows: [0; COLUMNS.len()],
+            attempt: None,
+            log: LogTail::default(),
+            log_stream: None,
+            questions: Vec::new(),
+            approvals: Vec::new(),
+            inbox_row: 0,
+            dialog: None,
+            status: None,
+            should_quit: false,
+        }
+    }
+
+    /// Tasks in a board column, newest first.
+    pub fn column_tasks(&self, column: usize) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self
+            .tasks
+            .iter()
+            .filter(|t| t.status == COLUMNS[column].0)
+            .collect();
+        tasks.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        tasks
+    }
+
+    pub fn selected_task(&self) -> Option<&Task> {
+        self.column_tasks(self.column)
+            .get(self.rows[self.column])
+            .copied()
+    }
+
+    pub fn inbox(&self) -> Vec<InboxItem<'_>> {
+        self.questions
+            .iter()
+            .map(InboxItem::Question)
+            .chain(self.approvals.iter().map(InboxItem::Approval))
+            .collect()
+    }
+
+    fn report(&mut self, result: Result<(), CliError>) {
+        if let Err(e) = result {
+            self.status = Some(format!("Error: {e}"));
+        }
+    }
+
+    /// Reload everything shown on screen.
+    pub async fn refresh(&mut self) {
+        let result = self.load_project().await;
+        self.report(result);
+        let result = self.load_tasks().await;
+        self.report(result);
+        let result = self.load_inbox().await;
+        self.report(result);
+        let result = self.load_attempt().await;
+        self.report(result);
+    }
+
+    async fn load_project(&mut self) -> Result<(), CliError> {
+        let project = self
+            .client
+            .get(&format!("/projects/{}", self.project_id))
+            .await?;
+        self.project_name = project["name"].as_str().unwrap_or_default().to_string();
+        Ok(())
+    }
+
+    async fn load_tasks(&mut self) -> Result<(), CliError> {
+        self.tasks = self
+            .client
+            .get_as(&format!("/tasks?project_id={}", self.project_id))
+            .await?;
+        self.clamp_rows();
+        Ok(())
+    }
+
+    async fn load_inbox(&mut self) -> Result<(), CliError> {
+        self.questions = self.client.pending_questions().await?;
+        self.approvals = self.client.pending_approvals().await?;
+        self.inbox_row = self
+            .inbox_row
+            .min((self.questions.len() + self.approvals.len()).saturating_sub(1));
+        Ok(())
+    }
+
+    /// Load the latest attempt of the selected task and follow its newest process's logs.
+    async fn load_attempt(&mut self) -> Result<(), CliError> {
+        let Some(task_id) = self.selected_task().map(|t| t.id) else {
+            self.attempt = None;
+            self.follow_logs(None);
+            return Ok(());
+        };
+
+        let workspaces: Vec<Workspace> = self
+            .client
+            .get_as(&format!("/task-attempts?task_id={task_id}"))
+            .await?;
+        let attempt = match workspaces.into_iter().next() {
+            Some(workspace) => {
+                let processes = self
+                    .client
+                    .workspace_processes(workspace.id)
+                    .await?
+                    .into_iter()
+                    .filter(|p| !p.dropped)
+                    .collect();
+                Some(Attempt {
+                    workspace,
+                    processes,
+                })
+            }
+            None => None,
+        };
+
+        let process_id = attempt
+            .as_ref()
+            .and_then(|a| a.latest_process())
+            .map(|p| p.id);
+        self.attempt = attempt;
+        if process_id != self.log.process_id {
+            self.follow_logs(process_id);
+        }
+        Ok(())
+    }
+
+    /// Replace the log tail with a stream of `process_id`'s output.
+    fn follow_logs(&mut self, process_id: Option<Uuid>) {
+        if let Some(handle) = self.log_stream.take() {
+            handle.abort();
+        }
+        self.log.reset(process_id);
+        let Some(process_id) = process_id else {
+            return;
+        };
+
+        let client = self.client.clone();
+        let events = self.events.clone();
+        self.log_stream = Some(tokio::spawn(async move {
+            let path = format!("/execution-processes/{process_id}/raw-logs/stream");
+            let result = sse::read_events(&client, &path, |event| {
+                events.send(AppEvent::Log { process_id, event }).is_ok()
+            })
+            .await;
+            if let Err(e) = result {
+                let _ = events.send(AppEvent::StreamError(format!("Log stream failed: {e}")));
+            }
+        }));
+    }
+
+    fn clamp_rows(&mut self) {
+        for column in 0..COLUMNS.len() {
+            let len = self.column_tasks(column).len();
+            self.rows[column] = self.rows[column].min(len.saturating_sub(1));
+        }
+    }
+
+    pub async fn handle_event(&mut self, event: AppEvent) {
+        match event {
+            AppEvent::Input(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                self.handle_key(key).await
+            }
+            AppEvent::Input(_) => {}
+            AppEvent::Entity(event) => self.handle_entity(event).await,
+            AppEvent::Log { process_id, event } => {
+                if self.log.process_id != Some(process_id) {
+                    return;
+                }
+                match event.event.as_str() {
+                    "stdout" | "stderr" => {
+                        let content: String =
+                            serde_json::from_str(&event.data).unwrap_or(event.data);
+                        self.log.append(&content);
+                    }
+                    "finished" => self.log.finished = true,
+                    "error" => self.status = Some(format!("Log stream error: {}", event.data)),
+                    _ => {}
+                }
+            }
+            AppEvent::StreamError(message) => self.status = Some(message),
+        }
+    }
+
+    async fn handle_entity(&mut self, event: SseEvent) {
+        if event.event == "resync" {
+            self.refresh().await;
+            return;
+        }
+        let Ok(event) = serde_json::from_str::<Value>(&event.data) else {
+            return;
+        };
+
+        let selected = self.selected_task().map(|t| t.id);
+        let result = match event["event"]["type"].as_str() {
+            Some("task_created" | "task_updated") => {
+                match serde_json::from_value::<Task>(event["event"]["task"].clone()) {
+                    Ok(task) if task.project_id == self.project_id => {
+                        match self.tasks.iter_mut().find(|t| t.id == task.id) {
+                            Some(existing) => *existing = task,
+                            None => self.tasks.push(task),
+                        }
+                        self.clamp_rows();
+                    }
+                    _ => {}
+                }
+                Ok(())
+            }
+            Some("task_deleted") => {
+                let task_id = event["event"]["task_id"].as_str();
+                self.tasks
+                    .retain(|t| Some(t.id.to_string().as_str()) != task_id);
+                self.clamp_rows();
+                Ok(())
+            }
+            Some("attempt_status_changed") => {
+                if selected.map(|id| id.to_string()).as_deref()
+                    == event["event"]["task_id"].as_str()
+                {
+                    self.load_attempt().await
+                } else {
+                    Ok(())
+                }
+            }
+            Some("question_pending" | "approval_pending") => {
+                self.status = Some("An agent is waiting for input (Tab to answer)".to_string());
+                self.load_inbox().await
+            }
+            Some("question_answered" | "approval_resolved") => self.load_inbox().await,
+            _ => Ok(()),
+        };
+        self.report(result);
+
+        if self.selected_task().map(|t| t.id) != selected {
+            let result = self.load_attempt().await;
+            self.report(result);
+        }
+    }
+
+    async fn handle_key(&mut self, key: KeyEvent) {
+        if let Some(dialog) = self.dialog.take() {
+            let result = match dialog.handle_key(key) {
+                DialogOutcome::Keep(dialog) => {
+                    self.dialog = Some(dialog);
+                    return;
+                }
+                DialogOutcome::Close => return,
+                DialogOutcome::Answer(question, answers) => self
+                    .client
+                    .answer_question(&question, &answers)
+                    .await
+                    .map(|_| self.status = Some("Answer sent".to_string())),
+                DialogOutcome::Deny(approval, reason) => self
+                    .client
+                    .respond_to_approval(&approval, ApprovalDecision::Denied { reason })
+                    .await
+                    .map(|_| self.status = Some(format!("Denied {}", approval.tool_name))),
+            };
+            self.report(result);
+            let result = self.load_inbox().await;
+            self.report(result);
+            return;
+        }
+
+        let ctrl_c =
+            key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c');
+        match key.code {
+            KeyCode::Char('q') => self.should_quit = true,
+            _ if ctrl_c => self.should_quit = true,
+            KeyCode::Tab => {
+                self.focus = match self.focus {
+                    Focus::Board => Focus::Inbox,
+                    Focus::Inbox => Focus::Board,
+                }
+            }
+            KeyCode::Char('r') => {
+                self.status = None;
+                self.refresh().await;
+            }
+            _ => match self.focus {
+                Focus::Board => self.handle_board_key(key).await,
+                Focus::Inbox => self.handle_inbox_key(key).await,
+            },
+        }
+    }
+
+    async fn handle_board_key(&mut self, key: KeyEvent) {
+        let selected = self.selected_task().map(|t| t.id);
+        match key.code {
+            KeyCode::Left | KeyCode::Char('h') => self.column = self.column.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.column = (self.column + 1).min(COLUMNS.len() - 1)
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.rows[self.column] = self.rows[self.column].saturating_sub(1)
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let len = self.column_tasks(self.column).len();
+                self.rows[self.column] = (self.rows[self.column] + 1).min(len.saturating_sub(1));
+            }
+            _ => return,
+        }
+        if self.selected_task().map(|t| t.id) != selected {
+            let result = self.load_attempt().await;
+            self.report(result);
+        }
+    }
+
+    async fn handle_inbox_key(&mut self, key: KeyEvent) {
+        let len = self.questions.len() + self.approvals.len();
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.inbox_row = self.inbox_row.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.inbox_row = (self.inbox_row + 1).min(len.saturating_sub(1))
+            }
+            KeyCode::Enter => {
+                if let Some(question) = self.questions.get(self.inbox_row)
+                    && !question.questions.is_empty()
+                {
+                    self.dialog = Some(Dialog::question(question.clone(), 0, Vec::new()));
+                }
+            }
+            KeyCode::Char('a') => {
+                let Some(approval) = self.selected_approval().cloned() else {
+                    return;
+                };
+                let result = self
+                    .client
+                    .respond_to_approval(&approval, ApprovalDecision::Approved)
+                    .await
+                    .map(|_| self.status = Some(format!("Approved {}", approval.tool_name)));
+                self.report(result);
+                let result = self.load_inbox().await;
+                self.report(result);
+            }
+            KeyCode::Char('d') => {
+                if let Some(approval) = self.selected_approval().cloned() {
+                    self.dialog = Some(Dialog::DenyReason {
+                        approval,
+                        reason: String::new(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn selected_approval(&self) -> Option<&PendingApproval> {
+        self.inbox_row
+            .checked_sub(self.questions.len())
+            .and_then(|row| self.approvals.get(row))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn pending_question() -> PendingQuestion {
+        serde_json::from_value(serde_json::json!({
+            "id": "q1",
+            "execution_process_id": Uuid::nil(),
+            "questions": [
+                { "question": "Which?", "options": [{ "label": "a" }, { "label": "b" }] },
+                { "question": "Why?", "options": [] },
+            ],
+            "timeout_at": "2026-01-01T00:00:00Z",
+        }))
+        .unwrap()
+    }
+
+    fn press(dialog: Dialog, codes: &[KeyCode]) -> DialogOutcome {
+        let mut outcome = DialogOutcome::Keep(dialog);
+        for code in codes {
+            let DialogOutcome::Keep(dialog) = outcome else {
+                panic!("dialog closed early");
+            };
+            outcome = dialog.handle_key(key(*code));
+        }
+        outcome
+    }
+
+    #[test]
+    fn question_dialog_collects_options_and_text() {
+        let outcome = press(
+            Dialog::question(pending_question(), 0, Vec::new()),
+            &[
+                KeyCode::Down,
+                KeyCode::Enter,
+                KeyCode::Char('o'),
+                KeyCode::Char('k'),
+                KeyCode::Enter,
+            ],
+        );
+        let DialogOutcome::Answer(question, answers) = outcome else {
+            panic!("expected the answers to be submitted");
+        };
+        assert_eq!(question.id, "q1");
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[0].selected_options, vec![1]);
+        assert_eq!(answers[1].question_index, 1);
+        assert_eq!(answers[1].custom_text.as_deref(), Some("ok"));
+    }
+
+    #[test]
+    fn log_tail_keeps_partial_lines() {
+        let mut log = LogTail::default();
+        log.append("one\ntw");
+        log.append("o\r\nthree");
+        assert_eq!(log.tail(2), vec!["two", "three"]);
+        assert_eq!(log.tail(10), vec!["one", "two", "three"]);
+    }
+}
@@ -0,0 +1,83 @@
+mod app;
+mod sse;
+mod ui;
+
+use std::time::Duration;
+
+use app::{App, AppEvent};
+use ratatui::crossterm::event;
+use tokio::sync::mpsc::{self, UnboundedSender};
+use uuid::Uuid;
+
+use crate::{api::ApiClient, error::CliError};
+
+/// Delay before reconnecting a dropped event stream
+const RECONNECT_DELAY: Duration = Duration::from_secs(3);
+
+/// Run the dashboard for a project until the user quits.
+pub async fn run(client: ApiClient, project_id: Uuid) -> Result<(), CliError> {
+    let (events, mut rx) = mpsc::unbounded_channel();
+    spawn_input_reader(events.clone());
+    spawn_entity_stream(client.clone(), project_id, events.clone());
+
+    let mut app = App::new(client, project_id, events);
+    app.refresh().await;
+
+    let mut terminal = ratatui::init();
+    let result = loop {
+        if let Err(e) = terminal.draw(|frame| ui::draw(frame, &app)) {
+            break Err(e.into());
+        }
+        let Some(event) = rx.recv().await else {
+            break Ok(());
+        };
+        app.handle_event(event).await;
+        if app.should_quit {
+            break Ok(());
+        }
+    };
+    ratatui::restore();
+    result
+}
+
+/// Crossterm reads block, so terminal input is read on its own thread.
+fn spawn_input_reader(events: UnboundedSender<AppEvent>) {
+    std::thread::spawn(move || {
+        while let Ok(input) = event::read() {
+            if events.send(AppEvent::Input(input)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Follow the project's entity events, resuming after the last seen event when reconnecting.
+fn spawn_entity_stream(client: ApiClient, project_id: Uuid, events: UnboundedSender<AppEvent>) {
+    tokio::spawn(async move {
+        let mut last_event_id: Option<String> = None;
+        loop {
+            let mut path = format!("/events/stream?project_id={project_id}");
+            if let Some(id) = &last_event_id {
+                path.push_str(&format!("&last_event_id={id}"));
+            }
+
+            let result = sse::read_events(&client, &path, |event| {
+                if event.id.is_some() {
+                    last_event_id = event.id.clone();
+                }
+                events.send(AppEvent::Entity(event)).is_ok()
+            })
+            .await;
+            if events.is_closed() {
+                return;
+            }
+
+            let message = match result {
+                Ok(()) => "Event stream closed".to_string(),
+                Err(e) => format!("Event stream failed: {e}"),
+            };
+            let _ = events.send(AppEvent::StreamError(format!("{message}, reconnecting")));
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    });
+}
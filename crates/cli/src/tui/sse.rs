@@ -0,0 +1,121 @@
+use crate::{api::ApiClient, error::CliError};
+
+/// A single server-sent event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    pub id: Option<String>,
+    /// Event name, `message` when the server did not set one
+    pub event: String,
+    pub data: String,
+}
+
+/// Incremental `text/event-stream` parser fed with response body chunks.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    buffer: Vec<u8>,
+    id: Option<String>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// Consume a chunk and return the events it completed.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                if let Some(event) = self.dispatch() {
+                    events.push(event);
+                }
+                continue;
+            }
+            if line.starts_with(':') {
+                continue;
+            }
+
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "id" => self.id = Some(value.to_string()),
+                "event" => self.event = Some(value.to_string()),
+                "data" => self.data.push(value.to_string()),
+                _ => {}
+            }
+        }
+        events
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = self.event.take();
+        if self.data.is_empty() && event.is_none() {
+            return None;
+        }
+        Some(SseEvent {
+            id: self.id.clone(),
+            event: event.unwrap_or_else(|| "message".to_string()),
+            data: std::mem::take(&mut self.data).join("\n"),
+        })
+    }
+}
+
+/// Read a server-sent events endpoint, calling `on_event` for each event until the stream ends
+/// or `on_event` returns false.
+pub async fn read_events(
+    client: &ApiClient,
+    path: &str,
+    mut on_event: impl FnMut(SseEvent) -> bool,
+) -> Result<(), CliError> {
+    let mut response = client.open_stream(path).await?;
+    let mut parser = SseParser::default();
+    while let Some(chunk) = response.chunk().await? {
+        for event in parser.push(&chunk) {
+            if !on_event(event) {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_events_split_across_chunks() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b": keep-alive\n\nid: 4\nda").is_empty());
+        let events = parser.push(b"ta: {\"a\":1}\n\nevent: resync\r\ndata: line one\ndata:two\n\n");
+
+        assert_eq!(
+            events,
+            vec![
+                SseEvent {
+                    id: Some("4".to_string()),
+                    event: "message".to_string(),
+                    data: "{\"a\":1}".to_string(),
+                },
+                SseEvent {
+                    id: Some("4".to_string()),
+                    event: "resync".to_string(),
+                    data: "line one\ntwo".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn dispatches_named_events_without_data() {
+        let mut parser = SseParser::default();
+        let events = parser.push(b"event: finished\n\n");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "finished");
+        assert_eq!(events[0].data, "");
+    }
+}
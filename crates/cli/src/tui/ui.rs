@@ -0,0 +1,240 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use super::app::{App, COLUMNS, Dialog, Focus, InboxItem};
+use crate::output::truncate;
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [header, board, bottom, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Percentage(45),
+        Constraint::Fill(1),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [attempt, inbox] =
+        Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(bottom);
+
+    frame.render_widget(
+        Line::from(vec![
+            " vibe-kanban ".bold().reversed(),
+            Span::raw(format!(" {}", app.project_name)).bold(),
+        ]),
+        header,
+    );
+    draw_board(frame, app, board);
+    draw_attempt(frame, app, attempt);
+    draw_inbox(frame, app, inbox);
+    draw_footer(frame, app, footer);
+
+    if let Some(dialog) = &app.dialog {
+        draw_dialog(frame, dialog);
+    }
+}
+
+fn focused_block(title: String, focused: bool) -> Block<'static> {
+    let block = Block::bordered().title(title);
+    if focused {
+        block.border_style(Style::new().yellow())
+    } else {
+        block
+    }
+}
+
+fn draw_board(frame: &mut Frame, app: &App, area: Rect) {
+    let areas =
+        Layout::horizontal([Constraint::Ratio(1, COLUMNS.len() as u32); COLUMNS.len()]).split(area);
+
+    for (column, (_, heading)) in COLUMNS.iter().enumerate() {
+        let tasks = app.column_tasks(column);
+        let items: Vec<ListItem> = tasks
+            .iter()
+            .map(|task| {
+                let marker = if task.has_in_progress_attempt {
+                    "● ".green()
+                } else if task.last_attempt_failed {
+                    "✗ ".red()
+                } else {
+                    "  ".into()
+                };
+                ListItem::new(Line::from(vec![marker, Span::raw(task.title.clone())]))
+            })
+            .collect();
+
+        let focused = app.focus == Focus::Board && app.column == column;
+        let list = List::new(items)
+            .block(focused_block(
+                format!(" {heading} ({}) ", tasks.len()),
+                focused,
+            ))
+            .highlight_style(if focused {
+                Style::new().reversed()
+            } else {
+                Style::new().bold()
+            });
+        let mut state = ListState::default()
+            .with_selected((app.column == column && !tasks.is_empty()).then_some(app.rows[column]));
+        frame.render_stateful_widget(list, areas[column], &mut state);
+    }
+}
+
+fn draw_attempt(frame: &mut Frame, app: &App, area: Rect) {
+    let title = match (&app.attempt, app.selected_task()) {
+        (Some(attempt), _) => {
+            let process = attempt
+                .latest_process()
+                .map(|p| format!(" · {} {}", p.run_reason, p.status))
+                .unwrap_or_default();
+            format!(" {}{process} ", attempt.workspace.branch)
+        }
+        (None, Some(_)) => " No attempts yet ".to_string(),
+        (None, None) => " No task selected ".to_string(),
+    };
+
+    let block = Block::bordered().title(title);
+    let height = block.inner(area).height as usize;
+    let lines: Vec<Line> = app
+        .log
+        .tail(height)
+        .into_iter()
+        .map(|line| Line::raw(line.to_string()))
+        .collect();
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_inbox(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .inbox()
+        .into_iter()
+        .map(|item| match item {
+            InboxItem::Question(pending) => {
+                let text = pending
+                    .questions
+                    .first()
+                    .map(|q| q.question.as_str())
+                    .unwrap_or_default();
+                ListItem::new(Line::from(vec![
+                    "? ".yellow().bold(),
+                    Span::raw(text.to_string()),
+                ]))
+            }
+            InboxItem::Approval(approval) => ListItem::new(Line::from(vec![
+                "! ".magenta().bold(),
+                Span::raw(format!(
+                    "{} {}",
+                    approval.tool_name,
+                    truncate(&approval.tool_input.to_string(), 60)
+                )),
+            ])),
+        })
+        .collect();
+
+    let focused = app.focus == Focus::Inbox;
+    let title = format!(" Waiting on you ({}) ", items.len());
+    let list = List::new(items)
+        .block(focused_block(title, focused))
+        .highlight_style(Style::new().reversed());
+    let mut state = ListState::default().with_selected(focused.then_some(app.inbox_row));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let help = match (app.dialog.is_some(), app.focus) {
+        (true, _) => "",
+        (false, Focus::Board) => "←→↑↓ move · Tab inbox · r refresh · q quit",
+        (false, Focus::Inbox) => {
+            "↑↓ move · Enter answer question · a approve · d deny · Tab board · q quit"
+        }
+    };
+    let line = match &app.status {
+        Some(status) => Line::from(vec![
+            Span::raw(status.clone()).yellow(),
+            Span::raw(format!("  {help}")).dim(),
+        ]),
+        None => Line::from(help.dim()),
+    };
+    frame.render_widget(line, area);
+}
+
+/// A rectangle of the given percentages centered in `area`.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Percentage(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Percentage(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
+
+fn draw_dialog(frame: &mut Frame, dialog: &Dialog) {
+    let area = centered(frame.area(), 60, 50);
+    let (title, lines, help) = match dialog {
+        Dialog::Question {
+            question,
+            index,
+            cursor,
+            selected,
+            text,
+            ..
+        } => {
+            let current = &question.questions[*index];
+            let mut lines = vec![
+                Line::from(Span::raw(current.question.clone()).bold()),
+                Line::raw(""),
+            ];
+            for (i, option) in current.options.iter().enumerate() {
+                let mark = match (current.multi_select, selected.contains(&i)) {
+                    (true, true) => "[x] ",
+                    (true, false) => "[ ] ",
+                    (false, _) => "",
+                };
+                let style = if text.is_none() && i == *cursor {
+                    Style::new().reversed()
+                } else {
+                    Style::new()
+                };
+                lines.push(Line::styled(format!("{mark}{}", option.label), style));
+            }
+            let help = match text {
+                Some(text) => {
+                    lines.push(Line::raw(""));
+                    lines.push(Line::raw(format!("> {text}_")));
+                    "Enter send · Esc back to options"
+                }
+                None if current.multi_select => {
+                    "↑↓ move · Space toggle · Enter confirm · t type an answer · Esc cancel"
+                }
+                None => "↑↓ move · Enter choose · t type an answer · Esc cancel",
+            };
+            let title = format!(" Question {}/{} ", index + 1, question.questions.len());
+            (title, lines, help)
+        }
+        Dialog::DenyReason { approval, reason } => (
+            format!(" Deny {} ", approval.tool_name),
+            vec![
+                Line::raw("Reason for the agent (optional):"),
+                Line::raw(""),
+                Line::raw(format!("> {reason}_")),
+            ],
+            "Enter deny · Esc cancel",
+        ),
+    };
+
+    let block = Block::bordered()
+        .title(title)
+        .title_bottom(Line::from(Span::raw(format!(" {help} ")).dim()))
+        .border_style(Style::new().yellow());
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
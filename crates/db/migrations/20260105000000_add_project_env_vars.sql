@@ -0,0 +1,16 @@
+PRAGMA foreign_keys = ON;
+
+CREATE TABLE project_env_vars (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    key         TEXT NOT NULL,
+    -- Secret values are stored encrypted (base64 nonce || ciphertext)
+    value       TEXT NOT NULL,
+    is_secret   BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, key)
+);
+
+CREATE INDEX idx_project_env_vars_project_id ON project_env_vars(project_id);
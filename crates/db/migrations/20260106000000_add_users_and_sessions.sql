@@ -0,0 +1,29 @@
+PRAGMA foreign_keys = ON;
+
+CREATE TABLE users (
+    id            BLOB PRIMARY KEY,
+    username      TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    display_name  TEXT,
+    email         TEXT,
+    -- NULL for accounts that can only sign in through an external identity provider
+    password_hash TEXT,
+    role          TEXT NOT NULL DEFAULT 'member'
+                     CHECK (role IN ('admin', 'member', 'reviewer')),
+    disabled      BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE TABLE user_sessions (
+    id           BLOB PRIMARY KEY,
+    user_id      BLOB NOT NULL,
+    -- SHA-256 of the session token; the token itself is only ever held by the client
+    token_hash   TEXT NOT NULL UNIQUE,
+    expires_at   TEXT NOT NULL,
+    last_seen_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_user_sessions_user_id ON user_sessions(user_id);
+CREATE INDEX idx_user_sessions_expires_at ON user_sessions(expires_at);
@@ -0,0 +1,19 @@
+PRAGMA foreign_keys = ON;
+
+CREATE TABLE notifications (
+    id         BLOB PRIMARY KEY,
+    kind       TEXT NOT NULL
+                  CHECK (kind IN ('execution_completed', 'execution_failed', 'question_pending', 'approval_pending')),
+    title      TEXT NOT NULL,
+    message    TEXT NOT NULL,
+    project_id BLOB,
+    task_id    BLOB,
+    -- NULL until the notification is marked as read
+    read_at    TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_notifications_created_at ON notifications(created_at);
+CREATE INDEX idx_notifications_unread ON notifications(read_at) WHERE read_at IS NULL;
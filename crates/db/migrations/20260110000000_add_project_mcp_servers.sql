@@ -0,0 +1,18 @@
+PRAGMA foreign_keys = ON;
+
+CREATE TABLE project_mcp_servers (
+    id            BLOB PRIMARY KEY,
+    project_id    BLOB NOT NULL,
+    name          TEXT NOT NULL,
+    -- JSON McpServerTransport: stdio command/args or http url/headers
+    transport     TEXT NOT NULL,
+    -- JSON object; values may reference project env vars as ${NAME}
+    env           TEXT NOT NULL DEFAULT '{}',
+    auto_approve  BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, name)
+);
+
+CREATE INDEX idx_project_mcp_servers_project_id ON project_mcp_servers(project_id);
@@ -0,0 +1,10 @@
+PRAGMA foreign_keys = ON;
+
+-- Raw prompt of a task whose title and description were generated by a model.
+-- The executor keeps receiving this text rather than the generated summary.
+CREATE TABLE task_prompts (
+    task_id          BLOB PRIMARY KEY,
+    original_prompt  TEXT NOT NULL,
+    created_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
@@ -0,0 +1,10 @@
+PRAGMA foreign_keys = ON;
+
+-- Generated "what changed and why" summary of an attempt, refreshed each time it finishes
+CREATE TABLE workspace_summaries (
+    workspace_id  BLOB PRIMARY KEY,
+    summary       TEXT NOT NULL,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
@@ -0,0 +1,34 @@
+PRAGMA foreign_keys = ON;
+
+-- Review of an attempt's diff by a second coding agent, run in its own session
+CREATE TABLE reviews (
+    id            BLOB PRIMARY KEY,
+    workspace_id  BLOB NOT NULL,
+    session_id    BLOB NOT NULL UNIQUE,
+    status        TEXT NOT NULL DEFAULT 'running'
+                     CHECK (status IN ('running', 'completed', 'failed')),
+    executor      TEXT NOT NULL,
+    -- Why the review failed, e.g. the reviewer's reply could not be parsed
+    error         TEXT,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    completed_at  TEXT,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_reviews_workspace_id ON reviews(workspace_id);
+
+CREATE TABLE review_findings (
+    id          BLOB PRIMARY KEY,
+    review_id   BLOB NOT NULL,
+    file_path   TEXT,
+    line        INTEGER,
+    severity    TEXT NOT NULL
+                   CHECK (severity IN ('blocking', 'warning', 'suggestion')),
+    comment     TEXT NOT NULL,
+    resolved    BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (review_id) REFERENCES reviews(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_review_findings_review_id ON review_findings(review_id);
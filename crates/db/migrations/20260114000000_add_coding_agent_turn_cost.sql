@@ -0,0 +1,2 @@
+-- Cost the coding agent reported for the turn, in US dollars; NULL when the agent reports none
+ALTER TABLE coding_agent_turns ADD COLUMN cost_usd REAL;
@@ -0,0 +1,44 @@
+PRAGMA foreign_keys = ON;
+
+-- Every column a task has entered, for project statistics
+CREATE TABLE task_status_changes (
+    id          BLOB PRIMARY KEY,
+    task_id     BLOB NOT NULL,
+    status      TEXT NOT NULL,
+    changed_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_status_changes_task_id ON task_status_changes(task_id, changed_at);
+
+-- Existing tasks only have their current column, entered when they were last updated
+INSERT INTO task_status_changes (id, task_id, status, changed_at)
+SELECT randomblob(16), id, status, updated_at FROM tasks;
+
+CREATE TRIGGER task_status_changes_on_insert
+AFTER INSERT ON tasks
+BEGIN
+    INSERT INTO task_status_changes (id, task_id, status)
+    VALUES (randomblob(16), NEW.id, NEW.status);
+END;
+
+CREATE TRIGGER task_status_changes_on_update
+AFTER UPDATE OF status ON tasks
+WHEN OLD.status IS NOT NEW.status
+BEGIN
+    INSERT INTO task_status_changes (id, task_id, status)
+    VALUES (randomblob(16), NEW.id, NEW.status);
+END;
+
+-- Answered agent questions and approval requests, for response latency
+CREATE TABLE agent_request_responses (
+    id                    BLOB PRIMARY KEY,
+    kind                  TEXT NOT NULL CHECK (kind IN ('question', 'approval')),
+    execution_process_id  BLOB NOT NULL,
+    requested_at          TEXT NOT NULL,
+    responded_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_agent_request_responses_execution_process_id
+    ON agent_request_responses(execution_process_id);
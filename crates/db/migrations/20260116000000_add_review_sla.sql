@@ -0,0 +1,36 @@
+PRAGMA foreign_keys = ON;
+
+-- Allow review SLA reminders in the notification center
+CREATE TABLE notifications_new (
+    id         BLOB PRIMARY KEY,
+    kind       TEXT NOT NULL
+                  CHECK (kind IN ('execution_completed', 'execution_failed', 'question_pending', 'approval_pending', 'review_sla_breached')),
+    title      TEXT NOT NULL,
+    message    TEXT NOT NULL,
+    project_id BLOB,
+    task_id    BLOB,
+    -- NULL until the notification is marked as read
+    read_at    TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO notifications_new (id, kind, title, message, project_id, task_id, read_at, created_at)
+SELECT id, kind, title, message, project_id, task_id, read_at, created_at FROM notifications;
+
+DROP TABLE notifications;
+ALTER TABLE notifications_new RENAME TO notifications;
+
+CREATE INDEX idx_notifications_created_at ON notifications(created_at);
+CREATE INDEX idx_notifications_unread ON notifications(read_at) WHERE read_at IS NULL;
+
+-- Reminder levels already sent for a task's current stay in review
+CREATE TABLE review_sla_reminders (
+    task_id          BLOB NOT NULL,
+    in_review_since  TEXT NOT NULL,
+    level            INTEGER NOT NULL,
+    sent_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_id, in_review_since, level),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
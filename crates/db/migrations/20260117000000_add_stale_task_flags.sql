@@ -0,0 +1,35 @@
+PRAGMA foreign_keys = ON;
+
+-- Allow stale task warnings in the notification center
+CREATE TABLE notifications_new (
+    id         BLOB PRIMARY KEY,
+    kind       TEXT NOT NULL
+                  CHECK (kind IN ('execution_completed', 'execution_failed', 'question_pending', 'approval_pending', 'review_sla_breached', 'task_stale')),
+    title      TEXT NOT NULL,
+    message    TEXT NOT NULL,
+    project_id BLOB,
+    task_id    BLOB,
+    -- NULL until the notification is marked as read
+    read_at    TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO notifications_new (id, kind, title, message, project_id, task_id, read_at, created_at)
+SELECT id, kind, title, message, project_id, task_id, read_at, created_at FROM notifications;
+
+DROP TABLE notifications;
+ALTER TABLE notifications_new RENAME TO notifications;
+
+CREATE INDEX idx_notifications_created_at ON notifications(created_at);
+CREATE INDEX idx_notifications_unread ON notifications(read_at) WHERE read_at IS NULL;
+
+-- Tasks flagged by the stale task policy; removed again once the task sees activity
+CREATE TABLE stale_task_flags (
+    task_id           BLOB PRIMARY KEY,
+    -- Last activity seen when the task was flagged
+    last_activity_at  TEXT NOT NULL,
+    flagged_at        TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
@@ -0,0 +1,19 @@
+PRAGMA foreign_keys = ON;
+
+CREATE TABLE redis_task_sources (
+    id             BLOB PRIMARY KEY,
+    project_id     BLOB NOT NULL,
+    name           TEXT NOT NULL,
+    -- Redis key holding a JSON array of task objects
+    redis_key      TEXT NOT NULL,
+    -- JSON TaskSourceFieldMapping: dot-separated paths of each task field in an item
+    field_mapping  TEXT NOT NULL,
+    -- JSON object mapping the source's status values to task statuses
+    status_mapping TEXT NOT NULL DEFAULT '{}',
+    created_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, name)
+);
+
+CREATE INDEX idx_redis_task_sources_project_id ON redis_task_sources(project_id);
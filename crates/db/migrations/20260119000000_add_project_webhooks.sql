@@ -0,0 +1,13 @@
+PRAGMA foreign_keys = ON;
+
+-- Incoming webhook that lets external systems create tasks in a project
+CREATE TABLE project_webhooks (
+    project_id   BLOB PRIMARY KEY,
+    -- Public token identifying the project in the webhook URL
+    token        TEXT NOT NULL UNIQUE,
+    -- SHA-256 of the secret callers must present
+    secret_hash  TEXT NOT NULL,
+    last_used_at TEXT,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
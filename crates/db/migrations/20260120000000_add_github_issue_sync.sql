@@ -0,0 +1,30 @@
+PRAGMA foreign_keys = ON;
+
+-- GitHub repository whose issues become tasks of a project
+CREATE TABLE github_issue_syncs (
+    project_id     BLOB PRIMARY KEY,
+    -- owner/name of the repository
+    repo_full_name TEXT NOT NULL,
+    -- Only issues carrying this label become tasks; NULL accepts every issue
+    label_filter   TEXT,
+    -- Webhook secret, encrypted with the local secret key
+    webhook_secret TEXT NOT NULL,
+    created_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+-- Task created from a GitHub issue
+CREATE TABLE github_issue_links (
+    task_id        BLOB PRIMARY KEY,
+    project_id     BLOB NOT NULL,
+    repo_full_name TEXT NOT NULL,
+    issue_number   INTEGER NOT NULL,
+    issue_url      TEXT NOT NULL,
+    -- Issue state last received from or pushed to GitHub
+    issue_open     BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, repo_full_name, issue_number)
+);
@@ -0,0 +1,29 @@
+PRAGMA foreign_keys = ON;
+
+-- Error tracker (Sentry or generic) whose issues become tasks of a project
+CREATE TABLE error_tracker_integrations (
+    project_id         BLOB PRIMARY KEY,
+    -- Webhook secret, encrypted with the local secret key
+    webhook_secret     TEXT NOT NULL,
+    -- Start a "diagnose and fix" attempt for every new issue
+    auto_start         BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Spend of auto-started attempts over the last 24 hours above which no more are started
+    daily_cost_cap_usd REAL,
+    created_at         TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at         TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+-- Task created for an error tracker issue, keyed by the issue fingerprint
+CREATE TABLE error_tracker_issues (
+    project_id   BLOB NOT NULL,
+    fingerprint  TEXT NOT NULL,
+    task_id      BLOB NOT NULL,
+    event_count  INTEGER NOT NULL DEFAULT 1,
+    auto_started BOOLEAN NOT NULL DEFAULT FALSE,
+    last_seen_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (project_id, fingerprint),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
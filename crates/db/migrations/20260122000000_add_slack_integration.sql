@@ -0,0 +1,26 @@
+PRAGMA foreign_keys = ON;
+
+-- Slack app whose `/vibe` command creates tasks in a project
+CREATE TABLE slack_integrations (
+    project_id     BLOB PRIMARY KEY,
+    -- Signing secret and bot token of the app, encrypted with the local secret key
+    signing_secret TEXT NOT NULL,
+    bot_token      TEXT NOT NULL,
+    -- Address of this instance used in task links posted to Slack
+    app_url        TEXT NOT NULL,
+    created_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+-- Slack message announcing a task; replies in its thread become task comments
+CREATE TABLE slack_task_threads (
+    task_id    BLOB PRIMARY KEY,
+    project_id BLOB NOT NULL,
+    channel_id TEXT NOT NULL,
+    message_ts TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (channel_id, message_ts)
+);
@@ -0,0 +1,18 @@
+PRAGMA foreign_keys = ON;
+
+-- Expiring read-only link to a task, handed to people without an account
+CREATE TABLE public_share_links (
+    id           BLOB PRIMARY KEY,
+    task_id      BLOB NOT NULL,
+    -- Attempt whose summary and diff are shown; NULL shows the task only
+    workspace_id BLOB,
+    -- Files of the attempt's diff chosen for the link, and their diff when it was created
+    diff_paths   TEXT NOT NULL DEFAULT '[]',
+    diff         TEXT,
+    expires_at   TEXT NOT NULL,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_public_share_links_task_id ON public_share_links(task_id);
@@ -0,0 +1,36 @@
+PRAGMA foreign_keys = ON;
+
+-- Group of users sharing a set of projects on one instance
+CREATE TABLE teams (
+    id                    BLOB PRIMARY KEY,
+    name                  TEXT NOT NULL UNIQUE,
+    -- Executor the team's attempts default to; NULL uses the instance default
+    executor_profile      TEXT,
+    -- JSON TeamNotificationDefaults
+    notification_defaults TEXT NOT NULL DEFAULT '{}',
+    created_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE TABLE team_members (
+    team_id    BLOB NOT NULL,
+    user_id    BLOB NOT NULL,
+    role       TEXT NOT NULL DEFAULT 'member' CHECK (role IN ('owner', 'member')),
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (team_id, user_id),
+    FOREIGN KEY (team_id) REFERENCES teams(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_team_members_user_id ON team_members(user_id);
+
+-- A project belongs to at most one team
+CREATE TABLE team_projects (
+    project_id BLOB PRIMARY KEY,
+    team_id    BLOB NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (team_id) REFERENCES teams(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_team_projects_team_id ON team_projects(team_id);
@@ -0,0 +1,12 @@
+PRAGMA foreign_keys = ON;
+
+-- Reusable project configuration captured from an existing project
+CREATE TABLE project_templates (
+    id          BLOB PRIMARY KEY,
+    name        TEXT NOT NULL UNIQUE,
+    description TEXT,
+    -- JSON ProjectTemplateConfig; env vars keep names only, never values
+    config      TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
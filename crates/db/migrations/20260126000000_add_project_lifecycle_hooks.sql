@@ -0,0 +1,17 @@
+PRAGMA foreign_keys = ON;
+
+-- Shell commands run in an attempt's worktree at fixed points of its lifecycle
+CREATE TABLE project_lifecycle_hooks (
+    project_id BLOB NOT NULL,
+    hook       TEXT NOT NULL CHECK (hook IN (
+                   'worktree_created', 'attempt_started', 'attempt_finished',
+                   'before_merge', 'after_merge'
+               )),
+    command    TEXT NOT NULL,
+    -- A failing blocking hook stops the attempt or merge; advisory failures are only reported
+    blocking   INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (project_id, hook),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
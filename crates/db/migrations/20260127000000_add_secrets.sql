@@ -0,0 +1,18 @@
+PRAGMA foreign_keys = ON;
+
+-- Encrypted provider credentials injected into executor environments. Rows without a
+-- project apply everywhere; a project row with the same name overrides the global one.
+CREATE TABLE secrets (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB,
+    name        TEXT NOT NULL,
+    -- Encrypted value (base64 nonce || ciphertext)
+    value       TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_secrets_global_name ON secrets(name) WHERE project_id IS NULL;
+CREATE UNIQUE INDEX idx_secrets_project_name ON secrets(project_id, name)
+    WHERE project_id IS NOT NULL;
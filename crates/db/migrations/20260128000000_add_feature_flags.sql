@@ -0,0 +1,16 @@
+PRAGMA foreign_keys = ON;
+
+-- Overrides of built-in feature flag defaults. Rows without a project apply to the whole
+-- instance; a project row takes precedence over the instance row for that project.
+CREATE TABLE feature_flags (
+    flag        TEXT NOT NULL,
+    project_id  BLOB,
+    enabled     INTEGER NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_feature_flags_instance ON feature_flags(flag) WHERE project_id IS NULL;
+CREATE UNIQUE INDEX idx_feature_flags_project ON feature_flags(flag, project_id)
+    WHERE project_id IS NOT NULL;
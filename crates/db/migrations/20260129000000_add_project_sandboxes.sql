@@ -0,0 +1,15 @@
+PRAGMA foreign_keys = ON;
+
+-- Docker container an attempt's executor and scripts run in, one per project
+CREATE TABLE project_sandboxes (
+    project_id BLOB PRIMARY KEY NOT NULL,
+    enabled    INTEGER NOT NULL DEFAULT 1,
+    image      TEXT NOT NULL,
+    network    TEXT NOT NULL DEFAULT 'bridge' CHECK (network IN ('none', 'bridge', 'host')),
+    cpus       REAL,
+    memory_mb  INTEGER,
+    pids_limit INTEGER,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,13 @@
+PRAGMA foreign_keys = ON;
+
+-- Resource limits an execution process ran with, and the one it was killed for exceeding
+CREATE TABLE execution_process_limits (
+    execution_process_id BLOB PRIMARY KEY NOT NULL,
+    memory_mb            INTEGER,
+    cpu_seconds          INTEGER,
+    wall_clock_secs      INTEGER,
+    exceeded             TEXT CHECK (exceeded IN ('memory', 'cpu', 'wall_clock')),
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
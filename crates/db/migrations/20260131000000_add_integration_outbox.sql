@@ -0,0 +1,17 @@
+PRAGMA foreign_keys = ON;
+
+-- Integration actions held back while their service was unreachable, retried until delivered
+CREATE TABLE integration_outbox (
+    id              BLOB PRIMARY KEY NOT NULL,
+    integration     TEXT NOT NULL CHECK (integration IN ('github', 'slack')),
+    -- At most one pending entry per key; a newer action for the same key replaces it
+    dedupe_key      TEXT NOT NULL UNIQUE,
+    payload         TEXT NOT NULL,
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    last_error      TEXT,
+    next_attempt_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE INDEX idx_integration_outbox_next_attempt_at ON integration_outbox(next_attempt_at);
@@ -0,0 +1,11 @@
+PRAGMA foreign_keys = ON;
+
+-- Where a project's unanswered agent questions are escalated to, and after how long
+CREATE TABLE project_question_escalations (
+    project_id BLOB PRIMARY KEY,
+    -- JSON array of steps ordered by `after_minutes`, each naming one channel
+    steps      TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,21 @@
+PRAGMA foreign_keys = ON;
+
+-- Canned answers for questions agents keep asking in a project
+CREATE TABLE question_auto_answer_rules (
+    id               BLOB PRIMARY KEY,
+    project_id       BLOB NOT NULL,
+    name             TEXT NOT NULL,
+    -- Regex matched against the question text
+    question_pattern TEXT NOT NULL,
+    -- Optional regex matched against the question header
+    header_pattern   TEXT,
+    -- Label of the option selected when the rule matches
+    option_label     TEXT NOT NULL,
+    enabled          BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_question_auto_answer_rules_project_id
+    ON question_auto_answer_rules(project_id);
@@ -0,0 +1,18 @@
+PRAGMA foreign_keys = ON;
+
+-- Outgoing webhooks called when an agent waits on, or stops waiting on, a reviewer
+CREATE TABLE project_event_webhooks (
+    id         BLOB PRIMARY KEY,
+    project_id BLOB NOT NULL,
+    url        TEXT NOT NULL,
+    -- Encrypted HMAC secret used to sign deliveries, if any
+    secret     TEXT,
+    -- JSON array of the event kinds delivered to the URL
+    events     TEXT NOT NULL DEFAULT '[]',
+    enabled    BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_project_event_webhooks_project_id ON project_event_webhooks(project_id);
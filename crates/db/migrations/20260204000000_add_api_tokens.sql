@@ -0,0 +1,15 @@
+PRAGMA foreign_keys = ON;
+
+-- Tokens for bots that list and answer questions and approvals of specific projects.
+-- Only the SHA-256 of the token is stored.
+CREATE TABLE api_tokens (
+    id           BLOB PRIMARY KEY,
+    name         TEXT NOT NULL,
+    token_hash   TEXT NOT NULL UNIQUE,
+    -- JSON array of the project ids the token may act on
+    project_ids  TEXT NOT NULL DEFAULT '[]',
+    created_by   BLOB,
+    last_used_at TEXT,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE SET NULL
+);
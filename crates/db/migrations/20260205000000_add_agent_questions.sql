@@ -0,0 +1,17 @@
+PRAGMA foreign_keys = ON;
+
+-- Every question request an agent made and how it ended, for question statistics
+CREATE TABLE agent_questions (
+    id                    TEXT PRIMARY KEY,
+    execution_process_id  BLOB NOT NULL,
+    -- Number of questions asked together in the request
+    question_count        INTEGER NOT NULL,
+    outcome               TEXT NOT NULL DEFAULT 'pending'
+                              CHECK (outcome IN ('pending', 'answered', 'timed_out', 'auto_answered')),
+    requested_at          TEXT NOT NULL,
+    resolved_at           TEXT,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_agent_questions_requested_at ON agent_questions(requested_at);
+CREATE INDEX idx_agent_questions_execution_process_id ON agent_questions(execution_process_id);
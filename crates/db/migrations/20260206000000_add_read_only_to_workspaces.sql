@@ -0,0 +1,2 @@
+-- Investigate-only attempts: the agent may read the codebase but not change it
+ALTER TABLE workspaces ADD COLUMN read_only INTEGER NOT NULL DEFAULT 0;
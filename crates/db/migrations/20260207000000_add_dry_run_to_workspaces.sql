@@ -0,0 +1,2 @@
+-- Dry-run attempts: tool calls that may change the codebase are logged instead of run
+ALTER TABLE workspaces ADD COLUMN dry_run INTEGER NOT NULL DEFAULT 0;
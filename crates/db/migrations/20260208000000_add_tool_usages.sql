@@ -0,0 +1,17 @@
+PRAGMA foreign_keys = ON;
+
+-- Every tool call a coding agent made and how it ended, for per-project tool statistics
+CREATE TABLE tool_usages (
+    id                    BLOB PRIMARY KEY,
+    execution_process_id  BLOB NOT NULL,
+    tool_name             TEXT NOT NULL,
+    status                TEXT NOT NULL
+                              CHECK (status IN ('succeeded', 'failed', 'denied', 'timed_out', 'unfinished')),
+    -- How long the call waited for a reviewer's decision, when it needed approval
+    approval_wait_ms      INTEGER,
+    created_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_tool_usages_created_at ON tool_usages(created_at);
+CREATE INDEX idx_tool_usages_execution_process_id ON tool_usages(execution_process_id);
@@ -0,0 +1,19 @@
+PRAGMA foreign_keys = ON;
+
+-- Shell commands agents may run without asking, or may never run, in a project
+CREATE TABLE bash_command_rules (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    -- Glob or regex matched against the command
+    pattern     TEXT NOT NULL,
+    match_kind  TEXT NOT NULL DEFAULT 'glob'
+                    CHECK (match_kind IN ('glob', 'regex')),
+    action      TEXT NOT NULL
+                    CHECK (action IN ('allow', 'deny')),
+    enabled     BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_bash_command_rules_project_id ON bash_command_rules(project_id);
@@ -0,0 +1,17 @@
+PRAGMA foreign_keys = ON;
+
+-- Files agents may edit without asking, need approval for, or may never edit, in a project
+CREATE TABLE file_path_rules (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    -- Glob matched against the path relative to the repository root
+    pattern     TEXT NOT NULL,
+    action      TEXT NOT NULL
+                    CHECK (action IN ('allow', 'ask', 'deny')),
+    enabled     BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_file_path_rules_project_id ON file_path_rules(project_id);
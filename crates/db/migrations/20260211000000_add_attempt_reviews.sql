@@ -0,0 +1,33 @@
+PRAGMA foreign_keys = ON;
+
+-- A reviewer's comments on lines of an attempt's diff
+CREATE TABLE attempt_review_comments (
+    id            BLOB PRIMARY KEY,
+    workspace_id  BLOB NOT NULL,
+    -- Path as shown in the attempt diff
+    file_path     TEXT NOT NULL,
+    -- Line in the new version of the file
+    line          INTEGER NOT NULL,
+    body          TEXT NOT NULL,
+    resolved      BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Set once the comment has been sent to the agent as feedback
+    sent_at       TEXT,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_attempt_review_comments_workspace_id ON attempt_review_comments(workspace_id);
+
+-- A reviewer's overall verdict on an attempt; the latest one counts
+CREATE TABLE attempt_reviews (
+    id            BLOB PRIMARY KEY,
+    workspace_id  BLOB NOT NULL,
+    verdict       TEXT NOT NULL
+                      CHECK (verdict IN ('approved', 'changes_requested')),
+    summary       TEXT,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_attempt_reviews_workspace_id ON attempt_reviews(workspace_id);
@@ -0,0 +1,26 @@
+PRAGMA foreign_keys = ON;
+
+-- Items reviewers tick off on every attempt of a project's tasks
+CREATE TABLE review_checklist_items (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    label       TEXT NOT NULL,
+    -- Tasks can't be moved to Done until the item is checked on their latest attempt
+    required    BOOLEAN NOT NULL DEFAULT TRUE,
+    position    INTEGER NOT NULL DEFAULT 0,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_review_checklist_items_project_id ON review_checklist_items(project_id);
+
+-- Checklist items ticked off on an attempt
+CREATE TABLE attempt_checklist_checks (
+    workspace_id  BLOB NOT NULL,
+    item_id       BLOB NOT NULL,
+    checked_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (workspace_id, item_id),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (item_id) REFERENCES review_checklist_items(id) ON DELETE CASCADE
+);
@@ -0,0 +1,11 @@
+PRAGMA foreign_keys = ON;
+
+-- Why a failed execution process failed, from the end of what it wrote to stderr
+CREATE TABLE execution_process_failures (
+    execution_process_id BLOB PRIMARY KEY NOT NULL,
+    kind                 TEXT NOT NULL CHECK (kind IN ('auth_failure', 'cli_missing', 'version_mismatch', 'unknown')),
+    -- Last lines the process wrote to stderr, oldest first
+    stderr_tail          TEXT NOT NULL,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
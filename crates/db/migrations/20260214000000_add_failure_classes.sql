@@ -0,0 +1,18 @@
+PRAGMA foreign_keys = ON;
+
+-- Classify rate limits, out-of-memory kills, test failures, merge conflicts and CLI crashes
+CREATE TABLE execution_process_failures_new (
+    execution_process_id BLOB PRIMARY KEY NOT NULL,
+    kind                 TEXT NOT NULL
+                            CHECK (kind IN ('rate_limited', 'auth_failure', 'out_of_memory', 'test_failure', 'merge_conflict', 'cli_missing', 'version_mismatch', 'cli_crash', 'unknown')),
+    -- Last lines the process wrote to stderr, oldest first
+    stderr_tail          TEXT NOT NULL,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+INSERT INTO execution_process_failures_new (execution_process_id, kind, stderr_tail, created_at)
+SELECT execution_process_id, kind, stderr_tail, created_at FROM execution_process_failures;
+
+DROP TABLE execution_process_failures;
+ALTER TABLE execution_process_failures_new RENAME TO execution_process_failures;
@@ -0,0 +1,16 @@
+PRAGMA foreign_keys = ON;
+
+-- Latest liveness sample of a running execution process, written periodically by the instance
+-- that spawned it
+CREATE TABLE execution_process_heartbeats (
+    execution_process_id BLOB PRIMARY KEY NOT NULL,
+    pid                  INTEGER,
+    -- Single-letter process state from the OS, e.g. R, S or Z
+    state                TEXT,
+    -- Resident memory of the whole process group
+    rss_kb               INTEGER,
+    -- CPU time used by the process group so far
+    cpu_seconds          REAL,
+    last_seen_at         TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
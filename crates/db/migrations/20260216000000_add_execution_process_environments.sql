@@ -0,0 +1,19 @@
+PRAGMA foreign_keys = ON;
+
+-- What an execution process ran with, so results can be reproduced and regressions traced to
+-- tool version changes
+CREATE TABLE execution_process_environments (
+    execution_process_id BLOB PRIMARY KEY NOT NULL,
+    -- Coding agent, CLI and model; NULL for scripts
+    executor             TEXT,
+    cli_command          TEXT,
+    cli_version          TEXT,
+    model                TEXT,
+    os                   TEXT NOT NULL,
+    arch                 TEXT NOT NULL,
+    app_version          TEXT NOT NULL,
+    -- JSON array of per-repo commits and lockfile hashes
+    repos                TEXT NOT NULL DEFAULT '[]',
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
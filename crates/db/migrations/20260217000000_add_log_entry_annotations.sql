@@ -0,0 +1,16 @@
+PRAGMA foreign_keys = ON;
+
+-- Bookmarks on entries of an execution process's normalized log, optionally with a note
+CREATE TABLE log_entry_annotations (
+    id                   BLOB PRIMARY KEY,
+    execution_process_id BLOB NOT NULL,
+    -- Index of the entry in the normalized log
+    entry_index          INTEGER NOT NULL,
+    note                 TEXT,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_log_entry_annotations_execution_process_id
+    ON log_entry_annotations(execution_process_id);
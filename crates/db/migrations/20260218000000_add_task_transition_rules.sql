@@ -0,0 +1,18 @@
+PRAGMA foreign_keys = ON;
+
+-- Conditions a task has to meet before it may move between statuses in a project
+CREATE TABLE task_transition_rules (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    -- NULL applies the rule to moves from any status
+    from_status TEXT CHECK (from_status IN ('todo','inprogress','inreview','done','cancelled')),
+    to_status   TEXT NOT NULL
+                CHECK (to_status IN ('todo','inprogress','inreview','done','cancelled')),
+    requirement TEXT NOT NULL
+                CHECK (requirement IN ('has_attempt','verification_passed','no_pending_questions')),
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_transition_rules_project_id
+    ON task_transition_rules(project_id);
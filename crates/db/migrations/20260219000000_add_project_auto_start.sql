@@ -0,0 +1,14 @@
+PRAGMA foreign_keys = ON;
+
+-- Start an attempt automatically when a task of the project enters a column
+CREATE TABLE project_auto_start (
+    project_id              BLOB PRIMARY KEY,
+    trigger_status          TEXT NOT NULL
+                            CHECK (trigger_status IN ('todo','inprogress','inreview','done','cancelled')),
+    -- Attempts of the project allowed to run at once before auto-starts are skipped
+    max_concurrent_attempts INTEGER NOT NULL DEFAULT 1 CHECK (max_concurrent_attempts >= 1),
+    enabled                 BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at              TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at              TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,67 @@
+PRAGMA foreign_keys = ON;
+
+-- Free-form labels on tasks
+CREATE TABLE task_labels (
+    task_id    BLOB NOT NULL,
+    label      TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_id, label),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+-- Board automations: when the trigger happens to a task of the project, run the actions
+CREATE TABLE automation_rules (
+    id           BLOB PRIMARY KEY,
+    project_id   BLOB NOT NULL,
+    name         TEXT NOT NULL,
+    -- JSON AutomationTrigger
+    rule_trigger TEXT NOT NULL,
+    -- JSON array of AutomationAction, run in order
+    actions      TEXT NOT NULL,
+    enabled      BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_automation_rules_project_id ON automation_rules(project_id);
+
+-- Audit of every time a rule fired and what its actions did
+CREATE TABLE automation_rule_firings (
+    id       BLOB PRIMARY KEY,
+    rule_id  BLOB NOT NULL,
+    task_id  BLOB NOT NULL,
+    -- JSON array of AutomationActionResult
+    results  TEXT NOT NULL,
+    fired_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (rule_id) REFERENCES automation_rules(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_automation_rule_firings_rule_id
+    ON automation_rule_firings(rule_id, fired_at);
+
+-- Allow automation messages in the notification center
+CREATE TABLE notifications_new (
+    id         BLOB PRIMARY KEY,
+    kind       TEXT NOT NULL
+                  CHECK (kind IN ('execution_completed', 'execution_failed', 'question_pending', 'approval_pending', 'review_sla_breached', 'task_stale', 'automation')),
+    title      TEXT NOT NULL,
+    message    TEXT NOT NULL,
+    project_id BLOB,
+    task_id    BLOB,
+    -- NULL until the notification is marked as read
+    read_at    TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO notifications_new (id, kind, title, message, project_id, task_id, read_at, created_at)
+SELECT id, kind, title, message, project_id, task_id, read_at, created_at FROM notifications;
+
+DROP TABLE notifications;
+ALTER TABLE notifications_new RENAME TO notifications;
+
+CREATE INDEX idx_notifications_created_at ON notifications(created_at);
+CREATE INDEX idx_notifications_unread ON notifications(read_at) WHERE read_at IS NULL;
@@ -0,0 +1,18 @@
+PRAGMA foreign_keys = ON;
+
+-- Time people spent on a task, logged by hand. Agent time is derived from execution processes.
+CREATE TABLE task_time_entries (
+    id         BLOB PRIMARY KEY,
+    task_id    BLOB NOT NULL,
+    -- NULL in single-user mode
+    user_id    BLOB,
+    seconds    INTEGER NOT NULL CHECK (seconds > 0),
+    note       TEXT,
+    -- When the work was done, used to bucket entries per week
+    worked_at  TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_task_time_entries_task_id ON task_time_entries(task_id);
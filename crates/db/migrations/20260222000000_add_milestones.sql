@@ -0,0 +1,27 @@
+PRAGMA foreign_keys = ON;
+
+-- Releases or other goals grouping tasks of a project
+CREATE TABLE milestones (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    title       TEXT NOT NULL,
+    description TEXT,
+    -- YYYY-MM-DD
+    target_date TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_milestones_project_id ON milestones(project_id);
+
+-- A task belongs to at most one milestone
+CREATE TABLE milestone_tasks (
+    task_id      BLOB PRIMARY KEY,
+    milestone_id BLOB NOT NULL,
+    added_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (milestone_id) REFERENCES milestones(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_milestone_tasks_milestone_id ON milestone_tasks(milestone_id);
@@ -0,0 +1,12 @@
+PRAGMA foreign_keys = ON;
+
+-- The user responsible for a task; a task has at most one assignee
+CREATE TABLE task_assignees (
+    task_id     BLOB PRIMARY KEY,
+    user_id     BLOB NOT NULL,
+    assigned_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_assignees_user_id ON task_assignees(user_id);
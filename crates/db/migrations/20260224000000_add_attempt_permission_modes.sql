@@ -0,0 +1,15 @@
+PRAGMA foreign_keys = ON;
+
+-- Permission mode picked when the attempt was started; NULL runs with the profile's
+ALTER TABLE workspaces ADD COLUMN permission_mode TEXT
+    CHECK (permission_mode IN ('plan', 'default', 'acceptEdits', 'bypassPermissions'));
+
+-- Least strict permission mode a project's attempts may run with, one per project
+CREATE TABLE project_permission_policies (
+    project_id          BLOB PRIMARY KEY NOT NULL,
+    max_permission_mode TEXT NOT NULL
+        CHECK (max_permission_mode IN ('plan', 'default', 'acceptEdits', 'bypassPermissions')),
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
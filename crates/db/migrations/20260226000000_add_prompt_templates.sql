@@ -0,0 +1,26 @@
+PRAGMA foreign_keys = ON;
+
+-- Reusable prompt text with {{variables}}; project_id NULL shares the template across projects
+CREATE TABLE prompt_templates (
+    id          BLOB PRIMARY KEY NOT NULL,
+    project_id  BLOB,
+    name        TEXT NOT NULL,
+    description TEXT,
+    kind        TEXT NOT NULL CHECK (kind IN ('system_prompt', 'follow_up_snippet')),
+    content     TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_prompt_templates_project_id ON prompt_templates(project_id);
+
+-- System prompt templates picked when an attempt was started, applied in position order
+CREATE TABLE workspace_prompt_templates (
+    workspace_id       BLOB NOT NULL,
+    prompt_template_id BLOB NOT NULL,
+    position           INTEGER NOT NULL,
+    PRIMARY KEY (workspace_id, prompt_template_id),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (prompt_template_id) REFERENCES prompt_templates(id) ON DELETE CASCADE
+);
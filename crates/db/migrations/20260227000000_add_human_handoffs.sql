@@ -0,0 +1,101 @@
+-- sqlx workaround: end auto-transaction to allow PRAGMA to take effect
+-- https://github.com/launchbadge/sqlx/issues/2085#issuecomment-1499859906
+COMMIT;
+
+PRAGMA foreign_keys = OFF;
+
+BEGIN TRANSACTION;
+
+-- Allow the blocked column, for tasks whose agent handed the attempt back to a person
+CREATE TABLE tasks_new (
+    id                  BLOB PRIMARY KEY,
+    project_id          BLOB NOT NULL,
+    title               TEXT NOT NULL,
+    description         TEXT,
+    status              TEXT NOT NULL DEFAULT 'todo'
+                           CHECK (status IN ('todo','inprogress','blocked','done','cancelled','inreview')),
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    parent_workspace_id BLOB REFERENCES workspaces(id),
+    shared_task_id      BLOB,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+INSERT INTO tasks_new (id, project_id, title, description, status, created_at, updated_at, parent_workspace_id, shared_task_id)
+SELECT id, project_id, title, description, status, created_at, updated_at, parent_workspace_id, shared_task_id
+FROM tasks;
+
+DROP TABLE tasks;
+ALTER TABLE tasks_new RENAME TO tasks;
+
+CREATE INDEX idx_tasks_project_created_at ON tasks (project_id, created_at DESC);
+CREATE INDEX idx_tasks_parent_workspace_id ON tasks(parent_workspace_id);
+CREATE UNIQUE INDEX idx_tasks_shared_task_unique
+    ON tasks(shared_task_id)
+    WHERE shared_task_id IS NOT NULL;
+
+CREATE TRIGGER task_status_changes_on_insert
+AFTER INSERT ON tasks
+BEGIN
+    INSERT INTO task_status_changes (id, task_id, status)
+    VALUES (randomblob(16), NEW.id, NEW.status);
+END;
+
+CREATE TRIGGER task_status_changes_on_update
+AFTER UPDATE OF status ON tasks
+WHEN OLD.status IS NOT NEW.status
+BEGIN
+    INSERT INTO task_status_changes (id, task_id, status)
+    VALUES (randomblob(16), NEW.id, NEW.status);
+END;
+
+-- Notify when an agent declares itself blocked
+CREATE TABLE notifications_new (
+    id         BLOB PRIMARY KEY,
+    kind       TEXT NOT NULL
+                  CHECK (kind IN ('execution_completed', 'execution_failed', 'question_pending', 'approval_pending', 'review_sla_breached', 'task_stale', 'automation', 'agent_blocked')),
+    title      TEXT NOT NULL,
+    message    TEXT NOT NULL,
+    project_id BLOB,
+    task_id    BLOB,
+    -- NULL until the notification is marked as read
+    read_at    TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO notifications_new (id, kind, title, message, project_id, task_id, read_at, created_at)
+SELECT id, kind, title, message, project_id, task_id, read_at, created_at FROM notifications;
+
+DROP TABLE notifications;
+ALTER TABLE notifications_new RENAME TO notifications;
+
+CREATE INDEX idx_notifications_created_at ON notifications(created_at);
+CREATE INDEX idx_notifications_unread ON notifications(read_at) WHERE read_at IS NULL;
+
+-- Notes left by coding agent runs that ended blocked, one per run
+CREATE TABLE attempt_handoffs (
+    id                   BLOB PRIMARY KEY,
+    workspace_id         BLOB NOT NULL,
+    execution_process_id BLOB NOT NULL UNIQUE,
+    done                 TEXT NOT NULL,
+    blocked              TEXT NOT NULL,
+    -- JSON array of strings
+    next_steps           TEXT NOT NULL DEFAULT '[]',
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_attempt_handoffs_workspace_id ON attempt_handoffs(workspace_id, created_at);
+
+-- Verify foreign key constraints before committing
+PRAGMA foreign_key_check;
+
+COMMIT;
+
+PRAGMA foreign_keys = ON;
+
+-- sqlx workaround: start empty transaction for sqlx to close gracefully
+BEGIN TRANSACTION;
@@ -0,0 +1,38 @@
+PRAGMA foreign_keys = ON;
+
+-- Attempts kept rebased onto their base branches as new commits land there
+CREATE TABLE workspace_base_watches (
+    workspace_id   BLOB PRIMARY KEY NOT NULL,
+    -- Ask the agent to reconcile its work with the new commits after each rebase
+    follow_up      INTEGER NOT NULL DEFAULT 0,
+    -- JSON object of repo id to the base branch commit last handled
+    seen_base_oids TEXT NOT NULL DEFAULT '{}',
+    created_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
+-- Notify when a watched attempt could not be rebased cleanly
+CREATE TABLE notifications_new (
+    id         BLOB PRIMARY KEY,
+    kind       TEXT NOT NULL
+                  CHECK (kind IN ('execution_completed', 'execution_failed', 'question_pending', 'approval_pending', 'review_sla_breached', 'task_stale', 'automation', 'agent_blocked', 'rebase_conflict')),
+    title      TEXT NOT NULL,
+    message    TEXT NOT NULL,
+    project_id BLOB,
+    task_id    BLOB,
+    -- NULL until the notification is marked as read
+    read_at    TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO notifications_new (id, kind, title, message, project_id, task_id, read_at, created_at)
+SELECT id, kind, title, message, project_id, task_id, read_at, created_at FROM notifications;
+
+DROP TABLE notifications;
+ALTER TABLE notifications_new RENAME TO notifications;
+
+CREATE INDEX idx_notifications_created_at ON notifications(created_at);
+CREATE INDEX idx_notifications_unread ON notifications(read_at) WHERE read_at IS NULL;
@@ -0,0 +1,18 @@
+PRAGMA foreign_keys = ON;
+
+-- Snapshots of an attempt's worktrees, uncommitted changes included, that it can be rolled back to
+CREATE TABLE attempt_checkpoints (
+    id                   BLOB PRIMARY KEY,
+    workspace_id         BLOB NOT NULL,
+    -- Run the snapshot was taken during or after
+    execution_process_id BLOB NOT NULL,
+    trigger              TEXT NOT NULL
+                            CHECK (trigger IN ('periodic', 'run_end', 'before_rollback')),
+    -- JSON array of per repository HEAD, snapshot commit and snapshot tree
+    repo_states          TEXT NOT NULL,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_attempt_checkpoints_workspace_id ON attempt_checkpoints(workspace_id, created_at);
@@ -0,0 +1,15 @@
+PRAGMA foreign_keys = ON;
+
+-- Screenshots of an attempt's running dev server
+CREATE TABLE attempt_screenshots (
+    id           BLOB PRIMARY KEY,
+    workspace_id BLOB NOT NULL,
+    image_id     BLOB NOT NULL,
+    -- Page the screenshot was taken of
+    url          TEXT NOT NULL,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (image_id) REFERENCES images(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_attempt_screenshots_workspace_id ON attempt_screenshots(workspace_id, created_at);
@@ -0,0 +1,62 @@
+PRAGMA foreign_keys = ON;
+
+-- Allow a browser E2E command (e.g. Playwright) run against the attempt's dev server
+CREATE TABLE project_lifecycle_hooks_new (
+    project_id BLOB NOT NULL,
+    hook       TEXT NOT NULL CHECK (hook IN (
+                   'worktree_created', 'attempt_started', 'attempt_finished',
+                   'before_merge', 'after_merge', 'e2e_verification'
+               )),
+    command    TEXT NOT NULL,
+    -- A failing blocking hook stops the attempt or merge; advisory failures are only reported
+    blocking   INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (project_id, hook),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+INSERT INTO project_lifecycle_hooks_new (project_id, hook, command, blocking, created_at, updated_at)
+SELECT project_id, hook, command, blocking, created_at, updated_at FROM project_lifecycle_hooks;
+
+DROP TABLE project_lifecycle_hooks;
+ALTER TABLE project_lifecycle_hooks_new RENAME TO project_lifecycle_hooks;
+
+-- Results of an E2E verification run against an attempt's dev server
+CREATE TABLE attempt_e2e_runs (
+    id           BLOB PRIMARY KEY,
+    workspace_id BLOB NOT NULL,
+    -- Dev server URL the tests ran against
+    url          TEXT NOT NULL,
+    command      TEXT NOT NULL,
+    status       TEXT NOT NULL CHECK (status IN ('passed', 'failed', 'error')),
+    passed       INTEGER NOT NULL DEFAULT 0,
+    failed       INTEGER NOT NULL DEFAULT 0,
+    flaky        INTEGER NOT NULL DEFAULT 0,
+    skipped      INTEGER NOT NULL DEFAULT 0,
+    -- JSON array of failing tests with their first error
+    failures     TEXT NOT NULL DEFAULT '[]',
+    -- Tail of the command's output
+    output       TEXT NOT NULL DEFAULT '',
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_attempt_e2e_runs_workspace_id ON attempt_e2e_runs(workspace_id, created_at);
+
+-- Screenshots and traces a run left behind, kept in blob storage
+CREATE TABLE attempt_e2e_artifacts (
+    id            BLOB PRIMARY KEY,
+    run_id        BLOB NOT NULL,
+    kind          TEXT NOT NULL CHECK (kind IN ('screenshot', 'trace')),
+    -- Blob storage path
+    file_path     TEXT NOT NULL,
+    -- Path relative to the test output directory
+    original_name TEXT NOT NULL,
+    mime_type     TEXT NOT NULL,
+    size_bytes    INTEGER NOT NULL,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (run_id) REFERENCES attempt_e2e_runs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_attempt_e2e_artifacts_run_id ON attempt_e2e_artifacts(run_id);
@@ -0,0 +1,33 @@
+PRAGMA foreign_keys = ON;
+
+-- Linters a project runs against the files its attempts change
+CREATE TABLE project_linters (
+    project_id    BLOB NOT NULL,
+    linter        TEXT NOT NULL CHECK (linter IN ('clippy', 'eslint')),
+    -- Must print the linter's JSON output to stdout
+    command       TEXT NOT NULL,
+    -- Send error annotations back to the attempt's agent as a follow-up to fix
+    fix_follow_up INTEGER NOT NULL DEFAULT 0,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (project_id, linter),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+-- Findings of the latest run of each linter on an attempt's changed files
+CREATE TABLE attempt_lint_annotations (
+    id           BLOB PRIMARY KEY,
+    workspace_id BLOB NOT NULL,
+    linter       TEXT NOT NULL CHECK (linter IN ('clippy', 'eslint')),
+    -- Path within the workspace, prefixed with the repository name
+    file         TEXT NOT NULL,
+    line         INTEGER NOT NULL,
+    col          INTEGER NOT NULL,
+    rule         TEXT,
+    severity     TEXT NOT NULL CHECK (severity IN ('error', 'warning')),
+    message      TEXT NOT NULL,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_attempt_lint_annotations_workspace_id ON attempt_lint_annotations(workspace_id, linter);
@@ -1,4 +1,7 @@
-use std::{str::FromStr, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use sqlx::{
     Error, Pool, Sqlite, SqlitePool,
@@ -8,18 +11,68 @@ use utils::assets::asset_dir;
 
 pub mod models;
 
+/// Location of the SQLite database in the asset directory.
+pub fn database_path() -> PathBuf {
+    asset_dir().join("db.sqlite")
+}
+
+/// Swap in a database staged by a restore before any connection is opened.
+fn apply_pending_restore(db_path: &Path) -> Result<(), Error> {
+    let staged = pending_restore_path(db_path);
+    if !staged.exists() {
+        return Ok(());
+    }
+
+    tracing::info!("Applying staged database restore from {}", staged.display());
+    if db_path.exists() {
+        std::fs::rename(db_path, path_with_suffix(db_path, ".pre-restore"))?;
+    }
+    for suffix in ["-wal", "-shm"] {
+        let sidecar = path_with_suffix(db_path, suffix);
+        if sidecar.exists() {
+            std::fs::remove_file(sidecar)?;
+        }
+    }
+    std::fs::rename(staged, db_path)?;
+    Ok(())
+}
+
+fn connect_options() -> Result<SqliteConnectOptions, Error> {
+    let db_path = database_path();
+    apply_pending_restore(&db_path)?;
+    Ok(SqliteConnectOptions::new()
+        .filename(db_path)
+        .create_if_missing(true))
+}
+
+fn path_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    path.into()
+}
+
+/// Where a restored database is staged until the next start, next to `db_path`.
+pub fn pending_restore_path(db_path: &Path) -> PathBuf {
+    path_with_suffix(db_path, ".restore")
+}
+
 #[derive(Clone)]
 pub struct DBService {
     pub pool: Pool<Sqlite>,
 }
 
 impl DBService {
+    /// Latest migration version known to this build.
+    pub fn schema_version() -> i64 {
+        sqlx::migrate!("./migrations")
+            .iter()
+            .map(|migration| migration.version)
+            .max()
+            .unwrap_or_default()
+    }
+
     pub async fn new() -> Result<DBService, Error> {
-        let database_url = format!(
-            "sqlite://{}",
-            asset_dir().join("db.sqlite").to_string_lossy()
-        );
-        let options = SqliteConnectOptions::from_str(&database_url)?.create_if_missing(true);
+        let options = connect_options()?;
         let pool = SqlitePool::connect_with(options).await?;
         sqlx::migrate!("./migrations").run(&pool).await?;
         Ok(DBService { pool })
@@ -49,11 +102,7 @@ impl DBService {
             + Sync
             + 'static,
     {
-        let database_url = format!(
-            "sqlite://{}",
-            asset_dir().join("db.sqlite").to_string_lossy()
-        );
-        let options = SqliteConnectOptions::from_str(&database_url)?.create_if_missing(true);
+        let options = connect_options()?;
 
         let pool = if let Some(hook) = after_connect {
             SqlitePoolOptions::new()
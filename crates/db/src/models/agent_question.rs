@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use uuid::Uuid;
+
+use super::execution_process::ExecutorActionField;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq)]
+#[sqlx(type_name = "agent_question_outcome", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AgentQuestionOutcome {
+    Pending,
+    Answered,
+    TimedOut,
+    AutoAnswered,
+}
+
+/// A question request from a coding agent with the executor that made it, for question
+/// statistics.
+#[derive(Debug, Clone, FromRow)]
+pub struct AgentQuestion {
+    pub executor_action: sqlx::types::Json<ExecutorActionField>,
+    pub question_count: i64,
+    pub outcome: AgentQuestionOutcome,
+    pub requested_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl AgentQuestion {
+    pub async fn create(
+        pool: &SqlitePool,
+        id: &str,
+        execution_process_id: Uuid,
+        question_count: i64,
+        requested_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO agent_questions (id, execution_process_id, question_count, requested_at)
+               VALUES ($1, $2, $3, $4)"#,
+            id,
+            execution_process_id,
+            question_count,
+            requested_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn resolve(
+        pool: &SqlitePool,
+        id: &str,
+        outcome: AgentQuestionOutcome,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE agent_questions
+               SET outcome = $2, resolved_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            outcome
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Question requests made since `since`, optionally only by agents of one project.
+    pub async fn find_since(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AgentQuestion,
+            r#"SELECT ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
+                      q.question_count as "question_count!: i64",
+                      q.outcome as "outcome!: AgentQuestionOutcome",
+                      q.requested_at as "requested_at!: DateTime<Utc>",
+                      q.resolved_at as "resolved_at?: DateTime<Utc>"
+               FROM agent_questions q
+               JOIN execution_processes ep ON ep.id = q.execution_process_id
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE ($1 IS NULL OR t.project_id = $1) AND q.requested_at >= $2
+               ORDER BY q.requested_at ASC"#,
+            project_id,
+            since
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
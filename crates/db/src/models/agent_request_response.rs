@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq)]
+#[sqlx(type_name = "agent_request_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AgentRequestKind {
+    Question,
+    Approval,
+}
+
+/// When a question or approval request from a coding agent was answered.
+#[derive(Debug, Clone, FromRow)]
+pub struct AgentRequestResponse {
+    pub kind: AgentRequestKind,
+    pub requested_at: DateTime<Utc>,
+    pub responded_at: DateTime<Utc>,
+}
+
+impl AgentRequestResponse {
+    pub async fn create(
+        pool: &SqlitePool,
+        kind: AgentRequestKind,
+        execution_process_id: Uuid,
+        requested_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO agent_request_responses (id, kind, execution_process_id, requested_at)
+               VALUES ($1, $2, $3, $4)"#,
+            id,
+            kind,
+            execution_process_id,
+            requested_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Responses to requests made since `since` by agents working on the project's tasks.
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AgentRequestResponse,
+            r#"SELECT r.kind as "kind!: AgentRequestKind",
+                      r.requested_at as "requested_at!: DateTime<Utc>",
+                      r.responded_at as "responded_at!: DateTime<Utc>"
+               FROM agent_request_responses r
+               JOIN execution_processes ep ON ep.id = r.execution_process_id
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE t.project_id = $1 AND r.requested_at >= $2
+               ORDER BY r.requested_at ASC"#,
+            project_id,
+            since
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
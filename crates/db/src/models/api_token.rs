@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Token for automation that may only list and answer the questions and approvals of some
+/// projects. Only the SHA-256 of the token is persisted.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub token_hash: String,
+    #[ts(type = "Array<string>")]
+    pub project_ids: Json<Vec<Uuid>>,
+    pub created_by: Option<Uuid>,
+    #[ts(type = "Date | null")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateApiToken {
+    pub name: String,
+    pub project_ids: Vec<Uuid>,
+}
+
+impl ApiToken {
+    pub fn covers_project(&self, project_id: Uuid) -> bool {
+        self.project_ids.0.contains(&project_id)
+    }
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApiToken,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      token_hash,
+                      project_ids as "project_ids!: Json<Vec<Uuid>>",
+                      created_by as "created_by: Uuid",
+                      last_used_at as "last_used_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM api_tokens
+               ORDER BY created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_token_hash(
+        pool: &SqlitePool,
+        token_hash: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApiToken,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      token_hash,
+                      project_ids as "project_ids!: Json<Vec<Uuid>>",
+                      created_by as "created_by: Uuid",
+                      last_used_at as "last_used_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM api_tokens
+               WHERE token_hash = $1"#,
+            token_hash
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateApiToken,
+        token_hash: &str,
+        created_by: Option<Uuid>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let project_ids = Json(&data.project_ids);
+        sqlx::query_as!(
+            ApiToken,
+            r#"INSERT INTO api_tokens (id, name, token_hash, project_ids, created_by)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         name,
+                         token_hash,
+                         project_ids as "project_ids!: Json<Vec<Uuid>>",
+                         created_by as "created_by: Uuid",
+                         last_used_at as "last_used_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            data.name,
+            token_hash,
+            project_ids,
+            created_by
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn touch(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE api_tokens SET last_used_at = datetime('now', 'subsec') WHERE id = $1",
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM api_tokens WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
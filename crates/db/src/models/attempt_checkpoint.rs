@@ -0,0 +1,134 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "checkpoint_trigger", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CheckpointTrigger {
+    /// Taken while a coding agent was running
+    Periodic,
+    /// Taken once a coding agent run finished
+    RunEnd,
+    /// The state a rollback replaced, so the rollback can be undone
+    BeforeRollback,
+}
+
+/// Where one repository of the attempt was when the checkpoint was taken.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+pub struct CheckpointRepoState {
+    pub repo_id: Uuid,
+    /// Branch head, restored as is
+    pub head_oid: String,
+    /// Commit on top of the head holding the uncommitted changes
+    pub snapshot_oid: String,
+    pub tree_oid: String,
+}
+
+/// Snapshot of an attempt's worktrees that the attempt can be rolled back to.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct AttemptCheckpoint {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub execution_process_id: Uuid,
+    pub trigger: CheckpointTrigger,
+    #[ts(type = "Array<CheckpointRepoState>")]
+    pub repo_states: Json<Vec<CheckpointRepoState>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl AttemptCheckpoint {
+    pub async fn create(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        execution_process_id: Uuid,
+        trigger: CheckpointTrigger,
+        repo_states: &[CheckpointRepoState],
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let repo_states = Json(repo_states);
+        sqlx::query_as!(
+            AttemptCheckpoint,
+            r#"INSERT INTO attempt_checkpoints
+                   (id, workspace_id, execution_process_id, trigger, repo_states)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         execution_process_id as "execution_process_id!: Uuid",
+                         trigger as "trigger!: CheckpointTrigger",
+                         repo_states as "repo_states!: Json<Vec<CheckpointRepoState>>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            workspace_id,
+            execution_process_id,
+            trigger,
+            repo_states
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptCheckpoint,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      trigger as "trigger!: CheckpointTrigger",
+                      repo_states as "repo_states!: Json<Vec<CheckpointRepoState>>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_checkpoints
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Every checkpoint of the attempt, newest first.
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptCheckpoint,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      trigger as "trigger!: CheckpointTrigger",
+                      repo_states as "repo_states!: Json<Vec<CheckpointRepoState>>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_checkpoints
+               WHERE workspace_id = $1
+               ORDER BY created_at DESC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_latest_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptCheckpoint,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      trigger as "trigger!: CheckpointTrigger",
+                      repo_states as "repo_states!: Json<Vec<CheckpointRepoState>>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_checkpoints
+               WHERE workspace_id = $1
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
@@ -0,0 +1,308 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "e2e_run_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum E2eRunStatus {
+    Passed,
+    /// At least one test failed, or the command exited with an error
+    Failed,
+    /// The command could not be run
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "e2e_artifact_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum E2eArtifactKind {
+    Screenshot,
+    /// Playwright trace archive, viewable with `npx playwright show-trace`
+    Trace,
+}
+
+/// A failing test and the first error it reported.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+pub struct E2eTestFailure {
+    /// Spec title, prefixed with its describe blocks
+    pub title: String,
+    pub file: String,
+    pub error: Option<String>,
+}
+
+/// Result of running a project's E2E verification against an attempt's dev server.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct AttemptE2eRun {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    /// Dev server URL the tests ran against
+    pub url: String,
+    pub command: String,
+    pub status: E2eRunStatus,
+    pub passed: i64,
+    pub failed: i64,
+    pub flaky: i64,
+    pub skipped: i64,
+    #[ts(type = "Array<E2eTestFailure>")]
+    pub failures: Json<Vec<E2eTestFailure>>,
+    /// Tail of the command's output
+    pub output: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateAttemptE2eRun {
+    pub url: String,
+    pub command: String,
+    pub status: E2eRunStatus,
+    pub passed: i64,
+    pub failed: i64,
+    pub flaky: i64,
+    pub skipped: i64,
+    pub failures: Vec<E2eTestFailure>,
+    pub output: String,
+}
+
+/// Screenshot or trace left behind by an E2E run.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct AttemptE2eArtifact {
+    pub id: Uuid,
+    pub run_id: Uuid,
+    pub kind: E2eArtifactKind,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub file_path: String,
+    /// Path relative to the test output directory
+    pub original_name: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl AttemptE2eRun {
+    pub async fn create(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        data: &CreateAttemptE2eRun,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let failures = Json(&data.failures);
+        sqlx::query_as!(
+            AttemptE2eRun,
+            r#"INSERT INTO attempt_e2e_runs
+                   (id, workspace_id, url, command, status, passed, failed, flaky, skipped,
+                    failures, output)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+               RETURNING id as "id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         url,
+                         command,
+                         status as "status!: E2eRunStatus",
+                         passed as "passed!: i64",
+                         failed as "failed!: i64",
+                         flaky as "flaky!: i64",
+                         skipped as "skipped!: i64",
+                         failures as "failures!: Json<Vec<E2eTestFailure>>",
+                         output,
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            workspace_id,
+            data.url,
+            data.command,
+            data.status,
+            data.passed,
+            data.failed,
+            data.flaky,
+            data.skipped,
+            failures,
+            data.output
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptE2eRun,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      url,
+                      command,
+                      status as "status!: E2eRunStatus",
+                      passed as "passed!: i64",
+                      failed as "failed!: i64",
+                      flaky as "flaky!: i64",
+                      skipped as "skipped!: i64",
+                      failures as "failures!: Json<Vec<E2eTestFailure>>",
+                      output,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_e2e_runs
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Every run of the attempt, newest first.
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptE2eRun,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      url,
+                      command,
+                      status as "status!: E2eRunStatus",
+                      passed as "passed!: i64",
+                      failed as "failed!: i64",
+                      flaky as "flaky!: i64",
+                      skipped as "skipped!: i64",
+                      failures as "failures!: Json<Vec<E2eTestFailure>>",
+                      output,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_e2e_runs
+               WHERE workspace_id = $1
+               ORDER BY created_at DESC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_latest_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptE2eRun,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      url,
+                      command,
+                      status as "status!: E2eRunStatus",
+                      passed as "passed!: i64",
+                      failed as "failed!: i64",
+                      flaky as "flaky!: i64",
+                      skipped as "skipped!: i64",
+                      failures as "failures!: Json<Vec<E2eTestFailure>>",
+                      output,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_e2e_runs
+               WHERE workspace_id = $1
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
+
+impl AttemptE2eArtifact {
+    pub async fn create(
+        pool: &SqlitePool,
+        run_id: Uuid,
+        kind: E2eArtifactKind,
+        file_path: &str,
+        original_name: &str,
+        mime_type: &str,
+        size_bytes: i64,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            AttemptE2eArtifact,
+            r#"INSERT INTO attempt_e2e_artifacts
+                   (id, run_id, kind, file_path, original_name, mime_type, size_bytes)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         run_id as "run_id!: Uuid",
+                         kind as "kind!: E2eArtifactKind",
+                         file_path,
+                         original_name,
+                         mime_type,
+                         size_bytes as "size_bytes!: i64",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            run_id,
+            kind,
+            file_path,
+            original_name,
+            mime_type,
+            size_bytes
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptE2eArtifact,
+            r#"SELECT id as "id!: Uuid",
+                      run_id as "run_id!: Uuid",
+                      kind as "kind!: E2eArtifactKind",
+                      file_path,
+                      original_name,
+                      mime_type,
+                      size_bytes as "size_bytes!: i64",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_e2e_artifacts
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_run_id(pool: &SqlitePool, run_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptE2eArtifact,
+            r#"SELECT id as "id!: Uuid",
+                      run_id as "run_id!: Uuid",
+                      kind as "kind!: E2eArtifactKind",
+                      file_path,
+                      original_name,
+                      mime_type,
+                      size_bytes as "size_bytes!: i64",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_e2e_artifacts
+               WHERE run_id = $1
+               ORDER BY original_name"#,
+            run_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Artifacts of every attempt of the task, gathered before the task is deleted.
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptE2eArtifact,
+            r#"SELECT a.id as "id!: Uuid",
+                      a.run_id as "run_id!: Uuid",
+                      a.kind as "kind!: E2eArtifactKind",
+                      a.file_path,
+                      a.original_name,
+                      a.mime_type,
+                      a.size_bytes as "size_bytes!: i64",
+                      a.created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_e2e_artifacts a
+               JOIN attempt_e2e_runs r ON r.id = a.run_id
+               JOIN workspaces w ON w.id = r.workspace_id
+               WHERE w.task_id = $1"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
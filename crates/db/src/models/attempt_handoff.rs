@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use executors::logs::human_handoff::HumanHandoff;
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Note a coding agent run left when it ended blocked on a person.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct AttemptHandoff {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub execution_process_id: Uuid,
+    pub done: String,
+    pub blocked: String,
+    #[ts(type = "Array<string>")]
+    pub next_steps: Json<Vec<String>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl AttemptHandoff {
+    pub async fn create(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        execution_process_id: Uuid,
+        handoff: &HumanHandoff,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let next_steps = Json(handoff.next_steps.clone());
+        sqlx::query_as!(
+            AttemptHandoff,
+            r#"INSERT INTO attempt_handoffs
+                   (id, workspace_id, execution_process_id, done, blocked, next_steps)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         execution_process_id as "execution_process_id!: Uuid",
+                         done,
+                         blocked,
+                         next_steps as "next_steps!: Json<Vec<String>>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            workspace_id,
+            execution_process_id,
+            handoff.done,
+            handoff.blocked,
+            next_steps
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// The handoff of the attempt's latest coding agent run, while no run has followed it.
+    pub async fn find_open_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptHandoff,
+            r#"SELECT ah.id as "id!: Uuid",
+                      ah.workspace_id as "workspace_id!: Uuid",
+                      ah.execution_process_id as "execution_process_id!: Uuid",
+                      ah.done,
+                      ah.blocked,
+                      ah.next_steps as "next_steps!: Json<Vec<String>>",
+                      ah.created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_handoffs ah
+               WHERE ah.workspace_id = $1
+                 AND ah.execution_process_id = (
+                     SELECT ep.id
+                     FROM execution_processes ep
+                     JOIN sessions s ON ep.session_id = s.id
+                     WHERE s.workspace_id = $1
+                       AND ep.run_reason = 'codingagent'
+                     ORDER BY ep.created_at DESC
+                     LIMIT 1
+                 )"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Every handoff of the attempt, newest first.
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptHandoff,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      done,
+                      blocked,
+                      next_steps as "next_steps!: Json<Vec<String>>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_handoffs
+               WHERE workspace_id = $1
+               ORDER BY created_at DESC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
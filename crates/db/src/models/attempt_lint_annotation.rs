@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::project_linter::Linter;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "lint_severity", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    /// Blocking; sent back to the agent when the linter has fix follow-ups enabled
+    Error,
+    Warning,
+}
+
+/// A linter finding on a file the attempt changed, from the linter's latest run.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct AttemptLintAnnotation {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub linter: Linter,
+    /// Path within the workspace, prefixed with the repository name
+    pub file: String,
+    pub line: i64,
+    pub col: i64,
+    pub rule: Option<String>,
+    pub severity: LintSeverity,
+    pub message: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateLintAnnotation {
+    pub file: String,
+    pub line: i64,
+    pub col: i64,
+    pub rule: Option<String>,
+    pub severity: LintSeverity,
+    pub message: String,
+}
+
+impl AttemptLintAnnotation {
+    /// Every annotation on the attempt, grouped by file.
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptLintAnnotation,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      linter as "linter!: Linter",
+                      file,
+                      line as "line!: i64",
+                      col as "col!: i64",
+                      rule,
+                      severity as "severity!: LintSeverity",
+                      message,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_lint_annotations
+               WHERE workspace_id = $1
+               ORDER BY file, line, col"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Swap the linter's annotations on the attempt for those of its latest run.
+    pub async fn replace_for_linter(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        linter: Linter,
+        annotations: &[CreateLintAnnotation],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            "DELETE FROM attempt_lint_annotations WHERE workspace_id = $1 AND linter = $2",
+            workspace_id,
+            linter
+        )
+        .execute(&mut *tx)
+        .await?;
+        for annotation in annotations {
+            let id = Uuid::new_v4();
+            sqlx::query!(
+                r#"INSERT INTO attempt_lint_annotations
+                       (id, workspace_id, linter, file, line, col, rule, severity, message)
+                   VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
+                id,
+                workspace_id,
+                linter,
+                annotation.file,
+                annotation.line,
+                annotation.col,
+                annotation.rule,
+                annotation.severity,
+                annotation.message
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "attempt_review_verdict", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AttemptReviewVerdict {
+    Approved,
+    ChangesRequested,
+}
+
+/// A reviewer's overall verdict on an attempt. Each submission adds one; the latest counts.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct AttemptReview {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub verdict: AttemptReviewVerdict,
+    pub summary: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateAttemptReview {
+    pub verdict: AttemptReviewVerdict,
+    #[serde(default)]
+    pub summary: Option<String>,
+}
+
+/// A reviewer's comment on a line of an attempt's diff.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct AttemptReviewComment {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    /// Path as shown in the attempt diff
+    pub file_path: String,
+    /// Line in the new version of the file
+    #[ts(type = "number")]
+    pub line: i64,
+    pub body: String,
+    pub resolved: bool,
+    /// When the comment was last sent to the agent as feedback
+    #[ts(type = "Date | null")]
+    pub sent_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateAttemptReviewComment {
+    pub file_path: String,
+    #[ts(type = "number")]
+    pub line: i64,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateAttemptReviewComment {
+    pub body: Option<String>,
+    pub resolved: Option<bool>,
+}
+
+impl AttemptReview {
+    pub async fn create(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        data: &CreateAttemptReview,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            AttemptReview,
+            r#"INSERT INTO attempt_reviews (id, workspace_id, verdict, summary)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         verdict as "verdict!: AttemptReviewVerdict",
+                         summary,
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            workspace_id,
+            data.verdict,
+            data.summary
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_latest_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptReview,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      verdict as "verdict!: AttemptReviewVerdict",
+                      summary,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_reviews
+               WHERE workspace_id = $1
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
+
+impl AttemptReviewComment {
+    /// Comments of an attempt in diff order
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptReviewComment,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      file_path,
+                      line as "line!: i64",
+                      body,
+                      resolved as "resolved!: bool",
+                      sent_at as "sent_at?: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM attempt_review_comments
+               WHERE workspace_id = $1
+               ORDER BY file_path ASC, line ASC, created_at ASC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptReviewComment,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      file_path,
+                      line as "line!: i64",
+                      body,
+                      resolved as "resolved!: bool",
+                      sent_at as "sent_at?: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM attempt_review_comments
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        data: &CreateAttemptReviewComment,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            AttemptReviewComment,
+            r#"INSERT INTO attempt_review_comments (id, workspace_id, file_path, line, body)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         file_path,
+                         line as "line!: i64",
+                         body,
+                         resolved as "resolved!: bool",
+                         sent_at as "sent_at?: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            workspace_id,
+            data.file_path,
+            data.line,
+            data.body
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        &self,
+        pool: &SqlitePool,
+        data: &UpdateAttemptReviewComment,
+    ) -> Result<Self, sqlx::Error> {
+        let body = data.body.as_ref().unwrap_or(&self.body);
+        let resolved = data.resolved.unwrap_or(self.resolved);
+        sqlx::query_as!(
+            AttemptReviewComment,
+            r#"UPDATE attempt_review_comments
+               SET body = $2, resolved = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         file_path,
+                         line as "line!: i64",
+                         body,
+                         resolved as "resolved!: bool",
+                         sent_at as "sent_at?: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            self.id,
+            body,
+            resolved
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn mark_sent(pool: &SqlitePool, ids: &[Uuid]) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for id in ids {
+            sqlx::query!(
+                r#"UPDATE attempt_review_comments
+                   SET sent_at = datetime('now', 'subsec')
+                   WHERE id = $1"#,
+                id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM attempt_review_comments WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
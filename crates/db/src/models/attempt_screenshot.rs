@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Screenshot of an attempt's running dev server, stored as an image.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct AttemptScreenshot {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub image_id: Uuid,
+    /// Page the screenshot was taken of
+    pub url: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl AttemptScreenshot {
+    pub async fn create(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        image_id: Uuid,
+        url: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            AttemptScreenshot,
+            r#"INSERT INTO attempt_screenshots (id, workspace_id, image_id, url)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         image_id as "image_id!: Uuid",
+                         url,
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            workspace_id,
+            image_id,
+            url
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Every screenshot of the attempt, newest first.
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptScreenshot,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      image_id as "image_id!: Uuid",
+                      url,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_screenshots
+               WHERE workspace_id = $1
+               ORDER BY created_at DESC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_latest_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptScreenshot,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      image_id as "image_id!: Uuid",
+                      url,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_screenshots
+               WHERE workspace_id = $1
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
@@ -0,0 +1,289 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+/// What has to happen to a task of the project for a rule to fire.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AutomationTrigger {
+    /// `null` matches any status
+    StatusChanged {
+        #[serde(default)]
+        from: Option<TaskStatus>,
+        #[serde(default)]
+        to: Option<TaskStatus>,
+    },
+    /// `null` matches any label
+    LabelAdded {
+        #[serde(default)]
+        label: Option<String>,
+    },
+    AttemptFailed,
+    QuestionTimedOut,
+}
+
+/// A step run, in order, when a rule fires.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AutomationAction {
+    MoveTo {
+        status: TaskStatus,
+    },
+    /// Assign the shared task to a user of the remote organization
+    Assign {
+        user_id: String,
+    },
+    Notify {
+        message: String,
+    },
+    StartAttempt,
+    AddLabel {
+        label: String,
+    },
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct AutomationRule {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    #[ts(type = "AutomationTrigger")]
+    pub trigger: Json<AutomationTrigger>,
+    #[ts(type = "Array<AutomationAction>")]
+    pub actions: Json<Vec<AutomationAction>>,
+    pub enabled: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateAutomationRule {
+    pub name: String,
+    pub trigger: AutomationTrigger,
+    pub actions: Vec<AutomationAction>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateAutomationRule {
+    pub name: Option<String>,
+    pub trigger: Option<AutomationTrigger>,
+    pub actions: Option<Vec<AutomationAction>>,
+    pub enabled: Option<bool>,
+}
+
+/// Outcome of one action of a firing.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct AutomationActionResult {
+    pub action: AutomationAction,
+    pub ok: bool,
+    /// Why the action failed or was skipped
+    pub detail: Option<String>,
+}
+
+/// A rule firing on a task, kept as an audit trail.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct AutomationRuleFiring {
+    pub id: Uuid,
+    pub rule_id: Uuid,
+    pub task_id: Uuid,
+    #[ts(type = "Array<AutomationActionResult>")]
+    pub results: Json<Vec<AutomationActionResult>>,
+    #[ts(type = "Date")]
+    pub fired_at: DateTime<Utc>,
+}
+
+impl AutomationRule {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AutomationRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      rule_trigger as "trigger!: Json<AutomationTrigger>",
+                      actions as "actions!: Json<Vec<AutomationAction>>",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM automation_rules
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AutomationRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      rule_trigger as "trigger!: Json<AutomationTrigger>",
+                      actions as "actions!: Json<Vec<AutomationAction>>",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM automation_rules
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateAutomationRule,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let trigger = Json(&data.trigger);
+        let actions = Json(&data.actions);
+        sqlx::query_as!(
+            AutomationRule,
+            r#"INSERT INTO automation_rules (id, project_id, name, rule_trigger, actions)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         rule_trigger as "trigger!: Json<AutomationTrigger>",
+                         actions as "actions!: Json<Vec<AutomationAction>>",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            trigger,
+            actions
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Replace every field.
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        name: &str,
+        trigger: &AutomationTrigger,
+        actions: &[AutomationAction],
+        enabled: bool,
+    ) -> Result<Self, sqlx::Error> {
+        let trigger = Json(trigger);
+        let actions = Json(actions);
+        sqlx::query_as!(
+            AutomationRule,
+            r#"UPDATE automation_rules
+               SET name = $2, rule_trigger = $3, actions = $4, enabled = $5,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         rule_trigger as "trigger!: Json<AutomationTrigger>",
+                         actions as "actions!: Json<Vec<AutomationAction>>",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            trigger,
+            actions,
+            enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM automation_rules WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl AutomationRuleFiring {
+    pub async fn create(
+        pool: &SqlitePool,
+        rule_id: Uuid,
+        task_id: Uuid,
+        results: &[AutomationActionResult],
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let results = Json(results);
+        sqlx::query_as!(
+            AutomationRuleFiring,
+            r#"INSERT INTO automation_rule_firings (id, rule_id, task_id, results)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         rule_id as "rule_id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         results as "results!: Json<Vec<AutomationActionResult>>",
+                         fired_at as "fired_at!: DateTime<Utc>""#,
+            id,
+            rule_id,
+            task_id,
+            results
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Most recent firings of a rule first.
+    pub async fn find_by_rule_id(
+        pool: &SqlitePool,
+        rule_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AutomationRuleFiring,
+            r#"SELECT id as "id!: Uuid",
+                      rule_id as "rule_id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      results as "results!: Json<Vec<AutomationActionResult>>",
+                      fired_at as "fired_at!: DateTime<Utc>"
+               FROM automation_rule_firings
+               WHERE rule_id = $1
+               ORDER BY fired_at DESC
+               LIMIT $2"#,
+            rule_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// How often a rule fired on a task within the last `window_secs` seconds.
+    pub async fn count_recent(
+        pool: &SqlitePool,
+        rule_id: Uuid,
+        task_id: Uuid,
+        window_secs: i64,
+    ) -> Result<i64, sqlx::Error> {
+        let modifier = format!("-{window_secs} seconds");
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM automation_rule_firings
+               WHERE rule_id = $1
+                 AND task_id = $2
+                 AND fired_at >= datetime('now', 'subsec', $3)"#,
+            rule_id,
+            task_id,
+            modifier
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
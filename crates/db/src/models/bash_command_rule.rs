@@ -0,0 +1,166 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Default)]
+#[sqlx(type_name = "command_match_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CommandMatchKind {
+    /// `*` matches anything and `?` any single character
+    #[default]
+    Glob,
+    Regex,
+}
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "command_rule_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CommandRuleAction {
+    Allow,
+    Deny,
+}
+
+/// Runs or rejects a shell command an agent asks approval for without waiting for a reviewer.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct BashCommandRule {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub pattern: String,
+    pub match_kind: CommandMatchKind,
+    pub action: CommandRuleAction,
+    pub enabled: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateBashCommandRule {
+    pub pattern: String,
+    #[serde(default)]
+    pub match_kind: CommandMatchKind,
+    pub action: CommandRuleAction,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateBashCommandRule {
+    pub pattern: Option<String>,
+    pub match_kind: Option<CommandMatchKind>,
+    pub action: Option<CommandRuleAction>,
+    pub enabled: Option<bool>,
+}
+
+impl BashCommandRule {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            BashCommandRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      pattern,
+                      match_kind as "match_kind!: CommandMatchKind",
+                      action as "action!: CommandRuleAction",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM bash_command_rules
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            BashCommandRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      pattern,
+                      match_kind as "match_kind!: CommandMatchKind",
+                      action as "action!: CommandRuleAction",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM bash_command_rules
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateBashCommandRule,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            BashCommandRule,
+            r#"INSERT INTO bash_command_rules (id, project_id, pattern, match_kind, action)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         pattern,
+                         match_kind as "match_kind!: CommandMatchKind",
+                         action as "action!: CommandRuleAction",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.pattern,
+            data.match_kind,
+            data.action
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        &self,
+        pool: &SqlitePool,
+        data: &UpdateBashCommandRule,
+    ) -> Result<Self, sqlx::Error> {
+        let pattern = data.pattern.as_ref().unwrap_or(&self.pattern);
+        let match_kind = data.match_kind.unwrap_or(self.match_kind);
+        let action = data.action.unwrap_or(self.action);
+        let enabled = data.enabled.unwrap_or(self.enabled);
+        sqlx::query_as!(
+            BashCommandRule,
+            r#"UPDATE bash_command_rules
+               SET pattern = $2, match_kind = $3, action = $4, enabled = $5,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         pattern,
+                         match_kind as "match_kind!: CommandMatchKind",
+                         action as "action!: CommandRuleAction",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            self.id,
+            pattern,
+            match_kind,
+            action,
+            enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM bash_command_rules WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
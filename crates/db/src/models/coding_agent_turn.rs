@@ -45,6 +45,32 @@ impl CodingAgentTurn {
         .await
     }
 
+    /// Find all coding agent turns of a workspace, oldest first
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CodingAgentTurn,
+            r#"SELECT
+                cat.id as "id!: Uuid",
+                cat.execution_process_id as "execution_process_id!: Uuid",
+                cat.agent_session_id,
+                cat.prompt,
+                cat.summary,
+                cat.created_at as "created_at!: DateTime<Utc>",
+                cat.updated_at as "updated_at!: DateTime<Utc>"
+               FROM coding_agent_turns cat
+               JOIN execution_processes ep ON ep.id = cat.execution_process_id
+               JOIN sessions s ON s.id = ep.session_id
+               WHERE s.workspace_id = $1
+               ORDER BY cat.created_at ASC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn find_by_agent_session_id(
         pool: &SqlitePool,
         agent_session_id: &str,
@@ -131,6 +157,27 @@ impl CodingAgentTurn {
         Ok(())
     }
 
+    /// Record the cost the coding agent reported for the turn
+    pub async fn update_cost(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        cost_usd: f64,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query!(
+            r#"UPDATE coding_agent_turns
+               SET cost_usd = $1, updated_at = $2
+               WHERE execution_process_id = $3"#,
+            cost_usd,
+            now,
+            execution_process_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Update coding agent turn summary
     pub async fn update_summary(
         pool: &SqlitePool,
@@ -0,0 +1,209 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Error tracker whose issues become tasks of a project.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ErrorTrackerIntegration {
+    pub project_id: Uuid,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub webhook_secret: String,
+    /// Start a "diagnose and fix" attempt for every new issue
+    pub auto_start: bool,
+    /// Spend of auto-started attempts over the last 24 hours above which no more are started
+    pub daily_cost_cap_usd: Option<f64>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A task created for an error tracker issue.
+#[derive(Debug, Clone, FromRow)]
+pub struct ErrorTrackerIssue {
+    pub project_id: Uuid,
+    pub fingerprint: String,
+    pub task_id: Uuid,
+    pub event_count: i64,
+    pub auto_started: bool,
+    pub last_seen_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ErrorTrackerIntegration {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ErrorTrackerIntegration,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      webhook_secret,
+                      auto_start as "auto_start!: bool",
+                      daily_cost_cap_usd,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM error_tracker_integrations
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Create or replace the project's integration. `webhook_secret` must already be encrypted.
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        webhook_secret: &str,
+        auto_start: bool,
+        daily_cost_cap_usd: Option<f64>,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ErrorTrackerIntegration,
+            r#"INSERT INTO error_tracker_integrations
+                   (project_id, webhook_secret, auto_start, daily_cost_cap_usd)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   webhook_secret = excluded.webhook_secret,
+                   auto_start = excluded.auto_start,
+                   daily_cost_cap_usd = excluded.daily_cost_cap_usd,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         webhook_secret,
+                         auto_start as "auto_start!: bool",
+                         daily_cost_cap_usd,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            webhook_secret,
+            auto_start,
+            daily_cost_cap_usd
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM error_tracker_integrations WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl ErrorTrackerIssue {
+    pub async fn find_by_fingerprint(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        fingerprint: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ErrorTrackerIssue,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      fingerprint,
+                      task_id as "task_id!: Uuid",
+                      event_count,
+                      auto_started as "auto_started!: bool",
+                      last_seen_at as "last_seen_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM error_tracker_issues
+               WHERE project_id = $1 AND fingerprint = $2"#,
+            project_id,
+            fingerprint
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        fingerprint: &str,
+        task_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ErrorTrackerIssue,
+            r#"INSERT INTO error_tracker_issues (project_id, fingerprint, task_id)
+               VALUES ($1, $2, $3)
+               RETURNING project_id as "project_id!: Uuid",
+                         fingerprint,
+                         task_id as "task_id!: Uuid",
+                         event_count,
+                         auto_started as "auto_started!: bool",
+                         last_seen_at as "last_seen_at!: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            project_id,
+            fingerprint,
+            task_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Count another occurrence of an issue that already has a task.
+    pub async fn record_event(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        fingerprint: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE error_tracker_issues
+               SET event_count = event_count + 1,
+                   last_seen_at = datetime('now', 'subsec')
+               WHERE project_id = $1 AND fingerprint = $2"#,
+            project_id,
+            fingerprint
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn mark_auto_started(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        fingerprint: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE error_tracker_issues
+               SET auto_started = TRUE
+               WHERE project_id = $1 AND fingerprint = $2"#,
+            project_id,
+            fingerprint
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Cost reported by the coding agents of auto-started attempts created since `since`.
+    pub async fn auto_start_cost_since(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<f64, sqlx::Error> {
+        let cost = sqlx::query_scalar!(
+            r#"SELECT COALESCE(SUM(cat.cost_usd), 0.0) as "cost!: f64"
+               FROM error_tracker_issues e
+               JOIN workspaces w ON w.task_id = e.task_id
+               JOIN sessions s ON s.workspace_id = w.id
+               JOIN execution_processes ep ON ep.session_id = s.id
+               JOIN coding_agent_turns cat ON cat.execution_process_id = ep.id
+               WHERE e.project_id = $1
+                 AND e.auto_started = TRUE
+                 AND w.created_at >= $2"#,
+            project_id,
+            since
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(cost)
+    }
+}
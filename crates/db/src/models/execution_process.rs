@@ -8,6 +8,7 @@ use serde_json::Value;
 use sqlx::{FromRow, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
+use utils::pagination::{Keyset, Paginate, SortField};
 use uuid::Uuid;
 
 use super::{
@@ -108,6 +109,82 @@ pub enum ExecutorActionField {
     Other(Value),
 }
 
+/// A running dev server together with the workspace/task/project it belongs to
+#[derive(Debug, Clone, FromRow)]
+pub struct RunningDevServer {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub branch: String,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub started_at: DateTime<Utc>,
+}
+
+/// A coding agent run with the workspace it belongs to, for executor statistics
+#[derive(Debug, Clone, FromRow)]
+pub struct CodingAgentRun {
+    pub workspace_id: Uuid,
+    pub executor_action: sqlx::types::Json<ExecutorActionField>,
+    pub status: ExecutionProcessStatus,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub cost_usd: Option<f64>,
+}
+
+/// Wall-clock span of a finished coding agent run, for time tracking
+#[derive(Debug, Clone, FromRow)]
+pub struct AgentRunTime {
+    pub task_id: Uuid,
+    pub workspace_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+}
+
+/// A run of an attempt with what its agent cost, for comparing attempts
+#[derive(Debug, Clone, FromRow)]
+pub struct AttemptRun {
+    pub run_reason: ExecutionProcessRunReason,
+    pub status: ExecutionProcessStatus,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub cost_usd: Option<f64>,
+}
+
+impl ExecutorActionField {
+    /// Profile of the coding agent the action runs, if it runs one
+    pub fn executor_profile_id(&self) -> Option<&ExecutorProfileId> {
+        match self {
+            ExecutorActionField::ExecutorAction(action) => match &action.typ {
+                ExecutorActionType::CodingAgentInitialRequest(request) => {
+                    Some(&request.executor_profile_id)
+                }
+                ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                    Some(&request.executor_profile_id)
+                }
+                _ => None,
+            },
+            ExecutorActionField::Other(_) => None,
+        }
+    }
+}
+
+impl AgentRunTime {
+    pub fn seconds(&self) -> f64 {
+        (self.completed_at - self.started_at)
+            .num_milliseconds()
+            .max(0) as f64
+            / 1000.0
+    }
+}
+
+impl CodingAgentRun {
+    pub fn executor_profile_id(&self) -> Option<&ExecutorProfileId> {
+        self.executor_action.0.executor_profile_id()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MissingBeforeContext {
     pub id: Uuid,
@@ -119,6 +196,19 @@ pub struct MissingBeforeContext {
     pub repo_path: Option<String>,
 }
 
+impl Paginate for ExecutionProcess {
+    fn page_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn sort_value(&self, field: SortField) -> DateTime<Utc> {
+        match field {
+            SortField::CreatedAt => self.created_at,
+            SortField::UpdatedAt => self.updated_at,
+        }
+    }
+}
+
 impl ExecutionProcess {
     /// Find execution process by ID
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
@@ -248,6 +338,77 @@ impl ExecutionProcess {
         .await
     }
 
+    /// One page of a workspace's execution processes across all of its sessions, sorted and cut
+    /// in SQL.
+    pub async fn find_page_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        show_soft_deleted: bool,
+        keyset: &Keyset,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let by_updated_at = keyset.by_updated_at();
+        let descending = keyset.descending();
+        let after_value = keyset.after_value();
+        let after_id = keyset.after_id();
+        let limit = keyset.fetch_limit();
+        sqlx::query_as!(
+            ExecutionProcess,
+            r#"SELECT
+                      ep.id              as "id!: Uuid",
+                      ep.session_id      as "session_id!: Uuid",
+                      ep.run_reason      as "run_reason!: ExecutionProcessRunReason",
+                      ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
+                      ep.status          as "status!: ExecutionProcessStatus",
+                      ep.exit_code,
+                      ep.dropped as "dropped!: bool",
+                      ep.started_at      as "started_at!: DateTime<Utc>",
+                      ep.completed_at    as "completed_at?: DateTime<Utc>",
+                      ep.created_at      as "created_at!: DateTime<Utc>",
+                      ep.updated_at      as "updated_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN sessions s ON s.id = ep.session_id
+               WHERE s.workspace_id = $1
+                 AND ($2 OR ep.dropped = FALSE)
+                 AND ($5 IS NULL OR CASE WHEN $4
+                   THEN (julianday(CASE WHEN $3 THEN ep.updated_at ELSE ep.created_at END), ep.id) < (julianday($5), $6)
+                   ELSE (julianday(CASE WHEN $3 THEN ep.updated_at ELSE ep.created_at END), ep.id) > (julianday($5), $6)
+                 END)
+               ORDER BY
+                 CASE WHEN $4 THEN julianday(CASE WHEN $3 THEN ep.updated_at ELSE ep.created_at END) END DESC,
+                 CASE WHEN $4 THEN ep.id END DESC,
+                 julianday(CASE WHEN $3 THEN ep.updated_at ELSE ep.created_at END) ASC,
+                 ep.id ASC
+               LIMIT $7"#,
+            workspace_id,
+            show_soft_deleted,
+            by_updated_at,
+            descending,
+            after_value,
+            after_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn count_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        show_soft_deleted: bool,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!: i64"
+               FROM execution_processes ep
+               JOIN sessions s ON s.id = ep.session_id
+               WHERE s.workspace_id = $1
+                 AND ($2 OR ep.dropped = FALSE)"#,
+            workspace_id,
+            show_soft_deleted
+        )
+        .fetch_one(pool)
+        .await
+    }
+
     /// Find running execution processes
     pub async fn find_running(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
@@ -292,6 +453,156 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Number of attempts of a project with a running process other than a dev server
+    pub async fn count_running_attempts_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(DISTINCT w.id) as "count!: i64"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               JOIN workspaces w ON s.workspace_id = w.id
+               JOIN tasks t ON w.task_id = t.id
+               WHERE ep.status = 'running'
+                 AND ep.run_reason != 'devserver'
+                 AND t.project_id = $1"#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Check if any attempt of a task has a running process other than a dev server
+    pub async fn has_running_non_dev_server_processes_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let count: i64 = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               JOIN workspaces w ON s.workspace_id = w.id
+               WHERE w.task_id = $1
+                 AND ep.status = 'running'
+                 AND ep.run_reason != 'devserver'"#,
+            task_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(count > 0)
+    }
+
+    /// Coding agent runs of attempts created since `since`, oldest first. Runs of code reviews
+    /// are left out.
+    pub async fn find_coding_agent_runs(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<CodingAgentRun>, sqlx::Error> {
+        sqlx::query_as!(
+            CodingAgentRun,
+            r#"SELECT s.workspace_id as "workspace_id!: Uuid",
+                      ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
+                      ep.status as "status!: ExecutionProcessStatus",
+                      ep.started_at as "started_at!: DateTime<Utc>",
+                      ep.completed_at as "completed_at?: DateTime<Utc>",
+                      cat.cost_usd as "cost_usd?: f64"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               JOIN workspaces w ON s.workspace_id = w.id
+               JOIN tasks t ON w.task_id = t.id
+               LEFT JOIN coding_agent_turns cat ON cat.execution_process_id = ep.id
+               WHERE ep.run_reason = 'codingagent'
+                 AND ep.dropped = FALSE
+                 AND ep.session_id NOT IN (SELECT session_id FROM reviews)
+                 AND ($1 IS NULL OR t.project_id = $1)
+                 AND w.created_at >= $2
+               ORDER BY ep.created_at ASC"#,
+            project_id,
+            since
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Finished coding agent runs of a task, dropped ones included since the time was spent.
+    pub async fn find_agent_run_times_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<AgentRunTime>, sqlx::Error> {
+        sqlx::query_as!(
+            AgentRunTime,
+            r#"SELECT w.task_id as "task_id!: Uuid",
+                      s.workspace_id as "workspace_id!: Uuid",
+                      ep.started_at as "started_at!: DateTime<Utc>",
+                      ep.completed_at as "completed_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               JOIN workspaces w ON s.workspace_id = w.id
+               WHERE ep.run_reason = 'codingagent'
+                 AND ep.completed_at IS NOT NULL
+                 AND w.task_id = $1
+               ORDER BY ep.started_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Runs of all sessions of an attempt other than dev servers, oldest first. Dropped runs are
+    /// included since their time and cost were spent.
+    pub async fn find_attempt_runs(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<AttemptRun>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptRun,
+            r#"SELECT ep.run_reason as "run_reason!: ExecutionProcessRunReason",
+                      ep.status as "status!: ExecutionProcessStatus",
+                      ep.started_at as "started_at!: DateTime<Utc>",
+                      ep.completed_at as "completed_at?: DateTime<Utc>",
+                      cat.cost_usd as "cost_usd?: f64"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               LEFT JOIN coding_agent_turns cat ON cat.execution_process_id = ep.id
+               WHERE s.workspace_id = $1
+                 AND ep.run_reason != 'devserver'
+               ORDER BY ep.started_at ASC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Finished coding agent runs on the project's tasks that started since `since`.
+    pub async fn find_agent_run_times_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AgentRunTime>, sqlx::Error> {
+        sqlx::query_as!(
+            AgentRunTime,
+            r#"SELECT w.task_id as "task_id!: Uuid",
+                      s.workspace_id as "workspace_id!: Uuid",
+                      ep.started_at as "started_at!: DateTime<Utc>",
+                      ep.completed_at as "completed_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               JOIN workspaces w ON s.workspace_id = w.id
+               JOIN tasks t ON w.task_id = t.id
+               WHERE ep.run_reason = 'codingagent'
+                 AND ep.completed_at IS NOT NULL
+                 AND t.project_id = $1
+                 AND ep.started_at >= $2
+               ORDER BY ep.started_at ASC"#,
+            project_id,
+            since
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Check if there are running processes (excluding dev servers) for a workspace (across all sessions)
     pub async fn has_running_non_dev_server_processes_for_workspace(
         pool: &SqlitePool,
@@ -344,6 +655,32 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Find all running dev servers across projects, with their owning context
+    pub async fn find_running_dev_servers_with_context(
+        pool: &SqlitePool,
+    ) -> Result<Vec<RunningDevServer>, sqlx::Error> {
+        sqlx::query_as!(
+            RunningDevServer,
+            r#"SELECT ep.id as "id!: Uuid",
+                      w.id as "workspace_id!: Uuid",
+                      w.branch,
+                      t.id as "task_id!: Uuid",
+                      t.title as task_title,
+                      p.id as "project_id!: Uuid",
+                      p.name as project_name,
+                      ep.started_at as "started_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               JOIN workspaces w ON s.workspace_id = w.id
+               JOIN tasks t ON w.task_id = t.id
+               JOIN projects p ON t.project_id = p.id
+               WHERE ep.status = 'running' AND ep.run_reason = 'devserver'
+               ORDER BY ep.started_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Find latest coding_agent_turn agent_session_id by session (simple scalar query)
     pub async fn find_latest_coding_agent_turn_session_id(
         pool: &SqlitePool,
@@ -545,6 +882,26 @@ impl ExecutionProcess {
         Ok(result.rows_affected() as i64)
     }
 
+    /// Soft-drop processes after the specified boundary (exclusive)
+    pub async fn drop_after(
+        pool: &SqlitePool,
+        session_id: Uuid,
+        boundary_process_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE execution_processes
+               SET dropped = TRUE
+             WHERE session_id = $1
+               AND created_at > (SELECT created_at FROM execution_processes WHERE id = $2)
+               AND dropped = FALSE"#,
+            session_id,
+            boundary_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() as i64)
+    }
+
     /// Find the previous process's after_head_commit before the given boundary process
     /// for a specific repository
     pub async fn find_prev_after_head_commit(
@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Hash of a dependency lockfile at the root of a repo.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+pub struct LockfileHash {
+    pub path: String,
+    pub sha256: String,
+}
+
+/// State of one of the attempt's repos when the process started.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct RepoEnvironment {
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    pub target_branch: String,
+    /// Merge base of the attempt branch and the target branch
+    pub base_commit: Option<String>,
+    pub head_commit: Option<String>,
+    pub lockfiles: Vec<LockfileHash>,
+}
+
+/// What an execution process ran with.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ExecutionProcessEnvironment {
+    pub execution_process_id: Uuid,
+    /// Coding agent, CLI and model; unset for scripts
+    pub executor: Option<String>,
+    pub cli_command: Option<String>,
+    /// Version pinned in the CLI command, when it pins one
+    pub cli_version: Option<String>,
+    /// Model the profile asks for; unset when left to the CLI's default
+    pub model: Option<String>,
+    pub os: String,
+    pub arch: String,
+    /// Version of vibe-kanban that started the process
+    pub app_version: String,
+    #[ts(type = "Array<RepoEnvironment>")]
+    pub repos: Json<Vec<RepoEnvironment>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateExecutionProcessEnvironment {
+    pub executor: Option<String>,
+    pub cli_command: Option<String>,
+    pub cli_version: Option<String>,
+    pub model: Option<String>,
+    pub os: String,
+    pub arch: String,
+    pub app_version: String,
+    pub repos: Vec<RepoEnvironment>,
+}
+
+impl ExecutionProcessEnvironment {
+    pub async fn create(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        data: &CreateExecutionProcessEnvironment,
+    ) -> Result<(), sqlx::Error> {
+        let repos = Json(&data.repos);
+        sqlx::query!(
+            r#"INSERT INTO execution_process_environments
+                   (execution_process_id, executor, cli_command, cli_version, model, os, arch,
+                    app_version, repos)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
+            execution_process_id,
+            data.executor,
+            data.cli_command,
+            data.cli_version,
+            data.model,
+            data.os,
+            data.arch,
+            data.app_version,
+            repos
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_by_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessEnvironment,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid",
+                      executor,
+                      cli_command,
+                      cli_version,
+                      model,
+                      os,
+                      arch,
+                      app_version,
+                      repos as "repos!: Json<Vec<RepoEnvironment>>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM execution_process_environments
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
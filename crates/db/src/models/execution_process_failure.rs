@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::Display;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Why an execution process failed, as a machine-readable code.
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Display)]
+#[sqlx(
+    type_name = "execution_process_failure_kind",
+    rename_all = "snake_case"
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ExecutionProcessFailureKind {
+    /// The provider rejected requests for exceeding a rate or usage limit
+    RateLimited,
+    /// The conversation no longer fits the model's context window
+    ContextOverflow,
+    /// The account ran out of credit or hit its spend limit
+    BudgetExhausted,
+    /// Not logged in, or the API key was rejected
+    AuthFailure,
+    /// Killed for running out of memory, or for exceeding its memory limit
+    OutOfMemory,
+    /// A script's test suite failed
+    TestFailure,
+    /// A merge or rebase stopped on conflicts
+    MergeConflict,
+    /// The CLI, or something it runs, isn't installed
+    CliMissing,
+    /// The CLI is too old or too new for the options it was given
+    VersionMismatch,
+    /// The CLI panicked or threw an unhandled error
+    CliCrash,
+    Unknown,
+}
+
+impl ExecutionProcessFailureKind {
+    /// Whether running the same attempt again is likely to get further
+    pub fn retryable(self) -> bool {
+        matches!(
+            self,
+            Self::RateLimited | Self::OutOfMemory | Self::CliCrash | Self::Unknown
+        )
+    }
+
+    /// Whether the next model of the profile's fallback chain may get further
+    pub fn falls_back_to_next_model(self) -> bool {
+        matches!(
+            self,
+            Self::RateLimited | Self::ContextOverflow | Self::BudgetExhausted
+        )
+    }
+
+    /// What the user can do about it
+    pub fn remediation(self) -> &'static str {
+        match self {
+            Self::RateLimited => "Wait for the provider's rate limit to reset, then retry.",
+            Self::ContextOverflow => {
+                "Start a fresh attempt, or split the task so it fits the model's context."
+            }
+            Self::BudgetExhausted => "Add credit or raise the spend limit of the agent's account.",
+            Self::AuthFailure => "Log in to the agent's CLI again or check its API key.",
+            Self::OutOfMemory => {
+                "Raise the memory limit or split the task into smaller pieces, then retry."
+            }
+            Self::TestFailure => "Check the failing tests in the log and fix them before retrying.",
+            Self::MergeConflict => "Resolve the conflicts in the worktree, or rebase the attempt.",
+            Self::CliMissing => "Install the agent's CLI and make sure it's on the PATH.",
+            Self::VersionMismatch => "Update the agent's CLI to a supported version.",
+            Self::CliCrash => "Retry the attempt; report the crash if it keeps happening.",
+            Self::Unknown => "Check the end of the process's stderr for details.",
+        }
+    }
+}
+
+/// What a failed execution process wrote to stderr before it exited.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ExecutionProcessFailure {
+    pub execution_process_id: Uuid,
+    pub kind: ExecutionProcessFailureKind,
+    /// Last lines written to stderr, oldest first
+    pub stderr_tail: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl ExecutionProcessFailure {
+    pub async fn create(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        kind: ExecutionProcessFailureKind,
+        stderr_tail: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO execution_process_failures (execution_process_id, kind, stderr_tail)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(execution_process_id) DO UPDATE
+               SET kind = excluded.kind, stderr_tail = excluded.stderr_tail"#,
+            execution_process_id,
+            kind,
+            stderr_tail
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_by_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessFailure,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid",
+                      kind as "kind!: ExecutionProcessFailureKind",
+                      stderr_tail,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM execution_process_failures
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Latest liveness sample of a running execution process.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ExecutionProcessHeartbeat {
+    pub execution_process_id: Uuid,
+    pub pid: Option<i64>,
+    /// Single-letter process state from the OS, e.g. R, S or Z
+    pub state: Option<String>,
+    /// Resident memory of the whole process group
+    pub rss_kb: Option<i64>,
+    /// CPU time used by the process group so far
+    pub cpu_seconds: Option<f64>,
+    #[ts(type = "Date")]
+    pub last_seen_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RecordHeartbeat {
+    pub pid: Option<i64>,
+    pub state: Option<String>,
+    pub rss_kb: Option<i64>,
+    pub cpu_seconds: Option<f64>,
+}
+
+impl ExecutionProcessHeartbeat {
+    pub async fn record(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        data: &RecordHeartbeat,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO execution_process_heartbeats
+                   (execution_process_id, pid, state, rss_kb, cpu_seconds)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT(execution_process_id) DO UPDATE
+               SET pid = excluded.pid,
+                   state = excluded.state,
+                   rss_kb = excluded.rss_kb,
+                   cpu_seconds = excluded.cpu_seconds,
+                   last_seen_at = datetime('now', 'subsec')"#,
+            execution_process_id,
+            data.pid,
+            data.state,
+            data.rss_kb,
+            data.cpu_seconds
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_by_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessHeartbeat,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid",
+                      pid,
+                      state,
+                      rss_kb,
+                      cpu_seconds,
+                      last_seen_at as "last_seen_at!: DateTime<Utc>"
+               FROM execution_process_heartbeats
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Running processes without a heartbeat for `stale_after_secs`, i.e. nobody is supervising
+    /// them any more
+    pub async fn find_stale_running(
+        pool: &SqlitePool,
+        stale_after_secs: i64,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT h.execution_process_id as "execution_process_id!: Uuid"
+               FROM execution_process_heartbeats h
+               JOIN execution_processes ep ON ep.id = h.execution_process_id
+               WHERE ep.status = 'running'
+                 AND h.last_seen_at < datetime('now', 'subsec', '-' || $1 || ' seconds')"#,
+            stale_after_secs
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
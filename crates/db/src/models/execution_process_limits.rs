@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use executors::limits::{ResourceLimit, ResourceLimits};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Resource limits an execution process ran with.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ExecutionProcessLimits {
+    pub execution_process_id: Uuid,
+    pub memory_mb: Option<i64>,
+    pub cpu_seconds: Option<i64>,
+    pub wall_clock_secs: Option<i64>,
+    /// Limit the process was killed for exceeding
+    pub exceeded: Option<ResourceLimit>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ExecutionProcessLimits {
+    pub fn limits(&self) -> ResourceLimits {
+        ResourceLimits {
+            memory_mb: self.memory_mb.map(|v| v as u64),
+            cpu_seconds: self.cpu_seconds.map(|v| v as u64),
+            wall_clock_secs: self.wall_clock_secs.map(|v| v as u64),
+        }
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        limits: &ResourceLimits,
+    ) -> Result<(), sqlx::Error> {
+        let memory_mb = limits.memory_mb.map(|v| v as i64);
+        let cpu_seconds = limits.cpu_seconds.map(|v| v as i64);
+        let wall_clock_secs = limits.wall_clock_secs.map(|v| v as i64);
+        sqlx::query!(
+            r#"INSERT INTO execution_process_limits
+                   (execution_process_id, memory_mb, cpu_seconds, wall_clock_secs)
+               VALUES ($1, $2, $3, $4)"#,
+            execution_process_id,
+            memory_mb,
+            cpu_seconds,
+            wall_clock_secs
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_by_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessLimits,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid",
+                      memory_mb,
+                      cpu_seconds,
+                      wall_clock_secs,
+                      exceeded as "exceeded: ResourceLimit",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM execution_process_limits
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn set_exceeded(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        limit: ResourceLimit,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE execution_process_limits
+               SET exceeded = $2, updated_at = datetime('now', 'subsec')
+               WHERE execution_process_id = $1"#,
+            execution_process_id,
+            limit
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
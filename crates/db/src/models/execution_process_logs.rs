@@ -35,6 +35,19 @@ impl ExecutionProcessLogs {
         .await
     }
 
+    /// Number of stored log chunks and their total size, which grow with every append
+    pub async fn version(pool: &SqlitePool, execution_id: Uuid) -> Result<(i64, i64), sqlx::Error> {
+        let rec = sqlx::query!(
+            r#"SELECT COUNT(*) AS "chunks!: i64", COALESCE(SUM(byte_size), 0) AS "bytes!: i64"
+               FROM execution_process_logs
+               WHERE execution_id = $1"#,
+            execution_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok((rec.chunks, rec.bytes))
+    }
+
     /// Parse JSONL logs back into Vec<LogMsg>
     pub fn parse_logs(records: &[Self]) -> Result<Vec<LogMsg>, serde_json::Error> {
         let mut messages = Vec::new();
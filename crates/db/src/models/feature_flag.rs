@@ -0,0 +1,204 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Capabilities that can be switched off per instance or per project without a rebuild.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, TS, EnumString, Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum FeatureFlag {
+    /// Second coding agent reviewing finished attempts
+    AutoReview,
+    /// Project lifecycle hooks around attempts and merges
+    LifecycleHooks,
+    /// Signed public read-only links to tasks and attempts
+    PublicShareLinks,
+    /// Repository `AGENTS.md` or `.vibe/instructions.md` appended to agent prompts
+    RepoInstructions,
+    /// Agents told how to hand a blocked attempt back with a structured note
+    HumanHandoff,
+    /// Screenshot of the dev server after each successful attempt
+    DevServerScreenshots,
+}
+
+impl FeatureFlag {
+    pub const ALL: [FeatureFlag; 6] = [
+        FeatureFlag::AutoReview,
+        FeatureFlag::LifecycleHooks,
+        FeatureFlag::PublicShareLinks,
+        FeatureFlag::RepoInstructions,
+        FeatureFlag::HumanHandoff,
+        FeatureFlag::DevServerScreenshots,
+    ];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            FeatureFlag::AutoReview => "Second coding agent reviewing finished attempts",
+            FeatureFlag::LifecycleHooks => "Project lifecycle hooks around attempts and merges",
+            FeatureFlag::PublicShareLinks => "Signed public read-only links to tasks and attempts",
+            FeatureFlag::RepoInstructions => {
+                "Repository AGENTS.md or .vibe/instructions.md appended to agent prompts"
+            }
+            FeatureFlag::HumanHandoff => {
+                "Agents told how to hand a blocked attempt back with a structured note"
+            }
+            FeatureFlag::DevServerScreenshots => {
+                "Screenshot of the dev server after each successful attempt"
+            }
+        }
+    }
+
+    /// Value used when no override is stored.
+    pub fn default_enabled(&self) -> bool {
+        true
+    }
+
+    /// Whether the flag is on for a project (or the instance when `project_id` is `None`).
+    /// A project override wins over the instance override, which wins over the default.
+    /// Lookup failures fall back to the default.
+    pub async fn is_enabled(self, pool: &SqlitePool, project_id: Option<Uuid>) -> bool {
+        let flag = self.to_string();
+        let stored = sqlx::query_scalar!(
+            r#"SELECT enabled as "enabled!: bool"
+               FROM feature_flags
+               WHERE flag = $1 AND (project_id IS NULL OR project_id = $2)
+               ORDER BY project_id IS NULL
+               LIMIT 1"#,
+            flag,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await;
+        match stored {
+            Ok(stored) => stored.unwrap_or_else(|| self.default_enabled()),
+            Err(e) => {
+                tracing::warn!("Failed to read feature flag {}: {}", self, e);
+                self.default_enabled()
+            }
+        }
+    }
+}
+
+/// A stored override of a flag's default.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct FeatureFlagOverride {
+    pub flag: String,
+    pub project_id: Option<Uuid>,
+    pub enabled: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct SetFeatureFlag {
+    pub enabled: bool,
+    /// Override for a single project instead of the whole instance
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+}
+
+impl FeatureFlagOverride {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            FeatureFlagOverride,
+            r#"SELECT flag,
+                      project_id as "project_id: Uuid",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM feature_flags
+               ORDER BY flag ASC, project_id IS NOT NULL, created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            FeatureFlagOverride,
+            r#"SELECT flag,
+                      project_id as "project_id: Uuid",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM feature_flags
+               WHERE project_id = $1
+               ORDER BY flag ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn set(
+        pool: &SqlitePool,
+        flag: FeatureFlag,
+        project_id: Option<Uuid>,
+        enabled: bool,
+    ) -> Result<Self, sqlx::Error> {
+        let flag = flag.to_string();
+        let mut tx = pool.begin().await?;
+        let existing = sqlx::query_scalar!(
+            r#"SELECT created_at as "created_at!: DateTime<Utc>"
+               FROM feature_flags
+               WHERE flag = $1 AND project_id IS $2"#,
+            flag,
+            project_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "DELETE FROM feature_flags WHERE flag = $1 AND project_id IS $2",
+            flag,
+            project_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        let created_at = existing.unwrap_or_else(Utc::now);
+        let row = sqlx::query_as!(
+            FeatureFlagOverride,
+            r#"INSERT INTO feature_flags (flag, project_id, enabled, created_at)
+               VALUES ($1, $2, $3, $4)
+               RETURNING flag,
+                         project_id as "project_id: Uuid",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            flag,
+            project_id,
+            enabled,
+            created_at
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(row)
+    }
+
+    /// Remove an override so the flag falls back to the instance value or default.
+    pub async fn clear(
+        pool: &SqlitePool,
+        flag: FeatureFlag,
+        project_id: Option<Uuid>,
+    ) -> Result<u64, sqlx::Error> {
+        let flag = flag.to_string();
+        let result = sqlx::query!(
+            "DELETE FROM feature_flags WHERE flag = $1 AND project_id IS $2",
+            flag,
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
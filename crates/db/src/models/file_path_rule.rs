@@ -0,0 +1,148 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, TS)]
+#[sqlx(type_name = "path_rule_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PathRuleAction {
+    Allow,
+    /// Leave the edit to a reviewer even if an allow rule matches too
+    Ask,
+    Deny,
+}
+
+/// Approves, holds or rejects agent edits to the files matching a glob without waiting for a
+/// reviewer.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct FilePathRule {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// Glob such as `src/**` or `.env*`; patterns without a `/` match file names anywhere
+    pub pattern: String,
+    pub action: PathRuleAction,
+    pub enabled: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateFilePathRule {
+    pub pattern: String,
+    pub action: PathRuleAction,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateFilePathRule {
+    pub pattern: Option<String>,
+    pub action: Option<PathRuleAction>,
+    pub enabled: Option<bool>,
+}
+
+impl FilePathRule {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            FilePathRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      pattern,
+                      action as "action!: PathRuleAction",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM file_path_rules
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            FilePathRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      pattern,
+                      action as "action!: PathRuleAction",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM file_path_rules
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateFilePathRule,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            FilePathRule,
+            r#"INSERT INTO file_path_rules (id, project_id, pattern, action)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         pattern,
+                         action as "action!: PathRuleAction",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.pattern,
+            data.action
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        &self,
+        pool: &SqlitePool,
+        data: &UpdateFilePathRule,
+    ) -> Result<Self, sqlx::Error> {
+        let pattern = data.pattern.as_ref().unwrap_or(&self.pattern);
+        let action = data.action.unwrap_or(self.action);
+        let enabled = data.enabled.unwrap_or(self.enabled);
+        sqlx::query_as!(
+            FilePathRule,
+            r#"UPDATE file_path_rules
+               SET pattern = $2, action = $3, enabled = $4, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         pattern,
+                         action as "action!: PathRuleAction",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            self.id,
+            pattern,
+            action,
+            enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM file_path_rules WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
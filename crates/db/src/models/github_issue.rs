@@ -0,0 +1,215 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// GitHub repository whose issues become tasks of a project.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct GitHubIssueSync {
+    pub project_id: Uuid,
+    /// `owner/name` of the repository
+    pub repo_full_name: String,
+    /// Only issues carrying this label become tasks; every issue when unset
+    pub label_filter: Option<String>,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub webhook_secret: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A task created from a GitHub issue.
+#[derive(Debug, Clone, FromRow)]
+pub struct GitHubIssueLink {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub repo_full_name: String,
+    pub issue_number: i64,
+    pub issue_url: String,
+    /// Issue state last received from or pushed to GitHub
+    pub issue_open: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl GitHubIssueSync {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueSync,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      repo_full_name,
+                      label_filter,
+                      webhook_secret,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM github_issue_syncs
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Create or replace the project's sync settings. `webhook_secret` must already be encrypted.
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        repo_full_name: &str,
+        label_filter: Option<&str>,
+        webhook_secret: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueSync,
+            r#"INSERT INTO github_issue_syncs
+                   (project_id, repo_full_name, label_filter, webhook_secret)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   repo_full_name = excluded.repo_full_name,
+                   label_filter = excluded.label_filter,
+                   webhook_secret = excluded.webhook_secret,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         repo_full_name,
+                         label_filter,
+                         webhook_secret,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            repo_full_name,
+            label_filter,
+            webhook_secret
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM github_issue_syncs WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl GitHubIssueLink {
+    pub async fn find_by_issue(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        repo_full_name: &str,
+        issue_number: i64,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueLink,
+            r#"SELECT task_id as "task_id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      repo_full_name,
+                      issue_number,
+                      issue_url,
+                      issue_open as "issue_open!: bool",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM github_issue_links
+               WHERE project_id = $1 AND repo_full_name = $2 AND issue_number = $3"#,
+            project_id,
+            repo_full_name,
+            issue_number
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueLink,
+            r#"SELECT task_id as "task_id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      repo_full_name,
+                      issue_number,
+                      issue_url,
+                      issue_open as "issue_open!: bool",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM github_issue_links
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Links whose issue state disagrees with their task: done or cancelled tasks with an open
+    /// issue, and other tasks with a closed one.
+    pub async fn find_out_of_sync(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueLink,
+            r#"SELECT l.task_id as "task_id!: Uuid",
+                      l.project_id as "project_id!: Uuid",
+                      l.repo_full_name,
+                      l.issue_number,
+                      l.issue_url,
+                      l.issue_open as "issue_open!: bool",
+                      l.created_at as "created_at!: DateTime<Utc>"
+               FROM github_issue_links l
+               JOIN tasks t ON t.id = l.task_id
+               WHERE l.issue_open = (t.status IN ('done', 'cancelled'))"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        project_id: Uuid,
+        repo_full_name: &str,
+        issue_number: i64,
+        issue_url: &str,
+        issue_open: bool,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueLink,
+            r#"INSERT INTO github_issue_links
+                   (task_id, project_id, repo_full_name, issue_number, issue_url, issue_open)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING task_id as "task_id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         repo_full_name,
+                         issue_number,
+                         issue_url,
+                         issue_open as "issue_open!: bool",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            task_id,
+            project_id,
+            repo_full_name,
+            issue_number,
+            issue_url,
+            issue_open
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_issue_open(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        issue_open: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE github_issue_links SET issue_open = $2 WHERE task_id = $1",
+            task_id,
+            issue_open
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
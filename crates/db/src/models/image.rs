@@ -169,7 +169,8 @@ impl Image {
                       i.updated_at as "updated_at!: DateTime<Utc>"
                FROM images i
                LEFT JOIN task_images ti ON i.id = ti.image_id
-               WHERE ti.task_id IS NULL"#
+               WHERE ti.task_id IS NULL
+                 AND NOT EXISTS (SELECT 1 FROM attempt_screenshots s WHERE s.image_id = i.id)"#
         )
         .fetch_all(pool)
         .await
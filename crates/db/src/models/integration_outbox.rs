@@ -0,0 +1,202 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display)]
+#[sqlx(type_name = "outbox_integration", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum OutboxIntegration {
+    Github,
+    Slack,
+}
+
+/// Integration action waiting for its service to become reachable again.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct IntegrationOutboxEntry {
+    pub id: Uuid,
+    pub integration: OutboxIntegration,
+    /// Identifies what the action is about; a newer action for the same key replaces this one
+    pub dedupe_key: String,
+    #[ts(type = "unknown")]
+    pub payload: Json<Value>,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    #[ts(type = "Date")]
+    pub next_attempt_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl IntegrationOutboxEntry {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            IntegrationOutboxEntry,
+            r#"SELECT id as "id!: Uuid",
+                      integration as "integration!: OutboxIntegration",
+                      dedupe_key,
+                      payload as "payload!: Json<Value>",
+                      attempts,
+                      last_error,
+                      next_attempt_at as "next_attempt_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM integration_outbox
+               ORDER BY created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            IntegrationOutboxEntry,
+            r#"SELECT id as "id!: Uuid",
+                      integration as "integration!: OutboxIntegration",
+                      dedupe_key,
+                      payload as "payload!: Json<Value>",
+                      attempts,
+                      last_error,
+                      next_attempt_at as "next_attempt_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM integration_outbox
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Entries whose next attempt is due and that haven't run out of attempts
+    pub async fn find_due(
+        pool: &SqlitePool,
+        max_attempts: i64,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            IntegrationOutboxEntry,
+            r#"SELECT id as "id!: Uuid",
+                      integration as "integration!: OutboxIntegration",
+                      dedupe_key,
+                      payload as "payload!: Json<Value>",
+                      attempts,
+                      last_error,
+                      next_attempt_at as "next_attempt_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM integration_outbox
+               WHERE next_attempt_at <= datetime('now', 'subsec') AND attempts < $1
+               ORDER BY created_at ASC
+               LIMIT $2"#,
+            max_attempts,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn exists_for_key(pool: &SqlitePool, dedupe_key: &str) -> Result<bool, sqlx::Error> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64" FROM integration_outbox WHERE dedupe_key = $1"#,
+            dedupe_key
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(count > 0)
+    }
+
+    /// Queue an action, replacing any pending one with the same key. `first_error` is why it
+    /// couldn't be delivered straight away.
+    pub async fn enqueue(
+        pool: &SqlitePool,
+        integration: OutboxIntegration,
+        dedupe_key: &str,
+        payload: &Value,
+        first_error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let payload = Json(payload);
+        sqlx::query_as!(
+            IntegrationOutboxEntry,
+            r#"INSERT INTO integration_outbox
+                   (id, integration, dedupe_key, payload, attempts, last_error, next_attempt_at)
+               VALUES ($1, $2, $3, $4, 1, $5, $6)
+               ON CONFLICT(dedupe_key) DO UPDATE SET
+                   integration = excluded.integration,
+                   payload = excluded.payload,
+                   attempts = 1,
+                   last_error = excluded.last_error,
+                   next_attempt_at = excluded.next_attempt_at,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid",
+                         integration as "integration!: OutboxIntegration",
+                         dedupe_key,
+                         payload as "payload!: Json<Value>",
+                         attempts,
+                         last_error,
+                         next_attempt_at as "next_attempt_at!: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            integration,
+            dedupe_key,
+            payload,
+            first_error,
+            next_attempt_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn record_failure(
+        pool: &SqlitePool,
+        id: Uuid,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE integration_outbox
+               SET attempts = attempts + 1,
+                   last_error = $2,
+                   next_attempt_at = $3,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            error,
+            next_attempt_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Make an entry due now with a fresh set of attempts
+    pub async fn reset(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE integration_outbox
+               SET attempts = 0,
+                   next_attempt_at = datetime('now', 'subsec'),
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM integration_outbox WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
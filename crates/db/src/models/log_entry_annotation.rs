@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A bookmark on an entry of an execution process's normalized log, optionally with a note.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct LogEntryAnnotation {
+    pub id: Uuid,
+    pub execution_process_id: Uuid,
+    /// Index of the entry in the normalized log
+    #[ts(type = "number")]
+    pub entry_index: i64,
+    /// Unset for plain bookmarks
+    pub note: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateLogEntryAnnotation {
+    #[ts(type = "number")]
+    pub entry_index: i64,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateLogEntryAnnotation {
+    /// An empty note turns the annotation back into a plain bookmark
+    pub note: Option<String>,
+}
+
+/// Blank notes are stored as plain bookmarks
+fn normalize_note(note: Option<&str>) -> Option<&str> {
+    note.map(str::trim).filter(|note| !note.is_empty())
+}
+
+impl LogEntryAnnotation {
+    /// Annotations of a process in log order
+    pub async fn find_by_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            LogEntryAnnotation,
+            r#"SELECT id as "id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      entry_index as "entry_index!: i64",
+                      note,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM log_entry_annotations
+               WHERE execution_process_id = $1
+               ORDER BY entry_index ASC, created_at ASC"#,
+            execution_process_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            LogEntryAnnotation,
+            r#"SELECT id as "id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      entry_index as "entry_index!: i64",
+                      note,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM log_entry_annotations
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        data: &CreateLogEntryAnnotation,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let note = normalize_note(data.note.as_deref());
+        sqlx::query_as!(
+            LogEntryAnnotation,
+            r#"INSERT INTO log_entry_annotations (id, execution_process_id, entry_index, note)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         execution_process_id as "execution_process_id!: Uuid",
+                         entry_index as "entry_index!: i64",
+                         note,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            execution_process_id,
+            data.entry_index,
+            note
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// A blank note turns the annotation back into a plain bookmark
+    pub async fn update(
+        &self,
+        pool: &SqlitePool,
+        data: &UpdateLogEntryAnnotation,
+    ) -> Result<Self, sqlx::Error> {
+        let note = match &data.note {
+            Some(note) => normalize_note(Some(note)),
+            None => self.note.as_deref(),
+        };
+        sqlx::query_as!(
+            LogEntryAnnotation,
+            r#"UPDATE log_entry_annotations
+               SET note = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         execution_process_id as "execution_process_id!: Uuid",
+                         entry_index as "entry_index!: i64",
+                         note,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            self.id,
+            note
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM log_entry_annotations WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
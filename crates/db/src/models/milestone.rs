@@ -0,0 +1,230 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+/// A release or other goal grouping tasks of a project.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct Milestone {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    #[ts(type = "string | null")]
+    pub target_date: Option<NaiveDate>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Used both to create a milestone and to replace every field of one.
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateMilestone {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    #[ts(type = "string | null")]
+    pub target_date: Option<NaiveDate>,
+}
+
+/// A task of a milestone with what its agents cost so far.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct MilestoneTask {
+    pub task_id: Uuid,
+    pub title: String,
+    pub status: TaskStatus,
+    /// Cost reported by the coding agents of all the task's attempts
+    pub cost_usd: f64,
+}
+
+impl Milestone {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Milestone,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      title,
+                      description,
+                      target_date as "target_date: NaiveDate",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM milestones
+               WHERE project_id = $1
+               ORDER BY target_date IS NULL, target_date ASC, created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Milestone,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      title,
+                      description,
+                      target_date as "target_date: NaiveDate",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM milestones
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateMilestone,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            Milestone,
+            r#"INSERT INTO milestones (id, project_id, title, description, target_date)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         title,
+                         description,
+                         target_date as "target_date: NaiveDate",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.title,
+            data.description,
+            data.target_date
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &CreateMilestone,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Milestone,
+            r#"UPDATE milestones
+               SET title = $2, description = $3, target_date = $4,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         title,
+                         description,
+                         target_date as "target_date: NaiveDate",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.title,
+            data.description,
+            data.target_date
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM milestones WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Put a task in the milestone, taking it out of any other one.
+    pub async fn add_task(pool: &SqlitePool, id: Uuid, task_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO milestone_tasks (task_id, milestone_id)
+               VALUES ($1, $2)
+               ON CONFLICT(task_id) DO UPDATE SET
+                   milestone_id = excluded.milestone_id,
+                   added_at = datetime('now', 'subsec')"#,
+            task_id,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn remove_task(
+        pool: &SqlitePool,
+        id: Uuid,
+        task_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM milestone_tasks WHERE milestone_id = $1 AND task_id = $2",
+            id,
+            task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn find_tasks(
+        pool: &SqlitePool,
+        id: Uuid,
+    ) -> Result<Vec<MilestoneTask>, sqlx::Error> {
+        sqlx::query_as!(
+            MilestoneTask,
+            r#"SELECT t.id as "task_id!: Uuid",
+                      t.title,
+                      t.status as "status!: TaskStatus",
+                      COALESCE((
+                          SELECT SUM(cat.cost_usd)
+                          FROM workspaces w
+                          JOIN sessions s ON s.workspace_id = w.id
+                          JOIN execution_processes ep ON ep.session_id = s.id
+                          JOIN coding_agent_turns cat ON cat.execution_process_id = ep.id
+                          WHERE w.task_id = t.id
+                      ), 0.0) as "cost_usd!: f64"
+               FROM milestone_tasks mt
+               JOIN tasks t ON t.id = mt.task_id
+               WHERE mt.milestone_id = $1
+               ORDER BY t.created_at ASC"#,
+            id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Average agent cost of the project's done tasks that reported a cost, the basis for
+    /// estimating what open tasks will still cost.
+    pub async fn average_done_task_cost(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<f64>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT AVG(task_cost) as "average: f64"
+               FROM (
+                   SELECT SUM(cat.cost_usd) as task_cost
+                   FROM tasks t
+                   JOIN workspaces w ON w.task_id = t.id
+                   JOIN sessions s ON s.workspace_id = w.id
+                   JOIN execution_processes ep ON ep.session_id = s.id
+                   JOIN coding_agent_turns cat ON cat.execution_process_id = ep.id
+                   WHERE t.project_id = $1
+                     AND t.status = 'done'
+                     AND cat.cost_usd IS NOT NULL
+                   GROUP BY t.id
+               )"#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -1,15 +1,74 @@
+pub mod agent_question;
+pub mod agent_request_response;
+pub mod api_token;
+pub mod attempt_checkpoint;
+pub mod attempt_e2e_run;
+pub mod attempt_handoff;
+pub mod attempt_lint_annotation;
+pub mod attempt_review;
+pub mod attempt_screenshot;
+pub mod automation_rule;
+pub mod bash_command_rule;
 pub mod coding_agent_turn;
+pub mod error_tracker;
 pub mod execution_process;
+pub mod execution_process_environment;
+pub mod execution_process_failure;
+pub mod execution_process_heartbeat;
+pub mod execution_process_limits;
 pub mod execution_process_logs;
 pub mod execution_process_repo_state;
+pub mod feature_flag;
+pub mod file_path_rule;
+pub mod github_issue;
 pub mod image;
+pub mod integration_outbox;
+pub mod log_entry_annotation;
 pub mod merge;
+pub mod milestone;
+pub mod notification;
 pub mod project;
+pub mod project_auto_start;
+pub mod project_env_var;
+pub mod project_event_webhook;
+pub mod project_lifecycle_hook;
+pub mod project_linter;
+pub mod project_mcp_server;
+pub mod project_permission_policy;
 pub mod project_repo;
+pub mod project_sandbox;
+pub mod project_template;
+pub mod project_webhook;
+pub mod prompt_template;
+pub mod public_share_link;
+pub mod question_auto_answer_rule;
+pub mod question_escalation;
+pub mod redis_task_source;
 pub mod repo;
+pub mod review;
+pub mod review_checklist;
+pub mod review_sla_reminder;
 pub mod scratch;
+pub mod secret;
 pub mod session;
+pub mod slack;
+pub mod stale_task_flag;
 pub mod tag;
 pub mod task;
+pub mod task_assignee;
+pub mod task_attachment;
+pub mod task_label;
+pub mod task_prompt;
+pub mod task_status_change;
+pub mod task_time_entry;
+pub mod task_transition_rule;
+pub mod team;
+pub mod tool_usage;
+pub mod user;
+pub mod user_identity;
+pub mod user_push_settings;
+pub mod user_session;
 pub mod workspace;
+pub mod workspace_base_watch;
 pub mod workspace_repo;
+pub mod workspace_summary;
@@ -0,0 +1,152 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Number of most recent notifications kept; older ones are pruned on insert
+const MAX_NOTIFICATIONS: i64 = 500;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "notification_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    ExecutionCompleted,
+    ExecutionFailed,
+    QuestionPending,
+    ApprovalPending,
+    ReviewSlaBreached,
+    TaskStale,
+    Automation,
+    AgentBlocked,
+    RebaseConflict,
+}
+
+/// An entry in the in-app notification center.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct Notification {
+    pub id: Uuid,
+    pub kind: NotificationKind,
+    pub title: String,
+    pub message: String,
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    #[ts(type = "Date | null")]
+    pub read_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateNotification {
+    pub kind: NotificationKind,
+    pub title: String,
+    pub message: String,
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+}
+
+impl Notification {
+    pub async fn create(pool: &SqlitePool, data: &CreateNotification) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let notification = sqlx::query_as!(
+            Notification,
+            r#"INSERT INTO notifications (id, kind, title, message, project_id, task_id)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         kind as "kind!: NotificationKind",
+                         title,
+                         message,
+                         project_id as "project_id: Uuid",
+                         task_id as "task_id: Uuid",
+                         read_at as "read_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            data.kind,
+            data.title,
+            data.message,
+            data.project_id,
+            data.task_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"DELETE FROM notifications
+               WHERE id NOT IN (
+                   SELECT id FROM notifications ORDER BY created_at DESC LIMIT $1
+               )"#,
+            MAX_NOTIFICATIONS
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(notification)
+    }
+
+    /// Most recent notifications first.
+    pub async fn find_recent(
+        pool: &SqlitePool,
+        unread_only: bool,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Notification,
+            r#"SELECT id as "id!: Uuid",
+                      kind as "kind!: NotificationKind",
+                      title,
+                      message,
+                      project_id as "project_id: Uuid",
+                      task_id as "task_id: Uuid",
+                      read_at as "read_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM notifications
+               WHERE ($1 = FALSE OR read_at IS NULL)
+               ORDER BY created_at DESC
+               LIMIT $2"#,
+            unread_only,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn count_unread(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64" FROM notifications WHERE read_at IS NULL"#
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Returns false when no notification has the given id.
+    pub async fn mark_read(pool: &SqlitePool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE notifications
+               SET read_at = COALESCE(read_at, datetime('now', 'subsec'))
+               WHERE id = $1"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn mark_all_read(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE notifications
+               SET read_at = datetime('now', 'subsec')
+               WHERE read_at IS NULL"#
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete_all(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(r#"DELETE FROM notifications"#)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
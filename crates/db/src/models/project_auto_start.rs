@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+/// Starts an attempt with the default executor when a task of the project enters a column.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ProjectAutoStart {
+    pub project_id: Uuid,
+    /// Column that starts an attempt when a task is dropped into it
+    pub trigger_status: TaskStatus,
+    /// Attempts of the project that may run at once; further auto-starts are skipped
+    #[ts(type = "number")]
+    pub max_concurrent_attempts: i64,
+    pub enabled: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpsertProjectAutoStart {
+    pub trigger_status: TaskStatus,
+    #[ts(type = "number")]
+    pub max_concurrent_attempts: i64,
+    pub enabled: bool,
+}
+
+impl ProjectAutoStart {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectAutoStart,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      trigger_status as "trigger_status!: TaskStatus",
+                      max_concurrent_attempts as "max_concurrent_attempts!: i64",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_auto_start
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &UpsertProjectAutoStart,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectAutoStart,
+            r#"INSERT INTO project_auto_start
+                   (project_id, trigger_status, max_concurrent_attempts, enabled)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   trigger_status = excluded.trigger_status,
+                   max_concurrent_attempts = excluded.max_concurrent_attempts,
+                   enabled = excluded.enabled,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         trigger_status as "trigger_status!: TaskStatus",
+                         max_concurrent_attempts as "max_concurrent_attempts!: i64",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            data.trigger_status,
+            data.max_concurrent_attempts,
+            data.enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_auto_start WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
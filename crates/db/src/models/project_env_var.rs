@@ -0,0 +1,169 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A project-scoped environment variable. When `is_secret` is set, `value`
+/// holds the encrypted payload rather than the plaintext.
+#[derive(Debug, Clone, FromRow)]
+pub struct ProjectEnvVar {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub key: String,
+    pub value: String,
+    pub is_secret: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// API representation of a project env var. Secret values are never returned.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ProjectEnvVarResponse {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub key: String,
+    pub value: Option<String>,
+    pub is_secret: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ProjectEnvVar> for ProjectEnvVarResponse {
+    fn from(var: ProjectEnvVar) -> Self {
+        Self {
+            id: var.id,
+            project_id: var.project_id,
+            key: var.key,
+            value: (!var.is_secret).then_some(var.value),
+            is_secret: var.is_secret,
+            created_at: var.created_at,
+            updated_at: var.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateProjectEnvVar {
+    pub key: String,
+    pub value: String,
+    #[serde(default)]
+    pub is_secret: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectEnvVar {
+    pub key: Option<String>,
+    pub value: Option<String>,
+    pub is_secret: Option<bool>,
+}
+
+impl ProjectEnvVar {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectEnvVar,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      key,
+                      value,
+                      is_secret as "is_secret!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_env_vars
+               WHERE project_id = $1
+               ORDER BY key ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectEnvVar,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      key,
+                      value,
+                      is_secret as "is_secret!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_env_vars
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Insert a new variable. `value` must already be encrypted for secrets.
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        key: &str,
+        value: &str,
+        is_secret: bool,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            ProjectEnvVar,
+            r#"INSERT INTO project_env_vars (id, project_id, key, value, is_secret)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         key,
+                         value,
+                         is_secret as "is_secret!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            key,
+            value,
+            is_secret
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Update a variable. `value` must already be encrypted for secrets.
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        key: &str,
+        value: &str,
+        is_secret: bool,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectEnvVar,
+            r#"UPDATE project_env_vars
+               SET key = $2, value = $3, is_secret = $4, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         key,
+                         value,
+                         is_secret as "is_secret!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            key,
+            value,
+            is_secret
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM project_env_vars WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,174 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Events an outgoing webhook can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum EventWebhookKind {
+    ApprovalPending,
+    ApprovalResolved,
+    QuestionPending,
+    QuestionResolved,
+}
+
+/// Outgoing webhook of a project, called when approvals and questions open or resolve.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ProjectEventWebhook {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub url: String,
+    /// Encrypted signing secret
+    #[serde(skip)]
+    #[ts(skip)]
+    pub secret: Option<String>,
+    #[ts(type = "Array<EventWebhookKind>")]
+    pub events: Json<Vec<EventWebhookKind>>,
+    pub enabled: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateProjectEventWebhook {
+    pub url: String,
+    /// Deliveries carry an `X-Vibe-Signature` HMAC of the body when set
+    #[serde(default)]
+    pub secret: Option<String>,
+    pub events: Vec<EventWebhookKind>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectEventWebhook {
+    pub url: Option<String>,
+    /// An empty string removes the secret
+    pub secret: Option<String>,
+    pub events: Option<Vec<EventWebhookKind>>,
+    pub enabled: Option<bool>,
+}
+
+impl ProjectEventWebhook {
+    pub fn subscribes_to(&self, kind: EventWebhookKind) -> bool {
+        self.enabled && self.events.0.contains(&kind)
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectEventWebhook,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      url,
+                      secret,
+                      events as "events!: Json<Vec<EventWebhookKind>>",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_event_webhooks
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectEventWebhook,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      url,
+                      secret,
+                      events as "events!: Json<Vec<EventWebhookKind>>",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_event_webhooks
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// `secret` is stored as given and should already be encrypted.
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        url: &str,
+        secret: Option<&str>,
+        events: &[EventWebhookKind],
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let events = Json(events);
+        sqlx::query_as!(
+            ProjectEventWebhook,
+            r#"INSERT INTO project_event_webhooks (id, project_id, url, secret, events)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         url,
+                         secret,
+                         events as "events!: Json<Vec<EventWebhookKind>>",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            url,
+            secret,
+            events
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Replace every field; `secret` should already be encrypted.
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        url: &str,
+        secret: Option<&str>,
+        events: &[EventWebhookKind],
+        enabled: bool,
+    ) -> Result<Self, sqlx::Error> {
+        let events = Json(events);
+        sqlx::query_as!(
+            ProjectEventWebhook,
+            r#"UPDATE project_event_webhooks
+               SET url = $2, secret = $3, events = $4, enabled = $5,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         url,
+                         secret,
+                         events as "events!: Json<Vec<EventWebhookKind>>",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            url,
+            secret,
+            events,
+            enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM project_event_webhooks WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
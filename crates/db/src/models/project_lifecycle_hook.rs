@@ -0,0 +1,141 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(
+    Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, Hash, TS, EnumString, Display,
+)]
+#[sqlx(type_name = "lifecycle_hook", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum LifecycleHook {
+    /// Before the setup scripts of a new attempt
+    WorktreeCreated,
+    /// After the setup scripts, right before the coding agent starts
+    AttemptStarted,
+    /// After the coding agent and cleanup scripts of each run
+    AttemptFinished,
+    /// Before an attempt is merged into its target branch
+    BeforeMerge,
+    /// After an attempt has been merged
+    AfterMerge,
+    /// Browser tests (e.g. `npx playwright test --reporter=json`) run against the attempt's dev
+    /// server once the attempt finishes; a blocking failure stops the merge
+    E2eVerification,
+}
+
+/// Shell command a project runs in an attempt's worktree at a lifecycle hook.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ProjectLifecycleHook {
+    pub project_id: Uuid,
+    pub hook: LifecycleHook,
+    pub command: String,
+    /// Whether a failure stops the attempt or merge; advisory failures are only reported
+    pub blocking: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpsertProjectLifecycleHook {
+    pub command: String,
+    #[serde(default = "default_blocking")]
+    pub blocking: bool,
+}
+
+fn default_blocking() -> bool {
+    true
+}
+
+impl ProjectLifecycleHook {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectLifecycleHook,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      hook as "hook!: LifecycleHook",
+                      command,
+                      blocking as "blocking!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_lifecycle_hooks
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        hook: LifecycleHook,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectLifecycleHook,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      hook as "hook!: LifecycleHook",
+                      command,
+                      blocking as "blocking!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_lifecycle_hooks
+               WHERE project_id = $1 AND hook = $2"#,
+            project_id,
+            hook
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        hook: LifecycleHook,
+        data: &UpsertProjectLifecycleHook,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectLifecycleHook,
+            r#"INSERT INTO project_lifecycle_hooks (project_id, hook, command, blocking)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id, hook) DO UPDATE SET
+                   command = excluded.command,
+                   blocking = excluded.blocking,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         hook as "hook!: LifecycleHook",
+                         command,
+                         blocking as "blocking!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            hook,
+            data.command,
+            data.blocking
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        hook: LifecycleHook,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_lifecycle_hooks WHERE project_id = $1 AND hook = $2",
+            project_id,
+            hook
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(
+    Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, Hash, TS, EnumString, Display,
+)]
+#[sqlx(type_name = "linter", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Linter {
+    /// Rust files, from `cargo clippy --message-format=json`
+    Clippy,
+    /// JavaScript and TypeScript files, from `eslint --format json`
+    Eslint,
+}
+
+/// Linter a project runs against the files an attempt changed.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ProjectLinter {
+    pub project_id: Uuid,
+    pub linter: Linter,
+    /// Must print the linter's JSON output to stdout
+    pub command: String,
+    /// Send error annotations back to the attempt's agent as a follow-up to fix
+    pub fix_follow_up: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpsertProjectLinter {
+    /// Defaults to the linter's standard JSON command
+    pub command: Option<String>,
+    #[serde(default)]
+    pub fix_follow_up: bool,
+}
+
+impl ProjectLinter {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectLinter,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      linter as "linter!: Linter",
+                      command,
+                      fix_follow_up as "fix_follow_up!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_linters
+               WHERE project_id = $1
+               ORDER BY linter"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        linter: Linter,
+        command: &str,
+        fix_follow_up: bool,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectLinter,
+            r#"INSERT INTO project_linters (project_id, linter, command, fix_follow_up)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id, linter) DO UPDATE SET
+                   command = excluded.command,
+                   fix_follow_up = excluded.fix_follow_up,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         linter as "linter!: Linter",
+                         command,
+                         fix_follow_up as "fix_follow_up!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            linter,
+            command,
+            fix_follow_up
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        linter: Linter,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_linters WHERE project_id = $1 AND linter = $2",
+            project_id,
+            linter
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
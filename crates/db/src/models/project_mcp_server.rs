@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use executors::mcp_config::{McpServerSpec, McpServerTransport};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// An MCP server made available to coding agents running in a project's workspaces.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ProjectMcpServer {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    #[ts(type = "McpServerTransport")]
+    pub transport: Json<McpServerTransport>,
+    #[ts(type = "Record<string, string>")]
+    pub env: Json<HashMap<String, String>>,
+    pub auto_approve: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateProjectMcpServer {
+    pub name: String,
+    pub transport: McpServerTransport,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    #[serde(default)]
+    pub auto_approve: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectMcpServer {
+    pub name: Option<String>,
+    pub transport: Option<McpServerTransport>,
+    pub env: Option<HashMap<String, String>>,
+    pub auto_approve: Option<bool>,
+}
+
+impl ProjectMcpServer {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectMcpServer,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      transport as "transport!: Json<McpServerTransport>",
+                      env as "env!: Json<HashMap<String, String>>",
+                      auto_approve as "auto_approve!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_mcp_servers
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectMcpServer,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      transport as "transport!: Json<McpServerTransport>",
+                      env as "env!: Json<HashMap<String, String>>",
+                      auto_approve as "auto_approve!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_mcp_servers
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateProjectMcpServer,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let transport = Json(&data.transport);
+        let env = Json(&data.env);
+        sqlx::query_as!(
+            ProjectMcpServer,
+            r#"INSERT INTO project_mcp_servers (id, project_id, name, transport, env, auto_approve)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         transport as "transport!: Json<McpServerTransport>",
+                         env as "env!: Json<HashMap<String, String>>",
+                         auto_approve as "auto_approve!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            transport,
+            env,
+            data.auto_approve
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        &self,
+        pool: &SqlitePool,
+        data: &UpdateProjectMcpServer,
+    ) -> Result<Self, sqlx::Error> {
+        let name = data.name.as_ref().unwrap_or(&self.name);
+        let transport = Json(data.transport.as_ref().unwrap_or(&self.transport.0));
+        let env = Json(data.env.as_ref().unwrap_or(&self.env.0));
+        let auto_approve = data.auto_approve.unwrap_or(self.auto_approve);
+        sqlx::query_as!(
+            ProjectMcpServer,
+            r#"UPDATE project_mcp_servers
+               SET name = $2, transport = $3, env = $4, auto_approve = $5,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         transport as "transport!: Json<McpServerTransport>",
+                         env as "env!: Json<HashMap<String, String>>",
+                         auto_approve as "auto_approve!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            self.id,
+            name,
+            transport,
+            env,
+            auto_approve
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM project_mcp_servers WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub fn to_spec(&self) -> McpServerSpec {
+        McpServerSpec {
+            name: self.name.clone(),
+            transport: self.transport.0.clone(),
+            env: self.env.0.clone(),
+            auto_approve: self.auto_approve,
+        }
+    }
+}
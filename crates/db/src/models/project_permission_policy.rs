@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use executors::executors::claude::types::PermissionMode;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Caps the permission mode a project's attempts may run with.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ProjectPermissionPolicy {
+    pub project_id: Uuid,
+    /// Least strict mode allowed; attempts may always pick a stricter one
+    pub max_permission_mode: PermissionMode,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpsertProjectPermissionPolicy {
+    pub max_permission_mode: PermissionMode,
+}
+
+impl ProjectPermissionPolicy {
+    /// Mode an attempt runs with under the policy. A requested mode must not be less strict than
+    /// the cap, which is returned as the error. Without a request the profile's mode applies,
+    /// pinned to the cap when the profile allows more.
+    pub fn resolve(
+        policy: Option<&Self>,
+        requested: Option<PermissionMode>,
+        profile_mode: Option<PermissionMode>,
+    ) -> Result<Option<PermissionMode>, PermissionMode> {
+        let Some(cap) = policy.map(|policy| policy.max_permission_mode) else {
+            return Ok(requested);
+        };
+        match requested {
+            Some(mode) if !mode.is_at_least_as_strict_as(cap) => Err(cap),
+            Some(mode) => Ok(Some(mode)),
+            None => Ok(profile_mode
+                .filter(|mode| !mode.is_at_least_as_strict_as(cap))
+                .map(|_| cap)),
+        }
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectPermissionPolicy,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      max_permission_mode as "max_permission_mode!: PermissionMode",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_permission_policies
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &UpsertProjectPermissionPolicy,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectPermissionPolicy,
+            r#"INSERT INTO project_permission_policies (project_id, max_permission_mode)
+               VALUES ($1, $2)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   max_permission_mode = excluded.max_permission_mode,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         max_permission_mode as "max_permission_mode!: PermissionMode",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            data.max_permission_mode
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_permission_policies WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
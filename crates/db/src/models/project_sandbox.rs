@@ -0,0 +1,139 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(
+    Debug, Clone, Copy, Default, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display,
+)]
+#[sqlx(type_name = "sandbox_network", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SandboxNetwork {
+    /// No network access at all
+    None,
+    /// Docker's default bridge network
+    #[default]
+    Bridge,
+    /// The host's network stack
+    Host,
+}
+
+/// Docker container a project's attempts run their executor and scripts in.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ProjectSandbox {
+    pub project_id: Uuid,
+    pub enabled: bool,
+    /// Image the container is started from; it must provide the coding agent CLI
+    pub image: String,
+    pub network: SandboxNetwork,
+    pub cpus: Option<f64>,
+    pub memory_mb: Option<i64>,
+    pub pids_limit: Option<i64>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpsertProjectSandbox {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub image: String,
+    #[serde(default)]
+    pub network: SandboxNetwork,
+    pub cpus: Option<f64>,
+    pub memory_mb: Option<i64>,
+    pub pids_limit: Option<i64>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl ProjectSandbox {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectSandbox,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      enabled as "enabled!: bool",
+                      image,
+                      network as "network!: SandboxNetwork",
+                      cpus,
+                      memory_mb,
+                      pids_limit,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_sandboxes
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// The project's sandbox, if it has one and it is enabled
+    pub async fn find_enabled(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        Ok(Self::find_by_project_id(pool, project_id)
+            .await?
+            .filter(|sandbox| sandbox.enabled))
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &UpsertProjectSandbox,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectSandbox,
+            r#"INSERT INTO project_sandboxes
+                   (project_id, enabled, image, network, cpus, memory_mb, pids_limit)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   enabled = excluded.enabled,
+                   image = excluded.image,
+                   network = excluded.network,
+                   cpus = excluded.cpus,
+                   memory_mb = excluded.memory_mb,
+                   pids_limit = excluded.pids_limit,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         enabled as "enabled!: bool",
+                         image,
+                         network as "network!: SandboxNetwork",
+                         cpus,
+                         memory_mb,
+                         pids_limit,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            data.enabled,
+            data.image,
+            data.network,
+            data.cpus,
+            data.memory_mb,
+            data.pids_limit
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_sandboxes WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,166 @@
+use chrono::{DateTime, Utc};
+use executors::mcp_config::McpServerTransport;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::project_repo::CreateProjectRepo;
+
+/// Scripts of one repository in a template, matched to new repositories by name.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProjectTemplateRepo {
+    pub name: String,
+    pub setup_script: Option<String>,
+    pub cleanup_script: Option<String>,
+    pub copy_files: Option<String>,
+    #[serde(default)]
+    pub parallel_setup_script: bool,
+}
+
+/// Environment variable declared by a template. Values are never stored.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProjectTemplateEnvVar {
+    pub key: String,
+    #[serde(default)]
+    pub is_secret: bool,
+}
+
+/// MCP server declared by a template, with the names of its environment variables.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProjectTemplateMcpServer {
+    pub name: String,
+    pub transport: McpServerTransport,
+    #[serde(default)]
+    pub env_keys: Vec<String>,
+    #[serde(default)]
+    pub auto_approve: bool,
+}
+
+/// Configuration applied to projects created from a template.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct ProjectTemplateConfig {
+    pub dev_script: Option<String>,
+    pub dev_script_working_dir: Option<String>,
+    pub default_agent_working_dir: Option<String>,
+    pub repositories: Vec<ProjectTemplateRepo>,
+    pub env_vars: Vec<ProjectTemplateEnvVar>,
+    pub mcp_servers: Vec<ProjectTemplateMcpServer>,
+    /// Team new projects join, sharing its executor and notification defaults
+    pub team_id: Option<Uuid>,
+}
+
+impl ProjectTemplateConfig {
+    /// Scripts for a repository: the entry with the same name, or the only entry when the
+    /// template was saved from a single-repository project.
+    pub fn repo_scripts(&self, repo_name: &str) -> Option<&ProjectTemplateRepo> {
+        self.repositories
+            .iter()
+            .find(|repo| repo.name == repo_name)
+            .or(match self.repositories.as_slice() {
+                [only] => Some(only),
+                _ => None,
+            })
+    }
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ProjectTemplate {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    #[ts(type = "ProjectTemplateConfig")]
+    pub config: Json<ProjectTemplateConfig>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Save a project's configuration as a template.
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateProjectTemplate {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateProjectFromTemplate {
+    pub template_id: Uuid,
+    pub name: String,
+    pub repositories: Vec<CreateProjectRepo>,
+}
+
+impl ProjectTemplate {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectTemplate,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      description,
+                      config as "config!: Json<ProjectTemplateConfig>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_templates
+               ORDER BY name ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectTemplate,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      description,
+                      config as "config!: Json<ProjectTemplateConfig>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_templates
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Create a template, or replace the configuration of the template with the same name.
+    pub async fn upsert(
+        pool: &SqlitePool,
+        data: &CreateProjectTemplate,
+        config: &ProjectTemplateConfig,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let config = Json(config);
+        sqlx::query_as!(
+            ProjectTemplate,
+            r#"INSERT INTO project_templates (id, name, description, config)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(name) DO UPDATE SET
+                   description = excluded.description,
+                   config = excluded.config,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid",
+                         name,
+                         description,
+                         config as "config!: Json<ProjectTemplateConfig>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.name,
+            data.description,
+            config
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM project_templates WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Incoming webhook of a project. Only the SHA-256 of the secret is persisted.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ProjectWebhook {
+    pub project_id: Uuid,
+    pub token: String,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub secret_hash: String,
+    #[ts(type = "Date | null")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl ProjectWebhook {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectWebhook,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      token,
+                      secret_hash,
+                      last_used_at as "last_used_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM project_webhooks
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_token(
+        pool: &SqlitePool,
+        token: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectWebhook,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      token,
+                      secret_hash,
+                      last_used_at as "last_used_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM project_webhooks
+               WHERE token = $1"#,
+            token
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Create the project's webhook, replacing any previous token and secret.
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        token: &str,
+        secret_hash: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectWebhook,
+            r#"INSERT INTO project_webhooks (project_id, token, secret_hash)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   token = excluded.token,
+                   secret_hash = excluded.secret_hash,
+                   last_used_at = NULL,
+                   created_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         token,
+                         secret_hash,
+                         last_used_at as "last_used_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            project_id,
+            token,
+            secret_hash
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn touch(pool: &SqlitePool, project_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE project_webhooks
+               SET last_used_at = datetime('now', 'subsec')
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_webhooks WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
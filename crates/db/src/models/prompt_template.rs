@@ -0,0 +1,236 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(
+    Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, Hash, TS, EnumString, Display,
+)]
+#[sqlx(type_name = "prompt_template_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PromptTemplateKind {
+    /// Added to the prompt of an attempt's first run when picked at attempt start
+    SystemPrompt,
+    /// Added to a follow-up message when picked for it
+    FollowUpSnippet,
+}
+
+/// Reusable prompt text with `{{variable}}` placeholders, filled in from the attempt.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct PromptTemplate {
+    pub id: Uuid,
+    /// `None` shares the template with every project
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    pub description: Option<String>,
+    pub kind: PromptTemplateKind,
+    pub content: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Used both to create a template and to replace every field of one.
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreatePromptTemplate {
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub kind: PromptTemplateKind,
+    pub content: String,
+}
+
+impl PromptTemplate {
+    /// Whether attempts of the project can use the template.
+    pub fn is_available_to(&self, project_id: Uuid) -> bool {
+        self.project_id.is_none_or(|id| id == project_id)
+    }
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PromptTemplate,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      name,
+                      description,
+                      kind as "kind!: PromptTemplateKind",
+                      content,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM prompt_templates
+               ORDER BY name ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Templates shared with every project plus the project's own.
+    pub async fn find_available_to_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PromptTemplate,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      name,
+                      description,
+                      kind as "kind!: PromptTemplateKind",
+                      content,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM prompt_templates
+               WHERE project_id IS NULL OR project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PromptTemplate,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      name,
+                      description,
+                      kind as "kind!: PromptTemplateKind",
+                      content,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM prompt_templates
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// System prompt templates picked when the attempt was started, in the order picked.
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PromptTemplate,
+            r#"SELECT pt.id as "id!: Uuid",
+                      pt.project_id as "project_id: Uuid",
+                      pt.name,
+                      pt.description,
+                      pt.kind as "kind!: PromptTemplateKind",
+                      pt.content,
+                      pt.created_at as "created_at!: DateTime<Utc>",
+                      pt.updated_at as "updated_at!: DateTime<Utc>"
+               FROM workspace_prompt_templates wpt
+               JOIN prompt_templates pt ON pt.id = wpt.prompt_template_id
+               WHERE wpt.workspace_id = $1
+               ORDER BY wpt.position ASC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreatePromptTemplate,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            PromptTemplate,
+            r#"INSERT INTO prompt_templates (id, project_id, name, description, kind, content)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id: Uuid",
+                         name,
+                         description,
+                         kind as "kind!: PromptTemplateKind",
+                         content,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.name,
+            data.description,
+            data.kind,
+            data.content
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &CreatePromptTemplate,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            PromptTemplate,
+            r#"UPDATE prompt_templates
+               SET project_id = $2, name = $3, description = $4, kind = $5, content = $6,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id: Uuid",
+                         name,
+                         description,
+                         kind as "kind!: PromptTemplateKind",
+                         content,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.name,
+            data.description,
+            data.kind,
+            data.content
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM prompt_templates WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Record the templates picked for an attempt, in order.
+    pub async fn set_for_workspace(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        template_ids: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            "DELETE FROM workspace_prompt_templates WHERE workspace_id = $1",
+            workspace_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        for (position, template_id) in template_ids.iter().enumerate() {
+            let position = position as i64;
+            sqlx::query!(
+                r#"INSERT INTO workspace_prompt_templates
+                       (workspace_id, prompt_template_id, position)
+                   VALUES ($1, $2, $3)
+                   ON CONFLICT(workspace_id, prompt_template_id) DO NOTHING"#,
+                workspace_id,
+                template_id,
+                position
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Expiring read-only link to a task for people without an account.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct PublicShareLink {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// Attempt whose summary and diff are shown
+    pub workspace_id: Option<Uuid>,
+    /// Files of the attempt's diff included in the link
+    #[ts(type = "Array<string>")]
+    pub diff_paths: Json<Vec<String>>,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub diff: Option<String>,
+    #[ts(type = "Date")]
+    pub expires_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl PublicShareLink {
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PublicShareLink,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      workspace_id as "workspace_id: Uuid",
+                      diff_paths as "diff_paths!: Json<Vec<String>>",
+                      diff,
+                      expires_at as "expires_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM public_share_links
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PublicShareLink,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      workspace_id as "workspace_id: Uuid",
+                      diff_paths as "diff_paths!: Json<Vec<String>>",
+                      diff,
+                      expires_at as "expires_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM public_share_links
+               WHERE task_id = $1
+               ORDER BY created_at DESC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        workspace_id: Option<Uuid>,
+        diff_paths: &[String],
+        diff: Option<&str>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let diff_paths = Json(diff_paths);
+        sqlx::query_as!(
+            PublicShareLink,
+            r#"INSERT INTO public_share_links
+                   (id, task_id, workspace_id, diff_paths, diff, expires_at)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         workspace_id as "workspace_id: Uuid",
+                         diff_paths as "diff_paths!: Json<Vec<String>>",
+                         diff,
+                         expires_at as "expires_at!: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            workspace_id,
+            diff_paths,
+            diff,
+            expires_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Revoke a link of a task before it expires.
+    pub async fn delete(pool: &SqlitePool, task_id: Uuid, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM public_share_links WHERE id = $1 AND task_id = $2",
+            id,
+            task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
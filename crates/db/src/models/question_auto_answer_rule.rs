@@ -0,0 +1,172 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Answers a recurring agent question in a project without waiting for a reviewer.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct QuestionAutoAnswerRule {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    /// Regex the question text has to match
+    pub question_pattern: String,
+    /// Regex the question header has to match, if set
+    pub header_pattern: Option<String>,
+    /// Label of the option to select
+    pub option_label: String,
+    pub enabled: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateQuestionAutoAnswerRule {
+    pub name: String,
+    pub question_pattern: String,
+    #[serde(default)]
+    pub header_pattern: Option<String>,
+    pub option_label: String,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateQuestionAutoAnswerRule {
+    pub name: Option<String>,
+    pub question_pattern: Option<String>,
+    /// An empty string clears the header pattern
+    pub header_pattern: Option<String>,
+    pub option_label: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+impl QuestionAutoAnswerRule {
+    /// Rules of a project in the order they're tried
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            QuestionAutoAnswerRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      question_pattern,
+                      header_pattern,
+                      option_label,
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM question_auto_answer_rules
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            QuestionAutoAnswerRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      question_pattern,
+                      header_pattern,
+                      option_label,
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM question_auto_answer_rules
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateQuestionAutoAnswerRule,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            QuestionAutoAnswerRule,
+            r#"INSERT INTO question_auto_answer_rules
+                   (id, project_id, name, question_pattern, header_pattern, option_label)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         question_pattern,
+                         header_pattern,
+                         option_label,
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            data.question_pattern,
+            data.header_pattern,
+            data.option_label
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// An empty `header_pattern` clears it, so the rule matches any header again
+    pub async fn update(
+        &self,
+        pool: &SqlitePool,
+        data: &UpdateQuestionAutoAnswerRule,
+    ) -> Result<Self, sqlx::Error> {
+        let name = data.name.as_ref().unwrap_or(&self.name);
+        let question_pattern = data
+            .question_pattern
+            .as_ref()
+            .unwrap_or(&self.question_pattern);
+        let header_pattern = match data.header_pattern.as_deref() {
+            Some("") => None,
+            Some(pattern) => Some(pattern),
+            None => self.header_pattern.as_deref(),
+        };
+        let option_label = data.option_label.as_ref().unwrap_or(&self.option_label);
+        let enabled = data.enabled.unwrap_or(self.enabled);
+        sqlx::query_as!(
+            QuestionAutoAnswerRule,
+            r#"UPDATE question_auto_answer_rules
+               SET name = $2, question_pattern = $3, header_pattern = $4, option_label = $5,
+                   enabled = $6, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         question_pattern,
+                         header_pattern,
+                         option_label,
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            self.id,
+            name,
+            question_pattern,
+            header_pattern,
+            option_label,
+            enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM question_auto_answer_rules WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Where an escalation step sends its reminder.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum EscalationChannel {
+    /// Notification center, desktop and mobile push, like the initial alert
+    Notify,
+    /// Phone notifications of one user, through their own ntfy topic or Pushover key
+    User { user_id: Uuid },
+    /// A channel of the project's Slack app
+    Slack { channel_id: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct EscalationStep {
+    /// Minutes after the question was asked
+    pub after_minutes: u32,
+    pub channel: EscalationChannel,
+}
+
+/// Escalation chain for a project's unanswered agent questions.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ProjectQuestionEscalation {
+    pub project_id: Uuid,
+    #[ts(type = "Array<EscalationStep>")]
+    pub steps: Json<Vec<EscalationStep>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateQuestionEscalation {
+    pub steps: Vec<EscalationStep>,
+}
+
+impl ProjectQuestionEscalation {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectQuestionEscalation,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      steps as "steps!: Json<Vec<EscalationStep>>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_question_escalations
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Replace the project's chain; steps are stored in the order they fire.
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        steps: &[EscalationStep],
+    ) -> Result<Self, sqlx::Error> {
+        let mut steps = steps.to_vec();
+        steps.sort_by_key(|step| step.after_minutes);
+        let steps = Json(steps);
+        sqlx::query_as!(
+            ProjectQuestionEscalation,
+            r#"INSERT INTO project_question_escalations (project_id, steps)
+               VALUES ($1, $2)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   steps = excluded.steps,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         steps as "steps!: Json<Vec<EscalationStep>>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            steps
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_question_escalations WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+/// Where each task field is found in the items of a task source, as dot-separated paths such as
+/// `fields.summary` or `labels.0`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskSourceFieldMapping {
+    /// Identifies an item across syncs
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// A Redis key that an external daemon fills with a JSON array of tasks for a project to import.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct RedisTaskSource {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub redis_key: String,
+    #[ts(type = "TaskSourceFieldMapping")]
+    pub field_mapping: Json<TaskSourceFieldMapping>,
+    /// Source status values mapped to task statuses; unmapped values import as todo
+    #[ts(type = "Record<string, TaskStatus>")]
+    pub status_mapping: Json<HashMap<String, TaskStatus>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateRedisTaskSource {
+    pub name: String,
+    pub redis_key: String,
+    pub field_mapping: TaskSourceFieldMapping,
+    #[serde(default)]
+    pub status_mapping: HashMap<String, TaskStatus>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateRedisTaskSource {
+    pub name: Option<String>,
+    pub redis_key: Option<String>,
+    pub field_mapping: Option<TaskSourceFieldMapping>,
+    pub status_mapping: Option<HashMap<String, TaskStatus>>,
+}
+
+impl RedisTaskSource {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            RedisTaskSource,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      redis_key,
+                      field_mapping as "field_mapping!: Json<TaskSourceFieldMapping>",
+                      status_mapping as "status_mapping!: Json<HashMap<String, TaskStatus>>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM redis_task_sources
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            RedisTaskSource,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      redis_key,
+                      field_mapping as "field_mapping!: Json<TaskSourceFieldMapping>",
+                      status_mapping as "status_mapping!: Json<HashMap<String, TaskStatus>>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM redis_task_sources
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateRedisTaskSource,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let field_mapping = Json(&data.field_mapping);
+        let status_mapping = Json(&data.status_mapping);
+        sqlx::query_as!(
+            RedisTaskSource,
+            r#"INSERT INTO redis_task_sources
+                   (id, project_id, name, redis_key, field_mapping, status_mapping)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         redis_key,
+                         field_mapping as "field_mapping!: Json<TaskSourceFieldMapping>",
+                         status_mapping as "status_mapping!: Json<HashMap<String, TaskStatus>>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            data.redis_key,
+            field_mapping,
+            status_mapping
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        &self,
+        pool: &SqlitePool,
+        data: &UpdateRedisTaskSource,
+    ) -> Result<Self, sqlx::Error> {
+        let name = data.name.as_ref().unwrap_or(&self.name);
+        let redis_key = data.redis_key.as_ref().unwrap_or(&self.redis_key);
+        let field_mapping = Json(data.field_mapping.as_ref().unwrap_or(&self.field_mapping.0));
+        let status_mapping = Json(
+            data.status_mapping
+                .as_ref()
+                .unwrap_or(&self.status_mapping.0),
+        );
+        sqlx::query_as!(
+            RedisTaskSource,
+            r#"UPDATE redis_task_sources
+               SET name = $2, redis_key = $3, field_mapping = $4, status_mapping = $5,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         redis_key,
+                         field_mapping as "field_mapping!: Json<TaskSourceFieldMapping>",
+                         status_mapping as "status_mapping!: Json<HashMap<String, TaskStatus>>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            self.id,
+            name,
+            redis_key,
+            field_mapping,
+            status_mapping
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM redis_task_sources WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
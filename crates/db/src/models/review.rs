@@ -0,0 +1,272 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "review_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "review_severity", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewSeverity {
+    Blocking,
+    Warning,
+    Suggestion,
+}
+
+/// A review of an attempt's diff by a second coding agent, run in its own session.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct Review {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub session_id: Uuid,
+    pub status: ReviewStatus,
+    pub executor: String,
+    pub error: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date | null")]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// A review comment on an attempt, optionally pointing at a file and line.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ReviewFinding {
+    pub id: Uuid,
+    pub review_id: Uuid,
+    pub file_path: Option<String>,
+    #[ts(type = "number | null")]
+    pub line: Option<i64>,
+    pub severity: ReviewSeverity,
+    pub comment: String,
+    /// Set by the user once the finding has been addressed or dismissed
+    pub resolved: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateReviewFinding {
+    pub file_path: Option<String>,
+    pub line: Option<i64>,
+    pub severity: ReviewSeverity,
+    pub comment: String,
+}
+
+impl Review {
+    pub async fn create(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        session_id: Uuid,
+        executor: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            Review,
+            r#"INSERT INTO reviews (id, workspace_id, session_id, executor)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         workspace_id as "workspace_id!: Uuid",
+                         session_id as "session_id!: Uuid",
+                         status as "status!: ReviewStatus",
+                         executor,
+                         error,
+                         created_at as "created_at!: DateTime<Utc>",
+                         completed_at as "completed_at: DateTime<Utc>""#,
+            id,
+            workspace_id,
+            session_id,
+            executor
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_session_id(
+        pool: &SqlitePool,
+        session_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Review,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      session_id as "session_id!: Uuid",
+                      status as "status!: ReviewStatus",
+                      executor,
+                      error,
+                      created_at as "created_at!: DateTime<Utc>",
+                      completed_at as "completed_at: DateTime<Utc>"
+               FROM reviews
+               WHERE session_id = $1"#,
+            session_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_latest_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Review,
+            r#"SELECT id as "id!: Uuid",
+                      workspace_id as "workspace_id!: Uuid",
+                      session_id as "session_id!: Uuid",
+                      status as "status!: ReviewStatus",
+                      executor,
+                      error,
+                      created_at as "created_at!: DateTime<Utc>",
+                      completed_at as "completed_at: DateTime<Utc>"
+               FROM reviews
+               WHERE workspace_id = $1
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn finish(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: ReviewStatus,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE reviews
+               SET status = $2, error = $3, completed_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            status,
+            error
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
+
+impl ReviewFinding {
+    pub async fn create_many(
+        pool: &SqlitePool,
+        review_id: Uuid,
+        findings: &[CreateReviewFinding],
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        let mut created = Vec::with_capacity(findings.len());
+        for finding in findings {
+            let id = Uuid::new_v4();
+            let row = sqlx::query_as!(
+                ReviewFinding,
+                r#"INSERT INTO review_findings (id, review_id, file_path, line, severity, comment)
+                   VALUES ($1, $2, $3, $4, $5, $6)
+                   RETURNING id as "id!: Uuid",
+                             review_id as "review_id!: Uuid",
+                             file_path,
+                             line,
+                             severity as "severity!: ReviewSeverity",
+                             comment,
+                             resolved as "resolved!: bool",
+                             created_at as "created_at!: DateTime<Utc>""#,
+                id,
+                review_id,
+                finding.file_path,
+                finding.line,
+                finding.severity,
+                finding.comment
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            created.push(row);
+        }
+        tx.commit().await?;
+        Ok(created)
+    }
+
+    pub async fn find_by_review_id(
+        pool: &SqlitePool,
+        review_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewFinding,
+            r#"SELECT id as "id!: Uuid",
+                      review_id as "review_id!: Uuid",
+                      file_path,
+                      line,
+                      severity as "severity!: ReviewSeverity",
+                      comment,
+                      resolved as "resolved!: bool",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM review_findings
+               WHERE review_id = $1
+               ORDER BY created_at ASC"#,
+            review_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Returns `None` when the finding does not belong to a review of the workspace.
+    pub async fn set_resolved(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        id: Uuid,
+        resolved: bool,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewFinding,
+            r#"UPDATE review_findings
+               SET resolved = $3
+               WHERE id = $2
+                 AND review_id IN (SELECT id FROM reviews WHERE workspace_id = $1)
+               RETURNING id as "id!: Uuid",
+                         review_id as "review_id!: Uuid",
+                         file_path,
+                         line,
+                         severity as "severity!: ReviewSeverity",
+                         comment,
+                         resolved as "resolved!: bool",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            workspace_id,
+            id,
+            resolved
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Unresolved blocking findings from the most recent completed review of any of the task's
+    /// attempts.
+    pub async fn count_open_blocking_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM review_findings
+               WHERE severity = 'blocking'
+                 AND resolved = FALSE
+                 AND review_id = (
+                     SELECT r.id
+                     FROM reviews r
+                     JOIN workspaces w ON w.id = r.workspace_id
+                     WHERE w.task_id = $1 AND r.status = 'completed'
+                     ORDER BY r.created_at DESC
+                     LIMIT 1
+                 )"#,
+            task_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
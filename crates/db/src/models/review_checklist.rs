@@ -0,0 +1,242 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// An item of a project's review checklist, ticked off separately on each attempt.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ReviewChecklistItem {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub label: String,
+    /// Tasks can't be moved to Done until the item is checked on their latest attempt
+    pub required: bool,
+    #[ts(type = "number")]
+    pub position: i64,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateReviewChecklistItem {
+    pub label: String,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateReviewChecklistItem {
+    pub label: Option<String>,
+    pub required: Option<bool>,
+    #[ts(type = "number | null")]
+    pub position: Option<i64>,
+}
+
+/// A checklist item and whether it's been checked on an attempt.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ChecklistItemState {
+    pub item_id: Uuid,
+    pub label: String,
+    pub required: bool,
+    #[ts(type = "Date | null")]
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+impl ChecklistItemState {
+    pub fn checked(&self) -> bool {
+        self.checked_at.is_some()
+    }
+}
+
+impl ReviewChecklistItem {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewChecklistItem,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      label,
+                      required as "required!: bool",
+                      position as "position!: i64",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM review_checklist_items
+               WHERE project_id = $1
+               ORDER BY position ASC, created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ReviewChecklistItem,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      label,
+                      required as "required!: bool",
+                      position as "position!: i64",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM review_checklist_items
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Add an item at the end of the project's checklist
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateReviewChecklistItem,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            ReviewChecklistItem,
+            r#"INSERT INTO review_checklist_items (id, project_id, label, required, position)
+               VALUES ($1, $2, $3, $4,
+                       (SELECT COALESCE(MAX(position) + 1, 0)
+                        FROM review_checklist_items WHERE project_id = $2))
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         label,
+                         required as "required!: bool",
+                         position as "position!: i64",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.label,
+            data.required
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        &self,
+        pool: &SqlitePool,
+        data: &UpdateReviewChecklistItem,
+    ) -> Result<Self, sqlx::Error> {
+        let label = data.label.as_ref().unwrap_or(&self.label);
+        let required = data.required.unwrap_or(self.required);
+        let position = data.position.unwrap_or(self.position);
+        sqlx::query_as!(
+            ReviewChecklistItem,
+            r#"UPDATE review_checklist_items
+               SET label = $2, required = $3, position = $4, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         label,
+                         required as "required!: bool",
+                         position as "position!: i64",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            self.id,
+            label,
+            required,
+            position
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM review_checklist_items WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// The checklist of the attempt's project with what's been checked on the attempt
+    pub async fn find_states_for_workspace(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<ChecklistItemState>, sqlx::Error> {
+        sqlx::query_as!(
+            ChecklistItemState,
+            r#"SELECT i.id as "item_id!: Uuid",
+                      i.label,
+                      i.required as "required!: bool",
+                      c.checked_at as "checked_at?: DateTime<Utc>"
+               FROM workspaces w
+               JOIN tasks t ON t.id = w.task_id
+               JOIN review_checklist_items i ON i.project_id = t.project_id
+               LEFT JOIN attempt_checklist_checks c
+                      ON c.item_id = i.id AND c.workspace_id = w.id
+               WHERE w.id = $1
+               ORDER BY i.position ASC, i.created_at ASC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn set_checked(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        item_id: Uuid,
+        checked: bool,
+    ) -> Result<(), sqlx::Error> {
+        if checked {
+            sqlx::query!(
+                r#"INSERT INTO attempt_checklist_checks (workspace_id, item_id)
+                   VALUES ($1, $2)
+                   ON CONFLICT (workspace_id, item_id) DO NOTHING"#,
+                workspace_id,
+                item_id
+            )
+            .execute(pool)
+            .await?;
+        } else {
+            sqlx::query!(
+                "DELETE FROM attempt_checklist_checks WHERE workspace_id = $1 AND item_id = $2",
+                workspace_id,
+                item_id
+            )
+            .execute(pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Required items not checked on the task's latest attempt. Tasks without attempts have none.
+    pub async fn count_unchecked_required_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM review_checklist_items i
+               JOIN tasks t ON t.project_id = i.project_id
+               JOIN workspaces w ON w.id = (
+                   SELECT id FROM workspaces
+                   WHERE task_id = t.id
+                   ORDER BY created_at DESC
+                   LIMIT 1
+               )
+               WHERE t.id = $1
+                 AND i.required = TRUE
+                 AND NOT EXISTS (
+                     SELECT 1 FROM attempt_checklist_checks c
+                     WHERE c.workspace_id = w.id AND c.item_id = i.id
+                 )"#,
+            task_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
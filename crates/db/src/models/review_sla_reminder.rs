@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// Reminder levels sent for a task's stay in review, so each is only sent once.
+pub struct ReviewSlaReminder;
+
+impl ReviewSlaReminder {
+    /// Returns `false` when the reminder was already recorded for this stay in review.
+    pub async fn record(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        in_review_since: DateTime<Utc>,
+        level: i64,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"INSERT OR IGNORE INTO review_sla_reminders (task_id, in_review_since, level)
+               VALUES ($1, $2, $3)"#,
+            task_id,
+            in_review_since,
+            level
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,191 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A provider credential from the secrets vault. `value` always holds the encrypted payload.
+/// Secrets without a project apply to every project unless overridden by name.
+#[derive(Debug, Clone, FromRow)]
+pub struct Secret {
+    pub id: Uuid,
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    pub value: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// API representation of a secret. Values are never returned.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct SecretResponse {
+    pub id: Uuid,
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Secret> for SecretResponse {
+    fn from(secret: Secret) -> Self {
+        Self {
+            id: secret.id,
+            project_id: secret.project_id,
+            name: secret.name,
+            created_at: secret.created_at,
+            updated_at: secret.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateSecret {
+    /// Environment variable name, e.g. `ANTHROPIC_API_KEY`
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateSecret {
+    pub value: String,
+}
+
+impl Secret {
+    pub async fn find_global(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Secret,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      name,
+                      value,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM secrets
+               WHERE project_id IS NULL
+               ORDER BY name ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Overrides configured for a single project.
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Secret,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      name,
+                      value,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM secrets
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Global secrets followed by the project's overrides, so later rows win by name.
+    pub async fn find_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Secret,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      name,
+                      value,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM secrets
+               WHERE project_id IS NULL OR project_id = $1
+               ORDER BY project_id IS NOT NULL, name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Secret,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      name,
+                      value,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM secrets
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Insert a new secret. `value` must already be encrypted.
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+        name: &str,
+        value: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            Secret,
+            r#"INSERT INTO secrets (id, project_id, name, value)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id: Uuid",
+                         name,
+                         value,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            name,
+            value
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Replace the value of a secret. `value` must already be encrypted.
+    pub async fn update_value(
+        pool: &SqlitePool,
+        id: Uuid,
+        value: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Secret,
+            r#"UPDATE secrets
+               SET value = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id: Uuid",
+                         name,
+                         value,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            value
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM secrets WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
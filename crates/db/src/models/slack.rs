@@ -0,0 +1,148 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Slack app whose `/vibe` command creates tasks in a project.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct SlackIntegration {
+    pub project_id: Uuid,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub signing_secret: String,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub bot_token: String,
+    /// Address of this instance used in task links posted to Slack
+    pub app_url: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Slack message announcing a task created from Slack.
+#[derive(Debug, Clone, FromRow)]
+pub struct SlackTaskThread {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub channel_id: String,
+    pub message_ts: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SlackIntegration {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SlackIntegration,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      signing_secret,
+                      bot_token,
+                      app_url,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM slack_integrations
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Create or replace the project's integration. The signing secret and bot token must
+    /// already be encrypted.
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        signing_secret: &str,
+        bot_token: &str,
+        app_url: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            SlackIntegration,
+            r#"INSERT INTO slack_integrations (project_id, signing_secret, bot_token, app_url)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   signing_secret = excluded.signing_secret,
+                   bot_token = excluded.bot_token,
+                   app_url = excluded.app_url,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         signing_secret,
+                         bot_token,
+                         app_url,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            signing_secret,
+            bot_token,
+            app_url
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM slack_integrations WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl SlackTaskThread {
+    pub async fn find_by_message(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        channel_id: &str,
+        message_ts: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SlackTaskThread,
+            r#"SELECT task_id as "task_id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      channel_id,
+                      message_ts,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM slack_task_threads
+               WHERE project_id = $1 AND channel_id = $2 AND message_ts = $3"#,
+            project_id,
+            channel_id,
+            message_ts
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        project_id: Uuid,
+        channel_id: &str,
+        message_ts: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            SlackTaskThread,
+            r#"INSERT INTO slack_task_threads (task_id, project_id, channel_id, message_ts)
+               VALUES ($1, $2, $3, $4)
+               RETURNING task_id as "task_id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         channel_id,
+                         message_ts,
+                         created_at as "created_at!: DateTime<Utc>""#,
+            task_id,
+            project_id,
+            channel_id,
+            message_ts
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+/// An open task with the last time it or one of its attempts saw any activity.
+#[derive(Debug, Clone, FromRow)]
+pub struct TaskActivity {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub title: String,
+    pub status: TaskStatus,
+    pub last_activity_at: DateTime<Utc>,
+    /// Set when the stale task policy flagged the task
+    pub flagged_at: Option<DateTime<Utc>>,
+    /// Last activity seen when the task was flagged
+    pub flagged_activity_at: Option<DateTime<Utc>>,
+}
+
+impl TaskActivity {
+    /// Tasks that are neither done nor cancelled, least recently active first.
+    pub async fn find_open(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskActivity,
+            r#"SELECT t.id as "task_id!: Uuid",
+                      t.project_id as "project_id!: Uuid",
+                      t.title,
+                      t.status as "status!: TaskStatus",
+                      MAX(
+                          t.updated_at,
+                          COALESCE(
+                              (SELECT MAX(ep.created_at)
+                               FROM execution_processes ep
+                               JOIN sessions s ON s.id = ep.session_id
+                               JOIN workspaces w ON w.id = s.workspace_id
+                               WHERE w.task_id = t.id),
+                              t.updated_at
+                          )
+                      ) as "last_activity_at!: DateTime<Utc>",
+                      f.flagged_at as "flagged_at?: DateTime<Utc>",
+                      f.last_activity_at as "flagged_activity_at?: DateTime<Utc>"
+               FROM tasks t
+               LEFT JOIN stale_task_flags f ON f.task_id = t.id
+               WHERE t.status NOT IN ('done', 'cancelled')
+                 AND ($1 IS NULL OR t.project_id = $1)
+               ORDER BY last_activity_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+/// Tasks flagged as stale, remembered so that owners are only warned once.
+pub struct StaleTaskFlag;
+
+impl StaleTaskFlag {
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        last_activity_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO stale_task_flags (task_id, last_activity_at)
+               VALUES ($1, $2)
+               ON CONFLICT(task_id) DO UPDATE SET
+                   last_activity_at = excluded.last_activity_at,
+                   flagged_at = datetime('now', 'subsec')"#,
+            task_id,
+            last_activity_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, task_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!("DELETE FROM stale_task_flags WHERE task_id = $1", task_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
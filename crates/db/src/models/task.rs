@@ -1,8 +1,11 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
 use strum_macros::{Display, EnumString};
 use ts_rs::TS;
+use utils::pagination::{Keyset, Paginate, SortField, SortOrder};
 use uuid::Uuid;
 
 use super::{project::Project, workspace::Workspace};
@@ -17,6 +20,8 @@ pub enum TaskStatus {
     #[default]
     Todo,
     InProgress,
+    /// The agent handed the attempt back to a person
+    Blocked,
     InReview,
     Done,
     Cancelled,
@@ -58,6 +63,29 @@ impl std::ops::DerefMut for TaskWithAttemptStatus {
     }
 }
 
+impl Paginate for Task {
+    fn page_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn sort_value(&self, field: SortField) -> DateTime<Utc> {
+        match field {
+            SortField::CreatedAt => self.created_at,
+            SortField::UpdatedAt => self.updated_at,
+        }
+    }
+}
+
+impl Paginate for TaskWithAttemptStatus {
+    fn page_id(&self) -> Uuid {
+        self.task.page_id()
+    }
+
+    fn sort_value(&self, field: SortField) -> DateTime<Utc> {
+        self.task.sort_value(field)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct TaskRelationships {
     pub parent_task: Option<Task>, // The task that owns the parent workspace
@@ -74,6 +102,11 @@ pub struct CreateTask {
     pub parent_workspace_id: Option<Uuid>,
     pub image_ids: Option<Vec<Uuid>>,
     pub shared_task_id: Option<Uuid>,
+    /// Replace the title and description with ones generated by the configured model, keeping
+    /// the submitted text as the prompt the executor receives
+    #[serde(default)]
+    #[ts(optional)]
+    pub generate_details: Option<bool>,
 }
 
 impl CreateTask {
@@ -90,6 +123,7 @@ impl CreateTask {
             parent_workspace_id: None,
             image_ids: None,
             shared_task_id: None,
+            generate_details: None,
         }
     }
 
@@ -108,6 +142,16 @@ impl CreateTask {
             parent_workspace_id: None,
             image_ids: None,
             shared_task_id: Some(shared_task_id),
+            generate_details: None,
+        }
+    }
+
+    /// The prompt the executor would receive for this task, as [`Task::to_prompt`] builds it.
+    pub fn to_prompt(&self) -> String {
+        if let Some(description) = self.description.as_ref().filter(|d| !d.trim().is_empty()) {
+            format!("{}\n\n{}", &self.title, description)
+        } else {
+            self.title.clone()
         }
     }
 }
@@ -138,6 +182,25 @@ impl Task {
         pool: &SqlitePool,
         project_id: Uuid,
     ) -> Result<Vec<TaskWithAttemptStatus>, sqlx::Error> {
+        Self::find_page_by_project_id_with_attempt_status(
+            pool,
+            project_id,
+            &Keyset::all(SortOrder::Desc),
+        )
+        .await
+    }
+
+    /// One page of the board listing, sorted and cut in SQL.
+    pub async fn find_page_by_project_id_with_attempt_status(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        keyset: &Keyset,
+    ) -> Result<Vec<TaskWithAttemptStatus>, sqlx::Error> {
+        let by_updated_at = keyset.by_updated_at();
+        let descending = keyset.descending();
+        let after_value = keyset.after_value();
+        let after_id = keyset.after_id();
+        let limit = keyset.fetch_limit();
         let records = sqlx::query!(
             r#"SELECT
   t.id                            AS "id!: Uuid",
@@ -183,8 +246,22 @@ impl Task {
 
 FROM tasks t
 WHERE t.project_id = $1
-ORDER BY t.created_at DESC"#,
-            project_id
+  AND ($4 IS NULL OR CASE WHEN $3
+    THEN (julianday(CASE WHEN $2 THEN t.updated_at ELSE t.created_at END), t.id) < (julianday($4), $5)
+    ELSE (julianday(CASE WHEN $2 THEN t.updated_at ELSE t.created_at END), t.id) > (julianday($4), $5)
+  END)
+ORDER BY
+  CASE WHEN $3 THEN julianday(CASE WHEN $2 THEN t.updated_at ELSE t.created_at END) END DESC,
+  CASE WHEN $3 THEN t.id END DESC,
+  julianday(CASE WHEN $2 THEN t.updated_at ELSE t.created_at END) ASC,
+  t.id ASC
+LIMIT $6"#,
+            project_id,
+            by_updated_at,
+            descending,
+            after_value,
+            after_id,
+            limit
         )
         .fetch_all(pool)
         .await?;
@@ -212,6 +289,59 @@ ORDER BY t.created_at DESC"#,
         Ok(tasks)
     }
 
+    pub async fn count_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!: i64" FROM tasks WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Cheap fingerprint of everything `find_by_project_id_with_attempt_status` depends on.
+    /// Changes whenever a task, session or execution process of the project is added,
+    /// removed or updated, so it can back the board listing's ETag.
+    pub async fn board_version(pool: &SqlitePool, project_id: Uuid) -> Result<String, sqlx::Error> {
+        let rec = sqlx::query!(
+            r#"SELECT
+  (SELECT COUNT(*) FROM tasks WHERE project_id = $1)        AS "task_count!: i64",
+  (SELECT MAX(updated_at) FROM tasks WHERE project_id = $1) AS "tasks_updated_at: String",
+  (SELECT COUNT(*)
+     FROM sessions s
+     JOIN workspaces w ON w.id = s.workspace_id
+     JOIN tasks t ON t.id = w.task_id
+    WHERE t.project_id = $1)                                AS "session_count!: i64",
+  (SELECT COUNT(*)
+     FROM execution_processes ep
+     JOIN sessions s ON s.id = ep.session_id
+     JOIN workspaces w ON w.id = s.workspace_id
+     JOIN tasks t ON t.id = w.task_id
+    WHERE t.project_id = $1)                                AS "process_count!: i64",
+  (SELECT MAX(ep.updated_at)
+     FROM execution_processes ep
+     JOIN sessions s ON s.id = ep.session_id
+     JOIN workspaces w ON w.id = s.workspace_id
+     JOIN tasks t ON t.id = w.task_id
+    WHERE t.project_id = $1)                                AS "processes_updated_at: String""#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(format!(
+            "{}:{}:{}:{}:{}:{}",
+            project_id,
+            rec.task_count,
+            rec.tasks_updated_at.unwrap_or_default(),
+            rec.session_count,
+            rec.process_count,
+            rec.processes_updated_at.unwrap_or_default()
+        ))
+    }
+
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
@@ -224,6 +354,22 @@ ORDER BY t.created_at DESC"#,
         .await
     }
 
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
@@ -315,6 +461,17 @@ ORDER BY t.created_at DESC"#,
         .await
     }
 
+    /// Current status of every task, keyed by task id.
+    pub async fn find_all_statuses(
+        pool: &SqlitePool,
+    ) -> Result<HashMap<Uuid, TaskStatus>, sqlx::Error> {
+        let rows =
+            sqlx::query!(r#"SELECT id as "id!: Uuid", status as "status!: TaskStatus" FROM tasks"#)
+                .fetch_all(pool)
+                .await?;
+        Ok(rows.into_iter().map(|row| (row.id, row.status)).collect())
+    }
+
     pub async fn update_status(
         pool: &SqlitePool,
         id: Uuid,
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::{Task, TaskStatus};
+
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct TaskAssignee {
+    pub task_id: Uuid,
+    pub user_id: Uuid,
+    #[ts(type = "Date")]
+    pub assigned_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct AssignTask {
+    pub user_id: Uuid,
+}
+
+impl TaskAssignee {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAssignee,
+            r#"SELECT task_id as "task_id!: Uuid",
+                      user_id as "user_id!: Uuid",
+                      assigned_at as "assigned_at!: DateTime<Utc>"
+               FROM task_assignees
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Open tasks assigned to the user, oldest assignment first.
+    pub async fn find_open_tasks_by_user_id(
+        pool: &SqlitePool,
+        user_id: Uuid,
+    ) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_workspace_id as "parent_workspace_id: Uuid", t.shared_task_id as "shared_task_id: Uuid", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_assignees a
+               JOIN tasks t ON t.id = a.task_id
+               WHERE a.user_id = $1 AND t.status NOT IN ('done', 'cancelled')
+               ORDER BY a.assigned_at ASC"#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replaces any existing assignee of the task.
+    pub async fn assign(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAssignee,
+            r#"INSERT INTO task_assignees (task_id, user_id)
+               VALUES ($1, $2)
+               ON CONFLICT(task_id) DO UPDATE SET
+                   user_id = excluded.user_id,
+                   assigned_at = datetime('now', 'subsec')
+               RETURNING task_id as "task_id!: Uuid",
+                         user_id as "user_id!: Uuid",
+                         assigned_at as "assigned_at!: DateTime<Utc>""#,
+            task_id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn unassign(pool: &SqlitePool, task_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_assignees WHERE task_id = $1", task_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A file of any type attached to a task and copied into its worktrees.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskAttachment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub file_path: String, // file name within cache/attachments/ and the worktree directory
+    pub original_name: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub hash: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateTaskAttachment {
+    pub task_id: Uuid,
+    pub file_path: String,
+    pub original_name: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub hash: String,
+}
+
+impl TaskAttachment {
+    pub async fn create(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &CreateTaskAttachment,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttachment,
+            r#"INSERT INTO task_attachments (id, task_id, file_path, original_name, mime_type, size_bytes, hash)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         file_path as "file_path!",
+                         original_name as "original_name!",
+                         mime_type as "mime_type!",
+                         size_bytes as "size_bytes!",
+                         hash as "hash!",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.task_id,
+            data.file_path,
+            data.original_name,
+            data.mime_type,
+            data.size_bytes,
+            data.hash,
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttachment,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      file_path as "file_path!",
+                      original_name as "original_name!",
+                      mime_type as "mime_type!",
+                      size_bytes as "size_bytes!",
+                      hash as "hash!",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_attachments
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttachment,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      file_path as "file_path!",
+                      original_name as "original_name!",
+                      mime_type as "mime_type!",
+                      size_bytes as "size_bytes!",
+                      hash as "hash!",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_attachments
+               WHERE task_id = $1
+               ORDER BY created_at"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(r#"DELETE FROM task_attachments WHERE id = $1"#, id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Path of the attachment relative to the workspace root.
+    pub fn worktree_path(&self) -> String {
+        format!("{}/{}", utils::path::VIBE_ATTACHMENTS_DIR, self.file_path)
+    }
+
+    /// Append a list of attached files to an agent prompt so the agent knows to read them.
+    pub fn append_to_prompt(prompt: String, attachments: &[TaskAttachment]) -> String {
+        if attachments.is_empty() {
+            return prompt;
+        }
+        let files = attachments
+            .iter()
+            .map(|a| {
+                format!(
+                    "- {} ({}, {} bytes): {}",
+                    a.original_name,
+                    a.mime_type,
+                    a.size_bytes,
+                    a.worktree_path()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "{prompt}\n\nThe following files are attached to this task as reference material. Read them as needed:\n{files}"
+        )
+    }
+}
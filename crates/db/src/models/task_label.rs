@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct TaskLabel {
+    pub task_id: Uuid,
+    pub label: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskLabel {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskLabel,
+            r#"SELECT task_id as "task_id!: Uuid",
+                      label,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_labels
+               WHERE task_id = $1
+               ORDER BY label ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Tasks of the project carrying `label`.
+    pub async fn find_task_ids_by_label(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        label: &str,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT l.task_id as "task_id!: Uuid"
+               FROM task_labels l
+               JOIN tasks t ON t.id = l.task_id
+               WHERE t.project_id = $1 AND l.label = $2"#,
+            project_id,
+            label
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Returns false when the task already has the label.
+    pub async fn add(pool: &SqlitePool, task_id: Uuid, label: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "INSERT OR IGNORE INTO task_labels (task_id, label) VALUES ($1, $2)",
+            task_id,
+            label
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn remove(pool: &SqlitePool, task_id: Uuid, label: &str) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM task_labels WHERE task_id = $1 AND label = $2",
+            task_id,
+            label
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
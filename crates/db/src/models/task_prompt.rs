@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// The prompt a task was created from, kept when its title and description were generated.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskPrompt {
+    pub task_id: Uuid,
+    pub original_prompt: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskPrompt {
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        original_prompt: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TaskPrompt,
+            r#"INSERT INTO task_prompts (task_id, original_prompt)
+               VALUES ($1, $2)
+               ON CONFLICT(task_id) DO UPDATE SET original_prompt = excluded.original_prompt
+               RETURNING task_id as "task_id!: Uuid",
+                         original_prompt as "original_prompt!",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            task_id,
+            original_prompt
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskPrompt,
+            r#"SELECT task_id as "task_id!: Uuid",
+                      original_prompt as "original_prompt!",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_prompts
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+/// A task entering a column. Rows are written by triggers on the tasks table.
+#[derive(Debug, Clone, FromRow)]
+pub struct TaskStatusChange {
+    pub task_id: Uuid,
+    pub status: TaskStatus,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl TaskStatusChange {
+    /// Status changes of all tasks in a project, grouped by task and oldest first.
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskStatusChange,
+            r#"SELECT c.task_id as "task_id!: Uuid",
+                      c.status as "status!: TaskStatus",
+                      c.changed_at as "changed_at!: DateTime<Utc>"
+               FROM task_status_changes c
+               JOIN tasks t ON t.id = c.task_id
+               WHERE t.project_id = $1
+               ORDER BY c.task_id, c.changed_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+/// A task currently in review, with when it last entered the column.
+#[derive(Debug, Clone, FromRow)]
+pub struct InReviewTask {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub title: String,
+    pub in_review_since: DateTime<Utc>,
+}
+
+impl InReviewTask {
+    pub async fn find_all(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            InReviewTask,
+            r#"SELECT task_id as "task_id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      title,
+                      in_review_since as "in_review_since!: DateTime<Utc>"
+               FROM (
+                   SELECT t.id AS task_id,
+                          t.project_id,
+                          t.title,
+                          COALESCE(
+                              (SELECT MAX(c.changed_at)
+                               FROM task_status_changes c
+                               WHERE c.task_id = t.id AND c.status = 'inreview'),
+                              t.updated_at
+                          ) AS in_review_since
+                   FROM tasks t
+                   WHERE t.status = 'inreview'
+                     AND ($1 IS NULL OR t.project_id = $1)
+               )
+               ORDER BY in_review_since ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
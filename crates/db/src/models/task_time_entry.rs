@@ -0,0 +1,138 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Time a person spent on a task, logged by hand.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct TaskTimeEntry {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// `null` in single-user mode
+    pub user_id: Option<Uuid>,
+    #[ts(type = "number")]
+    pub seconds: i64,
+    pub note: Option<String>,
+    #[ts(type = "Date")]
+    pub worked_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateTaskTimeEntry {
+    #[ts(type = "number")]
+    pub seconds: i64,
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Defaults to now
+    #[serde(default)]
+    #[ts(type = "Date | null")]
+    pub worked_at: Option<DateTime<Utc>>,
+}
+
+impl TaskTimeEntry {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTimeEntry,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      user_id as "user_id: Uuid",
+                      seconds,
+                      note,
+                      worked_at as "worked_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_time_entries
+               WHERE task_id = $1
+               ORDER BY worked_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Entries for work done since `since` on the project's tasks.
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTimeEntry,
+            r#"SELECT e.id as "id!: Uuid",
+                      e.task_id as "task_id!: Uuid",
+                      e.user_id as "user_id: Uuid",
+                      e.seconds,
+                      e.note,
+                      e.worked_at as "worked_at!: DateTime<Utc>",
+                      e.created_at as "created_at!: DateTime<Utc>"
+               FROM task_time_entries e
+               JOIN tasks t ON t.id = e.task_id
+               WHERE t.project_id = $1 AND e.worked_at >= $2
+               ORDER BY e.worked_at ASC"#,
+            project_id,
+            since
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTimeEntry,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      user_id as "user_id: Uuid",
+                      seconds,
+                      note,
+                      worked_at as "worked_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_time_entries
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        user_id: Option<Uuid>,
+        data: &CreateTaskTimeEntry,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let worked_at = data.worked_at.unwrap_or_else(Utc::now);
+        sqlx::query_as!(
+            TaskTimeEntry,
+            r#"INSERT INTO task_time_entries (id, task_id, user_id, seconds, note, worked_at)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         user_id as "user_id: Uuid",
+                         seconds,
+                         note,
+                         worked_at as "worked_at!: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            user_id,
+            data.seconds,
+            data.note,
+            worked_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_time_entries WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,207 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::Display;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Display)]
+#[sqlx(type_name = "transition_requirement", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TransitionRequirement {
+    /// At least one attempt was started
+    HasAttempt,
+    /// The latest run of the latest attempt, including its cleanup script, succeeded
+    VerificationPassed,
+    /// No agent of the task is waiting on an answer
+    NoPendingQuestions,
+}
+
+impl TransitionRequirement {
+    /// What to do so the requirement is met, shown when a move is rejected.
+    pub fn remediation(self) -> &'static str {
+        match self {
+            Self::HasAttempt => "start an attempt on the task first",
+            Self::VerificationPassed => {
+                "the latest attempt's last run did not pass; fix it and run it again"
+            }
+            Self::NoPendingQuestions => "answer the agent's pending questions first",
+        }
+    }
+
+    pub async fn is_met(self, pool: &SqlitePool, task_id: Uuid) -> Result<bool, sqlx::Error> {
+        match self {
+            Self::HasAttempt => {
+                let count: i64 = sqlx::query_scalar!(
+                    r#"SELECT COUNT(*) as "count!: i64" FROM workspaces WHERE task_id = $1"#,
+                    task_id
+                )
+                .fetch_one(pool)
+                .await?;
+                Ok(count > 0)
+            }
+            Self::VerificationPassed => {
+                let passed: Option<bool> = sqlx::query_scalar!(
+                    r#"SELECT ep.status = 'completed' as "passed!: bool"
+                       FROM execution_processes ep
+                       JOIN sessions s ON ep.session_id = s.id
+                       WHERE s.workspace_id = (
+                               SELECT id FROM workspaces
+                               WHERE task_id = $1
+                               ORDER BY created_at DESC
+                               LIMIT 1
+                           )
+                         AND ep.run_reason != 'devserver'
+                         AND ep.dropped = FALSE
+                       ORDER BY ep.created_at DESC
+                       LIMIT 1"#,
+                    task_id
+                )
+                .fetch_optional(pool)
+                .await?;
+                Ok(passed.unwrap_or(false))
+            }
+            Self::NoPendingQuestions => {
+                let count: i64 = sqlx::query_scalar!(
+                    r#"SELECT COUNT(*) as "count!: i64"
+                       FROM agent_questions q
+                       JOIN execution_processes ep ON q.execution_process_id = ep.id
+                       JOIN sessions s ON ep.session_id = s.id
+                       JOIN workspaces w ON s.workspace_id = w.id
+                       WHERE w.task_id = $1
+                         AND q.outcome = 'pending'
+                         AND ep.status = 'running'"#,
+                    task_id
+                )
+                .fetch_one(pool)
+                .await?;
+                Ok(count == 0)
+            }
+        }
+    }
+}
+
+/// A condition a task has to meet before moving to `to_status` in a project.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct TaskTransitionRule {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// `null` applies the rule to moves from any status
+    pub from_status: Option<TaskStatus>,
+    pub to_status: TaskStatus,
+    pub requirement: TransitionRequirement,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateTaskTransitionRule {
+    #[serde(default)]
+    pub from_status: Option<TaskStatus>,
+    pub to_status: TaskStatus,
+    pub requirement: TransitionRequirement,
+}
+
+impl TaskTransitionRule {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTransitionRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      from_status as "from_status: TaskStatus",
+                      to_status as "to_status!: TaskStatus",
+                      requirement as "requirement!: TransitionRequirement",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_transition_rules
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Rules that apply to moving a task of the project from `from` to `to`.
+    pub async fn find_for_transition(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        from: &TaskStatus,
+        to: &TaskStatus,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTransitionRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      from_status as "from_status: TaskStatus",
+                      to_status as "to_status!: TaskStatus",
+                      requirement as "requirement!: TransitionRequirement",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_transition_rules
+               WHERE project_id = $1
+                 AND to_status = $3
+                 AND (from_status IS NULL OR from_status = $2)
+               ORDER BY created_at ASC"#,
+            project_id,
+            from,
+            to
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTransitionRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      from_status as "from_status: TaskStatus",
+                      to_status as "to_status!: TaskStatus",
+                      requirement as "requirement!: TransitionRequirement",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_transition_rules
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateTaskTransitionRule,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskTransitionRule,
+            r#"INSERT INTO task_transition_rules (id, project_id, from_status, to_status, requirement)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         from_status as "from_status: TaskStatus",
+                         to_status as "to_status!: TaskStatus",
+                         requirement as "requirement!: TransitionRequirement",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.from_status,
+            data.to_status,
+            data.requirement
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_transition_rules WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,369 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{notification::NotificationKind, project::Project};
+
+#[derive(
+    Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, EnumString, Display,
+)]
+#[sqlx(type_name = "team_role", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum TeamRole {
+    /// Manages the team's settings, members and projects
+    Owner,
+    Member,
+}
+
+/// Notification settings applied to every project of a team.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct TeamNotificationDefaults {
+    /// Shared ntfy topic the team's notifications are also pushed to
+    pub ntfy_topic: Option<String>,
+    /// Shared Pushover user or group key the team's notifications are also pushed to
+    pub pushover_user_key: Option<String>,
+    /// Kinds that are not pushed to the team destinations
+    pub muted_kinds: Vec<NotificationKind>,
+}
+
+impl TeamNotificationDefaults {
+    pub fn pushes(&self, kind: NotificationKind) -> bool {
+        !self.muted_kinds.contains(&kind)
+    }
+}
+
+/// Group of users sharing a set of projects.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct Team {
+    pub id: Uuid,
+    pub name: String,
+    /// Executor the team's attempts default to; the instance default when unset
+    #[ts(type = "ExecutorProfileId | null")]
+    pub executor_profile: Option<Json<ExecutorProfileId>>,
+    #[ts(type = "TeamNotificationDefaults")]
+    pub notification_defaults: Json<TeamNotificationDefaults>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateTeam {
+    pub name: String,
+    pub executor_profile: Option<ExecutorProfileId>,
+    #[serde(default)]
+    pub notification_defaults: TeamNotificationDefaults,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateTeam {
+    pub name: Option<String>,
+    /// Replaces the shared executor; `null` falls back to the instance default
+    pub executor_profile: Option<ExecutorProfileId>,
+    pub notification_defaults: Option<TeamNotificationDefaults>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct TeamMember {
+    pub team_id: Uuid,
+    pub user_id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub role: TeamRole,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl Team {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Team,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      executor_profile as "executor_profile: Json<ExecutorProfileId>",
+                      notification_defaults as "notification_defaults!: Json<TeamNotificationDefaults>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM teams
+               ORDER BY name ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Teams the user is a member of.
+    pub async fn find_by_user_id(
+        pool: &SqlitePool,
+        user_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Team,
+            r#"SELECT t.id as "id!: Uuid",
+                      t.name,
+                      t.executor_profile as "executor_profile: Json<ExecutorProfileId>",
+                      t.notification_defaults as "notification_defaults!: Json<TeamNotificationDefaults>",
+                      t.created_at as "created_at!: DateTime<Utc>",
+                      t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM teams t
+               JOIN team_members m ON m.team_id = t.id
+               WHERE m.user_id = $1
+               ORDER BY t.name ASC"#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Team,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      executor_profile as "executor_profile: Json<ExecutorProfileId>",
+                      notification_defaults as "notification_defaults!: Json<TeamNotificationDefaults>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM teams
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// The team a project belongs to, if any.
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Team,
+            r#"SELECT t.id as "id!: Uuid",
+                      t.name,
+                      t.executor_profile as "executor_profile: Json<ExecutorProfileId>",
+                      t.notification_defaults as "notification_defaults!: Json<TeamNotificationDefaults>",
+                      t.created_at as "created_at!: DateTime<Utc>",
+                      t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM teams t
+               JOIN team_projects tp ON tp.team_id = t.id
+               WHERE tp.project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, data: &CreateTeam) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let executor_profile = data.executor_profile.as_ref().map(Json);
+        let notification_defaults = Json(&data.notification_defaults);
+        sqlx::query_as!(
+            Team,
+            r#"INSERT INTO teams (id, name, executor_profile, notification_defaults)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         name,
+                         executor_profile as "executor_profile: Json<ExecutorProfileId>",
+                         notification_defaults as "notification_defaults!: Json<TeamNotificationDefaults>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.name,
+            executor_profile,
+            notification_defaults
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        name: &str,
+        executor_profile: Option<&ExecutorProfileId>,
+        notification_defaults: &TeamNotificationDefaults,
+    ) -> Result<Self, sqlx::Error> {
+        let executor_profile = executor_profile.map(Json);
+        let notification_defaults = Json(notification_defaults);
+        sqlx::query_as!(
+            Team,
+            r#"UPDATE teams
+               SET name = $2,
+                   executor_profile = $3,
+                   notification_defaults = $4,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         name,
+                         executor_profile as "executor_profile: Json<ExecutorProfileId>",
+                         notification_defaults as "notification_defaults!: Json<TeamNotificationDefaults>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            executor_profile,
+            notification_defaults
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM teams WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn find_projects(
+        pool: &SqlitePool,
+        team_id: Uuid,
+    ) -> Result<Vec<Project>, sqlx::Error> {
+        sqlx::query_as!(
+            Project,
+            r#"SELECT p.id as "id!: Uuid",
+                      p.name,
+                      p.dev_script,
+                      p.dev_script_working_dir,
+                      p.default_agent_working_dir,
+                      p.remote_project_id as "remote_project_id: Uuid",
+                      p.created_at as "created_at!: DateTime<Utc>",
+                      p.updated_at as "updated_at!: DateTime<Utc>"
+               FROM projects p
+               JOIN team_projects tp ON tp.project_id = p.id
+               WHERE tp.team_id = $1
+               ORDER BY p.name ASC"#,
+            team_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Move a project into the team, out of any team it was in.
+    pub async fn add_project(
+        pool: &SqlitePool,
+        team_id: Uuid,
+        project_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO team_projects (project_id, team_id)
+               VALUES ($1, $2)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   team_id = excluded.team_id,
+                   created_at = datetime('now', 'subsec')"#,
+            project_id,
+            team_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn remove_project(
+        pool: &SqlitePool,
+        team_id: Uuid,
+        project_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM team_projects WHERE team_id = $1 AND project_id = $2",
+            team_id,
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl TeamMember {
+    pub async fn find_by_team_id(
+        pool: &SqlitePool,
+        team_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TeamMember,
+            r#"SELECT m.team_id as "team_id!: Uuid",
+                      m.user_id as "user_id!: Uuid",
+                      u.username,
+                      u.display_name,
+                      m.role as "role!: TeamRole",
+                      m.created_at as "created_at!: DateTime<Utc>"
+               FROM team_members m
+               JOIN users u ON u.id = m.user_id
+               WHERE m.team_id = $1
+               ORDER BY u.username ASC"#,
+            team_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_role(
+        pool: &SqlitePool,
+        team_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<TeamRole>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT role as "role!: TeamRole"
+               FROM team_members
+               WHERE team_id = $1 AND user_id = $2"#,
+            team_id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Add a user to the team or change their role.
+    pub async fn upsert(
+        pool: &SqlitePool,
+        team_id: Uuid,
+        user_id: Uuid,
+        role: TeamRole,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO team_members (team_id, user_id, role)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(team_id, user_id) DO UPDATE SET role = excluded.role"#,
+            team_id,
+            user_id,
+            role
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(
+        pool: &SqlitePool,
+        team_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM team_members WHERE team_id = $1 AND user_id = $2",
+            team_id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn count_owners(pool: &SqlitePool, team_id: Uuid) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM team_members
+               WHERE team_id = $1 AND role = 'owner'"#,
+            team_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
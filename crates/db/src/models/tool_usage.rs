@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq)]
+#[sqlx(type_name = "tool_usage_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ToolUsageStatus {
+    Succeeded,
+    Failed,
+    Denied,
+    TimedOut,
+    /// Still running or waiting when the agent stopped
+    Unfinished,
+}
+
+/// A tool call made by a coding agent, taken from its normalized log once the run ends.
+#[derive(Debug, Clone, FromRow)]
+pub struct ToolUsage {
+    pub tool_name: String,
+    pub status: ToolUsageStatus,
+    pub approval_wait_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateToolUsage {
+    pub tool_name: String,
+    pub status: ToolUsageStatus,
+    pub approval_wait_ms: Option<i64>,
+}
+
+impl ToolUsage {
+    pub async fn create_many(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        usages: &[CreateToolUsage],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for usage in usages {
+            let id = Uuid::new_v4();
+            sqlx::query!(
+                r#"INSERT INTO tool_usages (id, execution_process_id, tool_name, status, approval_wait_ms)
+                   VALUES ($1, $2, $3, $4, $5)"#,
+                id,
+                execution_process_id,
+                usage.tool_name,
+                usage.status,
+                usage.approval_wait_ms
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Tool calls recorded since `since` for agents working on the project's tasks.
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ToolUsage,
+            r#"SELECT u.tool_name,
+                      u.status as "status!: ToolUsageStatus",
+                      u.approval_wait_ms as "approval_wait_ms?: i64"
+               FROM tool_usages u
+               JOIN execution_processes ep ON ep.id = u.execution_process_id
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE t.project_id = $1 AND u.created_at >= $2
+               ORDER BY u.created_at ASC"#,
+            project_id,
+            since
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
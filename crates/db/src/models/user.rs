@@ -0,0 +1,245 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(
+    Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, EnumString, Display,
+)]
+#[sqlx(type_name = "user_role", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum UserRole {
+    Admin,
+    Member,
+    Reviewer,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct User {
+    pub id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub email: Option<String>,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub password_hash: Option<String>,
+    pub role: UserRole,
+    pub disabled: bool,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateUser {
+    pub username: String,
+    pub password: Option<String>,
+    pub display_name: Option<String>,
+    pub email: Option<String>,
+    pub role: UserRole,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateUser {
+    pub display_name: Option<String>,
+    pub email: Option<String>,
+    pub role: Option<UserRole>,
+    pub disabled: Option<bool>,
+    /// Replace the user's password
+    pub password: Option<String>,
+}
+
+impl User {
+    pub async fn count(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(r#"SELECT COUNT(*) as "count!: i64" FROM users"#)
+            .fetch_one(pool)
+            .await
+    }
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid",
+                      username,
+                      display_name,
+                      email,
+                      password_hash,
+                      role as "role!: UserRole",
+                      disabled as "disabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM users
+               ORDER BY username ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid",
+                      username,
+                      display_name,
+                      email,
+                      password_hash,
+                      role as "role!: UserRole",
+                      disabled as "disabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM users
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_username(
+        pool: &SqlitePool,
+        username: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid",
+                      username,
+                      display_name,
+                      email,
+                      password_hash,
+                      role as "role!: UserRole",
+                      disabled as "disabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM users
+               WHERE username = $1"#,
+            username
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Insert a user. `password_hash` must already be hashed.
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateUser,
+        password_hash: Option<&str>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            User,
+            r#"INSERT INTO users (id, username, display_name, email, password_hash, role)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         username,
+                         display_name,
+                         email,
+                         password_hash,
+                         role as "role!: UserRole",
+                         disabled as "disabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.username,
+            data.display_name,
+            data.email,
+            password_hash,
+            data.role
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        display_name: Option<&str>,
+        email: Option<&str>,
+        role: UserRole,
+        disabled: bool,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"UPDATE users
+               SET display_name = $2, email = $3, role = $4, disabled = $5,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         username,
+                         display_name,
+                         email,
+                         password_hash,
+                         role as "role!: UserRole",
+                         disabled as "disabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            display_name,
+            email,
+            role,
+            disabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_password_hash(
+        pool: &SqlitePool,
+        id: Uuid,
+        password_hash: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE users SET password_hash = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            id,
+            password_hash
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn count_active_admins(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64" FROM users WHERE role = 'admin' AND disabled = FALSE"#
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM users WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub fn can(&self, permission: Permission) -> bool {
+        !self.disabled && self.role.grants(permission)
+    }
+}
+
+/// Coarse-grained capabilities checked by the API layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    /// View projects, tasks, attempts and logs
+    Read,
+    /// Create and modify projects, tasks and attempts
+    Write,
+    /// Respond to approvals and review agent work
+    Review,
+    /// Manage users and server-wide settings
+    Admin,
+}
+
+impl UserRole {
+    pub fn grants(self, permission: Permission) -> bool {
+        match self {
+            UserRole::Admin => true,
+            UserRole::Member => matches!(permission, Permission::Read | Permission::Write),
+            UserRole::Reviewer => matches!(permission, Permission::Read | Permission::Review),
+        }
+    }
+}
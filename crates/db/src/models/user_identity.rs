@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+/// A user's account at an OpenID Connect identity provider, keyed by the provider's issuer and
+/// the `sub` claim it assigns.
+#[derive(Debug, Clone, FromRow)]
+pub struct UserIdentity {
+    pub issuer: String,
+    pub subject: String,
+    pub user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserIdentity {
+    pub async fn find(
+        pool: &SqlitePool,
+        issuer: &str,
+        subject: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            UserIdentity,
+            r#"SELECT issuer,
+                      subject,
+                      user_id as "user_id!: Uuid",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM user_identities
+               WHERE issuer = $1 AND subject = $2"#,
+            issuer,
+            subject
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        issuer: &str,
+        subject: &str,
+        user_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            UserIdentity,
+            r#"INSERT INTO user_identities (issuer, subject, user_id)
+               VALUES ($1, $2, $3)
+               RETURNING issuer,
+                         subject,
+                         user_id as "user_id!: Uuid",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            issuer,
+            subject,
+            user_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
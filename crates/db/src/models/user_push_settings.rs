@@ -0,0 +1,88 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Where a user's phone notifications are delivered.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct UserPushSettings {
+    pub user_id: Uuid,
+    pub ntfy_topic: Option<String>,
+    pub pushover_user_key: Option<String>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateUserPushSettings {
+    pub ntfy_topic: Option<String>,
+    pub pushover_user_key: Option<String>,
+}
+
+impl UserPushSettings {
+    pub async fn find_by_user_id(
+        pool: &SqlitePool,
+        user_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            UserPushSettings,
+            r#"SELECT user_id as "user_id!: Uuid",
+                      ntfy_topic,
+                      pushover_user_key,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM user_push_settings
+               WHERE user_id = $1"#,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Settings of enabled users that have at least one destination configured.
+    pub async fn find_active(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            UserPushSettings,
+            r#"SELECT s.user_id as "user_id!: Uuid",
+                      s.ntfy_topic,
+                      s.pushover_user_key,
+                      s.created_at as "created_at!: DateTime<Utc>",
+                      s.updated_at as "updated_at!: DateTime<Utc>"
+               FROM user_push_settings s
+               JOIN users u ON u.id = s.user_id
+               WHERE u.disabled = FALSE
+                 AND (s.ntfy_topic IS NOT NULL OR s.pushover_user_key IS NOT NULL)"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        user_id: Uuid,
+        data: &UpdateUserPushSettings,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            UserPushSettings,
+            r#"INSERT INTO user_push_settings (user_id, ntfy_topic, pushover_user_key)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(user_id) DO UPDATE SET
+                   ntfy_topic = excluded.ntfy_topic,
+                   pushover_user_key = excluded.pushover_user_key,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING user_id as "user_id!: Uuid",
+                         ntfy_topic,
+                         pushover_user_key,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            user_id,
+            data.ntfy_topic,
+            data.pushover_user_key
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+/// A login session. Only the SHA-256 of the session token is persisted.
+#[derive(Debug, Clone, FromRow)]
+pub struct UserSession {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserSession {
+    pub async fn create(
+        pool: &SqlitePool,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            UserSession,
+            r#"INSERT INTO user_sessions (id, user_id, token_hash, expires_at)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         user_id as "user_id!: Uuid",
+                         token_hash,
+                         expires_at as "expires_at!: DateTime<Utc>",
+                         last_seen_at as "last_seen_at!: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            user_id,
+            token_hash,
+            expires_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_token_hash(
+        pool: &SqlitePool,
+        token_hash: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            UserSession,
+            r#"SELECT id as "id!: Uuid",
+                      user_id as "user_id!: Uuid",
+                      token_hash,
+                      expires_at as "expires_at!: DateTime<Utc>",
+                      last_seen_at as "last_seen_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM user_sessions
+               WHERE token_hash = $1"#,
+            token_hash
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn touch(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE user_sessions SET last_seen_at = datetime('now', 'subsec') WHERE id = $1",
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!("DELETE FROM user_sessions WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_for_user(pool: &SqlitePool, user_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM user_sessions WHERE user_id = $1", user_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete_expired(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+        let now = Utc::now();
+        let result = sqlx::query!("DELETE FROM user_sessions WHERE expires_at < $1", now)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -1,8 +1,10 @@
 use chrono::{DateTime, Utc};
+use executors::executors::claude::types::PermissionMode;
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
+use utils::pagination::{Keyset, Paginate, SortField, SortOrder};
 use uuid::Uuid;
 
 use super::{
@@ -51,6 +53,12 @@ pub struct Workspace {
     pub container_ref: Option<String>,
     pub branch: String,
     pub agent_working_dir: Option<String>,
+    /// Investigate-only attempt: the agent gets read-only tools and can't change the worktree
+    pub read_only: bool,
+    /// Dry-run attempt: tool calls that may change the worktree are logged instead of run
+    pub dry_run: bool,
+    /// Permission mode picked for the attempt; the executor profile's applies when unset
+    pub permission_mode: Option<PermissionMode>,
     pub setup_completed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -87,10 +95,26 @@ pub struct WorkspaceContext {
     pub workspace_repos: Vec<RepoWithTargetBranch>,
 }
 
+impl Paginate for Workspace {
+    fn page_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn sort_value(&self, field: SortField) -> DateTime<Utc> {
+        match field {
+            SortField::CreatedAt => self.created_at,
+            SortField::UpdatedAt => self.updated_at,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, TS)]
 pub struct CreateWorkspace {
     pub branch: String,
     pub agent_working_dir: Option<String>,
+    pub read_only: bool,
+    pub dry_run: bool,
+    pub permission_mode: Option<PermissionMode>,
 }
 
 impl Workspace {
@@ -103,44 +127,81 @@ impl Workspace {
         pool: &SqlitePool,
         task_id: Option<Uuid>,
     ) -> Result<Vec<Self>, WorkspaceError> {
-        let workspaces = match task_id {
-            Some(tid) => sqlx::query_as!(
-                Workspace,
-                r#"SELECT id AS "id!: Uuid",
-                              task_id AS "task_id!: Uuid",
-                              container_ref,
-                              branch,
-                              agent_working_dir,
-                              setup_completed_at AS "setup_completed_at: DateTime<Utc>",
-                              created_at AS "created_at!: DateTime<Utc>",
-                              updated_at AS "updated_at!: DateTime<Utc>"
-                       FROM workspaces
-                       WHERE task_id = $1
-                       ORDER BY created_at DESC"#,
-                tid
-            )
-            .fetch_all(pool)
-            .await
-            .map_err(WorkspaceError::Database)?,
-            None => sqlx::query_as!(
-                Workspace,
-                r#"SELECT id AS "id!: Uuid",
-                              task_id AS "task_id!: Uuid",
-                              container_ref,
-                              branch,
-                              agent_working_dir,
-                              setup_completed_at AS "setup_completed_at: DateTime<Utc>",
-                              created_at AS "created_at!: DateTime<Utc>",
-                              updated_at AS "updated_at!: DateTime<Utc>"
-                       FROM workspaces
-                       ORDER BY created_at DESC"#
-            )
-            .fetch_all(pool)
-            .await
-            .map_err(WorkspaceError::Database)?,
-        };
+        Self::fetch_page(pool, task_id, &Keyset::all(SortOrder::Desc)).await
+    }
+
+    /// One page of the attempts listing, optionally for a single task, sorted and cut in SQL.
+    pub async fn fetch_page(
+        pool: &SqlitePool,
+        task_id: Option<Uuid>,
+        keyset: &Keyset,
+    ) -> Result<Vec<Self>, WorkspaceError> {
+        let by_updated_at = keyset.by_updated_at();
+        let descending = keyset.descending();
+        let after_value = keyset.after_value();
+        let after_id = keyset.after_id();
+        let limit = keyset.fetch_limit();
+        sqlx::query_as!(
+            Workspace,
+            r#"SELECT id AS "id!: Uuid",
+                      task_id AS "task_id!: Uuid",
+                      container_ref,
+                      branch,
+                      agent_working_dir,
+                      read_only AS "read_only!: bool",
+                      dry_run AS "dry_run!: bool",
+                      permission_mode AS "permission_mode: PermissionMode",
+                      setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                      created_at AS "created_at!: DateTime<Utc>",
+                      updated_at AS "updated_at!: DateTime<Utc>"
+               FROM workspaces
+               WHERE ($1 IS NULL OR task_id = $1)
+                 AND ($4 IS NULL OR CASE WHEN $3
+                   THEN (julianday(CASE WHEN $2 THEN updated_at ELSE created_at END), id) < (julianday($4), $5)
+                   ELSE (julianday(CASE WHEN $2 THEN updated_at ELSE created_at END), id) > (julianday($4), $5)
+                 END)
+               ORDER BY
+                 CASE WHEN $3 THEN julianday(CASE WHEN $2 THEN updated_at ELSE created_at END) END DESC,
+                 CASE WHEN $3 THEN id END DESC,
+                 julianday(CASE WHEN $2 THEN updated_at ELSE created_at END) ASC,
+                 id ASC
+               LIMIT $6"#,
+            task_id,
+            by_updated_at,
+            descending,
+            after_value,
+            after_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(WorkspaceError::Database)
+    }
 
-        Ok(workspaces)
+    pub async fn count(pool: &SqlitePool, task_id: Option<Uuid>) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!: i64" FROM workspaces WHERE ($1 IS NULL OR task_id = $1)"#,
+            task_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Number of attempts of each task in a project, including tasks without any.
+    pub async fn count_per_task_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<i64>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(w.id) as "count!: i64"
+               FROM tasks t
+               LEFT JOIN workspaces w ON w.task_id = t.id
+               WHERE t.project_id = $1
+               GROUP BY t.id"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
     }
 
     /// Load workspace with full validation - ensures workspace belongs to task and task belongs to project
@@ -157,6 +218,9 @@ impl Workspace {
                        w.container_ref,
                        w.branch,
                        w.agent_working_dir,
+                       w.read_only         AS "read_only!: bool",
+                       w.dry_run           AS "dry_run!: bool",
+                       w.permission_mode   AS "permission_mode: PermissionMode",
                        w.setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                        w.created_at        AS "created_at!: DateTime<Utc>",
                        w.updated_at        AS "updated_at!: DateTime<Utc>"
@@ -231,6 +295,9 @@ impl Workspace {
                        container_ref,
                        branch,
                        agent_working_dir,
+                       read_only         AS "read_only!: bool",
+                       dry_run           AS "dry_run!: bool",
+                       permission_mode   AS "permission_mode: PermissionMode",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
@@ -250,6 +317,9 @@ impl Workspace {
                        container_ref,
                        branch,
                        agent_working_dir,
+                       read_only         AS "read_only!: bool",
+                       dry_run           AS "dry_run!: bool",
+                       permission_mode   AS "permission_mode: PermissionMode",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
@@ -288,6 +358,9 @@ impl Workspace {
                 w.container_ref,
                 w.branch as "branch!",
                 w.agent_working_dir,
+                w.read_only as "read_only!: bool",
+                w.dry_run as "dry_run!: bool",
+                w.permission_mode as "permission_mode: PermissionMode",
                 w.setup_completed_at as "setup_completed_at: DateTime<Utc>",
                 w.created_at as "created_at!: DateTime<Utc>",
                 w.updated_at as "updated_at!: DateTime<Utc>"
@@ -330,14 +403,17 @@ impl Workspace {
     ) -> Result<Self, WorkspaceError> {
         Ok(sqlx::query_as!(
             Workspace,
-            r#"INSERT INTO workspaces (id, task_id, container_ref, branch, agent_working_dir, setup_completed_at)
-               VALUES ($1, $2, $3, $4, $5, $6)
-               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, agent_working_dir, setup_completed_at as "setup_completed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO workspaces (id, task_id, container_ref, branch, agent_working_dir, read_only, dry_run, permission_mode, setup_completed_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, agent_working_dir, read_only as "read_only!: bool", dry_run as "dry_run!: bool", permission_mode as "permission_mode: PermissionMode", setup_completed_at as "setup_completed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             task_id,
             Option::<String>::None,
             data.branch,
             data.agent_working_dir,
+            data.read_only,
+            data.dry_run,
+            data.permission_mode,
             Option::<DateTime<Utc>>::None
         )
         .fetch_one(pool)
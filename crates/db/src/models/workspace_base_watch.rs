@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// An attempt kept rebased onto its base branches as new commits land there.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct WorkspaceBaseWatch {
+    pub workspace_id: Uuid,
+    /// Ask the agent to reconcile its work with the new commits after each rebase
+    pub follow_up: bool,
+    /// Base branch commit last handled, per repository
+    #[serde(skip)]
+    #[ts(skip)]
+    pub seen_base_oids: Json<BTreeMap<Uuid, String>>,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct SetWorkspaceBaseWatch {
+    #[serde(default)]
+    pub follow_up: bool,
+}
+
+impl WorkspaceBaseWatch {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceBaseWatch,
+            r#"SELECT workspace_id as "workspace_id!: Uuid",
+                      follow_up as "follow_up!: bool",
+                      seen_base_oids as "seen_base_oids!: Json<BTreeMap<Uuid, String>>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM workspace_base_watches"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceBaseWatch,
+            r#"SELECT workspace_id as "workspace_id!: Uuid",
+                      follow_up as "follow_up!: bool",
+                      seen_base_oids as "seen_base_oids!: Json<BTreeMap<Uuid, String>>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM workspace_base_watches
+               WHERE workspace_id = $1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Start watching, or change the options of an existing watch.
+    pub async fn upsert(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        data: &SetWorkspaceBaseWatch,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceBaseWatch,
+            r#"INSERT INTO workspace_base_watches (workspace_id, follow_up)
+               VALUES ($1, $2)
+               ON CONFLICT(workspace_id) DO UPDATE
+                   SET follow_up = excluded.follow_up,
+                       updated_at = datetime('now', 'subsec')
+               RETURNING workspace_id as "workspace_id!: Uuid",
+                         follow_up as "follow_up!: bool",
+                         seen_base_oids as "seen_base_oids!: Json<BTreeMap<Uuid, String>>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            workspace_id,
+            data.follow_up
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_seen_base_oids(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        seen_base_oids: &BTreeMap<Uuid, String>,
+    ) -> Result<(), sqlx::Error> {
+        let seen_base_oids = Json(seen_base_oids);
+        sqlx::query!(
+            r#"UPDATE workspace_base_watches
+               SET seen_base_oids = $2, updated_at = datetime('now', 'subsec')
+               WHERE workspace_id = $1"#,
+            workspace_id,
+            seen_base_oids
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, workspace_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM workspace_base_watches WHERE workspace_id = $1",
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
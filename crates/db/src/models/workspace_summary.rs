@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Short summary of what an attempt changed and why, written for reviewers.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WorkspaceSummary {
+    pub workspace_id: Uuid,
+    pub summary: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WorkspaceSummary {
+    pub async fn upsert(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        summary: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceSummary,
+            r#"INSERT INTO workspace_summaries (workspace_id, summary)
+               VALUES ($1, $2)
+               ON CONFLICT(workspace_id) DO UPDATE SET
+                   summary = excluded.summary,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING workspace_id as "workspace_id!: Uuid",
+                         summary as "summary!",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            workspace_id,
+            summary
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceSummary,
+            r#"SELECT workspace_id as "workspace_id!: Uuid",
+                      summary as "summary!",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM workspace_summaries
+               WHERE workspace_id = $1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Summary of the task's newest attempt that has one.
+    pub async fn find_latest_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceSummary,
+            r#"SELECT s.workspace_id as "workspace_id!: Uuid",
+                      s.summary as "summary!",
+                      s.created_at as "created_at!: DateTime<Utc>",
+                      s.updated_at as "updated_at!: DateTime<Utc>"
+               FROM workspace_summaries s
+               JOIN workspaces w ON w.id = s.workspace_id
+               WHERE w.task_id = $1
+               ORDER BY w.created_at DESC
+               LIMIT 1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
@@ -16,9 +16,14 @@ use futures::{StreamExt, TryStreamExt};
 use git2::Error as Git2Error;
 use serde_json::Value;
 use services::services::{
+    accounts::AccountService,
     analytics::{AnalyticsContext, AnalyticsService},
+    analytics_export::AnalyticsExport,
     approvals::Approvals,
+    attachment::AttachmentService,
     auth::AuthContext,
+    backup::BackupService,
+    backup_scheduler::BackupSchedulerService,
     config::{Config, ConfigError},
     container::{ContainerError, ContainerService},
     events::{EventError, EventService},
@@ -26,12 +31,25 @@ use services::services::{
     filesystem::{FilesystemError, FilesystemService},
     filesystem_watcher::FilesystemWatcherError,
     git::{GitService, GitServiceError},
+    github_issues::GitHubIssueSyncMonitor,
     image::{ImageError, ImageService},
+    llm::LlmService,
+    maintenance::MaintenanceService,
+    oidc::OidcService,
+    outbox::OutboxWorker,
     pr_monitor::PrMonitorService,
+    presence::PresenceService,
     project::ProjectService,
+    project_env::ProjectEnvService,
     queued_message::QueuedMessageService,
     repo::RepoService,
+    review_sla::ReviewSlaMonitor,
+    secret_cipher::SecretCipherError,
+    secret_vault::SecretVault,
+    server_config::ServerConfigService,
     share::SharePublisher,
+    stale_tasks::StaleTaskMonitor,
+    tunnel::TunnelService,
     user_questions::UserQuestions,
     worktree_manager::WorktreeError,
 };
@@ -72,6 +90,8 @@ pub enum DeploymentError {
     Event(#[from] EventError),
     #[error(transparent)]
     Config(#[from] ConfigError),
+    #[error(transparent)]
+    SecretCipher(#[from] SecretCipherError),
     #[error("Remote client not configured")]
     RemoteClientNotConfigured,
     #[error(transparent)]
@@ -90,6 +110,8 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn analytics(&self) -> &Option<AnalyticsService>;
 
+    fn analytics_export(&self) -> &Option<AnalyticsExport>;
+
     fn container(&self) -> &impl ContainerService;
 
     fn git(&self) -> &GitService;
@@ -98,8 +120,30 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn repo(&self) -> &RepoService;
 
+    fn project_env(&self) -> &ProjectEnvService;
+
+    fn secret_vault(&self) -> &SecretVault;
+
+    fn server_config(&self) -> &ServerConfigService;
+
+    fn accounts(&self) -> &AccountService;
+
+    fn oidc(&self) -> &OidcService;
+
+    fn tunnels(&self) -> &TunnelService;
+
+    fn presence(&self) -> &PresenceService;
+
+    fn backups(&self) -> &BackupService;
+
+    fn maintenance(&self) -> &MaintenanceService;
+
+    fn llm(&self) -> &LlmService;
+
     fn image(&self) -> &ImageService;
 
+    fn attachments(&self) -> &AttachmentService;
+
     fn filesystem(&self) -> &FilesystemService;
 
     fn events(&self) -> &EventService;
@@ -139,7 +183,47 @@ pub trait Deployment: Clone + Send + Sync + 'static {
         PrMonitorService::spawn(db, analytics, publisher).await
     }
 
+    async fn spawn_backup_scheduler(&self) -> tokio::task::JoinHandle<()> {
+        BackupSchedulerService::spawn(self.backups().clone(), self.config().clone()).await
+    }
+
+    fn spawn_maintenance_scheduler(&self) -> tokio::task::JoinHandle<()> {
+        self.maintenance().spawn_scheduler(self.config().clone())
+    }
+
+    async fn spawn_review_sla_monitor(&self) -> tokio::task::JoinHandle<()> {
+        ReviewSlaMonitor::spawn(
+            self.db().clone(),
+            self.config().clone(),
+            self.container().notification_service().clone(),
+            self.approvals().clone(),
+            self.user_questions().clone(),
+        )
+        .await
+    }
+
+    async fn spawn_stale_task_monitor(&self) -> tokio::task::JoinHandle<()> {
+        StaleTaskMonitor::spawn(
+            self.db().clone(),
+            self.config().clone(),
+            self.container().notification_service().clone(),
+            self.share_publisher().ok(),
+        )
+        .await
+    }
+
+    async fn spawn_github_issue_sync(&self) -> tokio::task::JoinHandle<()> {
+        GitHubIssueSyncMonitor::spawn(self.db().clone()).await
+    }
+
+    async fn spawn_outbox_worker(&self) -> tokio::task::JoinHandle<()> {
+        OutboxWorker::spawn(self.db().clone(), self.project_env().cipher().clone()).await
+    }
+
     async fn track_if_analytics_allowed(&self, event_name: &str, properties: Value) {
+        if let Some(export) = self.analytics_export() {
+            export.record(self.user_id(), event_name, properties.clone());
+        }
         let analytics_enabled = self.config().read().await.analytics_enabled;
         // Track events unless user has explicitly opted out
         if analytics_enabled && let Some(analytics) = self.analytics() {
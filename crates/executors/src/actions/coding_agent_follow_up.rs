@@ -24,6 +24,9 @@ pub struct CodingAgentFollowUpRequest {
     /// If None, uses the container_ref directory directly.
     #[serde(default)]
     pub working_dir: Option<String>,
+    /// Model to run instead of the profile's, set when falling back after a failed run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
 }
 
 impl CodingAgentFollowUpRequest {
@@ -57,11 +60,15 @@ impl Executable for CodingAgentFollowUpRequest {
             .ok_or(ExecutorError::UnknownExecutorType(
                 executor_profile_id.to_string(),
             ))?;
+        agent.ensure_supports(env)?;
 
         agent.use_approvals(approvals.clone());
         if let Some(questions) = questions {
             agent.use_questions(questions);
         }
+        if let Some(model) = &self.model {
+            agent.use_model(model.clone());
+        }
 
         agent
             .spawn_follow_up(&effective_dir, &self.prompt, &self.session_id, env)
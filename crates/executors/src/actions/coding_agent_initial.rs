@@ -23,6 +23,9 @@ pub struct CodingAgentInitialRequest {
     /// If None, uses the container_ref directory directly.
     #[serde(default)]
     pub working_dir: Option<String>,
+    /// Model to run instead of the profile's, set when falling back after a failed run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
 }
 
 impl CodingAgentInitialRequest {
@@ -52,11 +55,15 @@ impl Executable for CodingAgentInitialRequest {
             .ok_or(ExecutorError::UnknownExecutorType(
                 executor_profile_id.to_string(),
             ))?;
+        agent.ensure_supports(env)?;
 
         agent.use_approvals(approvals.clone());
         if let Some(questions) = questions {
             agent.use_questions(questions);
         }
+        if let Some(model) = &self.model {
+            agent.use_model(model.clone());
+        }
 
         agent.spawn(&effective_dir, &self.prompt, env).await
     }
@@ -13,6 +13,7 @@ use crate::{
     approvals::{ExecutorApprovalService, ExecutorQuestionService},
     env::ExecutionEnv,
     executors::{BaseCodingAgent, ExecutorError, SpawnedChild},
+    profile::ExecutorProfileId,
 };
 pub mod coding_agent_follow_up;
 pub mod coding_agent_initial;
@@ -63,6 +64,28 @@ impl ExecutorAction {
             ExecutorActionType::ScriptRequest(_) => None,
         }
     }
+
+    /// Model the coding agent runs with in place of the profile's, if any
+    pub fn model_override(&self) -> Option<&str> {
+        match self.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => request.model.as_deref(),
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => request.model.as_deref(),
+            ExecutorActionType::ScriptRequest(_) => None,
+        }
+    }
+
+    /// Profile of the coding agent the action runs, if it runs one
+    pub fn executor_profile_id(&self) -> Option<&ExecutorProfileId> {
+        match self.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                Some(&request.executor_profile_id)
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                Some(&request.executor_profile_id)
+            }
+            ExecutorActionType::ScriptRequest(_) => None,
+        }
+    }
 }
 
 #[async_trait]
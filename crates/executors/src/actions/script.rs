@@ -1,7 +1,6 @@
 use std::{path::Path, sync::Arc};
 
 use async_trait::async_trait;
-use command_group::AsyncCommandGroup;
 use serde::{Deserialize, Serialize};
 use tokio::process::Command;
 use ts_rs::TS;
@@ -25,6 +24,7 @@ pub enum ScriptContext {
     CleanupScript,
     DevServer,
     ToolInstallScript,
+    LifecycleHook,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -67,7 +67,9 @@ impl Executable for ScriptRequest {
         // Apply environment variables
         env.apply_to_command(&mut command);
 
-        let child = command.group_spawn()?;
+        let mut child = env.spawn(&mut command)?;
+        // Remote runs always pipe stdin; close it so scripts see EOF as they would locally
+        drop(child.inner().stdin.take());
 
         Ok(child.into())
     }
@@ -52,10 +52,8 @@ pub struct CmdOverrides {
     )]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub additional_params: Option<Vec<String>>,
-    #[schemars(
-        title = "Environment Variables",
-        description = "Environment variables to set when running the executor"
-    )]
+    /// Environment variables to set when running the executor. Values may reference secrets with ${NAME}
+    #[schemars(title = "Environment Variables")]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub env: Option<HashMap<String, String>>,
 }
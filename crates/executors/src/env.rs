@@ -1,19 +1,52 @@
 use std::collections::HashMap;
 
+use command_group::{AsyncCommandGroup, AsyncGroupChild};
 use tokio::process::Command;
 
-use crate::command::CmdOverrides;
+use crate::{
+    command::CmdOverrides,
+    executors::claude::types::PermissionMode,
+    kubernetes::KubernetesJob,
+    limits::ResourceLimits,
+    mcp_config::{McpServerSpec, expand_vars},
+    remote::RemoteRunner,
+    sandbox::DockerSandbox,
+};
 
 /// Environment variables to inject into executor processes
 #[derive(Debug, Clone, Default)]
 pub struct ExecutionEnv {
     pub vars: HashMap<String, String>,
+    /// MCP servers declared by the project, for executors that accept per-run MCP config
+    pub mcp_servers: Vec<McpServerSpec>,
+    /// Remote host to run the process on instead of this machine
+    pub remote: Option<RemoteRunner>,
+    /// Kubernetes Job to run the process as instead of a local process
+    pub kubernetes: Option<KubernetesJob>,
+    /// Docker container to run the process in instead of directly on the host
+    pub sandbox: Option<DockerSandbox>,
+    /// Memory and CPU limits for a process running on this machine
+    pub limits: ResourceLimits,
+    /// Investigate-only attempt: coding agents must be restricted to read-only tools
+    pub read_only: bool,
+    /// Dry-run attempt: coding agents log the tool calls that may write instead of running them
+    pub dry_run: bool,
+    /// Permission mode picked for the attempt, overriding the one implied by the profile
+    pub permission_mode: Option<PermissionMode>,
 }
 
 impl ExecutionEnv {
     pub fn new() -> Self {
         Self {
             vars: HashMap::new(),
+            mcp_servers: Vec::new(),
+            remote: None,
+            kubernetes: None,
+            sandbox: None,
+            limits: ResourceLimits::default(),
+            read_only: false,
+            dry_run: false,
+            permission_mode: None,
         }
     }
 
@@ -34,10 +67,16 @@ impl ExecutionEnv {
         self
     }
 
-    /// Return a new env with profile env from CmdOverrides merged in.
+    /// Return a new env with profile env from CmdOverrides merged in. Profile values may
+    /// reference runtime variables such as vault secrets with `${NAME}`, so a profile variant
+    /// can select which provider account it runs with.
     pub fn with_profile(self, cmd: &CmdOverrides) -> Self {
         if let Some(ref profile_env) = cmd.env {
-            self.with_overrides(profile_env)
+            let expanded: HashMap<String, String> = profile_env
+                .iter()
+                .map(|(key, value)| (key.clone(), expand_vars(value, &self.vars)))
+                .collect();
+            self.with_overrides(&expanded)
         } else {
             self
         }
@@ -50,6 +89,22 @@ impl ExecutionEnv {
         }
     }
 
+    /// Spawn a fully configured command: over SSH when a remote runner is set, otherwise as a
+    /// Kubernetes Job or in a Docker container when one of those is
+    pub fn spawn(&self, command: &mut Command) -> std::io::Result<AsyncGroupChild> {
+        if let Some(remote) = &self.remote {
+            remote.spawn(command)
+        } else if let Some(job) = &self.kubernetes {
+            job.spawn(command)
+        } else if let Some(sandbox) = &self.sandbox {
+            sandbox.spawn(command)
+        } else if !self.limits.is_empty() {
+            self.limits.spawn(command)
+        } else {
+            command.group_spawn()
+        }
+    }
+
     pub fn contains_key(&self, key: &str) -> bool {
         self.vars.contains_key(key)
     }
@@ -75,4 +130,21 @@ mod tests {
         assert_eq!(merged.vars.get("FOO").unwrap(), "profile"); // overrides
         assert_eq!(merged.vars.get("BAR").unwrap(), "profile");
     }
+
+    #[test]
+    fn profile_env_can_reference_runtime_vars() {
+        let mut base = ExecutionEnv::default();
+        base.insert("ACME_ANTHROPIC_API_KEY", "sk-acme");
+
+        let cmd = CmdOverrides {
+            env: Some(HashMap::from([(
+                "ANTHROPIC_API_KEY".to_string(),
+                "${ACME_ANTHROPIC_API_KEY}".to_string(),
+            )])),
+            ..Default::default()
+        };
+
+        let merged = base.with_profile(&cmd);
+        assert_eq!(merged.vars.get("ANTHROPIC_API_KEY").unwrap(), "sk-acme");
+    }
 }
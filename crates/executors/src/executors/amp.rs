@@ -1,7 +1,6 @@
 use std::{path::Path, process::Stdio, sync::Arc};
 
 use async_trait::async_trait;
-use command_group::AsyncCommandGroup;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tokio::{io::AsyncWriteExt, process::Command};
@@ -33,9 +32,13 @@ pub struct Amp {
 }
 
 impl Amp {
+    pub fn base_command() -> &'static str {
+        "npx -y @sourcegraph/amp@0.0.1764777697-g907e30"
+    }
+
     fn build_command_builder(&self) -> CommandBuilder {
-        let mut builder = CommandBuilder::new("npx -y @sourcegraph/amp@0.0.1764777697-g907e30")
-            .params(["--execute", "--stream-json"]);
+        let mut builder =
+            CommandBuilder::new(Self::base_command()).params(["--execute", "--stream-json"]);
         if self.dangerously_allow_all.unwrap_or(false) {
             builder = builder.extend_params(["--dangerously-allow-all"]);
         }
@@ -69,7 +72,7 @@ impl StandardCodingAgentExecutor for Amp {
             .with_profile(&self.cmd)
             .apply_to_command(&mut command);
 
-        let mut child = command.group_spawn()?;
+        let mut child = env.spawn(&mut command)?;
 
         // Feed the prompt in, then close the pipe so amp sees EOF
         if let Some(mut stdin) = child.inner().stdin.take() {
@@ -142,7 +145,7 @@ impl StandardCodingAgentExecutor for Amp {
             .with_profile(&self.cmd)
             .apply_to_command(&mut command);
 
-        let mut child = command.group_spawn()?;
+        let mut child = env.spawn(&mut command)?;
 
         // Feed the prompt in, then close the pipe so amp sees EOF
         if let Some(mut stdin) = child.inner().stdin.take() {
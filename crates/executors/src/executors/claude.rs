@@ -1,12 +1,13 @@
 // SDK submodules
 pub mod client;
+pub mod dry_run;
 pub mod protocol;
+pub mod read_only;
 pub mod types;
 
 use std::{collections::HashMap, path::Path, process::Stdio, sync::Arc};
 
 use async_trait::async_trait;
-use command_group::AsyncCommandGroup;
 use futures::StreamExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -36,10 +37,11 @@ use crate::{
         stderr_processor::normalize_stderr_logs,
         utils::{EntryIndexProvider, patch::ConversationPatch},
     },
+    mcp_config::claude_mcp_config,
     stdout_dup::create_stdout_pipe_writer,
 };
 
-fn base_command(claude_code_router: bool) -> &'static str {
+pub(crate) fn base_command(claude_code_router: bool) -> &'static str {
     if claude_code_router {
         "npx -y @musistudio/claude-code-router@1.0.66 code"
     } else {
@@ -62,6 +64,10 @@ pub struct ClaudeCode {
     pub approvals: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
+    /// Models to try in order when a run fails on provider errors, a context overflow or an
+    /// exhausted budget
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_fallbacks: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub dangerously_skip_permissions: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -81,7 +87,7 @@ pub struct ClaudeCode {
 }
 
 impl ClaudeCode {
-    async fn build_command_builder(&self) -> CommandBuilder {
+    async fn build_command_builder(&self, env: &ExecutionEnv) -> CommandBuilder {
         // If base_command_override is provided and claude_code_router is also set, log a warning
         if self.cmd.base_command_override.is_some() && self.claude_code_router.is_some() {
             tracing::warn!(
@@ -95,10 +101,11 @@ impl ClaudeCode {
 
         let plan = self.plan.unwrap_or(false);
         let approvals = self.approvals.unwrap_or(false);
-        if plan && approvals {
+        if plan && approvals && env.permission_mode.is_none() {
             tracing::warn!("Both plan and approvals are enabled. Plan will take precedence.");
         }
-        if plan || approvals {
+        let guarded = env.read_only || env.dry_run;
+        if self.effective_permission_mode(env) != PermissionMode::BypassPermissions || guarded {
             // Enable bypass at startup, otherwise we cannot change to it after exiting plan mode
             builder = builder.extend_params(["--permission-prompt-tool=stdio"]);
             builder = builder.extend_params([format!(
@@ -106,7 +113,18 @@ impl ClaudeCode {
                 PermissionMode::BypassPermissions
             )]);
         }
-        if self.dangerously_skip_permissions.unwrap_or(false) {
+        if env.read_only {
+            builder = builder.extend_params([format!(
+                "--disallowedTools={}",
+                read_only::WRITE_TOOLS.join(",")
+            )]);
+        }
+        if self.dangerously_skip_permissions.unwrap_or(false)
+            && !guarded
+            && env
+                .permission_mode
+                .is_none_or(|mode| mode == PermissionMode::BypassPermissions)
+        {
             builder = builder.extend_params(["--dangerously-skip-permissions"]);
         }
         if let Some(model) = &self.model {
@@ -132,8 +150,31 @@ impl ClaudeCode {
         }
     }
 
-    pub fn get_hooks(&self) -> Option<serde_json::Value> {
-        if self.plan.unwrap_or(false) {
+    /// The attempt's permission mode if it picked one, otherwise the profile's
+    pub fn effective_permission_mode(&self, env: &ExecutionEnv) -> PermissionMode {
+        env.permission_mode
+            .unwrap_or_else(|| self.permission_mode())
+    }
+
+    /// Tools from `auto_approved_mcp_servers` skip the approval hook. In investigate-only and
+    /// dry-run attempts (`guarded`) every tool that may write goes through the permission check
+    /// instead.
+    pub fn get_hooks(
+        mode: PermissionMode,
+        auto_approved_mcp_servers: &[&str],
+        guarded: bool,
+    ) -> Option<serde_json::Value> {
+        if guarded {
+            let matcher = format!("^(?!({})$).*", read_only::READ_ONLY_TOOLS.join("|"));
+            Some(serde_json::json!({
+                "PreToolUse": [
+                    {
+                        "matcher": matcher,
+                        "hookCallbackIds": ["tool_approval"],
+                    }
+                ]
+            }))
+        } else if mode == PermissionMode::Plan {
             Some(serde_json::json!({
                 "PreToolUse": [
                     {
@@ -146,11 +187,24 @@ impl ClaudeCode {
                     }
                 ]
             }))
-        } else if self.approvals.unwrap_or(false) {
+        } else if matches!(mode, PermissionMode::Default | PermissionMode::AcceptEdits) {
+            let mut matcher = if mode == PermissionMode::AcceptEdits {
+                "^(?!(Edit|Glob|Grep|MultiEdit|NotebookEdit|NotebookRead|Read|Task|TodoWrite|Write)$)"
+                    .to_string()
+            } else {
+                "^(?!(Glob|Grep|NotebookRead|Read|Task|TodoWrite)$)".to_string()
+            };
+            if !auto_approved_mcp_servers.is_empty() {
+                matcher.push_str(&format!(
+                    "(?!mcp__({})__)",
+                    auto_approved_mcp_servers.join("|")
+                ));
+            }
+            matcher.push_str(".*");
             Some(serde_json::json!({
                 "PreToolUse": [
                     {
-                        "matcher": "^(?!(Glob|Grep|NotebookRead|Read|Task|TodoWrite)$).*",
+                        "matcher": matcher,
                         "hookCallbackIds": ["tool_approval"],
                     }
                 ]
@@ -177,7 +231,7 @@ impl StandardCodingAgentExecutor for ClaudeCode {
         prompt: &str,
         env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let command_builder = self.build_command_builder().await;
+        let command_builder = self.build_command_builder(env).await;
         let command_parts = command_builder.build_initial()?;
         self.spawn_internal(current_dir, prompt, command_parts, env)
             .await
@@ -190,7 +244,7 @@ impl StandardCodingAgentExecutor for ClaudeCode {
         session_id: &str,
         env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let command_builder = self.build_command_builder().await;
+        let command_builder = self.build_command_builder(env).await;
         let command_parts = command_builder.build_follow_up(&[
             "--fork-session".to_string(),
             "--resume".to_string(),
@@ -258,6 +312,13 @@ impl ClaudeCode {
             .current_dir(current_dir)
             .args(&args);
 
+        // Passed after command building, which would split the JSON on whitespace
+        if !env.mcp_servers.is_empty() {
+            command
+                .arg("--mcp-config")
+                .arg(claude_mcp_config(&env.mcp_servers).to_string());
+        }
+
         env.clone()
             .with_profile(&self.cmd)
             .apply_to_command(&mut command);
@@ -268,7 +329,7 @@ impl ClaudeCode {
             tracing::info!("ANTHROPIC_API_KEY removed from environment");
         }
 
-        let mut child = command.group_spawn()?;
+        let mut child = env.spawn(&mut command)?;
         let child_stdout = child.inner().stdout.take().ok_or_else(|| {
             ExecutorError::Io(std::io::Error::other("Claude Code missing stdout"))
         })?;
@@ -278,19 +339,42 @@ impl ClaudeCode {
             })?;
 
         let new_stdout = create_stdout_pipe_writer(&mut child)?;
-        let permission_mode = self.permission_mode();
-        let hooks = self.get_hooks();
+        let permission_mode = self.effective_permission_mode(env);
+        let auto_approved_mcp_servers: Vec<&str> = env
+            .mcp_servers
+            .iter()
+            .filter(|s| s.auto_approve)
+            .map(|s| s.name.as_str())
+            .collect();
+        let (read_only, dry_run) = (env.read_only, env.dry_run);
+        let hooks = Self::get_hooks(
+            permission_mode,
+            &auto_approved_mcp_servers,
+            read_only || dry_run,
+        );
 
         // Create interrupt channel for graceful shutdown
         let (interrupt_tx, interrupt_rx) = tokio::sync::oneshot::channel::<()>();
 
         // Spawn task to handle the SDK client with control protocol
         let prompt_clone = combined_prompt.clone();
-        let approvals_clone = self.approvals_service.clone();
+        // Without approvals in the mode, tools that pass the read-only check run unasked
+        let approvals_clone =
+            if (read_only || dry_run) && permission_mode == PermissionMode::BypassPermissions {
+                None
+            } else {
+                self.approvals_service.clone()
+            };
         let questions_clone = self.questions_service.clone();
         tokio::spawn(async move {
             let log_writer = LogWriter::new(new_stdout);
-            let client = ClaudeAgentClient::new(log_writer.clone(), approvals_clone, questions_clone);
+            let client = ClaudeAgentClient::new(
+                log_writer.clone(),
+                approvals_clone,
+                questions_clone,
+                read_only,
+                dry_run,
+            );
             let protocol_peer =
                 ProtocolPeer::spawn(child_stdin, child_stdout, client.clone(), interrupt_rx);
 
@@ -2036,6 +2120,7 @@ mod tests {
             plan: None,
             approvals: None,
             model: None,
+            model_fallbacks: None,
             append_prompt: AppendPrompt::default(),
             dangerously_skip_permissions: None,
             cmd: crate::command::CmdOverrides {
@@ -2044,6 +2129,7 @@ mod tests {
                 env: None,
             },
             approvals_service: None,
+            questions_service: None,
             disable_api_key: None,
         };
         let msg_store = Arc::new(MsgStore::new());
@@ -2366,4 +2452,45 @@ mod tests {
 
         // ToolResult entry is ignored - no third entry
     }
+
+    #[test]
+    fn test_attempt_permission_mode_overrides_profile() {
+        let executor: ClaudeCode =
+            serde_json::from_value(serde_json::json!({ "approvals": true })).unwrap();
+        let mut env = ExecutionEnv::new();
+        assert_eq!(
+            executor.effective_permission_mode(&env),
+            PermissionMode::Default
+        );
+
+        env.permission_mode = Some(PermissionMode::Plan);
+        assert_eq!(
+            executor.effective_permission_mode(&env),
+            PermissionMode::Plan
+        );
+    }
+
+    #[test]
+    fn test_accept_edits_hooks_skip_edit_tools() {
+        let hooks = ClaudeCode::get_hooks(PermissionMode::AcceptEdits, &[], false).unwrap();
+        let matcher = hooks["PreToolUse"][0]["matcher"].as_str().unwrap();
+        assert!(matcher.contains("(Edit|"));
+        assert!(matcher.contains("|Write)"));
+
+        let hooks = ClaudeCode::get_hooks(PermissionMode::Default, &[], false).unwrap();
+        let matcher = hooks["PreToolUse"][0]["matcher"].as_str().unwrap();
+        assert!(!matcher.contains("Write"));
+
+        assert!(ClaudeCode::get_hooks(PermissionMode::BypassPermissions, &[], false).is_none());
+    }
+
+    #[test]
+    fn test_permission_mode_strictness() {
+        assert!(PermissionMode::Plan.is_at_least_as_strict_as(PermissionMode::Default));
+        assert!(PermissionMode::AcceptEdits.is_at_least_as_strict_as(PermissionMode::AcceptEdits));
+        assert!(
+            !PermissionMode::BypassPermissions
+                .is_at_least_as_strict_as(PermissionMode::AcceptEdits)
+        );
+    }
 }
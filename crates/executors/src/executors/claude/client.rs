@@ -1,9 +1,9 @@
 use std::sync::Arc;
 
 use workspace_utils::approvals::ApprovalStatus;
-use workspace_utils::user_questions::UserQuestion;
+use workspace_utils::user_questions::{QuestionAnswer, UserQuestion};
 
-use super::types::PermissionMode;
+use super::{dry_run, read_only, types::PermissionMode};
 use crate::{
     approvals::{ExecutorApprovalError, ExecutorApprovalService, ExecutorQuestionService},
     executors::{
@@ -23,12 +23,27 @@ const EXIT_PLAN_MODE_NAME: &str = "ExitPlanMode";
 const ASK_USER_QUESTION_NAME: &str = "AskUserQuestion";
 pub const AUTO_APPROVE_CALLBACK_ID: &str = "AUTO_APPROVE_CALLBACK_ID";
 
+/// Describe files the user attached to an answer so the agent can open them.
+fn attachment_note(answer: &QuestionAnswer) -> Option<String> {
+    let paths: Vec<&str> = answer
+        .attachments
+        .iter()
+        .flatten()
+        .filter_map(|attachment| attachment.path.as_deref())
+        .collect();
+    (!paths.is_empty()).then(|| format!("Attached files: {}", paths.join(", ")))
+}
+
 /// Claude Agent client with control protocol support
 pub struct ClaudeAgentClient {
     log_writer: LogWriter,
     approvals: Option<Arc<dyn ExecutorApprovalService>>,
     questions: Option<Arc<dyn ExecutorQuestionService>>,
     auto_approve: bool, // true when approvals is None
+    /// Investigate-only attempt: tools that may write are denied whatever the approvals say
+    read_only: bool,
+    /// Dry-run attempt: tools that may write are logged with what they would do, then denied
+    dry_run: bool,
 }
 
 impl ClaudeAgentClient {
@@ -37,6 +52,8 @@ impl ClaudeAgentClient {
         log_writer: LogWriter,
         approvals: Option<Arc<dyn ExecutorApprovalService>>,
         questions: Option<Arc<dyn ExecutorQuestionService>>,
+        read_only: bool,
+        dry_run: bool,
     ) -> Arc<Self> {
         let auto_approve = approvals.is_none();
         Arc::new(Self {
@@ -44,6 +61,8 @@ impl ClaudeAgentClient {
             approvals,
             questions,
             auto_approve,
+            read_only,
+            dry_run,
         })
     }
 
@@ -139,9 +158,15 @@ impl ClaudeAgentClient {
                             format!("question_{}", answer.question_index)
                         });
 
+                        let attached = attachment_note(answer);
+
                         // If there's custom text (Other option), use that
                         if let Some(custom) = &answer.custom_text {
-                            answers_map.insert(key, serde_json::Value::String(custom.clone()));
+                            let text = match &attached {
+                                Some(note) => format!("{custom}\n\n{note}"),
+                                None => custom.clone(),
+                            };
+                            answers_map.insert(key, serde_json::Value::String(text));
                         } else if question.multi_select {
                             // For multi-select, return array of selected labels
                             let selected_labels: Vec<serde_json::Value> = answer
@@ -152,17 +177,22 @@ impl ClaudeAgentClient {
                                         serde_json::Value::String(opt.label.clone())
                                     })
                                 })
+                                .chain(attached.map(serde_json::Value::String))
                                 .collect();
                             answers_map.insert(key, serde_json::Value::Array(selected_labels));
                         } else {
                             // For single-select, return the selected label
-                            if let Some(&idx) = answer.selected_options.first() {
-                                if let Some(opt) = question.options.get(idx) {
-                                    answers_map.insert(
-                                        key,
-                                        serde_json::Value::String(opt.label.clone()),
-                                    );
-                                }
+                            let label = answer
+                                .selected_options
+                                .first()
+                                .and_then(|&idx| question.options.get(idx))
+                                .map(|opt| opt.label.clone());
+                            let text = match (label, attached) {
+                                (Some(label), Some(note)) => Some(format!("{label}\n\n{note}")),
+                                (label, note) => label.or(note),
+                            };
+                            if let Some(text) = text {
+                                answers_map.insert(key, serde_json::Value::String(text));
                             }
                         }
                     }
@@ -197,6 +227,34 @@ impl ClaudeAgentClient {
         _permission_suggestions: Option<Vec<PermissionUpdate>>,
         tool_use_id: Option<String>,
     ) -> Result<PermissionResult, ExecutorError> {
+        if self.read_only
+            && let Some(message) = read_only::denial(&tool_name, &input)
+        {
+            tracing::info!("Denied {tool_name} in investigate-only attempt");
+            return Ok(PermissionResult::Deny {
+                message,
+                interrupt: Some(false),
+            });
+        }
+
+        if self.dry_run && read_only::denial(&tool_name, &input).is_some() {
+            let summary = dry_run::summary(&tool_name, &input);
+            // Shown in the transcript like a denial, with what would have run
+            self.log_writer
+                .log_raw(&serde_json::to_string(&ClaudeJson::ApprovalResponse {
+                    call_id: tool_use_id.unwrap_or_default(),
+                    tool_name,
+                    approval_status: ApprovalStatus::Denied {
+                        reason: Some(summary.clone()),
+                    },
+                })?)
+                .await?;
+            return Ok(PermissionResult::Deny {
+                message: dry_run::agent_message(&summary),
+                interrupt: Some(false),
+            });
+        }
+
         if self.auto_approve {
             Ok(PermissionResult::Allow {
                 updated_input: input,
@@ -239,7 +297,8 @@ impl ClaudeAgentClient {
         _input: serde_json::Value,
         _tool_use_id: Option<String>,
     ) -> Result<serde_json::Value, ExecutorError> {
-        if self.auto_approve {
+        // Investigate-only and dry-run attempts check every hooked tool in can_use_tool
+        if self.auto_approve && !self.read_only && !self.dry_run {
             Ok(serde_json::json!({
                 "hookSpecificOutput": {
                     "hookEventName": "PreToolUse",
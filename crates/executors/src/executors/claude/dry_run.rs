@@ -0,0 +1,73 @@
+//! Dry-run attempts let the agent work through its task while every tool call that may change
+//! the codebase is intercepted: the transcript records what would have run, and the agent is
+//! told to carry on as if the call had succeeded. Which calls count as writing is decided by
+//! [`super::read_only::denial`].
+
+/// Longest command or input quoted in a summary
+const MAX_SUMMARY_INPUT: usize = 500;
+
+fn truncate(text: &str) -> String {
+    match text.char_indices().nth(MAX_SUMMARY_INPUT) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+fn input_str<'a>(input: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    input.get(key).and_then(|value| value.as_str())
+}
+
+/// What the tool call would have done, for the transcript
+pub fn summary(tool_name: &str, input: &serde_json::Value) -> String {
+    let path = input_str(input, "file_path").or_else(|| input_str(input, "notebook_path"));
+    match (tool_name, path) {
+        ("Bash", _) => format!(
+            "Dry run: would run `{}`",
+            truncate(input_str(input, "command").unwrap_or_default())
+        ),
+        ("Write", Some(path)) => format!("Dry run: would write {path}"),
+        ("Edit" | "MultiEdit" | "NotebookEdit", Some(path)) => {
+            format!("Dry run: would edit {path}")
+        }
+        _ => format!(
+            "Dry run: would call {tool_name} with {}",
+            truncate(&input.to_string())
+        ),
+    }
+}
+
+/// Tool result the agent sees instead of the call's output
+pub fn agent_message(summary: &str) -> String {
+    format!(
+        "{summary}. This is a dry run, so the call was recorded for review but not executed. \
+         Assume it succeeded and continue with the rest of the task."
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_what_would_run() {
+        assert_eq!(
+            summary("Bash", &serde_json::json!({ "command": "npm install" })),
+            "Dry run: would run `npm install`"
+        );
+        assert_eq!(
+            summary("Edit", &serde_json::json!({ "file_path": "src/main.rs" })),
+            "Dry run: would edit src/main.rs"
+        );
+        assert!(
+            summary(
+                "mcp__github__create_issue",
+                &serde_json::json!({ "title": "Bug" })
+            )
+            .starts_with("Dry run: would call mcp__github__create_issue with")
+        );
+
+        let long = "x".repeat(MAX_SUMMARY_INPUT + 10);
+        let summary = summary("Bash", &serde_json::json!({ "command": long }));
+        assert!(summary.ends_with("…`"));
+    }
+}
@@ -0,0 +1,160 @@
+//! Tool restrictions for investigate-only attempts. The CLI is started with the editing tools
+//! disallowed, and every other tool that isn't known to be read-only is checked here before it
+//! runs, so a prompt can't talk the agent into changing the worktree.
+
+/// Tools that can't change anything; they skip the permission hook entirely
+pub const READ_ONLY_TOOLS: &[&str] = &[
+    "BashOutput",
+    "Glob",
+    "Grep",
+    "LS",
+    "NotebookRead",
+    "Read",
+    "Task",
+    "TodoWrite",
+    "WebFetch",
+    "WebSearch",
+];
+
+/// Editing tools, disallowed on the command line as well
+pub const WRITE_TOOLS: &[&str] = &["Edit", "MultiEdit", "NotebookEdit", "Write"];
+
+const BASH_TOOL: &str = "Bash";
+/// Read-only too, but hooked so the question reaches the reviewer
+const ASK_USER_QUESTION_TOOL: &str = "AskUserQuestion";
+
+/// Commands that only read, with subcommands that are allowed where the command can also write
+const READ_ONLY_COMMANDS: &[(&str, &[&str])] = &[
+    ("cat", &[]),
+    ("cut", &[]),
+    ("diff", &[]),
+    ("du", &[]),
+    ("echo", &[]),
+    ("file", &[]),
+    (
+        "git",
+        &[
+            "blame",
+            "cat-file",
+            "describe",
+            "diff",
+            "grep",
+            "log",
+            "ls-files",
+            "ls-tree",
+            "rev-parse",
+            "shortlog",
+            "show",
+            "status",
+        ],
+    ),
+    ("grep", &[]),
+    ("head", &[]),
+    ("ls", &[]),
+    ("pwd", &[]),
+    ("rg", &[]),
+    ("sort", &[]),
+    ("stat", &[]),
+    ("tail", &[]),
+    ("tree", &[]),
+    ("uniq", &[]),
+    ("wc", &[]),
+    ("which", &[]),
+];
+
+/// `find` actions that run or delete things
+const FIND_WRITE_ACTIONS: &[&str] = &["-delete", "-exec", "-execdir", "-ok", "-okdir", "-fprint"];
+
+/// Whether a shell command line only reads. Anything the check doesn't understand, such as
+/// redirections, substitutions or unknown programs, counts as writing.
+pub fn is_read_only_command(command: &str) -> bool {
+    let command = command.replace("2>&1", "");
+    if command.trim().is_empty()
+        || command.contains(['>', '`'])
+        || command.contains("$(")
+        || command.contains("<(")
+    {
+        return false;
+    }
+    command
+        .split(['\n', ';', '|', '&'])
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .all(is_read_only_segment)
+}
+
+fn is_read_only_segment(segment: &str) -> bool {
+    let mut words = segment.split_whitespace();
+    let Some(program) = words.next() else {
+        return true;
+    };
+    if program == "find" {
+        return !words.any(|word| FIND_WRITE_ACTIONS.contains(&word));
+    }
+    let Some((_, subcommands)) = READ_ONLY_COMMANDS.iter().find(|(name, _)| *name == program)
+    else {
+        return false;
+    };
+    if subcommands.is_empty() {
+        return true;
+    }
+    words
+        .find(|word| !word.starts_with('-'))
+        .is_some_and(|subcommand| subcommands.contains(&subcommand))
+}
+
+/// Why the tool call isn't allowed in an investigate-only attempt, if it isn't
+pub fn denial(tool_name: &str, input: &serde_json::Value) -> Option<String> {
+    if READ_ONLY_TOOLS.contains(&tool_name) || tool_name == ASK_USER_QUESTION_TOOL {
+        return None;
+    }
+    if tool_name == BASH_TOOL {
+        let command = input
+            .get("command")
+            .and_then(|command| command.as_str())
+            .unwrap_or_default();
+        return (!is_read_only_command(command)).then(|| {
+            "This is an investigate-only attempt: only read-only shell commands such as ls, \
+             cat, grep or git log are allowed"
+                .to_string()
+        });
+    }
+    Some(format!(
+        "This is an investigate-only attempt: {tool_name} can't be used because it may change \
+         the codebase"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognises_read_only_commands() {
+        assert!(is_read_only_command("ls -la src"));
+        assert!(is_read_only_command("git log --oneline -5 && git status"));
+        assert!(is_read_only_command("rg -n 'fn main' | head -20 2>&1"));
+        assert!(is_read_only_command("find . -name '*.rs'"));
+
+        assert!(!is_read_only_command("cat a > b"));
+        assert!(!is_read_only_command("git commit -am wip"));
+        assert!(!is_read_only_command("git"));
+        assert!(!is_read_only_command("ls; rm -rf target"));
+        assert!(!is_read_only_command("echo $(touch x)"));
+        assert!(!is_read_only_command("find . -name '*.tmp' -delete"));
+        assert!(!is_read_only_command("cargo build"));
+    }
+
+    #[test]
+    fn denies_tools_that_can_write() {
+        let input = serde_json::json!({ "file_path": "src/main.rs" });
+        assert!(denial("Read", &input).is_none());
+        assert!(denial("AskUserQuestion", &input).is_none());
+        assert!(denial("Edit", &input).is_some());
+        assert!(denial("mcp__github__create_issue", &input).is_some());
+
+        assert!(denial("Bash", &serde_json::json!({ "command": "git diff" })).is_none());
+        assert!(denial("Bash", &serde_json::json!({ "command": "npm install" })).is_some());
+        assert!(denial("Bash", &serde_json::json!({})).is_some());
+    }
+}
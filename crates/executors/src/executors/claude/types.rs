@@ -4,6 +4,8 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sqlx::Type;
+use ts_rs::TS;
 
 /// Top-level message types from CLI stdout
 #[derive(Debug, Deserialize)]
@@ -177,16 +179,38 @@ pub enum SDKControlRequestType {
     Interrupt {},
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// How much Claude Code may do without asking. Chosen per attempt, or implied by the profile's
+/// `plan` and `approvals` settings when the attempt doesn't pick one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "camelCase")]
 #[serde(rename_all = "camelCase")]
 pub enum PermissionMode {
+    /// Every tool that may write needs approval
     Default,
+    /// File edits run unasked; other tools that may write need approval
     AcceptEdits,
+    /// Read-only planning until the plan is approved
     Plan,
+    /// Every tool runs unasked
     BypassPermissions,
 }
 
 impl PermissionMode {
+    /// Lower is stricter: plan, then default, accept edits and bypass.
+    pub fn strictness_rank(&self) -> u8 {
+        match self {
+            Self::Plan => 0,
+            Self::Default => 1,
+            Self::AcceptEdits => 2,
+            Self::BypassPermissions => 3,
+        }
+    }
+
+    /// Whether the mode lets the agent do at most what `other` does
+    pub fn is_at_least_as_strict_as(&self, other: PermissionMode) -> bool {
+        self.strictness_rank() <= other.strictness_rank()
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Default => "default",
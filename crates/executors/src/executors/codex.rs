@@ -13,7 +13,6 @@ use codex_app_server_protocol::NewConversationParams;
 use codex_protocol::{
     config_types::SandboxMode as CodexSandboxMode, protocol::AskForApproval as CodexAskForApproval,
 };
-use command_group::AsyncCommandGroup;
 use derivative::Derivative;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -223,8 +222,14 @@ impl Codex {
         apply_overrides(builder, &self.cmd)
     }
 
-    fn build_new_conversation_params(&self, cwd: &Path) -> NewConversationParams {
+    fn build_new_conversation_params(
+        &self,
+        cwd: &Path,
+        env: &ExecutionEnv,
+    ) -> NewConversationParams {
         let sandbox = match self.sandbox.as_ref() {
+            // Investigate-only and dry-run attempts can't loosen the sandbox from the profile
+            _ if env.read_only || env.dry_run => Some(CodexSandboxMode::ReadOnly),
             None | Some(SandboxMode::Auto) => Some(CodexSandboxMode::WorkspaceWrite), // match the Auto preset in codex
             Some(SandboxMode::ReadOnly) => Some(CodexSandboxMode::ReadOnly),
             Some(SandboxMode::WorkspaceWrite) => Some(CodexSandboxMode::WorkspaceWrite),
@@ -232,6 +237,10 @@ impl Codex {
         };
 
         let approval_policy = match self.ask_for_approval.as_ref() {
+            // Nobody gets asked to let the agent out of the read-only sandbox
+            _ if env.read_only => Some(CodexAskForApproval::Never),
+            // Every untrusted command and patch comes to the client, which logs and denies it
+            _ if env.dry_run => Some(CodexAskForApproval::UnlessTrusted),
             None if matches!(self.sandbox.as_ref(), None | Some(SandboxMode::Auto)) => {
                 // match the Auto preset in codex
                 Some(CodexAskForApproval::OnRequest)
@@ -249,7 +258,7 @@ impl Codex {
             cwd: Some(cwd.to_string_lossy().to_string()),
             approval_policy,
             sandbox,
-            config: self.build_config_overrides(),
+            config: self.build_config_overrides(env),
             base_instructions: self.base_instructions.clone(),
             include_apply_patch_tool: self.include_apply_patch_tool,
             model_provider: self.model_provider.clone(),
@@ -258,9 +267,21 @@ impl Codex {
         }
     }
 
-    fn build_config_overrides(&self) -> Option<HashMap<String, Value>> {
+    fn build_config_overrides(&self, env: &ExecutionEnv) -> Option<HashMap<String, Value>> {
         let mut overrides = HashMap::new();
 
+        for server in &env.mcp_servers {
+            match server.codex_entry() {
+                Some(entry) => {
+                    overrides.insert(format!("mcp_servers.{}", server.name), entry);
+                }
+                None => tracing::warn!(
+                    "Skipping MCP server '{}': Codex only supports stdio servers",
+                    server.name
+                ),
+            }
+        }
+
         if let Some(effort) = &self.model_reasoning_effort {
             overrides.insert(
                 "model_reasoning_effort".to_string(),
@@ -318,7 +339,7 @@ impl Codex {
             .with_profile(&self.cmd)
             .apply_to_command(&mut process);
 
-        let mut child = process.group_spawn()?;
+        let mut child = env.spawn(&mut process)?;
 
         let child_stdout = child.inner().stdout.take().ok_or_else(|| {
             ExecutorError::Io(std::io::Error::other("Codex app server missing stdout"))
@@ -330,12 +351,14 @@ impl Codex {
         let new_stdout = create_stdout_pipe_writer(&mut child)?;
         let (exit_signal_tx, exit_signal_rx) = tokio::sync::oneshot::channel();
 
-        let params = self.build_new_conversation_params(current_dir);
+        let params = self.build_new_conversation_params(current_dir, env);
         let resume_session = resume_session.map(|s| s.to_string());
-        let auto_approve = matches!(
-            (&self.sandbox, &self.ask_for_approval),
-            (Some(SandboxMode::DangerFullAccess), None)
-        );
+        let auto_approve = !env.dry_run
+            && matches!(
+                (&self.sandbox, &self.ask_for_approval),
+                (Some(SandboxMode::DangerFullAccess), None)
+            );
+        let dry_run = env.dry_run;
         let approvals = self.approvals.clone();
         tokio::spawn(async move {
             let exit_signal_tx = ExitSignalSender::new(exit_signal_tx);
@@ -350,6 +373,7 @@ impl Codex {
                 exit_signal_tx.clone(),
                 approvals,
                 auto_approve,
+                dry_run,
             )
             .await
             {
@@ -404,8 +428,9 @@ impl Codex {
         exit_signal_tx: ExitSignalSender,
         approvals: Option<Arc<dyn ExecutorApprovalService>>,
         auto_approve: bool,
+        dry_run: bool,
     ) -> Result<(), ExecutorError> {
-        let client = AppServerClient::new(log_writer, approvals, auto_approve);
+        let client = AppServerClient::new(log_writer, approvals, auto_approve, dry_run);
         let rpc_peer =
             JsonRpcPeer::spawn(child_stdin, child_stdout, client.clone(), exit_signal_tx);
         client.connect(rpc_peer);
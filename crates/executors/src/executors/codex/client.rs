@@ -26,7 +26,7 @@ use workspace_utils::approvals::ApprovalStatus;
 use super::jsonrpc::{JsonRpcCallbacks, JsonRpcPeer};
 use crate::{
     approvals::{ExecutorApprovalError, ExecutorApprovalService},
-    executors::{ExecutorError, codex::normalize_logs::Approval},
+    executors::{ExecutorError, claude::dry_run, codex::normalize_logs::Approval},
 };
 
 pub struct AppServerClient {
@@ -36,6 +36,33 @@ pub struct AppServerClient {
     conversation_id: Mutex<Option<ConversationId>>,
     pending_feedback: Mutex<VecDeque<String>>,
     auto_approve: bool,
+    /// Dry-run attempt: commands and patches are denied with a note of what they would do
+    dry_run: bool,
+}
+
+/// What an exec or patch approval request would have done
+fn dry_run_summary(tool_name: &str, input: &Value) -> String {
+    if tool_name == "bash" {
+        let command = input
+            .get("command")
+            .and_then(Value::as_array)
+            .map(|parts| {
+                parts
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default();
+        format!("Dry run: would run `{command}`")
+    } else {
+        let files = input
+            .get("file_changes")
+            .and_then(Value::as_object)
+            .map(|changes| changes.keys().cloned().collect::<Vec<_>>().join(", "))
+            .unwrap_or_default();
+        format!("Dry run: would edit {files}")
+    }
 }
 
 impl AppServerClient {
@@ -43,12 +70,14 @@ impl AppServerClient {
         log_writer: LogWriter,
         approvals: Option<Arc<dyn ExecutorApprovalService>>,
         auto_approve: bool,
+        dry_run: bool,
     ) -> Arc<Self> {
         Arc::new(Self {
             rpc: OnceLock::new(),
             log_writer,
             approvals,
             auto_approve,
+            dry_run,
             conversation_id: Mutex::new(None),
             pending_feedback: Mutex::new(VecDeque::new()),
         })
@@ -241,6 +270,15 @@ impl AppServerClient {
         tool_call_id: &str,
     ) -> Result<ApprovalStatus, ExecutorError> {
         tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        if self.dry_run {
+            // The denial reason goes back to the agent as feedback, so it carries on
+            return Ok(ApprovalStatus::Denied {
+                reason: Some(dry_run::agent_message(&dry_run_summary(
+                    tool_name,
+                    &tool_input,
+                ))),
+            });
+        }
         if self.auto_approve {
             return Ok(ApprovalStatus::Approved);
         }
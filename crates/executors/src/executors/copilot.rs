@@ -6,7 +6,6 @@ use std::{
 };
 
 use async_trait::async_trait;
-use command_group::AsyncCommandGroup;
 use futures::StreamExt;
 use regex::Regex;
 use schemars::JsonSchema;
@@ -55,8 +54,12 @@ pub struct Copilot {
 }
 
 impl Copilot {
+    pub fn base_command() -> &'static str {
+        "npx -y @github/copilot@0.0.367"
+    }
+
     fn build_command_builder(&self, log_dir: &str) -> CommandBuilder {
-        let mut builder = CommandBuilder::new("npx -y @github/copilot@0.0.367").params([
+        let mut builder = CommandBuilder::new(Self::base_command()).params([
             "--no-color",
             "--log-level",
             "debug",
@@ -126,7 +129,7 @@ impl StandardCodingAgentExecutor for Copilot {
             .with_profile(&self.cmd)
             .apply_to_command(&mut command);
 
-        let mut child = command.group_spawn()?;
+        let mut child = env.spawn(&mut command)?;
 
         // Write prompt to stdin
         if let Some(mut stdin) = child.inner().stdin.take() {
@@ -170,7 +173,7 @@ impl StandardCodingAgentExecutor for Copilot {
             .with_profile(&self.cmd)
             .apply_to_command(&mut command);
 
-        let mut child = command.group_spawn()?;
+        let mut child = env.spawn(&mut command)?;
 
         // Write comprehensive prompt to stdin
         if let Some(mut stdin) = child.inner().stdin.take() {
@@ -1,7 +1,6 @@
 use std::{path::Path, process::Stdio, sync::Arc};
 
 use async_trait::async_trait;
-use command_group::AsyncCommandGroup;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use strum_macros::AsRefStr;
@@ -83,10 +82,14 @@ pub struct Droid {
 }
 
 impl Droid {
+    pub fn base_command() -> &'static str {
+        "droid exec"
+    }
+
     pub fn build_command_builder(&self) -> crate::command::CommandBuilder {
         use crate::command::{CommandBuilder, apply_overrides};
         let mut builder =
-            CommandBuilder::new("droid exec").params(["--output-format", "stream-json"]);
+            CommandBuilder::new(Self::base_command()).params(["--output-format", "stream-json"]);
         builder = match &self.autonomy {
             Autonomy::Normal => builder,
             Autonomy::Low => builder.extend_params(["--auto", "low"]),
@@ -127,7 +130,7 @@ async fn spawn_droid(
         .with_profile(cmd_overrides)
         .apply_to_command(&mut command);
 
-    let mut child = command.group_spawn()?;
+    let mut child = env.spawn(&mut command)?;
 
     if let Some(mut stdin) = child.inner().stdin.take() {
         stdin.write_all(prompt.as_bytes()).await?;
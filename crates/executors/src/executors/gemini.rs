@@ -35,8 +35,12 @@ pub struct Gemini {
 }
 
 impl Gemini {
+    pub fn base_command() -> &'static str {
+        "npx -y @google/gemini-cli@0.21.1"
+    }
+
     fn build_command_builder(&self) -> CommandBuilder {
-        let mut builder = CommandBuilder::new("npx -y @google/gemini-cli@0.21.1");
+        let mut builder = CommandBuilder::new(Self::base_command());
 
         if let Some(model) = &self.model {
             builder = builder.extend_params(["--model", model.as_str()]);
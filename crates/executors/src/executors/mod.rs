@@ -18,8 +18,15 @@ use crate::{
     command::CommandBuildError,
     env::ExecutionEnv,
     executors::{
-        amp::Amp, claude::ClaudeCode, codex::Codex, copilot::Copilot, cursor::CursorAgent,
-        droid::Droid, gemini::Gemini, opencode::Opencode, qwen::QwenCode,
+        amp::Amp,
+        claude::{ClaudeCode, types::PermissionMode},
+        codex::Codex,
+        copilot::Copilot,
+        cursor::CursorAgent,
+        droid::Droid,
+        gemini::Gemini,
+        opencode::Opencode,
+        qwen::QwenCode,
     },
     mcp_config::McpConfig,
 };
@@ -42,6 +49,12 @@ pub enum BaseAgentCapability {
     SessionFork,
     /// Agent requires a setup script before it can run (e.g., login, installation)
     SetupHelper,
+    /// Agent can be restricted to read-only tools for investigate-only attempts
+    ReadOnly,
+    /// Agent's writing tool calls can be logged instead of run
+    DryRun,
+    /// Agent can run with a permission mode picked for the attempt
+    PermissionModes,
 }
 
 #[derive(Debug, Error)]
@@ -70,6 +83,12 @@ pub enum ExecutorError {
     ExecutableNotFound { program: String },
     #[error("Setup helper not supported")]
     SetupHelperNotSupported,
+    #[error("{0} can't run investigate-only attempts")]
+    ReadOnlyNotSupported(String),
+    #[error("{0} can't run dry runs")]
+    DryRunNotSupported(String),
+    #[error("{0} doesn't support permission modes")]
+    PermissionModeNotSupported(String),
     #[error("Auth required: {0}")]
     AuthRequired(String),
 }
@@ -157,8 +176,13 @@ impl CodingAgent {
 
     pub fn capabilities(&self) -> Vec<BaseAgentCapability> {
         match self {
-            Self::ClaudeCode(_)
-            | Self::Amp(_)
+            Self::ClaudeCode(_) => vec![
+                BaseAgentCapability::SessionFork,
+                BaseAgentCapability::ReadOnly,
+                BaseAgentCapability::DryRun,
+                BaseAgentCapability::PermissionModes,
+            ],
+            Self::Amp(_)
             | Self::Gemini(_)
             | Self::QwenCode(_)
             | Self::Droid(_)
@@ -166,11 +190,137 @@ impl CodingAgent {
             Self::Codex(_) => vec![
                 BaseAgentCapability::SessionFork,
                 BaseAgentCapability::SetupHelper,
+                BaseAgentCapability::ReadOnly,
+                BaseAgentCapability::DryRun,
             ],
             Self::CursorAgent(_) => vec![BaseAgentCapability::SetupHelper],
             Self::Copilot(_) => vec![],
         }
     }
+
+    /// Fail before spawning when the run needs tool restrictions the agent can't enforce
+    pub fn ensure_supports(&self, env: &ExecutionEnv) -> Result<(), ExecutorError> {
+        let capabilities = self.capabilities();
+        if env.read_only && !capabilities.contains(&BaseAgentCapability::ReadOnly) {
+            return Err(ExecutorError::ReadOnlyNotSupported(
+                BaseCodingAgent::from(self).to_string(),
+            ));
+        }
+        if env.dry_run && !capabilities.contains(&BaseAgentCapability::DryRun) {
+            return Err(ExecutorError::DryRunNotSupported(
+                BaseCodingAgent::from(self).to_string(),
+            ));
+        }
+        if env.permission_mode.is_some()
+            && !capabilities.contains(&BaseAgentCapability::PermissionModes)
+        {
+            return Err(ExecutorError::PermissionModeNotSupported(
+                BaseCodingAgent::from(self).to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Permission mode the profile runs with when the attempt doesn't pick one
+    pub fn default_permission_mode(&self) -> Option<PermissionMode> {
+        match self {
+            Self::ClaudeCode(agent) => Some(agent.permission_mode()),
+            _ => None,
+        }
+    }
+
+    /// Command the agent's CLI is started with, which pins the package version for npx agents
+    pub fn cli_command(&self) -> String {
+        let (cmd, default) = match self {
+            Self::ClaudeCode(agent) => (
+                &agent.cmd,
+                claude::base_command(agent.claude_code_router.unwrap_or(false)),
+            ),
+            Self::Amp(agent) => (&agent.cmd, Amp::base_command()),
+            Self::Gemini(agent) => (&agent.cmd, Gemini::base_command()),
+            Self::Codex(agent) => (&agent.cmd, Codex::base_command()),
+            Self::Opencode(agent) => (&agent.cmd, Opencode::base_command()),
+            Self::CursorAgent(agent) => (&agent.cmd, CursorAgent::base_command()),
+            Self::QwenCode(agent) => (&agent.cmd, QwenCode::base_command()),
+            Self::Copilot(agent) => (&agent.cmd, Copilot::base_command()),
+            Self::Droid(agent) => (&agent.cmd, Droid::base_command()),
+        };
+        cmd.base_command_override
+            .clone()
+            .unwrap_or_else(|| default.to_string())
+    }
+
+    /// Model the profile asks for; `None` leaves it to the CLI's default
+    pub fn model(&self) -> Option<&str> {
+        match self {
+            Self::ClaudeCode(agent) => agent.model.as_deref(),
+            Self::Gemini(agent) => agent.model.as_deref(),
+            Self::Codex(agent) => agent.model.as_deref(),
+            Self::Opencode(agent) => agent.model.as_deref(),
+            Self::CursorAgent(agent) => agent.model.as_deref(),
+            Self::Copilot(agent) => agent.model.as_deref(),
+            Self::Droid(agent) => agent.model.as_deref(),
+            Self::Amp(_) | Self::QwenCode(_) => None,
+        }
+    }
+
+    /// Run with `model` instead of the profile's. Agents without a model setting ignore it.
+    pub fn use_model(&mut self, model: String) {
+        let model = Some(model);
+        match self {
+            Self::ClaudeCode(agent) => agent.model = model,
+            Self::Gemini(agent) => agent.model = model,
+            Self::Codex(agent) => agent.model = model,
+            Self::Opencode(agent) => agent.model = model,
+            Self::CursorAgent(agent) => agent.model = model,
+            Self::Copilot(agent) => agent.model = model,
+            Self::Droid(agent) => agent.model = model,
+            Self::Amp(_) | Self::QwenCode(_) => {}
+        }
+    }
+
+    /// Text the profile appends to every prompt it is given.
+    pub fn append_prompt(&self) -> &AppendPrompt {
+        match self {
+            Self::ClaudeCode(agent) => &agent.append_prompt,
+            Self::Amp(agent) => &agent.append_prompt,
+            Self::Gemini(agent) => &agent.append_prompt,
+            Self::Codex(agent) => &agent.append_prompt,
+            Self::Opencode(agent) => &agent.append_prompt,
+            Self::CursorAgent(agent) => &agent.append_prompt,
+            Self::QwenCode(agent) => &agent.append_prompt,
+            Self::Copilot(agent) => &agent.append_prompt,
+            Self::Droid(agent) => &agent.append_prompt,
+        }
+    }
+
+    /// Model to fall back to after a run with `current` failed, `None` meaning the profile's
+    /// own model. Follows the profile's fallback chain in order.
+    pub fn next_fallback_model(&self, current: Option<&str>) -> Option<String> {
+        let fallbacks = match self {
+            Self::ClaudeCode(agent) => agent.model_fallbacks.as_deref().unwrap_or_default(),
+            _ => &[],
+        };
+        let next = match current {
+            Some(current) if Some(current) != self.model() => fallbacks
+                .iter()
+                .position(|model| model == current)
+                .map_or(fallbacks.len(), |index| index + 1),
+            _ => 0,
+        };
+        fallbacks.get(next).cloned()
+    }
+}
+
+/// Package version pinned in an npx command such as `npx -y @openai/codex@0.75.0 app-server`
+pub fn pinned_cli_version(command: &str) -> Option<&str> {
+    command
+        .split_whitespace()
+        .skip(1)
+        .find(|arg| !arg.starts_with('-'))
+        .and_then(|package| package.rsplit_once('@'))
+        .filter(|(name, _)| !name.is_empty())
+        .map(|(_, version)| version)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -324,4 +474,19 @@ mod tests {
         assert!(result.is_ok(), "CURSOR should deserialize via serde");
         assert_eq!(result.unwrap(), BaseCodingAgent::CursorAgent);
     }
+
+    #[test]
+    fn reads_pinned_cli_version() {
+        assert_eq!(
+            pinned_cli_version("npx -y @openai/codex@0.75.0 app-server"),
+            Some("0.75.0")
+        );
+        assert_eq!(
+            pinned_cli_version("npx -y opencode-ai@1.0.134"),
+            Some("1.0.134")
+        );
+        assert_eq!(pinned_cli_version("npx -y @anthropic-ai/claude-code"), None);
+        assert_eq!(pinned_cli_version("droid exec"), None);
+        assert_eq!(pinned_cli_version("cursor-agent"), None);
+    }
 }
@@ -0,0 +1,406 @@
+//! Run executor processes as Kubernetes Jobs.
+//!
+//! Meant for a server running inside the cluster: its workspace directory lives on a shared
+//! (ReadWriteMany) claim that every Job mounts at the same path, so worktrees, diffs and commits
+//! stay where the server expects them. Each attempt also gets its own claim mounted as `$HOME`,
+//! which keeps agent session state around for follow-ups. Jobs are applied and attached to with
+//! `kubectl`, so pod output streams into the attempt's logs and stdin carries the approval and
+//! question protocols, as for a local process.
+//!
+//! The process's environment goes into a Secret named after the Job rather than the Job spec, so
+//! API keys don't show up to anyone who can read Jobs or pods. The Secret is owned by its Job and
+//! is garbage collected with it.
+
+use std::{io, path::PathBuf, process::Stdio};
+
+use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use serde_json::{Value, json};
+use tokio::{io::AsyncWriteExt, process::Command};
+
+use crate::remote::{program_name, quote};
+
+const DEFAULT_NAMESPACE: &str = "default";
+const DEFAULT_HOME_SIZE: &str = "5Gi";
+const HOME_MOUNT: &str = "/home/vibe-kanban";
+const MANAGED_BY: &str = "vibe-kanban";
+
+/// Cluster settings shared by every Job this server schedules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KubernetesConfig {
+    pub namespace: String,
+    /// kubeconfig context; the current one when unset
+    pub context: Option<String>,
+    /// Claim holding the server's workspace directory
+    pub workspace_claim: String,
+    /// Path the workspace claim is mounted at, in this server and in every Job
+    pub workspace_mount: PathBuf,
+    pub storage_class: Option<String>,
+    /// Requested size of each attempt's home claim
+    pub home_size: String,
+    /// Image for projects that don't set one
+    pub default_image: Option<String>,
+}
+
+impl KubernetesConfig {
+    /// Build the config from `VK_KUBERNETES_*` settings. Returns `None` when no workspace claim
+    /// is set.
+    pub fn from_settings(
+        setting: impl Fn(&str) -> Option<String>,
+        workspace_base_dir: PathBuf,
+    ) -> Option<Self> {
+        let workspace_claim =
+            setting("VK_KUBERNETES_WORKSPACE_CLAIM").filter(|claim| !claim.is_empty())?;
+
+        Some(Self {
+            namespace: setting("VK_KUBERNETES_NAMESPACE")
+                .unwrap_or_else(|| DEFAULT_NAMESPACE.to_string()),
+            context: setting("VK_KUBERNETES_CONTEXT"),
+            workspace_claim,
+            workspace_mount: setting("VK_KUBERNETES_WORKSPACE_MOUNT")
+                .map(PathBuf::from)
+                .unwrap_or(workspace_base_dir),
+            storage_class: setting("VK_KUBERNETES_STORAGE_CLASS"),
+            home_size: setting("VK_KUBERNETES_HOME_SIZE")
+                .unwrap_or_else(|| DEFAULT_HOME_SIZE.to_string()),
+            default_image: setting("VK_KUBERNETES_DEFAULT_IMAGE"),
+        })
+    }
+
+    /// `kubectl` scoped to the configured context and namespace
+    fn kubectl_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(context) = &self.context {
+            args.push(format!("--context={context}"));
+        }
+        args.push(format!("--namespace={}", self.namespace));
+        args
+    }
+
+    fn kubectl(&self) -> Command {
+        let mut command = Command::new("kubectl");
+        command
+            .kill_on_drop(true)
+            .args(self.kubectl_args())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+        command
+    }
+
+    /// Create an attempt's home claim if it doesn't exist yet
+    pub async fn ensure_home_claim(&self, name: &str) -> io::Result<()> {
+        let mut spec = json!({
+            "accessModes": ["ReadWriteOnce"],
+            "resources": { "requests": { "storage": self.home_size } },
+        });
+        if let Some(storage_class) = &self.storage_class {
+            spec["storageClassName"] = json!(storage_class);
+        }
+        let manifest = json!({
+            "apiVersion": "v1",
+            "kind": "PersistentVolumeClaim",
+            "metadata": { "name": name, "labels": labels() },
+            "spec": spec,
+        });
+
+        let mut command = self.kubectl();
+        command.args(["apply", "-f", "-"]).stdin(Stdio::piped());
+        let mut child = command.spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(manifest.to_string().as_bytes()).await?;
+        }
+        let output = child.wait_with_output().await?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!(
+                "kubectl apply failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )))
+        }
+    }
+
+    /// Delete a Job, its pod and its env Secret; missing objects are ignored
+    pub async fn delete_job(&self, name: &str) {
+        self.delete("job", name).await;
+        // Normally collected with the Job, unless it was deleted before its owner was recorded
+        self.delete("secret", name).await;
+    }
+
+    /// Delete an attempt's home claim; missing claims are ignored
+    pub async fn delete_claim(&self, name: &str) {
+        self.delete("pvc", name).await;
+    }
+
+    async fn delete(&self, kind: &str, name: &str) {
+        let result = self
+            .kubectl()
+            .args(["delete", kind, name, "--ignore-not-found", "--wait=false"])
+            .args(["--cascade=background"])
+            .output()
+            .await;
+        match result {
+            Ok(output) if !output.status.success() => tracing::warn!(
+                "Failed to delete {} {}: {}",
+                kind,
+                name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Err(e) => tracing::warn!("Failed to delete {} {}: {}", kind, name, e),
+            Ok(_) => {}
+        }
+    }
+}
+
+/// One execution, scheduled as a Job
+#[derive(Debug, Clone, PartialEq)]
+pub struct KubernetesJob {
+    pub config: KubernetesConfig,
+    pub name: String,
+    pub image: String,
+    pub cpus: Option<f64>,
+    pub memory_mb: Option<i64>,
+    /// Claim mounted as the process's home directory
+    pub home_claim: Option<String>,
+}
+
+impl KubernetesJob {
+    /// Spawn a fully configured command as a Job
+    pub fn spawn(&self, command: &Command) -> io::Result<AsyncGroupChild> {
+        self.wrap(command)?.group_spawn()
+    }
+
+    /// Secret holding the explicit env of `command`, read by the Job's container
+    pub fn secret(&self, command: &Command) -> Value {
+        let data: serde_json::Map<String, Value> = command
+            .as_std()
+            .get_envs()
+            .filter_map(|(key, value)| {
+                value.map(|value| {
+                    (
+                        key.to_string_lossy().into_owned(),
+                        json!(value.to_string_lossy()),
+                    )
+                })
+            })
+            .collect();
+        json!({
+            "apiVersion": "v1",
+            "kind": "Secret",
+            "metadata": { "name": self.name, "labels": labels() },
+            "type": "Opaque",
+            "stringData": data,
+        })
+    }
+
+    /// Job running the same program, arguments, explicit env and working directory as `command`.
+    /// Env values are referenced from [`Self::secret`], which must be applied alongside it.
+    pub fn manifest(&self, command: &Command) -> Value {
+        let local = command.as_std();
+
+        let mut env: Vec<Value> = local
+            .get_envs()
+            .filter(|(_, value)| value.is_some())
+            .map(|(key, _)| {
+                json!({
+                    "name": key.to_string_lossy(),
+                    "valueFrom": {
+                        "secretKeyRef": { "name": self.name, "key": key.to_string_lossy() },
+                    },
+                })
+            })
+            .collect();
+        let mut volumes = vec![json!({
+            "name": "workspaces",
+            "persistentVolumeClaim": { "claimName": self.config.workspace_claim },
+        })];
+        let mut volume_mounts = vec![json!({
+            "name": "workspaces",
+            "mountPath": self.config.workspace_mount.to_string_lossy(),
+        })];
+        if let Some(home_claim) = &self.home_claim {
+            volumes.push(json!({
+                "name": "home",
+                "persistentVolumeClaim": { "claimName": home_claim },
+            }));
+            volume_mounts.push(json!({ "name": "home", "mountPath": HOME_MOUNT }));
+            env.push(json!({ "name": "HOME", "value": HOME_MOUNT }));
+        }
+
+        let mut limits = serde_json::Map::new();
+        if let Some(cpus) = self.cpus {
+            limits.insert("cpu".to_string(), json!(cpus.to_string()));
+        }
+        if let Some(memory_mb) = self.memory_mb {
+            limits.insert("memory".to_string(), json!(format!("{memory_mb}Mi")));
+        }
+
+        let mut container_command = vec![program_name(local.get_program()).to_string_lossy()];
+        container_command.extend(local.get_args().map(|arg| arg.to_string_lossy()));
+
+        let mut container = json!({
+            "name": "executor",
+            "image": self.image,
+            "command": container_command,
+            "env": env,
+            "stdin": true,
+            "stdinOnce": true,
+            "volumeMounts": volume_mounts,
+            "resources": { "limits": limits },
+        });
+        if let Some(dir) = local.get_current_dir() {
+            container["workingDir"] = json!(dir.to_string_lossy());
+        }
+
+        json!({
+            "apiVersion": "batch/v1",
+            "kind": "Job",
+            "metadata": { "name": self.name, "labels": labels() },
+            "spec": {
+                "backoffLimit": 0,
+                "ttlSecondsAfterFinished": 3600,
+                "template": {
+                    "metadata": { "labels": labels() },
+                    "spec": {
+                        "restartPolicy": "Never",
+                        "containers": [container],
+                        "volumes": volumes,
+                    },
+                },
+            },
+        })
+    }
+
+    /// The env Secret and the Job, applied together
+    fn manifests(&self, command: &Command) -> Value {
+        json!({
+            "apiVersion": "v1",
+            "kind": "List",
+            "items": [self.secret(command), self.manifest(command)],
+        })
+    }
+
+    /// Shell pipeline that applies the Job and its env Secret, hands the Secret to the Job so
+    /// they're deleted together, attaches to its pod and exits non-zero unless the Job
+    /// succeeded. The manifests are passed through the environment so secrets stay off the
+    /// command line.
+    pub fn wrap(&self, command: &Command) -> io::Result<Command> {
+        let kubectl = std::iter::once("kubectl".to_string())
+            .chain(self.config.kubectl_args())
+            .map(|word| quote(&word))
+            .collect::<io::Result<Vec<_>>>()?
+            .join(" ");
+        let job = quote(&format!("job/{}", self.name))?;
+        let secret = quote(&format!("secret/{}", self.name))?;
+        let owner = quote(&format!(
+            r#"{{"metadata":{{"ownerReferences":[{{"apiVersion":"batch/v1","kind":"Job","name":"{}","uid":"%s"}}]}}}}"#,
+            self.name
+        ))?;
+        let script = format!(
+            "printf '%s' \"$VK_JOB_MANIFEST\" | {kubectl} apply -f - >/dev/null || exit 1\n\
+             uid=$({kubectl} get {job} -o jsonpath='{{.metadata.uid}}') && \
+             {kubectl} patch {secret} --type=merge -p \"$(printf {owner} \"$uid\")\" >/dev/null\n\
+             {kubectl} attach -i -q --pod-running-timeout=10m {job}\n\
+             exec {kubectl} wait --for=jsonpath='{{.status.succeeded}}'=1 --timeout=30s {job} \
+             >/dev/null"
+        );
+
+        let mut wrapped = Command::new("sh");
+        wrapped
+            .kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .arg("-c")
+            .arg(script)
+            .env("VK_JOB_MANIFEST", self.manifests(command).to_string());
+        if let Some(dir) = command
+            .as_std()
+            .get_current_dir()
+            .filter(|dir| dir.exists())
+        {
+            wrapped.current_dir(dir);
+        }
+        Ok(wrapped)
+    }
+}
+
+fn labels() -> Value {
+    json!({ "app.kubernetes.io/managed-by": MANAGED_BY })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job() -> KubernetesJob {
+        let config = KubernetesConfig::from_settings(
+            |key| match key {
+                "VK_KUBERNETES_WORKSPACE_CLAIM" => Some("vk-workspaces".to_string()),
+                "VK_KUBERNETES_NAMESPACE" => Some("agents".to_string()),
+                _ => None,
+            },
+            PathBuf::from("/data/worktrees"),
+        )
+        .unwrap();
+        KubernetesJob {
+            config,
+            name: "vk-abcd".to_string(),
+            image: "ghcr.io/acme/agent:latest".to_string(),
+            cpus: Some(2.0),
+            memory_mb: Some(4096),
+            home_claim: Some("vk-home-abcd".to_string()),
+        }
+    }
+
+    #[test]
+    fn builds_job_manifest_from_command() {
+        let mut command = Command::new("/usr/local/bin/claude");
+        command
+            .arg("-p")
+            .env("API_KEY", "secret")
+            .current_dir("/data/worktrees/abcd-task");
+
+        let manifest = job().manifest(&command);
+        let container = &manifest["spec"]["template"]["spec"]["containers"][0];
+        assert_eq!(container["command"], json!(["claude", "-p"]));
+        assert_eq!(container["workingDir"], "/data/worktrees/abcd-task");
+        assert_eq!(container["resources"]["limits"]["memory"], "4096Mi");
+        assert!(container["env"].as_array().unwrap().contains(&json!({
+            "name": "API_KEY",
+            "valueFrom": { "secretKeyRef": { "name": "vk-abcd", "key": "API_KEY" } },
+        })));
+        assert!(!manifest.to_string().contains("\"secret\""));
+
+        let secret = job().secret(&command);
+        assert_eq!(secret["metadata"]["name"], "vk-abcd");
+        assert_eq!(secret["stringData"]["API_KEY"], "secret");
+        let volumes = &manifest["spec"]["template"]["spec"]["volumes"];
+        assert_eq!(
+            volumes[0]["persistentVolumeClaim"]["claimName"],
+            "vk-workspaces"
+        );
+    }
+
+    #[test]
+    fn wraps_command_in_kubectl_pipeline() {
+        let command = Command::new("claude");
+        let wrapped = job().wrap(&command).unwrap();
+        let std = wrapped.as_std();
+        assert_eq!(std.get_program(), "sh");
+        let script = std
+            .get_args()
+            .nth(1)
+            .unwrap()
+            .to_string_lossy()
+            .into_owned();
+        assert!(script.contains("kubectl --namespace=agents attach -i -q"));
+        assert!(script.contains("job/vk-abcd"));
+        assert!(script.contains("patch secret/vk-abcd"));
+        assert!(std.get_envs().any(|(key, _)| key == "VK_JOB_MANIFEST"));
+    }
+
+    #[test]
+    fn disabled_without_workspace_claim() {
+        assert!(KubernetesConfig::from_settings(|_| None, PathBuf::from("/data")).is_none());
+    }
+}
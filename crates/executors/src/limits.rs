@@ -0,0 +1,334 @@
+//! CPU, memory and wall-clock limits for executor and script processes.
+//!
+//! On Linux, memory is capped by running the process in a transient systemd scope (a cgroup)
+//! when a user systemd session is available, and with `RLIMIT_AS` otherwise; CPU time is capped
+//! with `RLIMIT_CPU` on every unix. On Windows both become limits of a job object the process is
+//! added to right after spawning. Wall-clock limits are enforced by whoever waits on the process.
+
+use std::{io, process::ExitStatus, time::Duration};
+
+use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+use strum_macros::{Display, EnumString};
+use tokio::process::Command;
+use ts_rs::TS;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct ResourceLimits {
+    pub memory_mb: Option<u64>,
+    pub cpu_seconds: Option<u64>,
+    pub wall_clock_secs: Option<u64>,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, Display, EnumString, Type,
+)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ResourceLimit {
+    Memory,
+    Cpu,
+    WallClock,
+}
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        self.memory_mb.is_none() && self.cpu_seconds.is_none() && self.wall_clock_secs.is_none()
+    }
+
+    /// Just the wall-clock limit, for processes that run where OS limits can't be applied
+    pub fn wall_clock_only(&self) -> Self {
+        Self {
+            wall_clock_secs: self.wall_clock_secs,
+            ..Default::default()
+        }
+    }
+
+    pub fn wall_clock(&self) -> Option<Duration> {
+        self.wall_clock_secs.map(Duration::from_secs)
+    }
+
+    /// Human-readable value of one limit, for log entries
+    pub fn describe(&self, limit: ResourceLimit) -> String {
+        match limit {
+            ResourceLimit::Memory => format!("{} MB memory", self.memory_mb.unwrap_or_default()),
+            ResourceLimit::Cpu => format!("{}s CPU time", self.cpu_seconds.unwrap_or_default()),
+            ResourceLimit::WallClock => {
+                format!(
+                    "{}s wall-clock time",
+                    self.wall_clock_secs.unwrap_or_default()
+                )
+            }
+        }
+    }
+
+    /// Spawn a fully configured command with the memory and CPU limits applied
+    pub fn spawn(&self, command: &mut Command) -> io::Result<AsyncGroupChild> {
+        #[cfg(target_os = "linux")]
+        if self.memory_mb.is_some() && linux::systemd_scope_available() {
+            let mut scoped = linux::systemd_scope(command, self);
+            unix::set_rlimits(&mut scoped, self, false);
+            return scoped.group_spawn();
+        }
+
+        #[cfg(unix)]
+        unix::set_rlimits(command, self, true);
+
+        #[allow(unused_mut)]
+        let mut child = command.group_spawn()?;
+
+        #[cfg(windows)]
+        if let Err(e) = windows::assign_job(&mut child, self) {
+            tracing::warn!("Failed to apply resource limits: {}", e);
+        }
+
+        Ok(child)
+    }
+
+    /// The limit the OS enforced to end a process with this status, if any. Processes killed by
+    /// the kernel report the signal themselves; shells running them report `128 + signal`.
+    pub fn exceeded_by(&self, status: &ExitStatus) -> Option<ResourceLimit> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+
+            const SIGKILL: i32 = 9;
+            const SIGXCPU: i32 = 24;
+
+            let signal = status.signal().or_else(|| {
+                status
+                    .code()
+                    .filter(|code| *code > 128)
+                    .map(|code| code - 128)
+            })?;
+            match signal {
+                SIGXCPU if self.cpu_seconds.is_some() => Some(ResourceLimit::Cpu),
+                SIGKILL if self.memory_mb.is_some() => Some(ResourceLimit::Memory),
+                SIGKILL if self.cpu_seconds.is_some() => Some(ResourceLimit::Cpu),
+                _ => None,
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = status;
+            None
+        }
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use nix::sys::resource::{Resource, setrlimit};
+    use tokio::process::Command;
+
+    use super::ResourceLimits;
+
+    /// Grace between the soft CPU limit (SIGXCPU) and the hard one (SIGKILL)
+    const CPU_GRACE_SECS: u64 = 5;
+
+    pub(super) fn set_rlimits(command: &mut Command, limits: &ResourceLimits, memory: bool) {
+        let memory_bytes = limits
+            .memory_mb
+            .filter(|_| memory)
+            .map(|mb| mb.saturating_mul(1024 * 1024));
+        let cpu_seconds = limits.cpu_seconds;
+        if memory_bytes.is_none() && cpu_seconds.is_none() {
+            return;
+        }
+
+        // SAFETY: only async-signal-safe setrlimit calls run between fork and exec
+        unsafe {
+            command.pre_exec(move || {
+                if let Some(bytes) = memory_bytes {
+                    setrlimit(Resource::RLIMIT_AS, bytes as _, bytes as _)?;
+                }
+                if let Some(seconds) = cpu_seconds {
+                    setrlimit(
+                        Resource::RLIMIT_CPU,
+                        seconds as _,
+                        seconds.saturating_add(CPU_GRACE_SECS) as _,
+                    )?;
+                }
+                Ok(())
+            });
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{path::Path, process::Stdio, sync::LazyLock};
+
+    use tokio::process::Command;
+    use workspace_utils::shell::resolve_executable_path_blocking;
+
+    use super::ResourceLimits;
+
+    static SYSTEMD_SCOPE_AVAILABLE: LazyLock<bool> = LazyLock::new(|| {
+        let has_bus = std::env::var_os("DBUS_SESSION_BUS_ADDRESS").is_some()
+            || std::env::var_os("XDG_RUNTIME_DIR")
+                .is_some_and(|dir| Path::new(&dir).join("bus").exists());
+        has_bus
+            && Path::new("/sys/fs/cgroup/cgroup.controllers").exists()
+            && resolve_executable_path_blocking("systemd-run").is_some()
+    });
+
+    /// Whether processes can be placed in a user-level systemd scope (cgroup v2)
+    pub(super) fn systemd_scope_available() -> bool {
+        *SYSTEMD_SCOPE_AVAILABLE
+    }
+
+    /// `systemd-run --scope` wrapper running the same program, arguments, env and working
+    /// directory in a cgroup with the memory limit applied. Stdio is always piped.
+    pub(super) fn systemd_scope(command: &Command, limits: &ResourceLimits) -> Command {
+        let local = command.as_std();
+
+        let mut scoped = Command::new("systemd-run");
+        scoped
+            .kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .args(["--user", "--scope", "--quiet", "--collect"]);
+        if let Some(memory_mb) = limits.memory_mb {
+            scoped
+                .arg("-p")
+                .arg(format!("MemoryMax={memory_mb}M"))
+                .args(["-p", "MemorySwapMax=0"]);
+        }
+        scoped
+            .arg("--")
+            .arg(local.get_program())
+            .args(local.get_args());
+
+        for (key, value) in local.get_envs() {
+            match value {
+                Some(value) => scoped.env(key, value),
+                None => scoped.env_remove(key),
+            };
+        }
+        if let Some(dir) = local.get_current_dir() {
+            scoped.current_dir(dir);
+        }
+        scoped
+    }
+}
+
+#[cfg(windows)]
+mod windows {
+    use std::{ffi::c_void, io, mem::size_of};
+
+    use command_group::AsyncGroupChild;
+    use windows_sys::Win32::{
+        Foundation::CloseHandle,
+        System::JobObjects::{
+            AssignProcessToJobObject, CreateJobObjectW, JOB_OBJECT_LIMIT_PROCESS_MEMORY,
+            JOB_OBJECT_LIMIT_PROCESS_TIME, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+            JobObjectExtendedLimitInformation, SetInformationJobObject,
+        },
+    };
+
+    use super::ResourceLimits;
+
+    /// Add the process to a new job object carrying the limits. Jobs nest, so this works even
+    /// though the process already belongs to its process-group job.
+    pub(super) fn assign_job(
+        child: &mut AsyncGroupChild,
+        limits: &ResourceLimits,
+    ) -> io::Result<()> {
+        if limits.memory_mb.is_none() && limits.cpu_seconds.is_none() {
+            return Ok(());
+        }
+        let Some(process) = child.inner().raw_handle() else {
+            return Ok(());
+        };
+
+        // SAFETY: the job handle is created, used and closed here; the process handle is owned
+        // by the child and outlives this call
+        unsafe {
+            let job = CreateJobObjectW(std::ptr::null(), std::ptr::null());
+            if job.is_null() {
+                return Err(io::Error::last_os_error());
+            }
+
+            let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
+            if let Some(memory_mb) = limits.memory_mb {
+                info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_MEMORY;
+                info.ProcessMemoryLimit = memory_mb.saturating_mul(1024 * 1024) as usize;
+            }
+            if let Some(cpu_seconds) = limits.cpu_seconds {
+                // In 100ns ticks
+                info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_TIME;
+                info.BasicLimitInformation.PerProcessUserTimeLimit =
+                    cpu_seconds.saturating_mul(10_000_000) as i64;
+            }
+
+            let applied = SetInformationJobObject(
+                job,
+                JobObjectExtendedLimitInformation,
+                &info as *const _ as *const c_void,
+                size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+            ) != 0
+                && AssignProcessToJobObject(job, process as _) != 0;
+            let result = if applied {
+                Ok(())
+            } else {
+                Err(io::Error::last_os_error())
+            };
+            // The job lives on while the process is assigned to it
+            CloseHandle(job);
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wall_clock_only_drops_os_limits() {
+        let limits = ResourceLimits {
+            memory_mb: Some(2048),
+            cpu_seconds: Some(600),
+            wall_clock_secs: Some(3600),
+        };
+        assert_eq!(
+            limits.wall_clock_only(),
+            ResourceLimits {
+                wall_clock_secs: Some(3600),
+                ..Default::default()
+            }
+        );
+        assert!(ResourceLimits::default().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn detects_limit_from_exit_status() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let limits = ResourceLimits {
+            memory_mb: Some(2048),
+            cpu_seconds: Some(600),
+            wall_clock_secs: None,
+        };
+        // Killed by SIGXCPU
+        assert_eq!(
+            limits.exceeded_by(&ExitStatus::from_raw(24)),
+            Some(ResourceLimit::Cpu)
+        );
+        // Shell reporting a child killed by SIGKILL
+        assert_eq!(
+            limits.exceeded_by(&ExitStatus::from_raw(137 << 8)),
+            Some(ResourceLimit::Memory)
+        );
+        assert_eq!(limits.exceeded_by(&ExitStatus::from_raw(1 << 8)), None);
+        assert_eq!(
+            ResourceLimits::default().exceeded_by(&ExitStatus::from_raw(9)),
+            None
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Agents that cannot go on without a person end their message with a handoff block:
+//!
+//! ```text
+//! <needs_human>
+//! Done: what was completed
+//! Blocked: what stops the agent
+//! Next steps:
+//! - what a person should do or decide
+//! </needs_human>
+//! ```
+//!
+//! Assistant messages carrying a block are normalized into a [`NormalizedEntryType::HumanHandoff`]
+//! entry, whichever executor produced them.
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::logs::{NormalizedEntry, NormalizedEntryType};
+
+pub const OPEN_TAG: &str = "<needs_human>";
+pub const CLOSE_TAG: &str = "</needs_human>";
+
+/// Note an agent leaves when it declares itself blocked.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+pub struct HumanHandoff {
+    /// What the agent completed before getting stuck
+    pub done: String,
+    /// What keeps the agent from going on
+    pub blocked: String,
+    pub next_steps: Vec<String>,
+}
+
+enum Section {
+    Done,
+    Blocked,
+    NextSteps,
+}
+
+/// Split a `Label: rest` line on a known label, matched case-insensitively.
+fn section_header(line: &str) -> Option<(Section, &str)> {
+    let (label, rest) = line.split_once(':')?;
+    let section = match label.trim().to_ascii_lowercase().as_str() {
+        "done" => Section::Done,
+        "blocked" => Section::Blocked,
+        "next steps" | "next" => Section::NextSteps,
+        _ => return None,
+    };
+    Some((section, rest.trim()))
+}
+
+fn push_line(text: &mut String, line: &str) {
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text.push_str(line);
+}
+
+/// The last complete handoff block of a message. A block without a `Blocked:` section is not
+/// a handoff.
+pub fn parse(content: &str) -> Option<HumanHandoff> {
+    let start = content.rfind(OPEN_TAG)? + OPEN_TAG.len();
+    let end = start + content[start..].find(CLOSE_TAG)?;
+
+    let mut handoff = HumanHandoff {
+        done: String::new(),
+        blocked: String::new(),
+        next_steps: Vec::new(),
+    };
+    let mut section = None;
+    for line in content[start..end].lines().map(str::trim) {
+        let line = match section_header(line) {
+            Some((header, rest)) => {
+                section = Some(header);
+                rest
+            }
+            None => line,
+        };
+        if line.is_empty() {
+            continue;
+        }
+        match section {
+            Some(Section::Done) => push_line(&mut handoff.done, line),
+            Some(Section::Blocked) => push_line(&mut handoff.blocked, line),
+            Some(Section::NextSteps) => {
+                let step = line.trim_start_matches(['-', '*', ' ']).trim();
+                if !step.is_empty() {
+                    handoff.next_steps.push(step.to_string());
+                }
+            }
+            None => {}
+        }
+    }
+
+    (!handoff.blocked.is_empty()).then_some(handoff)
+}
+
+/// Turn an assistant message carrying a handoff block into a handoff entry. Other entries are
+/// returned unchanged.
+pub fn promote(entry: NormalizedEntry) -> NormalizedEntry {
+    if !matches!(entry.entry_type, NormalizedEntryType::AssistantMessage) {
+        return entry;
+    }
+    match parse(&entry.content) {
+        Some(handoff) => NormalizedEntry {
+            entry_type: NormalizedEntryType::HumanHandoff { handoff },
+            ..entry
+        },
+        None => entry,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sections_and_steps() {
+        let content = "I could not finish.\n\n<needs_human>\nDone: Added the endpoint.\n\
+                       Wrote its tests.\nBLOCKED: The staging API key is missing.\nNext steps:\n\
+                       - Add STAGING_KEY to .env\n* Rerun the tests\n</needs_human>";
+        assert_eq!(
+            parse(content),
+            Some(HumanHandoff {
+                done: "Added the endpoint.\nWrote its tests.".to_string(),
+                blocked: "The staging API key is missing.".to_string(),
+                next_steps: vec![
+                    "Add STAGING_KEY to .env".to_string(),
+                    "Rerun the tests".to_string()
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn incomplete_blocks_are_not_handoffs() {
+        assert!(parse("<needs_human>\nBlocked: no access").is_none());
+        assert!(parse("<needs_human>\nDone: everything\n</needs_human>").is_none());
+        assert!(parse("All done, tests pass.").is_none());
+    }
+
+    #[test]
+    fn promote_only_touches_assistant_messages() {
+        let entry = |entry_type| NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: "<needs_human>\nBlocked: no access\n</needs_human>".to_string(),
+            metadata: None,
+        };
+        assert!(matches!(
+            promote(entry(NormalizedEntryType::AssistantMessage)).entry_type,
+            NormalizedEntryType::HumanHandoff { .. }
+        ));
+        assert!(matches!(
+            promote(entry(NormalizedEntryType::UserMessage)).entry_type,
+            NormalizedEntryType::UserMessage
+        ));
+    }
+}
@@ -1,9 +1,12 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
-use workspace_utils::approvals::ApprovalStatus;
-use workspace_utils::user_questions::UserQuestion;
+use workspace_utils::{
+    approvals::ApprovalStatus,
+    user_questions::{QuestionClaim, UserQuestion},
+};
 
+pub mod human_handoff;
 pub mod plain_text_processor;
 pub mod stderr_processor;
 pub mod utils;
@@ -96,6 +99,10 @@ pub enum NormalizedEntryType {
         execution_processes: usize,
         needs_setup: bool,
     },
+    /// Assistant message in which the agent declared itself blocked
+    HumanHandoff {
+        handoff: human_handoff::HumanHandoff,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -150,6 +157,9 @@ pub enum ToolStatus {
         requested_at: DateTime<Utc>,
         timeout_at: DateTime<Utc>,
         questions: Vec<UserQuestion>,
+        /// Reviewer currently answering the question
+        #[serde(default)]
+        claimed_by: Option<QuestionClaim>,
     },
     TimedOut,
 }
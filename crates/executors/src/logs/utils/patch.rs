@@ -6,7 +6,7 @@ use serde_json::{from_value, json, to_value};
 use ts_rs::TS;
 use workspace_utils::{diff::Diff, msg_store::MsgStore};
 
-use crate::logs::{NormalizedEntry, utils::EntryIndexProvider};
+use crate::logs::{NormalizedEntry, human_handoff, utils::EntryIndexProvider};
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, TS)]
 #[serde(rename_all = "lowercase")]
@@ -41,12 +41,13 @@ pub fn escape_json_pointer_segment(s: &str) -> String {
 pub struct ConversationPatch;
 
 impl ConversationPatch {
-    /// Create an ADD patch for a new conversation entry at the given index
+    /// Create an ADD patch for a new conversation entry at the given index. Assistant messages
+    /// carrying a handoff block become handoff entries here, for every executor.
     pub fn add_normalized_entry(entry_index: usize, entry: NormalizedEntry) -> Patch {
         let patch_entry = PatchEntry {
             op: PatchOperation::Add,
             path: format!("/entries/{entry_index}"),
-            value: PatchType::NormalizedEntry(entry),
+            value: PatchType::NormalizedEntry(human_handoff::promote(entry)),
         };
 
         from_value(json!([patch_entry])).unwrap()
@@ -110,7 +111,7 @@ impl ConversationPatch {
         let patch_entry = PatchEntry {
             op: PatchOperation::Replace,
             path: format!("/entries/{entry_index}"),
-            value: PatchType::NormalizedEntry(entry),
+            value: PatchType::NormalizedEntry(human_handoff::promote(entry)),
         };
 
         from_value(json!([patch_entry])).unwrap()
@@ -123,6 +124,16 @@ impl ConversationPatch {
         }]))
         .unwrap()
     }
+
+    /// Create an ADD patch setting a top-level field next to the entries, e.g. annotations
+    pub fn add_field(field: &str, value: serde_json::Value) -> Patch {
+        from_value(json!([{
+            "op": PatchOperation::Add,
+            "path": format!("/{}", escape_json_pointer_segment(field)),
+            "value": value,
+        }]))
+        .unwrap()
+    }
 }
 
 /// Extract the entry index and `NormalizedEntry` from a JsonPatch if it contains one
@@ -305,3 +305,151 @@ impl CodingAgent {
         apply_adapter(adapter, canonical)
     }
 }
+
+/// How an agent reaches a project-declared MCP server.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum McpServerTransport {
+    Stdio {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    Http {
+        url: String,
+        #[serde(default)]
+        headers: HashMap<String, String>,
+    },
+}
+
+/// An MCP server declared by a project, passed to executors that accept per-run MCP config
+/// (Claude Code via `--mcp-config`, Codex via config overrides).
+#[derive(Debug, Clone, PartialEq)]
+pub struct McpServerSpec {
+    pub name: String,
+    pub transport: McpServerTransport,
+    pub env: HashMap<String, String>,
+    /// Run this server's tools without asking, even when tool approvals are enabled.
+    /// Only Claude Code routes MCP tool calls through approvals.
+    pub auto_approve: bool,
+}
+
+impl McpServerSpec {
+    /// Agents prefix tool names with the server name (`mcp__{name}__{tool}`), so keep it simple.
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    }
+
+    /// Replace `${VAR}` references in env values and headers with the given variables, so
+    /// secrets can stay in project env vars. Unknown references are left as-is.
+    pub fn expand_vars(&mut self, vars: &HashMap<String, String>) {
+        for value in self.env.values_mut() {
+            *value = expand_vars(value, vars);
+        }
+        if let McpServerTransport::Http { headers, .. } = &mut self.transport {
+            for value in headers.values_mut() {
+                *value = expand_vars(value, vars);
+            }
+        }
+    }
+
+    fn claude_entry(&self) -> Value {
+        match &self.transport {
+            McpServerTransport::Stdio { command, args } => serde_json::json!({
+                "type": "stdio",
+                "command": command,
+                "args": args,
+                "env": self.env,
+            }),
+            McpServerTransport::Http { url, headers } => serde_json::json!({
+                "type": "http",
+                "url": url,
+                "headers": headers,
+            }),
+        }
+    }
+
+    /// Entry for Codex's `mcp_servers` table. Like the preconfigured servers, only stdio
+    /// servers are passed to Codex.
+    pub fn codex_entry(&self) -> Option<Value> {
+        match &self.transport {
+            McpServerTransport::Stdio { command, args } => Some(serde_json::json!({
+                "command": command,
+                "args": args,
+                "env": self.env,
+            })),
+            McpServerTransport::Http { .. } => None,
+        }
+    }
+}
+
+/// JSON accepted by Claude Code's `--mcp-config` flag.
+pub fn claude_mcp_config(servers: &[McpServerSpec]) -> Value {
+    let servers: Map<String, Value> = servers
+        .iter()
+        .map(|s| (s.name.clone(), s.claude_entry()))
+        .collect();
+    serde_json::json!({ "mcpServers": servers })
+}
+
+/// Replace `${VAR}` references in `value`, leaving unknown references as-is.
+pub(crate) fn expand_vars(value: &str, vars: &HashMap<String, String>) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find('}') {
+            Some(end) => {
+                let name = &after[..end];
+                match vars.get(name) {
+                    Some(v) => result.push_str(v),
+                    None => result.push_str(&rest[start..start + 2 + end + 1]),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_known_vars_only() {
+        let vars = HashMap::from([("TOKEN".to_string(), "abc".to_string())]);
+        assert_eq!(expand_vars("Bearer ${TOKEN}", &vars), "Bearer abc");
+        assert_eq!(expand_vars("${MISSING}-${TOKEN}", &vars), "${MISSING}-abc");
+        assert_eq!(expand_vars("open ${TOKEN", &vars), "open ${TOKEN");
+    }
+
+    #[test]
+    fn codex_skips_http_servers() {
+        let http = McpServerSpec {
+            name: "api".to_string(),
+            transport: McpServerTransport::Http {
+                url: "https://example.com/mcp".to_string(),
+                headers: HashMap::new(),
+            },
+            env: HashMap::new(),
+            auto_approve: false,
+        };
+        assert!(http.codex_entry().is_none());
+        assert_eq!(
+            claude_mcp_config(&[http])["mcpServers"]["api"]["type"],
+            "http"
+        );
+        assert!(McpServerSpec::is_valid_name("my-db_1"));
+        assert!(!McpServerSpec::is_valid_name("has space"));
+    }
+}
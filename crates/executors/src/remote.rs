@@ -0,0 +1,326 @@
+//! Run executor processes on a remote build host over SSH.
+//!
+//! The local server keeps orchestrating: it creates the worktree, mirrors it to the remote host
+//! with rsync before each process starts and mirrors it back once the process exits, so commits,
+//! diffs and merges still happen locally. The executor itself runs under `ssh -T`, which means its
+//! stdio - logs, and the stdin/stdout control protocols used for approvals and questions - flows
+//! through the SSH session unchanged. The backend port is reverse-forwarded so MCP task tools on
+//! the remote host can still reach this server.
+//!
+//! Files matched by `.gitignore` are not mirrored in either direction, so dependencies installed
+//! by a setup script stay on the remote host. Agent session state (used to resume follow-ups)
+//! lives on the remote host as well.
+//!
+//! Environment variables such as API keys are forwarded with `SendEnv` rather than written into
+//! the remote command line, where they would show up in process listings on both hosts. The
+//! remote `sshd` must accept them, e.g. with `AcceptEnv *` in `sshd_config`; variables it
+//! refuses are silently dropped.
+
+use std::{
+    ffi::OsStr,
+    io,
+    path::{Component, Path, PathBuf},
+    process::Stdio,
+};
+
+use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use tokio::process::Command;
+
+const DEFAULT_REMOTE_ROOT: &str = "vibe-kanban/worktrees";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteRunner {
+    /// SSH destination, e.g. `builder@build-01`
+    pub destination: String,
+    pub port: Option<u16>,
+    pub identity_file: Option<PathBuf>,
+    /// Local directory holding workspaces; paths below it are mirrored under `remote_root`
+    pub local_root: PathBuf,
+    /// Directory on the remote host mirroring `local_root`, relative to the remote home directory
+    /// unless absolute
+    pub remote_root: String,
+    /// Local backend port, reverse-forwarded into each session
+    pub backend_port: Option<u16>,
+}
+
+impl RemoteRunner {
+    /// Build a runner from `VK_REMOTE_RUNNER_*` settings. Returns `None` when no host is set.
+    pub fn from_settings(
+        setting: impl Fn(&str) -> Option<String>,
+        local_root: PathBuf,
+    ) -> Option<Self> {
+        let destination = setting("VK_REMOTE_RUNNER_HOST").filter(|host| !host.is_empty())?;
+        let port = setting("VK_REMOTE_RUNNER_PORT").and_then(|port| port.parse().ok());
+        let identity_file = setting("VK_REMOTE_RUNNER_IDENTITY").map(PathBuf::from);
+        let remote_root = setting("VK_REMOTE_RUNNER_ROOT")
+            .map(|root| root.trim_end_matches('/').to_string())
+            .filter(|root| !root.is_empty())
+            .unwrap_or_else(|| DEFAULT_REMOTE_ROOT.to_string());
+
+        Some(Self {
+            destination,
+            port,
+            identity_file,
+            local_root,
+            remote_root,
+            backend_port: None,
+        })
+    }
+
+    pub fn with_backend_port(mut self, port: Option<u16>) -> Self {
+        self.backend_port = port;
+        self
+    }
+
+    /// Remote path mirroring a local path under `local_root`
+    pub fn remote_path(&self, local: &Path) -> String {
+        let relative = match local.strip_prefix(&self.local_root) {
+            Ok(relative) => relative.to_path_buf(),
+            Err(_) => local.file_name().map(PathBuf::from).unwrap_or_default(),
+        };
+        let mut path = self.remote_root.clone();
+        for component in relative.components() {
+            if let Component::Normal(part) = component {
+                path.push('/');
+                path.push_str(&part.to_string_lossy());
+            }
+        }
+        path
+    }
+
+    /// Mirror a local workspace directory to the remote host
+    pub async fn push(&self, local_dir: &Path) -> io::Result<()> {
+        let remote_dir = self.remote_path(local_dir);
+        let mkdir = format!("mkdir -p {} && rsync", quote(&remote_dir)?);
+        let mut command = self.rsync_command()?;
+        command
+            .arg(format!("--rsync-path={mkdir}"))
+            .arg(format!("{}/", local_dir.display()))
+            .arg(format!("{}:{}/", self.destination, remote_dir));
+        run(command, "push").await
+    }
+
+    /// Mirror a remote workspace directory back over the local one
+    pub async fn pull(&self, local_dir: &Path) -> io::Result<()> {
+        let remote_dir = self.remote_path(local_dir);
+        let mut command = self.rsync_command()?;
+        command
+            .arg(format!("{}:{}/", self.destination, remote_dir))
+            .arg(format!("{}/", local_dir.display()));
+        run(command, "pull").await
+    }
+
+    /// Spawn a fully configured command on the remote host
+    pub fn spawn(&self, command: &Command) -> io::Result<AsyncGroupChild> {
+        self.wrap(command)?.group_spawn()
+    }
+
+    /// Rewrite a local command into an `ssh` invocation running the same program, arguments and
+    /// explicit env in the mirrored working directory. Stdio is always piped.
+    pub fn wrap(&self, command: &Command) -> io::Result<Command> {
+        let local = command.as_std();
+
+        let mut unset = Vec::new();
+        let mut forwarded = Vec::new();
+        let mut assignments = Vec::new();
+        for (key, value) in local.get_envs() {
+            match value {
+                Some(value) => forwarded.push((key, value)),
+                None => unset.push(key.to_string_lossy().into_owned()),
+            }
+        }
+        if let Some(port) = self.backend_port
+            && !local.get_envs().any(|(key, _)| key == "BACKEND_PORT")
+        {
+            assignments.push(format!("BACKEND_PORT={port}"));
+        }
+
+        let program = program_name(local.get_program());
+
+        let mut words = vec!["exec".to_string(), "env".to_string()];
+        for key in unset {
+            words.push("-u".to_string());
+            words.push(key);
+        }
+        words.extend(assignments);
+        words.push(program.to_string_lossy().into_owned());
+        words.extend(
+            local
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned()),
+        );
+
+        let mut script = words
+            .iter()
+            .map(|word| quote(word))
+            .collect::<io::Result<Vec<_>>>()?;
+        if let Some(dir) = local.get_current_dir() {
+            script.insert(0, format!("cd {} &&", quote(&self.remote_path(dir))?));
+        }
+
+        let mut ssh = Command::new("ssh");
+        ssh.kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .args(self.ssh_options())
+            .arg("-T");
+        if let Some(port) = self.backend_port {
+            ssh.arg("-R").arg(format!("{port}:127.0.0.1:{port}"));
+        }
+        // Only the names go on the command line; ssh reads the values from its own environment
+        for (key, value) in forwarded {
+            ssh.arg("-o")
+                .arg(format!("SendEnv={}", key.to_string_lossy()))
+                .env(key, value);
+        }
+        ssh.arg(&self.destination).arg(script.join(" "));
+        if let Some(dir) = local.get_current_dir().filter(|dir| dir.exists()) {
+            ssh.current_dir(dir);
+        }
+        Ok(ssh)
+    }
+
+    fn ssh_options(&self) -> Vec<String> {
+        let mut options = vec!["-o".to_string(), "BatchMode=yes".to_string()];
+        if let Some(port) = self.port {
+            options.push("-p".to_string());
+            options.push(port.to_string());
+        }
+        if let Some(identity_file) = &self.identity_file {
+            options.push("-i".to_string());
+            options.push(identity_file.to_string_lossy().into_owned());
+        }
+        options
+    }
+
+    fn rsync_command(&self) -> io::Result<Command> {
+        let mut ssh = vec!["ssh".to_string()];
+        ssh.extend(self.ssh_options());
+        let ssh = ssh
+            .iter()
+            .map(|word| quote(word))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let mut command = Command::new("rsync");
+        command
+            .kill_on_drop(true)
+            .stdin(Stdio::null())
+            .arg("-az")
+            .arg("--delete")
+            .arg("--exclude=.git")
+            .arg("--filter=:- .gitignore")
+            .arg("-e")
+            .arg(ssh.join(" "));
+        Ok(command)
+    }
+}
+
+/// Local absolute paths to a program are meaningless on another machine or image, so only the
+/// file name is kept and resolved from `PATH` there.
+pub(crate) fn program_name(program: &OsStr) -> &Path {
+    let program = Path::new(program);
+    if program.is_absolute() {
+        program.file_name().map(Path::new).unwrap_or(program)
+    } else {
+        program
+    }
+}
+
+pub(crate) fn quote(word: &str) -> io::Result<String> {
+    shlex::try_quote(word)
+        .map(|quoted| quoted.into_owned())
+        .map_err(io::Error::other)
+}
+
+async fn run(mut command: Command, direction: &str) -> io::Result<()> {
+    let output = command.output().await?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "rsync {direction} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runner() -> RemoteRunner {
+        RemoteRunner::from_settings(
+            |key| match key {
+                "VK_REMOTE_RUNNER_HOST" => Some("builder@build-01".to_string()),
+                "VK_REMOTE_RUNNER_PORT" => Some("2222".to_string()),
+                _ => None,
+            },
+            PathBuf::from("/tmp/vk/worktrees"),
+        )
+        .unwrap()
+        .with_backend_port(Some(4000))
+    }
+
+    #[test]
+    fn maps_local_paths_under_remote_root() {
+        let runner = runner();
+        assert_eq!(
+            runner.remote_path(Path::new("/tmp/vk/worktrees/abcd-task/repo")),
+            "vibe-kanban/worktrees/abcd-task/repo"
+        );
+        assert_eq!(
+            runner.remote_path(Path::new("/elsewhere/other")),
+            "vibe-kanban/worktrees/other"
+        );
+    }
+
+    #[test]
+    fn wraps_command_in_ssh() {
+        let mut command = Command::new("/usr/local/bin/claude");
+        command
+            .arg("-p")
+            .arg("fix the bug")
+            .env("API_KEY", "secret value")
+            .env_remove("ANTHROPIC_API_KEY")
+            .current_dir("/tmp/vk/worktrees/abcd-task");
+
+        let wrapped = runner().wrap(&command).unwrap();
+        let std = wrapped.as_std();
+        assert_eq!(std.get_program(), "ssh");
+
+        let args: Vec<_> = std
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        assert!(args.windows(2).any(|w| w == ["-p", "2222"]));
+        assert!(args.windows(2).any(|w| w == ["-R", "4000:127.0.0.1:4000"]));
+        assert!(args.windows(2).any(|w| w == ["-o", "SendEnv=API_KEY"]));
+        assert!(
+            std.get_envs()
+                .any(|(key, value)| key == "API_KEY" && value == Some(OsStr::new("secret value")))
+        );
+        assert!(!args.iter().any(|arg| arg.contains("secret value")));
+        assert_eq!(
+            shlex::split(args.last().unwrap()).unwrap(),
+            [
+                "cd",
+                "vibe-kanban/worktrees/abcd-task",
+                "&&",
+                "exec",
+                "env",
+                "-u",
+                "ANTHROPIC_API_KEY",
+                "BACKEND_PORT=4000",
+                "claude",
+                "-p",
+                "fix the bug",
+            ]
+        );
+    }
+
+    #[test]
+    fn disabled_without_host() {
+        assert!(RemoteRunner::from_settings(|_| None, PathBuf::from("/tmp")).is_none());
+    }
+}
@@ -0,0 +1,166 @@
+//! Run executor processes inside a Docker container.
+//!
+//! The worktree, and the git directories its checkouts point into, are bind-mounted at their host
+//! paths, so diffs, commits and log paths look exactly like a local run while the rest of the host
+//! stays out of the agent's reach. Only env set explicitly for the process is passed through, by
+//! name so values never show up in the host's process list; provider credentials should come from
+//! the secrets vault.
+
+use std::{io, path::PathBuf, process::Stdio};
+
+use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use tokio::process::Command;
+
+use crate::remote::program_name;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DockerSandbox {
+    pub image: String,
+    /// Docker network mode: `none`, `bridge` or `host`
+    pub network: String,
+    pub cpus: Option<f64>,
+    pub memory_mb: Option<i64>,
+    pub pids_limit: Option<i64>,
+    /// Host paths mounted read-write at the same path inside the container
+    pub mounts: Vec<PathBuf>,
+    /// `uid:gid` to run as, so files written in the worktree stay owned by the host user
+    pub user: Option<String>,
+    /// Container name, used to remove the container if the docker client is killed
+    pub name: String,
+}
+
+impl DockerSandbox {
+    /// Spawn a fully configured command inside the sandbox
+    pub fn spawn(&self, command: &Command) -> io::Result<AsyncGroupChild> {
+        self.wrap(command).group_spawn()
+    }
+
+    /// Rewrite a local command into a `docker run` invocation of the same program, arguments and
+    /// explicit env. Stdio is always piped.
+    pub fn wrap(&self, command: &Command) -> Command {
+        let local = command.as_std();
+
+        let mut docker = Command::new("docker");
+        docker
+            .kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .args(["run", "--rm", "-i", "--init"])
+            .arg("--name")
+            .arg(&self.name)
+            .arg("--network")
+            .arg(&self.network);
+        if let Some(cpus) = self.cpus {
+            docker.arg("--cpus").arg(cpus.to_string());
+        }
+        if let Some(memory_mb) = self.memory_mb {
+            docker.arg("--memory").arg(format!("{memory_mb}m"));
+        }
+        if let Some(pids_limit) = self.pids_limit {
+            docker.arg("--pids-limit").arg(pids_limit.to_string());
+        }
+        if let Some(user) = &self.user {
+            docker.arg("--user").arg(user);
+        }
+        for mount in &self.mounts {
+            docker.arg("-v").arg(format!("{0}:{0}", mount.display()));
+        }
+        if let Some(dir) = local.get_current_dir() {
+            docker.arg("-w").arg(dir);
+            if dir.exists() {
+                docker.current_dir(dir);
+            }
+        }
+        for (key, value) in local.get_envs() {
+            if let Some(value) = value {
+                docker.arg("-e").arg(key).env(key, value);
+            }
+        }
+
+        docker
+            .arg(&self.image)
+            .arg(program_name(local.get_program()))
+            .args(local.get_args());
+        docker
+    }
+
+    /// Force-remove a sandbox container; it normally removes itself when its process exits.
+    pub async fn remove(name: &str) {
+        let result = Command::new("docker")
+            .args(["rm", "-f", name])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await;
+        if let Err(e) = result {
+            tracing::debug!("Failed to remove sandbox container {}: {}", name, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_command_in_docker_run() {
+        let sandbox = DockerSandbox {
+            image: "ghcr.io/acme/agent:latest".to_string(),
+            network: "none".to_string(),
+            cpus: Some(2.0),
+            memory_mb: Some(4096),
+            pids_limit: None,
+            mounts: vec![PathBuf::from("/tmp/vk/worktrees/abcd-task")],
+            user: Some("1000:1000".to_string()),
+            name: "vk-abcd".to_string(),
+        };
+        let mut command = Command::new("/usr/local/bin/claude");
+        command
+            .arg("-p")
+            .env("API_KEY", "secret")
+            .current_dir("/tmp/vk/worktrees/abcd-task");
+
+        let wrapped = sandbox.wrap(&command);
+        let std = wrapped.as_std();
+        assert_eq!(std.get_program(), "docker");
+        assert!(
+            std.get_envs()
+                .any(|(key, value)| key == "API_KEY" && value == Some("secret".as_ref()))
+        );
+
+        let args: Vec<_> = std
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            args,
+            [
+                "run",
+                "--rm",
+                "-i",
+                "--init",
+                "--name",
+                "vk-abcd",
+                "--network",
+                "none",
+                "--cpus",
+                "2",
+                "--memory",
+                "4096m",
+                "--user",
+                "1000:1000",
+                "-v",
+                "/tmp/vk/worktrees/abcd-task:/tmp/vk/worktrees/abcd-task",
+                "-w",
+                "/tmp/vk/worktrees/abcd-task",
+                "-e",
+                "API_KEY",
+                "ghcr.io/acme/agent:latest",
+                "claude",
+                "-p",
+            ]
+        );
+    }
+}
@@ -2,7 +2,10 @@ use std::{
     collections::HashMap,
     io,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
     time::Duration,
 };
 
@@ -12,15 +15,23 @@ use command_group::AsyncGroupChild;
 use db::{
     DBService,
     models::{
+        attempt_checkpoint::CheckpointTrigger,
+        attempt_handoff::AttemptHandoff,
         coding_agent_turn::CodingAgentTurn,
         execution_process::{
             ExecutionContext, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
         },
+        execution_process_failure::{ExecutionProcessFailure, ExecutionProcessFailureKind},
+        execution_process_heartbeat::{ExecutionProcessHeartbeat, RecordHeartbeat},
+        execution_process_limits::ExecutionProcessLimits,
         execution_process_repo_state::ExecutionProcessRepoState,
+        project_mcp_server::ProjectMcpServer,
         project_repo::ProjectRepo,
+        project_sandbox::ProjectSandbox,
         repo::Repo,
         scratch::{DraftFollowUpData, Scratch, ScratchType},
-        task::{Task, TaskStatus},
+        task::TaskStatus,
+        tool_usage::ToolUsage,
         workspace::Workspace,
         workspace_repo::WorkspaceRepo,
     },
@@ -35,35 +46,72 @@ use executors::{
     approvals::{ExecutorApprovalService, ExecutorQuestionService, NoopExecutorApprovalService},
     env::ExecutionEnv,
     executors::{BaseCodingAgent, ExecutorExitResult, ExecutorExitSignal, InterruptSender},
-    logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch},
-    profile::ExecutorProfileId,
+    kubernetes::{KubernetesConfig, KubernetesJob},
+    limits::ResourceLimit,
+    logs::{
+        NormalizedEntryType, human_handoff::HumanHandoff,
+        utils::patch::extract_normalized_entry_from_patch,
+    },
+    profile::{ExecutorConfigs, ExecutorProfileId},
+    remote::RemoteRunner,
+    sandbox::DockerSandbox,
+};
+use futures::{
+    FutureExt, Stream, StreamExt,
+    stream::{self, select},
 };
-use futures::{FutureExt, TryStreamExt, stream::select};
-use serde_json::json;
 use services::services::{
-    analytics::AnalyticsContext,
     approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
-    config::Config,
+    attachment::AttachmentService,
+    attempt_summary,
+    code_map::CodeMapService,
+    config::{CodeMapConfig, CodeReviewConfig, Config},
     container::{ContainerError, ContainerRef, ContainerService},
+    context_handoff,
     diff_stream::{self, DiffStreamHandle},
+    events::{EntityEvent, EntityEventBus},
+    execution_failure, executor_stats,
     git::{Commit, GitCli, GitService},
+    human_handoff,
     image::ImageService,
+    llm::{LlmError, LlmService},
+    model_fallback,
     notification::NotificationService,
+    project_env::{ProjectEnvService, SecretRedactor},
     queued_message::QueuedMessageService,
+    secret_vault::SecretVault,
+    server_config,
     share::SharePublisher,
-    user_questions::{UserQuestions, executor_questions::ExecutorQuestionBridge},
+    task_workflow, tool_stats,
+    tunnel::TunnelService,
+    user_questions::{
+        UserQuestions, auto_answer::QuestionAutoAnswers, escalation::QuestionEscalation,
+        executor_questions::ExecutorQuestionBridge,
+    },
     workspace_manager::{RepoWorkspaceInput, WorkspaceManager},
 };
-use tokio::{sync::RwLock, task::JoinHandle};
+use tokio::{io::AsyncRead, sync::RwLock, task::JoinHandle};
 use tokio_util::io::ReaderStream;
+use tracing::Instrument;
 use utils::{
     log_msg::LogMsg,
     msg_store::MsgStore,
+    port_file::read_port_file,
     text::{git_branch_id, short_uuid, truncate_to_char_boundary},
 };
 use uuid::Uuid;
 
-use crate::{command, copy};
+use crate::{command, coordination::InstanceCoordinator, copy, supervisor};
+
+/// How often running child processes are sampled and their heartbeats recorded
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+/// Running processes without a heartbeat for this long have lost their supervising instance
+const HEARTBEAT_STALE_AFTER: Duration = Duration::from_secs(120);
+/// How often the worktrees of running coding agents are checkpointed when they changed
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(120);
+/// How long output that could start a secret is held back before it is shown anyway, so a
+/// trailing prompt without a newline doesn't stall
+const REDACTION_IDLE_FLUSH: Duration = Duration::from_millis(250);
 
 #[derive(Clone)]
 pub struct LocalContainerService {
@@ -74,14 +122,28 @@ pub struct LocalContainerService {
     config: Arc<RwLock<Config>>,
     git: GitService,
     image_service: ImageService,
-    analytics: Option<AnalyticsContext>,
+    attachment_service: AttachmentService,
     approvals: Approvals,
     user_questions: UserQuestions,
     queued_message_service: QueuedMessageService,
     publisher: Result<SharePublisher, RemoteClientNotConfigured>,
     notification_service: NotificationService,
+    llm: LlmService,
+    code_map: CodeMapService,
+    project_env: ProjectEnvService,
+    secret_vault: SecretVault,
+    tunnels: TunnelService,
+    entity_events: EntityEventBus,
+    /// Build host that coding agents and scripts run on over SSH, if configured
+    remote_runner: Option<RemoteRunner>,
+    /// Cluster that attempts run in as Jobs, if configured
+    kubernetes: Option<KubernetesConfig>,
     /// Stores devctl2 subdomain URLs for execution processes (exec_id -> URL)
     devctl2_urls: Arc<RwLock<HashMap<Uuid, String>>>,
+    /// Set once shutdown starts; no new executions are started after that
+    draining: Arc<AtomicBool>,
+    /// Attempt locks shared with other instances, when several share this board
+    coordinator: Option<InstanceCoordinator>,
 }
 
 impl LocalContainerService {
@@ -92,16 +154,44 @@ impl LocalContainerService {
         config: Arc<RwLock<Config>>,
         git: GitService,
         image_service: ImageService,
-        analytics: Option<AnalyticsContext>,
+        attachment_service: AttachmentService,
         approvals: Approvals,
         queued_message_service: QueuedMessageService,
         publisher: Result<SharePublisher, RemoteClientNotConfigured>,
+        llm: LlmService,
+        project_env: ProjectEnvService,
+        secret_vault: SecretVault,
+        tunnels: TunnelService,
+        entity_events: EntityEventBus,
+        coordinator: Option<InstanceCoordinator>,
     ) -> Self {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
         let interrupt_senders = Arc::new(RwLock::new(HashMap::new()));
-        let notification_service = NotificationService::new(config.clone());
+        let notification_service =
+            NotificationService::new(config.clone(), db.pool.clone(), entity_events.clone());
         let devctl2_urls = Arc::new(RwLock::new(HashMap::new()));
-        let user_questions = UserQuestions::new(msg_stores.clone());
+        let user_questions = UserQuestions::new(msg_stores.clone())
+            .with_escalation(QuestionEscalation::new(
+                db.clone(),
+                notification_service.clone(),
+                project_env.cipher().clone(),
+            ))
+            .with_auto_answers(QuestionAutoAnswers::new(db.clone()));
+        let code_map = CodeMapService::new(git.clone());
+        let remote_runner = RemoteRunner::from_settings(
+            server_config::setting,
+            WorkspaceManager::get_workspace_base_dir(),
+        );
+        if let Some(remote) = &remote_runner {
+            tracing::info!("Running executors on remote host {}", remote.destination);
+        }
+        let kubernetes = kubernetes_config();
+        if let Some(kubernetes) = &kubernetes {
+            tracing::info!(
+                "Running executors as Kubernetes Jobs in namespace {}",
+                kubernetes.namespace
+            );
+        }
 
         let container = LocalContainerService {
             db,
@@ -111,20 +201,94 @@ impl LocalContainerService {
             config,
             git,
             image_service,
-            analytics,
+            attachment_service,
             approvals,
             user_questions,
             queued_message_service,
             publisher,
             notification_service,
+            llm,
+            code_map,
+            project_env,
+            secret_vault,
+            tunnels,
+            entity_events,
+            remote_runner,
+            kubernetes,
             devctl2_urls,
+            draining: Arc::new(AtomicBool::new(false)),
+            coordinator,
         };
 
         container.spawn_workspace_cleanup().await;
+        container.spawn_process_supervisor();
+        container.spawn_checkpointer();
 
         container
     }
 
+    /// Docker sandbox for one execution, with the workspace and its repos' git dirs mounted
+    async fn docker_sandbox(
+        &self,
+        sandbox: &ProjectSandbox,
+        workspace: &Workspace,
+        exec_id: Uuid,
+        workspace_dir: &Path,
+    ) -> Result<DockerSandbox, ContainerError> {
+        let repos = WorkspaceRepo::find_repos_for_workspace(&self.db.pool, workspace.id).await?;
+        let mut mounts = vec![workspace_dir.to_path_buf()];
+        mounts.extend(
+            repos
+                .iter()
+                .map(|repo| repo.path.join(".git"))
+                .filter(|git_dir| git_dir.is_dir()),
+        );
+
+        Ok(DockerSandbox {
+            image: sandbox.image.clone(),
+            network: sandbox.network.to_string(),
+            cpus: sandbox.cpus,
+            memory_mb: sandbox.memory_mb,
+            pids_limit: sandbox.pids_limit,
+            mounts,
+            user: sandbox_user(workspace_dir),
+            name: execution_container_name(exec_id),
+        })
+    }
+
+    /// Kubernetes Job for one execution, using the project's sandbox image and limits
+    async fn kubernetes_job(
+        &self,
+        config: &KubernetesConfig,
+        project_id: Uuid,
+        workspace: &Workspace,
+        exec_id: Uuid,
+    ) -> Result<KubernetesJob, ContainerError> {
+        let sandbox = ProjectSandbox::find_by_project_id(&self.db.pool, project_id).await?;
+        let image = sandbox
+            .as_ref()
+            .map(|sandbox| sandbox.image.clone())
+            .or_else(|| config.default_image.clone())
+            .ok_or(ContainerError::Other(anyhow!(
+                "No image for Kubernetes Jobs: set a project sandbox image or a default image"
+            )))?;
+
+        let home_claim = kubernetes_home_claim(workspace.id);
+        config
+            .ensure_home_claim(&home_claim)
+            .await
+            .map_err(|e| ContainerError::Other(anyhow!("Failed to create {home_claim}: {e}")))?;
+
+        Ok(KubernetesJob {
+            config: config.clone(),
+            name: execution_container_name(exec_id),
+            image,
+            cpus: sandbox.as_ref().and_then(|sandbox| sandbox.cpus),
+            memory_mb: sandbox.as_ref().and_then(|sandbox| sandbox.memory_mb),
+            home_claim: Some(home_claim),
+        })
+    }
+
     pub async fn get_child_from_store(&self, id: &Uuid) -> Option<Arc<RwLock<AsyncGroupChild>>> {
         let map = self.child_store.read().await;
         map.get(id).cloned()
@@ -186,6 +350,12 @@ impl LocalContainerService {
                 });
         }
 
+        if let Some(kubernetes) = kubernetes_config() {
+            kubernetes
+                .delete_claim(&kubernetes_home_claim(workspace.id))
+                .await;
+        }
+
         // Clear container_ref so this workspace won't be picked up again
         let _ = Workspace::clear_container_ref(&db.pool, workspace.id).await;
     }
@@ -223,6 +393,128 @@ impl LocalContainerService {
         });
     }
 
+    /// Periodically record a heartbeat for every child this instance runs, reap children that
+    /// exited without being collected, and fail running processes nobody supervises any more.
+    fn spawn_process_supervisor(&self) {
+        let container = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+            loop {
+                interval.tick().await;
+                container.record_heartbeats().await;
+                container.reconcile_stale_processes().await;
+            }
+        });
+    }
+
+    /// Periodically checkpoint the worktrees of the coding agents this instance runs.
+    fn spawn_checkpointer(&self) {
+        let container = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CHECKPOINT_INTERVAL);
+            loop {
+                interval.tick().await;
+                container.checkpoint_running_agents().await;
+            }
+        });
+    }
+
+    async fn checkpoint_running_agents(&self) {
+        let exec_ids: Vec<Uuid> = self.child_store.read().await.keys().copied().collect();
+        for exec_id in exec_ids {
+            let Ok(ctx) = ExecutionProcess::load_context(&self.db.pool, exec_id).await else {
+                continue;
+            };
+            if !matches!(
+                ctx.execution_process.run_reason,
+                ExecutionProcessRunReason::CodingAgent
+            ) || !matches!(
+                ctx.execution_process.status,
+                ExecutionProcessStatus::Running
+            ) {
+                continue;
+            }
+            if let Err(e) = self
+                .create_checkpoint(&ctx.workspace, exec_id, CheckpointTrigger::Periodic)
+                .await
+            {
+                tracing::warn!("Failed to checkpoint execution process {}: {}", exec_id, e);
+            }
+        }
+    }
+
+    async fn record_heartbeats(&self) {
+        let children: Vec<_> = self
+            .child_store
+            .read()
+            .await
+            .iter()
+            .map(|(id, child)| (*id, child.clone()))
+            .collect();
+        for (exec_id, child_lock) in children {
+            // No pid once the exit status has been collected; the exit monitor takes it from here
+            let Some(pid) = child_lock.write().await.inner().id() else {
+                continue;
+            };
+            let Some(sample) = supervisor::sample(pid) else {
+                tracing::warn!("Execution process {} (pid {}) has vanished", exec_id, pid);
+                continue;
+            };
+            if sample.is_defunct() {
+                // Collect the exit status so the exit monitor sees the process finish
+                tracing::warn!(
+                    "Reaping defunct execution process {} (pid {})",
+                    exec_id,
+                    pid
+                );
+                if let Err(e) = child_lock.write().await.try_wait() {
+                    tracing::error!("Failed to reap execution process {}: {}", exec_id, e);
+                }
+            }
+            if let Err(e) =
+                ExecutionProcessHeartbeat::record(&self.db.pool, exec_id, &sample.heartbeat()).await
+            {
+                tracing::error!("Failed to record heartbeat for {}: {}", exec_id, e);
+            }
+        }
+    }
+
+    /// Fail running processes whose supervising instance stopped sending heartbeats, e.g. because
+    /// it crashed, so they don't show as running forever
+    async fn reconcile_stale_processes(&self) {
+        let stale = match ExecutionProcessHeartbeat::find_stale_running(
+            &self.db.pool,
+            HEARTBEAT_STALE_AFTER.as_secs() as i64,
+        )
+        .await
+        {
+            Ok(stale) => stale,
+            Err(e) => {
+                tracing::error!("Failed to find stale execution processes: {}", e);
+                return;
+            }
+        };
+        for exec_id in stale {
+            if self.get_child_from_store(&exec_id).await.is_some() {
+                continue;
+            }
+            tracing::warn!(
+                "Execution process {} stopped sending heartbeats, marking it failed",
+                exec_id
+            );
+            if let Err(e) = ExecutionProcess::update_completion(
+                &self.db.pool,
+                exec_id,
+                ExecutionProcessStatus::Failed,
+                None,
+            )
+            .await
+            {
+                tracing::error!("Failed to fail stale execution process {}: {}", exec_id, e);
+            }
+        }
+    }
+
     /// Record the current HEAD commit for each repository as the "after" state.
     /// Errors are silently ignored since this runs after the main execution completes
     /// and failure should not block process finalization.
@@ -361,18 +653,31 @@ impl LocalContainerService {
         let child_store = self.child_store.clone();
         let msg_stores = self.msg_stores.clone();
         let db = self.db.clone();
-        let config = self.config.clone();
         let container = self.clone();
-        let analytics = self.analytics.clone();
+        let entity_events = self.entity_events.clone();
         let publisher = self.publisher.clone();
 
         let mut process_exit_rx = self.spawn_os_exit_watcher(exec_id);
+        // Covers the lifetime of the process for trace export
+        let span = tracing::info_span!("execution_process.run", execution_process_id = %exec_id);
 
         tokio::spawn(async move {
             let mut exit_signal_future = exit_signal
                 .map(|rx| rx.boxed()) // wait for result
                 .unwrap_or_else(|| std::future::pending().boxed()); // no signal, stall forever
 
+            let limits = ExecutionProcessLimits::find_by_execution_process_id(&db.pool, exec_id)
+                .await
+                .ok()
+                .flatten()
+                .map(|row| row.limits())
+                .unwrap_or_default();
+            let mut wall_clock_future = limits
+                .wall_clock()
+                .map(|limit| tokio::time::sleep(limit).boxed())
+                .unwrap_or_else(|| std::future::pending().boxed());
+            let mut exceeded: Option<ResourceLimit> = None;
+
             let status_result: std::io::Result<std::process::ExitStatus>;
 
             // Wait for process to exit, or exit signal from executor
@@ -399,6 +704,35 @@ impl LocalContainerService {
                 // Process exit
                 exit_status_result = &mut process_exit_rx => {
                     status_result = exit_status_result.unwrap_or_else(|e| Err(std::io::Error::other(e)));
+                    if let Ok(exit_status) = &status_result
+                        && !ExecutionProcess::was_stopped(&db.pool, exec_id).await
+                    {
+                        exceeded = limits.exceeded_by(exit_status);
+                    }
+                }
+                // Wall-clock limit reached: kill group and fail the run
+                _ = &mut wall_clock_future => {
+                    if let Some(child_lock) = child_store.read().await.get(&exec_id).cloned() {
+                        let mut child = child_lock.write().await;
+                        if let Err(err) = command::kill_process_group(&mut child).await {
+                            tracing::error!("Failed to kill process group after wall-clock limit: {} {}", exec_id, err);
+                        }
+                    }
+                    exceeded = Some(ResourceLimit::WallClock);
+                    status_result = Ok(failure_exit_status());
+                }
+            }
+
+            if let Some(limit) = exceeded {
+                let message = format!("killed: exceeded {} limit", limits.describe(limit));
+                tracing::warn!("Execution process {} {}", exec_id, message);
+                if let Some(msg_store) = msg_stores.read().await.get(&exec_id) {
+                    msg_store.push_stderr(message);
+                }
+                if let Err(e) =
+                    ExecutionProcessLimits::set_exceeded(&db.pool, exec_id, limit).await
+                {
+                    tracing::error!("Failed to record exceeded limit: {}", e);
                 }
             }
 
@@ -423,16 +757,112 @@ impl LocalContainerService {
             }
 
             if let Ok(ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
+                // Bring the remote host's changes back before anything inspects the worktree
+                if let Some(remote) = &container.remote_runner
+                    && !matches!(
+                        ctx.execution_process.run_reason,
+                        ExecutionProcessRunReason::DevServer
+                    )
+                    && let Some(dir) = ctx.workspace.container_ref.as_deref()
+                    && let Err(e) = remote.pull(Path::new(dir)).await
+                {
+                    tracing::error!("Failed to sync workspace back from remote host: {}", e);
+                }
+
+                if let Some(kubernetes) = &container.kubernetes
+                    && !matches!(
+                        ctx.execution_process.run_reason,
+                        ExecutionProcessRunReason::DevServer
+                    )
+                {
+                    kubernetes
+                        .delete_job(&execution_container_name(exec_id))
+                        .await;
+                }
+
+                // Sandbox containers remove themselves, unless the docker client was killed
+                if let Ok(Some(_)) = ProjectSandbox::find_enabled(&db.pool, ctx.project.id).await {
+                    DockerSandbox::remove(&execution_container_name(exec_id)).await;
+                }
+
+                // Dev server exited on its own: tear down any public tunnel pointing at it
+                if matches!(
+                    ctx.execution_process.run_reason,
+                    ExecutionProcessRunReason::DevServer
+                ) {
+                    container.tunnels.close(&exec_id).await;
+                }
+
                 // Update executor session summary if available
                 if let Err(e) = container.update_executor_session_summary(&exec_id).await {
                     tracing::warn!("Failed to update executor session summary: {}", e);
                 }
+                if let Err(e) = container.update_executor_session_cost(&exec_id).await {
+                    tracing::warn!("Failed to update executor session cost: {}", e);
+                }
+                if let Err(e) = container.record_tool_usages(&exec_id).await {
+                    tracing::warn!("Failed to record tool usage: {}", e);
+                }
+                if matches!(
+                    ctx.execution_process.status,
+                    ExecutionProcessStatus::Failed
+                ) && let Err(e) = container.record_failure_detail(&exec_id).await
+                {
+                    tracing::warn!("Failed to record failure detail: {}", e);
+                }
+
+                // A failed agent run is continued rather than ending the attempt where possible:
+                // provider errors, context overflows and exhausted budgets move on to the
+                // profile's next model, and an overflow with no model left to try restarts in a
+                // fresh session seeded with a summary of the conversation
+                let continued = if matches!(
+                    ctx.execution_process.status,
+                    ExecutionProcessStatus::Failed
+                ) && matches!(
+                    ctx.execution_process.run_reason,
+                    ExecutionProcessRunReason::CodingAgent
+                ) {
+                    match container.try_start_model_fallback(&ctx).await {
+                        Ok(true) => true,
+                        Ok(false) => match container.try_start_context_handoff(&ctx).await {
+                            Ok(started) => started,
+                            Err(e) => {
+                                tracing::error!("Failed to start context handoff: {}", e);
+                                false
+                            }
+                        },
+                        Err(e) => {
+                            tracing::error!("Failed to start model fallback: {}", e);
+                            false
+                        }
+                    }
+                } else {
+                    false
+                };
 
                 let success = matches!(
                     ctx.execution_process.status,
                     ExecutionProcessStatus::Completed
                 ) && exit_code == Some(0);
 
+                // An agent that declared itself blocked hands the attempt to a person instead of
+                // moving on to the next action or review
+                let handoff = if success
+                    && matches!(
+                        ctx.execution_process.run_reason,
+                        ExecutionProcessRunReason::CodingAgent
+                    ) {
+                    match container.record_human_handoff(&ctx).await {
+                        Ok(handoff) => handoff,
+                        Err(e) => {
+                            tracing::error!("Failed to record human handoff: {}", e);
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+
                 let cleanup_done = matches!(
                     ctx.execution_process.run_reason,
                     ExecutionProcessRunReason::CleanupScript
@@ -456,7 +886,7 @@ impl LocalContainerService {
                         ctx.execution_process.run_reason,
                         ExecutionProcessRunReason::CodingAgent
                     ) {
-                        changes_committed
+                        changes_committed && handoff.is_none()
                     } else {
                         true
                     };
@@ -466,7 +896,7 @@ impl LocalContainerService {
                         if let Err(e) = container.try_start_next_action(&ctx).await {
                             tracing::error!("Failed to start next action after completion: {}", e);
                         }
-                    } else {
+                    } else if handoff.is_none() {
                         tracing::info!(
                             "Skipping cleanup script for workspace {} - no changes made by coding agent",
                             ctx.workspace.id
@@ -477,7 +907,11 @@ impl LocalContainerService {
                     }
                 }
 
-                if container.should_finalize(&ctx) {
+                if let Some(handoff) = &handoff {
+                    container
+                        .block_task(publisher.as_ref().ok(), &ctx, handoff)
+                        .await;
+                } else if !continued && container.should_finalize(&ctx) {
                     // Only execute queued messages if the execution succeeded
                     // If it failed or was killed, just clear the queue and finalize
                     let should_execute_queued = !matches!(
@@ -531,22 +965,35 @@ impl LocalContainerService {
                     }
                 }
 
-                // Fire analytics event when CodingAgent execution has finished
-                if config.read().await.analytics_enabled
+                // Every finished agent run leaves a checkpoint to roll back to
+                if matches!(
+                    ctx.execution_process.run_reason,
+                    ExecutionProcessRunReason::CodingAgent
+                ) && let Err(e) = container
+                    .create_checkpoint(&ctx.workspace, exec_id, CheckpointTrigger::RunEnd)
+                    .await
+                {
+                    tracing::warn!("Failed to checkpoint execution process {}: {}", exec_id, e);
+                }
+
+                // Integrations such as analytics consume this from the entity event bus
+                if !continued
                     && matches!(
                         &ctx.execution_process.run_reason,
                         ExecutionProcessRunReason::CodingAgent
                     )
-                    && let Some(analytics) = &analytics
                 {
-                    analytics.analytics_service.track_event(&analytics.user_id, "task_attempt_finished", Some(json!({
-                        "task_id": ctx.task.id.to_string(),
-                        "project_id": ctx.task.project_id.to_string(),
-                        "workspace_id": ctx.workspace.id.to_string(),
-                        "session_id": ctx.session.id.to_string(),
-                        "execution_success": matches!(ctx.execution_process.status, ExecutionProcessStatus::Completed),
-                        "exit_code": ctx.execution_process.exit_code,
-                    })));
+                    entity_events.publish(
+                        Some(ctx.task.project_id),
+                        EntityEvent::AttemptFinished {
+                            workspace_id: ctx.workspace.id,
+                            task_id: ctx.task.id,
+                            session_id: ctx.session.id,
+                            execution_process_id: ctx.execution_process.id,
+                            status: ctx.execution_process.status.clone(),
+                            exit_code: ctx.execution_process.exit_code,
+                        },
+                    );
                 }
             }
 
@@ -570,7 +1017,12 @@ impl LocalContainerService {
 
             // Cleanup child handle
             child_store.write().await.remove(&exec_id);
-        })
+
+            if let Some(coordinator) = &container.coordinator {
+                coordinator.release(exec_id).await;
+            }
+        }
+        .instrument(span))
     }
 
     pub fn spawn_os_exit_watcher(
@@ -615,19 +1067,20 @@ impl LocalContainerService {
         format!("{}-{}", short_uuid(workspace_id), task_title_id)
     }
 
-    async fn track_child_msgs_in_store(&self, id: Uuid, child: &mut AsyncGroupChild) {
+    async fn track_child_msgs_in_store(
+        &self,
+        id: Uuid,
+        child: &mut AsyncGroupChild,
+        secrets: Arc<Vec<String>>,
+    ) {
         let store = Arc::new(MsgStore::new());
 
         let out = child.inner().stdout.take().expect("no stdout");
         let err = child.inner().stderr.take().expect("no stderr");
 
-        // Map stdout bytes -> LogMsg::Stdout
-        let out = ReaderStream::new(out)
-            .map_ok(|chunk| LogMsg::Stdout(String::from_utf8_lossy(&chunk).into_owned()));
-
-        // Map stderr bytes -> LogMsg::Stderr
-        let err = ReaderStream::new(err)
-            .map_ok(|chunk| LogMsg::Stderr(String::from_utf8_lossy(&chunk).into_owned()));
+        // Map stdout/stderr bytes -> LogMsg, scrubbing project secrets
+        let out = redacted_output(out, secrets.clone(), LogMsg::Stdout);
+        let err = redacted_output(err, secrets, LogMsg::Stderr);
 
         // If you have a JSON Patch source, map it to LogMsg::JsonPatch too, then select all three.
 
@@ -672,7 +1125,11 @@ impl LocalContainerService {
             if let LogMsg::JsonPatch(patch) = msg {
                 // Try to extract a NormalizedEntry from the patch
                 if let Some((_, entry)) = extract_normalized_entry_from_patch(patch)
-                    && matches!(entry.entry_type, NormalizedEntryType::AssistantMessage)
+                    && matches!(
+                        entry.entry_type,
+                        NormalizedEntryType::AssistantMessage
+                            | NormalizedEntryType::HumanHandoff { .. }
+                    )
                 {
                     let content = entry.content.trim();
                     if !content.is_empty() {
@@ -709,7 +1166,212 @@ impl LocalContainerService {
         Ok(())
     }
 
-    /// Copy project files and images to the workspace.
+    /// Record the cost the coding agent reported at the end of its run, if any
+    async fn update_executor_session_cost(&self, exec_id: &Uuid) -> Result<(), anyhow::Error> {
+        let cost = {
+            let msg_stores = self.msg_stores.read().await;
+            msg_stores
+                .get(exec_id)
+                .and_then(|store| executor_stats::reported_cost_usd(&store.get_history()))
+        };
+        if let Some(cost) = cost {
+            CodingAgentTurn::update_cost(&self.db.pool, *exec_id, cost).await?;
+        }
+        Ok(())
+    }
+
+    /// Record the tool calls in the run's normalized log for tool statistics
+    async fn record_tool_usages(&self, exec_id: &Uuid) -> Result<(), anyhow::Error> {
+        let approval_waits = self.approvals.take_approval_waits(exec_id);
+        let usages = {
+            let msg_stores = self.msg_stores.read().await;
+            msg_stores
+                .get(exec_id)
+                .map(|store| tool_stats::usages_from_history(&store.get_history(), &approval_waits))
+                .unwrap_or_default()
+        };
+        if !usages.is_empty() {
+            ToolUsage::create_many(&self.db.pool, *exec_id, &usages).await?;
+        }
+        Ok(())
+    }
+
+    /// Keep the end of a failed run's stderr, which is otherwise dropped with its log store
+    async fn record_failure_detail(&self, exec_id: &Uuid) -> Result<(), anyhow::Error> {
+        let stderr_tail = {
+            let msg_stores = self.msg_stores.read().await;
+            msg_stores
+                .get(exec_id)
+                .map(|store| execution_failure::stderr_tail(&store.get_history()))
+                .unwrap_or_default()
+        };
+        let exceeded =
+            ExecutionProcessLimits::find_by_execution_process_id(&self.db.pool, *exec_id)
+                .await?
+                .and_then(|limits| limits.exceeded);
+        if stderr_tail.is_empty() && exceeded.is_none() {
+            return Ok(());
+        }
+        let kind = execution_failure::classify(&stderr_tail, exceeded);
+        ExecutionProcessFailure::create(&self.db.pool, *exec_id, kind, &stderr_tail).await?;
+        Ok(())
+    }
+
+    /// Store the handoff a finished coding agent run ended with, if it declared one
+    async fn record_human_handoff(
+        &self,
+        ctx: &ExecutionContext,
+    ) -> Result<Option<HumanHandoff>, anyhow::Error> {
+        let handoff = {
+            let msg_stores = self.msg_stores.read().await;
+            msg_stores
+                .get(&ctx.execution_process.id)
+                .and_then(|store| human_handoff::declared(&store.get_history()))
+        };
+        let Some(handoff) = handoff else {
+            return Ok(None);
+        };
+        AttemptHandoff::create(
+            &self.db.pool,
+            ctx.workspace.id,
+            ctx.execution_process.id,
+            &handoff,
+        )
+        .await?;
+        tracing::info!(
+            "Agent of workspace {} handed the attempt back: {}",
+            ctx.workspace.id,
+            handoff.blocked
+        );
+        Ok(Some(handoff))
+    }
+
+    /// Continue a failed coding agent run with the profile's next fallback model, returning
+    /// whether a fallback run was started
+    async fn try_start_model_fallback(
+        &self,
+        ctx: &ExecutionContext,
+    ) -> Result<bool, ContainerError> {
+        let pool = &self.db.pool;
+        let Some(failure) =
+            ExecutionProcessFailure::find_by_execution_process_id(pool, ctx.execution_process.id)
+                .await?
+        else {
+            return Ok(false);
+        };
+        let action = ctx.execution_process.executor_action()?;
+        let Some(agent) = action
+            .executor_profile_id()
+            .and_then(|profile| ExecutorConfigs::get_cached().get_coding_agent(profile))
+        else {
+            return Ok(false);
+        };
+        let agent_session_id =
+            ExecutionProcess::find_latest_coding_agent_turn_session_id(pool, ctx.session.id)
+                .await?;
+        let Some(fallback) =
+            model_fallback::fallback_action(action, &agent, failure.kind, agent_session_id)
+        else {
+            return Ok(false);
+        };
+        tracing::info!(
+            "Falling back to model {:?} for session {} after {:?}",
+            fallback.model_override(),
+            ctx.session.id,
+            failure.kind
+        );
+        self.start_execution(
+            &ctx.workspace,
+            &ctx.session,
+            &fallback,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+        Ok(true)
+    }
+
+    /// Restart an agent run that overflowed its context in a fresh session, seeded with a summary
+    /// of the conversation and the diff so far. Returns whether the new session was started.
+    async fn try_start_context_handoff(
+        &self,
+        ctx: &ExecutionContext,
+    ) -> Result<bool, ContainerError> {
+        if !self.config.read().await.restart_on_context_overflow {
+            return Ok(false);
+        }
+        let pool = &self.db.pool;
+        let exec_id = ctx.execution_process.id;
+        let overflowed = ExecutionProcessFailure::find_by_execution_process_id(pool, exec_id)
+            .await?
+            .is_some_and(|failure| failure.kind == ExecutionProcessFailureKind::ContextOverflow);
+        let action = ctx.execution_process.executor_action()?;
+        if !overflowed || context_handoff::is_handoff(action) {
+            return Ok(false);
+        }
+
+        // Earlier turns only kept their prompts and final messages; the overflowed run's full
+        // log is still in memory
+        let turns = CodingAgentTurn::find_by_workspace_id(pool, ctx.workspace.id).await?;
+        let earlier_turns = turns
+            .into_iter()
+            .filter(|turn| turn.execution_process_id != exec_id)
+            .collect::<Vec<_>>();
+        let history = self
+            .get_msg_store_by_id(&exec_id)
+            .await
+            .map(|store| store.get_history())
+            .unwrap_or_default();
+        let transcript = [
+            attempt_summary::transcript_from_turns(&earlier_turns),
+            attempt_summary::transcript_from_history(&history),
+        ]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        let task_prompt = ctx.task.to_prompt();
+        let summary =
+            match context_handoff::summarize_conversation(self.llm(), &task_prompt, &transcript)
+                .await
+            {
+                Ok(summary) => summary,
+                Err(e) => {
+                    if !matches!(e, LlmError::MissingApiKey) {
+                        tracing::warn!("Failed to summarize overflowed session: {}", e);
+                    }
+                    context_handoff::unsummarized(&transcript)
+                }
+            };
+        let diffs = self
+            .diff_snapshot(&ctx.workspace, false)
+            .await
+            .inspect_err(|e| tracing::warn!("Failed to compute diff for handoff: {}", e))
+            .unwrap_or_default();
+        let prompt = context_handoff::seed_prompt(
+            &task_prompt,
+            &summary,
+            &attempt_summary::render_diffs(&diffs),
+        );
+        let Some(handoff) = context_handoff::handoff_action(action, prompt) else {
+            return Ok(false);
+        };
+
+        tracing::info!(
+            "Session {} overflowed its context, continuing in a fresh session",
+            ctx.session.id
+        );
+        self.start_execution(
+            &ctx.workspace,
+            &ctx.session,
+            &handoff,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+        Ok(true)
+    }
+
+    /// Copy project files, images and attachments to the workspace.
     /// Skips files/images that already exist (fast no-op if all exist).
     async fn copy_files_and_images(
         &self,
@@ -743,6 +1405,14 @@ impl LocalContainerService {
             tracing::warn!("Failed to copy task images to workspace: {}", e);
         }
 
+        if let Err(e) = self
+            .attachment_service
+            .copy_attachments_by_task_to_worktree(workspace_dir, workspace.task_id)
+            .await
+        {
+            tracing::warn!("Failed to copy task attachments to workspace: {}", e);
+        }
+
         Ok(())
     }
 
@@ -830,7 +1500,7 @@ impl LocalContainerService {
 
         let project_repos =
             ProjectRepo::find_by_project_id_with_names(&self.db.pool, ctx.project.id).await?;
-        let cleanup_action = self.cleanup_actions_for_repos(&project_repos);
+        let finish_action = self.finish_actions(ctx.project.id, &project_repos).await?;
 
         let working_dir = ctx
             .workspace
@@ -839,22 +1509,30 @@ impl LocalContainerService {
             .filter(|dir| !dir.is_empty())
             .cloned();
 
+        // Nobody is waiting on a queued message, so unresolved references become a note
+        let prompt = self
+            .resolve_prompt_references(&ctx.workspace, queued_data.message.clone())
+            .await?
+            .into_prompt_with_notes();
+
         let action_type = if let Some(agent_session_id) = latest_agent_session_id {
             ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
-                prompt: queued_data.message.clone(),
+                prompt,
                 session_id: agent_session_id,
                 executor_profile_id: executor_profile_id.clone(),
                 working_dir: working_dir.clone(),
+                model: None,
             })
         } else {
             ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
-                prompt: queued_data.message.clone(),
+                prompt,
                 executor_profile_id: executor_profile_id.clone(),
                 working_dir,
+                model: None,
             })
         };
 
-        let action = ExecutorAction::new(action_type, cleanup_action.map(Box::new));
+        let action = ExecutorAction::new(action_type, finish_action.map(Box::new));
 
         self.start_execution(
             &ctx.workspace,
@@ -866,6 +1544,81 @@ impl LocalContainerService {
     }
 }
 
+/// Stream a child's output as log messages with project secrets redacted,
+/// including secrets that straddle two reads.
+fn redacted_output<R>(
+    reader: R,
+    secrets: Arc<Vec<String>>,
+    to_msg: fn(String) -> LogMsg,
+) -> impl Stream<Item = std::io::Result<LogMsg>> + Send + 'static
+where
+    R: AsyncRead + Send + Unpin + 'static,
+{
+    let state = (
+        ReaderStream::new(reader),
+        Some(SecretRedactor::new(secrets)),
+    );
+    stream::unfold(state, move |(mut reader, mut redactor)| async move {
+        let active = redactor.as_mut()?;
+        loop {
+            let next = if active.is_holding() {
+                match tokio::time::timeout(REDACTION_IDLE_FLUSH, reader.next()).await {
+                    Ok(next) => next,
+                    Err(_) => {
+                        let held = active.finish();
+                        return Some((Ok(to_msg(held)), (reader, redactor)));
+                    }
+                }
+            } else {
+                reader.next().await
+            };
+            match next {
+                Some(Ok(chunk)) => {
+                    let text = active.push(&String::from_utf8_lossy(&chunk));
+                    if !text.is_empty() {
+                        return Some((Ok(to_msg(text)), (reader, redactor)));
+                    }
+                }
+                Some(Err(e)) => return Some((Err(e), (reader, redactor))),
+                None => {
+                    let rest = active.finish();
+                    return (!rest.is_empty()).then(|| (Ok(to_msg(rest)), (reader, None)));
+                }
+            }
+        }
+    })
+}
+
+fn execution_container_name(exec_id: Uuid) -> String {
+    format!("vk-{exec_id}")
+}
+
+fn kubernetes_config() -> Option<KubernetesConfig> {
+    KubernetesConfig::from_settings(
+        server_config::setting,
+        WorkspaceManager::get_workspace_base_dir(),
+    )
+}
+
+fn kubernetes_home_claim(workspace_id: Uuid) -> String {
+    format!("vk-home-{workspace_id}")
+}
+
+/// Owner of the worktree, so sandboxed processes write files as the host user
+fn sandbox_user(workspace_dir: &Path) -> Option<String> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let metadata = std::fs::metadata(workspace_dir).ok()?;
+        Some(format!("{}:{}", metadata.uid(), metadata.gid()))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = workspace_dir;
+        None
+    }
+}
+
 fn failure_exit_status() -> std::process::ExitStatus {
     #[cfg(unix)]
     {
@@ -901,6 +1654,30 @@ impl ContainerService for LocalContainerService {
         &self.notification_service
     }
 
+    fn image_service(&self) -> &ImageService {
+        &self.image_service
+    }
+
+    fn attachment_service(&self) -> &AttachmentService {
+        &self.attachment_service
+    }
+
+    fn llm(&self) -> &LlmService {
+        &self.llm
+    }
+
+    async fn code_review_config(&self) -> CodeReviewConfig {
+        self.config.read().await.code_review.clone()
+    }
+
+    fn code_map(&self) -> &CodeMapService {
+        &self.code_map
+    }
+
+    async fn code_map_config(&self) -> CodeMapConfig {
+        self.config.read().await.code_map.clone()
+    }
+
     async fn git_branch_prefix(&self) -> String {
         self.config.read().await.git_branch_prefix.clone()
     }
@@ -1045,6 +1822,15 @@ impl ContainerService for LocalContainerService {
         Ok(true)
     }
 
+    #[tracing::instrument(
+        name = "execution_process.spawn",
+        skip_all,
+        fields(
+            workspace_id = %workspace.id,
+            execution_process_id = %execution_process.id,
+            run_reason = ?execution_process.run_reason,
+        )
+    )]
     async fn start_execution_inner(
         &self,
         workspace: &Workspace,
@@ -1075,12 +1861,15 @@ impl ContainerService for LocalContainerService {
                     self.approvals.clone(),
                     self.db.clone(),
                     self.notification_service.clone(),
+                    self.entity_events.clone(),
                     execution_process.id,
                 ),
                 Some(ExecutorQuestionBridge::new(
                     self.user_questions.clone(),
                     self.db.clone(),
                     self.notification_service.clone(),
+                    self.image_service.clone(),
+                    self.entity_events.clone(),
                     execution_process.id,
                 )),
             ),
@@ -1102,14 +1891,122 @@ impl ContainerService for LocalContainerService {
             .await?
             .ok_or(ContainerError::Other(anyhow!("Project not found for task")))?;
 
+        // Outbound proxy and CA settings, overridable per project
+        for (key, value) in utils::http::subprocess_env() {
+            env.insert(key, value);
+        }
+
+        // Vault secrets first, then project-level env vars, so the VK_* variables below always win
+        let vault_secrets = self
+            .secret_vault
+            .resolve(&self.db.pool, project.id)
+            .await
+            .map_err(|e| ContainerError::Other(anyhow!("Failed to load secrets: {e}")))?;
+        env.merge(&vault_secrets.vars);
+
+        let mut project_env = self
+            .project_env
+            .resolve(&self.db.pool, project.id)
+            .await
+            .map_err(|e| ContainerError::Other(anyhow!("Failed to load project env vars: {e}")))?;
+        env.merge(&project_env.vars);
+        project_env.secrets.extend(vault_secrets.values().cloned());
+
+        env.mcp_servers = ProjectMcpServer::find_by_project_id(&self.db.pool, project.id)
+            .await?
+            .iter()
+            .map(|server| {
+                let mut spec = server.to_spec();
+                spec.expand_vars(&env.vars);
+                spec
+            })
+            .collect();
+
         env.insert("VK_PROJECT_NAME", &project.name);
         env.insert("VK_PROJECT_ID", project.id.to_string());
         env.insert("VK_TASK_ID", task.id.to_string());
         env.insert("VK_WORKSPACE_ID", workspace.id.to_string());
         env.insert("VK_WORKSPACE_BRANCH", &workspace.branch);
+        env.read_only = workspace.read_only;
+        env.dry_run = workspace.dry_run;
+        env.permission_mode = workspace.permission_mode;
+
+        // Everything but dev servers runs on the remote host, so previews stay local
+        if let Some(remote) = &self.remote_runner
+            && !matches!(
+                execution_process.run_reason,
+                ExecutionProcessRunReason::DevServer
+            )
+        {
+            let remote = remote
+                .clone()
+                .with_backend_port(read_port_file("vibe-kanban").await.ok());
+            remote.push(&current_dir).await.map_err(|e| {
+                ContainerError::Other(anyhow!(
+                    "Failed to sync workspace to {}: {e}",
+                    remote.destination
+                ))
+            })?;
+            env.remote = Some(remote);
+        }
+
+        // With a cluster configured, everything but dev servers runs as a Kubernetes Job
+        if env.remote.is_none()
+            && let Some(kubernetes) = &self.kubernetes
+            && !matches!(
+                execution_process.run_reason,
+                ExecutionProcessRunReason::DevServer
+            )
+        {
+            env.kubernetes = Some(
+                self.kubernetes_job(kubernetes, project.id, workspace, execution_process.id)
+                    .await?,
+            );
+        }
+
+        // Sandboxed projects run everything but dev servers in Docker, unless sent elsewhere
+        if env.remote.is_none()
+            && env.kubernetes.is_none()
+            && !matches!(
+                execution_process.run_reason,
+                ExecutionProcessRunReason::DevServer
+            )
+            && let Some(sandbox) = ProjectSandbox::find_enabled(&self.db.pool, project.id).await?
+        {
+            env.sandbox = Some(
+                self.docker_sandbox(&sandbox, workspace, execution_process.id, &current_dir)
+                    .await?,
+            );
+        }
+
+        // OS limits only apply to local processes; elsewhere just the wall clock is enforced
+        let limits = {
+            let config = self.config.read().await;
+            match execution_process.run_reason {
+                ExecutionProcessRunReason::CodingAgent => config.execution_limits.coding_agent,
+                _ => config.execution_limits.scripts,
+            }
+        };
+        env.limits = if env.remote.is_some() || env.kubernetes.is_some() || env.sandbox.is_some() {
+            limits.wall_clock_only()
+        } else {
+            limits
+        };
+        if !env.limits.is_empty() {
+            ExecutionProcessLimits::create(&self.db.pool, execution_process.id, &env.limits)
+                .await?;
+        }
+
+        // Another instance sharing the board may already be running this attempt
+        if let Some(coordinator) = &self.coordinator {
+            coordinator
+                .lock_attempt(workspace.id, execution_process.id)
+                .await
+                .map_err(|e| ContainerError::Other(anyhow!(e)))?;
+        }
 
         // Create the child and stream, add to execution tracker with timeout
-        let mut spawned = tokio::time::timeout(
+        let spawn_result = tokio::time::timeout(
             Duration::from_secs(30),
             executor_action.spawn(&current_dir, approvals_service, questions_service, &env),
         )
@@ -1118,10 +2015,21 @@ impl ContainerService for LocalContainerService {
             ContainerError::Other(anyhow!(
                 "Timeout: process took more than 30 seconds to start"
             ))
-        })??;
+        })
+        .and_then(|spawned| spawned.map_err(ContainerError::from));
+        if spawn_result.is_err()
+            && let Some(coordinator) = &self.coordinator
+        {
+            coordinator.release(execution_process.id).await;
+        }
+        let mut spawned = spawn_result?;
 
-        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
-            .await;
+        self.track_child_msgs_in_store(
+            execution_process.id,
+            &mut spawned.child,
+            Arc::new(project_env.secrets),
+        )
+        .await;
 
         self.add_child_to_store(execution_process.id, spawned.child)
             .await;
@@ -1138,6 +2046,17 @@ impl ContainerService for LocalContainerService {
         Ok(())
     }
 
+    async fn sample_child(&self, exec_id: &Uuid) -> Option<RecordHeartbeat> {
+        let child_lock = self.get_child_from_store(exec_id).await?;
+        let pid = child_lock.write().await.inner().id()?;
+        supervisor::sample(pid).map(|sample| sample.heartbeat())
+    }
+
+    #[tracing::instrument(
+        name = "execution_process.stop",
+        skip_all,
+        fields(execution_process_id = %execution_process.id, status = ?status)
+    )]
     async fn stop_execution(
         &self,
         execution_process: &ExecutionProcess,
@@ -1214,7 +2133,13 @@ impl ContainerService for LocalContainerService {
                 ExecutionProcessRunReason::DevServer
             )
         {
-            match Task::update_status(&self.db.pool, ctx.task.id, TaskStatus::InReview).await {
+            match task_workflow::update_task_status(
+                &self.db.pool,
+                ctx.task.id,
+                TaskStatus::InReview,
+            )
+            .await
+            {
                 Ok(_) => {
                     if let Some(publisher) = self.share_publisher()
                         && let Err(err) = publisher.update_shared_task_by_id(ctx.task.id).await
@@ -1242,6 +2167,8 @@ impl ContainerService for LocalContainerService {
             execution_process.run_reason,
             ExecutionProcessRunReason::DevServer
         ) {
+            self.tunnels.close(&execution_process.id).await;
+
             if let Some(url) = self.remove_devctl2_url(&execution_process.id).await {
                 // Extract subdomain from URL to pass to devctl2 remove
                 if let Some(subdomain) = crate::devctl2::extract_subdomain_from_url(&url) {
@@ -1407,6 +2334,75 @@ impl ContainerService for LocalContainerService {
         Ok(())
     }
 
+    fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    async fn drain(&self, timeout: Duration) -> Result<(), ContainerError> {
+        self.draining.store(true, Ordering::SeqCst);
+        let running = ExecutionProcess::find_running(&self.db.pool).await?;
+        tracing::info!(
+            "Draining {} running execution processes before shutdown",
+            running.len()
+        );
+
+        // Interrupt coding agents so they checkpoint their session and exit on their own. Marking
+        // them killed first keeps the exit monitor from starting cleanup scripts or queued
+        // follow-ups.
+        let mut interrupted = Vec::new();
+        for process in running
+            .iter()
+            .filter(|p| p.run_reason == ExecutionProcessRunReason::CodingAgent)
+        {
+            if let Some(interrupt_sender) = self.take_interrupt_sender(&process.id).await {
+                ExecutionProcess::update_completion(
+                    &self.db.pool,
+                    process.id,
+                    ExecutionProcessStatus::Killed,
+                    None,
+                )
+                .await?;
+                let _ = interrupt_sender.send(());
+                interrupted.push(process);
+            }
+        }
+
+        // The exit monitor drops a process from the child store once its logs, session id and
+        // summary, and any changes have been saved
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let pending = {
+                let children = self.child_store.read().await;
+                interrupted
+                    .iter()
+                    .filter(|p| children.contains_key(&p.id))
+                    .count()
+            };
+            if pending == 0 || tokio::time::Instant::now() >= deadline {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+
+        // Agents that ignored the interrupt, scripts and dev servers are stopped outright, which
+        // also removes devctl2 routes and tunnels
+        for process in interrupted {
+            if self.get_child_from_store(&process.id).await.is_some() {
+                tracing::warn!(
+                    "Execution process {} did not exit within the drain timeout",
+                    process.id
+                );
+                if let Err(e) = self
+                    .stop_execution(process, ExecutionProcessStatus::Killed)
+                    .await
+                {
+                    tracing::error!("Failed to stop execution process {}: {}", process.id, e);
+                }
+            }
+        }
+        self.kill_all_running_processes().await
+    }
+
     async fn set_devctl2_url(&self, exec_id: Uuid, url: String) {
         self.devctl2_urls.write().await.insert(exec_id, url);
     }
@@ -1418,6 +2414,36 @@ impl ContainerService for LocalContainerService {
     async fn remove_devctl2_url(&self, exec_id: &Uuid) -> Option<String> {
         self.devctl2_urls.write().await.remove(exec_id)
     }
+
+    fn spawn_e2e_verification(&self, workspace: &Workspace) {
+        let container = self.clone();
+        let workspace = workspace.clone();
+        tokio::spawn(async move {
+            match container.run_e2e_verification(&workspace).await {
+                Ok(Some(run)) => tracing::info!(
+                    "E2E verification of workspace {} finished: {:?}",
+                    workspace.id,
+                    run.status
+                ),
+                Ok(None) => {}
+                Err(e) => tracing::warn!(
+                    "Failed to run E2E verification for workspace {}: {}",
+                    workspace.id,
+                    e
+                ),
+            }
+        });
+    }
+
+    fn spawn_linters(&self, workspace: &Workspace) {
+        let container = self.clone();
+        let workspace = workspace.clone();
+        tokio::spawn(async move {
+            if let Err(e) = container.run_linters(&workspace).await {
+                tracing::warn!("Failed to lint workspace {}: {}", workspace.id, e);
+            }
+        });
+    }
 }
 fn success_exit_status() -> std::process::ExitStatus {
     #[cfg(unix)]
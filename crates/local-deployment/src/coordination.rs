@@ -0,0 +1,591 @@
+//! Coordination between vibe-kanban instances that share a board, through Redis.
+//!
+//! Each instance locks the attempts it runs executions for, so two instances never drive the
+//! same worktree. Changes an instance writes to the database reach the other instances' event
+//! streams over pub/sub, and approval or question responses sent to an instance that isn't
+//! running the agent are forwarded to the one that is.
+//!
+//! This relies on every instance reading and writing the same database. The only backend is a
+//! local SQLite file, which can't be shared between servers safely, so `VK_MULTI_INSTANCE` is
+//! refused and the server runs as a single instance until a shared backend is supported.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use services::services::{
+    approvals::{ApprovalError, Approvals},
+    user_questions::{QuestionError, UserQuestions},
+};
+use sqlx::SqlitePool;
+use tokio::sync::{broadcast::error::RecvError, mpsc};
+use utils::{
+    approvals::{ApprovalRequest, ApprovalResponse, ApprovalStatus},
+    log_msg::LogMsg,
+    msg_store::MsgStore,
+    user_questions::{QuestionClaim, UserQuestionRequest, UserQuestionResponse},
+};
+use uuid::Uuid;
+
+use crate::redis_client::{RedisClient, RedisClientError};
+
+const EVENTS_CHANNEL: &str = "vibe-kanban:events";
+const REQUESTS_CHANNEL: &str = "vibe-kanban:requests";
+const REPLIES_CHANNEL: &str = "vibe-kanban:replies";
+const ATTEMPT_LOCK_PREFIX: &str = "vibe-kanban:lock:attempt:";
+/// Locks expire this long after the instance holding them stops renewing them
+const LOCK_TTL: Duration = Duration::from_secs(30);
+const LOCK_RENEW_INTERVAL: Duration = Duration::from_secs(10);
+/// How long a forwarded response waits for the instance running the agent
+const FORWARD_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long pending lists are collected from other instances
+const LIST_WINDOW: Duration = Duration::from_millis(300);
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Debug, thiserror::Error)]
+pub enum CoordinationError {
+    #[error("Attempt {attempt_id} is running on another instance ({holder})")]
+    AttemptLocked { attempt_id: Uuid, holder: String },
+    #[error(transparent)]
+    Redis(#[from] RedisClientError),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct EventEnvelope {
+    origin: Uuid,
+    patch: json_patch::Patch,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Request {
+    RespondApproval {
+        id: String,
+        response: ApprovalResponse,
+    },
+    RespondQuestion {
+        id: String,
+        response: UserQuestionResponse,
+        responder: Option<Uuid>,
+    },
+    ClaimQuestion {
+        id: String,
+        user_id: Option<Uuid>,
+        name: String,
+    },
+    ReleaseQuestion {
+        id: String,
+        user_id: Option<Uuid>,
+    },
+    ListApprovals,
+    ListQuestions,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RequestEnvelope {
+    origin: Uuid,
+    request_id: Uuid,
+    request: Request,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReplyEnvelope {
+    request_id: Uuid,
+    result: Value,
+}
+
+/// Services requests from other instances are answered with
+#[derive(Clone)]
+struct LocalServices {
+    events: Arc<MsgStore>,
+    approvals: Approvals,
+    questions: UserQuestions,
+    pool: SqlitePool,
+}
+
+#[derive(Clone)]
+pub struct InstanceCoordinator {
+    instance_id: Uuid,
+    redis: RedisClient,
+    /// Executions this instance runs, by attempt
+    held: Arc<Mutex<HashMap<Uuid, HashSet<Uuid>>>>,
+    /// Patches received from other instances and not yet seen on the local stream, so they
+    /// aren't published back
+    relayed: Arc<Mutex<HashMap<String, usize>>>,
+    /// Forwarded requests waiting for replies
+    replies: Arc<Mutex<HashMap<Uuid, mpsc::UnboundedSender<Value>>>>,
+}
+
+impl InstanceCoordinator {
+    /// Requested by `VK_MULTI_INSTANCE`, but always refused: instances would need a shared
+    /// database, and SQLite can't provide one.
+    pub fn from_settings(setting: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let requested = setting("VK_MULTI_INSTANCE")
+            .is_some_and(|value| matches!(value.trim(), "1" | "true" | "yes"));
+        if requested {
+            tracing::error!(
+                "VK_MULTI_INSTANCE needs a database shared by every instance, which the SQLite \
+                 backend can't provide; running as a single instance"
+            );
+        }
+        None
+    }
+
+    #[cfg(test)]
+    fn new() -> Self {
+        Self {
+            instance_id: Uuid::new_v4(),
+            redis: RedisClient::new(),
+            held: Default::default(),
+            relayed: Default::default(),
+            replies: Default::default(),
+        }
+    }
+
+    pub fn instance_id(&self) -> Uuid {
+        self.instance_id
+    }
+
+    fn owner(&self) -> String {
+        self.instance_id.to_string()
+    }
+
+    /// Claim an attempt for an execution. Fails while another instance runs one of its
+    /// executions.
+    pub async fn lock_attempt(
+        &self,
+        attempt_id: Uuid,
+        execution_process_id: Uuid,
+    ) -> Result<(), CoordinationError> {
+        let key = format!("{ATTEMPT_LOCK_PREFIX}{attempt_id}");
+        if let Some(holder) = self.redis.try_lock(&key, &self.owner(), LOCK_TTL).await? {
+            return Err(CoordinationError::AttemptLocked { attempt_id, holder });
+        }
+        self.held
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(attempt_id)
+            .or_default()
+            .insert(execution_process_id);
+        Ok(())
+    }
+
+    /// Drop an execution's claim, releasing the attempt once none of its executions run here.
+    pub async fn release(&self, execution_process_id: Uuid) {
+        let released = {
+            let mut held = self.held.lock().unwrap_or_else(|e| e.into_inner());
+            let released = held
+                .iter_mut()
+                .find_map(|(attempt_id, executions)| {
+                    executions
+                        .remove(&execution_process_id)
+                        .then_some((*attempt_id, executions.is_empty()))
+                })
+                .and_then(|(attempt_id, empty)| empty.then_some(attempt_id));
+            if let Some(attempt_id) = released {
+                held.remove(&attempt_id);
+            }
+            released
+        };
+        if let Some(attempt_id) = released {
+            let key = format!("{ATTEMPT_LOCK_PREFIX}{attempt_id}");
+            if let Err(e) = self.redis.unlock(&key, &self.owner()).await {
+                tracing::warn!("Failed to release lock on attempt {}: {}", attempt_id, e);
+            }
+        }
+    }
+
+    /// Start renewing locks, relaying events and answering other instances' requests.
+    pub fn spawn(
+        &self,
+        events: Arc<MsgStore>,
+        approvals: Approvals,
+        questions: UserQuestions,
+        pool: SqlitePool,
+    ) {
+        tracing::info!("Coordinating with other instances as {}", self.instance_id);
+        let services = LocalServices {
+            events,
+            approvals,
+            questions,
+            pool,
+        };
+        tokio::spawn(self.clone().renew_locks());
+        tokio::spawn(self.clone().publish_events(services.events.clone()));
+        tokio::spawn(self.clone().subscribe(services));
+    }
+
+    async fn renew_locks(self) {
+        let mut interval = tokio::time::interval(LOCK_RENEW_INTERVAL);
+        loop {
+            interval.tick().await;
+            let attempts: Vec<Uuid> = {
+                let held = self.held.lock().unwrap_or_else(|e| e.into_inner());
+                held.keys().copied().collect()
+            };
+            for attempt_id in attempts {
+                let key = format!("{ATTEMPT_LOCK_PREFIX}{attempt_id}");
+                match self.redis.try_lock(&key, &self.owner(), LOCK_TTL).await {
+                    Ok(None) => {}
+                    Ok(Some(holder)) => {
+                        tracing::warn!("Lost lock on attempt {} to instance {}", attempt_id, holder)
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to renew lock on attempt {}: {}", attempt_id, e)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether a patch on the local stream was relayed from another instance
+    fn take_relayed(&self, patch_json: &str) -> bool {
+        let mut relayed = self.relayed.lock().unwrap_or_else(|e| e.into_inner());
+        match relayed.get_mut(patch_json) {
+            Some(count) => {
+                *count -= 1;
+                if *count == 0 {
+                    relayed.remove(patch_json);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    async fn publish_events(self, events: Arc<MsgStore>) {
+        let mut receiver = events.get_receiver();
+        loop {
+            let patch = match receiver.recv().await {
+                Ok(LogMsg::JsonPatch(patch)) => patch,
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Skipped relaying {} events to other instances", skipped);
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+            let Ok(patch_json) = serde_json::to_string(&patch) else {
+                continue;
+            };
+            if self.take_relayed(&patch_json) {
+                continue;
+            }
+            let envelope = EventEnvelope {
+                origin: self.instance_id,
+                patch,
+            };
+            if let Ok(payload) = serde_json::to_string(&envelope)
+                && let Err(e) = self.redis.publish(EVENTS_CHANNEL, &payload).await
+            {
+                tracing::warn!("Failed to relay event to other instances: {}", e);
+            }
+        }
+    }
+
+    async fn subscribe(self, services: LocalServices) {
+        loop {
+            match self
+                .redis
+                .subscribe(&[EVENTS_CHANNEL, REQUESTS_CHANNEL, REPLIES_CHANNEL])
+                .await
+            {
+                Ok(mut pubsub) => {
+                    let mut messages = pubsub.on_message();
+                    while let Some(msg) = messages.next().await {
+                        let Ok(payload) = msg.get_payload::<String>() else {
+                            continue;
+                        };
+                        self.handle_message(msg.get_channel_name(), &payload, &services);
+                    }
+                    tracing::warn!("Lost subscription to other instances, resubscribing");
+                }
+                Err(RedisClientError::SubscribeUnsupported(topology)) => {
+                    tracing::error!(
+                        "Coordination needs a single Redis server, not a {} deployment",
+                        topology
+                    );
+                    return;
+                }
+                Err(e) => tracing::warn!("Failed to subscribe to other instances: {}", e),
+            }
+            tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+        }
+    }
+
+    fn handle_message(&self, channel: &str, payload: &str, services: &LocalServices) {
+        match channel {
+            EVENTS_CHANNEL => self.relay_event(payload, &services.events),
+            REQUESTS_CHANNEL => {
+                let Ok(envelope) = serde_json::from_str::<RequestEnvelope>(payload) else {
+                    return;
+                };
+                if envelope.origin == self.instance_id {
+                    return;
+                }
+                let coordinator = self.clone();
+                let services = services.clone();
+                tokio::spawn(async move {
+                    if let Some(result) = handle_request(envelope.request, &services).await {
+                        coordinator.reply(envelope.request_id, result).await;
+                    }
+                });
+            }
+            REPLIES_CHANNEL => {
+                let Ok(envelope) = serde_json::from_str::<ReplyEnvelope>(payload) else {
+                    return;
+                };
+                let replies = self.replies.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(sender) = replies.get(&envelope.request_id) {
+                    let _ = sender.send(envelope.result);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Push another instance's event onto the local stream
+    fn relay_event(&self, payload: &str, events: &MsgStore) {
+        let Ok(envelope) = serde_json::from_str::<EventEnvelope>(payload) else {
+            return;
+        };
+        if envelope.origin == self.instance_id {
+            return;
+        }
+        if let Ok(patch_json) = serde_json::to_string(&envelope.patch) {
+            *self
+                .relayed
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .entry(patch_json)
+                .or_default() += 1;
+        }
+        events.push_patch(envelope.patch);
+    }
+
+    async fn reply(&self, request_id: Uuid, result: Value) {
+        let envelope = ReplyEnvelope { request_id, result };
+        if let Ok(payload) = serde_json::to_string(&envelope)
+            && let Err(e) = self.redis.publish(REPLIES_CHANNEL, &payload).await
+        {
+            tracing::warn!("Failed to reply to another instance: {}", e);
+        }
+    }
+
+    /// Send a request to the other instances and collect replies until `window` passes, or
+    /// until the first one arrives when `first_only` is set.
+    async fn request(&self, request: Request, window: Duration, first_only: bool) -> Vec<Value> {
+        let request_id = Uuid::new_v4();
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        self.replies
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(request_id, sender);
+
+        let envelope = RequestEnvelope {
+            origin: self.instance_id,
+            request_id,
+            request,
+        };
+        let mut results = Vec::new();
+        match serde_json::to_string(&envelope) {
+            Ok(payload) => match self.redis.publish(REQUESTS_CHANNEL, &payload).await {
+                Ok(()) => {
+                    let deadline = tokio::time::Instant::now() + window;
+                    while let Ok(Some(result)) =
+                        tokio::time::timeout_at(deadline, receiver.recv()).await
+                    {
+                        results.push(result);
+                        if first_only {
+                            break;
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to reach other instances: {}", e),
+            },
+            Err(e) => tracing::warn!("Failed to serialize request: {}", e),
+        }
+
+        self.replies
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&request_id);
+        results
+    }
+
+    async fn request_one<T: DeserializeOwned>(&self, request: Request) -> Option<T> {
+        self.request(request, FORWARD_TIMEOUT, true)
+            .await
+            .into_iter()
+            .find_map(|result| serde_json::from_value(result).ok())
+    }
+
+    async fn request_all<T: DeserializeOwned>(&self, request: Request) -> Vec<T> {
+        self.request(request, LIST_WINDOW, false)
+            .await
+            .into_iter()
+            .filter_map(|result| serde_json::from_value::<Vec<T>>(result).ok())
+            .flatten()
+            .collect()
+    }
+
+    /// Answer an approval pending on another instance. `None` when no instance has it.
+    pub async fn forward_approval(
+        &self,
+        id: &str,
+        response: ApprovalResponse,
+    ) -> Option<ApprovalStatus> {
+        self.request_one(Request::RespondApproval {
+            id: id.to_string(),
+            response,
+        })
+        .await
+    }
+
+    /// Answer a question pending on another instance. `None` when no instance has it, an
+    /// error with the holder's claim when someone else is answering it.
+    pub async fn forward_question(
+        &self,
+        id: &str,
+        response: UserQuestionResponse,
+        responder: Option<Uuid>,
+    ) -> Option<Result<UserQuestionResponse, QuestionClaim>> {
+        self.request_one(Request::RespondQuestion {
+            id: id.to_string(),
+            response,
+            responder,
+        })
+        .await
+    }
+
+    /// Claim a question pending on another instance. `None` when no instance has it.
+    pub async fn forward_question_claim(
+        &self,
+        id: &str,
+        user_id: Option<Uuid>,
+        name: String,
+    ) -> Option<Result<QuestionClaim, QuestionClaim>> {
+        self.request_one(Request::ClaimQuestion {
+            id: id.to_string(),
+            user_id,
+            name,
+        })
+        .await
+    }
+
+    /// Release a claim on a question pending on another instance. `None` when no instance
+    /// has it.
+    pub async fn forward_question_release(
+        &self,
+        id: &str,
+        user_id: Option<Uuid>,
+    ) -> Option<Result<(), QuestionClaim>> {
+        self.request_one(Request::ReleaseQuestion {
+            id: id.to_string(),
+            user_id,
+        })
+        .await
+    }
+
+    /// Approvals pending on other instances
+    pub async fn remote_pending_approvals(&self) -> Vec<ApprovalRequest> {
+        self.request_all(Request::ListApprovals).await
+    }
+
+    /// Questions pending on other instances
+    pub async fn remote_pending_questions(&self) -> Vec<UserQuestionRequest> {
+        self.request_all(Request::ListQuestions).await
+    }
+}
+
+/// Answer a request from another instance; `None` when it concerns something this instance
+/// doesn't have.
+async fn handle_request(request: Request, services: &LocalServices) -> Option<Value> {
+    match request {
+        Request::RespondApproval { id, response } => {
+            match services
+                .approvals
+                .respond(&services.pool, &id, response)
+                .await
+            {
+                Ok((status, _)) => serde_json::to_value(status).ok(),
+                Err(ApprovalError::NotFound) => None,
+                Err(e) => {
+                    tracing::warn!("Failed to answer forwarded approval {}: {:?}", id, e);
+                    None
+                }
+            }
+        }
+        // Forwarded answers skip the grace period, as amending only works where it was given
+        Request::RespondQuestion {
+            id,
+            response,
+            responder,
+        } => question_outcome(
+            &id,
+            services
+                .questions
+                .respond(&services.pool, &id, response, responder, Duration::ZERO)
+                .await,
+        ),
+        Request::ClaimQuestion { id, user_id, name } => {
+            question_outcome(&id, services.questions.claim(&id, user_id, name).await)
+        }
+        Request::ReleaseQuestion { id, user_id } => {
+            question_outcome(&id, services.questions.release(&id, user_id).await)
+        }
+        Request::ListApprovals => serde_json::to_value(services.approvals.list_pending()).ok(),
+        Request::ListQuestions => serde_json::to_value(services.questions.list_pending()).ok(),
+    }
+}
+
+/// Reply to a forwarded question request: the result, or the claim that blocked it
+fn question_outcome<T: Serialize>(id: &str, result: Result<T, QuestionError>) -> Option<Value> {
+    let outcome = match result {
+        Ok(value) => Ok(value),
+        Err(QuestionError::Claimed(claim)) => Err(claim),
+        Err(QuestionError::NotFound) => return None,
+        Err(e) => {
+            tracing::warn!("Failed to handle forwarded question {}: {:?}", id, e);
+            return None;
+        }
+    };
+    serde_json::to_value(outcome).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refused_without_a_shared_database() {
+        assert!(InstanceCoordinator::from_settings(|_| None).is_none());
+        assert!(InstanceCoordinator::from_settings(|_| Some("false".to_string())).is_none());
+        assert!(InstanceCoordinator::from_settings(|_| Some("true".to_string())).is_none());
+    }
+
+    #[test]
+    fn relayed_patches_are_not_published_back() {
+        let coordinator = InstanceCoordinator::new();
+        let events = MsgStore::new();
+        let patch: json_patch::Patch = serde_json::from_value(serde_json::json!([
+            { "op": "add", "path": "/tasks/abc", "value": { "title": "Remote" } }
+        ]))
+        .unwrap();
+        let envelope = EventEnvelope {
+            origin: Uuid::new_v4(),
+            patch: patch.clone(),
+        };
+        coordinator.relay_event(&serde_json::to_string(&envelope).unwrap(), &events);
+
+        assert_eq!(events.get_history().len(), 1);
+        let patch_json = serde_json::to_string(&patch).unwrap();
+        assert!(coordinator.take_relayed(&patch_json));
+        assert!(!coordinator.take_relayed(&patch_json));
+    }
+}
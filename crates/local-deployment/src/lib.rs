@@ -5,40 +5,61 @@ use db::DBService;
 use deployment::{Deployment, DeploymentError, RemoteClientNotConfigured};
 use executors::profile::ExecutorConfigs;
 use services::services::{
-    analytics::{AnalyticsConfig, AnalyticsContext, AnalyticsService, generate_user_id},
+    accounts::AccountService,
+    analytics::{AnalyticsConfig, AnalyticsService, AnalyticsSubscriber, generate_user_id},
+    analytics_export::AnalyticsExport,
     approvals::Approvals,
+    attachment::AttachmentService,
     auth::AuthContext,
+    automation::AutomationEngine,
+    backup::BackupService,
+    base_watch::BaseWatchMonitor,
+    blob_storage::BlobStorage,
     config::{Config, load_config_from_file, save_config_to_file},
     container::ContainerService,
-    events::EventService,
+    event_webhooks::EventWebhookSubscriber,
+    events::{EntityEventBus, EventService, spawn_subscriber},
     file_search_cache::FileSearchCache,
     filesystem::FilesystemService,
     git::GitService,
-    image::ImageService,
+    image::{ImageError, ImageService},
+    llm::LlmService,
+    maintenance::MaintenanceService,
     oauth_credentials::OAuthCredentials,
+    oidc::OidcService,
+    presence::PresenceService,
     project::ProjectService,
+    project_env::ProjectEnvService,
     queued_message::QueuedMessageService,
     remote_client::{RemoteClient, RemoteClientError},
     repo::RepoService,
+    secret_cipher::SecretCipher,
+    secret_vault::SecretVault,
+    server_config::{self, ServerConfigService, server_config_path},
     share::{ShareConfig, SharePublisher},
+    tunnel::TunnelService,
     user_questions::UserQuestions,
 };
 use tokio::sync::RwLock;
 use utils::{
     api::oauth::LoginStatus,
-    assets::{config_path, credentials_path},
+    assets::{config_path, credentials_path, secret_key_path},
     msg_store::MsgStore,
 };
 use uuid::Uuid;
 
-use crate::container::LocalContainerService;
+use crate::{container::LocalContainerService, coordination::InstanceCoordinator};
 mod command;
 pub mod container;
+pub mod coordination;
 mod copy;
 pub mod devctl2;
 pub mod redis_client;
+pub mod supervisor;
 
-pub use redis_client::{NotionTask, RedisClient, RedisClientError};
+pub use redis_client::{
+    NOTION_TASKS_KEY, NotionTask, RedisClient, RedisClientError, RedisSnapshot,
+};
 
 #[derive(Clone)]
 pub struct LocalDeployment {
@@ -46,11 +67,23 @@ pub struct LocalDeployment {
     user_id: String,
     db: DBService,
     analytics: Option<AnalyticsService>,
+    analytics_export: Option<AnalyticsExport>,
     container: LocalContainerService,
     git: GitService,
     project: ProjectService,
     repo: RepoService,
+    project_env: ProjectEnvService,
+    secret_vault: SecretVault,
+    server_config: ServerConfigService,
+    tunnels: TunnelService,
+    presence: PresenceService,
+    accounts: AccountService,
+    oidc: OidcService,
+    backups: BackupService,
+    maintenance: MaintenanceService,
+    llm: LlmService,
     image: ImageService,
+    attachments: AttachmentService,
     filesystem: FilesystemService,
     events: EventService,
     file_search_cache: Arc<FileSearchCache>,
@@ -62,6 +95,7 @@ pub struct LocalDeployment {
     auth_context: AuthContext,
     oauth_handoffs: Arc<RwLock<HashMap<Uuid, PendingHandoff>>>,
     redis_client: RedisClient,
+    coordinator: Option<InstanceCoordinator>,
 }
 
 #[derive(Debug, Clone)]
@@ -98,29 +132,55 @@ impl Deployment for LocalDeployment {
         save_config_to_file(&raw_config, &config_path()).await?;
 
         let config = Arc::new(RwLock::new(raw_config));
+        // Loaded before anything reads env-backed settings that the file can provide
+        let server_config = ServerConfigService::load(server_config_path(), config.clone()).await;
+        server_config.spawn_watcher();
         let user_id = generate_user_id();
         let analytics = AnalyticsConfig::new().map(AnalyticsService::new);
         let git = GitService::new();
         let project = ProjectService::new();
         let repo = RepoService::new();
+        let cipher = SecretCipher::load_or_create(&secret_key_path())?;
+        let project_env = ProjectEnvService::new(cipher.clone());
+        let secret_vault = SecretVault::new(cipher);
+        let tunnels = TunnelService::new();
+        let accounts = AccountService::from_env();
+        let oidc = OidcService::from_env();
         let msg_stores = Arc::new(RwLock::new(HashMap::new()));
         let filesystem = FilesystemService::new();
 
         // Create shared components for EventService
         let events_msg_store = Arc::new(MsgStore::new());
         let events_entry_count = Arc::new(RwLock::new(0));
+        let entity_events = EntityEventBus::new();
+        let presence = PresenceService::new(entity_events.clone());
+        presence.spawn_sweeper();
 
         // Create DB with event hooks
         let db = {
             let hook = EventService::create_hook(
                 events_msg_store.clone(),
                 events_entry_count.clone(),
+                entity_events.clone(),
                 DBService::new().await?, // Temporary DB service for the hook
             );
             DBService::new_with_after_connect(hook).await?
         };
 
-        let image = ImageService::new(db.clone().pool)?;
+        let backups = BackupService::new(db.clone());
+        let maintenance = MaintenanceService::new(db.clone());
+        let llm = LlmService::new(config.clone());
+        let blob_storage = BlobStorage::from_env().map_err(ImageError::from)?;
+        tracing::info!("Storing uploads in {}", blob_storage.description());
+        let image = ImageService::new(db.clone().pool, blob_storage.clone());
+        let attachments = AttachmentService::new(db.clone().pool, blob_storage);
+        let analytics_export = AnalyticsExport::from_env()
+            .transpose()
+            .map_err(ImageError::from)?;
+        if let Some(export) = &analytics_export {
+            tracing::info!("Exporting analytics events to {}", export.description());
+            export.spawn_flusher();
+        }
         {
             let image_service = image.clone();
             tokio::spawn(async move {
@@ -172,41 +232,103 @@ impl Deployment for LocalDeployment {
 
         let oauth_handoffs = Arc::new(RwLock::new(HashMap::new()));
 
-        // We need to make analytics accessible to the ContainerService
-        // TODO: Handle this more gracefully
-        let analytics_ctx = analytics.as_ref().map(|s| AnalyticsContext {
-            user_id: user_id.clone(),
-            analytics_service: s.clone(),
-        });
+        // Analytics derived from domain events are tracked by a subscriber of the event bus
+        if analytics.is_some() || analytics_export.is_some() {
+            spawn_subscriber(
+                &entity_events,
+                Arc::new(AnalyticsSubscriber::new(
+                    user_id.clone(),
+                    analytics.clone(),
+                    analytics_export.clone(),
+                    config.clone(),
+                )),
+            );
+        }
+        let coordinator = InstanceCoordinator::from_settings(server_config::setting);
         let container = LocalContainerService::new(
             db.clone(),
             msg_stores.clone(),
             config.clone(),
             git.clone(),
             image.clone(),
-            analytics_ctx,
+            attachments.clone(),
             approvals.clone(),
             queued_message_service.clone(),
             share_publisher.clone(),
+            llm.clone(),
+            project_env.clone(),
+            secret_vault.clone(),
+            tunnels.clone(),
+            entity_events.clone(),
+            coordinator.clone(),
+        )
+        .await;
+
+        // Approvals and questions are announced to the projects' outgoing webhooks
+        spawn_subscriber(
+            &entity_events,
+            Arc::new(EventWebhookSubscriber::new(
+                db.clone(),
+                project_env.cipher().clone(),
+                approvals.clone(),
+                container.user_questions().clone(),
+            )),
+        );
+
+        // Board automations run their rules off task events
+        AutomationEngine::new(
+            db.clone(),
+            container.clone(),
+            config.clone(),
+            entity_events.clone(),
         )
+        .spawn()
         .await;
 
-        let events = EventService::new(db.clone(), events_msg_store, events_entry_count);
+        // Watched attempts are rebased as their base branches move
+        BaseWatchMonitor::new(db.clone(), git.clone(), container.clone()).spawn();
+
+        let events = EventService::new(
+            db.clone(),
+            events_msg_store,
+            events_entry_count,
+            entity_events,
+        );
 
         let file_search_cache = Arc::new(FileSearchCache::new());
 
         let redis_client = RedisClient::new();
+        if let Some(coordinator) = &coordinator {
+            coordinator.spawn(
+                events.msg_store().clone(),
+                approvals.clone(),
+                container.user_questions().clone(),
+                db.pool.clone(),
+            );
+        }
 
         let deployment = Self {
             config,
             user_id,
             db,
             analytics,
+            analytics_export,
             container,
             git,
             project,
             repo,
+            project_env,
+            secret_vault,
+            server_config,
+            tunnels,
+            presence,
+            accounts,
+            oidc,
+            backups,
+            maintenance,
+            llm,
             image,
+            attachments,
             filesystem,
             events,
             file_search_cache,
@@ -218,6 +340,7 @@ impl Deployment for LocalDeployment {
             auth_context,
             oauth_handoffs,
             redis_client,
+            coordinator,
         };
 
         Ok(deployment)
@@ -239,6 +362,10 @@ impl Deployment for LocalDeployment {
         &self.analytics
     }
 
+    fn analytics_export(&self) -> &Option<AnalyticsExport> {
+        &self.analytics_export
+    }
+
     fn container(&self) -> &impl ContainerService {
         &self.container
     }
@@ -255,10 +382,54 @@ impl Deployment for LocalDeployment {
         &self.repo
     }
 
+    fn project_env(&self) -> &ProjectEnvService {
+        &self.project_env
+    }
+
+    fn secret_vault(&self) -> &SecretVault {
+        &self.secret_vault
+    }
+
+    fn server_config(&self) -> &ServerConfigService {
+        &self.server_config
+    }
+
+    fn tunnels(&self) -> &TunnelService {
+        &self.tunnels
+    }
+
+    fn presence(&self) -> &PresenceService {
+        &self.presence
+    }
+
+    fn accounts(&self) -> &AccountService {
+        &self.accounts
+    }
+
+    fn oidc(&self) -> &OidcService {
+        &self.oidc
+    }
+
+    fn backups(&self) -> &BackupService {
+        &self.backups
+    }
+
+    fn maintenance(&self) -> &MaintenanceService {
+        &self.maintenance
+    }
+
+    fn llm(&self) -> &LlmService {
+        &self.llm
+    }
+
     fn image(&self) -> &ImageService {
         &self.image
     }
 
+    fn attachments(&self) -> &AttachmentService {
+        &self.attachments
+    }
+
     fn filesystem(&self) -> &FilesystemService {
         &self.filesystem
     }
@@ -357,4 +528,9 @@ impl LocalDeployment {
     pub fn redis_client(&self) -> &RedisClient {
         &self.redis_client
     }
+
+    /// Coordination with other instances sharing this board, when enabled
+    pub fn coordinator(&self) -> Option<&InstanceCoordinator> {
+        self.coordinator.as_ref()
+    }
 }
@@ -1,10 +1,24 @@
-use redis::aio::ConnectionManager;
-use redis::AsyncCommands;
-use serde::Deserialize;
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use redis::{
+    AsyncCommands, Cmd, ConnectionInfo, IntoConnectionInfo, Pipeline, RedisConnectionInfo,
+    RedisFuture, TlsMode, Value,
+    aio::{ConnectionLike, ConnectionManager, MultiplexedConnection, PubSub},
+    cluster::ClusterClient,
+    cluster_async::ClusterConnection,
+    sentinel::{SentinelClient, SentinelNodeConnectionInfo, SentinelServerType},
+};
+use serde::{Deserialize, de::DeserializeOwned};
+use services::services::server_config;
 use tokio::sync::RwLock;
 
-const REDIS_KEY: &str = "workstream:notion:tasks";
+/// Key the workstream daemon publishes Notion tasks under
+pub const NOTION_TASKS_KEY: &str = "workstream:notion:tasks";
 
 /// Notion task structure from Redis (workstream-daemon format)
 #[derive(Debug, Clone, Deserialize)]
@@ -33,34 +47,337 @@ pub enum RedisClientError {
     Connection(#[from] redis::RedisError),
     #[error("Failed to parse tasks: {0}")]
     Parse(#[from] serde_json::Error),
+    #[error("Redis is unavailable after repeated failures; retrying in {0}s")]
+    CircuitOpen(u64),
+    #[error("Subscribing is only supported with a single Redis server, not a {0} deployment")]
+    SubscribeUnsupported(&'static str),
+}
+
+const DEFAULT_REDIS_URL: &str = "redis://localhost:6379";
+
+/// Take or extend a lock: set `KEYS[1]` to the owner `ARGV[1]` for `ARGV[2]` milliseconds unless
+/// someone else holds it, in which case the holder is returned.
+const LOCK_SCRIPT: &str = r#"
+local holder = redis.call('GET', KEYS[1])
+if holder and holder ~= ARGV[1] then
+    return holder
+end
+redis.call('SET', KEYS[1], ARGV[1], 'PX', ARGV[2])
+return false
+"#;
+
+/// Delete `KEYS[1]` only while it is still held by `ARGV[1]`.
+const UNLOCK_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+const DEFAULT_SENTINEL_MASTER: &str = "mymaster";
+
+/// How Redis is deployed. URLs may use `rediss://` for TLS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RedisTopology {
+    /// A single server at `REDIS_URL`
+    Single { url: String },
+    /// Servers monitored by the sentinels in `REDIS_SENTINELS`, connecting to the current
+    /// master of `REDIS_SENTINEL_MASTER`
+    Sentinel {
+        sentinels: Vec<String>,
+        master_name: String,
+    },
+    /// The cluster reachable through the nodes in `REDIS_CLUSTER_NODES`
+    Cluster { nodes: Vec<String> },
+}
+
+impl RedisTopology {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RedisTopology::Single { .. } => "single",
+            RedisTopology::Sentinel { .. } => "sentinel",
+            RedisTopology::Cluster { .. } => "cluster",
+        }
+    }
+}
+
+/// Redis connection settings, read from the environment or the `[redis]` section of
+/// `vibe-kanban.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedisSettings {
+    pub topology: RedisTopology,
+    /// `REDIS_USERNAME`, for ACL users; overrides the username in URLs
+    pub username: Option<String>,
+    /// `REDIS_PASSWORD`; overrides the password in URLs
+    pub password: Option<String>,
+}
+
+/// Comma separated `host:port` or URL list, with `redis://` added where the scheme is missing.
+fn parse_node_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|node| !node.is_empty())
+        .map(|node| {
+            if node.contains("://") {
+                node.to_string()
+            } else {
+                format!("redis://{node}")
+            }
+        })
+        .collect()
 }
 
+impl RedisSettings {
+    pub fn from_env() -> Self {
+        Self::from_vars(server_config::setting)
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let var = |name: &str| var(name).filter(|value| !value.trim().is_empty());
+        let sentinels = var("REDIS_SENTINELS").map(|list| parse_node_list(&list));
+        let cluster_nodes = var("REDIS_CLUSTER_NODES").map(|list| parse_node_list(&list));
+
+        let topology = match (sentinels, cluster_nodes) {
+            (Some(sentinels), _) if !sentinels.is_empty() => RedisTopology::Sentinel {
+                sentinels,
+                master_name: var("REDIS_SENTINEL_MASTER")
+                    .unwrap_or_else(|| DEFAULT_SENTINEL_MASTER.to_string()),
+            },
+            (_, Some(nodes)) if !nodes.is_empty() => RedisTopology::Cluster { nodes },
+            _ => RedisTopology::Single {
+                url: var("REDIS_URL").unwrap_or_else(|| DEFAULT_REDIS_URL.to_string()),
+            },
+        };
+        Self {
+            topology,
+            username: var("REDIS_USERNAME"),
+            password: var("REDIS_PASSWORD"),
+        }
+    }
+
+    /// Connection info for a URL, with the configured credentials applied.
+    fn connection_info(&self, url: &str) -> Result<ConnectionInfo, redis::RedisError> {
+        let mut info = url.into_connection_info()?;
+        if let Some(username) = &self.username {
+            info.redis.username = Some(username.clone());
+        }
+        if let Some(password) = &self.password {
+            info.redis.password = Some(password.clone());
+        }
+        Ok(info)
+    }
+}
+
+/// A connection to whichever topology is configured.
 #[derive(Clone)]
-pub struct RedisClient {
-    connection: Arc<RwLock<Option<ConnectionManager>>>,
-    url: Option<String>,
+enum RedisConnection {
+    Single(Box<ConnectionManager>),
+    Sentinel(MultiplexedConnection),
+    Cluster(ClusterConnection),
 }
 
-const DEFAULT_REDIS_URL: &str = "redis://localhost:6379";
+impl ConnectionLike for RedisConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        match self {
+            RedisConnection::Single(conn) => conn.req_packed_command(cmd),
+            RedisConnection::Sentinel(conn) => conn.req_packed_command(cmd),
+            RedisConnection::Cluster(conn) => conn.req_packed_command(cmd),
+        }
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        match self {
+            RedisConnection::Single(conn) => conn.req_packed_commands(cmd, offset, count),
+            RedisConnection::Sentinel(conn) => conn.req_packed_commands(cmd, offset, count),
+            RedisConnection::Cluster(conn) => conn.req_packed_commands(cmd, offset, count),
+        }
+    }
+
+    fn get_db(&self) -> i64 {
+        match self {
+            RedisConnection::Single(conn) => conn.get_db(),
+            RedisConnection::Sentinel(conn) => conn.get_db(),
+            RedisConnection::Cluster(conn) => conn.get_db(),
+        }
+    }
+}
+
+/// Served from memory without asking Redis again
+const CACHE_TTL: Duration = Duration::from_secs(5);
+const MAX_ATTEMPTS: u32 = 3;
+/// Doubled after each failed attempt
+const INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+/// Consecutive failed fetches after which Redis is left alone for a while
+const FAILURE_THRESHOLD: u32 = 3;
+const CIRCUIT_OPEN_FOR: Duration = Duration::from_secs(30);
+
+/// A JSON list as last read from a Redis key.
+#[derive(Debug, Clone)]
+pub struct RedisSnapshot<T> {
+    pub items: Vec<T>,
+    pub synced_at: DateTime<Utc>,
+    /// Why Redis could not be read, when the last good payload is served instead
+    pub sync_error: Option<String>,
+}
+
+/// Stops calling Redis after repeated failures, then lets a single call through once the
+/// circuit has been open for a while.
+#[derive(Debug, Default)]
+struct CircuitBreaker {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Time left before Redis may be called again.
+    fn retry_in(&self, now: Instant) -> Option<Duration> {
+        self.open_until
+            .map(|until| until.saturating_duration_since(now))
+            .filter(|left| !left.is_zero())
+    }
+
+    fn record_success(&mut self) {
+        *self = Self::default();
+    }
+
+    fn record_failure(&mut self, now: Instant) {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= FAILURE_THRESHOLD {
+            self.open_until = Some(now + CIRCUIT_OPEN_FOR);
+        }
+    }
+}
+
+/// Last good payload of a key; `None` when the key did not exist.
+struct CachedPayload {
+    json: Option<String>,
+    synced_at: DateTime<Utc>,
+    fetched_at: Instant,
+}
+
+impl CachedPayload {
+    fn snapshot<T: DeserializeOwned>(
+        &self,
+        sync_error: Option<String>,
+    ) -> Result<RedisSnapshot<T>, RedisClientError> {
+        let items = match &self.json {
+            Some(json) => serde_json::from_str(json)?,
+            None => Vec::new(),
+        };
+        Ok(RedisSnapshot {
+            items,
+            synced_at: self.synced_at,
+            sync_error,
+        })
+    }
+}
+
+#[derive(Default)]
+struct SyncState {
+    breaker: CircuitBreaker,
+    last_good: HashMap<String, CachedPayload>,
+}
+
+#[derive(Clone)]
+pub struct RedisClient {
+    connection: Arc<RwLock<Option<RedisConnection>>>,
+    settings: Option<RedisSettings>,
+    state: Arc<Mutex<SyncState>>,
+}
 
 impl RedisClient {
     pub fn new() -> Self {
-        let url = std::env::var("REDIS_URL")
-            .ok()
-            .unwrap_or_else(|| DEFAULT_REDIS_URL.to_string());
-        tracing::info!("Redis client initialized with URL: {}", url);
+        let settings = RedisSettings::from_env();
+        tracing::info!(
+            "Redis client initialized for a {} deployment",
+            settings.topology.name()
+        );
         Self {
             connection: Arc::new(RwLock::new(None)),
-            url: Some(url),
+            settings: Some(settings),
+            state: Arc::new(Mutex::new(SyncState::default())),
         }
     }
 
     pub fn is_configured(&self) -> bool {
-        self.url.is_some()
+        self.settings.is_some()
+    }
+
+    pub fn topology(&self) -> Option<&RedisTopology> {
+        self.settings.as_ref().map(|settings| &settings.topology)
+    }
+
+    /// When `key` was last read successfully.
+    pub fn last_synced_at(&self, key: &str) -> Option<DateTime<Utc>> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.last_good.get(key).map(|cached| cached.synced_at)
+    }
+
+    /// Time left before Redis is called again, while the circuit breaker is open.
+    pub fn circuit_retry_in(&self) -> Option<Duration> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.breaker.retry_in(Instant::now())
+    }
+
+    async fn connect(settings: &RedisSettings) -> Result<RedisConnection, RedisClientError> {
+        match &settings.topology {
+            RedisTopology::Single { url } => {
+                let client = redis::Client::open(settings.connection_info(url)?)?;
+                Ok(RedisConnection::Single(Box::new(
+                    ConnectionManager::new(client).await?,
+                )))
+            }
+            RedisTopology::Sentinel {
+                sentinels,
+                master_name,
+            } => {
+                let tls_mode = sentinels
+                    .iter()
+                    .any(|url| url.starts_with("rediss://"))
+                    .then_some(TlsMode::Secure);
+                let node_connection_info = SentinelNodeConnectionInfo {
+                    tls_mode,
+                    redis_connection_info: Some(RedisConnectionInfo {
+                        username: settings.username.clone(),
+                        password: settings.password.clone(),
+                        ..Default::default()
+                    }),
+                };
+                let mut client = SentinelClient::build(
+                    sentinels.clone(),
+                    master_name.clone(),
+                    Some(node_connection_info),
+                    SentinelServerType::Master,
+                )?;
+                Ok(RedisConnection::Sentinel(
+                    client.get_async_connection().await?,
+                ))
+            }
+            RedisTopology::Cluster { nodes } => {
+                let mut builder = ClusterClient::builder(nodes.clone());
+                if let Some(username) = &settings.username {
+                    builder = builder.username(username.clone());
+                }
+                if let Some(password) = &settings.password {
+                    builder = builder.password(password.clone());
+                }
+                let client = builder.build()?;
+                Ok(RedisConnection::Cluster(
+                    client.get_async_connection().await?,
+                ))
+            }
+        }
     }
 
-    async fn get_connection(&self) -> Result<ConnectionManager, RedisClientError> {
-        let url = self.url.as_ref().ok_or(RedisClientError::NotConfigured)?;
+    async fn get_connection(&self) -> Result<RedisConnection, RedisClientError> {
+        let settings = self
+            .settings
+            .as_ref()
+            .ok_or(RedisClientError::NotConfigured)?;
 
         // Check if we have an existing connection
         {
@@ -70,9 +387,7 @@ impl RedisClient {
             }
         }
 
-        // Create new connection
-        let client = redis::Client::open(url.as_str())?;
-        let conn = ConnectionManager::new(client).await?;
+        let conn = Self::connect(settings).await?;
 
         // Store for reuse
         {
@@ -83,18 +398,172 @@ impl RedisClient {
         Ok(conn)
     }
 
-    pub async fn get_notion_tasks(&self) -> Result<Vec<NotionTask>, RedisClientError> {
+    /// Drop the cached connection after a failure so the next call reconnects, which also
+    /// finds the new master after a Sentinel failover.
+    async fn reset_on_error<T>(
+        &self,
+        result: Result<T, redis::RedisError>,
+    ) -> Result<T, RedisClientError> {
+        if result.is_err() {
+            *self.connection.write().await = None;
+        }
+        Ok(result?)
+    }
+
+    async fn fetch(&self, key: &str) -> Result<Option<String>, RedisClientError> {
         let mut conn = self.get_connection().await?;
+        self.reset_on_error(conn.get(key).await).await
+    }
 
-        let data: Option<String> = conn.get(REDIS_KEY).await?;
+    /// Retry connection failures with exponential backoff.
+    async fn fetch_with_retry(&self, key: &str) -> Result<Option<String>, RedisClientError> {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut attempt = 1;
+        loop {
+            match self.fetch(key).await {
+                Err(RedisClientError::Connection(e)) if attempt < MAX_ATTEMPTS => {
+                    tracing::warn!(
+                        "Reading {} from Redis failed (attempt {}): {}",
+                        key,
+                        attempt,
+                        e
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
 
-        match data {
-            Some(json) => {
-                let tasks: Vec<NotionTask> = serde_json::from_str(&json)?;
-                Ok(tasks)
+    /// The JSON list stored at `key`, empty when the key does not exist. Recent payloads are
+    /// served from memory, and the last good payload stands in while Redis is unreachable.
+    pub async fn get_list<T: DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<RedisSnapshot<T>, RedisClientError> {
+        {
+            let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let cached = state.last_good.get(key);
+            if let Some(cached) = cached
+                && now.duration_since(cached.fetched_at) < CACHE_TTL
+            {
+                return cached.snapshot(None);
+            }
+            if let Some(retry_in) = state.breaker.retry_in(now) {
+                let error = RedisClientError::CircuitOpen(retry_in.as_secs().max(1));
+                return match cached {
+                    Some(cached) => cached.snapshot(Some(error.to_string())),
+                    None => Err(error),
+                };
             }
-            None => Ok(vec![]),
         }
+
+        let result = self.fetch_with_retry(key).await;
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let error = match result {
+            Ok(json) => {
+                state.breaker.record_success();
+                let fetched = CachedPayload {
+                    json,
+                    synced_at: Utc::now(),
+                    fetched_at: Instant::now(),
+                };
+                match fetched.snapshot(None) {
+                    Ok(snapshot) => {
+                        state.last_good.insert(key.to_string(), fetched);
+                        return Ok(snapshot);
+                    }
+                    Err(e) => e,
+                }
+            }
+            Err(e) => {
+                if matches!(e, RedisClientError::Connection(_)) {
+                    state.breaker.record_failure(Instant::now());
+                }
+                e
+            }
+        };
+        match state.last_good.get(key) {
+            Some(cached) => {
+                tracing::warn!("Serving last synced payload of {}: {}", key, error);
+                cached.snapshot(Some(error.to_string()))
+            }
+            None => Err(error),
+        }
+    }
+
+    pub async fn get_notion_tasks(&self) -> Result<RedisSnapshot<NotionTask>, RedisClientError> {
+        self.get_list(NOTION_TASKS_KEY).await
+    }
+
+    pub async fn publish(&self, channel: &str, payload: &str) -> Result<(), RedisClientError> {
+        let mut conn = self.get_connection().await?;
+        let _: i64 = self
+            .reset_on_error(conn.publish(channel, payload).await)
+            .await?;
+        Ok(())
+    }
+
+    /// Take or extend the lock at `key` for `owner`. Returns the current holder when someone
+    /// else has it.
+    pub async fn try_lock(
+        &self,
+        key: &str,
+        owner: &str,
+        ttl: Duration,
+    ) -> Result<Option<String>, RedisClientError> {
+        let mut conn = self.get_connection().await?;
+        let result = redis::Script::new(LOCK_SCRIPT)
+            .key(key)
+            .arg(owner)
+            .arg(ttl.as_millis() as u64)
+            .invoke_async(&mut conn)
+            .await;
+        self.reset_on_error(result).await
+    }
+
+    /// Release the lock at `key` if `owner` still holds it.
+    pub async fn unlock(&self, key: &str, owner: &str) -> Result<(), RedisClientError> {
+        let mut conn = self.get_connection().await?;
+        let result: Result<i64, _> = redis::Script::new(UNLOCK_SCRIPT)
+            .key(key)
+            .arg(owner)
+            .invoke_async(&mut conn)
+            .await;
+        self.reset_on_error(result).await?;
+        Ok(())
+    }
+
+    /// A dedicated connection subscribed to `channels`.
+    pub async fn subscribe(&self, channels: &[&str]) -> Result<PubSub, RedisClientError> {
+        let settings = self
+            .settings
+            .as_ref()
+            .ok_or(RedisClientError::NotConfigured)?;
+        let RedisTopology::Single { url } = &settings.topology else {
+            return Err(RedisClientError::SubscribeUnsupported(
+                settings.topology.name(),
+            ));
+        };
+        let client = redis::Client::open(settings.connection_info(url)?)?;
+        let mut pubsub = client.get_async_pubsub().await?;
+        for channel in channels {
+            pubsub.subscribe(*channel).await?;
+        }
+        Ok(pubsub)
+    }
+
+    /// Round trip time of a `PING`, connecting first if needed.
+    pub async fn ping(&self) -> Result<Duration, RedisClientError> {
+        let started = Instant::now();
+        let mut conn = self.get_connection().await?;
+        let _: String = self
+            .reset_on_error(redis::cmd("PING").query_async(&mut conn).await)
+            .await?;
+        Ok(started.elapsed())
     }
 }
 
@@ -103,3 +572,84 @@ impl Default for RedisClient {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn settings(vars: &[(&str, &str)]) -> RedisSettings {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        RedisSettings::from_vars(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn reads_topology_from_environment() {
+        assert_eq!(
+            settings(&[]).topology,
+            RedisTopology::Single {
+                url: DEFAULT_REDIS_URL.to_string()
+            }
+        );
+
+        let sentinel = settings(&[
+            ("REDIS_SENTINELS", "10.0.0.1:26379, rediss://10.0.0.2:26379"),
+            ("REDIS_SENTINEL_MASTER", "notion"),
+            ("REDIS_PASSWORD", "secret"),
+        ]);
+        assert_eq!(
+            sentinel.topology,
+            RedisTopology::Sentinel {
+                sentinels: vec![
+                    "redis://10.0.0.1:26379".to_string(),
+                    "rediss://10.0.0.2:26379".to_string()
+                ],
+                master_name: "notion".to_string(),
+            }
+        );
+        assert_eq!(sentinel.password.as_deref(), Some("secret"));
+
+        let cluster = settings(&[("REDIS_CLUSTER_NODES", "a:6379,b:6379")]);
+        assert_eq!(cluster.topology.name(), "cluster");
+    }
+
+    #[test]
+    fn circuit_opens_after_repeated_failures() {
+        let now = Instant::now();
+        let mut breaker = CircuitBreaker::default();
+        breaker.record_failure(now);
+        breaker.record_failure(now);
+        assert_eq!(breaker.retry_in(now), None);
+
+        breaker.record_failure(now);
+        assert_eq!(breaker.retry_in(now), Some(CIRCUIT_OPEN_FOR));
+        assert_eq!(breaker.retry_in(now + CIRCUIT_OPEN_FOR), None);
+
+        // A failed trial call opens the circuit again straight away
+        breaker.record_failure(now + CIRCUIT_OPEN_FOR);
+        assert!(breaker.retry_in(now + CIRCUIT_OPEN_FOR).is_some());
+
+        breaker.record_success();
+        assert_eq!(breaker.retry_in(now), None);
+    }
+
+    #[test]
+    fn applies_credentials_to_urls() {
+        let settings = settings(&[
+            ("REDIS_URL", "rediss://cache.example.com:6380/2"),
+            ("REDIS_USERNAME", "vibe"),
+            ("REDIS_PASSWORD", "secret"),
+        ]);
+        let RedisTopology::Single { url } = &settings.topology else {
+            panic!("expected a single server");
+        };
+        let info = settings.connection_info(url).unwrap();
+        assert_eq!(info.redis.username.as_deref(), Some("vibe"));
+        assert_eq!(info.redis.password.as_deref(), Some("secret"));
+        assert_eq!(info.redis.db, 2);
+    }
+}
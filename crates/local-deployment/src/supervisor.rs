@@ -0,0 +1,152 @@
+//! Liveness sampling for executor child processes. Every child is the leader of its own process
+//! group, so memory and CPU are summed over the group to include the agent the shell started.
+
+use db::models::execution_process_heartbeat::RecordHeartbeat;
+
+/// USER_HZ, which `/proc/<pid>/stat` reports CPU times in; 100 on every mainstream architecture
+#[cfg(target_os = "linux")]
+const CLOCK_TICKS_PER_SEC: f64 = 100.0;
+
+/// What the OS reports about a child process right now.
+#[derive(Debug, Clone)]
+pub struct ProcessSample {
+    pub pid: u32,
+    /// Single-letter process state, e.g. R, S or Z. Unknown where `/proc` isn't available
+    pub state: Option<String>,
+    /// Resident memory of the whole process group
+    pub rss_kb: Option<u64>,
+    /// CPU time used by the process group so far
+    pub cpu_seconds: Option<f64>,
+}
+
+impl ProcessSample {
+    /// The leader exited but nobody has collected its exit status yet
+    pub fn is_defunct(&self) -> bool {
+        matches!(self.state.as_deref(), Some("Z" | "X"))
+    }
+
+    pub fn heartbeat(&self) -> RecordHeartbeat {
+        RecordHeartbeat {
+            pid: Some(self.pid as i64),
+            state: self.state.clone(),
+            rss_kb: self.rss_kb.map(|kb| kb as i64),
+            cpu_seconds: self.cpu_seconds,
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, PartialEq)]
+struct StatFields {
+    state: char,
+    pgrp: i32,
+    cpu_ticks: u64,
+}
+
+/// Parse `/proc/<pid>/stat`. The command name is in parentheses and may contain spaces, so fields
+/// are counted from the last closing parenthesis.
+#[cfg(any(target_os = "linux", test))]
+fn parse_stat(contents: &str) -> Option<StatFields> {
+    let (_, rest) = contents.rsplit_once(')')?;
+    let fields: Vec<&str> = rest.split_whitespace().collect();
+    let state = fields.first()?.chars().next()?;
+    let pgrp = fields.get(2)?.parse().ok()?;
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(StatFields {
+        state,
+        pgrp,
+        cpu_ticks: utime + stime,
+    })
+}
+
+/// Parse `VmRSS` from `/proc/<pid>/status`; absent for zombies and kernel threads
+#[cfg(any(target_os = "linux", test))]
+fn parse_vm_rss_kb(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|value| value.split_whitespace().next())
+        .and_then(|kb| kb.parse().ok())
+}
+
+/// Sample a child process and the rest of its process group. `None` once the process is gone.
+#[cfg(target_os = "linux")]
+pub fn sample(pid: u32) -> Option<ProcessSample> {
+    let read_stat = |pid: &str| {
+        std::fs::read_to_string(format!("/proc/{pid}/stat"))
+            .ok()
+            .and_then(|stat| parse_stat(&stat))
+    };
+    let read_rss = |pid: &str| {
+        std::fs::read_to_string(format!("/proc/{pid}/status"))
+            .ok()
+            .and_then(|status| parse_vm_rss_kb(&status))
+    };
+
+    let leader = read_stat(&pid.to_string())?;
+    let mut rss_kb = 0;
+    let mut cpu_ticks = 0;
+    if let Ok(entries) = std::fs::read_dir("/proc") {
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let Some(member) = name
+                .to_str()
+                .filter(|n| n.chars().all(|c| c.is_ascii_digit()))
+            else {
+                continue;
+            };
+            if let Some(stat) = read_stat(member)
+                && stat.pgrp == leader.pgrp
+            {
+                cpu_ticks += stat.cpu_ticks;
+                rss_kb += read_rss(member).unwrap_or(0);
+            }
+        }
+    }
+
+    Some(ProcessSample {
+        pid,
+        state: Some(leader.state.to_string()),
+        rss_kb: Some(rss_kb),
+        cpu_seconds: Some(cpu_ticks as f64 / CLOCK_TICKS_PER_SEC),
+    })
+}
+
+/// Without `/proc` only the pid is known, and the exit watcher is left to notice exits
+#[cfg(not(target_os = "linux"))]
+pub fn sample(pid: u32) -> Option<ProcessSample> {
+    Some(ProcessSample {
+        pid,
+        state: None,
+        rss_kb: None,
+        cpu_seconds: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_stat_with_spaces_in_command() {
+        let stat = "4242 (node (claude) x) S 1 4242 4242 0 -1 4194560 1 0 0 0 150 50 0 0 20 0 \
+                    11 0 100 1000 200 18446744073709551615";
+        assert_eq!(
+            parse_stat(stat),
+            Some(StatFields {
+                state: 'S',
+                pgrp: 4242,
+                cpu_ticks: 200,
+            })
+        );
+        assert_eq!(parse_stat("4242 (node"), None);
+    }
+
+    #[test]
+    fn parses_vm_rss() {
+        let status = "Name:\tnode\nState:\tS (sleeping)\nVmRSS:\t  123456 kB\nThreads:\t11\n";
+        assert_eq!(parse_vm_rss_kb(status), Some(123456));
+        assert_eq!(parse_vm_rss_kb("Name:\tzombie\nState:\tZ (zombie)\n"), None);
+    }
+}
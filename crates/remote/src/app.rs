@@ -95,7 +95,7 @@ impl Server {
             );
         }
 
-        let http_client = reqwest::Client::builder()
+        let http_client = utils::http::client_builder()
             .user_agent("VibeKanbanRemote/1.0")
             .build()
             .context("failed to create HTTP client")?;
@@ -107,7 +107,9 @@ pub struct GitHubOAuthProvider {
 
 impl GitHubOAuthProvider {
     pub fn new(client_id: String, client_secret: SecretString) -> Result<Self> {
-        let client = Client::builder().user_agent(USER_AGENT).build()?;
+        let client = utils::http::client_builder()
+            .user_agent(USER_AGENT)
+            .build()?;
         Ok(Self {
             client,
             client_id,
@@ -368,7 +370,9 @@ pub struct GoogleOAuthProvider {
 
 impl GoogleOAuthProvider {
     pub fn new(client_id: String, client_secret: SecretString) -> Result<Self> {
-        let client = Client::builder().user_agent(USER_AGENT).build()?;
+        let client = utils::http::client_builder()
+            .user_agent(USER_AGENT)
+            .build()?;
         Ok(Self {
             client,
             client_id,
@@ -32,7 +32,7 @@ pub struct LoopsMailer {
 
 impl LoopsMailer {
     pub fn new(api_key: String) -> Self {
-        let client = reqwest::Client::builder()
+        let client = utils::http::client_builder()
             .timeout(Duration::from_secs(5))
             .build()
             .expect("failed to build reqwest client");
@@ -20,13 +20,172 @@ fn generate_types_content() -> String {
         db::models::project::UpdateProject::decl(),
         db::models::project::SearchResult::decl(),
         db::models::project::SearchMatchType::decl(),
+        services::services::code_search::CodeSearchMode::decl(),
+        services::services::code_search::CodeSearchResult::decl(),
+        services::services::executor_stats::ExecutorStats::decl(),
+        services::services::question_stats::QuestionStats::decl(),
+        services::services::question_stats::ExecutorQuestionStats::decl(),
+        services::services::project_stats::ProjectStats::decl(),
+        services::services::project_stats::WeeklyThroughput::decl(),
+        services::services::project_stats::ColumnTime::decl(),
+        services::services::project_stats::AttemptsPerTask::decl(),
+        services::services::project_stats::ResponseLatency::decl(),
+        services::services::project_stats::TimeSpent::decl(),
+        services::services::project_stats::WeeklyTime::decl(),
+        services::services::flow_reports::CycleTimeReport::decl(),
+        services::services::flow_reports::TaskCycleTime::decl(),
+        services::services::flow_reports::CycleTimePoint::decl(),
+        services::services::flow_reports::ColumnLeadTime::decl(),
+        services::services::flow_reports::BurndownReport::decl(),
+        services::services::flow_reports::BurndownPoint::decl(),
+        db::models::milestone::Milestone::decl(),
+        db::models::milestone::CreateMilestone::decl(),
+        db::models::milestone::MilestoneTask::decl(),
+        services::services::milestones::MilestoneProgress::decl(),
+        services::services::milestones::MilestoneSummary::decl(),
+        services::services::milestones::MilestoneView::decl(),
+        services::services::time_tracking::TaskTime::decl(),
+        services::services::time_tracking::AttemptTime::decl(),
+        db::models::task_time_entry::TaskTimeEntry::decl(),
+        db::models::task_time_entry::CreateTaskTimeEntry::decl(),
+        services::services::tool_stats::ToolStats::decl(),
+        services::services::review_sla::ReviewWaitingOn::decl(),
+        services::services::review_sla::ReviewSlaBreach::decl(),
+        services::services::stale_tasks::StaleTask::decl(),
+        services::services::code_search::CodeSearchMatch::decl(),
         db::models::repo::Repo::decl(),
+        db::models::review::Review::decl(),
+        db::models::review::ReviewStatus::decl(),
+        db::models::review::ReviewFinding::decl(),
+        db::models::review::ReviewSeverity::decl(),
+        db::models::attempt_review::AttemptReview::decl(),
+        db::models::attempt_review::AttemptReviewVerdict::decl(),
+        db::models::attempt_review::CreateAttemptReview::decl(),
+        db::models::attempt_review::AttemptReviewComment::decl(),
+        db::models::attempt_review::CreateAttemptReviewComment::decl(),
+        db::models::attempt_review::UpdateAttemptReviewComment::decl(),
+        db::models::review_checklist::ReviewChecklistItem::decl(),
+        db::models::review_checklist::CreateReviewChecklistItem::decl(),
+        db::models::review_checklist::UpdateReviewChecklistItem::decl(),
+        db::models::review_checklist::ChecklistItemState::decl(),
         db::models::project_repo::ProjectRepo::decl(),
         db::models::project_repo::CreateProjectRepo::decl(),
         db::models::project_repo::UpdateProjectRepo::decl(),
+        db::models::project_env_var::ProjectEnvVarResponse::decl(),
+        db::models::project_env_var::CreateProjectEnvVar::decl(),
+        db::models::project_env_var::UpdateProjectEnvVar::decl(),
+        db::models::secret::SecretResponse::decl(),
+        db::models::secret::CreateSecret::decl(),
+        db::models::secret::UpdateSecret::decl(),
+        db::models::feature_flag::FeatureFlag::decl(),
+        db::models::feature_flag::FeatureFlagOverride::decl(),
+        db::models::feature_flag::SetFeatureFlag::decl(),
+        db::models::project_mcp_server::ProjectMcpServer::decl(),
+        db::models::project_mcp_server::CreateProjectMcpServer::decl(),
+        db::models::project_mcp_server::UpdateProjectMcpServer::decl(),
+        db::models::project_lifecycle_hook::LifecycleHook::decl(),
+        db::models::project_lifecycle_hook::ProjectLifecycleHook::decl(),
+        db::models::project_lifecycle_hook::UpsertProjectLifecycleHook::decl(),
+        db::models::project_linter::Linter::decl(),
+        db::models::project_linter::ProjectLinter::decl(),
+        db::models::project_linter::UpsertProjectLinter::decl(),
+        db::models::project_sandbox::SandboxNetwork::decl(),
+        db::models::project_sandbox::ProjectSandbox::decl(),
+        db::models::project_sandbox::UpsertProjectSandbox::decl(),
+        db::models::project_permission_policy::ProjectPermissionPolicy::decl(),
+        db::models::project_permission_policy::UpsertProjectPermissionPolicy::decl(),
+        db::models::project_template::ProjectTemplateRepo::decl(),
+        db::models::project_template::ProjectTemplateEnvVar::decl(),
+        db::models::project_template::ProjectTemplateMcpServer::decl(),
+        db::models::project_template::ProjectTemplateConfig::decl(),
+        db::models::project_template::ProjectTemplate::decl(),
+        db::models::project_template::CreateProjectTemplate::decl(),
+        db::models::project_template::CreateProjectFromTemplate::decl(),
+        db::models::prompt_template::PromptTemplateKind::decl(),
+        db::models::prompt_template::PromptTemplate::decl(),
+        db::models::prompt_template::CreatePromptTemplate::decl(),
+        services::services::prompt_templates::PromptTemplateVariable::decl(),
+        db::models::redis_task_source::RedisTaskSource::decl(),
+        db::models::redis_task_source::TaskSourceFieldMapping::decl(),
+        db::models::redis_task_source::CreateRedisTaskSource::decl(),
+        db::models::redis_task_source::UpdateRedisTaskSource::decl(),
+        db::models::project_webhook::ProjectWebhook::decl(),
+        db::models::project_event_webhook::EventWebhookKind::decl(),
+        db::models::project_event_webhook::ProjectEventWebhook::decl(),
+        db::models::project_event_webhook::CreateProjectEventWebhook::decl(),
+        db::models::project_event_webhook::UpdateProjectEventWebhook::decl(),
+        db::models::public_share_link::PublicShareLink::decl(),
+        db::models::github_issue::GitHubIssueSync::decl(),
+        db::models::error_tracker::ErrorTrackerIntegration::decl(),
+        db::models::project_auto_start::ProjectAutoStart::decl(),
+        db::models::project_auto_start::UpsertProjectAutoStart::decl(),
+        db::models::slack::SlackIntegration::decl(),
+        db::models::question_escalation::EscalationChannel::decl(),
+        db::models::question_escalation::EscalationStep::decl(),
+        db::models::question_escalation::ProjectQuestionEscalation::decl(),
+        db::models::question_escalation::UpdateQuestionEscalation::decl(),
+        db::models::question_auto_answer_rule::QuestionAutoAnswerRule::decl(),
+        db::models::question_auto_answer_rule::CreateQuestionAutoAnswerRule::decl(),
+        db::models::question_auto_answer_rule::UpdateQuestionAutoAnswerRule::decl(),
+        db::models::task_transition_rule::TransitionRequirement::decl(),
+        db::models::task_transition_rule::TaskTransitionRule::decl(),
+        db::models::task_transition_rule::CreateTaskTransitionRule::decl(),
+        db::models::task_label::TaskLabel::decl(),
+        db::models::task_assignee::TaskAssignee::decl(),
+        db::models::task_assignee::AssignTask::decl(),
+        db::models::automation_rule::AutomationTrigger::decl(),
+        db::models::automation_rule::AutomationAction::decl(),
+        db::models::automation_rule::AutomationRule::decl(),
+        db::models::automation_rule::CreateAutomationRule::decl(),
+        db::models::automation_rule::UpdateAutomationRule::decl(),
+        db::models::automation_rule::AutomationActionResult::decl(),
+        db::models::automation_rule::AutomationRuleFiring::decl(),
+        db::models::bash_command_rule::BashCommandRule::decl(),
+        db::models::bash_command_rule::CreateBashCommandRule::decl(),
+        db::models::bash_command_rule::UpdateBashCommandRule::decl(),
+        db::models::bash_command_rule::CommandMatchKind::decl(),
+        db::models::bash_command_rule::CommandRuleAction::decl(),
+        db::models::file_path_rule::FilePathRule::decl(),
+        db::models::file_path_rule::CreateFilePathRule::decl(),
+        db::models::file_path_rule::UpdateFilePathRule::decl(),
+        db::models::file_path_rule::PathRuleAction::decl(),
+        services::services::approvals::command_rules::CommandDecision::decl(),
+        services::services::approvals::command_rules::CommandClassification::decl(),
+        executors::mcp_config::McpServerTransport::decl(),
+        db::models::user::UserRole::decl(),
+        db::models::user::User::decl(),
+        db::models::user::CreateUser::decl(),
+        db::models::api_token::ApiToken::decl(),
+        db::models::api_token::CreateApiToken::decl(),
+        db::models::user::UpdateUser::decl(),
+        db::models::user_push_settings::UserPushSettings::decl(),
+        db::models::user_push_settings::UpdateUserPushSettings::decl(),
+        db::models::team::TeamRole::decl(),
+        db::models::team::TeamNotificationDefaults::decl(),
+        db::models::team::Team::decl(),
+        db::models::team::CreateTeam::decl(),
+        db::models::team::UpdateTeam::decl(),
+        db::models::team::TeamMember::decl(),
+        db::models::notification::NotificationKind::decl(),
+        db::models::notification::Notification::decl(),
         db::models::workspace_repo::WorkspaceRepo::decl(),
         db::models::workspace_repo::CreateWorkspaceRepo::decl(),
         db::models::workspace_repo::RepoWithTargetBranch::decl(),
+        db::models::workspace_summary::WorkspaceSummary::decl(),
+        db::models::attempt_checkpoint::CheckpointTrigger::decl(),
+        db::models::attempt_checkpoint::CheckpointRepoState::decl(),
+        db::models::attempt_checkpoint::AttemptCheckpoint::decl(),
+        db::models::attempt_handoff::AttemptHandoff::decl(),
+        db::models::attempt_screenshot::AttemptScreenshot::decl(),
+        db::models::attempt_e2e_run::E2eRunStatus::decl(),
+        db::models::attempt_e2e_run::E2eArtifactKind::decl(),
+        db::models::attempt_e2e_run::E2eTestFailure::decl(),
+        db::models::attempt_e2e_run::AttemptE2eRun::decl(),
+        db::models::attempt_e2e_run::AttemptE2eArtifact::decl(),
+        db::models::attempt_lint_annotation::LintSeverity::decl(),
+        db::models::attempt_lint_annotation::AttemptLintAnnotation::decl(),
+        db::models::workspace_base_watch::WorkspaceBaseWatch::decl(),
+        db::models::workspace_base_watch::SetWorkspaceBaseWatch::decl(),
         db::models::tag::Tag::decl(),
         db::models::tag::CreateTag::decl(),
         db::models::tag::UpdateTag::decl(),
@@ -44,25 +203,47 @@ fn generate_types_content() -> String {
         db::models::scratch::UpdateScratch::decl(),
         db::models::image::Image::decl(),
         db::models::image::CreateImage::decl(),
+        db::models::task_attachment::TaskAttachment::decl(),
+        db::models::task_prompt::TaskPrompt::decl(),
         db::models::workspace::Workspace::decl(),
         db::models::session::Session::decl(),
         db::models::execution_process::ExecutionProcess::decl(),
         db::models::execution_process::ExecutionProcessStatus::decl(),
         db::models::execution_process::ExecutionProcessRunReason::decl(),
         db::models::execution_process_repo_state::ExecutionProcessRepoState::decl(),
+        db::models::execution_process_environment::ExecutionProcessEnvironment::decl(),
+        db::models::execution_process_environment::RepoEnvironment::decl(),
+        db::models::execution_process_environment::LockfileHash::decl(),
+        db::models::execution_process_failure::ExecutionProcessFailure::decl(),
+        db::models::execution_process_failure::ExecutionProcessFailureKind::decl(),
+        services::services::model_fallback::ModelSegment::decl(),
+        db::models::execution_process_heartbeat::ExecutionProcessHeartbeat::decl(),
+        db::models::log_entry_annotation::LogEntryAnnotation::decl(),
+        db::models::log_entry_annotation::CreateLogEntryAnnotation::decl(),
+        db::models::log_entry_annotation::UpdateLogEntryAnnotation::decl(),
+        db::models::execution_process_limits::ExecutionProcessLimits::decl(),
+        db::models::integration_outbox::OutboxIntegration::decl(),
+        db::models::integration_outbox::IntegrationOutboxEntry::decl(),
         db::models::merge::Merge::decl(),
         db::models::merge::DirectMerge::decl(),
         db::models::merge::PrMerge::decl(),
         db::models::merge::MergeStatus::decl(),
         db::models::merge::PullRequestInfo::decl(),
         utils::approvals::ApprovalStatus::decl(),
+        utils::approvals::ApprovalRequest::decl(),
         utils::approvals::CreateApprovalRequest::decl(),
         utils::approvals::ApprovalResponse::decl(),
         utils::user_questions::UserQuestion::decl(),
         utils::user_questions::QuestionOption::decl(),
+        utils::user_questions::QuestionAttachment::decl(),
         utils::user_questions::QuestionAnswer::decl(),
+        utils::user_questions::UserQuestionRequest::decl(),
+        utils::user_questions::QuestionClaim::decl(),
         utils::user_questions::UserQuestionResponse::decl(),
         utils::user_questions::UserQuestionStatus::decl(),
+        utils::pagination::SortField::decl(),
+        utils::pagination::SortOrder::decl(),
+        utils::pagination::PageQuery::decl(),
         utils::diff::Diff::decl(),
         utils::diff::DiffChangeKind::decl(),
         utils::response::ApiResponse::<()>::decl(),
@@ -96,6 +277,7 @@ fn generate_types_content() -> String {
         utils::api::projects::RemoteProjectMembersResponse::decl(),
         server::routes::projects::CreateRemoteProjectRequest::decl(),
         server::routes::projects::LinkToExistingRequest::decl(),
+        server::routes::bash_command_rules::CheckBashCommandRequest::decl(),
         server::routes::repo::RegisterRepoRequest::decl(),
         server::routes::repo::InitRepoRequest::decl(),
         server::routes::tags::TagSearchParams::decl(),
@@ -118,9 +300,50 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::RenameBranchResponse::decl(),
         server::routes::task_attempts::OpenEditorRequest::decl(),
         server::routes::task_attempts::OpenEditorResponse::decl(),
+        server::routes::accounts::AccountStatus::decl(),
+        server::routes::accounts::LoginRequest::decl(),
+        server::routes::accounts::LoginResponse::decl(),
+        server::routes::notifications::NotificationList::decl(),
+        server::routes::notifications::UnreadNotificationCount::decl(),
+        server::routes::dev_servers::DevServerHealth::decl(),
+        server::routes::dev_servers::DevServerInfo::decl(),
+        server::routes::dev_servers::OpenTunnelRequest::decl(),
+        services::services::tunnel::TunnelProviderKind::decl(),
+        services::services::tunnel::ActiveTunnel::decl(),
+        services::services::presence::PresenceActivity::decl(),
+        services::services::presence::TaskPresence::decl(),
+        services::services::events::EntityEvent::decl(),
+        services::services::events::EntityEventEnvelope::decl(),
+        services::services::backup::BackupManifest::decl(),
+        services::services::backup::BackupInfo::decl(),
+        services::services::backup::RestoreResult::decl(),
+        services::services::backup::BackupStatus::decl(),
+        services::services::maintenance::MaintenanceStep::decl(),
+        services::services::maintenance::MaintenanceReport::decl(),
+        services::services::maintenance::MaintenanceStatus::decl(),
+        services::services::analytics_export::ExportedAnalyticsEvent::decl(),
+        services::services::server_config::EffectiveServerConfig::decl(),
+        server::routes::execution_processes::RawLogLine::decl(),
+        server::routes::execution_processes::RawLogSnapshot::decl(),
+        server::routes::execution_processes::ExecutionFailureDetail::decl(),
+        server::routes::execution_processes::ExecutionProcessDetail::decl(),
+        server::routes::execution_processes::ProcessInspection::decl(),
+        server::routes::feature_flags::FeatureFlagInfo::decl(),
+        server::routes::feature_flags::FeatureFlagState::decl(),
         server::routes::shared_tasks::AssignSharedTaskRequest::decl(),
         server::routes::tasks::ShareTaskResponse::decl(),
         server::routes::tasks::CreateAndStartTaskRequest::decl(),
+        server::routes::tasks::FindSimilarTasksRequest::decl(),
+        server::routes::tasks::UpdateTaskPresenceRequest::decl(),
+        server::routes::tasks::AddTaskLabelRequest::decl(),
+        server::routes::me::WorkItemContext::decl(),
+        server::routes::me::MyPendingQuestion::decl(),
+        server::routes::me::MyPendingApproval::decl(),
+        server::routes::me::AwaitingReviewTask::decl(),
+        server::routes::me::MyWork::decl(),
+        services::services::task_similarity::SimilarTask::decl(),
+        services::services::task_assist::ProposedSubtask::decl(),
+        server::routes::task_breakdown::AcceptSubtasksRequest::decl(),
         server::routes::task_attempts::pr::CreateGitHubPrRequest::decl(),
         server::routes::images::ImageResponse::decl(),
         server::routes::images::ImageMetadata::decl(),
@@ -129,6 +352,34 @@ fn generate_types_content() -> String {
         server::routes::notion_import::NotionImportRequest::decl(),
         server::routes::notion_import::NotionImportResponse::decl(),
         server::routes::notion_import::NotionImportError::decl(),
+        server::routes::notion_import::NotionRedisHealth::decl(),
+        server::routes::task_sources::TaskSourcePreviewItem::decl(),
+        server::routes::task_sources::TaskSourcePreviewResponse::decl(),
+        server::routes::task_sources::TaskSourceImportRequest::decl(),
+        server::routes::task_sources::TaskSourceImportResponse::decl(),
+        server::routes::task_sources::TaskSourceImportError::decl(),
+        server::routes::webhooks::IncomingTaskPriority::decl(),
+        server::routes::webhooks::IncomingTaskPayload::decl(),
+        server::routes::webhooks::IncomingTaskResponse::decl(),
+        server::routes::webhooks::ProjectWebhookSecret::decl(),
+        server::routes::api_tokens::ApiTokenSecret::decl(),
+        services::services::github_issues::IssueEventOutcome::decl(),
+        server::routes::github_issues::UpdateGitHubIssueSync::decl(),
+        server::routes::github_issues::GitHubWebhookResponse::decl(),
+        services::services::error_tracker::ErrorReportOutcome::decl(),
+        server::routes::error_tracker::UpdateErrorTrackerIntegration::decl(),
+        server::routes::error_tracker::ErrorTrackerWebhookResponse::decl(),
+        server::routes::slack::UpdateSlackIntegration::decl(),
+        services::services::public_share::PublicTaskView::decl(),
+        services::services::public_share::PublicAttemptView::decl(),
+        server::routes::public_share::CreatePublicShareLink::decl(),
+        server::routes::public_share::PublicShareLinkWithUrl::decl(),
+        server::routes::teams::SetTeamMember::decl(),
+        server::routes::teams::TeamDetails::decl(),
+        server::routes::teams::PendingItemContext::decl(),
+        server::routes::teams::TeamPendingQuestion::decl(),
+        server::routes::teams::TeamPendingApproval::decl(),
+        server::routes::teams::TeamPendingItems::decl(),
         server::routes::task_attempts::CreateTaskAttemptBody::decl(),
         server::routes::task_attempts::WorkspaceRepoInput::decl(),
         server::routes::task_attempts::RunAgentSetupRequest::decl(),
@@ -143,6 +394,20 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::RunScriptError::decl(),
         server::routes::task_attempts::pr::AttachPrResponse::decl(),
         server::routes::task_attempts::pr::AttachExistingPrRequest::decl(),
+        server::routes::task_attempts::review::StartReviewRequest::decl(),
+        server::routes::task_attempts::review::ReviewWithFindings::decl(),
+        server::routes::task_attempts::review::ResolveReviewFindingRequest::decl(),
+        server::routes::task_attempts::attempt_review::AttemptReviewState::decl(),
+        services::services::repo_instructions::RepoInstructions::decl(),
+        server::routes::task_attempts::prompt_preview::PromptPreview::decl(),
+        services::services::attempt_compare::FileComparisonStatus::decl(),
+        services::services::attempt_compare::FileChange::decl(),
+        services::services::attempt_compare::FileComparison::decl(),
+        services::services::attempt_compare::TestOutcome::decl(),
+        services::services::attempt_compare::AttemptSide::decl(),
+        services::services::attempt_compare::AttemptComparison::decl(),
+        server::routes::task_attempts::checklist::SetChecklistItemRequest::decl(),
+        server::routes::task_attempts::checkpoints::RollbackCheckpointRequest::decl(),
         server::routes::task_attempts::pr::PrCommentsResponse::decl(),
         server::routes::task_attempts::pr::GetPrCommentsError::decl(),
         server::routes::task_attempts::pr::GetPrCommentsQuery::decl(),
@@ -160,12 +425,27 @@ fn generate_types_content() -> String {
         services::services::config::SoundFile::decl(),
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
+        services::services::config::BackupScheduleConfig::decl(),
+        services::services::config::ScheduleFrequency::decl(),
+        services::services::config::MaintenanceScheduleConfig::decl(),
+        services::services::config::MobilePushConfig::decl(),
+        services::services::config::LlmConfig::decl(),
+        services::services::config::LlmProvider::decl(),
+        services::services::config::EmbeddingConfig::decl(),
+        services::services::config::EmbeddingProvider::decl(),
+        services::services::config::CodeReviewConfig::decl(),
+        services::services::config::CodeMapConfig::decl(),
+        services::services::config::ReviewSlaConfig::decl(),
+        services::services::config::StaleTaskConfig::decl(),
+        services::services::config::ExecutionLimitsConfig::decl(),
         services::services::git::GitBranch::decl(),
         services::services::share::SharedTaskDetails::decl(),
         services::services::queued_message::QueuedMessage::decl(),
         services::services::queued_message::QueueStatus::decl(),
         services::services::git::ConflictOp::decl(),
         executors::actions::ExecutorAction::decl(),
+        executors::limits::ResourceLimits::decl(),
+        executors::limits::ResourceLimit::decl(),
         executors::mcp_config::McpConfig::decl(),
         executors::actions::ExecutorActionType::decl(),
         executors::actions::script::ScriptContext::decl(),
@@ -180,6 +460,7 @@ fn generate_types_content() -> String {
         executors::profile::ExecutorConfigs::decl(),
         executors::executors::BaseAgentCapability::decl(),
         executors::executors::claude::ClaudeCode::decl(),
+        executors::executors::claude::types::PermissionMode::decl(),
         executors::executors::gemini::Gemini::decl(),
         executors::executors::amp::Amp::decl(),
         executors::executors::codex::Codex::decl(),
@@ -202,6 +483,7 @@ fn generate_types_content() -> String {
         executors::logs::CommandRunResult::decl(),
         executors::logs::NormalizedEntry::decl(),
         executors::logs::NormalizedEntryType::decl(),
+        executors::logs::human_handoff::HumanHandoff::decl(),
         executors::logs::FileChange::decl(),
         executors::logs::ActionType::decl(),
         executors::logs::TodoItem::decl(),
@@ -1,11 +1,38 @@
-use rmcp::{ServiceExt, transport::stdio};
+use axum::{
+    extract::{Request, State},
+    http::{StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use rmcp::{
+    ServiceExt,
+    transport::{
+        stdio,
+        streamable_http_server::{StreamableHttpService, session::local::LocalSessionManager},
+    },
+};
 use server::mcp::task_server::TaskServer;
+use services::services::error_tracker::secret_matches;
 use tracing_subscriber::{EnvFilter, prelude::*};
 use utils::{
     port_file::read_port_file,
     sentry::{self as sentry_utils, SentrySource, sentry_layer},
 };
 
+/// Reject MCP HTTP requests that don't carry the `VK_MCP_HTTP_TOKEN` bearer token
+async fn require_mcp_token(State(token): State<String>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|presented| secret_matches(&token, presented.trim()));
+    if !authorized {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    next.run(request).await
+}
+
 fn main() -> anyhow::Result<()> {
     sentry_utils::init_once(SentrySource::Mcp);
     tokio::runtime::Builder::new_multi_thread()
@@ -52,15 +79,40 @@ fn main() -> anyhow::Result<()> {
                 url
             };
 
-            let service = TaskServer::new(&base_url)
-                .init()
-                .await
-                .serve(stdio())
-                .await
-                .map_err(|e| {
-                    tracing::error!("serving error: {:?}", e);
-                    e
-                })?;
+            // Session token for a backend running in multi-user mode, as used by the CLI
+            let token = std::env::var("VIBE_TOKEN").ok().filter(|t| !t.is_empty());
+            let server = TaskServer::new(&base_url, token).init().await;
+
+            // Serve over streamable HTTP instead of stdio when an address is configured
+            if let Ok(addr) = std::env::var("VK_MCP_HTTP_ADDR") {
+                // Anyone who can reach the endpoint acts with the backend token, so require one
+                let http_token = std::env::var("VK_MCP_HTTP_TOKEN")
+                    .ok()
+                    .filter(|t| !t.is_empty())
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("VK_MCP_HTTP_TOKEN must be set when VK_MCP_HTTP_ADDR is")
+                    })?;
+                let service = StreamableHttpService::new(
+                    move || Ok(server.clone()),
+                    LocalSessionManager::default().into(),
+                    Default::default(),
+                );
+                let router = axum::Router::new().nest_service("/mcp", service).layer(
+                    middleware::from_fn_with_state(http_token, require_mcp_token),
+                );
+                let listener = tokio::net::TcpListener::bind(&addr).await?;
+                tracing::info!(
+                    "[MCP] Serving streamable HTTP on http://{}/mcp",
+                    listener.local_addr()?
+                );
+                axum::serve(listener, router).await?;
+                return Ok(());
+            }
+
+            let service = server.serve(stdio()).await.map_err(|e| {
+                tracing::error!("serving error: {:?}", e);
+                e
+            })?;
 
             service.waiting().await?;
             Ok(())
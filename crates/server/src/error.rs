@@ -13,19 +13,34 @@ use deployment::{DeploymentError, RemoteClientNotConfigured};
 use executors::executors::ExecutorError;
 use git2::Error as Git2Error;
 use services::services::{
+    accounts::AccountError,
+    attachment::AttachmentError,
+    backup::BackupError,
+    blob_storage::BlobStorageError,
     config::{ConfigError, EditorOpenError},
     container::ContainerError,
     git::GitServiceError,
     github::GitHubServiceError,
     image::ImageError,
+    lifecycle_hooks::LifecycleHookError,
+    llm::LlmError,
+    maintenance::MaintenanceError,
+    oidc::OidcError,
+    outbox::OutboxError,
+    presence::PresenceError,
     project::ProjectServiceError,
+    project_env::ProjectEnvError,
+    public_share::PublicShareError,
     remote_client::RemoteClientError,
     repo::RepoError as RepoServiceError,
+    secret_vault::SecretVaultError,
     share::ShareError,
+    task_workflow::TransitionError,
+    tunnel::TunnelError,
     worktree_manager::WorktreeError,
 };
 use thiserror::Error;
-use utils::response::ApiResponse;
+use utils::{pagination::PaginationError, response::ApiResponse};
 
 #[derive(Debug, Error, ts_rs::TS)]
 #[ts(type = "string")]
@@ -60,6 +75,10 @@ pub enum ApiError {
     Config(#[from] ConfigError),
     #[error(transparent)]
     Image(#[from] ImageError),
+    #[error(transparent)]
+    Attachment(#[from] AttachmentError),
+    #[error(transparent)]
+    Llm(#[from] LlmError),
     #[error("Multipart error: {0}")]
     Multipart(#[from] MultipartError),
     #[error("IO error: {0}")]
@@ -76,6 +95,8 @@ pub enum ApiError {
     Conflict(String),
     #[error("Forbidden: {0}")]
     Forbidden(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
 }
 
 impl From<&'static str> for ApiError {
@@ -122,6 +143,9 @@ impl IntoResponse for ApiError {
             },
             ApiError::GitHubService(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitHubServiceError"),
             ApiError::Deployment(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DeploymentError"),
+            ApiError::Container(ContainerError::Draining) => {
+                (StatusCode::SERVICE_UNAVAILABLE, "ContainerError")
+            }
             ApiError::Container(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError"),
             ApiError::Executor(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ExecutorError"),
             ApiError::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DatabaseError"),
@@ -130,9 +154,26 @@ impl IntoResponse for ApiError {
             ApiError::Image(img_err) => match img_err {
                 ImageError::InvalidFormat => (StatusCode::BAD_REQUEST, "InvalidImageFormat"),
                 ImageError::TooLarge(_, _) => (StatusCode::PAYLOAD_TOO_LARGE, "ImageTooLarge"),
-                ImageError::NotFound => (StatusCode::NOT_FOUND, "ImageNotFound"),
+                ImageError::NotFound | ImageError::Storage(BlobStorageError::NotFound(_)) => {
+                    (StatusCode::NOT_FOUND, "ImageNotFound")
+                }
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "ImageError"),
             },
+            ApiError::Attachment(err) => match err {
+                AttachmentError::Empty => (StatusCode::BAD_REQUEST, "AttachmentError"),
+                AttachmentError::TooLarge(_, _) => {
+                    (StatusCode::PAYLOAD_TOO_LARGE, "AttachmentTooLarge")
+                }
+                AttachmentError::NotFound
+                | AttachmentError::Storage(BlobStorageError::NotFound(_)) => {
+                    (StatusCode::NOT_FOUND, "AttachmentNotFound")
+                }
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "AttachmentError"),
+            },
+            ApiError::Llm(err) => match err {
+                LlmError::MissingApiKey => (StatusCode::BAD_REQUEST, "LlmError"),
+                _ => (StatusCode::BAD_GATEWAY, "LlmError"),
+            },
             ApiError::Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, "IoError"),
             ApiError::EditorOpen(err) => match err {
                 EditorOpenError::LaunchFailed { .. } => {
@@ -177,6 +218,7 @@ impl IntoResponse for ApiError {
             ApiError::BadRequest(_) => (StatusCode::BAD_REQUEST, "BadRequest"),
             ApiError::Conflict(_) => (StatusCode::CONFLICT, "ConflictError"),
             ApiError::Forbidden(_) => (StatusCode::FORBIDDEN, "ForbiddenError"),
+            ApiError::NotFound(_) => (StatusCode::NOT_FOUND, "NotFoundError"),
         };
 
         let error_message = match &self {
@@ -187,11 +229,26 @@ impl IntoResponse for ApiError {
                     *size as f64 / 1_048_576.0,
                     *max as f64 / 1_048_576.0
                 ),
-                ImageError::NotFound => "Image not found.".to_string(),
+                ImageError::NotFound | ImageError::Storage(BlobStorageError::NotFound(_)) => {
+                    "Image not found.".to_string()
+                }
                 _ => {
                     "Failed to process image. Please try again.".to_string()
                 }
             },
+            ApiError::Attachment(err) => match err {
+                AttachmentError::Empty => "The file is empty.".to_string(),
+                AttachmentError::TooLarge(size, max) => format!(
+                    "This file is too large ({:.1} MB). Maximum file size is {:.1} MB.",
+                    *size as f64 / 1_048_576.0,
+                    *max as f64 / 1_048_576.0
+                ),
+                AttachmentError::NotFound
+                | AttachmentError::Storage(BlobStorageError::NotFound(_)) => {
+                    "Attachment not found.".to_string()
+                }
+                _ => "Failed to process attachment. Please try again.".to_string(),
+            },
             ApiError::GitService(git_err) => match git_err {
                 services::services::git::GitServiceError::MergeConflicts(msg) => msg.clone(),
                 services::services::git::GitServiceError::RebaseInProgress => {
@@ -253,6 +310,7 @@ impl IntoResponse for ApiError {
             ApiError::BadRequest(msg) => msg.clone(),
             ApiError::Conflict(msg) => msg.clone(),
             ApiError::Forbidden(msg) => msg.clone(),
+            ApiError::NotFound(msg) => msg.clone(),
             _ => format!("{}: {}", error_type, self),
         };
         let response = ApiResponse::<()>::error(&error_message);
@@ -313,6 +371,140 @@ impl From<ShareError> for ApiError {
     }
 }
 
+impl From<AccountError> for ApiError {
+    fn from(err: AccountError) -> Self {
+        match err {
+            AccountError::Database(db_err) => ApiError::Database(db_err),
+            AccountError::InvalidCredentials => ApiError::Unauthorized,
+            AccountError::Disabled => ApiError::Forbidden(err.to_string()),
+            AccountError::Validation(msg) => ApiError::BadRequest(msg),
+            AccountError::UsernameTaken | AccountError::LastAdmin | AccountError::AlreadySetUp => {
+                ApiError::Conflict(err.to_string())
+            }
+            AccountError::PasswordHash => ApiError::Io(std::io::Error::other(err.to_string())),
+        }
+    }
+}
+
+impl From<OidcError> for ApiError {
+    fn from(err: OidcError) -> Self {
+        match err {
+            OidcError::Account(account_err) => account_err.into(),
+            OidcError::Database(db_err) => ApiError::Database(db_err),
+            OidcError::NotConfigured => ApiError::BadRequest(err.to_string()),
+            OidcError::InvalidState | OidcError::InvalidIdToken(_) => ApiError::Unauthorized,
+            OidcError::AccessDenied => ApiError::Forbidden(err.to_string()),
+            OidcError::Provider(_) | OidcError::Http(_) => {
+                tracing::warn!(?err, "single sign-on failed");
+                ApiError::Conflict(err.to_string())
+            }
+        }
+    }
+}
+
+impl From<PublicShareError> for ApiError {
+    fn from(err: PublicShareError) -> Self {
+        // Forged, revoked and expired links all end up here; only expiry is worth telling apart
+        ApiError::Forbidden(err.to_string())
+    }
+}
+
+impl From<BackupError> for ApiError {
+    fn from(err: BackupError) -> Self {
+        match err {
+            BackupError::Database(db_err) => ApiError::Database(db_err),
+            BackupError::Io(io_err) => ApiError::Io(io_err),
+            BackupError::IncompatibleVersion { .. } | BackupError::Storage(_) => {
+                ApiError::Conflict(err.to_string())
+            }
+            BackupError::Manifest(_) | BackupError::NotFound | BackupError::InvalidArchive(_) => {
+                ApiError::BadRequest(err.to_string())
+            }
+        }
+    }
+}
+
+impl From<MaintenanceError> for ApiError {
+    fn from(err: MaintenanceError) -> Self {
+        match err {
+            MaintenanceError::AlreadyRunning => ApiError::Conflict(err.to_string()),
+        }
+    }
+}
+
+impl From<PresenceError> for ApiError {
+    fn from(err: PresenceError) -> Self {
+        match err {
+            PresenceError::Claimed(_) => ApiError::Conflict(err.to_string()),
+        }
+    }
+}
+
+impl From<TransitionError> for ApiError {
+    fn from(err: TransitionError) -> Self {
+        match err {
+            TransitionError::Database(db_err) => ApiError::Database(db_err),
+            TransitionError::Blocked { .. } => ApiError::Conflict(err.to_string()),
+        }
+    }
+}
+
+impl From<TunnelError> for ApiError {
+    fn from(err: TunnelError) -> Self {
+        match err {
+            TunnelError::ProviderUnavailable(_) => ApiError::BadRequest(err.to_string()),
+            TunnelError::Spawn(_) | TunnelError::Timeout(_) | TunnelError::Exited => {
+                tracing::warn!(?err, "failed to open dev server tunnel");
+                ApiError::Conflict(err.to_string())
+            }
+        }
+    }
+}
+
+impl From<ProjectEnvError> for ApiError {
+    fn from(err: ProjectEnvError) -> Self {
+        match err {
+            ProjectEnvError::Database(db_err) => ApiError::Database(db_err),
+            ProjectEnvError::Cipher(cipher_err) => {
+                ApiError::Deployment(DeploymentError::from(cipher_err))
+            }
+            ProjectEnvError::InvalidKey(msg) => {
+                ApiError::BadRequest(format!("Invalid environment variable name: {}", msg))
+            }
+        }
+    }
+}
+
+impl From<SecretVaultError> for ApiError {
+    fn from(err: SecretVaultError) -> Self {
+        match err {
+            SecretVaultError::Database(db_err) => ApiError::Database(db_err),
+            SecretVaultError::Cipher(cipher_err) => {
+                ApiError::Deployment(DeploymentError::from(cipher_err))
+            }
+            SecretVaultError::InvalidName(_) => ApiError::BadRequest(err.to_string()),
+        }
+    }
+}
+
+impl From<LifecycleHookError> for ApiError {
+    fn from(err: LifecycleHookError) -> Self {
+        match err {
+            LifecycleHookError::Database(db_err) => ApiError::Database(db_err),
+            LifecycleHookError::Io(io_err) => ApiError::Io(io_err),
+            LifecycleHookError::Failed { .. } | LifecycleHookError::E2eNotPassed(_) => {
+                ApiError::Conflict(err.to_string())
+            }
+        }
+    }
+}
+
+impl From<PaginationError> for ApiError {
+    fn from(err: PaginationError) -> Self {
+        ApiError::BadRequest(err.to_string())
+    }
+}
+
 impl From<ProjectServiceError> for ApiError {
     fn from(err: ProjectServiceError) -> Self {
         match err {
@@ -389,3 +581,19 @@ impl From<ProjectRepoError> for ApiError {
         }
     }
 }
+
+impl From<OutboxError> for ApiError {
+    fn from(err: OutboxError) -> Self {
+        match err {
+            OutboxError::Database(db_err) => ApiError::Database(db_err),
+            OutboxError::GitHub(github_err) => ApiError::GitHubService(github_err),
+            OutboxError::Git(git_err) => ApiError::GitService(git_err),
+            OutboxError::Cipher(cipher_err) => {
+                ApiError::Deployment(DeploymentError::from(cipher_err))
+            }
+            OutboxError::Serde(_) | OutboxError::Slack(_) | OutboxError::Gone(_) => {
+                ApiError::Conflict(err.to_string())
+            }
+        }
+    }
+}
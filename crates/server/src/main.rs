@@ -1,7 +1,14 @@
+use std::{net::SocketAddr, time::Duration};
+
 use anyhow::{self, Error as AnyhowError};
 use deployment::{Deployment, DeploymentError};
-use server::{DeploymentImpl, routes};
-use services::services::container::ContainerService;
+use server::{
+    DeploymentImpl,
+    middleware::{ProxySettings, RequestLimits},
+    routes,
+    tls::{TlsError, TlsSettings, serve_tls},
+};
+use services::services::{container::ContainerService, server_config};
 use sqlx::Error as SqlxError;
 use strip_ansi_escapes::strip;
 use thiserror::Error;
@@ -11,8 +18,11 @@ use utils::{
     browser::open_browser,
     port_file::write_port_file,
     sentry::{self as sentry_utils, SentrySource, sentry_layer},
+    telemetry::{self, otel_layer},
 };
 
+const DEFAULT_SHUTDOWN_DRAIN: Duration = Duration::from_secs(30);
+
 #[derive(Debug, Error)]
 pub enum VibeKanbanError {
     #[error(transparent)]
@@ -22,6 +32,8 @@ pub enum VibeKanbanError {
     #[error(transparent)]
     Deployment(#[from] DeploymentError),
     #[error(transparent)]
+    Tls(#[from] TlsError),
+    #[error(transparent)]
     Other(#[from] AnyhowError),
 }
 
@@ -38,6 +50,7 @@ async fn main() -> Result<(), VibeKanbanError> {
     tracing_subscriber::registry()
         .with(tracing_subscriber::fmt::layer().with_filter(env_filter))
         .with(sentry_layer())
+        .with(otel_layer())
         .init();
 
     // Create asset directory if it doesn't exist
@@ -63,6 +76,12 @@ async fn main() -> Result<(), VibeKanbanError> {
         .await
         .map_err(DeploymentError::from)?;
     deployment.spawn_pr_monitor_service().await;
+    deployment.spawn_backup_scheduler().await;
+    deployment.spawn_maintenance_scheduler();
+    deployment.spawn_review_sla_monitor().await;
+    deployment.spawn_stale_task_monitor().await;
+    deployment.spawn_github_issue_sync().await;
+    deployment.spawn_outbox_worker().await;
     deployment
         .track_if_analytics_allowed("session_start", serde_json::json!({}))
         .await;
@@ -88,11 +107,25 @@ async fn main() -> Result<(), VibeKanbanError> {
         }
     });
 
-    let app_router = routes::router(deployment.clone());
+    let tls_acceptor = TlsSettings::from_env()?
+        .map(|settings| settings.acceptor())
+        .transpose()?;
+    let proxy_settings = ProxySettings::from_env(tls_acceptor.is_some());
+    if proxy_settings.trust_forwarded_headers {
+        tracing::info!("Trusting X-Forwarded-* headers from reverse proxy");
+    }
+    let request_limits = RequestLimits::from_env();
+    if request_limits.rate_limiting_enabled() {
+        tracing::info!(
+            "Rate limiting API requests to {}/min (burst {})",
+            request_limits.requests_per_minute,
+            request_limits.burst
+        );
+    }
+    let app_router = routes::router(deployment.clone(), proxy_settings, request_limits);
 
-    let port = std::env::var("BACKEND_PORT")
-        .or_else(|_| std::env::var("PORT"))
-        .ok()
+    let port = server_config::setting("BACKEND_PORT")
+        .or_else(|| server_config::setting("PORT"))
         .and_then(|s| {
             // remove any ANSI codes, then turn into String
             let cleaned =
@@ -104,7 +137,7 @@ async fn main() -> Result<(), VibeKanbanError> {
             0
         }); // Use 0 to find free port if no specific port provided
 
-    let host = std::env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    let host = server_config::setting("HOST").unwrap_or_else(|| "127.0.0.1".to_string());
     let listener = tokio::net::TcpListener::bind(format!("{host}:{port}")).await?;
     let actual_port = listener.local_addr()?.port(); // get → 53427 (example)
 
@@ -113,26 +146,56 @@ async fn main() -> Result<(), VibeKanbanError> {
         tracing::warn!("Failed to write port file: {}", e);
     }
 
-    tracing::info!("Server running on http://{host}:{actual_port}");
+    let scheme = if tls_acceptor.is_some() {
+        "https"
+    } else {
+        "http"
+    };
+    tracing::info!("Server running on {scheme}://{host}:{actual_port}");
 
     if !cfg!(debug_assertions) {
         tracing::info!("Opening browser...");
         tokio::spawn(async move {
-            if let Err(e) = open_browser(&format!("http://127.0.0.1:{actual_port}")).await {
+            if let Err(e) = open_browser(&format!("{scheme}://127.0.0.1:{actual_port}")).await {
                 tracing::warn!(
-                    "Failed to open browser automatically: {}. Please open http://127.0.0.1:{} manually.",
+                    "Failed to open browser automatically: {}. Please open {}://127.0.0.1:{} manually.",
                     e,
+                    scheme,
                     actual_port
                 );
             }
         });
     }
 
-    axum::serve(listener, app_router)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    // Drain while the server still answers requests, so clients see executions wind down and
+    // new ones are refused
+    let drain_then_shutdown = {
+        let deployment = deployment.clone();
+        async move {
+            shutdown_signal().await;
+            tokio::select! {
+                _ = drain_executions(&deployment) => {},
+                _ = shutdown_signal() => tracing::warn!("Second shutdown signal, skipping drain"),
+            }
+        }
+    };
+
+    match tls_acceptor {
+        Some(acceptor) => {
+            serve_tls(listener, acceptor, app_router, drain_then_shutdown).await?;
+        }
+        None => {
+            axum::serve(
+                listener,
+                app_router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .with_graceful_shutdown(drain_then_shutdown)
+            .await?;
+        }
+    }
 
     perform_cleanup_actions(&deployment).await;
+    telemetry::shutdown();
 
     Ok(())
 }
@@ -173,6 +236,21 @@ pub async fn shutdown_signal() {
     }
 }
 
+/// Stop starting executions and give running coding agents time to checkpoint and exit.
+pub async fn drain_executions(deployment: &DeploymentImpl) {
+    let timeout = server_config::setting("VK_SHUTDOWN_DRAIN_SECS")
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_SHUTDOWN_DRAIN);
+    tracing::info!(
+        "Shutting down, draining running executions for up to {}s",
+        timeout.as_secs()
+    );
+    if let Err(e) = deployment.container().drain(timeout).await {
+        tracing::error!("Failed to drain running executions: {}", e);
+    }
+}
+
 pub async fn perform_cleanup_actions(deployment: &DeploymentImpl) {
     deployment
         .container()
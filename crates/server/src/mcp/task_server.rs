@@ -1,6 +1,7 @@
 use std::{future::Future, str::FromStr};
 
 use db::models::{
+    execution_process::ExecutionProcess,
     project::Project,
     repo::Repo,
     tag::Tag,
@@ -19,6 +20,10 @@ use rmcp::{
 };
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json;
+use services::services::code_search::{CodeSearchMatch, CodeSearchResult};
+use utils::user_questions::{
+    QuestionAnswer, UserQuestion, UserQuestionRequest, UserQuestionResponse,
+};
 use uuid::Uuid;
 
 use crate::routes::{
@@ -96,7 +101,7 @@ pub struct ListTasksRequest {
     #[schemars(description = "The ID of the project to list tasks from")]
     pub project_id: Uuid,
     #[schemars(
-        description = "Optional status filter: 'todo', 'inprogress', 'inreview', 'done', 'cancelled'"
+        description = "Optional status filter: 'todo', 'inprogress', 'blocked', 'inreview', 'done', 'cancelled'"
     )]
     pub status: Option<String>,
     #[schemars(description = "Maximum number of tasks to return (default: 50)")]
@@ -192,7 +197,9 @@ pub struct UpdateTaskRequest {
     pub title: Option<String>,
     #[schemars(description = "New description for the task")]
     pub description: Option<String>,
-    #[schemars(description = "New status: 'todo', 'inprogress', 'inreview', 'done', 'cancelled'")]
+    #[schemars(
+        description = "New status: 'todo', 'inprogress', 'blocked', 'inreview', 'done', 'cancelled'"
+    )]
     pub status: Option<String>,
 }
 
@@ -227,6 +234,14 @@ pub struct StartWorkspaceSessionRequest {
     pub variant: Option<String>,
     #[schemars(description = "Base branch for each repository in the project")]
     pub repos: Vec<McpWorkspaceRepoInput>,
+    #[schemars(
+        description = "Investigate only: the agent may read the codebase but not edit files or run commands that write. Supported by CLAUDE_CODE and CODEX"
+    )]
+    pub read_only: Option<bool>,
+    #[schemars(
+        description = "Dry run: edits and commands that may write are logged with what would run instead of being executed, so the plan can be reviewed first. Supported by CLAUDE_CODE and CODEX"
+    )]
+    pub dry_run: Option<bool>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -251,10 +266,206 @@ pub struct GetTaskResponse {
     pub task: TaskDetails,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SearchCodeRequest {
+    #[schemars(
+        description = "The ID of the project whose repositories to search. Defaults to the project of the current workspace session"
+    )]
+    pub project_id: Option<Uuid>,
+    #[schemars(
+        description = "Words to look for, or a description of the code you need, e.g. 'where are webhook signatures verified'"
+    )]
+    pub query: String,
+    #[schemars(
+        description = "'keyword' for exact words, 'semantic' for similar meaning, or 'all' (default)"
+    )]
+    pub mode: Option<String>,
+    #[schemars(description = "Maximum number of results to return (default: 20)")]
+    pub limit: Option<i32>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct CodeSearchMatchSummary {
+    pub repo_name: String,
+    #[schemars(description = "Path relative to the repository root")]
+    pub file: String,
+    #[schemars(description = "First line of the snippet, 1-based")]
+    pub start_line: usize,
+    #[schemars(description = "Last line of the snippet, inclusive")]
+    pub end_line: usize,
+    #[schemars(description = "Public symbol enclosing the snippet, if any")]
+    pub symbol: Option<String>,
+    #[schemars(description = "'keyword' or 'semantic'")]
+    pub match_type: String,
+    pub score: f32,
+    pub snippet: String,
+}
+
+impl CodeSearchMatchSummary {
+    fn from_result(result: CodeSearchResult) -> Self {
+        Self {
+            repo_name: result.repo_name,
+            file: result.file,
+            start_line: result.start_line,
+            end_line: result.end_line,
+            symbol: result.symbol,
+            match_type: match result.match_type {
+                CodeSearchMatch::Keyword => "keyword",
+                CodeSearchMatch::Semantic => "semantic",
+            }
+            .to_string(),
+            score: result.score,
+            snippet: result.snippet,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct SearchCodeResponse {
+    pub matches: Vec<CodeSearchMatchSummary>,
+    pub count: usize,
+    pub project_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetAttemptStatusRequest {
+    #[schemars(description = "The workspace ID returned by `start_workspace_session`")]
+    pub workspace_id: Uuid,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ExecutionProcessSummary {
+    #[schemars(description = "The unique identifier of the execution process")]
+    pub id: String,
+    #[schemars(
+        description = "Why the process ran: 'setupscript', 'codingagent', 'cleanupscript', 'devserver'"
+    )]
+    pub run_reason: String,
+    #[schemars(description = "Process status: 'running', 'completed', 'failed', 'killed'")]
+    pub status: String,
+    #[schemars(description = "Exit code, once the process has finished")]
+    pub exit_code: Option<i64>,
+    #[schemars(description = "When the process started")]
+    pub started_at: String,
+    #[schemars(description = "When the process finished")]
+    pub completed_at: Option<String>,
+}
+
+impl ExecutionProcessSummary {
+    fn from_process(process: &ExecutionProcess) -> Self {
+        Self {
+            id: process.id.to_string(),
+            run_reason: enum_name(&process.run_reason),
+            status: enum_name(&process.status),
+            exit_code: process.exit_code,
+            started_at: process.started_at.to_rfc3339(),
+            completed_at: process.completed_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct GetAttemptStatusResponse {
+    pub workspace_id: String,
+    pub task_id: String,
+    pub branch: String,
+    #[schemars(
+        description = "Status of the most recent process, or 'not_started' if nothing has run yet"
+    )]
+    pub status: String,
+    pub latest_process: Option<ExecutionProcessSummary>,
+    #[schemars(
+        description = "IDs of questions the agent is waiting on; answer with `answer_question`"
+    )]
+    pub pending_question_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListPendingQuestionsRequest {
+    #[schemars(description = "Only return questions asked in this workspace")]
+    pub workspace_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpQuestionOption {
+    pub label: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpQuestion {
+    pub question: String,
+    pub header: Option<String>,
+    #[schemars(description = "Available options; answer with their indices")]
+    pub options: Vec<McpQuestionOption>,
+    #[schemars(description = "Whether more than one option may be selected")]
+    pub multi_select: bool,
+}
+
+impl McpQuestion {
+    fn from_question(question: UserQuestion) -> Self {
+        Self {
+            question: question.question,
+            header: question.header,
+            options: question
+                .options
+                .into_iter()
+                .map(|o| McpQuestionOption {
+                    label: o.label,
+                    description: o.description,
+                })
+                .collect(),
+            multi_select: question.multi_select,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct PendingQuestionSummary {
+    #[schemars(description = "The question ID to pass to `answer_question`")]
+    pub question_id: String,
+    pub execution_process_id: String,
+    pub questions: Vec<McpQuestion>,
+    #[schemars(description = "When the question times out and the agent stops waiting")]
+    pub timeout_at: String,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct ListPendingQuestionsResponse {
+    pub questions: Vec<PendingQuestionSummary>,
+    pub count: usize,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct McpQuestionAnswer {
+    #[schemars(description = "Index of the question being answered (0-based)")]
+    pub question_index: usize,
+    #[schemars(description = "Indices of the chosen options (0-based)")]
+    #[serde(default)]
+    pub selected_options: Vec<usize>,
+    #[schemars(description = "Free-form answer, used instead of or alongside the options")]
+    pub custom_text: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct AnswerQuestionRequest {
+    #[schemars(description = "The question ID from `list_pending_questions`")]
+    pub question_id: String,
+    #[schemars(description = "One answer per question")]
+    pub answers: Vec<McpQuestionAnswer>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct AnswerQuestionResponse {
+    pub question_id: String,
+    pub answer_count: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct TaskServer {
     client: reqwest::Client,
     base_url: String,
+    token: Option<String>,
     tool_router: ToolRouter<TaskServer>,
     context: Option<McpContext>,
 }
@@ -283,10 +494,11 @@ pub struct McpContext {
 }
 
 impl TaskServer {
-    pub fn new(base_url: &str) -> Self {
+    pub fn new(base_url: &str, token: Option<String>) -> Self {
         Self {
             client: reqwest::Client::new(),
             base_url: base_url.to_string(),
+            token,
             tool_router: Self::tool_router(),
             context: None,
         }
@@ -318,7 +530,7 @@ impl TaskServer {
 
         let response = tokio::time::timeout(
             std::time::Duration::from_millis(500),
-            self.client.get(&url).query(&query).send(),
+            self.request(self.client.get(&url).query(&query)).send(),
         )
         .await
         .ok()?
@@ -358,6 +570,14 @@ impl TaskServer {
     }
 }
 
+/// Serialized name of a unit enum variant, e.g. `ExecutionProcessStatus::Running` -> "running".
+fn enum_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
 #[derive(Debug, Deserialize)]
 struct ApiResponseEnvelope<T> {
     success: bool,
@@ -392,10 +612,10 @@ impl TaskServer {
         &self,
         rb: reqwest::RequestBuilder,
     ) -> Result<T, CallToolResult> {
-        let resp = rb
-            .send()
-            .await
-            .map_err(|e| Self::err("Failed to connect to VK API", Some(&e.to_string())).unwrap())?;
+        let resp =
+            self.request(rb).send().await.map_err(|e| {
+                Self::err("Failed to connect to VK API", Some(&e.to_string())).unwrap()
+            })?;
 
         if !resp.status().is_success() {
             let status = resp.status();
@@ -418,6 +638,14 @@ impl TaskServer {
             .ok_or_else(|| Self::err("VK API response missing data field", None).unwrap())
     }
 
+    /// Authenticate as the configured account when the backend runs in multi-user mode
+    fn request(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+
     fn url(&self, path: &str) -> String {
         format!(
             "{}/{}",
@@ -426,6 +654,22 @@ impl TaskServer {
         )
     }
 
+    async fn pending_questions(&self) -> Result<Vec<UserQuestionRequest>, CallToolResult> {
+        self.send_json(self.client.get(self.url("/api/questions")))
+            .await
+    }
+
+    async fn workspace_processes(
+        &self,
+        workspace_id: Uuid,
+    ) -> Result<Vec<ExecutionProcess>, CallToolResult> {
+        let url = self.url(&format!(
+            "/api/execution-processes?workspace_id={}",
+            workspace_id
+        ));
+        self.send_json(self.client.get(&url)).await
+    }
+
     /// Expands @tagname references in text by replacing them with tag content.
     /// Returns the original text if expansion fails (e.g., network error).
     /// Unknown tags are left as-is (not expanded, not an error).
@@ -450,7 +694,7 @@ impl TaskServer {
 
         // Fetch all tags from the API
         let url = self.url("/api/tags");
-        let tags: Vec<Tag> = match self.client.get(&url).send().await {
+        let tags: Vec<Tag> = match self.request(self.client.get(&url)).send().await {
             Ok(resp) if resp.status().is_success() => {
                 match resp.json::<ApiResponseEnvelope<Vec<Tag>>>().await {
                     Ok(envelope) if envelope.success => envelope.data.unwrap_or_default(),
@@ -596,7 +840,7 @@ impl TaskServer {
                 Ok(s) => Some(s),
                 Err(_) => {
                     return Self::err(
-                        "Invalid status filter. Valid values: 'todo', 'inprogress', 'inreview', 'done', 'cancelled'".to_string(),
+                        "Invalid status filter. Valid values: 'todo', 'inprogress', 'blocked', 'inreview', 'done', 'cancelled'".to_string(),
                         Some(status_str.to_string()),
                     );
                 }
@@ -650,6 +894,8 @@ impl TaskServer {
             executor,
             variant,
             repos,
+            read_only,
+            dry_run,
         }): Parameters<StartWorkspaceSessionRequest>,
     ) -> Result<CallToolResult, ErrorData> {
         if repos.is_empty() {
@@ -701,6 +947,10 @@ impl TaskServer {
             task_id,
             executor_profile_id,
             repos: workspace_repos,
+            read_only,
+            dry_run,
+            permission_mode: None,
+            prompt_template_ids: None,
         };
 
         let url = self.url("/api/task-attempts");
@@ -718,6 +968,165 @@ impl TaskServer {
         TaskServer::success(&response)
     }
 
+    #[tool(
+        description = "Get the status of a workspace session started with `start_workspace_session`: the latest execution process and any questions the agent is waiting on. `workspace_id` is required!"
+    )]
+    async fn get_attempt_status(
+        &self,
+        Parameters(GetAttemptStatusRequest { workspace_id }): Parameters<GetAttemptStatusRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let url = self.url(&format!("/api/task-attempts/{}", workspace_id));
+        let workspace: Workspace = match self.send_json(self.client.get(&url)).await {
+            Ok(w) => w,
+            Err(e) => return Ok(e),
+        };
+        let processes = match self.workspace_processes(workspace_id).await {
+            Ok(ps) => ps,
+            Err(e) => return Ok(e),
+        };
+        let pending = match self.pending_questions().await {
+            Ok(qs) => qs,
+            Err(e) => return Ok(e),
+        };
+
+        let latest_process = processes
+            .iter()
+            .filter(|p| !p.dropped)
+            .max_by_key(|p| p.started_at)
+            .map(ExecutionProcessSummary::from_process);
+        let status = latest_process
+            .as_ref()
+            .map(|p| p.status.clone())
+            .unwrap_or_else(|| "not_started".to_string());
+        let pending_question_ids = pending
+            .into_iter()
+            .filter(|q| processes.iter().any(|p| p.id == q.execution_process_id))
+            .map(|q| q.id)
+            .collect();
+
+        let response = GetAttemptStatusResponse {
+            workspace_id: workspace.id.to_string(),
+            task_id: workspace.task_id.to_string(),
+            branch: workspace.branch,
+            status,
+            latest_process,
+            pending_question_ids,
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "List questions coding agents are waiting on. Pass `workspace_id` to only see questions from one workspace session."
+    )]
+    async fn list_pending_questions(
+        &self,
+        Parameters(ListPendingQuestionsRequest { workspace_id }): Parameters<
+            ListPendingQuestionsRequest,
+        >,
+    ) -> Result<CallToolResult, ErrorData> {
+        let mut pending = match self.pending_questions().await {
+            Ok(qs) => qs,
+            Err(e) => return Ok(e),
+        };
+
+        if let Some(workspace_id) = workspace_id {
+            let processes = match self.workspace_processes(workspace_id).await {
+                Ok(ps) => ps,
+                Err(e) => return Ok(e),
+            };
+            pending.retain(|q| processes.iter().any(|p| p.id == q.execution_process_id));
+        }
+
+        let questions: Vec<PendingQuestionSummary> = pending
+            .into_iter()
+            .map(|q| PendingQuestionSummary {
+                question_id: q.id,
+                execution_process_id: q.execution_process_id.to_string(),
+                questions: q
+                    .questions
+                    .into_iter()
+                    .map(McpQuestion::from_question)
+                    .collect(),
+                timeout_at: q.timeout_at.to_rfc3339(),
+            })
+            .collect();
+
+        let response = ListPendingQuestionsResponse {
+            count: questions.len(),
+            questions,
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Answer a question a coding agent is waiting on. Use `list_pending_questions` to find the `question_id` and the available options. `question_id` and `answers` are required!"
+    )]
+    async fn answer_question(
+        &self,
+        Parameters(AnswerQuestionRequest {
+            question_id,
+            answers,
+        }): Parameters<AnswerQuestionRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if answers.is_empty() {
+            return Self::err(
+                "At least one answer is required.".to_string(),
+                None::<String>,
+            );
+        }
+
+        let pending = match self.pending_questions().await {
+            Ok(qs) => qs,
+            Err(e) => return Ok(e),
+        };
+        let Some(question) = pending.into_iter().find(|q| q.id == question_id) else {
+            return Self::err(
+                "Question not found or already answered.".to_string(),
+                Some(question_id),
+            );
+        };
+
+        let payload = UserQuestionResponse {
+            execution_process_id: question.execution_process_id,
+            answers: answers
+                .into_iter()
+                .map(|a| QuestionAnswer {
+                    question_index: a.question_index,
+                    selected_options: a.selected_options,
+                    custom_text: a.custom_text,
+                    attachments: None,
+                })
+                .collect(),
+            amendable_until: None,
+        };
+
+        // The respond endpoint returns the stored response directly rather than an ApiResponse
+        let url = self.url(&format!("/api/questions/{}/respond", question_id));
+        let resp = match self
+            .request(self.client.post(&url).json(&payload))
+            .send()
+            .await
+        {
+            Ok(resp) => resp,
+            Err(e) => return Self::err("Failed to connect to VK API", Some(&e.to_string())),
+        };
+        if !resp.status().is_success() {
+            return Self::err(
+                format!("VK API returned error status: {}", resp.status()),
+                None,
+            );
+        }
+
+        let response = AnswerQuestionResponse {
+            question_id,
+            answer_count: payload.answers.len(),
+        };
+
+        TaskServer::success(&response)
+    }
+
     #[tool(
         description = "Update an existing task/ticket's title, description, or status. `project_id` and `task_id` are required! `title`, `description`, and `status` are optional."
     )]
@@ -735,7 +1144,7 @@ impl TaskServer {
                 Ok(s) => Some(s),
                 Err(_) => {
                     return Self::err(
-                        "Invalid status filter. Valid values: 'todo', 'inprogress', 'inreview', 'done', 'cancelled'".to_string(),
+                        "Invalid status filter. Valid values: 'todo', 'inprogress', 'blocked', 'inreview', 'done', 'cancelled'".to_string(),
                         Some(status_str.to_string()),
                     );
                 }
@@ -808,12 +1217,65 @@ impl TaskServer {
 
         TaskServer::success(&response)
     }
+
+    #[tool(
+        description = "Search the code of a project's repositories by keyword and by meaning. Returns file paths, line spans and snippets. Use it before exploring the tree by hand. `project_id` defaults to the current workspace's project."
+    )]
+    async fn search_code(
+        &self,
+        Parameters(SearchCodeRequest {
+            project_id,
+            query,
+            mode,
+            limit,
+        }): Parameters<SearchCodeRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let Some(project_id) = project_id.or(self.context.as_ref().map(|c| c.project_id)) else {
+            return Self::err(
+                "`project_id` is required outside of a workspace session".to_string(),
+                None,
+            );
+        };
+        let mode = mode.unwrap_or_else(|| "all".to_string());
+        if !matches!(mode.as_str(), "all" | "keyword" | "semantic") {
+            return Self::err(
+                "Invalid mode. Valid values: 'all', 'keyword', 'semantic'".to_string(),
+                Some(mode),
+            );
+        }
+
+        let url = self.url(&format!("/api/projects/{}/code-search", project_id));
+        let limit = limit.unwrap_or(20).max(1).to_string();
+        let results: Vec<CodeSearchResult> = match self
+            .send_json(self.client.get(&url).query(&[
+                ("q", query.as_str()),
+                ("mode", mode.as_str()),
+                ("limit", limit.as_str()),
+            ]))
+            .await
+        {
+            Ok(r) => r,
+            Err(e) => return Ok(e),
+        };
+
+        let matches: Vec<CodeSearchMatchSummary> = results
+            .into_iter()
+            .map(CodeSearchMatchSummary::from_result)
+            .collect();
+        let response = SearchCodeResponse {
+            count: matches.len(),
+            matches,
+            project_id: project_id.to_string(),
+        };
+
+        TaskServer::success(&response)
+    }
 }
 
 #[tool_handler]
 impl ServerHandler for TaskServer {
     fn get_info(&self) -> ServerInfo {
-        let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project`.. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_workspace_session', 'get_task', 'update_task', 'delete_task', 'list_repos'. Make sure to pass `project_id` or `task_id` where required. You can use list tools to get the available ids.".to_string();
+        let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project`.. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_workspace_session', 'get_attempt_status', 'list_pending_questions', 'answer_question', 'get_task', 'update_task', 'delete_task', 'list_repos', 'search_code'. Make sure to pass `project_id` or `task_id` where required. You can use list tools to get the available ids.".to_string();
         if self.context.is_some() {
             let context_instruction = "Use 'get_context' to fetch project/task/workspace metadata for the active Vibe Kanban workspace session when available.";
             instruction = format!("{} {}", context_instruction, instruction);
@@ -0,0 +1,226 @@
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, Method, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use db::models::{
+    api_token::ApiToken,
+    execution_process::ExecutionProcess,
+    user::{Permission, User},
+};
+use deployment::Deployment;
+use services::services::accounts::{SESSION_COOKIE_NAME, is_api_token};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// The authenticated user for a request. Always `None` in single-user mode.
+#[derive(Debug, Clone)]
+pub struct CurrentUser(pub Option<User>);
+
+/// Routes reachable without a session so users can sign in.
+const PUBLIC_PATHS: &[&str] = &[
+    "/health",
+    "/accounts/status",
+    "/accounts/setup",
+    "/accounts/login",
+    "/accounts/oidc/login",
+    "/accounts/oidc/callback",
+];
+
+/// Route prefixes that authenticate with their own secret or signed token instead of a session.
+const PUBLIC_PREFIXES: &[&str] = &["/hooks/", "/public/"];
+
+/// Extract the session token from the `vk_session` cookie or a bearer token.
+pub fn session_token(headers: &HeaderMap) -> Option<String> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|v| v.trim().to_string());
+    if bearer.is_some() {
+        return bearer;
+    }
+
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, _)| *name == SESSION_COOKIE_NAME)
+        .map(|(_, value)| value.to_string())
+}
+
+/// Minimum permission needed for an API request. Paths are relative to `/api`.
+pub fn required_permission(method: &Method, path: &str) -> Permission {
+    let is_read = matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS);
+
+    // Global secrets are provider credentials shared by every project
+    if path.starts_with("/users")
+        || path.starts_with("/admin")
+        || path.starts_with("/secrets")
+        || path.starts_with("/api-tokens")
+    {
+        return Permission::Admin;
+    }
+    if path.starts_with("/accounts/") {
+        return Permission::Read;
+    }
+    if is_read {
+        return Permission::Read;
+    }
+    // Answering and claiming agent questions is review work, like resolving approvals
+    if path.starts_with("/approvals/") || path.starts_with("/questions/") {
+        return Permission::Review;
+    }
+    // Server-wide settings and the remote account link affect every user
+    if path == "/config"
+        || path == "/profiles"
+        || path == "/mcp-config"
+        || path.starts_with("/auth/")
+    {
+        return Permission::Admin;
+    }
+    Permission::Write
+}
+
+/// Whether an automation API token may make the request: listing and answering questions and
+/// approvals, nothing else. Paths are relative to `/api`.
+pub fn api_token_allows(method: &Method, path: &str) -> bool {
+    let segments: Vec<_> = path.trim_matches('/').split('/').collect();
+    matches!(
+        (method, segments.as_slice()),
+        (&Method::GET, ["questions" | "approvals"])
+            | (&Method::POST, ["questions" | "approvals", _, "respond"])
+    )
+}
+
+/// Whether the request may act on the execution process: always for sessions, and for API
+/// tokens only when it belongs to one of the token's projects.
+pub async fn token_covers(
+    deployment: &DeploymentImpl,
+    api_token: Option<&ApiToken>,
+    execution_process_id: Uuid,
+) -> bool {
+    let Some(api_token) = api_token else {
+        return true;
+    };
+    ExecutionProcess::load_context(&deployment.db().pool, execution_process_id)
+        .await
+        .is_ok_and(|ctx| api_token.covers_project(ctx.project.id))
+}
+
+/// Authenticate an automation API token, which works the same with and without multi-user mode.
+async fn require_api_token(
+    deployment: &DeploymentImpl,
+    token: &str,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let api_token = match deployment
+        .accounts()
+        .authenticate_api_token(&deployment.db().pool, token)
+        .await
+    {
+        Ok(Some(api_token)) => api_token,
+        Ok(None) => return ApiError::Unauthorized.into_response(),
+        Err(e) => return ApiError::from(e).into_response(),
+    };
+    if !api_token_allows(request.method(), request.uri().path()) {
+        return ApiError::Forbidden(
+            "API tokens can only list and answer questions and approvals".to_string(),
+        )
+        .into_response();
+    }
+
+    request.extensions_mut().insert(CurrentUser(None));
+    request.extensions_mut().insert(api_token);
+    next.run(request).await
+}
+
+/// Authenticate the request and enforce role permissions when multi-user mode is enabled.
+pub async fn require_account(
+    State(deployment): State<DeploymentImpl>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if let Some(token) = session_token(request.headers()).filter(|token| is_api_token(token)) {
+        return require_api_token(&deployment, &token, request, next).await;
+    }
+
+    let accounts = deployment.accounts();
+    if !accounts.enabled() {
+        request.extensions_mut().insert(CurrentUser(None));
+        return next.run(request).await;
+    }
+
+    let path = request.uri().path().to_string();
+    if PUBLIC_PATHS.contains(&path.as_str())
+        || PUBLIC_PREFIXES
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+    {
+        request.extensions_mut().insert(CurrentUser(None));
+        return next.run(request).await;
+    }
+
+    let Some(token) = session_token(request.headers()) else {
+        return ApiError::Unauthorized.into_response();
+    };
+    let user = match accounts.authenticate(&deployment.db().pool, &token).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return ApiError::Unauthorized.into_response(),
+        Err(e) => return ApiError::from(e).into_response(),
+    };
+
+    let permission = required_permission(request.method(), &path);
+    if !user.can(permission) {
+        tracing::debug!(
+            user = %user.username,
+            role = %user.role,
+            ?permission,
+            "Rejected {} {}",
+            request.method(),
+            path
+        );
+        return ApiError::Forbidden(format!(
+            "Your role ({}) does not allow this action",
+            user.role
+        ))
+        .into_response();
+    }
+
+    request.extensions_mut().insert(CurrentUser(Some(user)));
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_routes_to_permissions() {
+        let cases = [
+            (Method::GET, "/projects", Permission::Read),
+            (Method::GET, "/users", Permission::Admin),
+            (Method::POST, "/accounts/login", Permission::Read),
+            (Method::POST, "/projects", Permission::Write),
+            (Method::POST, "/approvals/abc/respond", Permission::Review),
+            (Method::POST, "/questions/abc/respond", Permission::Review),
+            (Method::PUT, "/questions/abc/respond", Permission::Review),
+            (Method::DELETE, "/questions/abc/respond", Permission::Review),
+            (Method::POST, "/questions/abc/claim", Permission::Review),
+            (Method::DELETE, "/questions/abc/claim", Permission::Review),
+            (Method::PUT, "/config", Permission::Admin),
+        ];
+        for (method, path, permission) in cases {
+            assert_eq!(
+                required_permission(&method, path),
+                permission,
+                "{method} {path}"
+            );
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use std::net::{IpAddr, SocketAddr};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, header},
+    middleware::Next,
+    response::Response,
+};
+
+/// How the server is exposed, used to work out the externally visible origin of a request.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProxySettings {
+    /// Honour `X-Forwarded-*` headers. Only enable behind a trusted reverse proxy.
+    pub trust_forwarded_headers: bool,
+    /// The server terminates TLS itself.
+    pub tls: bool,
+}
+
+impl ProxySettings {
+    pub fn from_env(tls: bool) -> Self {
+        let trust_forwarded_headers = matches!(
+            std::env::var("VK_TRUST_PROXY").as_deref(),
+            Ok("1" | "true" | "TRUE" | "yes")
+        );
+        Self {
+            trust_forwarded_headers,
+            tls,
+        }
+    }
+}
+
+/// The origin a client used to reach the server, accounting for reverse proxies.
+#[derive(Debug, Clone)]
+pub struct RequestOrigin {
+    pub scheme: String,
+    pub host: Option<String>,
+    /// Path prefix the proxy mounts the app under (from `X-Forwarded-Prefix`), without trailing slash
+    pub prefix: String,
+    pub client_ip: Option<IpAddr>,
+}
+
+impl RequestOrigin {
+    /// Absolute base URL (`scheme://host/prefix`) if the host is known.
+    pub fn base_url(&self) -> Option<String> {
+        self.host
+            .as_ref()
+            .map(|host| format!("{}://{}{}", self.scheme, host, self.prefix))
+    }
+
+    pub(crate) fn from_parts(
+        headers: &HeaderMap,
+        peer: Option<SocketAddr>,
+        settings: ProxySettings,
+    ) -> Self {
+        let header_str = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                // Each hop appends to these lists, so only the last entry comes from our own proxy;
+                // anything before it was supplied by the client and can be spoofed
+                .and_then(|v| v.rsplit(',').next())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        let default_scheme = if settings.tls { "https" } else { "http" };
+        let host_header = header_str(header::HOST.as_str());
+
+        if !settings.trust_forwarded_headers {
+            return Self {
+                scheme: default_scheme.to_string(),
+                host: host_header,
+                prefix: String::new(),
+                client_ip: peer.map(|addr| addr.ip()),
+            };
+        }
+
+        let scheme = header_str("x-forwarded-proto")
+            .filter(|proto| proto == "http" || proto == "https")
+            .unwrap_or_else(|| default_scheme.to_string());
+        let host = match (
+            header_str("x-forwarded-host"),
+            header_str("x-forwarded-port"),
+        ) {
+            (Some(host), _) => Some(host),
+            (None, Some(port)) => host_header.map(|h| {
+                let bare = h.rsplit_once(':').map_or(h.as_str(), |(bare, _)| bare);
+                format!("{bare}:{port}")
+            }),
+            (None, None) => host_header,
+        };
+        let prefix = header_str("x-forwarded-prefix")
+            .map(|p| format!("/{}", p.trim_matches('/')))
+            .filter(|p| p != "/")
+            .unwrap_or_default();
+        let client_ip = header_str("x-forwarded-for")
+            .and_then(|ip| ip.parse().ok())
+            .or_else(|| peer.map(|addr| addr.ip()));
+
+        Self {
+            scheme,
+            host,
+            prefix,
+            client_ip,
+        }
+    }
+}
+
+/// Attach a [`RequestOrigin`] extension to every request.
+pub async fn resolve_request_origin(
+    State(settings): State<ProxySettings>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|info| info.0);
+    let origin = RequestOrigin::from_parts(request.headers(), peer, settings);
+    request.extensions_mut().insert(origin);
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn trusted() -> ProxySettings {
+        ProxySettings {
+            trust_forwarded_headers: true,
+            tls: false,
+        }
+    }
+
+    #[test]
+    fn client_ip_uses_the_proxy_appended_forwarded_for_entry() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("6.6.6.6, 10.0.0.9, 203.0.113.7"),
+        );
+        let peer = Some("127.0.0.1:4000".parse().unwrap());
+
+        let origin = RequestOrigin::from_parts(&headers, peer, trusted());
+
+        assert_eq!(origin.client_ip, Some("203.0.113.7".parse().unwrap()));
+    }
+
+    #[test]
+    fn forwarded_for_is_ignored_without_a_trusted_proxy() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", HeaderValue::from_static("6.6.6.6"));
+        let peer = Some("127.0.0.1:4000".parse().unwrap());
+
+        let origin = RequestOrigin::from_parts(&headers, peer, ProxySettings::default());
+
+        assert_eq!(origin.client_ip, Some("127.0.0.1".parse().unwrap()));
+    }
+}
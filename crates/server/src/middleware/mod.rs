@@ -1,3 +1,9 @@
+pub mod auth;
+pub mod forwarded;
 pub mod model_loaders;
+pub mod rate_limit;
 
+pub use auth::*;
+pub use forwarded::*;
 pub use model_loaders::*;
+pub use rate_limit::*;
@@ -0,0 +1,283 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    Json,
+    extract::{DefaultBodyLimit, Request, State},
+    http::{StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use db::models::api_token::ApiToken;
+use services::services::server_config;
+use utils::response::ApiResponse;
+
+use super::{CurrentUser, RequestOrigin};
+
+const DEFAULT_MAX_BODY_BYTES: usize = 2 * 1024 * 1024; // 2MB
+const DEFAULT_MAX_UPLOAD_BYTES: usize = 20 * 1024 * 1024; // 20MB
+/// Buckets idle for this long are dropped when the table is pruned
+const BUCKET_IDLE_TTL: Duration = Duration::from_secs(10 * 60);
+const PRUNE_THRESHOLD: usize = 10_000;
+
+fn env_number<T: std::str::FromStr>(name: &str) -> Option<T> {
+    server_config::setting(name).and_then(|v| v.trim().parse().ok())
+}
+
+/// Request rate and body size limits, configured from the environment or the `[limits]`
+/// section of `vibe-kanban.toml`.
+///
+/// - `VK_RATE_LIMIT_RPM`: sustained requests per minute per client IP and per signed-in account
+///   or API token (0 disables, the default)
+/// - `VK_RATE_LIMIT_BURST`: extra requests allowed in a burst (defaults to the per-minute rate)
+/// - `VK_MAX_BODY_BYTES`: maximum JSON request body size
+/// - `VK_MAX_UPLOAD_BYTES`: maximum body size for upload endpoints
+#[derive(Debug, Clone, Copy)]
+pub struct RequestLimits {
+    pub requests_per_minute: u32,
+    pub burst: u32,
+    pub max_body_bytes: usize,
+    pub max_upload_bytes: usize,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            requests_per_minute: 0,
+            burst: 0,
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            max_upload_bytes: DEFAULT_MAX_UPLOAD_BYTES,
+        }
+    }
+}
+
+impl RequestLimits {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let requests_per_minute = env_number("VK_RATE_LIMIT_RPM").unwrap_or(0);
+        Self {
+            requests_per_minute,
+            burst: env_number("VK_RATE_LIMIT_BURST").unwrap_or(requests_per_minute),
+            max_body_bytes: env_number("VK_MAX_BODY_BYTES").unwrap_or(defaults.max_body_bytes),
+            max_upload_bytes: env_number("VK_MAX_UPLOAD_BYTES")
+                .unwrap_or(defaults.max_upload_bytes),
+        }
+    }
+
+    pub fn rate_limiting_enabled(&self) -> bool {
+        self.requests_per_minute > 0
+    }
+}
+
+/// Body limit layer for upload endpoints (images, attachments, logs).
+pub fn upload_body_limit() -> DefaultBodyLimit {
+    DefaultBodyLimit::max(env_number("VK_MAX_UPLOAD_BYTES").unwrap_or(DEFAULT_MAX_UPLOAD_BYTES))
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// In-memory token bucket rate limiter keyed by client IP or authenticated caller.
+#[derive(Clone)]
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    buckets: Arc<Mutex<HashMap<String, Bucket>>>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: &RequestLimits) -> Self {
+        Self {
+            capacity: limits.burst.max(1) as f64,
+            refill_per_sec: limits.requests_per_minute as f64 / 60.0,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Take a token for `key`. Returns the time to wait before retrying when exhausted.
+    pub fn check(&self, key: &str) -> Result<(), Duration> {
+        self.check_at(key, Instant::now())
+    }
+
+    fn check_at(&self, key: &str, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        if buckets.len() > PRUNE_THRESHOLD {
+            buckets.retain(|_, bucket| now.duration_since(bucket.updated_at) < BUCKET_IDLE_TTL);
+        }
+
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: self.capacity,
+            updated_at: now,
+        });
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let wait = (1.0 - bucket.tokens) / self.refill_per_sec;
+            Err(Duration::from_secs_f64(wait))
+        }
+    }
+}
+
+/// Key on the resolved client address: the TCP peer, or behind a trusted proxy the address that
+/// proxy appended to `X-Forwarded-For`, never an entry the client could have written itself.
+fn client_key(request: &Request) -> Option<String> {
+    request
+        .extensions()
+        .get::<RequestOrigin>()
+        .and_then(|origin| origin.client_ip)
+        .map(|ip| format!("ip:{ip}"))
+}
+
+/// Key of the caller `require_account` validated, if any
+fn account_key(request: &Request) -> Option<String> {
+    if let Some(api_token) = request.extensions().get::<ApiToken>() {
+        return Some(format!("api-token:{}", api_token.id));
+    }
+    match request.extensions().get::<CurrentUser>() {
+        Some(CurrentUser(Some(user))) => Some(format!("user:{}", user.id)),
+        _ => None,
+    }
+}
+
+fn too_many_requests(key: &str, retry_after: Duration) -> Response {
+    let retry_after_secs = (retry_after.as_secs_f64().ceil() as u64).max(1);
+    tracing::debug!("Rate limited {} for {}s", key, retry_after_secs);
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        [(header::RETRY_AFTER, retry_after_secs.to_string())],
+        Json(ApiResponse::<()>::error(&format!(
+            "Too many requests. Please retry in {retry_after_secs} seconds."
+        ))),
+    )
+        .into_response()
+}
+
+/// Reject client IPs that exceed their request budget with `429 Too Many Requests`. Runs
+/// before authentication, so login attempts and requests with made-up tokens count too.
+pub async fn enforce_rate_limit(
+    State(limiter): State<RateLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(key) = client_key(&request) else {
+        return next.run(request).await;
+    };
+    match limiter.check(&key) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => too_many_requests(&key, retry_after),
+    }
+}
+
+/// Reject signed-in users and API tokens that exceed their own request budget. Runs after
+/// `require_account`, so only validated callers get a bucket.
+pub async fn enforce_account_rate_limit(
+    State(limiter): State<RateLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(key) = account_key(&request) else {
+        return next.run(request).await;
+    };
+    match limiter.check(&key) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => too_many_requests(&key, retry_after),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use axum::{body::Body, http::HeaderValue};
+
+    use super::*;
+    use crate::middleware::ProxySettings;
+
+    fn limiter(requests_per_minute: u32, burst: u32) -> RateLimiter {
+        RateLimiter::new(&RequestLimits {
+            requests_per_minute,
+            burst,
+            ..RequestLimits::default()
+        })
+    }
+
+    #[test]
+    fn buckets_refill_over_time() {
+        let limiter = limiter(60, 2);
+        let start = Instant::now();
+
+        assert!(limiter.check_at("ip:1", start).is_ok());
+        assert!(limiter.check_at("ip:1", start).is_ok());
+        let wait = limiter.check_at("ip:1", start).unwrap_err();
+        assert_eq!(wait, Duration::from_secs(1));
+
+        // Other clients have their own bucket
+        assert!(limiter.check_at("ip:2", start).is_ok());
+
+        let later = start + Duration::from_millis(500);
+        assert!(limiter.check_at("ip:1", later).is_err());
+        let later = start + Duration::from_secs(1);
+        assert!(limiter.check_at("ip:1", later).is_ok());
+        assert!(limiter.check_at("ip:1", later).is_err());
+
+        // Refills never exceed the burst capacity
+        let much_later = start + Duration::from_secs(60);
+        assert!(limiter.check_at("ip:1", much_later).is_ok());
+        assert!(limiter.check_at("ip:1", much_later).is_ok());
+        assert!(limiter.check_at("ip:1", much_later).is_err());
+    }
+
+    #[test]
+    fn limited_requests_get_429_with_retry_after() {
+        let response = too_many_requests("ip:1", Duration::from_millis(2_500));
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "3");
+
+        // Waits under a second still ask for at least one
+        let response = too_many_requests("ip:1", Duration::from_millis(200));
+        assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+    }
+
+    #[test]
+    fn rotating_forwarded_for_does_not_reset_the_bucket() {
+        let limiter = limiter(60, 2);
+        let start = Instant::now();
+        let peer: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let settings = ProxySettings {
+            trust_forwarded_headers: true,
+            tls: false,
+        };
+
+        let results: Vec<_> = ["6.6.6.1", "6.6.6.2", "6.6.6.3"]
+            .into_iter()
+            .map(|spoofed| {
+                let mut request = Request::new(Body::empty());
+                request.headers_mut().insert(
+                    "x-forwarded-for",
+                    HeaderValue::from_str(&format!("{spoofed}, 203.0.113.7")).unwrap(),
+                );
+                let origin = RequestOrigin::from_parts(request.headers(), Some(peer), settings);
+                request.extensions_mut().insert(origin);
+                let key = client_key(&request).unwrap();
+                assert_eq!(key, "ip:203.0.113.7");
+                limiter.check_at(&key, start)
+            })
+            .collect();
+
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        assert!(results[2].is_err());
+    }
+}
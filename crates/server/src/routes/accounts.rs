@@ -0,0 +1,248 @@
+use axum::{
+    Extension, Json, Router,
+    extract::State,
+    http::{HeaderMap, header},
+    response::{IntoResponse, Json as ResponseJson},
+    routing::{get, post},
+};
+use db::models::{
+    user::User,
+    user_push_settings::{UpdateUserPushSettings, UserPushSettings},
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{accounts::SESSION_COOKIE_NAME, container::ContainerService};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{CurrentUser, RequestOrigin, session_token},
+};
+
+#[derive(Debug, Serialize, TS)]
+pub struct AccountStatus {
+    /// Whether the server enforces user accounts
+    pub multi_user: bool,
+    /// No users exist yet; the first account created becomes an admin
+    pub setup_required: bool,
+    /// Users can sign in through the configured OpenID Connect provider
+    pub oidc_enabled: bool,
+    pub user: Option<User>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct LoginResponse {
+    pub user: User,
+    /// Session token for non-browser clients; browsers use the session cookie
+    pub token: String,
+}
+
+pub(crate) fn session_cookie(
+    deployment: &DeploymentImpl,
+    origin: &RequestOrigin,
+    token: &str,
+) -> String {
+    let max_age = deployment.accounts().session_ttl().num_seconds();
+    let secure = if origin.scheme == "https" {
+        "; Secure"
+    } else {
+        ""
+    };
+    format!(
+        "{SESSION_COOKIE_NAME}={token}; Path=/; HttpOnly; SameSite=Lax; Max-Age={max_age}{secure}"
+    )
+}
+
+fn clear_session_cookie() -> String {
+    format!("{SESSION_COOKIE_NAME}=; Path=/; HttpOnly; SameSite=Lax; Max-Age=0")
+}
+
+pub async fn get_account_status(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+) -> Result<ResponseJson<ApiResponse<AccountStatus>>, ApiError> {
+    let accounts = deployment.accounts();
+    let pool = &deployment.db().pool;
+
+    if !accounts.enabled() {
+        return Ok(ResponseJson(ApiResponse::success(AccountStatus {
+            multi_user: false,
+            setup_required: false,
+            oidc_enabled: false,
+            user: None,
+        })));
+    }
+
+    let user = match session_token(&headers) {
+        Some(token) => accounts.authenticate(pool, &token).await?,
+        None => None,
+    };
+
+    Ok(ResponseJson(ApiResponse::success(AccountStatus {
+        multi_user: true,
+        setup_required: User::count(pool).await? == 0,
+        oidc_enabled: deployment.oidc().enabled(),
+        user,
+    })))
+}
+
+pub async fn setup_first_admin(
+    State(deployment): State<DeploymentImpl>,
+    Extension(origin): Extension<RequestOrigin>,
+    Json(payload): Json<LoginRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let accounts = deployment.accounts();
+    if !accounts.enabled() {
+        return Err(ApiError::BadRequest(
+            "Multi-user mode is not enabled".to_string(),
+        ));
+    }
+
+    let pool = &deployment.db().pool;
+    let user = accounts
+        .setup(pool, &payload.username, &payload.password)
+        .await?;
+    let token = accounts.issue_session(pool, &user).await?;
+    tracing::info!("Created initial admin account {}", user.username);
+
+    Ok((
+        [(
+            header::SET_COOKIE,
+            session_cookie(&deployment, &origin, &token),
+        )],
+        ResponseJson(ApiResponse::<LoginResponse>::success(LoginResponse {
+            user,
+            token,
+        })),
+    ))
+}
+
+pub async fn login(
+    State(deployment): State<DeploymentImpl>,
+    Extension(origin): Extension<RequestOrigin>,
+    Json(payload): Json<LoginRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let accounts = deployment.accounts();
+    if !accounts.enabled() {
+        return Err(ApiError::BadRequest(
+            "Multi-user mode is not enabled".to_string(),
+        ));
+    }
+
+    let (user, token) = accounts
+        .login(&deployment.db().pool, &payload.username, &payload.password)
+        .await?;
+
+    Ok((
+        [(
+            header::SET_COOKIE,
+            session_cookie(&deployment, &origin, &token),
+        )],
+        ResponseJson(ApiResponse::<LoginResponse>::success(LoginResponse {
+            user,
+            token,
+        })),
+    ))
+}
+
+pub async fn logout(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    if let Some(token) = session_token(&headers) {
+        deployment
+            .accounts()
+            .logout(&deployment.db().pool, &token)
+            .await?;
+    }
+
+    Ok((
+        [(header::SET_COOKIE, clear_session_cookie())],
+        ResponseJson(ApiResponse::<()>::success(())),
+    ))
+}
+
+pub async fn get_current_account(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+) -> ResponseJson<ApiResponse<Option<User>>> {
+    ResponseJson(ApiResponse::success(user))
+}
+
+fn require_user(user: Option<User>) -> Result<User, ApiError> {
+    user.ok_or_else(|| {
+        ApiError::BadRequest(
+            "Per-user push settings require multi-user mode; use the server-wide mobile push settings instead".to_string(),
+        )
+    })
+}
+
+pub async fn get_push_settings(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<UserPushSettings>>>, ApiError> {
+    let user = require_user(user)?;
+    let settings = UserPushSettings::find_by_user_id(&deployment.db().pool, user.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Set the ntfy topic and Pushover user key the current user's phone notifications go to.
+pub async fn update_push_settings(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateUserPushSettings>,
+) -> Result<ResponseJson<ApiResponse<UserPushSettings>>, ApiError> {
+    let user = require_user(user)?;
+    let normalize = |value: Option<String>| {
+        value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let payload = UpdateUserPushSettings {
+        ntfy_topic: normalize(payload.ntfy_topic),
+        pushover_user_key: normalize(payload.pushover_user_key),
+    };
+    let settings = UserPushSettings::upsert(&deployment.db().pool, user.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+pub async fn test_push_settings(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let user = require_user(user)?;
+    let settings = UserPushSettings::find_by_user_id(&deployment.db().pool, user.id).await?;
+    deployment
+        .container()
+        .notification_service()
+        .send_test_push(
+            settings.as_ref().and_then(|s| s.ntfy_topic.as_deref()),
+            settings
+                .as_ref()
+                .and_then(|s| s.pushover_user_key.as_deref()),
+        )
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Test notification failed: {e}")))?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/accounts/status", get(get_account_status))
+        .route("/accounts/setup", post(setup_first_admin))
+        .route("/accounts/login", post(login))
+        .route("/accounts/logout", post(logout))
+        .route("/accounts/me", get(get_current_account))
+        .route(
+            "/accounts/me/push",
+            get(get_push_settings).put(update_push_settings),
+        )
+        .route("/accounts/me/push/test", post(test_push_settings))
+}
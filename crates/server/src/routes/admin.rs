@@ -0,0 +1,185 @@
+use axum::{
+    Router,
+    body::Body,
+    extract::{DefaultBodyLimit, Multipart, Path, Query, State},
+    http::{StatusCode, header},
+    response::{Json as ResponseJson, Response},
+    routing::{get, post},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::{
+    analytics_export::ExportedAnalyticsEvent,
+    backup::{BackupInfo, RestoreResult},
+    maintenance::MaintenanceStatus,
+    server_config::EffectiveServerConfig,
+};
+use tokio::{fs::File, io::AsyncWriteExt};
+use tokio_util::io::ReaderStream;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Take a consistent snapshot of the database and images while the server keeps running.
+pub async fn create_backup(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<BackupInfo>>, ApiError> {
+    let backup = deployment.backups().create_backup().await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "backup_created",
+            serde_json::json!({ "size_bytes": backup.size_bytes }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(backup)))
+}
+
+pub async fn list_backups(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<BackupInfo>>>, ApiError> {
+    let backups = deployment.backups().list_backups().await?;
+    Ok(ResponseJson(ApiResponse::success(backups)))
+}
+
+pub async fn download_backup(
+    Path(file_name): Path<String>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Response, ApiError> {
+    let path = deployment.backups().backup_path(&file_name)?;
+    let file = File::open(&path).await?;
+    let metadata = file.metadata().await?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/gzip")
+        .header(header::CONTENT_LENGTH, metadata.len())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{file_name}\""),
+        )
+        .body(Body::from_stream(ReaderStream::new(file)))
+        .map_err(|e| ApiError::Io(std::io::Error::other(e)))
+}
+
+pub async fn delete_backup(
+    Path(file_name): Path<String>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    deployment.backups().delete_backup(&file_name).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Stage a stored backup to replace the database on the next restart.
+pub async fn restore_backup(
+    Path(file_name): Path<String>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<RestoreResult>>, ApiError> {
+    let backups = deployment.backups();
+    let result = backups.restore(&backups.backup_path(&file_name)?).await?;
+    Ok(ResponseJson(ApiResponse::success(result)))
+}
+
+/// Stage an uploaded backup archive (multipart field `backup`) for restore.
+pub async fn restore_upload(
+    State(deployment): State<DeploymentImpl>,
+    mut multipart: Multipart,
+) -> Result<ResponseJson<ApiResponse<RestoreResult>>, ApiError> {
+    let backups = deployment.backups();
+
+    while let Some(mut field) = multipart.next_field().await? {
+        if field.name() != Some("backup") {
+            continue;
+        }
+
+        let upload_path = backups.upload_path().await?;
+        let mut file = File::create(&upload_path).await?;
+        while let Some(chunk) = field.chunk().await? {
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+        drop(file);
+
+        let result = backups.restore(&upload_path).await;
+        let _ = tokio::fs::remove_file(&upload_path).await;
+        return Ok(ResponseJson(ApiResponse::success(result?)));
+    }
+
+    Err(ApiError::BadRequest(
+        "Missing `backup` file in upload".to_string(),
+    ))
+}
+
+/// Start VACUUM, ANALYZE and an integrity check in the background.
+pub async fn start_maintenance(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<(StatusCode, ResponseJson<ApiResponse<MaintenanceStatus>>), ApiError> {
+    let status = deployment.maintenance().start().await?;
+    Ok((
+        StatusCode::ACCEPTED,
+        ResponseJson(ApiResponse::success(status)),
+    ))
+}
+
+/// Progress of the running maintenance job and the report from the last one.
+pub async fn maintenance_status(
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<MaintenanceStatus>> {
+    ResponseJson(ApiResponse::success(
+        deployment.maintenance().status().await,
+    ))
+}
+
+/// Effective server settings from the environment, `vibe-kanban.toml` and the user config.
+pub async fn effective_config(
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<EffectiveServerConfig>> {
+    ResponseJson(ApiResponse::success(
+        deployment.server_config().effective().await,
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AnalyticsEventsQuery {
+    pub limit: Option<usize>,
+    pub event: Option<String>,
+}
+
+/// Most recent events written to the local analytics export, newest first.
+pub async fn recent_analytics_events(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<AnalyticsEventsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExportedAnalyticsEvent>>>, ApiError> {
+    let Some(export) = deployment.analytics_export() else {
+        return Err(ApiError::BadRequest(
+            "Analytics export is not configured; set VK_ANALYTICS_EXPORT_URL".to_string(),
+        ));
+    };
+    let events = export.recent(query.limit.unwrap_or(100), query.event.as_deref());
+    Ok(ResponseJson(ApiResponse::success(events)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let admin_router = Router::new()
+        .route("/analytics/events", get(recent_analytics_events))
+        .route("/backup", post(create_backup))
+        .route("/config", get(effective_config))
+        .route("/backups", get(list_backups))
+        .route(
+            "/backups/{file_name}",
+            get(download_backup).delete(delete_backup),
+        )
+        .route("/backups/{file_name}/restore", post(restore_backup))
+        .route(
+            "/maintenance",
+            get(maintenance_status).post(start_maintenance),
+        )
+        // Archives include every uploaded image, so the usual body limits don't apply
+        .route(
+            "/restore",
+            post(restore_upload).layer(DefaultBodyLimit::disable()),
+        );
+
+    Router::new().nest("/admin", admin_router)
+}
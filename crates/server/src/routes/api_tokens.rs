@@ -0,0 +1,85 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get},
+};
+use db::models::{
+    api_token::{ApiToken, CreateApiToken},
+    project::Project,
+};
+use deployment::Deployment;
+use serde::Serialize;
+use services::services::accounts::{generate_api_token, hash_token};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::CurrentUser};
+
+/// A newly created API token. The token is only ever returned here.
+#[derive(Debug, Serialize, TS)]
+pub struct ApiTokenSecret {
+    pub api_token: ApiToken,
+    pub token: String,
+}
+
+pub async fn list_api_tokens(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ApiToken>>>, ApiError> {
+    let tokens = ApiToken::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(tokens)))
+}
+
+/// Create a token that may list and answer the questions and approvals of the given projects.
+pub async fn create_api_token(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateApiToken>,
+) -> Result<ResponseJson<ApiResponse<ApiTokenSecret>>, ApiError> {
+    if payload.name.trim().is_empty() {
+        return Err(ApiError::BadRequest("API tokens need a name".to_string()));
+    }
+    if payload.project_ids.is_empty() {
+        return Err(ApiError::BadRequest(
+            "API tokens need at least one project".to_string(),
+        ));
+    }
+    let pool = &deployment.db().pool;
+    for project_id in &payload.project_ids {
+        if Project::find_by_id(pool, *project_id).await?.is_none() {
+            return Err(ApiError::BadRequest(format!(
+                "Unknown project {project_id}"
+            )));
+        }
+    }
+
+    let token = generate_api_token();
+    let api_token = ApiToken::create(
+        pool,
+        &payload,
+        &hash_token(&token),
+        user.as_ref().map(|user| user.id),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(ApiTokenSecret {
+        api_token,
+        token,
+    })))
+}
+
+pub async fn delete_api_token(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    if ApiToken::delete(&deployment.db().pool, id).await? == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/api-tokens", get(list_api_tokens).post(create_api_token))
+        .route("/api-tokens/{id}", delete(delete_api_token))
+}
@@ -1,21 +1,79 @@
 use axum::{
-    Json, Router,
+    Extension, Json, Router,
     extract::{Path, State},
     http::StatusCode,
-    routing::post,
+    response::Json as ResponseJson,
+    routing::{get, post},
 };
+use db::models::api_token::ApiToken;
 use deployment::Deployment;
-use utils::approvals::{ApprovalResponse, ApprovalStatus};
+use utils::{
+    approvals::{ApprovalRequest, ApprovalResponse, ApprovalStatus},
+    response::ApiResponse,
+};
+
+use crate::{DeploymentImpl, middleware::token_covers};
 
-use crate::DeploymentImpl;
+/// Pending approvals here and on other instances sharing the board, oldest first
+pub(crate) async fn all_pending(deployment: &DeploymentImpl) -> Vec<ApprovalRequest> {
+    let mut pending = deployment.approvals().list_pending();
+    if let Some(coordinator) = deployment.coordinator() {
+        pending.extend(coordinator.remote_pending_approvals().await);
+        pending.sort_by_key(|request| request.created_at);
+    }
+    pending
+}
+
+/// Tool calls agents are currently waiting on approval for, oldest first. Includes agents run by
+/// other instances sharing the board.
+pub async fn list_pending_approvals(
+    api_token: Option<Extension<ApiToken>>,
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<Vec<ApprovalRequest>>> {
+    let mut pending = all_pending(&deployment).await;
+    if let Some(Extension(api_token)) = &api_token {
+        let mut covered = Vec::with_capacity(pending.len());
+        for request in pending {
+            if token_covers(&deployment, Some(api_token), request.execution_process_id).await {
+                covered.push(request);
+            }
+        }
+        pending = covered;
+    }
+    ResponseJson(ApiResponse::success(pending))
+}
 
 pub async fn respond_to_approval(
+    api_token: Option<Extension<ApiToken>>,
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<String>,
     Json(request): Json<ApprovalResponse>,
 ) -> Result<Json<ApprovalStatus>, StatusCode> {
     let service = deployment.approvals();
 
+    if let Some(Extension(api_token)) = &api_token {
+        let execution_process_id = all_pending(&deployment)
+            .await
+            .into_iter()
+            .find(|pending| pending.id == id)
+            .map(|pending| pending.execution_process_id)
+            .ok_or(StatusCode::NOT_FOUND)?;
+        if !token_covers(&deployment, Some(api_token), execution_process_id).await {
+            return Err(StatusCode::FORBIDDEN);
+        }
+    }
+
+    // The agent may be running on another instance sharing the board
+    if let Some(coordinator) = deployment.coordinator()
+        && service
+            .list_pending()
+            .iter()
+            .all(|pending| pending.id != id)
+        && let Some(status) = coordinator.forward_approval(&id, request.clone()).await
+    {
+        return Ok(Json(status));
+    }
+
     match service.respond(&deployment.db().pool, &id, request).await {
         Ok((status, context)) => {
             deployment
@@ -40,5 +98,7 @@ pub async fn respond_to_approval(
 }
 
 pub fn router() -> Router<DeploymentImpl> {
-    Router::new().route("/approvals/{id}/respond", post(respond_to_approval))
+    Router::new()
+        .route("/approvals", get(list_pending_approvals))
+        .route("/approvals/{id}/respond", post(respond_to_approval))
 }
@@ -0,0 +1,125 @@
+use axum::{
+    Extension, Router,
+    body::Body,
+    extract::{DefaultBodyLimit, Multipart, Path, State},
+    http::{StatusCode, header},
+    middleware::from_fn_with_state,
+    response::{Json as ResponseJson, Response},
+    routing::get,
+};
+use db::models::{task::Task, task_attachment::TaskAttachment};
+use deployment::Deployment;
+use services::services::attachment::AttachmentError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_task_middleware};
+
+/// Multipart framing on top of the file itself
+const MULTIPART_OVERHEAD_BYTES: usize = 1024 * 1024;
+
+pub async fn get_task_attachments(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskAttachment>>>, ApiError> {
+    let attachments = TaskAttachment::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(attachments)))
+}
+
+/// Attach a file (multipart field `file`) to a task. Any file type is accepted; the content type
+/// is detected from the file contents.
+pub async fn upload_task_attachment(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    mut multipart: Multipart,
+) -> Result<ResponseJson<ApiResponse<TaskAttachment>>, ApiError> {
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let filename = field
+            .file_name()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "attachment".to_string());
+        let data = field.bytes().await?;
+        let attachment = deployment
+            .attachments()
+            .store_attachment(task.id, &data, &filename)
+            .await?;
+
+        deployment
+            .track_if_analytics_allowed(
+                "task_attachment_uploaded",
+                serde_json::json!({
+                    "task_id": task.id.to_string(),
+                    "size_bytes": attachment.size_bytes,
+                    "mime_type": attachment.mime_type,
+                }),
+            )
+            .await;
+
+        return Ok(ResponseJson(ApiResponse::success(attachment)));
+    }
+
+    Err(ApiError::BadRequest("Missing `file` in upload".to_string()))
+}
+
+pub async fn download_attachment(
+    Path(attachment_id): Path<Uuid>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Response, ApiError> {
+    let attachments = deployment.attachments();
+    let attachment = attachments
+        .get_attachment(attachment_id)
+        .await?
+        .ok_or(AttachmentError::NotFound)?;
+    let blob = attachments.open_attachment(&attachment).await?;
+
+    // The stored name is already sanitized, so it is safe to put in the header
+    let file_name = attachment
+        .file_path
+        .rsplit('/')
+        .next()
+        .unwrap_or("attachment");
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, &attachment.mime_type)
+        .header(header::CONTENT_LENGTH, blob.size)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{file_name}\""),
+        )
+        .body(Body::from_stream(blob.stream))
+        .map_err(|e| ApiError::Io(std::io::Error::other(e)))
+}
+
+pub async fn delete_attachment(
+    Path(attachment_id): Path<Uuid>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    deployment
+        .attachments()
+        .delete_attachment(attachment_id)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let body_limit = DefaultBodyLimit::max(
+        deployment.attachments().max_size_bytes() as usize + MULTIPART_OVERHEAD_BYTES,
+    );
+
+    let task_attachments_router = Router::new()
+        .route("/", get(get_task_attachments).post(upload_task_attachment))
+        .layer(body_limit)
+        .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
+
+    Router::new()
+        .nest("/tasks/{task_id}/attachments", task_attachments_router)
+        .route(
+            "/attachments/{attachment_id}",
+            get(download_attachment).delete(delete_attachment),
+        )
+}
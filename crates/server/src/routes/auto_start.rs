@@ -0,0 +1,118 @@
+use axum::{
+    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::get,
+};
+use db::models::{
+    execution_process::ExecutionProcess,
+    project::Project,
+    project_auto_start::{ProjectAutoStart, UpsertProjectAutoStart},
+    task::Task,
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use services::services::container::ContainerService;
+use tokio::sync::Mutex;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+/// Held while checking the concurrency limit and starting, so two tasks dropped at once can't
+/// both take the last slot.
+static AUTO_START_LOCK: Mutex<()> = Mutex::const_new(());
+
+pub async fn get_auto_start(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ProjectAutoStart>>>, ApiError> {
+    let auto_start =
+        ProjectAutoStart::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(auto_start)))
+}
+
+pub async fn update_auto_start(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertProjectAutoStart>,
+) -> Result<ResponseJson<ApiResponse<ProjectAutoStart>>, ApiError> {
+    if payload.max_concurrent_attempts < 1 {
+        return Err(ApiError::BadRequest(
+            "At least one concurrent attempt must be allowed".to_string(),
+        ));
+    }
+    let auto_start = ProjectAutoStart::upsert(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(auto_start)))
+}
+
+pub async fn delete_auto_start(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ProjectAutoStart::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Start an attempt for a task that was just moved into its project's auto-start column, unless
+/// it already has a running attempt or the project is at its concurrency limit.
+pub(crate) async fn auto_start_on_move(
+    deployment: &DeploymentImpl,
+    task: &Task,
+) -> Result<Option<Workspace>, ApiError> {
+    let pool = &deployment.db().pool;
+    let Some(auto_start) = ProjectAutoStart::find_by_project_id(pool, task.project_id).await?
+    else {
+        return Ok(None);
+    };
+    if !auto_start.enabled || auto_start.trigger_status != task.status {
+        return Ok(None);
+    }
+
+    let _guard = AUTO_START_LOCK.lock().await;
+    if ExecutionProcess::has_running_non_dev_server_processes_for_task(pool, task.id).await? {
+        return Ok(None);
+    }
+    let running =
+        ExecutionProcess::count_running_attempts_for_project(pool, task.project_id).await?;
+    if running >= auto_start.max_concurrent_attempts {
+        tracing::info!(
+            "Not auto-starting task {}: {} of {} concurrent attempts already running",
+            task.id,
+            running,
+            auto_start.max_concurrent_attempts
+        );
+        return Ok(None);
+    }
+
+    let default_profile = deployment.config().read().await.executor_profile.clone();
+    let workspace = deployment
+        .container()
+        .start_default_attempt(task, default_profile)
+        .await?;
+    if workspace.is_some() {
+        deployment
+            .track_if_analytics_allowed(
+                "task_attempt_auto_started",
+                serde_json::json!({
+                    "task_id": task.id.to_string(),
+                    "project_id": task.project_id.to_string(),
+                }),
+            )
+            .await;
+    }
+    Ok(workspace)
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_auto_start_router = Router::new()
+        .route(
+            "/auto-start",
+            get(get_auto_start)
+                .put(update_auto_start)
+                .delete(delete_auto_start),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{id}", project_auto_start_router)
+}
@@ -0,0 +1,129 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    automation_rule::{
+        AutomationAction, AutomationRule, AutomationRuleFiring, CreateAutomationRule,
+        UpdateAutomationRule,
+    },
+    project::Project,
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+/// Firings returned by the audit endpoint
+const FIRINGS_LIMIT: i64 = 100;
+
+fn validate(name: &str, actions: &[AutomationAction]) -> Result<(), ApiError> {
+    if name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "An automation needs a name".to_string(),
+        ));
+    }
+    if actions.is_empty() {
+        return Err(ApiError::BadRequest(
+            "An automation needs at least one action".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+async fn load_rule(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    rule_id: Uuid,
+) -> Result<AutomationRule, ApiError> {
+    AutomationRule::find_by_id(&deployment.db().pool, rule_id)
+        .await?
+        .filter(|rule| rule.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn get_automations(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<AutomationRule>>>, ApiError> {
+    let rules = AutomationRule::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(rules)))
+}
+
+pub async fn create_automation(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateAutomationRule>,
+) -> Result<ResponseJson<ApiResponse<AutomationRule>>, ApiError> {
+    validate(&payload.name, &payload.actions)?;
+    let rule = AutomationRule::create(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+pub async fn update_automation(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, rule_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateAutomationRule>,
+) -> Result<ResponseJson<ApiResponse<AutomationRule>>, ApiError> {
+    let rule = load_rule(&deployment, project_id, rule_id).await?;
+    let name = payload.name.unwrap_or(rule.name);
+    let trigger = payload.trigger.unwrap_or(rule.trigger.0);
+    let actions = payload.actions.unwrap_or(rule.actions.0);
+    validate(&name, &actions)?;
+    let rule = AutomationRule::update(
+        &deployment.db().pool,
+        rule.id,
+        &name,
+        &trigger,
+        &actions,
+        payload.enabled.unwrap_or(rule.enabled),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+pub async fn delete_automation(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, rule_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rule = load_rule(&deployment, project_id, rule_id).await?;
+    AutomationRule::delete(&deployment.db().pool, rule.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn get_automation_firings(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, rule_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Vec<AutomationRuleFiring>>>, ApiError> {
+    let rule = load_rule(&deployment, project_id, rule_id).await?;
+    let firings =
+        AutomationRuleFiring::find_by_rule_id(&deployment.db().pool, rule.id, FIRINGS_LIMIT)
+            .await?;
+    Ok(ResponseJson(ApiResponse::success(firings)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_automations_router = Router::new()
+        .route("/automations", get(get_automations).post(create_automation))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    let projects_router = Router::new()
+        .route(
+            "/{project_id}/automations/{rule_id}",
+            put(update_automation).delete(delete_automation),
+        )
+        .route(
+            "/{project_id}/automations/{rule_id}/firings",
+            get(get_automation_firings),
+        )
+        .nest("/{id}", project_automations_router);
+
+    Router::new().nest("/projects", projects_router)
+}
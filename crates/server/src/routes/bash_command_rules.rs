@@ -0,0 +1,124 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post, put},
+};
+use db::models::{
+    bash_command_rule::{
+        BashCommandRule, CommandMatchKind, CreateBashCommandRule, UpdateBashCommandRule,
+    },
+    project::Project,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::approvals::command_rules::{self, CommandClassification};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CheckBashCommandRequest {
+    pub command: String,
+}
+
+fn validate_pattern(match_kind: CommandMatchKind, pattern: &str) -> Result<(), ApiError> {
+    if pattern.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Command rules need a pattern".to_string(),
+        ));
+    }
+    command_rules::compile(match_kind, pattern)
+        .map(|_| ())
+        .map_err(|e| ApiError::BadRequest(format!("Invalid pattern '{pattern}': {e}")))
+}
+
+pub async fn get_bash_command_rules(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<BashCommandRule>>>, ApiError> {
+    let rules = BashCommandRule::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(rules)))
+}
+
+pub async fn create_bash_command_rule(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateBashCommandRule>,
+) -> Result<ResponseJson<ApiResponse<BashCommandRule>>, ApiError> {
+    validate_pattern(payload.match_kind, &payload.pattern)?;
+    let rule = BashCommandRule::create(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+/// How the project's rules would treat a command, without running anything
+pub async fn check_bash_command(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CheckBashCommandRequest>,
+) -> Result<ResponseJson<ApiResponse<CommandClassification>>, ApiError> {
+    let rules = BashCommandRule::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(command_rules::classify(
+        &rules,
+        &payload.command,
+    ))))
+}
+
+async fn load_rule(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    rule_id: Uuid,
+) -> Result<BashCommandRule, ApiError> {
+    BashCommandRule::find_by_id(&deployment.db().pool, rule_id)
+        .await?
+        .filter(|rule| rule.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn update_bash_command_rule(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, rule_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateBashCommandRule>,
+) -> Result<ResponseJson<ApiResponse<BashCommandRule>>, ApiError> {
+    let existing = load_rule(&deployment, project_id, rule_id).await?;
+    validate_pattern(
+        payload.match_kind.unwrap_or(existing.match_kind),
+        payload.pattern.as_deref().unwrap_or(&existing.pattern),
+    )?;
+    let rule = existing.update(&deployment.db().pool, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+pub async fn delete_bash_command_rule(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, rule_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let existing = load_rule(&deployment, project_id, rule_id).await?;
+    BashCommandRule::delete(&deployment.db().pool, existing.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_rules_router = Router::new()
+        .route(
+            "/bash-command-rules",
+            get(get_bash_command_rules).post(create_bash_command_rule),
+        )
+        .route("/bash-command-rules/check", post(check_bash_command))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    let projects_router = Router::new()
+        .route(
+            "/{project_id}/bash-command-rules/{rule_id}",
+            put(update_bash_command_rule).delete(delete_bash_command_rule),
+        )
+        .nest("/{id}", project_rules_router);
+
+    Router::new().nest("/projects", projects_router)
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use axum::{
-    Json, Router,
+    Extension, Json, Router,
     body::Body,
     extract::{Path, Query, State},
     http,
@@ -27,7 +27,7 @@ use tokio::fs;
 use ts_rs::TS;
 use utils::{api::oauth::LoginStatus, assets::config_path, response::ApiResponse};
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{DeploymentImpl, error::ApiError, middleware::RequestOrigin};
 
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
@@ -79,12 +79,15 @@ pub struct UserSystemInfo {
     pub environment: Environment,
     /// Capabilities supported per executor (e.g., { "CLAUDE_CODE": ["SESSION_FORK"] })
     pub capabilities: HashMap<String, Vec<BaseAgentCapability>>,
+    /// Externally visible base URL of this server, as seen by the client
+    pub server_url: Option<String>,
 }
 
 // TODO: update frontend, BE schema has changed, this replaces GET /config and /config/constants
 #[axum::debug_handler]
 async fn get_user_system_info(
     State(deployment): State<DeploymentImpl>,
+    Extension(origin): Extension<RequestOrigin>,
 ) -> ResponseJson<ApiResponse<UserSystemInfo>> {
     let config = deployment.config().read().await;
     let login_status = deployment.get_login_status().await;
@@ -105,6 +108,7 @@ async fn get_user_system_info(
             }
             caps
         },
+        server_url: origin.base_url(),
     };
 
     ResponseJson(ApiResponse::success(user_system_info))
@@ -0,0 +1,244 @@
+use std::time::Duration;
+
+use axum::{
+    Extension, Json, Router,
+    extract::State,
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use chrono::{DateTime, Utc};
+use db::models::execution_process::{
+    ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus, RunningDevServer,
+};
+use deployment::Deployment;
+use futures_util::future::join_all;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::ContainerService,
+    tunnel::{ActiveTunnel, TunnelProviderKind},
+};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl, error::ApiError, middleware::load_execution_process_middleware,
+    routes::task_attempts::launch_dev_server,
+};
+
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum DevServerHealth {
+    Healthy,
+    Unhealthy,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct DevServerInfo {
+    pub execution_process_id: Uuid,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub branch: String,
+    /// devctl2 subdomain URL if registered, otherwise the local URL detected in the logs
+    pub url: Option<String>,
+    /// Public tunnel URL, if the dev server is currently shared
+    pub public_url: Option<String>,
+    #[ts(type = "Date")]
+    pub started_at: DateTime<Utc>,
+    pub uptime_seconds: i64,
+    pub health: DevServerHealth,
+}
+
+async fn check_health(client: &reqwest::Client, url: Option<&str>) -> DevServerHealth {
+    let Some(url) = url else {
+        return DevServerHealth::Unknown;
+    };
+    // Any HTTP response means the server is accepting connections
+    match client.get(url).send().await {
+        Ok(_) => DevServerHealth::Healthy,
+        Err(_) => DevServerHealth::Unhealthy,
+    }
+}
+
+#[derive(Debug, Default, Deserialize, TS)]
+pub struct OpenTunnelRequest {
+    pub provider: Option<TunnelProviderKind>,
+}
+
+async fn describe_dev_server(
+    deployment: &DeploymentImpl,
+    client: &reqwest::Client,
+    server: RunningDevServer,
+) -> DevServerInfo {
+    let url = deployment.container().dev_server_url(&server.id).await;
+    let health = check_health(client, url.as_deref()).await;
+    let public_url = deployment
+        .tunnels()
+        .get(&server.id)
+        .await
+        .map(|tunnel| tunnel.public_url);
+
+    DevServerInfo {
+        execution_process_id: server.id,
+        workspace_id: server.workspace_id,
+        task_id: server.task_id,
+        task_title: server.task_title,
+        project_id: server.project_id,
+        project_name: server.project_name,
+        branch: server.branch,
+        url,
+        public_url,
+        started_at: server.started_at,
+        uptime_seconds: (Utc::now() - server.started_at).num_seconds().max(0),
+        health,
+    }
+}
+
+pub async fn list_dev_servers(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<DevServerInfo>>>, ApiError> {
+    let servers =
+        ExecutionProcess::find_running_dev_servers_with_context(&deployment.db().pool).await?;
+
+    let client = reqwest::Client::builder()
+        .timeout(HEALTH_CHECK_TIMEOUT)
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap_or_default();
+
+    let infos = join_all(
+        servers
+            .into_iter()
+            .map(|server| describe_dev_server(&deployment, &client, server)),
+    )
+    .await;
+
+    Ok(ResponseJson(ApiResponse::success(infos)))
+}
+
+fn ensure_dev_server(execution_process: &ExecutionProcess) -> Result<(), ApiError> {
+    if execution_process.run_reason != ExecutionProcessRunReason::DevServer {
+        return Err(ApiError::BadRequest(
+            "Execution process is not a dev server".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub async fn stop_dev_server(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ensure_dev_server(&execution_process)?;
+
+    deployment
+        .container()
+        .stop_execution(&execution_process, ExecutionProcessStatus::Killed)
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn restart_dev_server(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    ensure_dev_server(&execution_process)?;
+
+    let (workspace, _) = execution_process
+        .parent_workspace_and_session(&deployment.db().pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    // launch_dev_server stops the project's running dev servers (including this one) first
+    match launch_dev_server(&deployment, &workspace).await? {
+        Some(process) => Ok(ResponseJson(ApiResponse::success(process))),
+        None => Ok(ResponseJson(ApiResponse::error(
+            "No dev server script configured for this project",
+        ))),
+    }
+}
+
+pub async fn get_dev_server_tunnel(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ActiveTunnel>>>, ApiError> {
+    let tunnel = deployment.tunnels().get(&execution_process.id).await;
+    Ok(ResponseJson(ApiResponse::success(tunnel)))
+}
+
+pub async fn open_dev_server_tunnel(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<OpenTunnelRequest>,
+) -> Result<ResponseJson<ApiResponse<ActiveTunnel>>, ApiError> {
+    ensure_dev_server(&execution_process)?;
+    if execution_process.status != ExecutionProcessStatus::Running {
+        return Err(ApiError::Conflict("Dev server is not running".to_string()));
+    }
+
+    let local_url = deployment
+        .container()
+        .dev_server_url(&execution_process.id)
+        .await
+        .ok_or_else(|| {
+            ApiError::Conflict("Could not determine the dev server's URL".to_string())
+        })?;
+
+    let provider = payload.provider.unwrap_or_default();
+    let tunnel = deployment
+        .tunnels()
+        .open(execution_process.id, &local_url, provider)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "dev_server_tunnel_opened",
+            serde_json::json!({
+                "execution_process_id": execution_process.id.to_string(),
+                "provider": provider,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(tunnel)))
+}
+
+pub async fn close_dev_server_tunnel(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    deployment.tunnels().close(&execution_process.id).await;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let dev_server_id_router = Router::new()
+        .route("/stop", post(stop_dev_server))
+        .route("/restart", post(restart_dev_server))
+        .route(
+            "/tunnel",
+            get(get_dev_server_tunnel)
+                .post(open_dev_server_tunnel)
+                .delete(close_dev_server_tunnel),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_execution_process_middleware,
+        ));
+
+    let dev_servers_router = Router::new()
+        .route("/", get(list_dev_servers))
+        .nest("/{id}", dev_server_id_router);
+
+    Router::new().nest("/dev-servers", dev_servers_router)
+}
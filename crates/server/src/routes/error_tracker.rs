@@ -0,0 +1,186 @@
+use axum::{
+    Extension, Json, Router,
+    body::Bytes,
+    extract::{Path, State},
+    http::HeaderMap,
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{error_tracker::ErrorTrackerIntegration, project::Project, task::Task};
+use deployment::{Deployment, DeploymentError};
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::ContainerService,
+    error_tracker::{self, ErrorReport, ErrorReportOutcome},
+};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl, error::ApiError, middleware::load_project_middleware,
+    routes::webhooks::presented_secret,
+};
+
+const SENTRY_SIGNATURE_HEADER: &str = "sentry-hook-signature";
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateErrorTrackerIntegration {
+    /// Sentry client secret, or the bearer secret of generic trackers
+    pub webhook_secret: String,
+    #[serde(default)]
+    pub auto_start: bool,
+    pub daily_cost_cap_usd: Option<f64>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ErrorTrackerWebhookResponse {
+    pub outcome: ErrorReportOutcome,
+    pub task_id: Uuid,
+    /// Attempt started to diagnose and fix a new issue
+    pub workspace_id: Option<Uuid>,
+}
+
+pub async fn get_error_tracker_integration(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ErrorTrackerIntegration>>>, ApiError> {
+    let integration =
+        ErrorTrackerIntegration::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(integration)))
+}
+
+pub async fn update_error_tracker_integration(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateErrorTrackerIntegration>,
+) -> Result<ResponseJson<ApiResponse<ErrorTrackerIntegration>>, ApiError> {
+    if payload.webhook_secret.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Webhook secret is required".to_string(),
+        ));
+    }
+    if payload
+        .daily_cost_cap_usd
+        .is_some_and(|cap| !cap.is_finite() || cap < 0.0)
+    {
+        return Err(ApiError::BadRequest(
+            "Daily cost cap must be a positive amount".to_string(),
+        ));
+    }
+    let webhook_secret = deployment
+        .project_env()
+        .cipher()
+        .encrypt(&payload.webhook_secret)
+        .map_err(DeploymentError::from)?;
+
+    let integration = ErrorTrackerIntegration::upsert(
+        &deployment.db().pool,
+        project.id,
+        &webhook_secret,
+        payload.auto_start,
+        payload.daily_cost_cap_usd,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(integration)))
+}
+
+pub async fn delete_error_tracker_integration(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ErrorTrackerIntegration::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Receives Sentry webhooks, signed with `Sentry-Hook-Signature`, and reports from other
+/// trackers authenticated with the secret as a bearer token.
+pub async fn receive_error_report(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<ResponseJson<ApiResponse<ErrorTrackerWebhookResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let integration = ErrorTrackerIntegration::find_by_project_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+    let secret = deployment
+        .project_env()
+        .cipher()
+        .decrypt(&integration.webhook_secret)
+        .map_err(DeploymentError::from)?;
+    let authorized = match headers
+        .get(SENTRY_SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+    {
+        Some(signature) => {
+            error_tracker::verify_sentry_signature(secret.as_bytes(), signature, &body)
+        }
+        None => presented_secret(&headers)
+            .is_some_and(|presented| error_tracker::secret_matches(&secret, presented)),
+    };
+    if !authorized {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let report = ErrorReport::parse(&body)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid error report: {e}")))?;
+    if report.title.is_empty() {
+        return Err(ApiError::BadRequest("Error title is required".to_string()));
+    }
+    let (outcome, task_id) = error_tracker::record_report(pool, project_id, &report).await?;
+
+    let mut workspace_id = None;
+    if outcome == ErrorReportOutcome::Created {
+        deployment
+            .track_if_analytics_allowed(
+                "task_created_from_error_tracker",
+                serde_json::json!({
+                    "project_id": project_id.to_string(),
+                    "auto_start": integration.auto_start,
+                }),
+            )
+            .await;
+
+        if error_tracker::can_auto_start(pool, &integration).await? {
+            error_tracker::prepare_fix_attempt(pool, project_id, task_id, &report).await?;
+            let task = Task::find_by_id(pool, task_id)
+                .await?
+                .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+            let default_profile = deployment.config().read().await.executor_profile.clone();
+            workspace_id = deployment
+                .container()
+                .start_default_attempt(&task, default_profile)
+                .await?
+                .map(|workspace| workspace.id);
+        }
+    }
+
+    Ok(ResponseJson(ApiResponse::success(
+        ErrorTrackerWebhookResponse {
+            outcome,
+            task_id,
+            workspace_id,
+        },
+    )))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_integration_router = Router::new()
+        .route(
+            "/error-tracker",
+            get(get_error_tracker_integration)
+                .put(update_error_tracker_integration)
+                .delete(delete_error_tracker_integration),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new()
+        .route("/hooks/errors/{project_id}", post(receive_error_report))
+        .nest("/projects/{id}", project_integration_router)
+}
@@ -0,0 +1,140 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    project::Project,
+    project_event_webhook::{
+        CreateProjectEventWebhook, EventWebhookKind, ProjectEventWebhook, UpdateProjectEventWebhook,
+    },
+};
+use deployment::{Deployment, DeploymentError};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+fn validate_webhook(url: &str, events: &[EventWebhookKind]) -> Result<(), ApiError> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid webhook URL '{url}': use an http(s) address"
+        )));
+    }
+    if events.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Pick at least one event for the webhook".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn encrypt_secret(
+    deployment: &DeploymentImpl,
+    secret: Option<&str>,
+) -> Result<Option<String>, ApiError> {
+    secret
+        .filter(|secret| !secret.is_empty())
+        .map(|secret| deployment.project_env().cipher().encrypt(secret))
+        .transpose()
+        .map_err(|e| DeploymentError::from(e).into())
+}
+
+pub async fn get_event_webhooks(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectEventWebhook>>>, ApiError> {
+    let webhooks =
+        ProjectEventWebhook::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(webhooks)))
+}
+
+pub async fn create_event_webhook(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProjectEventWebhook>,
+) -> Result<ResponseJson<ApiResponse<ProjectEventWebhook>>, ApiError> {
+    let url = payload.url.trim();
+    validate_webhook(url, &payload.events)?;
+    let secret = encrypt_secret(&deployment, payload.secret.as_deref())?;
+    let webhook = ProjectEventWebhook::create(
+        &deployment.db().pool,
+        project.id,
+        url,
+        secret.as_deref(),
+        &payload.events,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(webhook)))
+}
+
+async fn load_webhook(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    webhook_id: Uuid,
+) -> Result<ProjectEventWebhook, ApiError> {
+    ProjectEventWebhook::find_by_id(&deployment.db().pool, webhook_id)
+        .await?
+        .filter(|webhook| webhook.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn update_event_webhook(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, webhook_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateProjectEventWebhook>,
+) -> Result<ResponseJson<ApiResponse<ProjectEventWebhook>>, ApiError> {
+    let existing = load_webhook(&deployment, project_id, webhook_id).await?;
+    let url = payload
+        .url
+        .as_deref()
+        .map_or(existing.url.as_str(), str::trim);
+    let events = payload.events.as_ref().unwrap_or(&existing.events.0);
+    validate_webhook(url, events)?;
+    let secret = match payload.secret.as_deref() {
+        Some(secret) => encrypt_secret(&deployment, Some(secret))?,
+        None => existing.secret.clone(),
+    };
+    let webhook = ProjectEventWebhook::update(
+        &deployment.db().pool,
+        existing.id,
+        url,
+        secret.as_deref(),
+        events,
+        payload.enabled.unwrap_or(existing.enabled),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(webhook)))
+}
+
+pub async fn delete_event_webhook(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, webhook_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let existing = load_webhook(&deployment, project_id, webhook_id).await?;
+    ProjectEventWebhook::delete(&deployment.db().pool, existing.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_webhooks_router = Router::new()
+        .route(
+            "/event-webhooks",
+            get(get_event_webhooks).post(create_event_webhook),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    let projects_router = Router::new()
+        .route(
+            "/{project_id}/event-webhooks/{webhook_id}",
+            put(update_event_webhook).delete(delete_event_webhook),
+        )
+        .nest("/{id}", project_webhooks_router);
+
+    Router::new().nest("/projects", projects_router)
+}
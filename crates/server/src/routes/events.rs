@@ -1,6 +1,9 @@
+use std::convert::Infallible;
+
 use axum::{
     BoxError, Router,
-    extract::State,
+    extract::{Query, State},
+    http::HeaderMap,
     response::{
         Sse,
         sse::{Event, KeepAlive},
@@ -8,7 +11,11 @@ use axum::{
     routing::get,
 };
 use deployment::Deployment;
-use futures_util::TryStreamExt;
+use futures_util::{Stream, StreamExt, TryStreamExt, stream};
+use serde::Deserialize;
+use services::services::events::EntityEventEnvelope;
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
 
 use crate::DeploymentImpl;
 
@@ -21,8 +28,76 @@ pub async fn events(
     Ok(Sse::new(stream.map_err(|e| -> BoxError { e.into() })).keep_alive(KeepAlive::default()))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct EntityStreamQuery {
+    /// Only deliver events for this project
+    pub project_id: Option<Uuid>,
+    /// Resume after this event id (the `Last-Event-ID` header takes precedence)
+    pub last_event_id: Option<u64>,
+}
+
+fn entity_sse_event(envelope: &EntityEventEnvelope) -> Event {
+    Event::default()
+        .id(envelope.id.to_string())
+        .json_data(envelope)
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to serialize entity event {}: {}", envelope.id, e);
+            Event::default().comment("serialization error")
+        })
+}
+
+/// Tells the client it missed events and should refetch any state it caches.
+fn resync_event() -> Event {
+    Event::default().event("resync").data("events were missed")
+}
+
+/// Server-sent events for task, attempt, question, approval and dev server changes, plus
+/// notification center updates.
+pub async fn entity_stream(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+    Query(query): Query<EntityStreamQuery>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+        .or(query.last_event_id);
+    let subscription = deployment.events().entity_events().subscribe(last_event_id);
+
+    let project_id = query.project_id;
+    let matches_project = move |envelope: &EntityEventEnvelope| {
+        envelope.event.is_global() || project_id.is_none_or(|id| envelope.project_id == Some(id))
+    };
+
+    let resync = stream::iter(subscription.truncated.then(resync_event));
+    let replay = stream::iter(subscription.replay)
+        .filter(move |envelope| std::future::ready(matches_project(envelope)))
+        .map(|envelope| entity_sse_event(&envelope));
+    let live = stream::unfold(subscription.live, move |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(envelope) if matches_project(&envelope) => {
+                    return Some((entity_sse_event(&envelope), rx));
+                }
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::debug!("Entity event subscriber lagged by {} events", skipped);
+                    return Some((resync_event(), rx));
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Sse::new(resync.chain(replay).chain(live).map(Ok::<_, Infallible>))
+        .keep_alive(KeepAlive::default())
+}
+
 pub fn router(_: &DeploymentImpl) -> Router<DeploymentImpl> {
-    let events_router = Router::new().route("/", get(events));
+    let events_router = Router::new()
+        .route("/", get(events))
+        .route("/stream", get(entity_stream));
 
     Router::new().nest("/events", events_router)
 }
@@ -1,23 +1,44 @@
+use std::convert::Infallible;
+
 use anyhow;
 use axum::{
-    Extension, Router,
+    Extension, Json, Router,
+    body::Body,
     extract::{
         Path, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
+    http::{HeaderMap, StatusCode, header},
     middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
-    routing::{get, post},
+    response::{
+        IntoResponse, Json as ResponseJson, Response, Sse,
+        sse::{Event, KeepAlive},
+    },
+    routing::{get, post, put},
 };
+use chrono::{DateTime, Utc};
 use db::models::{
     execution_process::{ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus},
+    execution_process_environment::ExecutionProcessEnvironment,
+    execution_process_failure::ExecutionProcessFailure,
+    execution_process_heartbeat::ExecutionProcessHeartbeat,
+    execution_process_logs::ExecutionProcessLogs,
     execution_process_repo_state::ExecutionProcessRepoState,
+    log_entry_annotation::{
+        CreateLogEntryAnnotation, LogEntryAnnotation, UpdateLogEntryAnnotation,
+    },
 };
 use deployment::Deployment;
-use futures_util::{SinkExt, StreamExt, TryStreamExt};
-use serde::Deserialize;
+use futures_util::{SinkExt, Stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
 use services::services::container::ContainerService;
-use utils::{log_msg::LogMsg, response::ApiResponse};
+use ts_rs::TS;
+use utils::{
+    etag::ETag,
+    log_msg::LogMsg,
+    pagination::{Page, PageQuery, SortOrder},
+    response::ApiResponse,
+};
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_execution_process_middleware};
@@ -30,11 +51,161 @@ pub struct ExecutionProcessQuery {
     pub show_soft_deleted: Option<bool>,
 }
 
+/// List a workspace's execution processes across all of its sessions
+pub async fn get_execution_processes(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ExecutionProcessQuery>,
+    Query(page): Query<PageQuery>,
+) -> Result<Page<ExecutionProcess>, ApiError> {
+    let pool = &deployment.db().pool;
+    let show_soft_deleted = query.show_soft_deleted.unwrap_or(false);
+
+    let keyset = page.keyset(SortOrder::Asc)?;
+    let processes = ExecutionProcess::find_page_by_workspace_id(
+        pool,
+        query.workspace_id,
+        show_soft_deleted,
+        &keyset,
+    )
+    .await?;
+    let total_count =
+        ExecutionProcess::count_by_workspace_id(pool, query.workspace_id, show_soft_deleted)
+            .await?;
+
+    Ok(keyset.page(processes, total_count as usize))
+}
+
+#[derive(Debug, Serialize, TS)]
+#[serde(tag = "stream", content = "content", rename_all = "snake_case")]
+pub enum RawLogLine {
+    Stdout(String),
+    Stderr(String),
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct RawLogSnapshot {
+    pub lines: Vec<RawLogLine>,
+    /// Whether the process has exited, i.e. no more lines will be appended
+    pub finished: bool,
+}
+
+/// Stored stdout/stderr of an execution process, with an ETag so pollers get cheap 304s
+pub async fn get_raw_logs(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+    let (chunks, bytes) = ExecutionProcessLogs::version(pool, execution_process.id).await?;
+    let etag = ETag::weak(format!(
+        "{}:{}:{}:{}",
+        execution_process.id,
+        execution_process.updated_at.timestamp_micros(),
+        chunks,
+        bytes
+    ));
+    if etag.matches(&headers) {
+        return Ok(etag.not_modified());
+    }
+
+    let records = ExecutionProcessLogs::find_by_execution_id(pool, execution_process.id).await?;
+    let lines = ExecutionProcessLogs::parse_logs(&records)
+        .map_err(|e| ApiError::Io(std::io::Error::other(e)))?
+        .into_iter()
+        .filter_map(|msg| match msg {
+            LogMsg::Stdout(content) => Some(RawLogLine::Stdout(content)),
+            LogMsg::Stderr(content) => Some(RawLogLine::Stderr(content)),
+            _ => None,
+        })
+        .collect();
+
+    Ok(
+        etag.attach(ResponseJson(ApiResponse::<RawLogSnapshot>::success(
+            RawLogSnapshot {
+                lines,
+                finished: execution_process.status != ExecutionProcessStatus::Running,
+            },
+        ))),
+    )
+}
+
+/// Download the stdout/stderr of a finished execution process as a text file
+pub async fn export_raw_logs(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Response, ApiError> {
+    if execution_process.status == ExecutionProcessStatus::Running {
+        return Err(ApiError::Conflict(
+            "Logs can be exported once the process has finished".to_string(),
+        ));
+    }
+    let pool = &deployment.db().pool;
+    let (workspace, _) = execution_process
+        .parent_workspace_and_session(pool)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let blob = deployment
+        .attachments()
+        .export_logs(workspace.task_id, execution_process.id)
+        .await?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+        .header(header::CONTENT_LENGTH, blob.size)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}.log\"", execution_process.id),
+        )
+        .body(Body::from_stream(blob.stream))
+        .map_err(|e| ApiError::Io(std::io::Error::other(e)))
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ExecutionFailureDetail {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub failure: ExecutionProcessFailure,
+    /// Whether retrying the attempt as-is is likely to get further
+    pub retryable: bool,
+    pub remediation: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ExecutionProcessDetail {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub execution_process: ExecutionProcess,
+    /// End of stderr and the likely cause, when the process failed
+    pub failure_detail: Option<ExecutionFailureDetail>,
+    /// Agent CLI, model, platform and repo state the process started with
+    pub environment: Option<ExecutionProcessEnvironment>,
+}
+
 pub async fn get_execution_process_by_id(
     Extension(execution_process): Extension<ExecutionProcess>,
-    State(_deployment): State<DeploymentImpl>,
-) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
-    Ok(ResponseJson(ApiResponse::success(execution_process)))
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcessDetail>>, ApiError> {
+    let failure_detail = ExecutionProcessFailure::find_by_execution_process_id(
+        &deployment.db().pool,
+        execution_process.id,
+    )
+    .await?
+    .map(|failure| ExecutionFailureDetail {
+        retryable: failure.kind.retryable(),
+        remediation: failure.kind.remediation().to_string(),
+        failure,
+    });
+    let environment = ExecutionProcessEnvironment::find_by_execution_process_id(
+        &deployment.db().pool,
+        execution_process.id,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(ExecutionProcessDetail {
+        execution_process,
+        failure_detail,
+        environment,
+    })))
 }
 
 pub async fn stream_raw_logs_ws(
@@ -120,6 +291,36 @@ async fn handle_raw_logs_ws(
     Ok(())
 }
 
+/// Server-sent events with the process's output, history first. `stdout` and `stderr` events
+/// carry the chunk as a JSON string; a `finished` event ends the stream.
+pub async fn stream_raw_logs_sse(
+    State(deployment): State<DeploymentImpl>,
+    Path(exec_id): Path<Uuid>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let stream = deployment
+        .container()
+        .stream_raw_logs(&exec_id)
+        .await
+        .ok_or_else(|| {
+            ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound)
+        })?;
+
+    let events = stream.map(|item| {
+        let event = match item {
+            Ok(LogMsg::Stdout(content)) => Event::default().event("stdout").json_data(content),
+            Ok(LogMsg::Stderr(content)) => Event::default().event("stderr").json_data(content),
+            // The raw stream only carries output and the finished marker
+            Ok(_) => Ok(Event::default().event("finished").data("")),
+            Err(e) => Event::default().event("error").json_data(e.to_string()),
+        };
+        Ok::<_, Infallible>(event.unwrap_or_else(|e| {
+            tracing::error!("Failed to serialize raw log event: {}", e);
+            Event::default().comment("serialization error")
+        }))
+    });
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
 pub async fn stream_normalized_logs_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -133,8 +334,19 @@ pub async fn stream_normalized_logs_ws(
             ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound)
         })?;
 
+    // Bookmarks and notes go first, under `/annotations` next to the entries
+    let annotations =
+        LogEntryAnnotation::find_by_execution_process_id(&deployment.db().pool, exec_id).await?;
+    let annotations =
+        LogMsg::JsonPatch(executors::logs::utils::patch::ConversationPatch::add_field(
+            "annotations",
+            serde_json::to_value(annotations)
+                .map_err(|e| ApiError::Io(std::io::Error::other(e)))?,
+        ));
+
     // Convert the error type to anyhow::Error and turn TryStream -> Stream<Result<_, _>>
-    let stream = stream.err_into::<anyhow::Error>().into_stream();
+    let stream = futures_util::stream::iter([Ok(annotations)])
+        .chain(stream.err_into::<anyhow::Error>().into_stream());
 
     Ok(ws.on_upgrade(move |socket| async move {
         if let Err(e) = handle_normalized_logs_ws(socket, stream).await {
@@ -246,6 +458,59 @@ async fn handle_execution_processes_ws(
     Ok(())
 }
 
+/// Liveness of an execution process's child process.
+#[derive(Debug, Serialize, TS)]
+pub struct ProcessInspection {
+    /// Sampled just now by this instance; otherwise from the last heartbeat
+    pub live: bool,
+    pub pid: Option<i64>,
+    /// Single-letter process state from the OS, e.g. R, S or Z
+    pub state: Option<String>,
+    /// Resident memory of the whole process group
+    pub rss_kb: Option<i64>,
+    /// CPU time used by the process group so far
+    pub cpu_seconds: Option<f64>,
+    #[ts(type = "Date | null")]
+    pub last_heartbeat_at: Option<DateTime<Utc>>,
+}
+
+pub async fn inspect_execution_process(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProcessInspection>>, ApiError> {
+    let heartbeat = ExecutionProcessHeartbeat::find_by_execution_process_id(
+        &deployment.db().pool,
+        execution_process.id,
+    )
+    .await?;
+    let last_heartbeat_at = heartbeat.as_ref().map(|h| h.last_seen_at);
+
+    let inspection = match deployment
+        .container()
+        .sample_child(&execution_process.id)
+        .await
+    {
+        Some(sample) => ProcessInspection {
+            live: true,
+            pid: sample.pid,
+            state: sample.state,
+            rss_kb: sample.rss_kb,
+            cpu_seconds: sample.cpu_seconds,
+            last_heartbeat_at,
+        },
+        None => ProcessInspection {
+            live: false,
+            pid: heartbeat.as_ref().and_then(|h| h.pid),
+            state: heartbeat.as_ref().and_then(|h| h.state.clone()),
+            rss_kb: heartbeat.as_ref().and_then(|h| h.rss_kb),
+            cpu_seconds: heartbeat.as_ref().and_then(|h| h.cpu_seconds),
+            last_heartbeat_at,
+        },
+    };
+
+    Ok(ResponseJson(ApiResponse::success(inspection)))
+}
+
 pub async fn get_execution_process_repo_states(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(deployment): State<DeploymentImpl>,
@@ -256,13 +521,86 @@ pub async fn get_execution_process_repo_states(
     Ok(ResponseJson(ApiResponse::success(repo_states)))
 }
 
+/// Bookmarks and notes on the process's log entries, in log order.
+pub async fn get_log_annotations(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<LogEntryAnnotation>>>, ApiError> {
+    let annotations = LogEntryAnnotation::find_by_execution_process_id(
+        &deployment.db().pool,
+        execution_process.id,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(annotations)))
+}
+
+/// Bookmark a log entry, optionally with a note.
+pub async fn create_log_annotation(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateLogEntryAnnotation>,
+) -> Result<ResponseJson<ApiResponse<LogEntryAnnotation>>, ApiError> {
+    if payload.entry_index < 0 {
+        return Err(ApiError::BadRequest(
+            "entry_index can't be negative".to_string(),
+        ));
+    }
+    let annotation =
+        LogEntryAnnotation::create(&deployment.db().pool, execution_process.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(annotation)))
+}
+
+async fn load_annotation(
+    deployment: &DeploymentImpl,
+    execution_process: &ExecutionProcess,
+    annotation_id: Uuid,
+) -> Result<LogEntryAnnotation, ApiError> {
+    LogEntryAnnotation::find_by_id(&deployment.db().pool, annotation_id)
+        .await?
+        .filter(|annotation| annotation.execution_process_id == execution_process.id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn update_log_annotation(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, annotation_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateLogEntryAnnotation>,
+) -> Result<ResponseJson<ApiResponse<LogEntryAnnotation>>, ApiError> {
+    let annotation = load_annotation(&deployment, &execution_process, annotation_id).await?;
+    let annotation = annotation.update(&deployment.db().pool, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(annotation)))
+}
+
+pub async fn delete_log_annotation(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, annotation_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let annotation = load_annotation(&deployment, &execution_process, annotation_id).await?;
+    LogEntryAnnotation::delete(&deployment.db().pool, annotation.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let workspace_id_router = Router::new()
         .route("/", get(get_execution_process_by_id))
         .route("/stop", post(stop_execution_process))
         .route("/devctl2-url", get(get_devctl2_url))
         .route("/repo-states", get(get_execution_process_repo_states))
+        .route("/inspect", get(inspect_execution_process))
+        .route(
+            "/annotations",
+            get(get_log_annotations).post(create_log_annotation),
+        )
+        .route(
+            "/annotations/{annotation_id}",
+            put(update_log_annotation).delete(delete_log_annotation),
+        )
+        .route("/logs", get(get_raw_logs))
+        .route("/logs/export", get(export_raw_logs))
         .route("/raw-logs/ws", get(stream_raw_logs_ws))
+        .route("/raw-logs/stream", get(stream_raw_logs_sse))
         .route("/normalized-logs/ws", get(stream_normalized_logs_ws))
         .layer(from_fn_with_state(
             deployment.clone(),
@@ -270,6 +608,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         ));
 
     let workspaces_router = Router::new()
+        .route("/", get(get_execution_processes))
         .route("/stream/ws", get(stream_execution_processes_ws))
         .nest("/{id}", workspace_id_router);
 
@@ -0,0 +1,44 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use chrono::{Duration, Utc};
+use db::models::execution_process::ExecutionProcess;
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::executor_stats::{self, ExecutorStats};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const DEFAULT_DAYS: i64 = 90;
+const MAX_DAYS: i64 = 3650;
+
+#[derive(Debug, Deserialize)]
+pub struct ExecutorStatsQuery {
+    /// Only count attempts of this project
+    pub project_id: Option<Uuid>,
+    /// Only count attempts created in the last `days` days
+    pub days: Option<i64>,
+}
+
+pub async fn get_executor_stats(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ExecutorStatsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExecutorStats>>>, ApiError> {
+    let days = query.days.unwrap_or(DEFAULT_DAYS).clamp(1, MAX_DAYS);
+    let since = Utc::now() - Duration::days(days);
+    let runs =
+        ExecutionProcess::find_coding_agent_runs(&deployment.db().pool, query.project_id, since)
+            .await?;
+    Ok(ResponseJson(ApiResponse::success(
+        executor_stats::aggregate(&runs),
+    )))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/executor-stats", get(get_executor_stats))
+}
@@ -0,0 +1,130 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, Query, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    feature_flag::{FeatureFlag, FeatureFlagOverride, SetFeatureFlag},
+    project::Project,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct FeatureFlagInfo {
+    pub flag: FeatureFlag,
+    pub description: String,
+    pub default_enabled: bool,
+    /// Instance override first, then per-project overrides
+    pub overrides: Vec<FeatureFlagOverride>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct FeatureFlagState {
+    pub flag: FeatureFlag,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClearFeatureFlagQuery {
+    pub project_id: Option<Uuid>,
+}
+
+pub async fn list_feature_flags(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<FeatureFlagInfo>>>, ApiError> {
+    let overrides = FeatureFlagOverride::find_all(&deployment.db().pool).await?;
+    let flags = FeatureFlag::ALL
+        .into_iter()
+        .map(|flag| FeatureFlagInfo {
+            flag,
+            description: flag.description().to_string(),
+            default_enabled: flag.default_enabled(),
+            overrides: overrides
+                .iter()
+                .filter(|o| o.flag == flag.to_string())
+                .cloned()
+                .collect(),
+        })
+        .collect();
+    Ok(ResponseJson(ApiResponse::success(flags)))
+}
+
+pub async fn set_feature_flag(
+    State(deployment): State<DeploymentImpl>,
+    Path(flag): Path<FeatureFlag>,
+    Json(payload): Json<SetFeatureFlag>,
+) -> Result<ResponseJson<ApiResponse<FeatureFlagOverride>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if let Some(project_id) = payload.project_id {
+        Project::find_by_id(pool, project_id)
+            .await?
+            .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    }
+    let row = FeatureFlagOverride::set(pool, flag, payload.project_id, payload.enabled).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "feature_flag_set",
+            serde_json::json!({
+                "flag": flag.to_string(),
+                "enabled": payload.enabled,
+                "project_scoped": payload.project_id.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(row)))
+}
+
+pub async fn clear_feature_flag(
+    State(deployment): State<DeploymentImpl>,
+    Path(flag): Path<FeatureFlag>,
+    Query(query): Query<ClearFeatureFlagQuery>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows = FeatureFlagOverride::clear(&deployment.db().pool, flag, query.project_id).await?;
+    if rows == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Effective value of every flag for a project.
+pub async fn get_project_feature_flags(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<Vec<FeatureFlagState>>> {
+    let pool = &deployment.db().pool;
+    let mut flags = Vec::with_capacity(FeatureFlag::ALL.len());
+    for flag in FeatureFlag::ALL {
+        flags.push(FeatureFlagState {
+            flag,
+            enabled: flag.is_enabled(pool, Some(project.id)).await,
+        });
+    }
+    ResponseJson(ApiResponse::success(flags))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_router = Router::new()
+        .route("/feature-flags", get(get_project_feature_flags))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new()
+        .route("/admin/feature-flags", get(list_feature_flags))
+        .route(
+            "/admin/feature-flags/{flag}",
+            put(set_feature_flag).delete(clear_feature_flag),
+        )
+        .nest("/projects/{id}", project_router)
+}
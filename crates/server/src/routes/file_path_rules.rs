@@ -0,0 +1,98 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    file_path_rule::{CreateFilePathRule, FilePathRule, UpdateFilePathRule},
+    project::Project,
+};
+use deployment::Deployment;
+use services::services::approvals::path_rules;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+fn validate_pattern(pattern: &str) -> Result<(), ApiError> {
+    if pattern.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "File path rules need a pattern".to_string(),
+        ));
+    }
+    path_rules::compile(pattern)
+        .map(|_| ())
+        .map_err(|e| ApiError::BadRequest(format!("Invalid pattern '{pattern}': {e}")))
+}
+
+pub async fn get_file_path_rules(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<FilePathRule>>>, ApiError> {
+    let rules = FilePathRule::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(rules)))
+}
+
+pub async fn create_file_path_rule(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateFilePathRule>,
+) -> Result<ResponseJson<ApiResponse<FilePathRule>>, ApiError> {
+    validate_pattern(&payload.pattern)?;
+    let rule = FilePathRule::create(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+async fn load_rule(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    rule_id: Uuid,
+) -> Result<FilePathRule, ApiError> {
+    FilePathRule::find_by_id(&deployment.db().pool, rule_id)
+        .await?
+        .filter(|rule| rule.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn update_file_path_rule(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, rule_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateFilePathRule>,
+) -> Result<ResponseJson<ApiResponse<FilePathRule>>, ApiError> {
+    let existing = load_rule(&deployment, project_id, rule_id).await?;
+    validate_pattern(payload.pattern.as_deref().unwrap_or(&existing.pattern))?;
+    let rule = existing.update(&deployment.db().pool, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+pub async fn delete_file_path_rule(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, rule_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let existing = load_rule(&deployment, project_id, rule_id).await?;
+    FilePathRule::delete(&deployment.db().pool, existing.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_rules_router = Router::new()
+        .route(
+            "/file-path-rules",
+            get(get_file_path_rules).post(create_file_path_rule),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    let projects_router = Router::new()
+        .route(
+            "/{project_id}/file-path-rules/{rule_id}",
+            put(update_file_path_rule).delete(delete_file_path_rule),
+        )
+        .nest("/{id}", project_rules_router);
+
+    Router::new().nest("/projects", projects_router)
+}
@@ -0,0 +1,169 @@
+use axum::{
+    Extension, Json, Router,
+    body::Bytes,
+    extract::{Path, State},
+    http::HeaderMap,
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{github_issue::GitHubIssueSync, project::Project};
+use deployment::{Deployment, DeploymentError};
+use serde::{Deserialize, Serialize};
+use services::services::github_issues::{self, IssueEventOutcome, IssuesEvent};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+const EVENT_HEADER: &str = "x-github-event";
+const SIGNATURE_HEADER: &str = "x-hub-signature-256";
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateGitHubIssueSync {
+    /// `owner/name` of the repository
+    pub repo_full_name: String,
+    pub label_filter: Option<String>,
+    /// Secret entered in the repository's webhook settings
+    pub webhook_secret: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct GitHubWebhookResponse {
+    pub outcome: IssueEventOutcome,
+    pub task_id: Option<Uuid>,
+}
+
+fn is_valid_repo(repo_full_name: &str) -> bool {
+    matches!(
+        repo_full_name.split('/').collect::<Vec<_>>().as_slice(),
+        [owner, name] if !owner.is_empty() && !name.is_empty()
+    )
+}
+
+pub async fn get_github_issue_sync(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<GitHubIssueSync>>>, ApiError> {
+    let sync = GitHubIssueSync::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(sync)))
+}
+
+pub async fn update_github_issue_sync(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateGitHubIssueSync>,
+) -> Result<ResponseJson<ApiResponse<GitHubIssueSync>>, ApiError> {
+    let repo_full_name = payload.repo_full_name.trim();
+    if !is_valid_repo(repo_full_name) {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid repository '{repo_full_name}': use owner/name"
+        )));
+    }
+    if payload.webhook_secret.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Webhook secret is required".to_string(),
+        ));
+    }
+    let webhook_secret = deployment
+        .project_env()
+        .cipher()
+        .encrypt(&payload.webhook_secret)
+        .map_err(DeploymentError::from)?;
+    let label_filter = payload
+        .label_filter
+        .as_deref()
+        .map(str::trim)
+        .filter(|label| !label.is_empty());
+
+    let sync = GitHubIssueSync::upsert(
+        &deployment.db().pool,
+        project.id,
+        repo_full_name,
+        label_filter,
+        &webhook_secret,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(sync)))
+}
+
+pub async fn delete_github_issue_sync(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    GitHubIssueSync::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Receives `issues` events from the repository's webhook.
+pub async fn receive_github_webhook(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<ResponseJson<ApiResponse<GitHubWebhookResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let sync = GitHubIssueSync::find_by_project_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+    let secret = deployment
+        .project_env()
+        .cipher()
+        .decrypt(&sync.webhook_secret)
+        .map_err(DeploymentError::from)?;
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if !github_issues::verify_signature(secret.as_bytes(), signature, &body) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let event = headers.get(EVENT_HEADER).and_then(|v| v.to_str().ok());
+    if event != Some("issues") {
+        // Includes the `ping` GitHub sends when the webhook is created
+        return Ok(ResponseJson(ApiResponse::success(GitHubWebhookResponse {
+            outcome: IssueEventOutcome::Ignored,
+            task_id: None,
+        })));
+    }
+
+    let event: IssuesEvent = serde_json::from_slice(&body)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid issues event: {e}")))?;
+    let (outcome, task_id) = github_issues::apply_issues_event(pool, &sync, &event).await?;
+
+    if outcome == IssueEventOutcome::Created {
+        deployment
+            .track_if_analytics_allowed(
+                "task_created_from_github_issue",
+                serde_json::json!({
+                    "project_id": project_id.to_string(),
+                }),
+            )
+            .await;
+    }
+
+    Ok(ResponseJson(ApiResponse::success(GitHubWebhookResponse {
+        outcome,
+        task_id,
+    })))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_sync_router = Router::new()
+        .route(
+            "/github-issues",
+            get(get_github_issue_sync)
+                .put(update_github_issue_sync)
+                .delete(delete_github_issue_sync),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new()
+        .route("/hooks/github/{project_id}", post(receive_github_webhook))
+        .nest("/projects/{id}", project_sync_router)
+}
@@ -1,6 +1,17 @@
-use axum::response::Json;
+use axum::{extract::State, response::Json};
+use deployment::Deployment;
+use services::services::backup::BackupStatus;
 use utils::response::ApiResponse;
 
+use crate::DeploymentImpl;
+
 pub async fn health_check() -> Json<ApiResponse<String>> {
     Json(ApiResponse::success("OK".to_string()))
 }
+
+/// Last successful scheduled backup and the next planned run.
+pub async fn backup_status(
+    State(deployment): State<DeploymentImpl>,
+) -> Json<ApiResponse<BackupStatus>> {
+    Json(ApiResponse::success(deployment.backups().status().await))
+}
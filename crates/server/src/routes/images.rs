@@ -3,7 +3,7 @@ use std::path::Path as StdPath;
 use axum::{
     Router,
     body::Body,
-    extract::{DefaultBodyLimit, Multipart, Path, Query, State},
+    extract::{Multipart, Path, Query, State},
     http::{StatusCode, header},
     response::{Json as ResponseJson, Response},
     routing::{delete, get, post},
@@ -17,13 +17,11 @@ use deployment::Deployment;
 use serde::{Deserialize, Serialize};
 use services::services::image::ImageError;
 use sqlx::Error as SqlxError;
-use tokio::fs::File;
-use tokio_util::io::ReaderStream;
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{DeploymentImpl, error::ApiError, middleware::upload_body_limit};
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct ImageResponse {
@@ -148,13 +146,8 @@ pub async fn serve_image(
         .get_image(image_id)
         .await?
         .ok_or_else(|| ApiError::Image(ImageError::NotFound))?;
-    let file_path = image_service.get_absolute_path(&image);
-
-    let file = File::open(&file_path).await?;
-    let metadata = file.metadata().await?;
-
-    let stream = ReaderStream::new(file);
-    let body = Body::from_stream(stream);
+    let blob = image_service.open_image(&image).await?;
+    let body = Body::from_stream(blob.stream);
 
     let content_type = image
         .mime_type
@@ -164,7 +157,7 @@ pub async fn serve_image(
     let response = Response::builder()
         .status(StatusCode::OK)
         .header(header::CONTENT_TYPE, content_type)
-        .header(header::CONTENT_LENGTH, metadata.len())
+        .header(header::CONTENT_LENGTH, blob.size)
         .header(header::CACHE_CONTROL, "public, max-age=31536000") // Cache for 1 year
         .body(body)
         .map_err(|e| ApiError::Image(ImageError::ResponseBuildError(e.to_string())))?;
@@ -255,16 +248,13 @@ pub async fn get_task_image_metadata(
 
 pub fn routes() -> Router<DeploymentImpl> {
     Router::new()
-        .route(
-            "/upload",
-            post(upload_image).layer(DefaultBodyLimit::max(20 * 1024 * 1024)), // 20MB limit
-        )
+        .route("/upload", post(upload_image).layer(upload_body_limit()))
         .route("/{id}/file", get(serve_image))
         .route("/{id}", delete(delete_image))
         .route("/task/{task_id}", get(get_task_images))
         .route("/task/{task_id}/metadata", get(get_task_image_metadata))
         .route(
             "/task/{task_id}/upload",
-            post(upload_task_image).layer(DefaultBodyLimit::max(20 * 1024 * 1024)),
+            post(upload_task_image).layer(upload_body_limit()),
         )
 }
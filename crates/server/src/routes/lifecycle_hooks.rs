@@ -0,0 +1,83 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    project::Project,
+    project_lifecycle_hook::{LifecycleHook, ProjectLifecycleHook, UpsertProjectLifecycleHook},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+pub async fn get_lifecycle_hooks(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectLifecycleHook>>>, ApiError> {
+    let hooks = ProjectLifecycleHook::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(hooks)))
+}
+
+pub async fn upsert_lifecycle_hook(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, hook)): Path<(Uuid, LifecycleHook)>,
+    Json(payload): Json<UpsertProjectLifecycleHook>,
+) -> Result<ResponseJson<ApiResponse<ProjectLifecycleHook>>, ApiError> {
+    if payload.command.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Hook command must not be empty".to_string(),
+        ));
+    }
+    let pool = &deployment.db().pool;
+    Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let hook = ProjectLifecycleHook::upsert(pool, project_id, hook, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_lifecycle_hook_updated",
+            serde_json::json!({
+                "project_id": project_id.to_string(),
+                "hook": hook.hook.to_string(),
+                "blocking": hook.blocking,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(hook)))
+}
+
+pub async fn delete_lifecycle_hook(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, hook)): Path<(Uuid, LifecycleHook)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows = ProjectLifecycleHook::delete(&deployment.db().pool, project_id, hook).await?;
+    if rows == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_hooks_router = Router::new()
+        .route("/lifecycle-hooks", get(get_lifecycle_hooks))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    let projects_router = Router::new()
+        .route(
+            "/{project_id}/lifecycle-hooks/{hook}",
+            put(upsert_lifecycle_hook).delete(delete_lifecycle_hook),
+        )
+        .nest("/{id}", project_hooks_router);
+
+    Router::new().nest("/projects", projects_router)
+}
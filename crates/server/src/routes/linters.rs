@@ -0,0 +1,87 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    project::Project,
+    project_linter::{Linter, ProjectLinter, UpsertProjectLinter},
+};
+use deployment::Deployment;
+use services::services::lint;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+pub async fn get_linters(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectLinter>>>, ApiError> {
+    let linters = ProjectLinter::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(linters)))
+}
+
+pub async fn upsert_linter(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, linter)): Path<(Uuid, Linter)>,
+    Json(payload): Json<UpsertProjectLinter>,
+) -> Result<ResponseJson<ApiResponse<ProjectLinter>>, ApiError> {
+    let command = payload
+        .command
+        .as_deref()
+        .map(str::trim)
+        .filter(|command| !command.is_empty())
+        .unwrap_or_else(|| lint::default_command(linter));
+    let pool = &deployment.db().pool;
+    Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let linter =
+        ProjectLinter::upsert(pool, project_id, linter, command, payload.fix_follow_up).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_linter_updated",
+            serde_json::json!({
+                "project_id": project_id.to_string(),
+                "linter": linter.linter.to_string(),
+                "fix_follow_up": linter.fix_follow_up,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(linter)))
+}
+
+pub async fn delete_linter(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, linter)): Path<(Uuid, Linter)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows = ProjectLinter::delete(&deployment.db().pool, project_id, linter).await?;
+    if rows == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_linters_router =
+        Router::new()
+            .route("/linters", get(get_linters))
+            .layer(from_fn_with_state(
+                deployment.clone(),
+                load_project_middleware,
+            ));
+
+    let projects_router = Router::new()
+        .route(
+            "/{project_id}/linters/{linter}",
+            put(upsert_linter).delete(delete_linter),
+        )
+        .nest("/{id}", project_linters_router);
+
+    Router::new().nest("/projects", projects_router)
+}
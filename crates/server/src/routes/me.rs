@@ -0,0 +1,179 @@
+use std::collections::HashSet;
+
+use axum::{Extension, Router, extract::State, response::Json as ResponseJson, routing::get};
+use chrono::{DateTime, Utc};
+use db::models::{
+    execution_process::ExecutionProcess, task::Task, task_assignee::TaskAssignee,
+    task_status_change::InReviewTask, team::Team,
+};
+use deployment::Deployment;
+use serde::Serialize;
+use services::services::stale_tasks::{self, StaleTask};
+use ts_rs::TS;
+use utils::{
+    approvals::ApprovalRequest, response::ApiResponse, user_questions::UserQuestionRequest,
+};
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::CurrentUser,
+    routes::{approvals, user_questions},
+};
+
+/// The task and project an agent request belongs to
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct WorkItemContext {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub task_title: String,
+    pub project_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct MyPendingQuestion {
+    pub question: UserQuestionRequest,
+    pub context: WorkItemContext,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct MyPendingApproval {
+    pub approval: ApprovalRequest,
+    pub context: WorkItemContext,
+}
+
+/// A task whose attempt is in review, waiting for someone to look at it
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct AwaitingReviewTask {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub title: String,
+    #[ts(type = "Date")]
+    pub in_review_since: DateTime<Utc>,
+}
+
+/// Everything waiting on the current user across the projects they can see
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct MyWork {
+    /// Open tasks assigned to the user
+    pub assigned_tasks: Vec<Task>,
+    /// Questions agents are waiting on, except those another reviewer has claimed
+    pub questions: Vec<MyPendingQuestion>,
+    pub approvals: Vec<MyPendingApproval>,
+    pub awaiting_review: Vec<AwaitingReviewTask>,
+    pub stale: Vec<StaleTask>,
+}
+
+/// Projects of the user's teams; `None` means every project, as in single-user mode or when
+/// the user is not on any team.
+async fn visible_project_ids(
+    deployment: &DeploymentImpl,
+    user_id: Option<Uuid>,
+) -> Result<Option<HashSet<Uuid>>, ApiError> {
+    let Some(user_id) = user_id else {
+        return Ok(None);
+    };
+    let pool = &deployment.db().pool;
+    let teams = Team::find_by_user_id(pool, user_id).await?;
+    if teams.is_empty() {
+        return Ok(None);
+    }
+    let mut ids = HashSet::new();
+    for team in teams {
+        let projects = Team::find_projects(pool, team.id).await?;
+        ids.extend(projects.into_iter().map(|project| project.id));
+    }
+    Ok(Some(ids))
+}
+
+fn is_visible(project_ids: &Option<HashSet<Uuid>>, project_id: Uuid) -> bool {
+    project_ids
+        .as_ref()
+        .is_none_or(|ids| ids.contains(&project_id))
+}
+
+/// Task and project of the execution process, if it is known to this instance and visible
+async fn work_item_context(
+    deployment: &DeploymentImpl,
+    project_ids: &Option<HashSet<Uuid>>,
+    execution_process_id: Uuid,
+) -> Option<WorkItemContext> {
+    let ctx = ExecutionProcess::load_context(&deployment.db().pool, execution_process_id)
+        .await
+        .ok()?;
+    is_visible(project_ids, ctx.project.id).then_some(WorkItemContext {
+        task_id: ctx.task.id,
+        project_id: ctx.project.id,
+        task_title: ctx.task.title,
+        project_name: ctx.project.name,
+    })
+}
+
+/// Personal inbox: assigned tasks, questions and approvals awaiting an answer, attempts awaiting
+/// review and stale tasks, in a single read.
+pub async fn get_my_work(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<MyWork>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let user_id = user.as_ref().map(|user| user.id);
+    let project_ids = visible_project_ids(&deployment, user_id).await?;
+
+    let assigned_tasks = match user_id {
+        Some(user_id) => TaskAssignee::find_open_tasks_by_user_id(pool, user_id).await?,
+        None => Vec::new(),
+    };
+
+    let mut questions = Vec::new();
+    for question in user_questions::all_pending(&deployment).await {
+        if question.claimed_by_other(user_id).is_some() {
+            continue;
+        }
+        if let Some(context) =
+            work_item_context(&deployment, &project_ids, question.execution_process_id).await
+        {
+            questions.push(MyPendingQuestion { question, context });
+        }
+    }
+
+    let mut approvals = Vec::new();
+    for approval in approvals::all_pending(&deployment).await {
+        if let Some(context) =
+            work_item_context(&deployment, &project_ids, approval.execution_process_id).await
+        {
+            approvals.push(MyPendingApproval { approval, context });
+        }
+    }
+
+    let awaiting_review = InReviewTask::find_all(pool, None)
+        .await?
+        .into_iter()
+        .filter(|task| is_visible(&project_ids, task.project_id))
+        .map(|task| AwaitingReviewTask {
+            task_id: task.task_id,
+            project_id: task.project_id,
+            title: task.title,
+            in_review_since: task.in_review_since,
+        })
+        .collect();
+
+    let stale_config = deployment.config().read().await.stale_tasks.clone();
+    let stale = stale_tasks::find_stale(pool, &stale_config, None)
+        .await?
+        .into_iter()
+        .filter(|task| is_visible(&project_ids, task.project_id))
+        .collect();
+
+    Ok(ResponseJson(ApiResponse::success(MyWork {
+        assigned_tasks,
+        questions,
+        approvals,
+        awaiting_review,
+        stale,
+    })))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/me/work", get(get_my_work))
+}
@@ -0,0 +1,137 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    milestone::{CreateMilestone, Milestone},
+    project::Project,
+    task::Task,
+};
+use deployment::Deployment;
+use services::services::milestones::{self, MilestoneSummary, MilestoneView};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+async fn load_milestone(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    milestone_id: Uuid,
+) -> Result<Milestone, ApiError> {
+    Milestone::find_by_id(&deployment.db().pool, milestone_id)
+        .await?
+        .filter(|milestone| milestone.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+fn validate(payload: &CreateMilestone) -> Result<(), ApiError> {
+    if payload.title.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "A milestone needs a title".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub async fn get_milestones(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<MilestoneSummary>>>, ApiError> {
+    let summaries = milestones::milestone_summaries(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(summaries)))
+}
+
+pub async fn create_milestone(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateMilestone>,
+) -> Result<ResponseJson<ApiResponse<Milestone>>, ApiError> {
+    validate(&payload)?;
+    let milestone = Milestone::create(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(milestone)))
+}
+
+/// The milestone with its tasks and progress.
+pub async fn get_milestone(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<MilestoneView>>, ApiError> {
+    let milestone = load_milestone(&deployment, project_id, milestone_id).await?;
+    let view = milestones::milestone_view(&deployment.db().pool, milestone).await?;
+    Ok(ResponseJson(ApiResponse::success(view)))
+}
+
+pub async fn update_milestone(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<CreateMilestone>,
+) -> Result<ResponseJson<ApiResponse<Milestone>>, ApiError> {
+    validate(&payload)?;
+    let milestone = load_milestone(&deployment, project_id, milestone_id).await?;
+    let milestone = Milestone::update(&deployment.db().pool, milestone.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(milestone)))
+}
+
+pub async fn delete_milestone(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let milestone = load_milestone(&deployment, project_id, milestone_id).await?;
+    Milestone::delete(&deployment.db().pool, milestone.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Put a task of the project in the milestone, moving it out of any other milestone.
+pub async fn add_milestone_task(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, milestone_id, task_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<MilestoneView>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let milestone = load_milestone(&deployment, project_id, milestone_id).await?;
+    let task = Task::find_by_id(pool, task_id)
+        .await?
+        .filter(|task| task.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    Milestone::add_task(pool, milestone.id, task.id).await?;
+    let view = milestones::milestone_view(pool, milestone).await?;
+    Ok(ResponseJson(ApiResponse::success(view)))
+}
+
+pub async fn remove_milestone_task(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, milestone_id, task_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<MilestoneView>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let milestone = load_milestone(&deployment, project_id, milestone_id).await?;
+    Milestone::remove_task(pool, milestone.id, task_id).await?;
+    let view = milestones::milestone_view(pool, milestone).await?;
+    Ok(ResponseJson(ApiResponse::success(view)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_milestones_router = Router::new()
+        .route("/milestones", get(get_milestones).post(create_milestone))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    let projects_router = Router::new()
+        .route(
+            "/{project_id}/milestones/{milestone_id}",
+            get(get_milestone)
+                .put(update_milestone)
+                .delete(delete_milestone),
+        )
+        .route(
+            "/{project_id}/milestones/{milestone_id}/tasks/{task_id}",
+            put(add_milestone_task).delete(remove_milestone_task),
+        )
+        .nest("/{id}", project_milestones_router);
+
+    Router::new().nest("/projects", projects_router)
+}
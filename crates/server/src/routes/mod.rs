@@ -1,61 +1,197 @@
 use axum::{
     Router,
-    routing::{IntoMakeService, get},
+    extract::{DefaultBodyLimit, MatchedPath},
+    http::Request,
+    middleware::from_fn_with_state,
+    routing::get,
 };
+use tower_http::trace::{DefaultOnFailure, DefaultOnResponse, TraceLayer};
+use tracing::Level;
 
-use crate::DeploymentImpl;
+use crate::{
+    DeploymentImpl,
+    middleware::{
+        ProxySettings, RateLimiter, RequestLimits, enforce_account_rate_limit, enforce_rate_limit,
+        require_account, resolve_request_origin,
+    },
+};
 
+pub mod accounts;
+pub mod admin;
+pub mod api_tokens;
 pub mod approvals;
+pub mod attachments;
+pub mod auto_start;
+pub mod automations;
+pub mod bash_command_rules;
 pub mod config;
 pub mod containers;
+pub mod dev_servers;
 pub mod filesystem;
 // pub mod github;
+pub mod error_tracker;
+pub mod event_webhooks;
 pub mod events;
 pub mod execution_processes;
+pub mod executor_stats;
+pub mod feature_flags;
+pub mod file_path_rules;
 pub mod frontend;
+pub mod github_issues;
 pub mod health;
 pub mod images;
+pub mod lifecycle_hooks;
+pub mod linters;
+pub mod me;
+pub mod milestones;
+pub mod notifications;
 pub mod notion_import;
 pub mod oauth;
+pub mod oidc;
 pub mod organizations;
+pub mod outbox;
+pub mod permission_policy;
+pub mod project_env_vars;
+pub mod project_mcp_servers;
+pub mod project_templates;
 pub mod projects;
+pub mod prompt_templates;
+pub mod public_share;
+pub mod question_auto_answers;
+pub mod question_escalations;
+pub mod question_stats;
 pub mod repo;
+pub mod reports;
+pub mod review_checklists;
+pub mod review_sla;
+pub mod sandbox;
 pub mod scratch;
+pub mod secrets;
 pub mod sessions;
 pub mod shared_tasks;
+pub mod slack;
+pub mod stale_tasks;
 pub mod tags;
 pub mod task_attempts;
+pub mod task_breakdown;
+pub mod task_sources;
+pub mod task_transition_rules;
 pub mod tasks;
+pub mod teams;
 pub mod user_questions;
+pub mod users;
+pub mod webhooks;
 
-pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
+pub fn router(deployment: DeploymentImpl, proxy: ProxySettings, limits: RequestLimits) -> Router {
     // Create routers with different middleware layers
-    let base_routes = Router::new()
+    let mut base_routes = Router::new()
         .route("/health", get(health::health_check))
+        .route("/health/backup", get(health::backup_status))
         .merge(config::router())
         .merge(containers::router(&deployment))
+        .merge(dev_servers::router(&deployment))
         .merge(projects::router(&deployment))
+        .merge(project_env_vars::router(&deployment))
+        .merge(project_mcp_servers::router(&deployment))
+        .merge(project_templates::router(&deployment))
+        .merge(prompt_templates::router())
+        .merge(lifecycle_hooks::router(&deployment))
+        .merge(linters::router(&deployment))
+        .merge(sandbox::router(&deployment))
+        .merge(permission_policy::router(&deployment))
+        .merge(secrets::router(&deployment))
         .merge(tasks::router(&deployment))
+        .merge(attachments::router(&deployment))
+        .merge(task_breakdown::router(&deployment))
         .merge(notion_import::router())
+        .merge(task_sources::router(&deployment))
+        .merge(webhooks::router(&deployment))
+        .merge(github_issues::router(&deployment))
+        .merge(error_tracker::router(&deployment))
+        .merge(auto_start::router(&deployment))
+        .merge(automations::router(&deployment))
+        .merge(slack::router(&deployment))
+        .merge(public_share::router(&deployment))
         .merge(shared_tasks::router())
+        .merge(stale_tasks::router())
+        .merge(me::router())
         .merge(task_attempts::router(&deployment))
         .merge(execution_processes::router(&deployment))
+        .merge(executor_stats::router())
         .merge(tags::router(&deployment))
         .merge(oauth::router())
         .merge(organizations::router())
+        .merge(outbox::router())
         .merge(filesystem::router())
         .merge(repo::router())
+        .merge(review_sla::router())
+        .merge(review_checklists::router(&deployment))
         .merge(events::router(&deployment))
         .merge(approvals::router())
+        .merge(bash_command_rules::router(&deployment))
+        .merge(file_path_rules::router(&deployment))
         .merge(user_questions::router())
+        .merge(question_escalations::router(&deployment))
+        .merge(question_auto_answers::router(&deployment))
+        .merge(task_transition_rules::router(&deployment))
+        .merge(question_stats::router())
+        .merge(reports::router(&deployment))
+        .merge(milestones::router(&deployment))
+        .merge(event_webhooks::router(&deployment))
+        .merge(notifications::router())
+        .merge(accounts::router())
+        .merge(oidc::router())
+        .merge(users::router())
+        .merge(api_tokens::router())
+        .merge(teams::router())
+        .merge(admin::router())
+        .merge(feature_flags::router(&deployment))
         .merge(scratch::router(&deployment))
         .merge(sessions::router(&deployment))
-        .nest("/images", images::routes())
+        .nest("/images", images::routes());
+
+    // Client IPs are throttled before authentication so login attempts are too; signed-in
+    // users and API tokens also get their own budget once validated
+    let rate_limiter = limits
+        .rate_limiting_enabled()
+        .then(|| RateLimiter::new(&limits));
+    if let Some(limiter) = &rate_limiter {
+        base_routes = base_routes.layer(from_fn_with_state(
+            limiter.clone(),
+            enforce_account_rate_limit,
+        ));
+    }
+    base_routes = base_routes.layer(from_fn_with_state(deployment.clone(), require_account));
+    if let Some(limiter) = rate_limiter {
+        base_routes = base_routes.layer(from_fn_with_state(limiter, enforce_rate_limit));
+    }
+
+    // One span per API request; exported when OpenTelemetry is configured
+    let trace_layer = TraceLayer::new_for_http()
+        .make_span_with(|request: &Request<_>| {
+            let route = request
+                .extensions()
+                .get::<MatchedPath>()
+                .map(MatchedPath::as_str)
+                .unwrap_or_default();
+            tracing::info_span!(
+                "http_request",
+                method = %request.method(),
+                route,
+                uri = %request.uri(),
+            )
+        })
+        .on_response(DefaultOnResponse::new().level(Level::DEBUG))
+        .on_failure(DefaultOnFailure::new().level(Level::ERROR));
+
+    let base_routes = base_routes
+        .layer(DefaultBodyLimit::max(limits.max_body_bytes))
+        .layer(trace_layer)
         .with_state(deployment);
 
     Router::new()
         .route("/", get(frontend::serve_frontend_root))
         .route("/{*path}", get(frontend::serve_frontend))
         .nest("/api", base_routes)
-        .into_make_service()
+        .layer(from_fn_with_state(proxy, resolve_request_origin))
 }
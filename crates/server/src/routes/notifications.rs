@@ -0,0 +1,103 @@
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::notification::Notification;
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::container::ContainerService;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationQuery {
+    #[serde(default)]
+    pub unread_only: bool,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct NotificationList {
+    pub notifications: Vec<Notification>,
+    #[ts(type = "number")]
+    pub unread_count: i64,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct UnreadNotificationCount {
+    #[ts(type = "number")]
+    pub unread_count: i64,
+}
+
+pub async fn list_notifications(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<NotificationQuery>,
+) -> Result<ResponseJson<ApiResponse<NotificationList>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let notifications = Notification::find_recent(pool, query.unread_only, limit).await?;
+    let unread_count = Notification::count_unread(pool).await?;
+    Ok(ResponseJson(ApiResponse::success(NotificationList {
+        notifications,
+        unread_count,
+    })))
+}
+
+pub async fn mark_notification_read(
+    Path(notification_id): Path<Uuid>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<UnreadNotificationCount>>, ApiError> {
+    let unread_count = deployment
+        .container()
+        .notification_service()
+        .mark_read(notification_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Notification not found".to_string()))?;
+    Ok(ResponseJson(ApiResponse::success(
+        UnreadNotificationCount { unread_count },
+    )))
+}
+
+pub async fn mark_all_notifications_read(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<UnreadNotificationCount>>, ApiError> {
+    let unread_count = deployment
+        .container()
+        .notification_service()
+        .mark_all_read()
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(
+        UnreadNotificationCount { unread_count },
+    )))
+}
+
+/// Delete every notification.
+pub async fn clear_notifications(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<UnreadNotificationCount>>, ApiError> {
+    let unread_count = deployment
+        .container()
+        .notification_service()
+        .clear()
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(
+        UnreadNotificationCount { unread_count },
+    )))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let notifications_router = Router::new()
+        .route("/", get(list_notifications).delete(clear_notifications))
+        .route("/read-all", post(mark_all_notifications_read))
+        .route("/{notification_id}/read", post(mark_notification_read));
+
+    Router::new().nest("/notifications", notifications_router)
+}
@@ -4,10 +4,15 @@ use axum::{
     response::Json as ResponseJson,
     routing::{get, post},
 };
-use db::models::task::{CreateTask, Task, TaskStatus};
+use chrono::{DateTime, Utc};
+use db::models::task::{Task, TaskStatus};
 use deployment::Deployment;
-use local_deployment::{NotionTask, RedisClientError};
+use local_deployment::{NOTION_TASKS_KEY, NotionTask, RedisClientError};
 use serde::{Deserialize, Serialize};
+use services::services::{
+    task_similarity::SimilarTask,
+    task_sources::{self, SourceTask},
+};
 use std::collections::HashSet;
 use ts_rs::TS;
 use utils::response::ApiResponse;
@@ -27,6 +32,8 @@ pub struct NotionImportPreviewItem {
     pub url: String,
     pub will_import: bool,
     pub skip_reason: Option<String>,
+    /// Most similar existing task when this one looks like a duplicate
+    pub similar_task: Option<SimilarTask>,
 }
 
 /// Preview response
@@ -37,6 +44,11 @@ pub struct NotionImportPreviewResponse {
     pub total_count: usize,
     pub importable_count: usize,
     pub duplicate_count: usize,
+    /// When the tasks were read from Redis
+    #[ts(type = "Date")]
+    pub synced_at: DateTime<Utc>,
+    /// Set when Redis is unreachable and the last synced tasks are shown instead
+    pub sync_error: Option<String>,
 }
 
 /// Import request - which tasks to import
@@ -63,6 +75,25 @@ pub struct NotionImportError {
     pub error: String,
 }
 
+/// Reachability of the Redis deployment that bridges Notion tasks
+#[derive(Debug, Serialize, TS)]
+#[ts(export)]
+pub struct NotionRedisHealth {
+    pub configured: bool,
+    /// "single", "sentinel" or "cluster"
+    pub topology: Option<String>,
+    pub connected: bool,
+    /// Round trip time of a PING
+    #[ts(type = "number | null")]
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+    /// Whether reads are paused after repeated failures
+    pub circuit_open: bool,
+    /// When Notion tasks were last read successfully
+    #[ts(type = "Date | null")]
+    pub last_synced_at: Option<DateTime<Utc>>,
+}
+
 fn map_status_group(status_group: &str) -> TaskStatus {
     match status_group {
         "to_do" => TaskStatus::Todo,
@@ -72,7 +103,17 @@ fn map_status_group(status_group: &str) -> TaskStatus {
     }
 }
 
-fn redis_error_to_api_error(err: RedisClientError) -> ApiError {
+fn to_source_task(task: &NotionTask) -> SourceTask {
+    SourceTask {
+        external_id: task.id.clone(),
+        title: task.title.clone(),
+        description: task.content_markdown.clone(),
+        status: map_status_group(&task.status_group),
+        url: Some(task.url.clone()),
+    }
+}
+
+pub(crate) fn redis_error_to_api_error(err: RedisClientError) -> ApiError {
     match err {
         RedisClientError::NotConfigured => {
             ApiError::BadRequest("Redis not configured. Set REDIS_URL environment variable.".to_string())
@@ -85,6 +126,9 @@ fn redis_error_to_api_error(err: RedisClientError) -> ApiError {
             tracing::error!("Redis parse error: {}", e);
             ApiError::BadRequest(format!("Failed to parse Notion tasks: {}", e))
         }
+        RedisClientError::CircuitOpen(_) | RedisClientError::SubscribeUnsupported(_) => {
+            ApiError::BadRequest(err.to_string())
+        }
     }
 }
 
@@ -100,27 +144,31 @@ pub async fn preview_notion_import(
         ));
     }
 
-    // Fetch from Redis
-    let notion_tasks = redis
+    // Fetch from Redis, falling back to the last synced tasks while it is unreachable
+    let snapshot = redis
         .get_notion_tasks()
         .await
         .map_err(redis_error_to_api_error)?;
+    let notion_tasks = snapshot.items;
 
-    // Get existing task titles for duplicate detection
-    let existing_tasks =
-        Task::find_by_project_id_with_attempt_status(&deployment.db().pool, project_id).await?;
-
-    let existing_titles: HashSet<String> = existing_tasks
-        .iter()
-        .map(|t| t.title.to_lowercase())
-        .collect();
+    // Compare against existing tasks by embedding similarity for duplicate detection
+    let source_tasks: Vec<SourceTask> = notion_tasks.iter().map(to_source_task).collect();
+    let config = deployment.config().read().await.embeddings.clone();
+    let duplicates = task_sources::find_duplicates(
+        &deployment.db().pool,
+        deployment.llm(),
+        &config,
+        project_id,
+        &source_tasks,
+    )
+    .await?;
 
     // Build preview
     let mut preview_items = Vec::new();
     let mut duplicate_count = 0;
 
-    for task in notion_tasks {
-        let is_duplicate = existing_titles.contains(&task.title.to_lowercase());
+    for (task, similar_task) in notion_tasks.into_iter().zip(duplicates) {
+        let is_duplicate = similar_task.is_some();
         if is_duplicate {
             duplicate_count += 1;
         }
@@ -133,11 +181,8 @@ pub async fn preview_notion_import(
             status: map_status_group(&task.status_group),
             url: task.url.clone(),
             will_import: !is_duplicate,
-            skip_reason: if is_duplicate {
-                Some("Task with same title already exists".to_string())
-            } else {
-                None
-            },
+            skip_reason: similar_task.as_ref().map(task_sources::duplicate_reason),
+            similar_task,
         });
     }
 
@@ -150,6 +195,8 @@ pub async fn preview_notion_import(
             total_count,
             importable_count,
             duplicate_count,
+            synced_at: snapshot.synced_at,
+            sync_error: snapshot.sync_error,
         },
     )))
 }
@@ -170,7 +217,8 @@ pub async fn execute_notion_import(
     let notion_tasks = redis
         .get_notion_tasks()
         .await
-        .map_err(redis_error_to_api_error)?;
+        .map_err(redis_error_to_api_error)?
+        .items;
 
     // Create a set of requested IDs for efficient lookup
     let requested_ids: HashSet<&str> = request.notion_ids.iter().map(|s| s.as_str()).collect();
@@ -185,15 +233,7 @@ pub async fn execute_notion_import(
     let mut errors = Vec::new();
 
     for notion_task in tasks_to_import {
-        let create_task = CreateTask {
-            project_id,
-            title: notion_task.title.clone(),
-            description: notion_task.content_markdown.clone(),
-            status: Some(map_status_group(&notion_task.status_group)),
-            parent_workspace_id: None,
-            image_ids: None,
-            shared_task_id: None,
-        };
+        let create_task = to_source_task(notion_task).to_create_task(project_id);
 
         match Task::create(&deployment.db().pool, &create_task, Uuid::new_v4()).await {
             Ok(_) => imported_count += 1,
@@ -214,8 +254,52 @@ pub async fn execute_notion_import(
     })))
 }
 
+pub async fn notion_redis_health(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<NotionRedisHealth>>, ApiError> {
+    let redis = deployment.redis_client();
+    let topology = redis.topology().map(|t| t.name().to_string());
+    let circuit_open = redis.circuit_retry_in().is_some();
+    let last_synced_at = redis.last_synced_at(NOTION_TASKS_KEY);
+    let health = if !redis.is_configured() {
+        NotionRedisHealth {
+            configured: false,
+            topology,
+            connected: false,
+            latency_ms: None,
+            error: None,
+            circuit_open,
+            last_synced_at,
+        }
+    } else {
+        match redis.ping().await {
+            Ok(latency) => NotionRedisHealth {
+                configured: true,
+                topology,
+                connected: true,
+                latency_ms: Some(latency.as_millis() as u64),
+                error: None,
+                circuit_open,
+                last_synced_at,
+            },
+            Err(e) => NotionRedisHealth {
+                configured: true,
+                topology,
+                connected: false,
+                latency_ms: None,
+                error: Some(e.to_string()),
+                circuit_open,
+                last_synced_at,
+            },
+        }
+    };
+
+    Ok(ResponseJson(ApiResponse::success(health)))
+}
+
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
+        .route("/import/notion/health", get(notion_redis_health))
         .route(
             "/projects/{project_id}/import/notion/preview",
             get(preview_notion_import),
@@ -0,0 +1,91 @@
+use axum::{
+    Extension, Router,
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Redirect},
+    routing::get,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+
+use crate::{
+    DeploymentImpl, error::ApiError, middleware::RequestOrigin, routes::accounts::session_cookie,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    /// Set by the provider when the user cancelled or was refused
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+fn ensure_enabled(deployment: &DeploymentImpl) -> Result<(), ApiError> {
+    if !deployment.accounts().enabled() {
+        return Err(ApiError::BadRequest(
+            "Multi-user mode is not enabled".to_string(),
+        ));
+    }
+    if !deployment.oidc().enabled() {
+        return Err(ApiError::BadRequest(
+            "Single sign-on is not configured".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Send the browser to the identity provider to sign in.
+pub async fn oidc_login(
+    State(deployment): State<DeploymentImpl>,
+    Extension(origin): Extension<RequestOrigin>,
+) -> Result<Redirect, ApiError> {
+    ensure_enabled(&deployment)?;
+    let oidc = deployment.oidc();
+    let redirect_url = oidc.redirect_url(origin.base_url().as_deref())?;
+    let authorization_url = oidc.begin_login(&redirect_url).await?;
+    Ok(Redirect::to(&authorization_url))
+}
+
+/// The identity provider's redirect back after sign-in. Opens a session and returns to the app.
+pub async fn oidc_callback(
+    State(deployment): State<DeploymentImpl>,
+    Extension(origin): Extension<RequestOrigin>,
+    Query(query): Query<CallbackQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    ensure_enabled(&deployment)?;
+    if let Some(error) = query.error {
+        let description = query.error_description.unwrap_or_default();
+        tracing::info!("Identity provider refused sign-in: {error} {description}");
+        return Err(ApiError::Forbidden(format!(
+            "Sign-in was refused by the identity provider: {error}"
+        )));
+    }
+    let (Some(code), Some(state)) = (query.code, query.state) else {
+        return Err(ApiError::BadRequest(
+            "Missing code or state in the sign-in callback".to_string(),
+        ));
+    };
+
+    let pool = &deployment.db().pool;
+    let accounts = deployment.accounts();
+    let user = deployment
+        .oidc()
+        .complete_login(pool, accounts, &code, &state)
+        .await?;
+    let token = accounts.issue_session(pool, &user).await?;
+
+    Ok((
+        [(
+            header::SET_COOKIE,
+            session_cookie(&deployment, &origin, &token),
+        )],
+        Redirect::to(&format!("{}/", origin.prefix)),
+    ))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/accounts/oidc/login", get(oidc_login))
+        .route("/accounts/oidc/callback", get(oidc_callback))
+}
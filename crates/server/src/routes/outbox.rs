@@ -0,0 +1,53 @@
+use axum::{
+    Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get, post},
+};
+use db::models::integration_outbox::IntegrationOutboxEntry;
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Integration actions waiting for GitHub or Slack to become reachable again.
+pub async fn list_outbox(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<IntegrationOutboxEntry>>>, ApiError> {
+    let entries = IntegrationOutboxEntry::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
+
+/// Retry an entry on the worker's next pass, including one that ran out of attempts.
+pub async fn retry_outbox_entry(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<IntegrationOutboxEntry>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if IntegrationOutboxEntry::reset(pool, id).await? == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    let entry = IntegrationOutboxEntry::find_by_id(pool, id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    Ok(ResponseJson(ApiResponse::success(entry)))
+}
+
+/// Drop an entry without delivering it.
+pub async fn delete_outbox_entry(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    if IntegrationOutboxEntry::delete(&deployment.db().pool, id).await? == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/outbox", get(list_outbox))
+        .route("/outbox/{id}", delete(delete_outbox_entry))
+        .route("/outbox/{id}/retry", post(retry_outbox_entry))
+}
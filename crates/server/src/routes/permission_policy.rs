@@ -0,0 +1,71 @@
+use axum::{
+    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::get,
+};
+use db::models::{
+    project::Project,
+    project_permission_policy::{ProjectPermissionPolicy, UpsertProjectPermissionPolicy},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+pub async fn get_permission_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ProjectPermissionPolicy>>>, ApiError> {
+    let policy =
+        ProjectPermissionPolicy::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// Cap the permission mode the project's attempts may run with. Attempts started before the
+/// change keep their mode.
+pub async fn upsert_permission_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertProjectPermissionPolicy>,
+) -> Result<ResponseJson<ApiResponse<ProjectPermissionPolicy>>, ApiError> {
+    let policy =
+        ProjectPermissionPolicy::upsert(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_permission_policy_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "max_permission_mode": policy.max_permission_mode.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+pub async fn delete_permission_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows = ProjectPermissionPolicy::delete(&deployment.db().pool, project.id).await?;
+    if rows == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_policy_router = Router::new()
+        .route(
+            "/permission-policy",
+            get(get_permission_policy)
+                .put(upsert_permission_policy)
+                .delete(delete_permission_policy),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{id}", project_policy_router)
+}
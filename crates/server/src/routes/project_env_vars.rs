@@ -0,0 +1,120 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    project::Project,
+    project_env_var::{
+        CreateProjectEnvVar, ProjectEnvVar, ProjectEnvVarResponse, UpdateProjectEnvVar,
+    },
+};
+use deployment::Deployment;
+use services::services::project_env::ProjectEnvError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+pub async fn get_project_env_vars(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectEnvVarResponse>>>, ApiError> {
+    let vars = ProjectEnvVar::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(
+        vars.into_iter().map(ProjectEnvVarResponse::from).collect(),
+    )))
+}
+
+pub async fn create_project_env_var(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProjectEnvVar>,
+) -> Result<ResponseJson<ApiResponse<ProjectEnvVarResponse>>, ApiError> {
+    let var = deployment
+        .project_env()
+        .create(&deployment.db().pool, project.id, &payload)
+        .await
+        .map_err(|e| env_var_error(e, &payload.key))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_env_var_created",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "is_secret": var.is_secret,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(var.into())))
+}
+
+/// Report a duplicate key as a conflict rather than a database failure.
+fn env_var_error(err: ProjectEnvError, key: &str) -> ApiError {
+    match err {
+        ProjectEnvError::Database(sqlx::Error::Database(db_err))
+            if db_err.is_unique_violation() =>
+        {
+            ApiError::Conflict(format!("Environment variable {key} already exists"))
+        }
+        other => other.into(),
+    }
+}
+
+async fn load_env_var(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    var_id: Uuid,
+) -> Result<ProjectEnvVar, ApiError> {
+    ProjectEnvVar::find_by_id(&deployment.db().pool, var_id)
+        .await?
+        .filter(|var| var.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn update_project_env_var(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, var_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateProjectEnvVar>,
+) -> Result<ResponseJson<ApiResponse<ProjectEnvVarResponse>>, ApiError> {
+    let existing = load_env_var(&deployment, project_id, var_id).await?;
+    let var = deployment
+        .project_env()
+        .update(&deployment.db().pool, &existing, &payload)
+        .await
+        .map_err(|e| env_var_error(e, payload.key.as_deref().unwrap_or(&existing.key)))?;
+    Ok(ResponseJson(ApiResponse::success(var.into())))
+}
+
+pub async fn delete_project_env_var(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, var_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let existing = load_env_var(&deployment, project_id, var_id).await?;
+    ProjectEnvVar::delete(&deployment.db().pool, existing.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_env_router = Router::new()
+        .route(
+            "/env-vars",
+            get(get_project_env_vars).post(create_project_env_var),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    let projects_router = Router::new()
+        .route(
+            "/{project_id}/env-vars/{var_id}",
+            put(update_project_env_var).delete(delete_project_env_var),
+        )
+        .nest("/{id}", project_env_router);
+
+    Router::new().nest("/projects", projects_router)
+}
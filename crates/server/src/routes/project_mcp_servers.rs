@@ -0,0 +1,137 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    project::Project,
+    project_mcp_server::{CreateProjectMcpServer, ProjectMcpServer, UpdateProjectMcpServer},
+};
+use deployment::Deployment;
+use executors::mcp_config::{McpServerSpec, McpServerTransport};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+fn validate_server(name: &str, transport: &McpServerTransport) -> Result<(), ApiError> {
+    if !McpServerSpec::is_valid_name(name) {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid MCP server name '{name}': use letters, digits, '-' and '_'"
+        )));
+    }
+    let target_missing = match transport {
+        McpServerTransport::Stdio { command, .. } => command.trim().is_empty(),
+        McpServerTransport::Http { url, .. } => url.trim().is_empty(),
+    };
+    if target_missing {
+        return Err(ApiError::BadRequest(
+            "MCP server needs a command or URL".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn conflict_on_duplicate(err: sqlx::Error, name: &str) -> ApiError {
+    match err {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+            ApiError::Conflict(format!("MCP server {name} already exists"))
+        }
+        other => other.into(),
+    }
+}
+
+pub async fn get_project_mcp_servers(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectMcpServer>>>, ApiError> {
+    let servers = ProjectMcpServer::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(servers)))
+}
+
+pub async fn create_project_mcp_server(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProjectMcpServer>,
+) -> Result<ResponseJson<ApiResponse<ProjectMcpServer>>, ApiError> {
+    validate_server(&payload.name, &payload.transport)?;
+    let server = ProjectMcpServer::create(&deployment.db().pool, project.id, &payload)
+        .await
+        .map_err(|e| conflict_on_duplicate(e, &payload.name))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_mcp_server_created",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "transport": match payload.transport {
+                    McpServerTransport::Stdio { .. } => "stdio",
+                    McpServerTransport::Http { .. } => "http",
+                },
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(server)))
+}
+
+async fn load_mcp_server(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    server_id: Uuid,
+) -> Result<ProjectMcpServer, ApiError> {
+    ProjectMcpServer::find_by_id(&deployment.db().pool, server_id)
+        .await?
+        .filter(|server| server.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn update_project_mcp_server(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, server_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateProjectMcpServer>,
+) -> Result<ResponseJson<ApiResponse<ProjectMcpServer>>, ApiError> {
+    let existing = load_mcp_server(&deployment, project_id, server_id).await?;
+    let name = payload.name.as_deref().unwrap_or(&existing.name);
+    validate_server(
+        name,
+        payload.transport.as_ref().unwrap_or(&existing.transport.0),
+    )?;
+    let server = existing
+        .update(&deployment.db().pool, &payload)
+        .await
+        .map_err(|e| conflict_on_duplicate(e, name))?;
+    Ok(ResponseJson(ApiResponse::success(server)))
+}
+
+pub async fn delete_project_mcp_server(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, server_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let existing = load_mcp_server(&deployment, project_id, server_id).await?;
+    ProjectMcpServer::delete(&deployment.db().pool, existing.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_mcp_router = Router::new()
+        .route(
+            "/mcp-servers",
+            get(get_project_mcp_servers).post(create_project_mcp_server),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    let projects_router = Router::new()
+        .route(
+            "/{project_id}/mcp-servers/{server_id}",
+            put(update_project_mcp_server).delete(delete_project_mcp_server),
+        )
+        .nest("/{id}", project_mcp_router);
+
+    Router::new().nest("/projects", projects_router)
+}
@@ -0,0 +1,144 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{delete, get, post},
+};
+use db::models::{
+    project::{CreateProject, Project},
+    project_env_var::CreateProjectEnvVar,
+    project_template::{CreateProjectFromTemplate, CreateProjectTemplate, ProjectTemplate},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+pub async fn get_project_templates(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectTemplate>>>, ApiError> {
+    let templates = ProjectTemplate::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(templates)))
+}
+
+/// Save the project's configuration as a template, replacing a template with the same name.
+pub async fn save_project_template(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProjectTemplate>,
+) -> Result<ResponseJson<ApiResponse<ProjectTemplate>>, ApiError> {
+    if payload.name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Template name must not be empty".to_string(),
+        ));
+    }
+    let pool = &deployment.db().pool;
+    let config = deployment.project().template_config(pool, &project).await?;
+    let template = ProjectTemplate::upsert(pool, &payload, &config).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_template_saved",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "template_id": template.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+pub async fn delete_project_template(
+    State(deployment): State<DeploymentImpl>,
+    Path(template_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows = ProjectTemplate::delete(&deployment.db().pool, template_id).await?;
+    if rows == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Create a project and apply a template's scripts, env var names, MCP servers and team.
+/// Env vars are created with empty values for the user to fill in.
+pub async fn create_project_from_template(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProjectFromTemplate>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let template = ProjectTemplate::find_by_id(pool, payload.template_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::BadRequest(format!("Unknown project template {}", payload.template_id))
+        })?;
+    let repo_count = payload.repositories.len();
+
+    let project = deployment
+        .project()
+        .create_project(
+            pool,
+            deployment.repo(),
+            CreateProject {
+                name: payload.name,
+                repositories: payload.repositories,
+            },
+        )
+        .await?;
+    let project = deployment
+        .project()
+        .apply_template(pool, &project, &template.config)
+        .await?;
+
+    for var in &template.config.env_vars {
+        deployment
+            .project_env()
+            .create(
+                pool,
+                project.id,
+                &CreateProjectEnvVar {
+                    key: var.key.clone(),
+                    value: String::new(),
+                    is_secret: var.is_secret,
+                },
+            )
+            .await?;
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_created",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "repository_count": repo_count,
+                "trigger": "template",
+                "template_id": template.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_template_router = Router::new()
+        .route("/template", post(save_project_template))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    let projects_router = Router::new()
+        .route("/from-template", post(create_project_from_template))
+        .nest("/{id}", project_template_router);
+
+    Router::new()
+        .nest("/projects", projects_router)
+        .route("/project-templates", get(get_project_templates))
+        .route(
+            "/project-templates/{template_id}",
+            delete(delete_project_template),
+        )
+}
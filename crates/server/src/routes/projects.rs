@@ -16,13 +16,19 @@ use db::models::{
     project::{CreateProject, Project, ProjectError, SearchResult, UpdateProject},
     project_repo::{CreateProjectRepo, ProjectRepo, UpdateProjectRepo},
     repo::Repo,
+    tool_usage::ToolUsage,
 };
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::Deserialize;
 use services::services::{
-    file_search_cache::SearchQuery, project::ProjectServiceError,
+    code_search::{self, CodeSearchQuery, CodeSearchResult},
+    container::ContainerService,
+    file_search_cache::SearchQuery,
+    project::ProjectServiceError,
+    project_stats::{self, ProjectStats},
     remote_client::CreateRemoteProjectPayload,
+    tool_stats::{self, ToolStats},
 };
 use ts_rs::TS;
 use utils::{
@@ -33,6 +39,9 @@ use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
 
+const DEFAULT_TOOL_STATS_DAYS: i64 = 90;
+const MAX_TOOL_STATS_DAYS: i64 = 3650;
+
 #[derive(Deserialize, TS)]
 pub struct LinkToExistingRequest {
     pub remote_project_id: Uuid,
@@ -420,6 +429,78 @@ pub async fn search_project_files(
     }
 }
 
+/// Search the project's repositories by keyword and by embedding similarity, using the code map
+/// built when attempts start.
+pub async fn search_project_code(
+    State(deployment): State<DeploymentImpl>,
+    Extension(project): Extension<Project>,
+    Query(query): Query<CodeSearchQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<CodeSearchResult>>>, ApiError> {
+    if query.q.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Query parameter 'q' is required and cannot be empty".to_string(),
+        ));
+    }
+
+    let repositories = deployment
+        .project()
+        .get_repositories(&deployment.db().pool, project.id)
+        .await?;
+    let embeddings = deployment.config().read().await.embeddings.clone();
+    let results = code_search::search(
+        deployment.container().code_map(),
+        deployment.llm(),
+        &embeddings,
+        &repositories,
+        &query,
+    )
+    .await;
+
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectStatsQuery {
+    /// Weeks reported, counting the current one
+    pub weeks: Option<u32>,
+}
+
+pub async fn get_project_stats(
+    State(deployment): State<DeploymentImpl>,
+    Extension(project): Extension<Project>,
+    Query(query): Query<ProjectStatsQuery>,
+) -> Result<ResponseJson<ApiResponse<ProjectStats>>, ApiError> {
+    let stats = project_stats::project_stats(
+        &deployment.db().pool,
+        project.id,
+        query.weeks.unwrap_or(project_stats::DEFAULT_WEEKS),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(stats)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ToolStatsQuery {
+    /// Only count tool calls made in the last `days` days
+    pub days: Option<i64>,
+}
+
+pub async fn get_project_tool_stats(
+    State(deployment): State<DeploymentImpl>,
+    Extension(project): Extension<Project>,
+    Query(query): Query<ToolStatsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ToolStats>>>, ApiError> {
+    let days = query
+        .days
+        .unwrap_or(DEFAULT_TOOL_STATS_DAYS)
+        .clamp(1, MAX_TOOL_STATS_DAYS);
+    let since = chrono::Utc::now() - chrono::Duration::days(days);
+    let usages = ToolUsage::find_by_project_id(&deployment.db().pool, project.id, since).await?;
+    Ok(ResponseJson(ApiResponse::success(tool_stats::aggregate(
+        &usages,
+    ))))
+}
+
 pub async fn get_project_repositories(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -590,6 +671,9 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         )
         .route("/remote/members", get(get_project_remote_members))
         .route("/search", get(search_project_files))
+        .route("/code-search", get(search_project_code))
+        .route("/stats", get(get_project_stats))
+        .route("/tool-stats", get(get_project_tool_stats))
         .route("/open-editor", post(open_project_in_editor))
         .route(
             "/link",
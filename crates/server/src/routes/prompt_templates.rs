@@ -0,0 +1,136 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::prompt_template::{CreatePromptTemplate, PromptTemplate, PromptTemplateKind};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::prompt_templates::{self, PromptTemplateVariable};
+use sqlx::SqlitePool;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize)]
+pub struct PromptTemplateQuery {
+    /// Only templates shared with every project plus this project's own
+    pub project_id: Option<Uuid>,
+}
+
+/// Templates picked for an attempt of the project, in the order given. Each must exist, be
+/// available to the project and be of the kind the caller applies.
+pub async fn load_prompt_templates(
+    pool: &SqlitePool,
+    template_ids: &[Uuid],
+    project_id: Uuid,
+    kind: PromptTemplateKind,
+) -> Result<Vec<PromptTemplate>, ApiError> {
+    let mut templates = Vec::with_capacity(template_ids.len());
+    for &template_id in template_ids {
+        let template = PromptTemplate::find_by_id(pool, template_id)
+            .await?
+            .filter(|template| template.is_available_to(project_id))
+            .ok_or_else(|| {
+                ApiError::BadRequest(format!("Unknown prompt template {template_id}"))
+            })?;
+        if template.kind != kind {
+            return Err(ApiError::BadRequest(format!(
+                "Prompt template '{}' is a {}, not a {kind}",
+                template.name, template.kind
+            )));
+        }
+        templates.push(template);
+    }
+    Ok(templates)
+}
+
+fn validate(payload: &CreatePromptTemplate) -> Result<(), ApiError> {
+    if payload.name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Template name must not be empty".to_string(),
+        ));
+    }
+    if payload.content.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Template content must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub async fn get_prompt_templates(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<PromptTemplateQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<PromptTemplate>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let templates = match query.project_id {
+        Some(project_id) => PromptTemplate::find_available_to_project(pool, project_id).await?,
+        None => PromptTemplate::find_all(pool).await?,
+    };
+    Ok(ResponseJson(ApiResponse::success(templates)))
+}
+
+/// Variables templates can use, for editors to offer.
+pub async fn get_prompt_template_variables()
+-> Result<ResponseJson<ApiResponse<Vec<PromptTemplateVariable>>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(
+        prompt_templates::variables(),
+    )))
+}
+
+pub async fn create_prompt_template(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreatePromptTemplate>,
+) -> Result<ResponseJson<ApiResponse<PromptTemplate>>, ApiError> {
+    validate(&payload)?;
+    let template = PromptTemplate::create(&deployment.db().pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "prompt_template_created",
+            serde_json::json!({
+                "template_id": template.id.to_string(),
+                "kind": template.kind.to_string(),
+                "shared": template.project_id.is_none(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+pub async fn update_prompt_template(
+    State(deployment): State<DeploymentImpl>,
+    Path(template_id): Path<Uuid>,
+    Json(payload): Json<CreatePromptTemplate>,
+) -> Result<ResponseJson<ApiResponse<PromptTemplate>>, ApiError> {
+    validate(&payload)?;
+    let template = PromptTemplate::update(&deployment.db().pool, template_id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+pub async fn delete_prompt_template(
+    State(deployment): State<DeploymentImpl>,
+    Path(template_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows = PromptTemplate::delete(&deployment.db().pool, template_id).await?;
+    if rows == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let inner = Router::new()
+        .route("/", get(get_prompt_templates).post(create_prompt_template))
+        .route("/variables", get(get_prompt_template_variables))
+        .route(
+            "/{template_id}",
+            put(update_prompt_template).delete(delete_prompt_template),
+        );
+
+    Router::new().nest("/prompt-templates", inner)
+}
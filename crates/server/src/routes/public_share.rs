@@ -0,0 +1,204 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::{Html, Json as ResponseJson},
+    routing::{delete, get},
+};
+use chrono::{Duration, Utc};
+use db::models::{
+    feature_flag::FeatureFlag, public_share_link::PublicShareLink, task::Task, workspace::Workspace,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    attempt_summary::render_diffs,
+    container::ContainerService,
+    public_share::{self, PublicShareError, PublicTaskView},
+};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{RequestOrigin, load_task_middleware},
+};
+
+const DEFAULT_EXPIRY_HOURS: u32 = 7 * 24;
+const MAX_EXPIRY_HOURS: u32 = 30 * 24;
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreatePublicShareLink {
+    /// Attempt whose summary and diff are shown
+    pub workspace_id: Option<Uuid>,
+    /// Files of the attempt's diff to include; none shows the summary only
+    #[serde(default)]
+    pub diff_paths: Vec<String>,
+    /// Defaults to a week; at most 30 days
+    pub expires_in_hours: Option<u32>,
+}
+
+/// A share link with its URL. The URL is only returned when the link is created.
+#[derive(Debug, Serialize, TS)]
+pub struct PublicShareLinkWithUrl {
+    pub link: PublicShareLink,
+    pub url: String,
+}
+
+pub async fn list_public_share_links(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<PublicShareLink>>>, ApiError> {
+    let links = PublicShareLink::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(links)))
+}
+
+pub async fn create_public_share_link(
+    Extension(task): Extension<Task>,
+    Extension(origin): Extension<RequestOrigin>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreatePublicShareLink>,
+) -> Result<ResponseJson<ApiResponse<PublicShareLinkWithUrl>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if !FeatureFlag::PublicShareLinks
+        .is_enabled(pool, Some(task.project_id))
+        .await
+    {
+        return Err(ApiError::Forbidden(
+            "Public share links are disabled for this project".to_string(),
+        ));
+    }
+
+    let hours = payload.expires_in_hours.unwrap_or(DEFAULT_EXPIRY_HOURS);
+    if hours == 0 || hours > MAX_EXPIRY_HOURS {
+        return Err(ApiError::BadRequest(format!(
+            "Links expire after 1 to {MAX_EXPIRY_HOURS} hours"
+        )));
+    }
+
+    let workspace = match payload.workspace_id {
+        Some(workspace_id) => Some(
+            Workspace::find_by_id(pool, workspace_id)
+                .await?
+                .filter(|workspace| workspace.task_id == task.id)
+                .ok_or_else(|| {
+                    ApiError::BadRequest("Attempt does not belong to this task".to_string())
+                })?,
+        ),
+        None => None,
+    };
+    // The diff is captured now so the link keeps working after the worktree is cleaned up
+    let diff = match &workspace {
+        Some(workspace) if !payload.diff_paths.is_empty() => {
+            let diffs = deployment
+                .container()
+                .diff_snapshot(workspace, false)
+                .await?;
+            let diffs = public_share::select_diffs(diffs, &payload.diff_paths);
+            (!diffs.is_empty()).then(|| render_diffs(&diffs))
+        }
+        _ => None,
+    };
+
+    let link = PublicShareLink::create(
+        pool,
+        task.id,
+        workspace.as_ref().map(|workspace| workspace.id),
+        &payload.diff_paths,
+        diff.as_deref(),
+        Utc::now() + Duration::hours(hours.into()),
+    )
+    .await?;
+    let token = public_share::link_token(deployment.project_env().cipher(), &link);
+    let url = format!(
+        "{}/api/public/share/{token}",
+        origin.base_url().unwrap_or_default()
+    );
+
+    deployment
+        .track_if_analytics_allowed(
+            "public_share_link_created",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "has_attempt": link.workspace_id.is_some(),
+                "expires_in_hours": hours,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(PublicShareLinkWithUrl {
+        link,
+        url,
+    })))
+}
+
+pub async fn delete_public_share_link(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, link_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    PublicShareLink::delete(&deployment.db().pool, task_id, link_id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Resolve a token to the view of its link. Revoked links are treated like forged ones.
+async fn load_public_view(
+    deployment: &DeploymentImpl,
+    token: &str,
+) -> Result<PublicTaskView, ApiError> {
+    let link_id =
+        public_share::verify_link_token(deployment.project_env().cipher(), token, Utc::now())?;
+    let pool = &deployment.db().pool;
+    let link = PublicShareLink::find_by_id(pool, link_id)
+        .await?
+        .ok_or(PublicShareError::Invalid)?;
+    // Links of projects with sharing switched off stop resolving until it is re-enabled
+    let task = Task::find_by_id(pool, link.task_id)
+        .await?
+        .ok_or(PublicShareError::Invalid)?;
+    if !FeatureFlag::PublicShareLinks
+        .is_enabled(pool, Some(task.project_id))
+        .await
+    {
+        return Err(PublicShareError::Invalid.into());
+    }
+    public_share::load_view(pool, &link)
+        .await?
+        .ok_or(PublicShareError::Invalid.into())
+}
+
+/// Read-only page for people without an account.
+pub async fn get_public_share_page(
+    State(deployment): State<DeploymentImpl>,
+    Path(token): Path<String>,
+) -> Result<Html<String>, ApiError> {
+    let view = load_public_view(&deployment, &token).await?;
+    Ok(Html(public_share::render_page(&view)))
+}
+
+pub async fn get_public_share_json(
+    State(deployment): State<DeploymentImpl>,
+    Path(token): Path<String>,
+) -> Result<ResponseJson<ApiResponse<PublicTaskView>>, ApiError> {
+    let view = load_public_view(&deployment, &token).await?;
+    Ok(ResponseJson(ApiResponse::success(view)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let task_links_router = Router::new()
+        .route(
+            "/public-links",
+            get(list_public_share_links).post(create_public_share_link),
+        )
+        .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
+
+    Router::new()
+        .nest("/tasks/{task_id}", task_links_router)
+        .route(
+            "/tasks/{task_id}/public-links/{link_id}",
+            delete(delete_public_share_link),
+        )
+        .route("/public/share/{token}", get(get_public_share_page))
+        .route("/public/share/{token}/json", get(get_public_share_json))
+}
@@ -0,0 +1,133 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    project::Project,
+    question_auto_answer_rule::{
+        CreateQuestionAutoAnswerRule, QuestionAutoAnswerRule, UpdateQuestionAutoAnswerRule,
+    },
+};
+use deployment::Deployment;
+use regex::Regex;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+fn validate_rule(
+    name: &str,
+    question_pattern: &str,
+    header_pattern: Option<&str>,
+    option_label: &str,
+) -> Result<(), ApiError> {
+    if name.trim().is_empty() || option_label.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Auto-answer rules need a name and an option label".to_string(),
+        ));
+    }
+    for pattern in std::iter::once(question_pattern).chain(header_pattern) {
+        if let Err(e) = Regex::new(pattern) {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid pattern '{pattern}': {e}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub async fn get_question_auto_answers(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<QuestionAutoAnswerRule>>>, ApiError> {
+    let rules =
+        QuestionAutoAnswerRule::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(rules)))
+}
+
+pub async fn create_question_auto_answer(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<CreateQuestionAutoAnswerRule>,
+) -> Result<ResponseJson<ApiResponse<QuestionAutoAnswerRule>>, ApiError> {
+    payload.header_pattern = payload.header_pattern.filter(|p| !p.is_empty());
+    validate_rule(
+        &payload.name,
+        &payload.question_pattern,
+        payload.header_pattern.as_deref(),
+        &payload.option_label,
+    )?;
+    let rule = QuestionAutoAnswerRule::create(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+async fn load_rule(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    rule_id: Uuid,
+) -> Result<QuestionAutoAnswerRule, ApiError> {
+    QuestionAutoAnswerRule::find_by_id(&deployment.db().pool, rule_id)
+        .await?
+        .filter(|rule| rule.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn update_question_auto_answer(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, rule_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateQuestionAutoAnswerRule>,
+) -> Result<ResponseJson<ApiResponse<QuestionAutoAnswerRule>>, ApiError> {
+    let existing = load_rule(&deployment, project_id, rule_id).await?;
+    let header_pattern = match payload.header_pattern.as_deref() {
+        Some("") => None,
+        Some(pattern) => Some(pattern),
+        None => existing.header_pattern.as_deref(),
+    };
+    validate_rule(
+        payload.name.as_deref().unwrap_or(&existing.name),
+        payload
+            .question_pattern
+            .as_deref()
+            .unwrap_or(&existing.question_pattern),
+        header_pattern,
+        payload
+            .option_label
+            .as_deref()
+            .unwrap_or(&existing.option_label),
+    )?;
+    let rule = existing.update(&deployment.db().pool, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+pub async fn delete_question_auto_answer(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, rule_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let existing = load_rule(&deployment, project_id, rule_id).await?;
+    QuestionAutoAnswerRule::delete(&deployment.db().pool, existing.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_rules_router = Router::new()
+        .route(
+            "/question-auto-answers",
+            get(get_question_auto_answers).post(create_question_auto_answer),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    let projects_router = Router::new()
+        .route(
+            "/{project_id}/question-auto-answers/{rule_id}",
+            put(update_question_auto_answer).delete(delete_question_auto_answer),
+        )
+        .nest("/{id}", project_rules_router);
+
+    Router::new().nest("/projects", projects_router)
+}
@@ -0,0 +1,79 @@
+use axum::{
+    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::get,
+};
+use db::models::{
+    project::Project,
+    question_escalation::{EscalationChannel, ProjectQuestionEscalation, UpdateQuestionEscalation},
+    user::User,
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+pub async fn get_question_escalation(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ProjectQuestionEscalation>>>, ApiError> {
+    let escalation =
+        ProjectQuestionEscalation::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(escalation)))
+}
+
+pub async fn update_question_escalation(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateQuestionEscalation>,
+) -> Result<ResponseJson<ApiResponse<ProjectQuestionEscalation>>, ApiError> {
+    let pool = &deployment.db().pool;
+    for step in &payload.steps {
+        if step.after_minutes == 0 {
+            return Err(ApiError::BadRequest(
+                "Escalation steps must wait at least one minute".to_string(),
+            ));
+        }
+        match &step.channel {
+            EscalationChannel::Notify => {}
+            EscalationChannel::User { user_id } => {
+                if User::find_by_id(pool, *user_id).await?.is_none() {
+                    return Err(ApiError::BadRequest(format!("Unknown user {user_id}")));
+                }
+            }
+            EscalationChannel::Slack { channel_id } => {
+                if channel_id.trim().is_empty() {
+                    return Err(ApiError::BadRequest(
+                        "Slack escalation steps need a channel ID".to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    let escalation = ProjectQuestionEscalation::upsert(pool, project.id, &payload.steps).await?;
+    Ok(ResponseJson(ApiResponse::success(escalation)))
+}
+
+pub async fn delete_question_escalation(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ProjectQuestionEscalation::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_escalation_router = Router::new()
+        .route(
+            "/question-escalation",
+            get(get_question_escalation)
+                .put(update_question_escalation)
+                .delete(delete_question_escalation),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{id}", project_escalation_router)
+}
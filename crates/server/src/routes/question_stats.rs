@@ -0,0 +1,43 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use chrono::{Duration, Utc};
+use db::models::agent_question::AgentQuestion;
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::question_stats::{self, QuestionStats};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const DEFAULT_DAYS: i64 = 90;
+const MAX_DAYS: i64 = 3650;
+
+#[derive(Debug, Deserialize)]
+pub struct QuestionStatsQuery {
+    /// Only count questions from agents of this project
+    pub project_id: Option<Uuid>,
+    /// Only count questions asked in the last `days` days
+    pub days: Option<i64>,
+}
+
+pub async fn get_question_stats(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<QuestionStatsQuery>,
+) -> Result<ResponseJson<ApiResponse<QuestionStats>>, ApiError> {
+    let days = query.days.unwrap_or(DEFAULT_DAYS).clamp(1, MAX_DAYS);
+    let since = Utc::now() - Duration::days(days);
+    let questions =
+        AgentQuestion::find_since(&deployment.db().pool, query.project_id, since).await?;
+    Ok(ResponseJson(ApiResponse::success(
+        question_stats::aggregate(&questions),
+    )))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/question-stats", get(get_question_stats))
+}
@@ -0,0 +1,69 @@
+use axum::{
+    Extension, Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::get,
+};
+use chrono::NaiveDate;
+use db::models::project::Project;
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::flow_reports::{self, BurndownReport, CycleTimeReport, ReportRange};
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+#[derive(Debug, Deserialize)]
+pub struct ReportQuery {
+    /// First day reported; defaults to the 30 days ending on `to`
+    pub from: Option<NaiveDate>,
+    /// Last day reported; defaults to today
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BurndownQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    /// Only count tasks carrying this label, such as a milestone's
+    pub label: Option<String>,
+}
+
+fn resolve_range(from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<ReportRange, ApiError> {
+    ReportRange::resolve(from, to).map_err(ApiError::BadRequest)
+}
+
+pub async fn get_cycle_time(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ReportQuery>,
+) -> Result<ResponseJson<ApiResponse<CycleTimeReport>>, ApiError> {
+    let range = resolve_range(query.from, query.to)?;
+    let report = flow_reports::cycle_time_report(&deployment.db().pool, project.id, range).await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+pub async fn get_burndown(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<BurndownQuery>,
+) -> Result<ResponseJson<ApiResponse<BurndownReport>>, ApiError> {
+    let range = resolve_range(query.from, query.to)?;
+    let label = query.label.filter(|label| !label.trim().is_empty());
+    let report =
+        flow_reports::burndown_report(&deployment.db().pool, project.id, label, range).await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_reports_router = Router::new()
+        .route("/reports/cycle-time", get(get_cycle_time))
+        .route("/reports/burndown", get(get_burndown))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{id}", project_reports_router)
+}
@@ -0,0 +1,95 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    project::Project,
+    review_checklist::{CreateReviewChecklistItem, ReviewChecklistItem, UpdateReviewChecklistItem},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+fn validate_label(label: &str) -> Result<(), ApiError> {
+    if label.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Checklist items need a label".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub async fn get_review_checklist(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ReviewChecklistItem>>>, ApiError> {
+    let items = ReviewChecklistItem::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(items)))
+}
+
+pub async fn create_review_checklist_item(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateReviewChecklistItem>,
+) -> Result<ResponseJson<ApiResponse<ReviewChecklistItem>>, ApiError> {
+    validate_label(&payload.label)?;
+    let item = ReviewChecklistItem::create(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(item)))
+}
+
+async fn load_item(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    item_id: Uuid,
+) -> Result<ReviewChecklistItem, ApiError> {
+    ReviewChecklistItem::find_by_id(&deployment.db().pool, item_id)
+        .await?
+        .filter(|item| item.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn update_review_checklist_item(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, item_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateReviewChecklistItem>,
+) -> Result<ResponseJson<ApiResponse<ReviewChecklistItem>>, ApiError> {
+    let existing = load_item(&deployment, project_id, item_id).await?;
+    validate_label(payload.label.as_deref().unwrap_or(&existing.label))?;
+    let item = existing.update(&deployment.db().pool, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(item)))
+}
+
+pub async fn delete_review_checklist_item(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, item_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let existing = load_item(&deployment, project_id, item_id).await?;
+    ReviewChecklistItem::delete(&deployment.db().pool, existing.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_checklist_router = Router::new()
+        .route(
+            "/review-checklist",
+            get(get_review_checklist).post(create_review_checklist_item),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    let projects_router = Router::new()
+        .route(
+            "/{project_id}/review-checklist/{item_id}",
+            put(update_review_checklist_item).delete(delete_review_checklist_item),
+        )
+        .nest("/{id}", project_checklist_router);
+
+    Router::new().nest("/projects", projects_router)
+}
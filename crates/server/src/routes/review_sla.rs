@@ -0,0 +1,39 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::review_sla::{self, ReviewSlaBreach};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewSlaQuery {
+    pub project_id: Option<Uuid>,
+}
+
+/// Tasks that have waited in review longer than the configured SLA.
+pub async fn list_breaches(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ReviewSlaQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ReviewSlaBreach>>>, ApiError> {
+    let config = deployment.config().read().await.review_sla.clone();
+    let breaches = review_sla::find_breaches(
+        &deployment.db().pool,
+        deployment.approvals(),
+        deployment.user_questions(),
+        &config,
+        query.project_id,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(breaches)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/review-sla/breaches", get(list_breaches))
+}
@@ -0,0 +1,82 @@
+use axum::{
+    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::get,
+};
+use db::models::{
+    project::Project,
+    project_sandbox::{ProjectSandbox, UpsertProjectSandbox},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+pub async fn get_sandbox(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ProjectSandbox>>>, ApiError> {
+    let sandbox = ProjectSandbox::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(sandbox)))
+}
+
+pub async fn upsert_sandbox(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertProjectSandbox>,
+) -> Result<ResponseJson<ApiResponse<ProjectSandbox>>, ApiError> {
+    if payload.image.trim().is_empty() || payload.image.contains(char::is_whitespace) {
+        return Err(ApiError::BadRequest(
+            "Sandbox image must be a single image reference".to_string(),
+        ));
+    }
+    if payload.cpus.is_some_and(|cpus| cpus <= 0.0)
+        || payload.memory_mb.is_some_and(|memory| memory <= 0)
+        || payload.pids_limit.is_some_and(|pids| pids <= 0)
+    {
+        return Err(ApiError::BadRequest(
+            "Sandbox resource limits must be positive".to_string(),
+        ));
+    }
+
+    let sandbox = ProjectSandbox::upsert(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_sandbox_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "enabled": sandbox.enabled,
+                "network": sandbox.network.to_string(),
+                "has_cpu_limit": sandbox.cpus.is_some(),
+                "has_memory_limit": sandbox.memory_mb.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(sandbox)))
+}
+
+pub async fn delete_sandbox(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows = ProjectSandbox::delete(&deployment.db().pool, project.id).await?;
+    if rows == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_sandbox_router = Router::new()
+        .route(
+            "/sandbox",
+            get(get_sandbox).put(upsert_sandbox).delete(delete_sandbox),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new().nest("/projects/{id}", project_sandbox_router)
+}
@@ -0,0 +1,181 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    project::Project,
+    secret::{CreateSecret, Secret, SecretResponse, UpdateSecret},
+};
+use deployment::Deployment;
+use services::services::secret_vault::SecretVaultError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+fn to_responses(secrets: Vec<Secret>) -> Vec<SecretResponse> {
+    secrets.into_iter().map(SecretResponse::from).collect()
+}
+
+async fn create_secret(
+    deployment: &DeploymentImpl,
+    project_id: Option<Uuid>,
+    payload: &CreateSecret,
+) -> Result<Secret, ApiError> {
+    let secret = deployment
+        .secret_vault()
+        .create(&deployment.db().pool, project_id, payload)
+        .await
+        .map_err(|e| match e {
+            SecretVaultError::Database(sqlx::Error::Database(db_err))
+                if db_err.is_unique_violation() =>
+            {
+                ApiError::Conflict(format!("Secret {} already exists", payload.name))
+            }
+            other => other.into(),
+        })?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "secret_created",
+            serde_json::json!({ "project_scoped": project_id.is_some() }),
+        )
+        .await;
+
+    Ok(secret)
+}
+
+pub async fn get_global_secrets(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<SecretResponse>>>, ApiError> {
+    let secrets = Secret::find_global(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(to_responses(secrets))))
+}
+
+pub async fn create_global_secret(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateSecret>,
+) -> Result<ResponseJson<ApiResponse<SecretResponse>>, ApiError> {
+    let secret = create_secret(&deployment, None, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(secret.into())))
+}
+
+pub async fn get_project_secrets(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<SecretResponse>>>, ApiError> {
+    let secrets = Secret::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(to_responses(secrets))))
+}
+
+pub async fn create_project_secret(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateSecret>,
+) -> Result<ResponseJson<ApiResponse<SecretResponse>>, ApiError> {
+    let secret = create_secret(&deployment, Some(project.id), &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(secret.into())))
+}
+
+async fn load_secret(
+    deployment: &DeploymentImpl,
+    project_id: Option<Uuid>,
+    secret_id: Uuid,
+) -> Result<Secret, ApiError> {
+    Secret::find_by_id(&deployment.db().pool, secret_id)
+        .await?
+        .filter(|secret| secret.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+/// Replace a secret's value. Takes effect for the next executor process that starts.
+async fn rotate_secret(
+    deployment: &DeploymentImpl,
+    existing: &Secret,
+    value: &str,
+) -> Result<Secret, ApiError> {
+    let secret = deployment
+        .secret_vault()
+        .update(&deployment.db().pool, existing.id, value)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "secret_rotated",
+            serde_json::json!({ "project_scoped": existing.project_id.is_some() }),
+        )
+        .await;
+
+    Ok(secret)
+}
+
+pub async fn update_global_secret(
+    State(deployment): State<DeploymentImpl>,
+    Path(secret_id): Path<Uuid>,
+    Json(payload): Json<UpdateSecret>,
+) -> Result<ResponseJson<ApiResponse<SecretResponse>>, ApiError> {
+    let existing = load_secret(&deployment, None, secret_id).await?;
+    let secret = rotate_secret(&deployment, &existing, &payload.value).await?;
+    Ok(ResponseJson(ApiResponse::success(secret.into())))
+}
+
+pub async fn delete_global_secret(
+    State(deployment): State<DeploymentImpl>,
+    Path(secret_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let existing = load_secret(&deployment, None, secret_id).await?;
+    Secret::delete(&deployment.db().pool, existing.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn update_project_secret(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, secret_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateSecret>,
+) -> Result<ResponseJson<ApiResponse<SecretResponse>>, ApiError> {
+    let existing = load_secret(&deployment, Some(project_id), secret_id).await?;
+    let secret = rotate_secret(&deployment, &existing, &payload.value).await?;
+    Ok(ResponseJson(ApiResponse::success(secret.into())))
+}
+
+pub async fn delete_project_secret(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, secret_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let existing = load_secret(&deployment, Some(project_id), secret_id).await?;
+    Secret::delete(&deployment.db().pool, existing.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_secrets_router = Router::new()
+        .route(
+            "/secrets",
+            get(get_project_secrets).post(create_project_secret),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    let projects_router = Router::new()
+        .route(
+            "/{project_id}/secrets/{secret_id}",
+            put(update_project_secret).delete(delete_project_secret),
+        )
+        .nest("/{id}", project_secrets_router);
+
+    Router::new()
+        .route(
+            "/secrets",
+            get(get_global_secrets).post(create_global_secret),
+        )
+        .route(
+            "/secrets/{secret_id}",
+            put(update_global_secret).delete(delete_global_secret),
+        )
+        .nest("/projects", projects_router)
+}
@@ -10,6 +10,7 @@ use axum::{
 use db::models::{
     execution_process::{ExecutionProcess, ExecutionProcessRunReason},
     project_repo::ProjectRepo,
+    prompt_template::PromptTemplateKind,
     scratch::{Scratch, ScratchType},
     session::{CreateSession, Session},
     workspace::{Workspace, WorkspaceError},
@@ -22,15 +23,22 @@ use executors::{
     profile::ExecutorProfileId,
 };
 use serde::Deserialize;
-use services::services::container::ContainerService;
+use services::services::{
+    container::ContainerService,
+    model_fallback::{self, ModelSegment},
+};
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_session_middleware,
-    routes::task_attempts::util::restore_worktrees_to_process,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_session_middleware,
+    routes::{
+        prompt_templates::load_prompt_templates, task_attempts::util::restore_worktrees_to_process,
+    },
 };
 
 #[derive(Debug, Deserialize)]
@@ -85,6 +93,16 @@ pub async fn create_session(
     Ok(ResponseJson(ApiResponse::success(session)))
 }
 
+/// Coding agent runs of the session with the model each one ran with, showing where a run
+/// fell back to the next model of the profile's chain
+pub async fn get_model_segments(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ModelSegment>>>, ApiError> {
+    let segments = model_fallback::model_segments(&deployment.db().pool, session.id).await?;
+    Ok(ResponseJson(ApiResponse::success(segments)))
+}
+
 #[derive(Debug, Deserialize, TS)]
 pub struct CreateFollowUpAttempt {
     pub prompt: String,
@@ -92,6 +110,10 @@ pub struct CreateFollowUpAttempt {
     pub retry_process_id: Option<Uuid>,
     pub force_when_dirty: Option<bool>,
     pub perform_git_reset: Option<bool>,
+    /// Follow-up snippet templates added after the message, in order
+    #[serde(default)]
+    #[ts(optional)]
+    pub prompt_template_ids: Option<Vec<Uuid>>,
 }
 
 pub async fn follow_up(
@@ -115,6 +137,16 @@ pub async fn follow_up(
         .ensure_container_exists(&workspace)
         .await?;
 
+    // Check references before any retry resets the worktrees
+    let resolved = deployment
+        .container()
+        .resolve_prompt_references(&workspace, payload.prompt)
+        .await?;
+    if resolved.has_missing() {
+        return Err(ApiError::BadRequest(resolved.missing_message()));
+    }
+    let prompt = resolved.prompt;
+
     // Get executor profile data from the latest CodingAgent process in this session
     let initial_executor_profile_id =
         ExecutionProcess::latest_executor_profile_for_session(pool, session.id).await?;
@@ -136,6 +168,18 @@ pub async fn follow_up(
         .await?
         .ok_or(SqlxError::RowNotFound)?;
 
+    let snippets = load_prompt_templates(
+        pool,
+        payload.prompt_template_ids.as_deref().unwrap_or_default(),
+        project.id,
+        PromptTemplateKind::FollowUpSnippet,
+    )
+    .await?;
+    let prompt = deployment
+        .container()
+        .apply_prompt_templates(&workspace, &task, &snippets, prompt)
+        .await?;
+
     // If retry settings provided, perform replace-logic before proceeding
     if let Some(proc_id) = payload.retry_process_id {
         // Validate process belongs to this session
@@ -174,12 +218,11 @@ pub async fn follow_up(
     let latest_agent_session_id =
         ExecutionProcess::find_latest_coding_agent_turn_session_id(pool, session.id).await?;
 
-    let prompt = payload.prompt;
-
     let project_repos = ProjectRepo::find_by_project_id_with_names(pool, project.id).await?;
-    let cleanup_action = deployment
+    let finish_action = deployment
         .container()
-        .cleanup_actions_for_repos(&project_repos);
+        .finish_actions(project.id, &project_repos)
+        .await?;
 
     let working_dir = workspace
         .agent_working_dir
@@ -193,6 +236,7 @@ pub async fn follow_up(
             session_id: agent_session_id,
             executor_profile_id: executor_profile_id.clone(),
             working_dir: working_dir.clone(),
+            model: None,
         })
     } else {
         ExecutorActionType::CodingAgentInitialRequest(
@@ -200,11 +244,12 @@ pub async fn follow_up(
                 prompt,
                 executor_profile_id: executor_profile_id.clone(),
                 working_dir,
+                model: None,
             },
         )
     };
 
-    let action = ExecutorAction::new(action_type, cleanup_action.map(Box::new));
+    let action = ExecutorAction::new(action_type, finish_action.map(Box::new));
 
     let execution_process = deployment
         .container()
@@ -234,6 +279,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let session_id_router = Router::new()
         .route("/", get(get_session))
         .route("/follow-up", post(follow_up))
+        .route("/model-segments", get(get_model_segments))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_session_middleware,
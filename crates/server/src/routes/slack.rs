@@ -0,0 +1,313 @@
+use axum::{
+    Extension, Json, Router,
+    body::Bytes,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    middleware::from_fn_with_state,
+    response::{IntoResponse, Json as ResponseJson, Response},
+    routing::{get, post},
+};
+use db::models::{
+    project::Project,
+    slack::{SlackIntegration, SlackTaskThread},
+    task::{CreateTask, Task},
+};
+use deployment::{Deployment, DeploymentError};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use services::services::{
+    outbox::{self, OutboxAction},
+    slack::{
+        self, EventEnvelope, SlackClient, SlashCommand, append_comment, create_task_modal, task_url,
+    },
+};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+const SIGNATURE_HEADER: &str = "x-slack-signature";
+const TIMESTAMP_HEADER: &str = "x-slack-request-timestamp";
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateSlackIntegration {
+    /// Signing secret from the app's Basic Information page
+    pub signing_secret: String,
+    /// Bot token (`xoxb-...`) with the `commands`, `chat:write` and `channels:history` scopes
+    pub bot_token: String,
+    /// Address of this instance used in task links posted to Slack
+    pub app_url: String,
+}
+
+/// Reply shown only to the user who ran the command
+fn ephemeral(text: &str) -> ResponseJson<Value> {
+    ResponseJson(json!({ "response_type": "ephemeral", "text": text }))
+}
+
+/// The project's integration and a client for its bot, once the request signature checks out.
+async fn verify_request(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<(SlackIntegration, SlackClient), ApiError> {
+    let integration = SlackIntegration::find_by_project_id(&deployment.db().pool, project_id)
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+    let cipher = deployment.project_env().cipher();
+    let signing_secret = cipher
+        .decrypt(&integration.signing_secret)
+        .map_err(DeploymentError::from)?;
+    let header = |name| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+    };
+    if !slack::verify_signature(
+        signing_secret.as_bytes(),
+        header(TIMESTAMP_HEADER),
+        header(SIGNATURE_HEADER),
+        body,
+    ) {
+        return Err(ApiError::Unauthorized);
+    }
+    let bot_token = cipher
+        .decrypt(&integration.bot_token)
+        .map_err(DeploymentError::from)?;
+    Ok((integration, SlackClient::new(bot_token)))
+}
+
+pub async fn get_slack_integration(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<SlackIntegration>>>, ApiError> {
+    let integration =
+        SlackIntegration::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(integration)))
+}
+
+pub async fn update_slack_integration(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateSlackIntegration>,
+) -> Result<ResponseJson<ApiResponse<SlackIntegration>>, ApiError> {
+    if payload.signing_secret.is_empty() || payload.bot_token.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Signing secret and bot token are required".to_string(),
+        ));
+    }
+    let app_url = payload.app_url.trim().trim_end_matches('/');
+    if !app_url.starts_with("http://") && !app_url.starts_with("https://") {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid app URL '{app_url}': use an http(s) address"
+        )));
+    }
+    let cipher = deployment.project_env().cipher();
+    let signing_secret = cipher
+        .encrypt(&payload.signing_secret)
+        .map_err(DeploymentError::from)?;
+    let bot_token = cipher
+        .encrypt(&payload.bot_token)
+        .map_err(DeploymentError::from)?;
+
+    let integration = SlackIntegration::upsert(
+        &deployment.db().pool,
+        project.id,
+        &signing_secret,
+        &bot_token,
+        app_url,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(integration)))
+}
+
+pub async fn delete_slack_integration(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    SlackIntegration::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// `/vibe create <title>` opens the task creation modal.
+pub async fn receive_slash_command(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let (_, client) = verify_request(&deployment, project_id, &headers, &body).await?;
+    let command = SlashCommand::parse(&body)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid slash command: {e}")))?;
+    let Some(title) = command.create_title() else {
+        return Ok(ephemeral("Usage: `/vibe create <title>`").into_response());
+    };
+
+    match client
+        .open_view(
+            &command.trigger_id,
+            create_task_modal(title, &command.channel_id),
+        )
+        .await
+    {
+        Ok(()) => Ok(StatusCode::OK.into_response()),
+        Err(e) => {
+            tracing::warn!("Failed to open Slack task modal: {}", e);
+            Ok(ephemeral(&format!("Could not open the task form: {e}")).into_response())
+        }
+    }
+}
+
+/// Creates the task of a submitted modal and announces it in the channel the command ran in.
+pub async fn receive_interaction(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let (integration, client) = verify_request(&deployment, project_id, &headers, &body).await?;
+    let submission =
+        slack::parse_submission(&body).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let Some(submission) = submission else {
+        return Ok(StatusCode::OK.into_response());
+    };
+    if submission.title.is_empty() {
+        return Ok(ResponseJson(json!({
+            "response_action": "errors",
+            "errors": { "title": "A title is required" },
+        }))
+        .into_response());
+    }
+
+    let pool = &deployment.db().pool;
+    let create_task = CreateTask {
+        project_id,
+        title: submission.title.clone(),
+        description: submission.description.clone(),
+        status: None,
+        parent_workspace_id: None,
+        image_ids: None,
+        shared_task_id: None,
+        generate_details: None,
+    };
+    let task = Task::create(pool, &create_task, Uuid::new_v4()).await?;
+    tracing::info!("Created task {} from Slack", task.id);
+
+    let text = format!(
+        "<@{}> created <{}|{}>. Reply in this thread to add comments.",
+        submission.user_id,
+        task_url(&integration.app_url, project_id, task.id),
+        task.title
+    );
+    match client.post_message(&submission.channel_id, &text).await {
+        Ok(message_ts) => {
+            SlackTaskThread::create(
+                pool,
+                task.id,
+                project_id,
+                &submission.channel_id,
+                &message_ts,
+            )
+            .await?;
+        }
+        Err(e) if e.is_connectivity_error() => {
+            let action = OutboxAction::SlackTaskAnnouncement {
+                project_id,
+                task_id: task.id,
+                channel_id: submission.channel_id.clone(),
+                text,
+            };
+            outbox::enqueue(pool, &action, &e.to_string()).await?;
+        }
+        Err(e) => tracing::warn!("Failed to post task {} to Slack: {}", task.id, e),
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_created_from_slack",
+            serde_json::json!({
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    // An empty body closes the modal
+    Ok(StatusCode::OK.into_response())
+}
+
+/// Events API endpoint. Replies in the thread of a task's announcement become comments.
+pub async fn receive_event(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    verify_request(&deployment, project_id, &headers, &body).await?;
+    let envelope: EventEnvelope = serde_json::from_slice(&body)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid Slack event: {e}")))?;
+    let event = match envelope {
+        EventEnvelope::UrlVerification { challenge } => {
+            return Ok(ResponseJson(json!({ "challenge": challenge })).into_response());
+        }
+        EventEnvelope::EventCallback { event } => event,
+        EventEnvelope::Other => return Ok(StatusCode::OK.into_response()),
+    };
+    let Some((channel_id, thread_ts)) = event.thread_reply() else {
+        return Ok(StatusCode::OK.into_response());
+    };
+
+    let pool = &deployment.db().pool;
+    let Some(thread) =
+        SlackTaskThread::find_by_message(pool, project_id, channel_id, thread_ts).await?
+    else {
+        return Ok(StatusCode::OK.into_response());
+    };
+    let Some(task) = Task::find_by_id(pool, thread.task_id).await? else {
+        return Ok(StatusCode::OK.into_response());
+    };
+    let description = append_comment(
+        task.description.as_deref(),
+        event.user.as_deref().unwrap_or("unknown"),
+        &event.text,
+    );
+    Task::update(
+        pool,
+        task.id,
+        task.project_id,
+        task.title.clone(),
+        Some(description),
+        task.status.clone(),
+        task.parent_workspace_id,
+    )
+    .await?;
+
+    Ok(StatusCode::OK.into_response())
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_slack_router = Router::new()
+        .route(
+            "/slack",
+            get(get_slack_integration)
+                .put(update_slack_integration)
+                .delete(delete_slack_integration),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new()
+        .route(
+            "/hooks/slack/{project_id}/commands",
+            post(receive_slash_command),
+        )
+        .route(
+            "/hooks/slack/{project_id}/interactions",
+            post(receive_interaction),
+        )
+        .route("/hooks/slack/{project_id}/events", post(receive_event))
+        .nest("/projects/{id}", project_slack_router)
+}
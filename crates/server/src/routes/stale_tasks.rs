@@ -0,0 +1,32 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::stale_tasks::{self, StaleTask};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize)]
+pub struct StaleTasksQuery {
+    pub project_id: Option<Uuid>,
+}
+
+/// Tasks flagged by the stale task policy that have not seen activity since.
+pub async fn list_stale_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<StaleTasksQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<StaleTask>>>, ApiError> {
+    let config = deployment.config().read().await.stale_tasks.clone();
+    let tasks = stale_tasks::find_stale(&deployment.db().pool, &config, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/stale-tasks", get(list_stale_tasks))
+}
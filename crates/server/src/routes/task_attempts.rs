@@ -1,8 +1,17 @@
+pub mod attempt_review;
+pub mod base_watch;
+pub mod checklist;
+pub mod checkpoints;
 pub mod codex_setup;
 pub mod cursor_setup;
+pub mod e2e_runs;
 pub mod gh_cli_setup;
 pub mod images;
+pub mod lint;
 pub mod pr;
+pub mod prompt_preview;
+pub mod review;
+pub mod screenshots;
 pub mod util;
 
 use std::{
@@ -16,20 +25,25 @@ use axum::{
         Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
-    routing::{get, post},
+    response::{IntoResponse, Json as ResponseJson, Response},
+    routing::{get, post, put},
 };
 use db::models::{
+    attempt_handoff::AttemptHandoff,
     execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
     merge::{Merge, MergeStatus, PrMerge, PullRequestInfo},
+    project_lifecycle_hook::LifecycleHook,
+    project_permission_policy::ProjectPermissionPolicy,
     project_repo::ProjectRepo,
+    prompt_template::{PromptTemplate, PromptTemplateKind},
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
     task::{Task, TaskRelationships, TaskStatus},
     workspace::{CreateWorkspace, Workspace, WorkspaceError},
     workspace_repo::{CreateWorkspaceRepo, RepoWithTargetBranch, WorkspaceRepo},
+    workspace_summary::WorkspaceSummary,
 };
 use deployment::Deployment;
 use executors::{
@@ -37,27 +51,36 @@ use executors::{
         ExecutorAction, ExecutorActionType,
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
-    executors::{CodingAgent, ExecutorError},
+    executors::{BaseAgentCapability, CodingAgent, ExecutorError, claude::types::PermissionMode},
     profile::{ExecutorConfigs, ExecutorProfileId},
 };
 use git2::BranchType;
+use local_deployment::devctl2::{
+    DevCtl2Config, is_devctl2_available, run_devctl2_setup, sanitize_branch_for_subdomain,
+};
 use serde::{Deserialize, Serialize};
 use services::services::{
     container::ContainerService,
     git::{ConflictOp, GitCliError, GitServiceError},
     github::GitHubService,
+    task_workflow,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
-use utils::response::ApiResponse;
+use utils::{
+    etag::json_with_etag,
+    pagination::{Page, PageQuery, SortOrder},
+    response::ApiResponse,
+};
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_workspace_middleware,
-    routes::task_attempts::gh_cli_setup::GhCliSetupError,
-};
-use local_deployment::devctl2::{
-    DevCtl2Config, is_devctl2_available, run_devctl2_setup, sanitize_branch_for_subdomain,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_workspace_middleware,
+    routes::{
+        prompt_templates::load_prompt_templates, task_attempts::gh_cli_setup::GhCliSetupError,
+    },
 };
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -94,10 +117,13 @@ pub struct DiffStreamQuery {
 pub async fn get_task_attempts(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<TaskAttemptQuery>,
-) -> Result<ResponseJson<ApiResponse<Vec<Workspace>>>, ApiError> {
+    Query(page): Query<PageQuery>,
+) -> Result<Page<Workspace>, ApiError> {
     let pool = &deployment.db().pool;
-    let workspaces = Workspace::fetch_all(pool, query.task_id).await?;
-    Ok(ResponseJson(ApiResponse::success(workspaces)))
+    let keyset = page.keyset(SortOrder::Desc)?;
+    let workspaces = Workspace::fetch_page(pool, query.task_id, &keyset).await?;
+    let total_count = Workspace::count(pool, query.task_id).await?;
+    Ok(keyset.page(workspaces, total_count as usize))
 }
 
 pub async fn get_task_attempt(
@@ -111,6 +137,22 @@ pub struct CreateTaskAttemptBody {
     pub task_id: Uuid,
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
+    /// Investigate only: run the agent with read-only tools, for codebase Q&A and audits
+    #[serde(default)]
+    #[ts(optional)]
+    pub read_only: Option<bool>,
+    /// Dry run: log what the agent's writing tool calls would do instead of running them
+    #[serde(default)]
+    #[ts(optional)]
+    pub dry_run: Option<bool>,
+    /// Permission mode to run the agent with instead of the one implied by the profile
+    #[serde(default)]
+    #[ts(optional)]
+    pub permission_mode: Option<PermissionMode>,
+    /// System prompt templates added to the agent's first prompt, in order
+    #[serde(default)]
+    #[ts(optional)]
+    pub prompt_template_ids: Option<Vec<Uuid>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ts_rs::TS)]
@@ -127,6 +169,63 @@ pub struct RunAgentSetupRequest {
 #[derive(Debug, Serialize, TS)]
 pub struct RunAgentSetupResponse {}
 
+/// Investigate-only and dry-run attempts need an executor that can restrict its tools that way
+pub fn ensure_attempt_mode_supported(
+    executor_profile_id: &ExecutorProfileId,
+    read_only: bool,
+    dry_run: bool,
+) -> Result<(), ApiError> {
+    let (capability, mode) = match (read_only, dry_run) {
+        (false, false) => return Ok(()),
+        (true, true) => {
+            return Err(ApiError::BadRequest(
+                "An attempt can't be both investigate-only and a dry run".to_string(),
+            ));
+        }
+        (true, false) => (BaseAgentCapability::ReadOnly, "investigate-only attempts"),
+        (false, true) => (BaseAgentCapability::DryRun, "dry runs"),
+    };
+    let supported = ExecutorConfigs::get_cached()
+        .get_coding_agent(executor_profile_id)
+        .is_some_and(|agent| agent.capabilities().contains(&capability));
+    if supported {
+        Ok(())
+    } else {
+        Err(ApiError::BadRequest(format!(
+            "{} can't run {}",
+            executor_profile_id.executor, mode
+        )))
+    }
+}
+
+/// Permission mode the attempt runs with: the requested one if the executor can pick one and
+/// the project's policy allows it, otherwise the profile's, capped by the policy
+pub async fn resolve_attempt_permission_mode(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    executor_profile_id: &ExecutorProfileId,
+    requested: Option<PermissionMode>,
+) -> Result<Option<PermissionMode>, ApiError> {
+    let agent = ExecutorConfigs::get_cached().get_coding_agent(executor_profile_id);
+    let supported = agent.as_ref().is_some_and(|agent| {
+        agent
+            .capabilities()
+            .contains(&BaseAgentCapability::PermissionModes)
+    });
+    if requested.is_some() && !supported {
+        return Err(ApiError::BadRequest(format!(
+            "{} doesn't support permission modes",
+            executor_profile_id.executor
+        )));
+    }
+    let policy =
+        ProjectPermissionPolicy::find_by_project_id(&deployment.db().pool, project_id).await?;
+    let profile_mode = agent.and_then(|agent| agent.default_permission_mode());
+    ProjectPermissionPolicy::resolve(policy.as_ref(), requested, profile_mode).map_err(|cap| {
+        ApiError::BadRequest(format!("This project allows permission modes up to {cap}"))
+    })
+}
+
 #[axum::debug_handler]
 pub async fn create_task_attempt(
     State(deployment): State<DeploymentImpl>,
@@ -139,6 +238,11 @@ pub async fn create_task_attempt(
             "At least one repository is required".to_string(),
         ));
     }
+    ensure_attempt_mode_supported(
+        &executor_profile_id,
+        payload.read_only.unwrap_or(false),
+        payload.dry_run.unwrap_or(false),
+    )?;
 
     let pool = &deployment.db().pool;
     let task = Task::find_by_id(&deployment.db().pool, payload.task_id)
@@ -150,6 +254,21 @@ pub async fn create_task_attempt(
         .await?
         .ok_or(SqlxError::RowNotFound)?;
 
+    let permission_mode = resolve_attempt_permission_mode(
+        &deployment,
+        project.id,
+        &executor_profile_id,
+        payload.permission_mode,
+    )
+    .await?;
+    let templates = load_prompt_templates(
+        pool,
+        payload.prompt_template_ids.as_deref().unwrap_or_default(),
+        project.id,
+        PromptTemplateKind::SystemPrompt,
+    )
+    .await?;
+
     let agent_working_dir = project
         .default_agent_working_dir
         .as_ref()
@@ -167,6 +286,9 @@ pub async fn create_task_attempt(
         &CreateWorkspace {
             branch: git_branch_name.clone(),
             agent_working_dir,
+            read_only: payload.read_only.unwrap_or(false),
+            dry_run: payload.dry_run.unwrap_or(false),
+            permission_mode,
         },
         attempt_id,
         payload.task_id,
@@ -183,6 +305,8 @@ pub async fn create_task_attempt(
         .collect();
 
     WorkspaceRepo::create_many(pool, workspace.id, &workspace_repos).await?;
+    let template_ids = templates.iter().map(|t| t.id).collect::<Vec<_>>();
+    PromptTemplate::set_for_workspace(pool, workspace.id, &template_ids).await?;
     if let Err(err) = deployment
         .container()
         .start_workspace(&workspace, executor_profile_id.clone())
@@ -242,6 +366,20 @@ pub async fn run_agent_setup(
 }
 
 #[axum::debug_handler]
+/// One-shot diff of the attempt's worktree, for clients that poll instead of streaming
+pub async fn get_task_attempt_diff(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+    Query(params): Query<DiffStreamQuery>,
+) -> Result<Response, ApiError> {
+    let diffs = deployment
+        .container()
+        .diff_snapshot(&workspace, params.stats_only)
+        .await?;
+    Ok(json_with_etag(&headers, diffs))
+}
+
 pub async fn stream_task_attempt_diff_ws(
     ws: WebSocketUpgrade,
     Query(params): Query<DiffStreamQuery>,
@@ -354,6 +492,20 @@ pub async fn merge_task_attempt(
         commit_message.push_str(description);
     }
 
+    deployment
+        .container()
+        .require_e2e_verification(&workspace, task.project_id)
+        .await?;
+    deployment
+        .container()
+        .run_merge_hook(
+            &workspace,
+            workspace_path,
+            task.project_id,
+            LifecycleHook::BeforeMerge,
+        )
+        .await?;
+
     let merge_commit_id = deployment.git().merge_changes(
         &repo.path,
         &worktree_path,
@@ -370,7 +522,18 @@ pub async fn merge_task_attempt(
         &merge_commit_id,
     )
     .await?;
-    Task::update_status(pool, task.id, TaskStatus::Done).await?;
+    task_workflow::update_task_status(pool, task.id, TaskStatus::Done).await?;
+
+    // The merge has landed; a failing blocking hook is still reported to the caller below
+    let after_merge = deployment
+        .container()
+        .run_merge_hook(
+            &workspace,
+            workspace_path,
+            task.project_id,
+            LifecycleHook::AfterMerge,
+        )
+        .await;
 
     // Stop any running dev servers for this workspace
     let dev_servers =
@@ -423,6 +586,7 @@ pub async fn merge_task_attempt(
         )
         .await;
 
+    after_merge?;
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
@@ -1095,6 +1259,21 @@ pub async fn start_dev_server(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    match launch_dev_server(&deployment, &workspace).await? {
+        Some(_) => Ok(ResponseJson(ApiResponse::success(()))),
+        None => Ok(ResponseJson(ApiResponse::error(
+            "No dev server script configured for this project",
+        ))),
+    }
+}
+
+/// Start the project's dev server in the given workspace, stopping any other
+/// dev servers running for the same project. Returns `None` when the project
+/// has no dev script configured.
+pub async fn launch_dev_server(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+) -> Result<Option<ExecutionProcess>, ApiError> {
     let pool = &deployment.db().pool;
 
     // Get parent task
@@ -1144,11 +1323,7 @@ pub async fn start_dev_server(
     // Get dev script from project (dev_script is project-level, not per-repo)
     let dev_script = match &project.dev_script {
         Some(script) if !script.is_empty() => script.clone(),
-        _ => {
-            return Ok(ResponseJson(ApiResponse::error(
-                "No dev server script configured for this project",
-            )));
-        }
+        _ => return Ok(None),
     };
 
     let working_dir = project
@@ -1186,7 +1361,7 @@ pub async fn start_dev_server(
     let execution_process = deployment
         .container()
         .start_execution(
-            &workspace,
+            workspace,
             &session,
             &executor_action,
             &ExecutionProcessRunReason::DevServer,
@@ -1233,7 +1408,27 @@ pub async fn start_dev_server(
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(())))
+    Ok(Some(execution_process))
+}
+
+/// The generated review summary of the attempt, if one has been produced yet.
+pub async fn get_task_attempt_summary(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<WorkspaceSummary>>>, ApiError> {
+    let summary =
+        WorkspaceSummary::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(summary)))
+}
+
+/// The note the agent left when it last stopped blocked, until another run follows it.
+pub async fn get_task_attempt_handoff(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<AttemptHandoff>>>, ApiError> {
+    let handoff =
+        AttemptHandoff::find_open_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(handoff)))
 }
 
 pub async fn get_task_attempt_children(
@@ -1518,17 +1713,82 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/run-setup-script", post(run_setup_script))
         .route("/run-cleanup-script", post(run_cleanup_script))
         .route("/branch-status", get(get_task_attempt_branch_status))
+        .route("/diff", get(get_task_attempt_diff))
         .route("/diff/ws", get(stream_task_attempt_diff_ws))
         .route("/merge", post(merge_task_attempt))
         .route("/push", post(push_task_attempt_branch))
         .route("/push/force", post(force_push_task_attempt_branch))
         .route("/rebase", post(rebase_task_attempt))
+        .route(
+            "/base-watch",
+            get(base_watch::get_base_watch)
+                .put(base_watch::set_base_watch)
+                .delete(base_watch::delete_base_watch),
+        )
         .route("/conflicts/abort", post(abort_conflicts_task_attempt))
         .route("/pr", post(pr::create_github_pr))
         .route("/pr/attach", post(pr::attach_existing_pr))
         .route("/pr/comments", get(pr::get_pr_comments))
+        .route(
+            "/review",
+            get(review::get_latest_review).post(review::start_review),
+        )
+        .route(
+            "/review/findings/{finding_id}",
+            put(review::resolve_finding),
+        )
+        .route(
+            "/human-review",
+            get(attempt_review::get_attempt_review).post(attempt_review::submit_attempt_review),
+        )
+        .route(
+            "/human-review/comments",
+            post(attempt_review::create_review_comment),
+        )
+        .route(
+            "/human-review/comments/{comment_id}",
+            put(attempt_review::update_review_comment)
+                .delete(attempt_review::delete_review_comment),
+        )
+        .route(
+            "/human-review/send-feedback",
+            post(attempt_review::send_review_feedback),
+        )
+        .route("/checklist", get(checklist::get_attempt_checklist))
+        .route(
+            "/checklist/{item_id}",
+            put(checklist::set_attempt_checklist_item),
+        )
+        .route("/checkpoints", get(checkpoints::get_checkpoints))
+        .route(
+            "/checkpoints/{checkpoint_id}/rollback",
+            post(checkpoints::rollback_to_checkpoint),
+        )
+        .route(
+            "/screenshots",
+            get(screenshots::get_screenshots).post(screenshots::capture_screenshot),
+        )
+        .route(
+            "/e2e-runs",
+            get(e2e_runs::get_e2e_runs).post(e2e_runs::run_e2e_verification),
+        )
+        .route(
+            "/e2e-runs/{run_id}/artifacts",
+            get(e2e_runs::get_e2e_artifacts),
+        )
+        .route(
+            "/e2e-runs/{run_id}/artifacts/{artifact_id}",
+            get(e2e_runs::download_e2e_artifact),
+        )
+        .route(
+            "/lint",
+            get(lint::get_lint_annotations).post(lint::run_linters),
+        )
         .route("/open-editor", post(open_task_attempt_in_editor))
         .route("/children", get(get_task_attempt_children))
+        .route("/summary", get(get_task_attempt_summary))
+        .route("/handoff", get(get_task_attempt_handoff))
+        .route("/prompt-preview", get(prompt_preview::get_prompt_preview))
         .route("/stop", post(stop_task_attempt_execution))
         .route("/change-target-branch", post(change_target_branch))
         .route("/rename-branch", post(rename_branch))
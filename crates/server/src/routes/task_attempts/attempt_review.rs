@@ -0,0 +1,206 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    attempt_review::{
+        AttemptReview, AttemptReviewComment, CreateAttemptReview, CreateAttemptReviewComment,
+        UpdateAttemptReviewComment,
+    },
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    session::{CreateSession, Session},
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use executors::actions::{
+    ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
+    coding_agent_initial::CodingAgentInitialRequest,
+};
+use serde::Serialize;
+use services::services::{attempt_review, container::ContainerService};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// A reviewer's inline comments on the attempt and their latest verdict.
+#[derive(Debug, Serialize, TS)]
+pub struct AttemptReviewState {
+    pub latest_review: Option<AttemptReview>,
+    pub comments: Vec<AttemptReviewComment>,
+}
+
+async fn load_comment(
+    deployment: &DeploymentImpl,
+    workspace_id: Uuid,
+    comment_id: Uuid,
+) -> Result<AttemptReviewComment, ApiError> {
+    AttemptReviewComment::find_by_id(&deployment.db().pool, comment_id)
+        .await?
+        .filter(|comment| comment.workspace_id == workspace_id)
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))
+}
+
+pub async fn get_attempt_review(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<AttemptReviewState>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let latest_review = AttemptReview::find_latest_by_workspace_id(pool, workspace.id).await?;
+    let comments = AttemptReviewComment::find_by_workspace_id(pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(AttemptReviewState {
+        latest_review,
+        comments,
+    })))
+}
+
+/// Approve the attempt or request changes.
+pub async fn submit_attempt_review(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<CreateAttemptReview>,
+) -> Result<ResponseJson<ApiResponse<AttemptReview>>, ApiError> {
+    payload.summary = payload.summary.filter(|summary| !summary.trim().is_empty());
+    let review = AttemptReview::create(&deployment.db().pool, workspace.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(review)))
+}
+
+pub async fn create_review_comment(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateAttemptReviewComment>,
+) -> Result<ResponseJson<ApiResponse<AttemptReviewComment>>, ApiError> {
+    if payload.file_path.trim().is_empty() || payload.body.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Review comments need a file and a comment".to_string(),
+        ));
+    }
+    if payload.line < 1 {
+        return Err(ApiError::BadRequest(
+            "Review comments need a line number".to_string(),
+        ));
+    }
+    let comment =
+        AttemptReviewComment::create(&deployment.db().pool, workspace.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+/// Edit a comment, or mark it resolved or open again.
+pub async fn update_review_comment(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, comment_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateAttemptReviewComment>,
+) -> Result<ResponseJson<ApiResponse<AttemptReviewComment>>, ApiError> {
+    if payload
+        .body
+        .as_deref()
+        .is_some_and(|body| body.trim().is_empty())
+    {
+        return Err(ApiError::BadRequest(
+            "Review comments can't be empty".to_string(),
+        ));
+    }
+    let existing = load_comment(&deployment, workspace.id, comment_id).await?;
+    let comment = existing.update(&deployment.db().pool, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+pub async fn delete_review_comment(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let existing = load_comment(&deployment, workspace.id, comment_id).await?;
+    AttemptReviewComment::delete(&deployment.db().pool, existing.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Send the unresolved comments to the agent as a follow-up prompt.
+pub async fn send_review_feedback(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Err(ApiError::Conflict(
+            "Wait for the agent to finish before sending it feedback".to_string(),
+        ));
+    }
+    let latest_review = AttemptReview::find_latest_by_workspace_id(pool, workspace.id).await?;
+    let comments = AttemptReviewComment::find_by_workspace_id(pool, workspace.id).await?;
+    let Some(prompt) = attempt_review::feedback_prompt(latest_review.as_ref(), &comments) else {
+        return Err(ApiError::BadRequest(
+            "There are no unresolved review comments to send".to_string(),
+        ));
+    };
+
+    deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+
+    let session = match Session::find_latest_by_workspace_id(pool, workspace.id).await? {
+        Some(session) => session,
+        None => {
+            Session::create(
+                pool,
+                &CreateSession { executor: None },
+                Uuid::new_v4(),
+                workspace.id,
+            )
+            .await?
+        }
+    };
+    let executor_profile_id =
+        ExecutionProcess::latest_executor_profile_for_session(pool, session.id).await?;
+    let latest_agent_session_id =
+        ExecutionProcess::find_latest_coding_agent_turn_session_id(pool, session.id).await?;
+    let working_dir = workspace
+        .agent_working_dir
+        .as_ref()
+        .filter(|dir| !dir.is_empty())
+        .cloned();
+
+    let action_type = if let Some(agent_session_id) = latest_agent_session_id {
+        ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+            prompt,
+            session_id: agent_session_id,
+            executor_profile_id,
+            working_dir,
+            model: None,
+        })
+    } else {
+        ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt,
+            executor_profile_id,
+            working_dir,
+            model: None,
+        })
+    };
+
+    let execution_process = deployment
+        .container()
+        .start_execution(
+            &workspace,
+            &session,
+            &ExecutorAction::new(action_type, None),
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+
+    let sent: Vec<Uuid> = comments
+        .iter()
+        .filter(|comment| !comment.resolved)
+        .map(|comment| comment.id)
+        .collect();
+    AttemptReviewComment::mark_sent(pool, &sent).await?;
+
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
+}
@@ -0,0 +1,37 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    workspace::Workspace,
+    workspace_base_watch::{SetWorkspaceBaseWatch, WorkspaceBaseWatch},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// The attempt's base branch watch, if it is being watched.
+pub async fn get_base_watch(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<WorkspaceBaseWatch>>>, ApiError> {
+    let watch =
+        WorkspaceBaseWatch::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(watch)))
+}
+
+/// Keep the attempt rebased onto its base branches as new commits land there.
+pub async fn set_base_watch(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetWorkspaceBaseWatch>,
+) -> Result<ResponseJson<ApiResponse<WorkspaceBaseWatch>>, ApiError> {
+    let watch = WorkspaceBaseWatch::upsert(&deployment.db().pool, workspace.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(watch)))
+}
+
+pub async fn delete_base_watch(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    WorkspaceBaseWatch::delete(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
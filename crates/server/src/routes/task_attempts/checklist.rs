@@ -0,0 +1,49 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    review_checklist::{ChecklistItemState, ReviewChecklistItem},
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct SetChecklistItemRequest {
+    pub checked: bool,
+}
+
+/// The project's review checklist with what's been checked on this attempt.
+pub async fn get_attempt_checklist(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ChecklistItemState>>>, ApiError> {
+    let items =
+        ReviewChecklistItem::find_states_for_workspace(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(items)))
+}
+
+/// Check or uncheck an item on this attempt.
+pub async fn set_attempt_checklist_item(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, item_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<SetChecklistItemRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<ChecklistItemState>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let items = ReviewChecklistItem::find_states_for_workspace(pool, workspace.id).await?;
+    if !items.iter().any(|item| item.item_id == item_id) {
+        return Err(ApiError::Database(SqlxError::RowNotFound));
+    }
+    ReviewChecklistItem::set_checked(pool, workspace.id, item_id, request.checked).await?;
+    let items = ReviewChecklistItem::find_states_for_workspace(pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(items)))
+}
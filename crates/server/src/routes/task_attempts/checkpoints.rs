@@ -0,0 +1,51 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{attempt_checkpoint::AttemptCheckpoint, workspace::Workspace};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::container::ContainerService;
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct RollbackCheckpointRequest {
+    /// Also drop the runs that followed the checkpoint, so the next follow-up continues from it
+    #[serde(default)]
+    pub truncate_conversation: bool,
+}
+
+/// The attempt's checkpoints, newest first.
+pub async fn get_checkpoints(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptCheckpoint>>>, ApiError> {
+    let checkpoints =
+        AttemptCheckpoint::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(checkpoints)))
+}
+
+/// Reset the attempt's worktrees to a checkpoint. Returns the checkpoint of the replaced state,
+/// which undoes the rollback.
+pub async fn rollback_to_checkpoint(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, checkpoint_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<RollbackCheckpointRequest>,
+) -> Result<ResponseJson<ApiResponse<Option<AttemptCheckpoint>>>, ApiError> {
+    let checkpoint = AttemptCheckpoint::find_by_id(&deployment.db().pool, checkpoint_id)
+        .await?
+        .filter(|checkpoint| checkpoint.workspace_id == workspace.id)
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    let replaced = deployment
+        .container()
+        .rollback_to_checkpoint(&workspace, &checkpoint, request.truncate_conversation)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(replaced)))
+}
@@ -0,0 +1,99 @@
+use axum::{
+    Extension,
+    body::Body,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::{Json as ResponseJson, Response},
+};
+use db::models::{
+    attempt_e2e_run::{AttemptE2eArtifact, AttemptE2eRun},
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use services::services::container::ContainerService;
+use sqlx::{Error as SqlxError, SqlitePool};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+async fn find_run(
+    pool: &SqlitePool,
+    workspace: &Workspace,
+    run_id: Uuid,
+) -> Result<AttemptE2eRun, ApiError> {
+    AttemptE2eRun::find_by_id(pool, run_id)
+        .await?
+        .filter(|run| run.workspace_id == workspace.id)
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))
+}
+
+/// The attempt's E2E verification runs, newest first.
+pub async fn get_e2e_runs(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptE2eRun>>>, ApiError> {
+    let runs = AttemptE2eRun::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(runs)))
+}
+
+/// Run the project's E2E verification against the attempt's dev server now.
+pub async fn run_e2e_verification(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<AttemptE2eRun>>, ApiError> {
+    let run = deployment
+        .container()
+        .run_e2e_verification(&workspace)
+        .await?
+        .ok_or_else(|| {
+            ApiError::Conflict(
+                "No E2E verification hook configured, or no running dev server with a known URL"
+                    .to_string(),
+            )
+        })?;
+    Ok(ResponseJson(ApiResponse::success(run)))
+}
+
+/// Screenshots and traces a run left behind.
+pub async fn get_e2e_artifacts(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, run_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptE2eArtifact>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let run = find_run(pool, &workspace, run_id).await?;
+    let artifacts = AttemptE2eArtifact::find_by_run_id(pool, run.id).await?;
+    Ok(ResponseJson(ApiResponse::success(artifacts)))
+}
+
+pub async fn download_e2e_artifact(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, run_id, artifact_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+    let run = find_run(pool, &workspace, run_id).await?;
+    let artifact = AttemptE2eArtifact::find_by_id(pool, artifact_id)
+        .await?
+        .filter(|artifact| artifact.run_id == run.id)
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    let blob = deployment
+        .attachments()
+        .open_e2e_artifact(&artifact)
+        .await?;
+
+    // The stored name is already sanitized, so it is safe to put in the header
+    let file_name = artifact.file_path.rsplit('/').next().unwrap_or("artifact");
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, &artifact.mime_type)
+        .header(header::CONTENT_LENGTH, blob.size)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{file_name}\""),
+        )
+        .body(Body::from_stream(blob.stream))
+        .map_err(|e| ApiError::Io(std::io::Error::other(e)))
+}
@@ -3,7 +3,7 @@ use std::path::Path;
 use axum::{
     Extension, Router,
     body::Body,
-    extract::{DefaultBodyLimit, Multipart, Query, Request, State},
+    extract::{Multipart, Query, Request, State},
     http::{StatusCode, header},
     middleware::{Next, from_fn_with_state},
     response::{Json as ResponseJson, Response},
@@ -21,7 +21,7 @@ use uuid::Uuid;
 use crate::{
     DeploymentImpl,
     error::ApiError,
-    middleware::load_workspace_middleware,
+    middleware::{load_workspace_middleware, upload_body_limit},
     routes::images::{ImageMetadata, ImageResponse, process_image_upload},
 };
 
@@ -230,10 +230,7 @@ async fn load_workspace_with_wildcard(
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let metadata_router = Router::new()
         .route("/metadata", get(get_image_metadata))
-        .route(
-            "/upload",
-            post(upload_image).layer(DefaultBodyLimit::max(20 * 1024 * 1024)), // 20MB limit
-        )
+        .route("/upload", post(upload_image).layer(upload_body_limit()))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_workspace_middleware,
@@ -0,0 +1,26 @@
+use axum::{Extension, extract::State, response::Json as ResponseJson};
+use db::models::{attempt_lint_annotation::AttemptLintAnnotation, workspace::Workspace};
+use deployment::Deployment;
+use services::services::container::ContainerService;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Findings of the latest run of each of the project's linters, grouped by file.
+pub async fn get_lint_annotations(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptLintAnnotation>>>, ApiError> {
+    let annotations =
+        AttemptLintAnnotation::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(annotations)))
+}
+
+/// Lint the files the attempt changed now.
+pub async fn run_linters(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptLintAnnotation>>>, ApiError> {
+    let annotations = deployment.container().run_linters(&workspace).await?;
+    Ok(ResponseJson(ApiResponse::success(annotations)))
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use axum::{
     Extension, Json,
@@ -6,13 +6,16 @@ use axum::{
     response::Json as ResponseJson,
 };
 use db::models::{
+    attempt_screenshot::AttemptScreenshot,
     execution_process::{ExecutionProcess, ExecutionProcessRunReason},
     merge::{Merge, MergeStatus},
     repo::{Repo, RepoError},
+    review_checklist::ReviewChecklistItem,
     session::{CreateSession, Session},
-    task::{Task, TaskStatus},
+    task::TaskStatus,
     workspace::{Workspace, WorkspaceError},
     workspace_repo::WorkspaceRepo,
+    workspace_summary::WorkspaceSummary,
 };
 use deployment::Deployment;
 use executors::actions::{
@@ -25,12 +28,15 @@ use services::services::{
     container::ContainerService,
     git::{GitCliError, GitServiceError},
     github::{CreatePrRequest, GitHubService, GitHubServiceError, UnifiedPrComment},
+    outbox::{self, OutboxAction},
+    review_checklist, screenshot, task_workflow,
 };
+use sqlx::SqlitePool;
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{DeploymentImpl, error::ApiError, middleware::RequestOrigin};
 
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct CreateGitHubPrRequest {
@@ -52,6 +58,7 @@ pub enum CreatePrError {
     GitCliNotLoggedIn,
     GitCliNotInstalled,
     TargetBranchNotFound { branch: String },
+    Queued { outbox_id: Uuid },
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -159,12 +166,14 @@ async fn trigger_pr_description_follow_up(
             session_id: agent_session_id,
             executor_profile_id: executor_profile_id.clone(),
             working_dir: working_dir.clone(),
+            model: None,
         })
     } else {
         ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
             prompt,
             executor_profile_id: executor_profile_id.clone(),
             working_dir,
+            model: None,
         })
     };
 
@@ -185,6 +194,7 @@ async fn trigger_pr_description_follow_up(
 
 pub async fn create_github_pr(
     Extension(workspace): Extension<Workspace>,
+    Extension(origin): Extension<RequestOrigin>,
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<CreateGitHubPrRequest>,
 ) -> Result<ResponseJson<ApiResponse<String, CreatePrError>>, ApiError> {
@@ -234,10 +244,41 @@ pub async fn create_github_pr(
                 CreatePrError::GitCliNotInstalled,
             )));
         }
+        Err(e) if outbox::is_connectivity_error(&e.to_string()) => {
+            let base_branch =
+                normalize_target_branch(&deployment, &repo_path, &worktree_path, &target_branch)?;
+            let pr_request = CreatePrRequest {
+                title: request.title.clone(),
+                body: pr_body(pool, &workspace, &origin, request.body.clone()).await?,
+                head_branch: workspace.branch.clone(),
+                base_branch,
+                draft: request.draft,
+            };
+            return queue_pr(
+                &deployment,
+                &workspace,
+                workspace_repo.repo_id,
+                worktree_path,
+                pr_request,
+                &e.to_string(),
+            )
+            .await;
+        }
         Err(e) => return Err(ApiError::GitService(e)),
         Ok(true) => {}
     }
 
+    let norm_target_branch_name =
+        normalize_target_branch(&deployment, &repo_path, &worktree_path, &target_branch)?;
+    // Create the PR using GitHub service
+    let pr_request = CreatePrRequest {
+        title: request.title.clone(),
+        body: pr_body(pool, &workspace, &origin, request.body.clone()).await?,
+        head_branch: workspace.branch.clone(),
+        base_branch: norm_target_branch_name.clone(),
+        draft: request.draft,
+    };
+
     // Push the branch to GitHub first
     if let Err(e) = deployment
         .git()
@@ -255,37 +296,21 @@ pub async fn create_github_pr(
                     CreatePrError::GitCliNotInstalled,
                 )));
             }
+            _ if outbox::is_connectivity_error(&e.to_string()) => {
+                return queue_pr(
+                    &deployment,
+                    &workspace,
+                    workspace_repo.repo_id,
+                    worktree_path,
+                    pr_request,
+                    &e.to_string(),
+                )
+                .await;
+            }
             _ => return Err(ApiError::GitService(e)),
         }
     }
 
-    let norm_target_branch_name = if matches!(
-        deployment
-            .git()
-            .find_branch_type(&repo_path, &target_branch)?,
-        BranchType::Remote
-    ) {
-        // Remote branches are formatted as {remote}/{branch} locally.
-        // For PR APIs, we must provide just the branch name.
-        let remote = deployment
-            .git()
-            .get_remote_name_from_branch_name(&worktree_path, &target_branch)?;
-        let remote_prefix = format!("{}/", remote);
-        target_branch
-            .strip_prefix(&remote_prefix)
-            .unwrap_or(&target_branch)
-            .to_string()
-    } else {
-        target_branch
-    };
-    // Create the PR using GitHub service
-    let pr_request = CreatePrRequest {
-        title: request.title.clone(),
-        body: request.body.clone(),
-        head_branch: workspace.branch.clone(),
-        base_branch: norm_target_branch_name.clone(),
-        draft: request.draft,
-    };
     // Use GitService to get the remote URL, then create GitHubRepoInfo
     let repo_info = deployment.git().get_github_repo_info(&repo_path)?;
 
@@ -352,12 +377,101 @@ pub async fn create_github_pr(
                 GitHubServiceError::AuthFailed(_) => Ok(ResponseJson(
                     ApiResponse::error_with_data(CreatePrError::GithubCliNotLoggedIn),
                 )),
+                _ if e.is_connectivity_error() => {
+                    queue_pr(
+                        &deployment,
+                        &workspace,
+                        workspace_repo.repo_id,
+                        worktree_path,
+                        pr_request,
+                        &e.to_string(),
+                    )
+                    .await
+                }
                 _ => Err(ApiError::GitHubService(e)),
             }
         }
     }
 }
 
+/// Remote branches are formatted as {remote}/{branch} locally. For PR APIs, we must provide
+/// just the branch name.
+fn normalize_target_branch(
+    deployment: &DeploymentImpl,
+    repo_path: &Path,
+    worktree_path: &Path,
+    target_branch: &str,
+) -> Result<String, ApiError> {
+    if !matches!(
+        deployment
+            .git()
+            .find_branch_type(repo_path, target_branch)?,
+        BranchType::Remote
+    ) {
+        return Ok(target_branch.to_string());
+    }
+    let remote = deployment
+        .git()
+        .get_remote_name_from_branch_name(worktree_path, target_branch)?;
+    let remote_prefix = format!("{}/", remote);
+    Ok(target_branch
+        .strip_prefix(&remote_prefix)
+        .unwrap_or(target_branch)
+        .to_string())
+}
+
+/// Without a body, describe the PR with the attempt's generated summary. The latest dev server
+/// screenshot is linked from this server when it knows its own address.
+async fn pr_body(
+    pool: &SqlitePool,
+    workspace: &Workspace,
+    origin: &RequestOrigin,
+    body: Option<String>,
+) -> Result<Option<String>, ApiError> {
+    let body = match body.filter(|b| !b.trim().is_empty()) {
+        Some(body) => Some(body),
+        None => WorkspaceSummary::find_by_workspace_id(pool, workspace.id)
+            .await?
+            .map(|s| s.summary),
+    };
+    let body = match (
+        origin.base_url(),
+        AttemptScreenshot::find_latest_by_workspace_id(pool, workspace.id).await?,
+    ) {
+        (Some(base_url), Some(shot)) => screenshot::append_to_body(body, &base_url, shot.image_id),
+        _ => body,
+    };
+    let checklist = ReviewChecklistItem::find_states_for_workspace(pool, workspace.id).await?;
+    Ok(review_checklist::append_to_body(body, &checklist))
+}
+
+/// Keep the PR in the integration outbox until GitHub is reachable again
+async fn queue_pr(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    repo_id: Uuid,
+    worktree_path: PathBuf,
+    pr_request: CreatePrRequest,
+    error: &str,
+) -> Result<ResponseJson<ApiResponse<String, CreatePrError>>, ApiError> {
+    let action = OutboxAction::GithubPullRequest {
+        workspace_id: workspace.id,
+        repo_id,
+        worktree_path,
+        title: pr_request.title,
+        body: pr_request.body,
+        head_branch: pr_request.head_branch,
+        base_branch: pr_request.base_branch,
+        draft: pr_request.draft,
+    };
+    let entry = outbox::enqueue(&deployment.db().pool, &action, error).await?;
+    Ok(ResponseJson(ApiResponse::error_with_data(
+        CreatePrError::Queued {
+            outbox_id: entry.id,
+        },
+    )))
+}
+
 pub async fn attach_existing_pr(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -424,7 +538,7 @@ pub async fn attach_existing_pr(
 
         // If PR is merged, mark task as done
         if matches!(pr_info.status, MergeStatus::Merged) {
-            Task::update_status(pool, task.id, TaskStatus::Done).await?;
+            task_workflow::update_task_status(pool, task.id, TaskStatus::Done).await?;
 
             // Try broadcast update to other users in organization
             if let Ok(publisher) = deployment.share_publisher() {
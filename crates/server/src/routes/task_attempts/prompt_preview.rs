@@ -0,0 +1,67 @@
+use axum::{Extension, extract::State, response::Json as ResponseJson};
+use db::models::{execution_process::ExecutionProcess, session::Session, workspace::Workspace};
+use deployment::Deployment;
+use executors::profile::{ExecutorConfigs, ExecutorProfileId};
+use serde::Serialize;
+use services::services::{container::ContainerService, repo_instructions::RepoInstructions};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// The prompt the attempt's first run is given, composed from the attempt as it is now.
+#[derive(Debug, Serialize, TS)]
+pub struct PromptPreview {
+    pub executor_profile_id: ExecutorProfileId,
+    /// Includes the profile's appended text
+    pub prompt: String,
+    /// Instructions files found in the attempt's repositories
+    pub instructions: Vec<RepoInstructions>,
+}
+
+pub async fn get_prompt_preview(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<PromptPreview>>, ApiError> {
+    let pool = &deployment.db().pool;
+    deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let task = workspace
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    // The profile the attempt last ran with, or the configured default before it has run
+    let latest_profile = match Session::find_latest_by_workspace_id(pool, workspace.id).await? {
+        Some(session) => ExecutionProcess::latest_executor_profile_for_session(pool, session.id)
+            .await
+            .ok(),
+        None => None,
+    };
+    let executor_profile_id = match latest_profile {
+        Some(profile) => profile,
+        None => deployment.config().read().await.executor_profile.clone(),
+    };
+
+    let prompt = deployment
+        .container()
+        .compose_initial_prompt(&workspace, &task)
+        .await?;
+    let prompt = match ExecutorConfigs::get_cached().get_coding_agent(&executor_profile_id) {
+        Some(agent) => agent.append_prompt().combine_prompt(&prompt),
+        None => prompt,
+    };
+    let instructions = deployment
+        .container()
+        .repo_instructions(&workspace, task.project_id)
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(PromptPreview {
+        executor_profile_id,
+        prompt,
+        instructions,
+    })))
+}
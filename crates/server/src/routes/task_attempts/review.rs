@@ -0,0 +1,135 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    execution_process::ExecutionProcess,
+    review::{Review, ReviewFinding, ReviewStatus},
+    session::Session,
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use services::services::container::ContainerService;
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct StartReviewRequest {
+    /// Defaults to the configured review executor, then to the one that ran the attempt
+    pub executor_profile_id: Option<ExecutorProfileId>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ReviewWithFindings {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub review: Review,
+    pub findings: Vec<ReviewFinding>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ResolveReviewFindingRequest {
+    pub resolved: bool,
+}
+
+/// Launch a second coding agent that reviews the attempt's diff.
+pub async fn start_review(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<StartReviewRequest>,
+) -> Result<ResponseJson<ApiResponse<Review>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Err(ApiError::Conflict(
+            "Wait for the attempt to finish before reviewing it".to_string(),
+        ));
+    }
+    let task = workspace
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+
+    let configured = deployment
+        .config()
+        .read()
+        .await
+        .code_review
+        .executor_profile
+        .clone();
+    let executor_profile_id = match request.executor_profile_id.or(configured) {
+        Some(profile) => profile,
+        None => {
+            let session = Session::find_latest_by_workspace_id(pool, workspace.id)
+                .await?
+                .ok_or_else(|| {
+                    ApiError::BadRequest("Choose an executor to review with".to_string())
+                })?;
+            ExecutionProcess::latest_executor_profile_for_session(pool, session.id).await?
+        }
+    };
+
+    let review = deployment
+        .container()
+        .start_review(&workspace, &task, executor_profile_id.clone())
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "review_started",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "executor": &executor_profile_id.executor,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(review)))
+}
+
+/// The most recent review of the attempt with its findings.
+pub async fn get_latest_review(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ReviewWithFindings>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let Some(review) = Review::find_latest_by_workspace_id(pool, workspace.id).await? else {
+        return Ok(ResponseJson(ApiResponse::success(None)));
+    };
+    let findings = match review.status {
+        ReviewStatus::Completed => ReviewFinding::find_by_review_id(pool, review.id).await?,
+        ReviewStatus::Running | ReviewStatus::Failed => Vec::new(),
+    };
+
+    Ok(ResponseJson(ApiResponse::success(Some(
+        ReviewWithFindings { review, findings },
+    ))))
+}
+
+/// Mark a finding as addressed or dismissed, or reopen it.
+pub async fn resolve_finding(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, finding_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<ResolveReviewFindingRequest>,
+) -> Result<ResponseJson<ApiResponse<ReviewFinding>>, ApiError> {
+    let finding = ReviewFinding::set_resolved(
+        &deployment.db().pool,
+        workspace.id,
+        finding_id,
+        request.resolved,
+    )
+    .await?
+    .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+
+    Ok(ResponseJson(ApiResponse::success(finding)))
+}
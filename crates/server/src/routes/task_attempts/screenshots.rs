@@ -0,0 +1,30 @@
+use axum::{Extension, extract::State, response::Json as ResponseJson};
+use db::models::{attempt_screenshot::AttemptScreenshot, workspace::Workspace};
+use deployment::Deployment;
+use services::services::container::ContainerService;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// The attempt's dev server screenshots, newest first.
+pub async fn get_screenshots(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptScreenshot>>>, ApiError> {
+    let screenshots =
+        AttemptScreenshot::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(screenshots)))
+}
+
+/// Screenshot the attempt's running dev server now.
+pub async fn capture_screenshot(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<AttemptScreenshot>>, ApiError> {
+    let screenshot = deployment
+        .container()
+        .capture_dev_server_screenshot(&workspace)
+        .await?
+        .ok_or_else(|| ApiError::Conflict("No running dev server with a known URL".to_string()))?;
+    Ok(ResponseJson(ApiResponse::success(screenshot)))
+}
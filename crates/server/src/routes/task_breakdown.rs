@@ -0,0 +1,109 @@
+use axum::{
+    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::post,
+};
+use db::models::{
+    task::{CreateTask, Task},
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::task_assist::{self, ProposedSubtask};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_task_middleware};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct AcceptSubtasksRequest {
+    /// The proposals to create, usually a subset of what the breakdown returned
+    pub subtasks: Vec<ProposedSubtask>,
+    /// Attempt to attach the subtasks to. Defaults to the task's latest attempt; without any
+    /// attempt the subtasks are created as regular tasks.
+    pub parent_workspace_id: Option<Uuid>,
+}
+
+/// Ask the configured model to propose subtasks for a task. Nothing is created.
+pub async fn propose_breakdown(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProposedSubtask>>>, ApiError> {
+    let subtasks = task_assist::propose_subtasks(deployment.llm(), &task).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_breakdown_proposed",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "subtask_count": subtasks.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(subtasks)))
+}
+
+/// Create the accepted proposals as child tasks, in their suggested order.
+pub async fn accept_breakdown(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<AcceptSubtasksRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    if payload.subtasks.iter().any(|s| s.title.trim().is_empty()) {
+        return Err(ApiError::BadRequest(
+            "Subtask titles cannot be empty".to_string(),
+        ));
+    }
+
+    let workspaces = Workspace::fetch_all(pool, Some(task.id)).await?;
+    let parent_workspace_id = match payload.parent_workspace_id {
+        Some(id) if workspaces.iter().any(|w| w.id == id) => Some(id),
+        Some(_) => {
+            return Err(ApiError::BadRequest(
+                "Parent attempt does not belong to this task".to_string(),
+            ));
+        }
+        None => workspaces.first().map(|w| w.id),
+    };
+
+    let mut subtasks = payload.subtasks;
+    subtasks.sort_by_key(|s| s.order);
+
+    let mut created = Vec::with_capacity(subtasks.len());
+    for subtask in subtasks {
+        let create = CreateTask {
+            parent_workspace_id,
+            ..CreateTask::from_title_description(
+                task.project_id,
+                subtask.title.trim().to_string(),
+                subtask.description,
+            )
+        };
+        created.push(Task::create(pool, &create, Uuid::new_v4()).await?);
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_breakdown_accepted",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "subtask_count": created.len(),
+                "has_parent_attempt": parent_workspace_id.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(created)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let breakdown_router = Router::new()
+        .route("/", post(propose_breakdown))
+        .route("/accept", post(accept_breakdown))
+        .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
+
+    Router::new().nest("/tasks/{task_id}/breakdown", breakdown_router)
+}
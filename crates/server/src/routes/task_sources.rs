@@ -0,0 +1,324 @@
+use std::collections::HashSet;
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post, put},
+};
+use chrono::{DateTime, Utc};
+use db::models::{
+    project::Project,
+    redis_task_source::{
+        CreateRedisTaskSource, RedisTaskSource, TaskSourceFieldMapping, UpdateRedisTaskSource,
+    },
+    task::{Task, TaskStatus},
+};
+use deployment::Deployment;
+use local_deployment::RedisSnapshot;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    task_similarity::SimilarTask,
+    task_sources::{self, SourceTask},
+};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl, error::ApiError, middleware::load_project_middleware,
+    routes::notion_import::redis_error_to_api_error,
+};
+
+/// Preview of a task read from a Redis task source
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct TaskSourcePreviewItem {
+    pub external_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    pub url: Option<String>,
+    pub will_import: bool,
+    pub skip_reason: Option<String>,
+    /// Most similar existing task when this one looks like a duplicate
+    pub similar_task: Option<SimilarTask>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct TaskSourcePreviewResponse {
+    pub tasks: Vec<TaskSourcePreviewItem>,
+    pub total_count: usize,
+    pub importable_count: usize,
+    pub duplicate_count: usize,
+    /// Items left out because they have no id or title under the field mapping
+    pub invalid_count: usize,
+    /// When the tasks were read from Redis
+    #[ts(type = "Date")]
+    pub synced_at: DateTime<Utc>,
+    /// Set when Redis is unreachable and the last synced tasks are shown instead
+    pub sync_error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct TaskSourceImportRequest {
+    pub external_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct TaskSourceImportResponse {
+    pub imported_count: usize,
+    pub skipped_count: usize,
+    pub errors: Vec<TaskSourceImportError>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct TaskSourceImportError {
+    pub external_id: String,
+    pub title: String,
+    pub error: String,
+}
+
+fn validate_source(
+    name: &str,
+    redis_key: &str,
+    field_mapping: &TaskSourceFieldMapping,
+) -> Result<(), ApiError> {
+    if name.trim().is_empty() {
+        return Err(ApiError::BadRequest("Task source needs a name".to_string()));
+    }
+    if redis_key.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Task source needs a Redis key".to_string(),
+        ));
+    }
+    if field_mapping.id.trim().is_empty() || field_mapping.title.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Field mapping needs paths for the id and title".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn conflict_on_duplicate(err: sqlx::Error, name: &str) -> ApiError {
+    match err {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+            ApiError::Conflict(format!("Task source {name} already exists"))
+        }
+        other => other.into(),
+    }
+}
+
+pub async fn get_task_sources(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<RedisTaskSource>>>, ApiError> {
+    let sources = RedisTaskSource::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(sources)))
+}
+
+pub async fn create_task_source(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateRedisTaskSource>,
+) -> Result<ResponseJson<ApiResponse<RedisTaskSource>>, ApiError> {
+    validate_source(&payload.name, &payload.redis_key, &payload.field_mapping)?;
+    let source = RedisTaskSource::create(&deployment.db().pool, project.id, &payload)
+        .await
+        .map_err(|e| conflict_on_duplicate(e, &payload.name))?;
+    Ok(ResponseJson(ApiResponse::success(source)))
+}
+
+async fn load_task_source(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    source_id: Uuid,
+) -> Result<RedisTaskSource, ApiError> {
+    RedisTaskSource::find_by_id(&deployment.db().pool, source_id)
+        .await?
+        .filter(|source| source.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn update_task_source(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, source_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateRedisTaskSource>,
+) -> Result<ResponseJson<ApiResponse<RedisTaskSource>>, ApiError> {
+    let existing = load_task_source(&deployment, project_id, source_id).await?;
+    let name = payload.name.as_deref().unwrap_or(&existing.name);
+    validate_source(
+        name,
+        payload.redis_key.as_deref().unwrap_or(&existing.redis_key),
+        payload
+            .field_mapping
+            .as_ref()
+            .unwrap_or(&existing.field_mapping.0),
+    )?;
+    let source = existing
+        .update(&deployment.db().pool, &payload)
+        .await
+        .map_err(|e| conflict_on_duplicate(e, name))?;
+    Ok(ResponseJson(ApiResponse::success(source)))
+}
+
+pub async fn delete_task_source(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, source_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let existing = load_task_source(&deployment, project_id, source_id).await?;
+    RedisTaskSource::delete(&deployment.db().pool, existing.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Tasks currently published under the source's key, and the number of items that could not be
+/// mapped.
+async fn read_source_tasks(
+    deployment: &DeploymentImpl,
+    source: &RedisTaskSource,
+) -> Result<(RedisSnapshot<SourceTask>, usize), ApiError> {
+    let redis = deployment.redis_client();
+    if !redis.is_configured() {
+        return Err(ApiError::BadRequest(
+            "Redis not configured. Set REDIS_URL environment variable.".to_string(),
+        ));
+    }
+    let snapshot = redis
+        .get_list::<serde_json::Value>(&source.redis_key)
+        .await
+        .map_err(redis_error_to_api_error)?;
+    let (tasks, invalid_count) = task_sources::map_items(source, &snapshot.items);
+    let snapshot = RedisSnapshot {
+        items: tasks,
+        synced_at: snapshot.synced_at,
+        sync_error: snapshot.sync_error,
+    };
+    Ok((snapshot, invalid_count))
+}
+
+pub async fn preview_task_source_import(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, source_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<TaskSourcePreviewResponse>>, ApiError> {
+    let source = load_task_source(&deployment, project_id, source_id).await?;
+    let (snapshot, invalid_count) = read_source_tasks(&deployment, &source).await?;
+
+    let config = deployment.config().read().await.embeddings.clone();
+    let duplicates = task_sources::find_duplicates(
+        &deployment.db().pool,
+        deployment.llm(),
+        &config,
+        project_id,
+        &snapshot.items,
+    )
+    .await?;
+
+    let preview_items: Vec<TaskSourcePreviewItem> = snapshot
+        .items
+        .into_iter()
+        .zip(duplicates)
+        .map(|(task, similar_task)| TaskSourcePreviewItem {
+            external_id: task.external_id,
+            title: task.title,
+            description: task.description,
+            status: task.status,
+            url: task.url,
+            will_import: similar_task.is_none(),
+            skip_reason: similar_task.as_ref().map(task_sources::duplicate_reason),
+            similar_task,
+        })
+        .collect();
+
+    let total_count = preview_items.len();
+    let duplicate_count = preview_items.iter().filter(|t| !t.will_import).count();
+
+    Ok(ResponseJson(ApiResponse::success(
+        TaskSourcePreviewResponse {
+            tasks: preview_items,
+            total_count,
+            importable_count: total_count - duplicate_count,
+            duplicate_count,
+            invalid_count,
+            synced_at: snapshot.synced_at,
+            sync_error: snapshot.sync_error,
+        },
+    )))
+}
+
+pub async fn execute_task_source_import(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, source_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<TaskSourceImportRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskSourceImportResponse>>, ApiError> {
+    let source = load_task_source(&deployment, project_id, source_id).await?;
+    let (snapshot, _) = read_source_tasks(&deployment, &source).await?;
+
+    let requested_ids: HashSet<&str> = request.external_ids.iter().map(|s| s.as_str()).collect();
+
+    let mut imported_count = 0;
+    let mut errors = Vec::new();
+    for task in snapshot
+        .items
+        .iter()
+        .filter(|t| requested_ids.contains(t.external_id.as_str()))
+    {
+        let create_task = task.to_create_task(project_id);
+        match Task::create(&deployment.db().pool, &create_task, Uuid::new_v4()).await {
+            Ok(_) => imported_count += 1,
+            Err(e) => errors.push(TaskSourceImportError {
+                external_id: task.external_id.clone(),
+                title: task.title.clone(),
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    let skipped_count = request.external_ids.len() - imported_count - errors.len();
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_source_imported",
+            serde_json::json!({
+                "project_id": project_id.to_string(),
+                "imported_count": imported_count,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        TaskSourceImportResponse {
+            imported_count,
+            skipped_count,
+            errors,
+        },
+    )))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_sources_router = Router::new()
+        .route(
+            "/task-sources",
+            get(get_task_sources).post(create_task_source),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    let projects_router = Router::new()
+        .route(
+            "/{project_id}/task-sources/{source_id}",
+            put(update_task_source).delete(delete_task_source),
+        )
+        .route(
+            "/{project_id}/task-sources/{source_id}/preview",
+            get(preview_task_source_import),
+        )
+        .route(
+            "/{project_id}/task-sources/{source_id}/import",
+            post(execute_task_source_import),
+        )
+        .nest("/{id}", project_sources_router);
+
+    Router::new().nest("/projects", projects_router)
+}
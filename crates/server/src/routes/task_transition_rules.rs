@@ -0,0 +1,71 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{delete, get},
+};
+use db::models::{
+    project::Project,
+    task_transition_rule::{CreateTaskTransitionRule, TaskTransitionRule},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+pub async fn get_transition_rules(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskTransitionRule>>>, ApiError> {
+    let rules = TaskTransitionRule::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(rules)))
+}
+
+pub async fn create_transition_rule(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskTransitionRule>,
+) -> Result<ResponseJson<ApiResponse<TaskTransitionRule>>, ApiError> {
+    if payload.from_status.as_ref() == Some(&payload.to_status) {
+        return Err(ApiError::BadRequest(
+            "A transition rule needs two different statuses".to_string(),
+        ));
+    }
+    let rule = TaskTransitionRule::create(&deployment.db().pool, project.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+pub async fn delete_transition_rule(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, rule_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rule = TaskTransitionRule::find_by_id(&deployment.db().pool, rule_id)
+        .await?
+        .filter(|rule| rule.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    TaskTransitionRule::delete(&deployment.db().pool, rule.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_rules_router = Router::new()
+        .route(
+            "/transition-rules",
+            get(get_transition_rules).post(create_transition_rule),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    let projects_router = Router::new()
+        .route(
+            "/{project_id}/transition-rules/{rule_id}",
+            delete(delete_transition_rule),
+        )
+        .nest("/{id}", project_rules_router);
+
+    Router::new().nest("/projects", projects_router)
+}
@@ -4,37 +4,71 @@ use anyhow;
 use axum::{
     Extension, Json, Router,
     extract::{
-        Query, State,
+        Path, Query, RawQuery, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
+    response::{IntoResponse, Json as ResponseJson, Response},
     routing::{delete, get, post, put},
 };
 use db::models::{
+    attempt_e2e_run::AttemptE2eArtifact,
+    execution_process::ExecutionProcess,
     image::TaskImage,
     project::{Project, ProjectError},
+    prompt_template::{PromptTemplate, PromptTemplateKind},
     repo::Repo,
-    task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
+    review::ReviewFinding,
+    review_checklist::ReviewChecklistItem,
+    task::{CreateTask, Task, TaskStatus, TaskWithAttemptStatus, UpdateTask},
+    task_assignee::{AssignTask, TaskAssignee},
+    task_attachment::TaskAttachment,
+    task_label::TaskLabel,
+    task_prompt::TaskPrompt,
+    task_time_entry::{CreateTaskTimeEntry, TaskTimeEntry},
+    user::User,
     workspace::{CreateWorkspace, Workspace},
     workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
 };
 use deployment::Deployment;
-use executors::profile::ExecutorProfileId;
+use executors::{executors::claude::types::PermissionMode, profile::ExecutorProfileId};
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use services::services::{
-    container::ContainerService, share::ShareError, workspace_manager::WorkspaceManager,
+    attempt_compare::{self, AttemptComparison, AttemptSide},
+    automation,
+    container::ContainerService,
+    presence::{PresenceActivity, TaskPresence, UpdatePresence},
+    share::ShareError,
+    task_assist,
+    task_similarity::{self, SimilarTask},
+    task_workflow,
+    time_tracking::{self, TaskTime},
+    workspace_manager::WorkspaceManager,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
-use utils::{api::oauth::LoginStatus, response::ApiResponse};
+use utils::{
+    api::oauth::LoginStatus,
+    diff::Diff,
+    etag::ETag,
+    pagination::{PageQuery, SortOrder},
+    response::ApiResponse,
+};
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_task_middleware,
-    routes::task_attempts::WorkspaceRepoInput,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{CurrentUser, load_task_middleware},
+    routes::{
+        auto_start::auto_start_on_move,
+        prompt_templates::load_prompt_templates,
+        task_attempts::{
+            WorkspaceRepoInput, ensure_attempt_mode_supported, resolve_attempt_permission_mode,
+        },
+    },
 };
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -44,13 +78,25 @@ pub struct TaskQuery {
 
 pub async fn get_tasks(
     State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+    RawQuery(raw_query): RawQuery,
     Query(query): Query<TaskQuery>,
-) -> Result<ResponseJson<ApiResponse<Vec<TaskWithAttemptStatus>>>, ApiError> {
-    let tasks =
-        Task::find_by_project_id_with_attempt_status(&deployment.db().pool, query.project_id)
-            .await?;
+    Query(page): Query<PageQuery>,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+
+    // The query string is part of the tag so each page/sort combination validates separately
+    let version = Task::board_version(pool, query.project_id).await?;
+    let etag = ETag::weak(format!("{version}?{}", raw_query.unwrap_or_default()));
+    if etag.matches(&headers) {
+        return Ok(etag.not_modified());
+    }
 
-    Ok(ResponseJson(ApiResponse::success(tasks)))
+    let keyset = page.keyset(SortOrder::Desc)?;
+    let tasks =
+        Task::find_page_by_project_id_with_attempt_status(pool, query.project_id, &keyset).await?;
+    let total_count = Task::count_by_project_id(pool, query.project_id).await?;
+    Ok(etag.attach(keyset.page(tasks, total_count as usize)))
 }
 
 pub async fn stream_tasks_ws(
@@ -107,11 +153,131 @@ pub async fn get_task(
     Ok(ResponseJson(ApiResponse::success(task)))
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct FindSimilarTasksRequest {
+    pub project_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    /// Task being edited, left out of the results
+    pub exclude_task_id: Option<Uuid>,
+}
+
+/// Existing tasks in the project that look like duplicates of the given title and description,
+/// so clients can warn before creating the task.
+pub async fn find_similar_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<FindSimilarTasksRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<SimilarTask>>>, ApiError> {
+    let text = task_similarity::task_text(&payload.title, payload.description.as_deref());
+    if text.is_empty() {
+        return Ok(ResponseJson(ApiResponse::success(Vec::new())));
+    }
+
+    let existing: Vec<Task> =
+        Task::find_by_project_id_with_attempt_status(&deployment.db().pool, payload.project_id)
+            .await?
+            .into_iter()
+            .map(|t| t.task)
+            .filter(|t| Some(t.id) != payload.exclude_task_id)
+            .collect();
+    let config = deployment.config().read().await.embeddings.clone();
+    let similar = task_similarity::find_similar(deployment.llm(), &config, &existing, &[text])
+        .await
+        .pop()
+        .unwrap_or_default();
+
+    Ok(ResponseJson(ApiResponse::success(similar)))
+}
+
+/// The prompt the task was created from, when its title and description were generated.
+pub async fn get_original_prompt(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<TaskPrompt>>>, ApiError> {
+    let prompt = TaskPrompt::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(prompt)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CompareAttemptsQuery {
+    pub a: Uuid,
+    pub b: Uuid,
+}
+
+async fn compared_attempt(
+    deployment: &DeploymentImpl,
+    task: &Task,
+    workspace_id: Uuid,
+) -> Result<(AttemptSide, Vec<Diff>), ApiError> {
+    let pool = &deployment.db().pool;
+    let workspace = Workspace::find_by_id(pool, workspace_id)
+        .await?
+        .filter(|workspace| workspace.task_id == task.id)
+        .ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "Attempt {workspace_id} is not an attempt of this task"
+            ))
+        })?;
+    let runs = ExecutionProcess::find_attempt_runs(pool, workspace.id).await?;
+    let diffs = deployment
+        .container()
+        .diff_snapshot(&workspace, false)
+        .await?;
+    Ok((
+        attempt_compare::attempt_side(&workspace, &runs, &diffs),
+        diffs,
+    ))
+}
+
+/// Two attempts of the task side by side: the files each changed and how, with their cost,
+/// agent time and test outcome.
+pub async fn compare_attempts(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<CompareAttemptsQuery>,
+) -> Result<ResponseJson<ApiResponse<AttemptComparison>>, ApiError> {
+    if query.a == query.b {
+        return Err(ApiError::BadRequest(
+            "Pick two different attempts to compare".to_string(),
+        ));
+    }
+    let (a, a_diffs) = compared_attempt(&deployment, &task, query.a).await?;
+    let (b, b_diffs) = compared_attempt(&deployment, &task, query.b).await?;
+    Ok(ResponseJson(ApiResponse::success(AttemptComparison {
+        a,
+        b,
+        files: attempt_compare::compare_diffs(&a_diffs, &b_diffs),
+    })))
+}
+
+/// If the payload asks for it, swap in a generated title and description and return the
+/// submitted prompt so it can be stored with the task.
+async fn generate_task_details(
+    deployment: &DeploymentImpl,
+    payload: &mut CreateTask,
+) -> Result<Option<String>, ApiError> {
+    if payload.generate_details != Some(true) {
+        return Ok(None);
+    }
+    let original_prompt = payload.to_prompt();
+    if original_prompt.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "A prompt is required to generate task details".to_string(),
+        ));
+    }
+
+    let details = task_assist::generate_task_details(deployment.llm(), &original_prompt).await?;
+    payload.title = details.title;
+    payload.description = Some(details.description);
+    Ok(Some(original_prompt))
+}
+
 pub async fn create_task(
     State(deployment): State<DeploymentImpl>,
-    Json(payload): Json<CreateTask>,
+    Json(mut payload): Json<CreateTask>,
 ) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
     let id = Uuid::new_v4();
+    let original_prompt = generate_task_details(&deployment, &mut payload).await?;
 
     tracing::debug!(
         "Creating task '{}' in project {}",
@@ -121,6 +287,10 @@ pub async fn create_task(
 
     let task = Task::create(&deployment.db().pool, &payload, id).await?;
 
+    if let Some(original_prompt) = &original_prompt {
+        TaskPrompt::create(&deployment.db().pool, task.id, original_prompt).await?;
+    }
+
     if let Some(image_ids) = &payload.image_ids {
         TaskImage::associate_many_dedup(&deployment.db().pool, task.id, image_ids).await?;
     }
@@ -133,6 +303,7 @@ pub async fn create_task(
             "project_id": payload.project_id,
             "has_description": task.description.is_some(),
             "has_images": payload.image_ids.is_some(),
+            "generated_details": original_prompt.is_some(),
             }),
         )
         .await;
@@ -145,23 +316,63 @@ pub struct CreateAndStartTaskRequest {
     pub task: CreateTask,
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
+    /// Investigate only: run the agent with read-only tools, for codebase Q&A and audits
+    #[serde(default)]
+    #[ts(optional)]
+    pub read_only: Option<bool>,
+    /// Dry run: log what the agent's writing tool calls would do instead of running them
+    #[serde(default)]
+    #[ts(optional)]
+    pub dry_run: Option<bool>,
+    /// Permission mode to run the agent with instead of the one implied by the profile
+    #[serde(default)]
+    #[ts(optional)]
+    pub permission_mode: Option<PermissionMode>,
+    /// System prompt templates added to the agent's first prompt, in order
+    #[serde(default)]
+    #[ts(optional)]
+    pub prompt_template_ids: Option<Vec<Uuid>>,
 }
 
 pub async fn create_task_and_start(
     State(deployment): State<DeploymentImpl>,
-    Json(payload): Json<CreateAndStartTaskRequest>,
+    Json(mut payload): Json<CreateAndStartTaskRequest>,
 ) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus>>, ApiError> {
     if payload.repos.is_empty() {
         return Err(ApiError::BadRequest(
             "At least one repository is required".to_string(),
         ));
     }
+    ensure_attempt_mode_supported(
+        &payload.executor_profile_id,
+        payload.read_only.unwrap_or(false),
+        payload.dry_run.unwrap_or(false),
+    )?;
+    let permission_mode = resolve_attempt_permission_mode(
+        &deployment,
+        payload.task.project_id,
+        &payload.executor_profile_id,
+        payload.permission_mode,
+    )
+    .await?;
 
     let pool = &deployment.db().pool;
+    let templates = load_prompt_templates(
+        pool,
+        payload.prompt_template_ids.as_deref().unwrap_or_default(),
+        payload.task.project_id,
+        PromptTemplateKind::SystemPrompt,
+    )
+    .await?;
+    let original_prompt = generate_task_details(&deployment, &mut payload.task).await?;
 
     let task_id = Uuid::new_v4();
     let task = Task::create(pool, &payload.task, task_id).await?;
 
+    if let Some(original_prompt) = &original_prompt {
+        TaskPrompt::create(pool, task.id, original_prompt).await?;
+    }
+
     if let Some(image_ids) = &payload.task.image_ids {
         TaskImage::associate_many_dedup(pool, task.id, image_ids).await?;
     }
@@ -174,6 +385,7 @@ pub async fn create_task_and_start(
                 "project_id": task.project_id,
                 "has_description": task.description.is_some(),
                 "has_images": payload.task.image_ids.is_some(),
+                "generated_details": original_prompt.is_some(),
             }),
         )
         .await;
@@ -199,6 +411,9 @@ pub async fn create_task_and_start(
         &CreateWorkspace {
             branch: git_branch_name,
             agent_working_dir,
+            read_only: payload.read_only.unwrap_or(false),
+            dry_run: payload.dry_run.unwrap_or(false),
+            permission_mode,
         },
         attempt_id,
         task.id,
@@ -214,6 +429,8 @@ pub async fn create_task_and_start(
         })
         .collect();
     WorkspaceRepo::create_many(&deployment.db().pool, workspace.id, &workspace_repos).await?;
+    let template_ids = templates.iter().map(|t| t.id).collect::<Vec<_>>();
+    PromptTemplate::set_for_workspace(pool, workspace.id, &template_ids).await?;
 
     let is_attempt_running = deployment
         .container()
@@ -246,6 +463,55 @@ pub async fn create_task_and_start(
     })))
 }
 
+/// Reject moving a task to Done while its latest review has unresolved blocking findings, when
+/// that gate is enabled.
+async fn ensure_no_blocking_findings(
+    task: &Task,
+    new_status: Option<&TaskStatus>,
+    deployment: &DeploymentImpl,
+) -> Result<(), ApiError> {
+    if new_status != Some(&TaskStatus::Done)
+        || task.status == TaskStatus::Done
+        || !deployment
+            .config()
+            .read()
+            .await
+            .code_review
+            .block_done_on_findings
+    {
+        return Ok(());
+    }
+    let blocking =
+        ReviewFinding::count_open_blocking_for_task(&deployment.db().pool, task.id).await?;
+    if blocking > 0 {
+        return Err(ApiError::Conflict(format!(
+            "The latest review has {blocking} unresolved blocking finding(s)"
+        )));
+    }
+    Ok(())
+}
+
+/// Reject moving a task to Done while required checklist items are unchecked on its latest
+/// attempt.
+async fn ensure_checklist_complete(
+    task: &Task,
+    new_status: Option<&TaskStatus>,
+    deployment: &DeploymentImpl,
+) -> Result<(), ApiError> {
+    if new_status != Some(&TaskStatus::Done) || task.status == TaskStatus::Done {
+        return Ok(());
+    }
+    let unchecked =
+        ReviewChecklistItem::count_unchecked_required_for_task(&deployment.db().pool, task.id)
+            .await?;
+    if unchecked > 0 {
+        return Err(ApiError::Conflict(format!(
+            "{unchecked} required review checklist item(s) are unchecked on the latest attempt"
+        )));
+    }
+    Ok(())
+}
+
 pub async fn update_task(
     Extension(existing_task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
@@ -253,6 +519,11 @@ pub async fn update_task(
     Json(payload): Json<UpdateTask>,
 ) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
     ensure_shared_task_auth(&existing_task, &deployment).await?;
+    ensure_no_blocking_findings(&existing_task, payload.status.as_ref(), &deployment).await?;
+    ensure_checklist_complete(&existing_task, payload.status.as_ref(), &deployment).await?;
+    if let Some(status) = &payload.status {
+        task_workflow::check_transition(&deployment.db().pool, &existing_task, status).await?;
+    }
 
     // Use existing values if not provided in update
     let title = payload.title.unwrap_or(existing_task.title);
@@ -261,6 +532,7 @@ pub async fn update_task(
         Some(s) => Some(s),                     // Non-empty string = update description
         None => existing_task.description,      // Field omitted = keep existing
     };
+    let existing_status = existing_task.status.clone();
     let status = payload.status.unwrap_or(existing_task.status);
     let parent_workspace_id = payload
         .parent_workspace_id
@@ -290,6 +562,16 @@ pub async fn update_task(
         publisher.update_shared_task(&task).await?;
     }
 
+    if task.status != existing_status
+        && let Err(e) = auto_start_on_move(&deployment, &task).await
+    {
+        tracing::error!(
+            "Failed to auto-start an attempt for task {}: {}",
+            task.id,
+            e
+        );
+    }
+
     Ok(ResponseJson(ApiResponse::success(task)))
 }
 
@@ -335,6 +617,10 @@ pub async fn delete_task(
 
     let repositories = WorkspaceRepo::find_unique_repos_for_task(pool, task.id).await?;
 
+    // Attachment rows are removed by FK CASCADE, their stored files are removed afterwards
+    let attachments = TaskAttachment::find_by_task_id(pool, task.id).await?;
+    let e2e_artifacts = AttemptE2eArtifact::find_by_task_id(pool, task.id).await?;
+
     // Collect workspace directories that need cleanup
     let workspace_dirs: Vec<PathBuf> = attempts
         .iter()
@@ -391,6 +677,7 @@ pub async fn delete_task(
 
     let task_id = task.id;
     let pool = pool.clone();
+    let attachment_service = deployment.attachments().clone();
     tokio::spawn(async move {
         tracing::info!(
             "Starting background cleanup for task {} ({} workspaces, {} repos)",
@@ -421,6 +708,12 @@ pub async fn delete_task(
             _ => {}
         }
 
+        attachment_service.delete_blobs(&attachments).await;
+        attachment_service
+            .delete_e2e_artifact_blobs(&e2e_artifacts)
+            .await;
+        attachment_service.delete_log_exports(task_id).await;
+
         tracing::info!("Background cleanup completed for task {}", task_id);
     });
 
@@ -460,6 +753,174 @@ pub async fn share_task(
     })))
 }
 
+/// Who currently has the task open and what they are doing.
+pub async fn get_task_presence(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskPresence>>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(
+        deployment.presence().list(task.id),
+    )))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateTaskPresenceRequest {
+    pub client_id: String,
+    pub activity: PresenceActivity,
+    /// Shown to other viewers in single-user mode; the account name is used otherwise
+    pub name: Option<String>,
+}
+
+/// Join the task or refresh this client's presence. Clients call this periodically while the
+/// task is open. Claiming an attempt start or a question answer that another client already
+/// holds fails with 409.
+pub async fn update_task_presence(
+    Extension(task): Extension<Task>,
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateTaskPresenceRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskPresence>>>, ApiError> {
+    if payload.client_id.trim().is_empty() {
+        return Err(ApiError::BadRequest("client_id is required".to_string()));
+    }
+    let name = user
+        .as_ref()
+        .map(|u| u.display_name.clone().unwrap_or_else(|| u.username.clone()))
+        .or(payload.name.filter(|n| !n.trim().is_empty()))
+        .unwrap_or_else(|| "Anonymous".to_string());
+
+    let viewers = deployment.presence().update(
+        task.project_id,
+        task.id,
+        UpdatePresence {
+            client_id: payload.client_id,
+            user_id: user.map(|u| u.id),
+            name,
+            activity: payload.activity,
+        },
+    )?;
+    Ok(ResponseJson(ApiResponse::success(viewers)))
+}
+
+pub async fn leave_task_presence(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, client_id)): Path<(Uuid, String)>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskPresence>>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(
+        deployment.presence().leave(task.id, &client_id),
+    )))
+}
+
+pub async fn get_task_labels(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskLabel>>>, ApiError> {
+    let labels = TaskLabel::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct AddTaskLabelRequest {
+    pub label: String,
+}
+
+/// Label the task. Adding a label the task already has is a no-op.
+pub async fn add_task_label(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<AddTaskLabelRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskLabel>>>, ApiError> {
+    let label = payload.label.trim();
+    if label.is_empty() {
+        return Err(ApiError::BadRequest("label is required".to_string()));
+    }
+    let pool = &deployment.db().pool;
+    automation::add_label(pool, deployment.events().entity_events(), &task, label).await?;
+    let labels = TaskLabel::find_by_task_id(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+pub async fn remove_task_label(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, label)): Path<(Uuid, String)>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskLabel>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    TaskLabel::remove(pool, task.id, &label).await?;
+    let labels = TaskLabel::find_by_task_id(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+/// Agent time per attempt and time logged by hand on the task.
+pub async fn get_task_time(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskTime>>, ApiError> {
+    let time = time_tracking::task_time(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(time)))
+}
+
+pub async fn create_task_time_entry(
+    Extension(task): Extension<Task>,
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskTimeEntry>,
+) -> Result<ResponseJson<ApiResponse<TaskTimeEntry>>, ApiError> {
+    if payload.seconds <= 0 {
+        return Err(ApiError::BadRequest(
+            "Logged time must be positive".to_string(),
+        ));
+    }
+    let entry =
+        TaskTimeEntry::create(&deployment.db().pool, task.id, user.map(|u| u.id), &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(entry)))
+}
+
+pub async fn delete_task_time_entry(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, entry_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let entry = TaskTimeEntry::find_by_id(pool, entry_id)
+        .await?
+        .filter(|entry| entry.task_id == task.id)
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    TaskTimeEntry::delete(pool, entry.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn get_task_assignee(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<TaskAssignee>>>, ApiError> {
+    let assignee = TaskAssignee::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(assignee)))
+}
+
+/// Assign the task to a user, replacing any previous assignee.
+pub async fn assign_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<AssignTask>,
+) -> Result<ResponseJson<ApiResponse<TaskAssignee>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if User::find_by_id(pool, payload.user_id).await?.is_none() {
+        return Err(ApiError::BadRequest("Unknown user".to_string()));
+    }
+    let assignee = TaskAssignee::assign(pool, task.id, payload.user_id).await?;
+    Ok(ResponseJson(ApiResponse::success(assignee)))
+}
+
+pub async fn unassign_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    TaskAssignee::unassign(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_actions_router = Router::new()
         .route("/", put(update_task))
@@ -468,6 +929,24 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let task_id_router = Router::new()
         .route("/", get(get_task))
+        .route("/original-prompt", get(get_original_prompt))
+        .route("/attempts/compare", get(compare_attempts))
+        .route(
+            "/presence",
+            get(get_task_presence).put(update_task_presence),
+        )
+        .route("/presence/{client_id}", delete(leave_task_presence))
+        .route(
+            "/assignee",
+            get(get_task_assignee)
+                .put(assign_task)
+                .delete(unassign_task),
+        )
+        .route("/labels", get(get_task_labels).post(add_task_label))
+        .route("/labels/{label}", delete(remove_task_label))
+        .route("/time", get(get_task_time))
+        .route("/time-entries", post(create_task_time_entry))
+        .route("/time-entries/{entry_id}", delete(delete_task_time_entry))
         .merge(task_actions_router)
         .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
 
@@ -475,6 +954,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/", get(get_tasks).post(create_task))
         .route("/stream/ws", get(stream_tasks_ws))
         .route("/create-and-start", post(create_task_and_start))
+        .route("/similar", post(find_similar_tasks))
         .nest("/{task_id}", task_id_router);
 
     // mount under /projects/:project_id/tasks
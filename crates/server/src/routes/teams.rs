@@ -0,0 +1,382 @@
+use std::collections::{HashMap, HashSet};
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    execution_process::ExecutionProcess,
+    project::Project,
+    team::{CreateTeam, Team, TeamMember, TeamRole, UpdateTeam},
+    user::{User, UserRole},
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utils::{
+    approvals::ApprovalRequest, response::ApiResponse, user_questions::UserQuestionRequest,
+};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::CurrentUser};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct SetTeamMember {
+    pub role: TeamRole,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct TeamDetails {
+    pub team: Team,
+    pub members: Vec<TeamMember>,
+    pub projects: Vec<Project>,
+}
+
+/// Where a pending question or approval comes from.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct PendingItemContext {
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub workspace_id: Uuid,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct TeamPendingQuestion {
+    pub context: PendingItemContext,
+    pub question: UserQuestionRequest,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct TeamPendingApproval {
+    pub context: PendingItemContext,
+    pub approval: ApprovalRequest,
+}
+
+/// Questions and approvals agents are waiting on across a set of projects.
+#[derive(Debug, Serialize, TS)]
+pub struct TeamPendingItems {
+    pub questions: Vec<TeamPendingQuestion>,
+    pub approvals: Vec<TeamPendingApproval>,
+}
+
+async fn load_team(deployment: &DeploymentImpl, id: Uuid) -> Result<Team, ApiError> {
+    Team::find_by_id(&deployment.db().pool, id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+/// Single-user mode and admins may do anything; otherwise members may read a team and owners
+/// may change it.
+async fn authorize(
+    deployment: &DeploymentImpl,
+    user: Option<&User>,
+    team_id: Uuid,
+    required: TeamRole,
+) -> Result<(), ApiError> {
+    let Some(user) = user.filter(|user| user.role != UserRole::Admin) else {
+        return Ok(());
+    };
+    match TeamMember::find_role(&deployment.db().pool, team_id, user.id).await? {
+        Some(TeamRole::Owner) => Ok(()),
+        Some(TeamRole::Member) if required == TeamRole::Member => Ok(()),
+        Some(TeamRole::Member) => Err(ApiError::Forbidden(
+            "Only team owners can change the team".to_string(),
+        )),
+        None => Err(ApiError::Forbidden(
+            "You are not a member of this team".to_string(),
+        )),
+    }
+}
+
+/// Pending questions and approvals whose attempts belong to one of the projects.
+async fn pending_items(
+    deployment: &DeploymentImpl,
+    project_ids: &HashSet<Uuid>,
+) -> Result<TeamPendingItems, ApiError> {
+    let pool = &deployment.db().pool;
+    let questions = deployment.user_questions().list_pending();
+    let approvals = deployment.approvals().list_pending();
+
+    let mut contexts: HashMap<Uuid, Option<PendingItemContext>> = HashMap::new();
+    let process_ids = questions
+        .iter()
+        .map(|q| q.execution_process_id)
+        .chain(approvals.iter().map(|a| a.execution_process_id));
+    for process_id in process_ids {
+        if contexts.contains_key(&process_id) {
+            continue;
+        }
+        let context = match ExecutionProcess::load_context(pool, process_id).await {
+            Ok(ctx) if project_ids.contains(&ctx.project.id) => Some(PendingItemContext {
+                project_id: ctx.project.id,
+                project_name: ctx.project.name,
+                task_id: ctx.task.id,
+                task_title: ctx.task.title,
+                workspace_id: ctx.workspace.id,
+            }),
+            Ok(_) | Err(sqlx::Error::RowNotFound) => None,
+            Err(e) => return Err(e.into()),
+        };
+        contexts.insert(process_id, context);
+    }
+
+    let context_of = |process_id: &Uuid| contexts.get(process_id).cloned().flatten();
+    Ok(TeamPendingItems {
+        questions: questions
+            .into_iter()
+            .filter_map(|question| {
+                Some(TeamPendingQuestion {
+                    context: context_of(&question.execution_process_id)?,
+                    question,
+                })
+            })
+            .collect(),
+        approvals: approvals
+            .into_iter()
+            .filter_map(|approval| {
+                Some(TeamPendingApproval {
+                    context: context_of(&approval.execution_process_id)?,
+                    approval,
+                })
+            })
+            .collect(),
+    })
+}
+
+async fn project_ids(
+    deployment: &DeploymentImpl,
+    teams: &[Team],
+) -> Result<HashSet<Uuid>, ApiError> {
+    let mut ids = HashSet::new();
+    for team in teams {
+        let projects = Team::find_projects(&deployment.db().pool, team.id).await?;
+        ids.extend(projects.into_iter().map(|project| project.id));
+    }
+    Ok(ids)
+}
+
+/// Teams of the current user; every team in single-user mode.
+async fn my_teams(deployment: &DeploymentImpl, user: Option<&User>) -> Result<Vec<Team>, ApiError> {
+    let pool = &deployment.db().pool;
+    Ok(match user {
+        Some(user) => Team::find_by_user_id(pool, user.id).await?,
+        None => Team::find_all(pool).await?,
+    })
+}
+
+pub async fn list_teams(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Team>>>, ApiError> {
+    let teams = match &user {
+        Some(user) if user.role == UserRole::Admin => Team::find_all(&deployment.db().pool).await?,
+        user => my_teams(&deployment, user.as_ref()).await?,
+    };
+    Ok(ResponseJson(ApiResponse::success(teams)))
+}
+
+pub async fn create_team(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<CreateTeam>,
+) -> Result<ResponseJson<ApiResponse<Team>>, ApiError> {
+    payload.name = payload.name.trim().to_string();
+    if payload.name.is_empty() {
+        return Err(ApiError::BadRequest("Team name is required".to_string()));
+    }
+    let pool = &deployment.db().pool;
+    let team = Team::create(pool, &payload).await?;
+    // The creator owns the team so they can manage it without an admin
+    if let Some(user) = &user {
+        TeamMember::upsert(pool, team.id, user.id, TeamRole::Owner).await?;
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "team_created",
+            serde_json::json!({ "team_id": team.id.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(team)))
+}
+
+pub async fn get_team(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Path(team_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<TeamDetails>>, ApiError> {
+    authorize(&deployment, user.as_ref(), team_id, TeamRole::Member).await?;
+    let pool = &deployment.db().pool;
+    let team = load_team(&deployment, team_id).await?;
+    let members = TeamMember::find_by_team_id(pool, team_id).await?;
+    let projects = Team::find_projects(pool, team_id).await?;
+    Ok(ResponseJson(ApiResponse::success(TeamDetails {
+        team,
+        members,
+        projects,
+    })))
+}
+
+pub async fn update_team(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Path(team_id): Path<Uuid>,
+    Json(payload): Json<UpdateTeam>,
+) -> Result<ResponseJson<ApiResponse<Team>>, ApiError> {
+    authorize(&deployment, user.as_ref(), team_id, TeamRole::Owner).await?;
+    let existing = load_team(&deployment, team_id).await?;
+    let name = payload
+        .name
+        .as_deref()
+        .map(str::trim)
+        .unwrap_or(&existing.name);
+    if name.is_empty() {
+        return Err(ApiError::BadRequest("Team name is required".to_string()));
+    }
+    let notification_defaults = payload
+        .notification_defaults
+        .unwrap_or(existing.notification_defaults.0);
+
+    let team = Team::update(
+        &deployment.db().pool,
+        team_id,
+        name,
+        payload.executor_profile.as_ref(),
+        &notification_defaults,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(team)))
+}
+
+pub async fn delete_team(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Path(team_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    authorize(&deployment, user.as_ref(), team_id, TeamRole::Owner).await?;
+    Team::delete(&deployment.db().pool, team_id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn set_team_member(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Path((team_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<SetTeamMember>,
+) -> Result<ResponseJson<ApiResponse<Vec<TeamMember>>>, ApiError> {
+    authorize(&deployment, user.as_ref(), team_id, TeamRole::Owner).await?;
+    let pool = &deployment.db().pool;
+    load_team(&deployment, team_id).await?;
+    User::find_by_id(pool, user_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest(format!("Unknown user {user_id}")))?;
+    if payload.role == TeamRole::Member
+        && TeamMember::find_role(pool, team_id, user_id).await? == Some(TeamRole::Owner)
+        && TeamMember::count_owners(pool, team_id).await? <= 1
+    {
+        return Err(ApiError::Conflict(
+            "A team needs at least one owner".to_string(),
+        ));
+    }
+
+    TeamMember::upsert(pool, team_id, user_id, payload.role).await?;
+    let members = TeamMember::find_by_team_id(pool, team_id).await?;
+    Ok(ResponseJson(ApiResponse::success(members)))
+}
+
+pub async fn remove_team_member(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Path((team_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Vec<TeamMember>>>, ApiError> {
+    authorize(&deployment, user.as_ref(), team_id, TeamRole::Owner).await?;
+    let pool = &deployment.db().pool;
+    if TeamMember::find_role(pool, team_id, user_id).await? == Some(TeamRole::Owner)
+        && TeamMember::count_owners(pool, team_id).await? <= 1
+    {
+        return Err(ApiError::Conflict(
+            "A team needs at least one owner".to_string(),
+        ));
+    }
+
+    TeamMember::delete(pool, team_id, user_id).await?;
+    let members = TeamMember::find_by_team_id(pool, team_id).await?;
+    Ok(ResponseJson(ApiResponse::success(members)))
+}
+
+pub async fn add_team_project(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Path((team_id, project_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Vec<Project>>>, ApiError> {
+    authorize(&deployment, user.as_ref(), team_id, TeamRole::Owner).await?;
+    let pool = &deployment.db().pool;
+    load_team(&deployment, team_id).await?;
+    Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest(format!("Unknown project {project_id}")))?;
+
+    Team::add_project(pool, team_id, project_id).await?;
+    let projects = Team::find_projects(pool, team_id).await?;
+    Ok(ResponseJson(ApiResponse::success(projects)))
+}
+
+pub async fn remove_team_project(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Path((team_id, project_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Vec<Project>>>, ApiError> {
+    authorize(&deployment, user.as_ref(), team_id, TeamRole::Owner).await?;
+    let pool = &deployment.db().pool;
+    Team::remove_project(pool, team_id, project_id).await?;
+    let projects = Team::find_projects(pool, team_id).await?;
+    Ok(ResponseJson(ApiResponse::success(projects)))
+}
+
+/// Questions and approvals pending in the team's projects.
+pub async fn get_team_pending(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Path(team_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<TeamPendingItems>>, ApiError> {
+    authorize(&deployment, user.as_ref(), team_id, TeamRole::Member).await?;
+    let team = load_team(&deployment, team_id).await?;
+    let project_ids = project_ids(&deployment, &[team]).await?;
+    let items = pending_items(&deployment, &project_ids).await?;
+    Ok(ResponseJson(ApiResponse::success(items)))
+}
+
+/// Questions and approvals pending in the projects of every team the current user is in.
+pub async fn get_my_pending(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TeamPendingItems>>, ApiError> {
+    let teams = my_teams(&deployment, user.as_ref()).await?;
+    let project_ids = project_ids(&deployment, &teams).await?;
+    let items = pending_items(&deployment, &project_ids).await?;
+    Ok(ResponseJson(ApiResponse::success(items)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/teams", get(list_teams).post(create_team))
+        .route("/teams/pending", get(get_my_pending))
+        .route(
+            "/teams/{team_id}",
+            get(get_team).put(update_team).delete(delete_team),
+        )
+        .route("/teams/{team_id}/pending", get(get_team_pending))
+        .route(
+            "/teams/{team_id}/members/{user_id}",
+            put(set_team_member).delete(remove_team_member),
+        )
+        .route(
+            "/teams/{team_id}/projects/{project_id}",
+            put(add_team_project).delete(remove_team_project),
+        )
+}
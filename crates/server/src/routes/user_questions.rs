@@ -1,42 +1,350 @@
+use std::time::Duration;
+
 use axum::{
-    Json, Router,
+    Extension, Json, Router,
     extract::{Path, State},
-    http::StatusCode,
-    routing::post,
+    http::{HeaderMap, StatusCode},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    api_token::ApiToken,
+    execution_process::ExecutionProcess,
+    image::{Image, TaskImage},
+    user::User,
 };
 use deployment::Deployment;
-use utils::user_questions::UserQuestionResponse;
+use services::services::user_questions::QuestionError;
+use utils::{
+    response::ApiResponse,
+    user_questions::{QuestionClaim, UserQuestionRequest, UserQuestionResponse},
+};
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{CurrentUser, token_covers},
+};
+
+/// Retries of a respond request carrying the same key get the first response back
+const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Name shown to other reviewers while a question is claimed
+fn claimant_name(user: Option<&User>) -> String {
+    user.map(|user| {
+        user.display_name
+            .clone()
+            .unwrap_or_else(|| user.username.clone())
+    })
+    .unwrap_or_else(|| "Local user".to_string())
+}
+
+fn question_error(e: QuestionError) -> ApiError {
+    match e {
+        QuestionError::NotFound => ApiError::Database(sqlx::Error::RowNotFound),
+        e => ApiError::Conflict(e.to_string()),
+    }
+}
+
+/// Copy images attached to answers into the attempt's worktree and record their paths,
+/// so the agent can open them.
+async fn resolve_answer_attachments(
+    deployment: &DeploymentImpl,
+    question_id: &str,
+    response: &mut UserQuestionResponse,
+) -> Result<(), ApiError> {
+    let Some(execution_process_id) = deployment
+        .user_questions()
+        .pending_execution_process_id(question_id)
+    else {
+        return Ok(());
+    };
+
+    let attachments: Vec<_> = response
+        .answers
+        .iter_mut()
+        .filter_map(|answer| answer.attachments.as_mut())
+        .flatten()
+        .filter(|attachment| attachment.image_id.is_some())
+        .collect();
+    if attachments.is_empty() {
+        return Ok(());
+    }
 
-use crate::DeploymentImpl;
+    let pool = &deployment.db().pool;
+    let ctx = ExecutionProcess::load_context(pool, execution_process_id).await?;
+    let worktree = ctx
+        .workspace
+        .container_ref
+        .as_deref()
+        .map(std::path::Path::new);
+
+    let mut image_ids = Vec::new();
+    for attachment in attachments {
+        let Some(image_id) = attachment.image_id else {
+            continue;
+        };
+        let image = Image::find_by_id(pool, image_id)
+            .await?
+            .ok_or_else(|| ApiError::BadRequest(format!("Unknown attachment image {image_id}")))?;
+        attachment.path = Some(format!(
+            "{}/{}",
+            utils::path::VIBE_IMAGES_DIR,
+            image.file_path
+        ));
+        attachment.name.get_or_insert(image.original_name);
+        image_ids.push(image_id);
+    }
+
+    TaskImage::associate_many_dedup(pool, ctx.task.id, &image_ids).await?;
+    if let Some(worktree) = worktree {
+        deployment
+            .image()
+            .copy_images_by_ids_to_worktree(worktree, &image_ids)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Pending questions here and on other instances sharing the board, oldest first
+pub(crate) async fn all_pending(deployment: &DeploymentImpl) -> Vec<UserQuestionRequest> {
+    let mut pending = deployment.user_questions().list_pending();
+    if let Some(coordinator) = deployment.coordinator() {
+        pending.extend(coordinator.remote_pending_questions().await);
+        pending.sort_by_key(|request| request.created_at);
+    }
+    pending
+}
+
+/// Questions agents are currently waiting on, oldest first. Includes agents run by other
+/// instances sharing the board.
+pub async fn list_pending_questions(
+    api_token: Option<Extension<ApiToken>>,
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<Vec<UserQuestionRequest>>> {
+    let mut pending = all_pending(&deployment).await;
+    if let Some(Extension(api_token)) = &api_token {
+        let mut covered = Vec::with_capacity(pending.len());
+        for request in pending {
+            if token_covers(&deployment, Some(api_token), request.execution_process_id).await {
+                covered.push(request);
+            }
+        }
+        pending = covered;
+    }
+    ResponseJson(ApiResponse::success(pending))
+}
+
+/// Execution process that asked the question, here or on another instance sharing the board
+async fn question_execution_process_id(deployment: &DeploymentImpl, id: &str) -> Option<Uuid> {
+    if let Some(execution_process_id) = deployment.user_questions().execution_process_id(id) {
+        return Some(execution_process_id);
+    }
+    deployment
+        .coordinator()?
+        .remote_pending_questions()
+        .await
+        .into_iter()
+        .find(|request| request.id == id)
+        .map(|request| request.execution_process_id)
+}
+
+/// Take the question so other reviewers see it is being answered. Claiming again renews the
+/// claim.
+pub async fn claim_question(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<String>,
+) -> Result<ResponseJson<ApiResponse<QuestionClaim>>, ApiError> {
+    let user_id = user.as_ref().map(|user| user.id);
+    let name = claimant_name(user.as_ref());
+    let service = deployment.user_questions();
+
+    if let Some(coordinator) = deployment.coordinator()
+        && service.pending_execution_process_id(&id).is_none()
+        && let Some(result) = coordinator
+            .forward_question_claim(&id, user_id, name.clone())
+            .await
+    {
+        return match result {
+            Ok(claim) => Ok(ResponseJson(ApiResponse::success(claim))),
+            Err(claim) => Err(question_error(QuestionError::Claimed(claim))),
+        };
+    }
+
+    let claim = service
+        .claim(&id, user_id, name)
+        .await
+        .map_err(question_error)?;
+    Ok(ResponseJson(ApiResponse::success(claim)))
+}
+
+pub async fn release_question(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<String>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let user_id = user.as_ref().map(|user| user.id);
+    let service = deployment.user_questions();
+
+    if let Some(coordinator) = deployment.coordinator()
+        && service.pending_execution_process_id(&id).is_none()
+        && let Some(result) = coordinator.forward_question_release(&id, user_id).await
+    {
+        return match result {
+            Ok(()) => Ok(ResponseJson(ApiResponse::success(()))),
+            Err(claim) => Err(question_error(QuestionError::Claimed(claim))),
+        };
+    }
+
+    service
+        .release(&id, user_id)
+        .await
+        .map_err(question_error)?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
 
 pub async fn respond_to_question(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    api_token: Option<Extension<ApiToken>>,
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<String>,
-    Json(response): Json<UserQuestionResponse>,
+    headers: HeaderMap,
+    Json(mut response): Json<UserQuestionResponse>,
 ) -> Result<Json<UserQuestionResponse>, StatusCode> {
     let service = deployment.user_questions();
 
-    match service.respond(&deployment.db().pool, &id, response).await {
-        Ok(response) => {
-            deployment
-                .track_if_analytics_allowed(
-                    "question_responded",
-                    serde_json::json!({
-                        "question_id": &id,
-                        "answer_count": response.answers.len(),
-                    }),
-                )
-                .await;
-
-            Ok(Json(response))
+    if let Some(Extension(api_token)) = &api_token {
+        let execution_process_id = question_execution_process_id(&deployment, &id)
+            .await
+            .ok_or(StatusCode::NOT_FOUND)?;
+        if !token_covers(&deployment, Some(api_token), execution_process_id).await {
+            return Err(StatusCode::FORBIDDEN);
+        }
+    }
+
+    let responder = user.as_ref().map(|user| user.id);
+    // Only the caller who sent a key gets its response replayed
+    let caller = api_token
+        .as_ref()
+        .map(|Extension(api_token)| api_token.id)
+        .or(responder);
+    let idempotency_key = headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|key| !key.is_empty());
+    if let Some(key) = idempotency_key
+        && let Some(response) = service.replay(&id, caller, key)
+    {
+        return Ok(Json(response));
+    }
+
+    // The agent may be running on another instance sharing the board
+    if let Some(coordinator) = deployment.coordinator()
+        && service.pending_execution_process_id(&id).is_none()
+        && let Some(result) = coordinator
+            .forward_question(&id, response.clone(), responder)
+            .await
+    {
+        let response = result.map_err(|_| StatusCode::CONFLICT)?;
+        if let Some(key) = idempotency_key {
+            service.remember(&id, caller, key, &response);
+        }
+        return Ok(Json(response));
+    }
+
+    // Turn away answers that can't be accepted before copying any attachments
+    service
+        .check_respondable(&id, responder)
+        .map_err(|e| respond_error(&id, e))?;
+
+    if let Err(e) = resolve_answer_attachments(&deployment, &id, &mut response).await {
+        tracing::warn!("Failed to attach files to question response: {}", e);
+        return Err(match e {
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        });
+    }
+
+    let grace = Duration::from_secs(deployment.config().read().await.question_answer_grace_secs);
+    let response = service
+        .respond(&deployment.db().pool, &id, response, responder, grace)
+        .await
+        .map_err(|e| respond_error(&id, e))?;
+    if let Some(key) = idempotency_key {
+        service.remember(&id, caller, key, &response);
+    }
+    deployment
+        .track_if_analytics_allowed(
+            "question_responded",
+            serde_json::json!({
+                "question_id": &id,
+                "answer_count": response.answers.len(),
+            }),
+        )
+        .await;
+
+    Ok(Json(response))
+}
+
+fn respond_error(id: &str, e: QuestionError) -> StatusCode {
+    match e {
+        QuestionError::NotFound => StatusCode::NOT_FOUND,
+        QuestionError::AlreadyCompleted => StatusCode::CONFLICT,
+        QuestionError::Claimed(claim) => {
+            tracing::debug!("Question {} is being answered by {}", id, claim.name);
+            StatusCode::CONFLICT
         }
-        Err(e) => {
+        e => {
             tracing::error!("Failed to respond to question: {:?}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            StatusCode::INTERNAL_SERVER_ERROR
         }
     }
 }
 
+/// Replace an answer that is still in its grace period
+pub async fn amend_answer(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<String>,
+    Json(mut response): Json<UserQuestionResponse>,
+) -> Result<ResponseJson<ApiResponse<UserQuestionResponse>>, ApiError> {
+    resolve_answer_attachments(&deployment, &id, &mut response).await?;
+    let responder = user.as_ref().map(|user| user.id);
+    let response = deployment
+        .user_questions()
+        .amend(&id, response, responder)
+        .map_err(question_error)?;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+/// Take back an answer that is still in its grace period, reopening the question
+pub async fn withdraw_answer(
+    Extension(CurrentUser(user)): Extension<CurrentUser>,
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<String>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let responder = user.as_ref().map(|user| user.id);
+    deployment
+        .user_questions()
+        .withdraw(&id, responder)
+        .await
+        .map_err(question_error)?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub fn router() -> Router<DeploymentImpl> {
-    Router::new().route("/questions/{id}/respond", post(respond_to_question))
+    Router::new()
+        .route("/questions", get(list_pending_questions))
+        .route(
+            "/questions/{id}/claim",
+            post(claim_question).delete(release_question),
+        )
+        .route(
+            "/questions/{id}/respond",
+            post(respond_to_question)
+                .put(amend_answer)
+                .delete(withdraw_answer),
+        )
 }
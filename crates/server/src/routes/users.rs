@@ -0,0 +1,75 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::user::{CreateUser, UpdateUser, User};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+async fn load_user(deployment: &DeploymentImpl, id: Uuid) -> Result<User, ApiError> {
+    User::find_by_id(&deployment.db().pool, id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn list_users(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<User>>>, ApiError> {
+    let users = User::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(users)))
+}
+
+pub async fn create_user(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateUser>,
+) -> Result<ResponseJson<ApiResponse<User>>, ApiError> {
+    let user = deployment
+        .accounts()
+        .create_user(&deployment.db().pool, &payload)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "user_created",
+            serde_json::json!({ "role": user.role.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(user)))
+}
+
+pub async fn update_user(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateUser>,
+) -> Result<ResponseJson<ApiResponse<User>>, ApiError> {
+    let existing = load_user(&deployment, id).await?;
+    let user = deployment
+        .accounts()
+        .update_user(&deployment.db().pool, &existing, &payload)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(user)))
+}
+
+pub async fn delete_user(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let existing = load_user(&deployment, id).await?;
+    deployment
+        .accounts()
+        .delete_user(&deployment.db().pool, &existing)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/users", get(list_users).post(create_user))
+        .route("/users/{id}", put(update_user).delete(delete_user))
+}
@@ -0,0 +1,319 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    http::HeaderMap,
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    project::Project,
+    project_webhook::ProjectWebhook,
+    task::{CreateTask, Task},
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::accounts::{generate_token, hash_token, token_matches_hash};
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+
+/// Header carrying the webhook secret when a bearer token is not used
+const SECRET_HEADER: &str = "x-webhook-secret";
+
+#[derive(Debug, Clone, Copy, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum IncomingTaskPriority {
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+/// Task opened through a project's incoming webhook
+#[derive(Debug, Deserialize, TS)]
+pub struct IncomingTaskPayload {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub priority: Option<IncomingTaskPriority>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct IncomingTaskResponse {
+    pub task_id: Uuid,
+}
+
+/// A newly created or rotated webhook. The secret is only ever returned here.
+#[derive(Debug, Serialize, TS)]
+pub struct ProjectWebhookSecret {
+    pub webhook: ProjectWebhook,
+    pub secret: String,
+}
+
+/// Labels and priority have no task field of their own, so they are listed after the
+/// description.
+fn task_description(payload: &IncomingTaskPayload) -> Option<String> {
+    let mut lines = Vec::new();
+    let labels: Vec<&str> = payload
+        .labels
+        .iter()
+        .map(|label| label.trim())
+        .filter(|label| !label.is_empty())
+        .collect();
+    if !labels.is_empty() {
+        lines.push(format!("Labels: {}", labels.join(", ")));
+    }
+    if let Some(priority) = payload.priority {
+        lines.push(format!("Priority: {priority:?}"));
+    }
+
+    let description = payload
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty());
+    match (description, lines.is_empty()) {
+        (description, true) => description.map(str::to_string),
+        (None, false) => Some(lines.join("\n")),
+        (Some(description), false) => Some(format!("{description}\n\n{}", lines.join("\n"))),
+    }
+}
+
+/// The secret presented as a bearer token or in the `X-Webhook-Secret` header.
+pub(crate) fn presented_secret(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .or_else(|| headers.get(SECRET_HEADER).and_then(|v| v.to_str().ok()))
+        .map(str::trim)
+}
+
+/// Create a task in the project whose webhook has `project_token`, once the presented secret
+/// checks out.
+async fn create_webhook_task(
+    pool: &SqlitePool,
+    project_token: &str,
+    headers: &HeaderMap,
+    payload: &IncomingTaskPayload,
+) -> Result<Task, ApiError> {
+    let webhook = ProjectWebhook::find_by_token(pool, project_token)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("Unknown webhook".to_string()))?;
+    if !presented_secret(headers).is_some_and(|s| token_matches_hash(s, &webhook.secret_hash)) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let title = payload.title.trim();
+    if title.is_empty() {
+        return Err(ApiError::BadRequest("Task title is required".to_string()));
+    }
+
+    let create_task = CreateTask {
+        project_id: webhook.project_id,
+        title: title.to_string(),
+        description: task_description(payload),
+        status: None,
+        parent_workspace_id: None,
+        image_ids: None,
+        shared_task_id: None,
+        generate_details: None,
+    };
+    let task = Task::create(pool, &create_task, Uuid::new_v4()).await?;
+    ProjectWebhook::touch(pool, webhook.project_id).await?;
+    tracing::info!(
+        "Created task {} in project {} from incoming webhook",
+        task.id,
+        webhook.project_id
+    );
+    Ok(task)
+}
+
+pub async fn create_task_from_webhook(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_token): Path<String>,
+    headers: HeaderMap,
+    Json(payload): Json<IncomingTaskPayload>,
+) -> Result<ResponseJson<ApiResponse<IncomingTaskResponse>>, ApiError> {
+    let task =
+        create_webhook_task(&deployment.db().pool, &project_token, &headers, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_created_from_webhook",
+            serde_json::json!({
+                "project_id": task.project_id.to_string(),
+                "task_id": task.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(IncomingTaskResponse {
+        task_id: task.id,
+    })))
+}
+
+pub async fn get_project_webhook(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ProjectWebhook>>>, ApiError> {
+    let webhook = ProjectWebhook::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(webhook)))
+}
+
+/// Create the project's webhook, or rotate its token and secret when it already exists.
+pub async fn create_project_webhook(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectWebhookSecret>>, ApiError> {
+    let secret = generate_token();
+    let webhook = ProjectWebhook::upsert(
+        &deployment.db().pool,
+        project.id,
+        &generate_token(),
+        &hash_token(&secret),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(ProjectWebhookSecret {
+        webhook,
+        secret,
+    })))
+}
+
+pub async fn delete_project_webhook(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ProjectWebhook::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let project_webhook_router = Router::new()
+        .route(
+            "/webhook",
+            get(get_project_webhook)
+                .post(create_project_webhook)
+                .delete(delete_project_webhook),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_project_middleware,
+        ));
+
+    Router::new()
+        .route(
+            "/hooks/{project_token}/tasks",
+            post(create_task_from_webhook),
+        )
+        .nest("/projects/{id}", project_webhook_router)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        http::{HeaderValue, StatusCode, header},
+        response::IntoResponse,
+    };
+    use db::models::project::{CreateProject, Project};
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+
+    /// In-memory database with a project whose webhook has token `hook-token` and secret
+    /// `hook-secret`
+    async fn setup() -> (SqlitePool, Uuid) {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("../db/migrations").run(&pool).await.unwrap();
+
+        let project_id = Uuid::new_v4();
+        let project = CreateProject {
+            name: "Webhooks".to_string(),
+            repositories: vec![],
+        };
+        Project::create(&pool, &project, project_id).await.unwrap();
+        ProjectWebhook::upsert(&pool, project_id, "hook-token", &hash_token("hook-secret"))
+            .await
+            .unwrap();
+        (pool, project_id)
+    }
+
+    fn headers(secret: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {secret}")).unwrap(),
+        );
+        headers
+    }
+
+    fn payload() -> IncomingTaskPayload {
+        IncomingTaskPayload {
+            title: "  Fix the login page  ".to_string(),
+            description: Some("It 500s".to_string()),
+            labels: vec!["bug".to_string()],
+            priority: Some(IncomingTaskPriority::High),
+        }
+    }
+
+    fn status(err: ApiError) -> StatusCode {
+        err.into_response().status()
+    }
+
+    #[tokio::test]
+    async fn unknown_token_is_not_found() {
+        let (pool, _) = setup().await;
+        let err = create_webhook_task(&pool, "other-token", &headers("hook-secret"), &payload())
+            .await
+            .unwrap_err();
+        assert_eq!(status(err), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn wrong_or_missing_secret_is_unauthorized() {
+        let (pool, _) = setup().await;
+        let err = create_webhook_task(&pool, "hook-token", &headers("guess"), &payload())
+            .await
+            .unwrap_err();
+        assert_eq!(status(err), StatusCode::UNAUTHORIZED);
+
+        let err = create_webhook_task(&pool, "hook-token", &HeaderMap::new(), &payload())
+            .await
+            .unwrap_err();
+        assert_eq!(status(err), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn creates_task_in_the_webhook_project() {
+        let (pool, project_id) = setup().await;
+        let mut headers = HeaderMap::new();
+        headers.insert(SECRET_HEADER, HeaderValue::from_static("hook-secret"));
+
+        let task = create_webhook_task(&pool, "hook-token", &headers, &payload())
+            .await
+            .unwrap();
+        assert_eq!(task.project_id, project_id);
+        assert_eq!(task.title, "Fix the login page");
+        assert_eq!(
+            task.description.as_deref(),
+            Some("It 500s\n\nLabels: bug\nPriority: High")
+        );
+        assert!(
+            ProjectWebhook::find_by_project_id(&pool, project_id)
+                .await
+                .unwrap()
+                .is_some_and(|webhook| webhook.last_used_at.is_some())
+        );
+    }
+}
@@ -0,0 +1,372 @@
+//! Local user accounts, password login and session tokens for team deployments.
+//!
+//! Multi-user mode is opt-in via `VK_MULTI_USER=true`. When disabled the server
+//! behaves as a single-user local app and no authentication is enforced.
+
+use argon2::{
+    Argon2,
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
+};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{Duration, Utc};
+use db::models::{
+    api_token::ApiToken,
+    user::{CreateUser, UpdateUser, User, UserRole},
+    user_session::UserSession,
+};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+
+pub const SESSION_COOKIE_NAME: &str = "vk_session";
+/// Prefix telling automation API tokens apart from session tokens
+pub const API_TOKEN_PREFIX: &str = "vkq_";
+const SESSION_TTL_DAYS: i64 = 30;
+const MIN_PASSWORD_LEN: usize = 8;
+
+#[derive(Debug, Error)]
+pub enum AccountError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Invalid username or password")]
+    InvalidCredentials,
+    #[error("Account is disabled")]
+    Disabled,
+    #[error("{0}")]
+    Validation(String),
+    #[error("Username already taken")]
+    UsernameTaken,
+    #[error("At least one active admin account is required")]
+    LastAdmin,
+    #[error("Initial setup has already been completed")]
+    AlreadySetUp,
+    #[error("Failed to hash password")]
+    PasswordHash,
+}
+
+#[derive(Clone)]
+pub struct AccountService {
+    enabled: bool,
+}
+
+impl AccountService {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    pub fn from_env() -> Self {
+        let enabled = matches!(
+            std::env::var("VK_MULTI_USER").as_deref(),
+            Ok("1" | "true" | "TRUE" | "yes")
+        );
+        Self::new(enabled)
+    }
+
+    /// Whether authentication and role checks are enforced.
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn session_ttl(&self) -> Duration {
+        Duration::days(SESSION_TTL_DAYS)
+    }
+
+    /// Create the first admin account. Only allowed while no users exist.
+    pub async fn setup(
+        &self,
+        pool: &SqlitePool,
+        username: &str,
+        password: &str,
+    ) -> Result<User, AccountError> {
+        if User::count(pool).await? > 0 {
+            return Err(AccountError::AlreadySetUp);
+        }
+        self.create_user(
+            pool,
+            &CreateUser {
+                username: username.to_string(),
+                password: Some(password.to_string()),
+                display_name: None,
+                email: None,
+                role: UserRole::Admin,
+            },
+        )
+        .await
+    }
+
+    pub async fn create_user(
+        &self,
+        pool: &SqlitePool,
+        data: &CreateUser,
+    ) -> Result<User, AccountError> {
+        validate_username(&data.username)?;
+        let password_hash = data
+            .password
+            .as_deref()
+            .map(|password| {
+                validate_password(password)?;
+                hash_password(password)
+            })
+            .transpose()?;
+
+        User::create(pool, data, password_hash.as_deref())
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                    AccountError::UsernameTaken
+                }
+                other => AccountError::Database(other),
+            })
+    }
+
+    pub async fn update_user(
+        &self,
+        pool: &SqlitePool,
+        existing: &User,
+        data: &UpdateUser,
+    ) -> Result<User, AccountError> {
+        let role = data.role.unwrap_or(existing.role);
+        let disabled = data.disabled.unwrap_or(existing.disabled);
+
+        let loses_admin = existing.role == UserRole::Admin
+            && !existing.disabled
+            && (role != UserRole::Admin || disabled);
+        if loses_admin && User::count_active_admins(pool).await? <= 1 {
+            return Err(AccountError::LastAdmin);
+        }
+
+        if let Some(password) = &data.password {
+            validate_password(password)?;
+            User::set_password_hash(pool, existing.id, &hash_password(password)?).await?;
+            // Force re-login everywhere after a password change
+            UserSession::delete_for_user(pool, existing.id).await?;
+        }
+
+        let user = User::update(
+            pool,
+            existing.id,
+            data.display_name
+                .as_deref()
+                .or(existing.display_name.as_deref()),
+            data.email.as_deref().or(existing.email.as_deref()),
+            role,
+            disabled,
+        )
+        .await?;
+
+        if disabled && !existing.disabled {
+            UserSession::delete_for_user(pool, existing.id).await?;
+        }
+        Ok(user)
+    }
+
+    pub async fn delete_user(
+        &self,
+        pool: &SqlitePool,
+        existing: &User,
+    ) -> Result<(), AccountError> {
+        if existing.role == UserRole::Admin
+            && !existing.disabled
+            && User::count_active_admins(pool).await? <= 1
+        {
+            return Err(AccountError::LastAdmin);
+        }
+        User::delete(pool, existing.id).await?;
+        Ok(())
+    }
+
+    /// Verify credentials and open a new session, returning the session token.
+    pub async fn login(
+        &self,
+        pool: &SqlitePool,
+        username: &str,
+        password: &str,
+    ) -> Result<(User, String), AccountError> {
+        let user = User::find_by_username(pool, username.trim())
+            .await?
+            .ok_or(AccountError::InvalidCredentials)?;
+        let hash = user
+            .password_hash
+            .as_deref()
+            .ok_or(AccountError::InvalidCredentials)?;
+        if !verify_password(hash, password) {
+            return Err(AccountError::InvalidCredentials);
+        }
+        if user.disabled {
+            return Err(AccountError::Disabled);
+        }
+
+        let token = self.issue_session(pool, &user).await?;
+        Ok((user, token))
+    }
+
+    /// Open a session for an already authenticated user.
+    pub async fn issue_session(
+        &self,
+        pool: &SqlitePool,
+        user: &User,
+    ) -> Result<String, AccountError> {
+        let token = generate_token();
+        UserSession::create(
+            pool,
+            user.id,
+            &hash_token(&token),
+            Utc::now() + self.session_ttl(),
+        )
+        .await?;
+        Ok(token)
+    }
+
+    /// Resolve a session token to its user, if the session is valid.
+    pub async fn authenticate(
+        &self,
+        pool: &SqlitePool,
+        token: &str,
+    ) -> Result<Option<User>, AccountError> {
+        let Some(session) = UserSession::find_by_token_hash(pool, &hash_token(token)).await? else {
+            return Ok(None);
+        };
+        if session.expires_at < Utc::now() {
+            UserSession::delete(pool, session.id).await?;
+            return Ok(None);
+        }
+
+        let user = User::find_by_id(pool, session.user_id)
+            .await?
+            .filter(|user| !user.disabled);
+        if user.is_some() {
+            UserSession::touch(pool, session.id).await?;
+        }
+        Ok(user)
+    }
+
+    /// Resolve an automation API token, recording its use.
+    pub async fn authenticate_api_token(
+        &self,
+        pool: &SqlitePool,
+        token: &str,
+    ) -> Result<Option<ApiToken>, AccountError> {
+        let api_token = ApiToken::find_by_token_hash(pool, &hash_token(token)).await?;
+        if let Some(api_token) = &api_token {
+            ApiToken::touch(pool, api_token.id).await?;
+        }
+        Ok(api_token)
+    }
+
+    pub async fn logout(&self, pool: &SqlitePool, token: &str) -> Result<(), AccountError> {
+        if let Some(session) = UserSession::find_by_token_hash(pool, &hash_token(token)).await? {
+            UserSession::delete(pool, session.id).await?;
+        }
+        Ok(())
+    }
+}
+
+fn validate_username(username: &str) -> Result<(), AccountError> {
+    let valid = !username.is_empty()
+        && username.len() <= 64
+        && username
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '@'));
+    if !valid {
+        return Err(AccountError::Validation(
+            "Usernames may only contain letters, numbers, '.', '_', '-' and '@'".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_password(password: &str) -> Result<(), AccountError> {
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        return Err(AccountError::Validation(format!(
+            "Password must be at least {MIN_PASSWORD_LEN} characters"
+        )));
+    }
+    Ok(())
+}
+
+fn hash_password(password: &str) -> Result<String, AccountError> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|_| AccountError::PasswordHash)
+}
+
+fn verify_password(hash: &str, password: &str) -> bool {
+    PasswordHash::new(hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+pub fn generate_api_token() -> String {
+    format!("{API_TOKEN_PREFIX}{}", generate_token())
+}
+
+pub fn is_api_token(token: &str) -> bool {
+    token.starts_with(API_TOKEN_PREFIX)
+}
+
+pub fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Whether `token` hashes to `token_hash`, compared without leaking timing.
+pub fn token_matches_hash(token: &str, token_hash: &str) -> bool {
+    hash_token(token)
+        .as_bytes()
+        .ct_eq(token_hash.as_bytes())
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn password_hash_round_trip() {
+        let hash = hash_password("correct horse").unwrap();
+        assert!(verify_password(&hash, "correct horse"));
+        assert!(!verify_password(&hash, "wrong horse"));
+        assert!(!verify_password("not-a-hash", "correct horse"));
+    }
+
+    #[test]
+    fn api_tokens_are_told_apart_from_sessions() {
+        assert!(is_api_token(&generate_api_token()));
+        assert!(!is_api_token(&generate_token()));
+    }
+
+    #[test]
+    fn token_hashes_match_only_their_token() {
+        let hash = hash_token("secret");
+        assert!(token_matches_hash("secret", &hash));
+        assert!(!token_matches_hash("Secret", &hash));
+        assert!(!token_matches_hash("secret", ""));
+    }
+
+    #[test]
+    fn role_permissions() {
+        use db::models::user::Permission;
+
+        assert!(UserRole::Admin.grants(Permission::Admin));
+        assert!(UserRole::Member.grants(Permission::Write));
+        assert!(!UserRole::Member.grants(Permission::Review));
+        assert!(UserRole::Reviewer.grants(Permission::Review));
+        assert!(!UserRole::Reviewer.grants(Permission::Write));
+    }
+}
@@ -1,11 +1,21 @@
 use std::{
     collections::hash_map::DefaultHasher,
     hash::{Hash, Hasher},
+    sync::Arc,
     time::Duration,
 };
 
+use async_trait::async_trait;
+use db::models::execution_process::ExecutionProcessStatus;
 use os_info;
 use serde_json::{Value, json};
+use tokio::sync::RwLock;
+
+use crate::services::{
+    analytics_export::AnalyticsExport,
+    config::Config,
+    events::{EntityEvent, EntityEventEnvelope, EntityEventSubscriber},
+};
 
 #[derive(Debug, Clone)]
 pub struct AnalyticsContext {
@@ -43,7 +53,7 @@ pub struct AnalyticsService {
 
 impl AnalyticsService {
     pub fn new(config: AnalyticsConfig) -> Self {
-        let client = reqwest::Client::builder()
+        let client = utils::http::client_builder()
             .timeout(Duration::from_secs(30))
             .build()
             .unwrap();
@@ -114,6 +124,71 @@ impl AnalyticsService {
     }
 }
 
+/// Tracks analytics events derived from the entity event stream, unless the user opted out.
+/// Events are always written to the local export when one is configured.
+pub struct AnalyticsSubscriber {
+    user_id: String,
+    analytics_service: Option<AnalyticsService>,
+    export: Option<AnalyticsExport>,
+    config: Arc<RwLock<Config>>,
+}
+
+impl AnalyticsSubscriber {
+    pub fn new(
+        user_id: String,
+        analytics_service: Option<AnalyticsService>,
+        export: Option<AnalyticsExport>,
+        config: Arc<RwLock<Config>>,
+    ) -> Self {
+        Self {
+            user_id,
+            analytics_service,
+            export,
+            config,
+        }
+    }
+}
+
+#[async_trait]
+impl EntityEventSubscriber for AnalyticsSubscriber {
+    fn name(&self) -> &'static str {
+        "analytics"
+    }
+
+    async fn handle(&self, envelope: &EntityEventEnvelope) {
+        let (event_name, properties) = match &envelope.event {
+            EntityEvent::AttemptFinished {
+                workspace_id,
+                task_id,
+                session_id,
+                status,
+                exit_code,
+                ..
+            } => (
+                "task_attempt_finished",
+                json!({
+                    "task_id": task_id.to_string(),
+                    "project_id": envelope.project_id.map(|id| id.to_string()),
+                    "workspace_id": workspace_id.to_string(),
+                    "session_id": session_id.to_string(),
+                    "execution_success": *status == ExecutionProcessStatus::Completed,
+                    "exit_code": exit_code,
+                }),
+            ),
+            _ => return,
+        };
+
+        if let Some(export) = &self.export {
+            export.record(&self.user_id, event_name, properties.clone());
+        }
+        if self.config.read().await.analytics_enabled
+            && let Some(analytics_service) = &self.analytics_service
+        {
+            analytics_service.track_event(&self.user_id, event_name, Some(properties));
+        }
+    }
+}
+
 /// Generates a consistent, anonymous user ID for npm package telemetry.
 /// Returns a hex string prefixed with "npm_user_"
 pub fn generate_user_id() -> String {
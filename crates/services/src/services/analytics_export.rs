@@ -0,0 +1,221 @@
+//! Local export of analytics events for self-hosters.
+//!
+//! Setting `VK_ANALYTICS_EXPORT_URL` to a directory, `file://` or `s3://bucket/prefix` URL
+//! writes every tracked event as a JSON line, independent of the third-party analytics opt-in.
+//! Events are buffered in the current segment and written every few seconds; a new segment
+//! starts each day or once the current one reaches [`MAX_SEGMENT_BYTES`].
+
+use std::{
+    collections::VecDeque,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use tokio::task::JoinHandle;
+use ts_rs::TS;
+
+use crate::services::blob_storage::{BlobStorage, BlobStorageError};
+
+const MAX_SEGMENT_BYTES: usize = 8 * 1024 * 1024;
+/// Events kept in memory for the recent events endpoint
+const RECENT_EVENTS: usize = 1000;
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ExportedAnalyticsEvent {
+    #[ts(type = "Date")]
+    pub timestamp: DateTime<Utc>,
+    pub event: String,
+    pub user_id: String,
+    #[ts(type = "Record<string, unknown>")]
+    pub properties: Value,
+}
+
+#[derive(Debug)]
+struct Segment {
+    key: String,
+    opened_at: DateTime<Utc>,
+    data: Vec<u8>,
+    dirty: bool,
+}
+
+impl Segment {
+    fn open(now: DateTime<Utc>) -> Self {
+        Self {
+            key: format!("analytics/events-{}.jsonl", now.format("%Y%m%dT%H%M%S%3f")),
+            opened_at: now,
+            data: Vec::new(),
+            dirty: false,
+        }
+    }
+
+    fn is_full(&self, now: DateTime<Utc>) -> bool {
+        self.data.len() >= MAX_SEGMENT_BYTES || self.opened_at.date_naive() != now.date_naive()
+    }
+}
+
+#[derive(Debug)]
+struct ExportState {
+    segment: Segment,
+    /// Rotated segments not yet written in full
+    pending: Vec<Segment>,
+    recent: VecDeque<ExportedAnalyticsEvent>,
+}
+
+#[derive(Clone)]
+pub struct AnalyticsExport {
+    storage: BlobStorage,
+    state: Arc<Mutex<ExportState>>,
+}
+
+impl AnalyticsExport {
+    /// Export selected by `VK_ANALYTICS_EXPORT_URL`; `None` when unset.
+    pub fn from_env() -> Option<Result<Self, BlobStorageError>> {
+        let raw = std::env::var("VK_ANALYTICS_EXPORT_URL").ok()?;
+        let raw = raw.trim();
+        if raw.is_empty() {
+            return None;
+        }
+        let storage = if raw.contains("://") {
+            BlobStorage::from_url(raw)
+        } else {
+            BlobStorage::local(Path::new(raw))
+        };
+        Some(storage.map(Self::new))
+    }
+
+    pub fn new(storage: BlobStorage) -> Self {
+        Self {
+            storage,
+            state: Arc::new(Mutex::new(ExportState {
+                segment: Segment::open(Utc::now()),
+                pending: Vec::new(),
+                recent: VecDeque::with_capacity(RECENT_EVENTS),
+            })),
+        }
+    }
+
+    /// Where events are written, for logging.
+    pub fn description(&self) -> &str {
+        self.storage.description()
+    }
+
+    pub fn record(&self, user_id: &str, event: &str, properties: Value) {
+        let event = ExportedAnalyticsEvent {
+            timestamp: Utc::now(),
+            event: event.to_string(),
+            user_id: user_id.to_string(),
+            properties,
+        };
+        let Ok(mut line) = serde_json::to_vec(&event) else {
+            return;
+        };
+        line.push(b'\n');
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.segment.is_full(event.timestamp) {
+            let full = std::mem::replace(&mut state.segment, Segment::open(event.timestamp));
+            if full.dirty {
+                state.pending.push(full);
+            }
+        }
+        state.segment.data.extend_from_slice(&line);
+        state.segment.dirty = true;
+        if state.recent.len() == RECENT_EVENTS {
+            state.recent.pop_front();
+        }
+        state.recent.push_back(event);
+    }
+
+    /// Most recent events first, optionally only those with the given name.
+    pub fn recent(&self, limit: usize, event: Option<&str>) -> Vec<ExportedAnalyticsEvent> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state
+            .recent
+            .iter()
+            .rev()
+            .filter(|e| event.is_none_or(|name| e.event == name))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Write rotated segments and the current segment if it changed since the last flush.
+    pub async fn flush(&self) -> Result<(), BlobStorageError> {
+        let writes = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let mut writes: Vec<(String, Vec<u8>)> = state
+                .pending
+                .drain(..)
+                .map(|segment| (segment.key, segment.data))
+                .collect();
+            if state.segment.dirty {
+                state.segment.dirty = false;
+                writes.push((state.segment.key.clone(), state.segment.data.clone()));
+            }
+            writes
+        };
+        for (key, data) in writes {
+            self.storage.put(&key, data).await?;
+        }
+        Ok(())
+    }
+
+    pub fn spawn_flusher(&self) -> JoinHandle<()> {
+        let export = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = export.flush().await {
+                    tracing::warn!("Failed to write analytics export: {e}");
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn writes_jsonl_segments_and_keeps_recent_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let export = AnalyticsExport::new(BlobStorage::local(dir.path()).unwrap());
+
+        export.record("user", "task_created", json!({ "project_id": "p" }));
+        export.record("user", "task_attempt_started", json!({}));
+        export.flush().await.unwrap();
+
+        let key = export.state.lock().unwrap().segment.key.clone();
+        let written = std::fs::read_to_string(dir.path().join(&key)).unwrap();
+        let lines: Vec<Value> = written
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["event"], "task_created");
+        assert_eq!(lines[0]["properties"]["project_id"], "p");
+
+        let recent = export.recent(10, None);
+        assert_eq!(recent[0].event, "task_attempt_started");
+        assert_eq!(export.recent(10, Some("task_created")).len(), 1);
+    }
+
+    #[test]
+    fn rotates_full_segments() {
+        let now = Utc::now();
+        let mut segment = Segment::open(now);
+        assert!(!segment.is_full(now));
+        segment.data = vec![b'x'; MAX_SEGMENT_BYTES];
+        assert!(segment.is_full(now));
+        assert!(Segment::open(now - chrono::Duration::days(1)).is_full(now));
+    }
+}
@@ -1,11 +1,14 @@
+pub mod command_rules;
 pub mod executor_approvals;
+pub mod path_rules;
 
 use std::{collections::HashMap, sync::Arc, time::Duration as StdDuration};
 
 use dashmap::DashMap;
 use db::models::{
+    agent_request_response::{AgentRequestKind, AgentRequestResponse},
     execution_process::ExecutionProcess,
-    task::{Task, TaskStatus},
+    task::TaskStatus,
 };
 use executors::{
     approvals::ToolCallMetadata,
@@ -25,12 +28,13 @@ use utils::{
 };
 use uuid::Uuid;
 
+use crate::services::task_workflow;
+
 #[derive(Debug)]
 struct PendingApproval {
     entry_index: usize,
     entry: NormalizedEntry,
-    execution_process_id: Uuid,
-    tool_name: String,
+    request: ApprovalRequest,
     response_tx: oneshot::Sender<ApprovalStatus>,
 }
 
@@ -47,6 +51,8 @@ pub struct Approvals {
     pending: Arc<DashMap<String, PendingApproval>>,
     completed: Arc<DashMap<String, ApprovalStatus>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+    /// Milliseconds each answered approval waited, by execution process and log entry index
+    waits: Arc<DashMap<Uuid, HashMap<usize, i64>>>,
 }
 
 #[derive(Debug, Error)]
@@ -71,6 +77,7 @@ impl Approvals {
             pending: Arc::new(DashMap::new()),
             completed: Arc::new(DashMap::new()),
             msg_stores,
+            waits: Arc::new(DashMap::new()),
         }
     }
 
@@ -104,8 +111,7 @@ impl Approvals {
                     PendingApproval {
                         entry_index: idx,
                         entry: matching_tool,
-                        execution_process_id: request.execution_process_id,
-                        tool_name: request.tool_name.clone(),
+                        request: request.clone(),
                         response_tx: tx,
                     },
                 );
@@ -133,6 +139,13 @@ impl Approvals {
         Ok((request, waiter))
     }
 
+    /// Approvals still waiting for a decision, oldest first.
+    pub fn list_pending(&self) -> Vec<ApprovalRequest> {
+        let mut requests: Vec<_> = self.pending.iter().map(|p| p.request.clone()).collect();
+        requests.sort_by_key(|r| r.created_at);
+        requests
+    }
+
     #[tracing::instrument(skip(self, id, req))]
     pub async fn respond(
         &self,
@@ -143,8 +156,13 @@ impl Approvals {
         if let Some((_, p)) = self.pending.remove(id) {
             self.completed.insert(id.to_string(), req.status.clone());
             let _ = p.response_tx.send(req.status.clone());
+            let waited = chrono::Utc::now() - p.request.created_at;
+            self.waits
+                .entry(p.request.execution_process_id)
+                .or_default()
+                .insert(p.entry_index, waited.num_milliseconds());
 
-            if let Some(store) = self.msg_store_by_id(&p.execution_process_id).await {
+            if let Some(store) = self.msg_store_by_id(&p.request.execution_process_id).await {
                 let status = ToolStatus::from_approval_status(&req.status).ok_or(
                     ApprovalError::Custom(anyhow::anyhow!("Invalid approval status")),
                 )?;
@@ -157,15 +175,29 @@ impl Approvals {
             } else {
                 tracing::warn!(
                     "No msg_store found for execution_process_id: {}",
-                    p.execution_process_id
+                    p.request.execution_process_id
                 );
             }
 
             let tool_ctx = ToolContext {
-                tool_name: p.tool_name,
-                execution_process_id: p.execution_process_id,
+                tool_name: p.request.tool_name,
+                execution_process_id: p.request.execution_process_id,
             };
 
+            if matches!(
+                req.status,
+                ApprovalStatus::Approved | ApprovalStatus::Denied { .. }
+            ) && let Err(e) = AgentRequestResponse::create(
+                pool,
+                AgentRequestKind::Approval,
+                p.request.execution_process_id,
+                p.request.created_at,
+            )
+            .await
+            {
+                tracing::warn!("Failed to record approval response: {}", e);
+            }
+
             // If approved or denied, and task is still InReview, move back to InProgress
             if matches!(
                 req.status,
@@ -173,7 +205,9 @@ impl Approvals {
             ) && let Ok(ctx) =
                 ExecutionProcess::load_context(pool, tool_ctx.execution_process_id).await
                 && ctx.task.status == TaskStatus::InReview
-                && let Err(e) = Task::update_status(pool, ctx.task.id, TaskStatus::InProgress).await
+                && let Err(e) =
+                    task_workflow::update_task_status(pool, ctx.task.id, TaskStatus::InProgress)
+                        .await
             {
                 tracing::warn!(
                     "Failed to update task status to InProgress after approval response: {}",
@@ -224,7 +258,8 @@ impl Approvals {
 
                 let store = {
                     let map = msg_stores.read().await;
-                    map.get(&pending_approval.execution_process_id).cloned()
+                    map.get(&pending_approval.request.execution_process_id)
+                        .cloned()
                 };
 
                 if let Some(store) = store {
@@ -245,13 +280,21 @@ impl Approvals {
                 } else {
                     tracing::warn!(
                         "No msg_store found for execution_process_id: {}",
-                        pending_approval.execution_process_id
+                        pending_approval.request.execution_process_id
                     );
                 }
             }
         });
     }
 
+    /// How long the process's answered approvals waited, by log entry index. Clears them.
+    pub fn take_approval_waits(&self, execution_process_id: &Uuid) -> HashMap<usize, i64> {
+        self.waits
+            .remove(execution_process_id)
+            .map(|(_, waits)| waits)
+            .unwrap_or_default()
+    }
+
     async fn msg_store_by_id(&self, execution_process_id: &Uuid) -> Option<Arc<MsgStore>> {
         let map = self.msg_stores.read().await;
         map.get(execution_process_id).cloned()
@@ -261,7 +304,8 @@ impl Approvals {
 pub(crate) async fn ensure_task_in_review(pool: &SqlitePool, execution_process_id: Uuid) {
     if let Ok(ctx) = ExecutionProcess::load_context(pool, execution_process_id).await
         && ctx.task.status == TaskStatus::InProgress
-        && let Err(e) = Task::update_status(pool, ctx.task.id, TaskStatus::InReview).await
+        && let Err(e) =
+            task_workflow::update_task_status(pool, ctx.task.id, TaskStatus::InReview).await
     {
         tracing::warn!(
             "Failed to update task status to InReview for approval request: {}",
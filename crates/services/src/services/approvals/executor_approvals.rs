@@ -1,18 +1,35 @@
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 use async_trait::async_trait;
-use db::{self, DBService};
+use db::{
+    self, DBService,
+    models::{
+        bash_command_rule::BashCommandRule,
+        execution_process::{ExecutionContext, ExecutionProcess},
+        file_path_rule::{FilePathRule, PathRuleAction},
+        notification::{CreateNotification, NotificationKind},
+    },
+};
 use executors::approvals::{ExecutorApprovalError, ExecutorApprovalService};
 use serde_json::Value;
 use utils::approvals::{ApprovalRequest, ApprovalStatus, CreateApprovalRequest};
 use uuid::Uuid;
 
-use crate::services::{approvals::Approvals, notification::NotificationService};
+use crate::services::{
+    approvals::{
+        Approvals,
+        command_rules::{self, CommandDecision},
+        path_rules,
+    },
+    events::{EntityEvent, EntityEventBus},
+    notification::NotificationService,
+};
 
 pub struct ExecutorApprovalBridge {
     approvals: Approvals,
     db: DBService,
     notification_service: NotificationService,
+    entity_events: EntityEventBus,
     execution_process_id: Uuid,
 }
 
@@ -21,15 +38,84 @@ impl ExecutorApprovalBridge {
         approvals: Approvals,
         db: DBService,
         notification_service: NotificationService,
+        entity_events: EntityEventBus,
         execution_process_id: Uuid,
     ) -> Arc<Self> {
         Arc::new(Self {
             approvals,
             db,
             notification_service,
+            entity_events,
             execution_process_id,
         })
     }
+
+    /// Settle a shell command or file edit with the project's rules, if one applies
+    async fn decide_by_rules(
+        &self,
+        ctx: &ExecutionContext,
+        tool_name: &str,
+        tool_input: &Value,
+    ) -> Option<ApprovalStatus> {
+        if let Some(command) = command_rules::command_line(tool_name, tool_input) {
+            return self.decide_command(ctx, &command).await;
+        }
+        let paths = path_rules::edited_paths(tool_name, tool_input);
+        if paths.is_empty() {
+            return None;
+        }
+        self.decide_edit(ctx, &paths).await
+    }
+
+    async fn decide_command(
+        &self,
+        ctx: &ExecutionContext,
+        command: &str,
+    ) -> Option<ApprovalStatus> {
+        let rules = BashCommandRule::find_by_project_id(&self.db.pool, ctx.project.id)
+            .await
+            .inspect_err(|e| tracing::warn!("Failed to load command rules: {}", e))
+            .ok()?;
+        let classification = command_rules::classify(&rules, command);
+        match classification.decision {
+            CommandDecision::Allow => {
+                tracing::debug!("Command allowed by project rule: {}", command);
+                Some(ApprovalStatus::Approved)
+            }
+            CommandDecision::Deny => Some(ApprovalStatus::Denied {
+                reason: Some(classification.denial_reason()),
+            }),
+            CommandDecision::Ask => None,
+        }
+    }
+
+    async fn decide_edit(
+        &self,
+        ctx: &ExecutionContext,
+        paths: &[String],
+    ) -> Option<ApprovalStatus> {
+        let rules = FilePathRule::find_by_project_id(&self.db.pool, ctx.project.id)
+            .await
+            .inspect_err(|e| tracing::warn!("Failed to load file path rules: {}", e))
+            .ok()?;
+        let workspace_dir = ctx.workspace.container_ref.as_deref().map(Path::new);
+        let repo_names: Vec<&str> = ctx.repos.iter().map(|repo| repo.name.as_str()).collect();
+        let paths: Vec<String> = paths
+            .iter()
+            .map(|path| path_rules::repo_relative_path(path, workspace_dir, &repo_names))
+            .collect();
+        let classification = path_rules::classify(&rules, &paths);
+        match classification.decision {
+            PathRuleAction::Allow => {
+                tracing::debug!("Edit allowed by project rule: {}", paths.join(", "));
+                Some(ApprovalStatus::Approved)
+            }
+            PathRuleAction::Deny => Some(ApprovalStatus::Denied {
+                reason: Some(classification.denial_reason()),
+            }),
+            PathRuleAction::Ask => None,
+        }
+    }
 }
 
 #[async_trait]
@@ -40,6 +126,15 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
         tool_input: Value,
         tool_call_id: &str,
     ) -> Result<ApprovalStatus, ExecutorApprovalError> {
+        let context = ExecutionProcess::load_context(&self.db.pool, self.execution_process_id)
+            .await
+            .ok();
+        if let Some(ctx) = &context
+            && let Some(status) = self.decide_by_rules(ctx, tool_name, &tool_input).await
+        {
+            return Ok(status);
+        }
+
         super::ensure_task_in_review(&self.db.pool, self.execution_process_id).await;
 
         let request = ApprovalRequest::from_create(
@@ -51,22 +146,49 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
             self.execution_process_id,
         );
 
-        let (_, waiter) = self
+        let (request, waiter) = self
             .approvals
             .create_with_waiter(request)
             .await
             .map_err(ExecutorApprovalError::request_failed)?;
 
+        if let Some(ctx) = &context {
+            self.entity_events.publish(
+                Some(ctx.task.project_id),
+                EntityEvent::ApprovalPending {
+                    approval_id: request.id.clone(),
+                    execution_process_id: self.execution_process_id,
+                    task_id: ctx.task.id,
+                    tool_name: tool_name.to_string(),
+                },
+            );
+        }
+
         // Play notification sound when approval is needed
         self.notification_service
-            .notify(
-                "Approval Needed",
-                &format!("Tool '{}' requires approval", tool_name),
-            )
+            .notify(CreateNotification {
+                kind: NotificationKind::ApprovalPending,
+                title: "Approval Needed".to_string(),
+                message: format!("Tool '{}' requires approval", tool_name),
+                project_id: context.as_ref().map(|ctx| ctx.task.project_id),
+                task_id: context.as_ref().map(|ctx| ctx.task.id),
+            })
             .await;
 
         let status = waiter.clone().await;
 
+        if let Some(ctx) = &context {
+            self.entity_events.publish(
+                Some(ctx.task.project_id),
+                EntityEvent::ApprovalResolved {
+                    approval_id: request.id.clone(),
+                    execution_process_id: self.execution_process_id,
+                    task_id: ctx.task.id,
+                    status: status.clone(),
+                },
+            );
+        }
+
         if matches!(status, ApprovalStatus::Pending) {
             return Err(ExecutorApprovalError::request_failed(
                 "approval finished in pending state",
@@ -0,0 +1,232 @@
+//! Project rules for the files agents edit. Allow rules approve edits without waiting for a
+//! reviewer, ask rules keep an edit with the reviewer even where an allow rule matches, and
+//! deny rules reject it outright.
+
+use std::path::Path;
+
+use db::models::file_path_rule::{FilePathRule, PathRuleAction};
+use regex::Regex;
+use serde_json::Value;
+
+/// How a project's rules treat an edit.
+#[derive(Debug, Clone)]
+pub struct PathClassification {
+    pub decision: PathRuleAction,
+    /// Rules behind the decision, empty when no rule covers the edit
+    pub rules: Vec<FilePathRule>,
+}
+
+impl PathClassification {
+    /// Denial message for the agent
+    pub fn denial_reason(&self) -> String {
+        let patterns: Vec<String> = self
+            .rules
+            .iter()
+            .map(|rule| format!("`{}`", rule.pattern))
+            .collect();
+        format!(
+            "Editing this file is blocked by project rule {}",
+            patterns.join(", ")
+        )
+    }
+}
+
+/// Files an editing tool call writes to: Claude Code's `Edit`, `MultiEdit`, `Write` and
+/// `NotebookEdit`, or the changes of a Codex patch.
+pub fn edited_paths(tool_name: &str, input: &Value) -> Vec<String> {
+    let field = |key: &str| input.get(key).and_then(Value::as_str).map(str::to_string);
+    match tool_name {
+        "Edit" | "MultiEdit" | "Write" => field("file_path").into_iter().collect(),
+        "NotebookEdit" => field("notebook_path").into_iter().collect(),
+        "edit" => input
+            .get("file_changes")
+            .and_then(Value::as_object)
+            .map(|changes| changes.keys().cloned().collect())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// Path of an edited file relative to its repository, given the workspace directory holding
+/// one checkout per repository.
+pub fn repo_relative_path(path: &str, workspace_dir: Option<&Path>, repo_names: &[&str]) -> String {
+    let path = Path::new(path);
+    let Some(relative) = workspace_dir.and_then(|dir| path.strip_prefix(dir).ok()) else {
+        return path.to_string_lossy().replace('\\', "/");
+    };
+    let mut components = relative.components();
+    let in_repo = components
+        .next()
+        .is_some_and(|first| repo_names.iter().any(|name| first.as_os_str() == *name));
+    let relative = if in_repo {
+        components.as_path()
+    } else {
+        relative
+    };
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+/// Regex a glob compiles to. `**` crosses directories, `*` and `?` stay within one, and a
+/// pattern without a `/` matches the file name in any directory, as in `.gitignore`.
+pub fn compile(pattern: &str) -> Result<Regex, regex::Error> {
+    let pattern = pattern.trim().trim_start_matches("./");
+    let mut regex = if pattern.contains('/') {
+        String::from("^")
+    } else {
+        String::from("^(?:.*/)?")
+    };
+    let pattern = pattern.trim_start_matches('/');
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex)
+}
+
+/// Classify an edit of one or more files. Every file gets the strictest action of the rules
+/// matching it, and the edit the strictest action of its files. A file no rule matches is left
+/// to the reviewer.
+pub fn classify(rules: &[FilePathRule], paths: &[String]) -> PathClassification {
+    let compiled: Vec<(&FilePathRule, Regex)> = rules
+        .iter()
+        .filter(|rule| rule.enabled)
+        .filter_map(|rule| match compile(&rule.pattern) {
+            Ok(regex) => Some((rule, regex)),
+            Err(e) => {
+                tracing::warn!("Ignoring invalid file path rule '{}': {}", rule.pattern, e);
+                None
+            }
+        })
+        .collect();
+
+    let mut decision = PathRuleAction::Allow;
+    let mut deciding: Vec<FilePathRule> = Vec::new();
+    for path in paths {
+        let matched: Vec<&FilePathRule> = compiled
+            .iter()
+            .filter(|(_, regex)| regex.is_match(path))
+            .map(|(rule, _)| *rule)
+            .collect();
+        let Some(action) = matched.iter().map(|rule| rule.action).max() else {
+            if decision < PathRuleAction::Ask {
+                decision = PathRuleAction::Ask;
+                deciding.clear();
+            }
+            continue;
+        };
+        if action > decision {
+            decision = action;
+            deciding.clear();
+        }
+        if action == decision {
+            for rule in matched.into_iter().filter(|rule| rule.action == action) {
+                if !deciding.iter().any(|r| r.id == rule.id) {
+                    deciding.push(rule.clone());
+                }
+            }
+        }
+    }
+    if paths.is_empty() {
+        decision = PathRuleAction::Ask;
+    }
+    PathClassification {
+        decision,
+        rules: deciding,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn rule(pattern: &str, action: PathRuleAction) -> FilePathRule {
+        FilePathRule {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            pattern: pattern.to_string(),
+            action,
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn decision(rules: &[FilePathRule], paths: &[&str]) -> PathRuleAction {
+        let paths: Vec<String> = paths.iter().map(|p| p.to_string()).collect();
+        classify(rules, &paths).decision
+    }
+
+    #[test]
+    fn matches_globs_like_gitignore() {
+        let matches = |pattern: &str, path: &str| compile(pattern).unwrap().is_match(path);
+        assert!(matches("src/**", "src/lib.rs"));
+        assert!(matches("src/**", "src/a/b/c.rs"));
+        assert!(!matches("src/**", "tests/src/lib.rs"));
+        assert!(matches("**/migrations/**", "crates/db/migrations/001.sql"));
+        assert!(matches("migrations/**", "migrations/001.sql"));
+        assert!(matches(".env*", ".env"));
+        assert!(matches(".env*", "frontend/.env.local"));
+        assert!(!matches("src/*.rs", "src/a/b.rs"));
+    }
+
+    #[test]
+    fn strictest_rule_wins() {
+        let rules = vec![
+            rule("src/**", PathRuleAction::Allow),
+            rule("src/generated/**", PathRuleAction::Ask),
+            rule("migrations/**", PathRuleAction::Ask),
+            rule(".env*", PathRuleAction::Deny),
+        ];
+
+        assert_eq!(decision(&rules, &["src/main.rs"]), PathRuleAction::Allow);
+        assert_eq!(
+            decision(&rules, &["src/generated/api.rs"]),
+            PathRuleAction::Ask
+        );
+        assert_eq!(decision(&rules, &["README.md"]), PathRuleAction::Ask);
+        assert_eq!(
+            decision(&rules, &["src/main.rs", "src/.env"]),
+            PathRuleAction::Deny
+        );
+
+        let classification = classify(&rules, &["migrations/001.sql".to_string()]);
+        assert_eq!(classification.rules.len(), 1);
+        assert_eq!(classification.rules[0].pattern, "migrations/**");
+    }
+
+    #[test]
+    fn finds_edited_files_relative_to_their_repo() {
+        let input = serde_json::json!({ "file_path": "/ws/backend/src/main.rs" });
+        let paths = edited_paths("Edit", &input);
+        assert_eq!(paths, vec!["/ws/backend/src/main.rs".to_string()]);
+        assert_eq!(
+            repo_relative_path(&paths[0], Some(Path::new("/ws")), &["backend"]),
+            "src/main.rs"
+        );
+        assert_eq!(
+            repo_relative_path("/elsewhere/x.rs", Some(Path::new("/ws")), &["backend"]),
+            "/elsewhere/x.rs"
+        );
+
+        let patch = serde_json::json!({ "file_changes": { "/ws/backend/a.rs": {} } });
+        assert_eq!(edited_paths("edit", &patch).len(), 1);
+        assert!(edited_paths("Read", &input).is_empty());
+    }
+}
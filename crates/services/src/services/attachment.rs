@@ -0,0 +1,373 @@
+use std::path::Path;
+
+use db::models::{
+    attempt_e2e_run::{AttemptE2eArtifact, E2eArtifactKind},
+    execution_process_logs::ExecutionProcessLogs,
+    task_attachment::{CreateTaskAttachment, TaskAttachment},
+};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use utils::log_msg::LogMsg;
+use uuid::Uuid;
+
+use crate::services::{
+    blob_storage::{BlobReader, BlobStorage, BlobStorageError},
+    server_config,
+};
+
+const ATTACHMENTS_PREFIX: &str = "attachments";
+const E2E_ARTIFACTS_PREFIX: &str = "e2e-artifacts";
+const LOG_EXPORTS_PREFIX: &str = "log-exports";
+const DEFAULT_MAX_SIZE_BYTES: u64 = 20 * 1024 * 1024; // 20MB, matching the upload body limit
+const MAX_FILE_NAME_LEN: usize = 100;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AttachmentError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] BlobStorageError),
+
+    #[error("Attachment is empty")]
+    Empty,
+
+    #[error("Attachment too large: {0} bytes (max: {1} bytes)")]
+    TooLarge(u64, u64),
+
+    #[error("Attachment not found")]
+    NotFound,
+}
+
+#[derive(Clone)]
+pub struct AttachmentService {
+    storage: BlobStorage,
+    pool: SqlitePool,
+    max_size_bytes: u64,
+}
+
+fn storage_key(file_path: &str) -> String {
+    format!("{ATTACHMENTS_PREFIX}/{file_path}")
+}
+
+fn e2e_storage_key(file_path: &str) -> String {
+    format!("{E2E_ARTIFACTS_PREFIX}/{file_path}")
+}
+
+fn log_export_key(task_id: Uuid, execution_process_id: Uuid) -> String {
+    format!("{LOG_EXPORTS_PREFIX}/{task_id}/{execution_process_id}.log")
+}
+
+/// Reduce an uploaded file name to something safe to use as a path component.
+fn sanitize_file_name(name: &str) -> String {
+    let base = Path::new(name)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let cleaned: String = base
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_FILE_NAME_LEN)
+        .collect();
+    let cleaned = cleaned.trim_start_matches('.');
+    if cleaned.is_empty() {
+        "attachment".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+/// Detect the content type from magic bytes, falling back to the file extension.
+pub fn detect_mime_type(data: &[u8], file_name: &str) -> String {
+    if let Some(kind) = infer::get(data) {
+        return kind.mime_type().to_string();
+    }
+    if let Some(mime) = mime_guess::from_path(file_name).first() {
+        return mime.essence_str().to_string();
+    }
+    if std::str::from_utf8(data).is_ok() {
+        "text/plain".to_string()
+    } else {
+        "application/octet-stream".to_string()
+    }
+}
+
+impl AttachmentService {
+    /// `VK_MAX_ATTACHMENT_BYTES` (or `limits.max_attachment_bytes`) overrides the default
+    /// size limit.
+    pub fn new(pool: SqlitePool, storage: BlobStorage) -> Self {
+        let max_size_bytes = server_config::setting("VK_MAX_ATTACHMENT_BYTES")
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_MAX_SIZE_BYTES);
+        Self {
+            storage,
+            pool,
+            max_size_bytes,
+        }
+    }
+
+    pub fn max_size_bytes(&self) -> u64 {
+        self.max_size_bytes
+    }
+
+    pub async fn store_attachment(
+        &self,
+        task_id: Uuid,
+        data: &[u8],
+        original_filename: &str,
+    ) -> Result<TaskAttachment, AttachmentError> {
+        let file_size = data.len() as u64;
+        if file_size == 0 {
+            return Err(AttachmentError::Empty);
+        }
+        if file_size > self.max_size_bytes {
+            return Err(AttachmentError::TooLarge(file_size, self.max_size_bytes));
+        }
+
+        let id = Uuid::new_v4();
+        let file_name = sanitize_file_name(original_filename);
+        let file_path = format!("{id}/{file_name}");
+        self.storage
+            .put(&storage_key(&file_path), data.to_vec())
+            .await?;
+
+        let attachment = TaskAttachment::create(
+            &self.pool,
+            id,
+            &CreateTaskAttachment {
+                task_id,
+                mime_type: detect_mime_type(data, &file_name),
+                file_path,
+                original_name: original_filename.to_string(),
+                size_bytes: file_size as i64,
+                hash: format!("{:x}", Sha256::digest(data)),
+            },
+        )
+        .await?;
+        Ok(attachment)
+    }
+
+    pub async fn get_attachment(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<TaskAttachment>, AttachmentError> {
+        Ok(TaskAttachment::find_by_id(&self.pool, id).await?)
+    }
+
+    /// Open the stored file for streaming.
+    pub async fn open_attachment(
+        &self,
+        attachment: &TaskAttachment,
+    ) -> Result<BlobReader, AttachmentError> {
+        Ok(self
+            .storage
+            .open(&storage_key(&attachment.file_path))
+            .await?)
+    }
+
+    pub async fn delete_attachment(&self, id: Uuid) -> Result<(), AttachmentError> {
+        let attachment = TaskAttachment::find_by_id(&self.pool, id)
+            .await?
+            .ok_or(AttachmentError::NotFound)?;
+        self.storage
+            .delete(&storage_key(&attachment.file_path))
+            .await?;
+        TaskAttachment::delete(&self.pool, id).await?;
+        Ok(())
+    }
+
+    /// Remove stored files for attachments whose rows are already gone, e.g. after their task
+    /// was deleted.
+    pub async fn delete_blobs(&self, attachments: &[TaskAttachment]) {
+        for attachment in attachments {
+            if let Err(e) = self
+                .storage
+                .delete(&storage_key(&attachment.file_path))
+                .await
+            {
+                tracing::error!("Failed to delete attachment {}: {}", attachment.id, e);
+            }
+        }
+    }
+
+    /// Keep a screenshot or trace an attempt's E2E run left behind.
+    pub async fn store_e2e_artifact(
+        &self,
+        run_id: Uuid,
+        kind: E2eArtifactKind,
+        data: &[u8],
+        original_name: &str,
+    ) -> Result<AttemptE2eArtifact, AttachmentError> {
+        let file_size = data.len() as u64;
+        if file_size == 0 {
+            return Err(AttachmentError::Empty);
+        }
+        if file_size > self.max_size_bytes {
+            return Err(AttachmentError::TooLarge(file_size, self.max_size_bytes));
+        }
+
+        let file_name = sanitize_file_name(original_name);
+        let file_path = format!("{run_id}/{}/{file_name}", Uuid::new_v4());
+        self.storage
+            .put(&e2e_storage_key(&file_path), data.to_vec())
+            .await?;
+
+        let artifact = AttemptE2eArtifact::create(
+            &self.pool,
+            run_id,
+            kind,
+            &file_path,
+            original_name,
+            &detect_mime_type(data, &file_name),
+            file_size as i64,
+        )
+        .await?;
+        Ok(artifact)
+    }
+
+    pub async fn open_e2e_artifact(
+        &self,
+        artifact: &AttemptE2eArtifact,
+    ) -> Result<BlobReader, AttachmentError> {
+        Ok(self
+            .storage
+            .open(&e2e_storage_key(&artifact.file_path))
+            .await?)
+    }
+
+    /// Remove stored files of E2E artifacts whose rows are already gone.
+    pub async fn delete_e2e_artifact_blobs(&self, artifacts: &[AttemptE2eArtifact]) {
+        for artifact in artifacts {
+            if let Err(e) = self
+                .storage
+                .delete(&e2e_storage_key(&artifact.file_path))
+                .await
+            {
+                tracing::error!("Failed to delete E2E artifact {}: {}", artifact.id, e);
+            }
+        }
+    }
+
+    /// Plain-text stdout/stderr of a finished execution process. The export is rendered from the
+    /// stored log records on first request and served from storage afterwards.
+    pub async fn export_logs(
+        &self,
+        task_id: Uuid,
+        execution_process_id: Uuid,
+    ) -> Result<BlobReader, AttachmentError> {
+        let key = log_export_key(task_id, execution_process_id);
+        if !self.storage.exists(&key).await? {
+            let records =
+                ExecutionProcessLogs::find_by_execution_id(&self.pool, execution_process_id)
+                    .await?;
+            let messages =
+                ExecutionProcessLogs::parse_logs(&records).map_err(std::io::Error::other)?;
+            let mut text = String::new();
+            for message in messages {
+                if let LogMsg::Stdout(chunk) | LogMsg::Stderr(chunk) = message {
+                    text.push_str(&chunk);
+                }
+            }
+            self.storage.put(&key, text).await?;
+        }
+        Ok(self.storage.open(&key).await?)
+    }
+
+    /// Remove every log export of a deleted task.
+    pub async fn delete_log_exports(&self, task_id: Uuid) {
+        let prefix = format!("{LOG_EXPORTS_PREFIX}/{task_id}");
+        let exports = match self.storage.list(&prefix).await {
+            Ok(exports) => exports,
+            Err(e) => {
+                tracing::error!("Failed to list log exports of task {}: {}", task_id, e);
+                return;
+            }
+        };
+        for export in exports {
+            if let Err(e) = self.storage.delete(&export.key).await {
+                tracing::error!("Failed to delete log export {}: {}", export.key, e);
+            }
+        }
+    }
+
+    /// Copy a task's attachments into the workspace. Skips files that already exist.
+    pub async fn copy_attachments_by_task_to_worktree(
+        &self,
+        worktree_path: &Path,
+        task_id: Uuid,
+    ) -> Result<(), AttachmentError> {
+        let attachments = TaskAttachment::find_by_task_id(&self.pool, task_id).await?;
+        if attachments.is_empty() {
+            return Ok(());
+        }
+
+        let attachments_dir = worktree_path.join(utils::path::VIBE_ATTACHMENTS_DIR);
+        std::fs::create_dir_all(&attachments_dir)?;
+
+        // Create .gitignore to ignore all files in this directory
+        let gitignore_path = attachments_dir.join(".gitignore");
+        if !gitignore_path.exists() {
+            std::fs::write(&gitignore_path, "*\n")?;
+        }
+
+        for attachment in attachments {
+            let dst = attachments_dir.join(&attachment.file_path);
+            if dst.exists() {
+                continue;
+            }
+            if let Some(parent) = dst.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            match self.storage.get(&storage_key(&attachment.file_path)).await {
+                Ok(data) => {
+                    if let Err(e) = tokio::fs::write(&dst, data).await {
+                        tracing::error!("Failed to copy {}: {}", attachment.file_path, e);
+                    }
+                }
+                Err(BlobStorageError::NotFound(key)) => {
+                    tracing::warn!("Missing stored attachment: {}", key);
+                }
+                Err(e) => tracing::error!("Failed to read {}: {}", attachment.file_path, e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitizes_file_names() {
+        assert_eq!(sanitize_file_name("report v2.pdf"), "report_v2.pdf");
+        assert_eq!(sanitize_file_name("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_file_name(".env"), "env");
+        assert_eq!(sanitize_file_name(""), "attachment");
+    }
+
+    #[test]
+    fn detects_mime_types() {
+        assert_eq!(
+            detect_mime_type(b"%PDF-1.7\n", "notes.bin"),
+            "application/pdf"
+        );
+        assert_eq!(detect_mime_type(b"a,b\n1,2\n", "data.csv"), "text/csv");
+        assert_eq!(detect_mime_type(b"plain words", "README"), "text/plain");
+        assert_eq!(
+            detect_mime_type(&[0x81, 0x82, 0x83], "blob"),
+            "application/octet-stream"
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Side-by-side comparison of two attempts of a task: how their changes differ file by file,
+//! and what each cost, how long its agent ran and whether its checks passed.
+
+use std::{collections::BTreeMap, mem};
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    execution_process::{AttemptRun, ExecutionProcessRunReason, ExecutionProcessStatus},
+    workspace::Workspace,
+};
+use serde::Serialize;
+use ts_rs::TS;
+use utils::diff::{Diff, DiffChangeKind, compute_line_change_counts};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum FileComparisonStatus {
+    OnlyInA,
+    OnlyInB,
+    /// Both attempts left the file with the same contents
+    Same,
+    /// Both changed the file differently, or its contents were too large to compare
+    Differs,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct FileChange {
+    pub change: DiffChangeKind,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct FileComparison {
+    pub path: String,
+    pub status: FileComparisonStatus,
+    pub a: Option<FileChange>,
+    pub b: Option<FileChange>,
+}
+
+/// Result of the latest cleanup script, which is where projects run their tests.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    /// No cleanup script ran, or the latest one was stopped or is still running
+    NotRun,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct AttemptSide {
+    pub workspace_id: Uuid,
+    pub branch: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    /// Cost reported by the attempt's coding agent runs, `None` when none reported one
+    pub cost_usd: Option<f64>,
+    /// Wall-clock time of the attempt's finished coding agent runs
+    pub agent_seconds: f64,
+    pub agent_runs: usize,
+    pub tests: TestOutcome,
+    pub files_changed: usize,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct AttemptComparison {
+    pub a: AttemptSide,
+    pub b: AttemptSide,
+    /// Files changed by either attempt, by path
+    pub files: Vec<FileComparison>,
+}
+
+fn diff_path(diff: &Diff) -> String {
+    diff.new_path
+        .as_deref()
+        .or(diff.old_path.as_deref())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn file_change(diff: &Diff) -> FileChange {
+    let (additions, deletions) = if diff.content_omitted {
+        (
+            diff.additions.unwrap_or_default(),
+            diff.deletions.unwrap_or_default(),
+        )
+    } else {
+        compute_line_change_counts(
+            diff.old_content.as_deref().unwrap_or_default(),
+            diff.new_content.as_deref().unwrap_or_default(),
+        )
+    };
+    FileChange {
+        change: diff.change.clone(),
+        additions,
+        deletions,
+    }
+}
+
+fn same_result(a: &Diff, b: &Diff) -> bool {
+    !a.content_omitted
+        && !b.content_omitted
+        && mem::discriminant(&a.change) == mem::discriminant(&b.change)
+        && a.new_content == b.new_content
+}
+
+/// Compare the files two attempts changed, sorted by path.
+pub fn compare_diffs(a: &[Diff], b: &[Diff]) -> Vec<FileComparison> {
+    let mut by_path: BTreeMap<String, (Option<&Diff>, Option<&Diff>)> = BTreeMap::new();
+    for diff in a {
+        by_path.entry(diff_path(diff)).or_default().0 = Some(diff);
+    }
+    for diff in b {
+        by_path.entry(diff_path(diff)).or_default().1 = Some(diff);
+    }
+
+    by_path
+        .into_iter()
+        .map(|(path, (a, b))| {
+            let status = match (a, b) {
+                (Some(a), Some(b)) if same_result(a, b) => FileComparisonStatus::Same,
+                (Some(_), Some(_)) => FileComparisonStatus::Differs,
+                (Some(_), None) => FileComparisonStatus::OnlyInA,
+                (None, _) => FileComparisonStatus::OnlyInB,
+            };
+            FileComparison {
+                path,
+                status,
+                a: a.map(file_change),
+                b: b.map(file_change),
+            }
+        })
+        .collect()
+}
+
+/// Cost, agent time and test outcome of an attempt next to the totals of its diff.
+pub fn attempt_side(workspace: &Workspace, runs: &[AttemptRun], diffs: &[Diff]) -> AttemptSide {
+    let agent_runs: Vec<&AttemptRun> = runs
+        .iter()
+        .filter(|run| run.run_reason == ExecutionProcessRunReason::CodingAgent)
+        .collect();
+    let costs: Vec<f64> = agent_runs.iter().filter_map(|run| run.cost_usd).collect();
+    let agent_seconds = agent_runs
+        .iter()
+        .filter_map(|run| {
+            let completed_at = run.completed_at?;
+            Some((completed_at - run.started_at).num_milliseconds().max(0) as f64 / 1000.0)
+        })
+        .sum();
+    let tests = match runs
+        .iter()
+        .rev()
+        .find(|run| run.run_reason == ExecutionProcessRunReason::CleanupScript)
+        .map(|run| &run.status)
+    {
+        Some(ExecutionProcessStatus::Completed) => TestOutcome::Passed,
+        Some(ExecutionProcessStatus::Failed) => TestOutcome::Failed,
+        _ => TestOutcome::NotRun,
+    };
+    let changes: Vec<FileChange> = diffs.iter().map(file_change).collect();
+
+    AttemptSide {
+        workspace_id: workspace.id,
+        branch: workspace.branch.clone(),
+        created_at: workspace.created_at,
+        cost_usd: (!costs.is_empty()).then(|| costs.iter().sum()),
+        agent_seconds,
+        agent_runs: agent_runs.len(),
+        tests,
+        files_changed: changes.len(),
+        additions: changes.iter().map(|c| c.additions).sum(),
+        deletions: changes.iter().map(|c| c.deletions).sum(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modified(path: &str, old: &str, new: &str) -> Diff {
+        Diff {
+            change: DiffChangeKind::Modified,
+            old_path: Some(path.to_string()),
+            new_path: Some(path.to_string()),
+            old_content: Some(old.to_string()),
+            new_content: Some(new.to_string()),
+            content_omitted: false,
+            additions: None,
+            deletions: None,
+        }
+    }
+
+    #[test]
+    fn compares_files_by_path() {
+        let a = vec![
+            modified("src/lib.rs", "a\n", "b\n"),
+            modified("src/auth.rs", "x\n", "y\n"),
+            modified("README.md", "old\n", "new\n"),
+        ];
+        let b = vec![
+            modified("src/lib.rs", "a\n", "b\n"),
+            modified("src/auth.rs", "x\n", "z\nw\n"),
+            modified("Cargo.toml", "1\n", "2\n"),
+        ];
+        let files = compare_diffs(&a, &b);
+        let statuses: Vec<(&str, FileComparisonStatus)> = files
+            .iter()
+            .map(|file| (file.path.as_str(), file.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("Cargo.toml", FileComparisonStatus::OnlyInB),
+                ("README.md", FileComparisonStatus::OnlyInA),
+                ("src/auth.rs", FileComparisonStatus::Differs),
+                ("src/lib.rs", FileComparisonStatus::Same),
+            ]
+        );
+        let auth = &files[2];
+        assert_eq!(auth.b.as_ref().unwrap().additions, 2);
+        assert_eq!(auth.b.as_ref().unwrap().deletions, 1);
+    }
+
+    #[test]
+    fn omitted_contents_never_compare_equal() {
+        let mut a = modified("big.json", "", "");
+        a.content_omitted = true;
+        let b = a.clone();
+        assert_eq!(
+            compare_diffs(&[a], &[b])[0].status,
+            FileComparisonStatus::Differs
+        );
+    }
+}
@@ -0,0 +1,94 @@
+//! Turns a reviewer's inline comments on an attempt's diff into feedback for the agent.
+
+use db::models::attempt_review::{AttemptReview, AttemptReviewComment, AttemptReviewVerdict};
+
+/// Follow-up prompt asking the agent to address the unresolved comments, grouped by file.
+/// `None` when every comment is resolved.
+pub fn feedback_prompt(
+    review: Option<&AttemptReview>,
+    comments: &[AttemptReviewComment],
+) -> Option<String> {
+    let mut unresolved: Vec<&AttemptReviewComment> =
+        comments.iter().filter(|c| !c.resolved).collect();
+    if unresolved.is_empty() {
+        return None;
+    }
+    unresolved.sort_by(|a, b| a.file_path.cmp(&b.file_path).then(a.line.cmp(&b.line)));
+
+    let mut prompt =
+        String::from("A reviewer left comments on your changes. Address each of them:\n");
+    if let Some(review) = review
+        && review.verdict == AttemptReviewVerdict::ChangesRequested
+        && let Some(summary) = review.summary.as_deref().map(str::trim)
+        && !summary.is_empty()
+    {
+        prompt.push_str(&format!("\nOverall: {summary}\n"));
+    }
+
+    let mut current_file = None;
+    for comment in unresolved {
+        if current_file != Some(&comment.file_path) {
+            prompt.push_str(&format!("\n{}\n", comment.file_path));
+            current_file = Some(&comment.file_path);
+        }
+        let body = comment.body.trim().replace('\n', "\n  ");
+        prompt.push_str(&format!("- Line {}: {}\n", comment.line, body));
+    }
+    Some(prompt)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn comment(file_path: &str, line: i64, body: &str, resolved: bool) -> AttemptReviewComment {
+        AttemptReviewComment {
+            id: Uuid::new_v4(),
+            workspace_id: Uuid::new_v4(),
+            file_path: file_path.to_string(),
+            line,
+            body: body.to_string(),
+            resolved,
+            sent_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn groups_unresolved_comments_by_file() {
+        let review = AttemptReview {
+            id: Uuid::new_v4(),
+            workspace_id: Uuid::new_v4(),
+            verdict: AttemptReviewVerdict::ChangesRequested,
+            summary: Some("Close, but the error handling needs work".to_string()),
+            created_at: Utc::now(),
+        };
+        let comments = vec![
+            comment("src/main.rs", 40, "Don't unwrap here", false),
+            comment("src/lib.rs", 3, "Typo", true),
+            comment("src/main.rs", 12, "Log the error\nand return it", false),
+            comment("README.md", 1, "Mention the new flag", false),
+        ];
+
+        let prompt = feedback_prompt(Some(&review), &comments).unwrap();
+        assert!(prompt.contains("Overall: Close, but the error handling needs work"));
+        assert!(!prompt.contains("Typo"));
+        let readme = prompt.find("README.md").unwrap();
+        let first = prompt
+            .find("- Line 12: Log the error\n  and return it")
+            .unwrap();
+        let second = prompt.find("- Line 40: Don't unwrap here").unwrap();
+        assert!(readme < first && first < second);
+        assert_eq!(prompt.matches("src/main.rs").count(), 1);
+    }
+
+    #[test]
+    fn nothing_to_send_when_all_resolved() {
+        let comments = vec![comment("src/main.rs", 1, "Done", true)];
+        assert!(feedback_prompt(None, &comments).is_none());
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+
+use db::models::{coding_agent_turn::CodingAgentTurn, task::Task};
+use executors::logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch};
+use utils::{
+    diff::{Diff, create_unified_diff},
+    log_msg::LogMsg,
+    text::truncate_to_char_boundary,
+};
+
+use crate::services::llm::{LlmError, LlmService};
+
+/// Budget for the log section of the prompt, in bytes; the end of the log is kept
+const MAX_TRANSCRIPT_BYTES: usize = 24_000;
+/// Budget for the diff section of the prompt, in bytes
+const MAX_DIFF_BYTES: usize = 32_000;
+
+const SUMMARY_SYSTEM_PROMPT: &str = "You summarize work done by a coding agent so that \
+reviewers do not have to read the whole transcript. From the task, the agent's log and the \
+resulting diff, explain what was changed and why in at most 150 words of Markdown: one sentence \
+of overview followed by a few bullet points. Mention anything left unfinished or failing. Reply \
+with the summary only.";
+
+/// Render the normalized entries of an execution's log as plain text, one entry per line.
+pub fn transcript_from_history(history: &[LogMsg]) -> String {
+    // Entries are replaced in place as they update, so keep the last version of each
+    let mut entries = BTreeMap::new();
+    for msg in history {
+        if let LogMsg::JsonPatch(patch) = msg
+            && let Some((index, entry)) = extract_normalized_entry_from_patch(patch)
+        {
+            entries.insert(index, entry);
+        }
+    }
+
+    entries
+        .into_values()
+        .filter_map(|entry| {
+            let content = entry.content.trim();
+            if content.is_empty() {
+                return None;
+            }
+            let label = match &entry.entry_type {
+                NormalizedEntryType::UserMessage => "User".to_string(),
+                NormalizedEntryType::UserFeedback { .. } => "User feedback".to_string(),
+                NormalizedEntryType::AssistantMessage
+                | NormalizedEntryType::HumanHandoff { .. } => "Agent".to_string(),
+                NormalizedEntryType::ToolUse { tool_name, .. } => format!("Tool {tool_name}"),
+                NormalizedEntryType::ErrorMessage { .. } => "Error".to_string(),
+                _ => return None,
+            };
+            Some(format!("{label}: {content}"))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prompts and final agent messages of an attempt's turns, for when the full log is gone.
+pub fn transcript_from_turns(turns: &[CodingAgentTurn]) -> String {
+    turns
+        .iter()
+        .flat_map(|turn| {
+            [("User", &turn.prompt), ("Agent", &turn.summary)]
+                .into_iter()
+                .filter_map(|(label, text)| {
+                    let text = text.as_deref()?.trim();
+                    (!text.is_empty()).then(|| format!("{label}: {text}"))
+                })
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Unified diffs of the changed files, or just their stats where contents were omitted.
+pub fn render_diffs(diffs: &[Diff]) -> String {
+    diffs
+        .iter()
+        .map(|diff| {
+            let path = diff
+                .new_path
+                .as_deref()
+                .or(diff.old_path.as_deref())
+                .unwrap_or_default();
+            if diff.content_omitted {
+                format!(
+                    "{path}: {:?}, +{} -{} (contents omitted)\n",
+                    diff.change,
+                    diff.additions.unwrap_or_default(),
+                    diff.deletions.unwrap_or_default()
+                )
+            } else {
+                create_unified_diff(
+                    path,
+                    diff.old_content.as_deref().unwrap_or_default(),
+                    diff.new_content.as_deref().unwrap_or_default(),
+                )
+            }
+        })
+        .collect()
+}
+
+/// The last `max_len` bytes of `content`, moved forward to a char boundary.
+pub(crate) fn tail(content: &str, max_len: usize) -> &str {
+    let mut start = content.len().saturating_sub(max_len);
+    while !content.is_char_boundary(start) {
+        start += 1;
+    }
+    &content[start..]
+}
+
+/// Ask the model for a short "what changed and why" summary of an attempt.
+pub async fn summarize_attempt(
+    llm: &LlmService,
+    task: &Task,
+    transcript: &str,
+    diff: &str,
+) -> Result<String, LlmError> {
+    let transcript = match transcript.trim() {
+        "" => "(no log available)",
+        t if t.len() > MAX_TRANSCRIPT_BYTES => tail(t, MAX_TRANSCRIPT_BYTES),
+        t => t,
+    };
+    let diff = match diff.trim() {
+        "" => "(no changes)".to_string(),
+        d if d.len() > MAX_DIFF_BYTES => {
+            format!(
+                "{}\n[diff truncated]",
+                truncate_to_char_boundary(d, MAX_DIFF_BYTES)
+            )
+        }
+        d => d.to_string(),
+    };
+    let prompt = format!(
+        "Task:\n{}\n\nAgent log (oldest first):\n{transcript}\n\nDiff:\n{diff}",
+        task.to_prompt()
+    );
+
+    let summary = llm.complete(SUMMARY_SYSTEM_PROMPT, &prompt).await?;
+    let summary = summary.trim();
+    if summary.is_empty() {
+        return Err(LlmError::InvalidResponse("summary is empty".to_string()));
+    }
+    Ok(summary.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::logs::{NormalizedEntry, utils::patch::ConversationPatch};
+
+    use super::*;
+
+    fn entry(entry_type: NormalizedEntryType, content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn transcript_keeps_latest_version_of_each_entry() {
+        let history = vec![
+            LogMsg::JsonPatch(ConversationPatch::add_normalized_entry(
+                0,
+                entry(NormalizedEntryType::UserMessage, "Fix the login bug"),
+            )),
+            LogMsg::Stdout("raw output".to_string()),
+            LogMsg::JsonPatch(ConversationPatch::add_normalized_entry(
+                1,
+                entry(NormalizedEntryType::AssistantMessage, "Looking"),
+            )),
+            LogMsg::JsonPatch(ConversationPatch::add_normalized_entry(
+                2,
+                entry(NormalizedEntryType::Thinking, "hmm"),
+            )),
+            LogMsg::JsonPatch(ConversationPatch::replace(
+                1,
+                entry(
+                    NormalizedEntryType::AssistantMessage,
+                    "Fixed the session check",
+                ),
+            )),
+        ];
+        assert_eq!(
+            transcript_from_history(&history),
+            "User: Fix the login bug\nAgent: Fixed the session check"
+        );
+    }
+
+    #[test]
+    fn tail_respects_char_boundaries() {
+        assert_eq!(tail("héllo", 4), "llo");
+        assert_eq!(tail("abc", 10), "abc");
+    }
+}
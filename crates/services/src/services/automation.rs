@@ -0,0 +1,413 @@
+//! Board automations: project rules that run actions when something happens to a task.
+//!
+//! The engine consumes the entity event bus. Status changes are detected by comparing each
+//! task update with the last status seen, since task update events only carry the new state.
+//! Every firing is recorded with the outcome of each action. A rule that already fired
+//! [`MAX_FIRINGS_PER_WINDOW`] times on a task within [`FIRING_WINDOW_SECS`] is skipped, so
+//! rules that trigger each other can't loop forever.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use db::{
+    DBService,
+    models::{
+        automation_rule::{
+            AutomationAction, AutomationActionResult, AutomationRule, AutomationRuleFiring,
+            AutomationTrigger,
+        },
+        execution_process::{ExecutionProcess, ExecutionProcessStatus},
+        notification::{CreateNotification, NotificationKind},
+        task::{Task, TaskStatus},
+        task_label::TaskLabel,
+    },
+};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::{sync::RwLock, task::JoinHandle};
+use uuid::Uuid;
+
+use crate::services::{
+    config::Config,
+    container::{ContainerError, ContainerService},
+    events::{
+        EntityEvent, EntityEventBus, EntityEventEnvelope, EntityEventSubscriber, spawn_subscriber,
+    },
+    share::ShareError,
+    task_workflow::{self, TransitionError},
+};
+
+pub const MAX_FIRINGS_PER_WINDOW: i64 = 5;
+pub const FIRING_WINDOW_SECS: i64 = 60;
+
+/// Something that happened to a task, in the terms rules are written in.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AutomationEvent {
+    StatusChanged { from: TaskStatus, to: TaskStatus },
+    LabelAdded { label: String },
+    AttemptFailed,
+    QuestionTimedOut,
+}
+
+pub fn trigger_matches(trigger: &AutomationTrigger, event: &AutomationEvent) -> bool {
+    match (trigger, event) {
+        (
+            AutomationTrigger::StatusChanged { from, to },
+            AutomationEvent::StatusChanged {
+                from: event_from,
+                to: event_to,
+            },
+        ) => {
+            from.as_ref().is_none_or(|from| from == event_from)
+                && to.as_ref().is_none_or(|to| to == event_to)
+        }
+        (AutomationTrigger::LabelAdded { label }, AutomationEvent::LabelAdded { label: added }) => {
+            label.as_ref().is_none_or(|label| label == added)
+        }
+        (AutomationTrigger::AttemptFailed, AutomationEvent::AttemptFailed)
+        | (AutomationTrigger::QuestionTimedOut, AutomationEvent::QuestionTimedOut) => true,
+        _ => false,
+    }
+}
+
+/// Label a task, announcing the label when the task didn't have it yet.
+pub async fn add_label(
+    pool: &SqlitePool,
+    entity_events: &EntityEventBus,
+    task: &Task,
+    label: &str,
+) -> Result<bool, sqlx::Error> {
+    let added = TaskLabel::add(pool, task.id, label).await?;
+    if added {
+        entity_events.publish(
+            Some(task.project_id),
+            EntityEvent::TaskLabelAdded {
+                task_id: task.id,
+                label: label.to_string(),
+            },
+        );
+    }
+    Ok(added)
+}
+
+#[derive(Debug, Error)]
+enum ActionError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Transition(#[from] TransitionError),
+    #[error(transparent)]
+    Container(#[from] ContainerError),
+    #[error(transparent)]
+    Share(#[from] ShareError),
+    #[error("{0}")]
+    Skipped(&'static str),
+}
+
+pub struct AutomationEngine<C> {
+    db: DBService,
+    container: C,
+    config: Arc<RwLock<Config>>,
+    entity_events: EntityEventBus,
+    /// Last status seen for each task
+    statuses: Mutex<HashMap<Uuid, TaskStatus>>,
+}
+
+impl<C: ContainerService + Send + Sync + 'static> AutomationEngine<C> {
+    pub fn new(
+        db: DBService,
+        container: C,
+        config: Arc<RwLock<Config>>,
+        entity_events: EntityEventBus,
+    ) -> Self {
+        Self {
+            db,
+            container,
+            config,
+            entity_events,
+            statuses: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Start evaluating rules against events published from now on.
+    pub async fn spawn(self) -> JoinHandle<()> {
+        match Task::find_all_statuses(&self.db.pool).await {
+            Ok(statuses) => *self.statuses.lock().unwrap_or_else(|e| e.into_inner()) = statuses,
+            Err(e) => tracing::error!("Failed to load task statuses for automations: {}", e),
+        }
+        let bus = self.entity_events.clone();
+        spawn_subscriber(&bus, Arc::new(self))
+    }
+
+    fn automation_event(&self, event: &EntityEvent) -> Option<(Uuid, AutomationEvent)> {
+        match event {
+            EntityEvent::TaskCreated { task } => {
+                self.statuses
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(task.id, task.status.clone());
+                None
+            }
+            EntityEvent::TaskUpdated { task } => {
+                let previous = self
+                    .statuses
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(task.id, task.status.clone());
+                match previous {
+                    Some(from) if from != task.status => Some((
+                        task.id,
+                        AutomationEvent::StatusChanged {
+                            from,
+                            to: task.status.clone(),
+                        },
+                    )),
+                    _ => None,
+                }
+            }
+            EntityEvent::TaskDeleted { task_id } => {
+                self.statuses
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .remove(task_id);
+                None
+            }
+            EntityEvent::TaskLabelAdded { task_id, label } => Some((
+                *task_id,
+                AutomationEvent::LabelAdded {
+                    label: label.clone(),
+                },
+            )),
+            EntityEvent::AttemptFinished {
+                task_id,
+                status: ExecutionProcessStatus::Failed,
+                ..
+            } => Some((*task_id, AutomationEvent::AttemptFailed)),
+            EntityEvent::QuestionAnswered {
+                task_id,
+                answered: false,
+                ..
+            } => Some((*task_id, AutomationEvent::QuestionTimedOut)),
+            _ => None,
+        }
+    }
+
+    async fn evaluate(&self, task_id: Uuid, event: &AutomationEvent) -> Result<(), sqlx::Error> {
+        let pool = &self.db.pool;
+        let Some(task) = Task::find_by_id(pool, task_id).await? else {
+            return Ok(());
+        };
+        let rules = AutomationRule::find_by_project_id(pool, task.project_id).await?;
+        for rule in rules
+            .into_iter()
+            .filter(|rule| rule.enabled && trigger_matches(&rule.trigger, event))
+        {
+            let recent =
+                AutomationRuleFiring::count_recent(pool, rule.id, task_id, FIRING_WINDOW_SECS)
+                    .await?;
+            if recent >= MAX_FIRINGS_PER_WINDOW {
+                tracing::warn!(
+                    "Not firing automation '{}' on task {}: it fired {} times in the last {}s",
+                    rule.name,
+                    task_id,
+                    recent,
+                    FIRING_WINDOW_SECS
+                );
+                continue;
+            }
+
+            let mut results = Vec::with_capacity(rule.actions.len());
+            for action in rule.actions.iter() {
+                let outcome = self.run_action(&rule, task_id, action).await;
+                if let Err(e) = &outcome {
+                    tracing::info!(
+                        "Automation '{}' action {:?} on task {} failed: {}",
+                        rule.name,
+                        action,
+                        task_id,
+                        e
+                    );
+                }
+                results.push(AutomationActionResult {
+                    action: action.clone(),
+                    ok: outcome.is_ok(),
+                    detail: outcome.err().map(|e| e.to_string()),
+                });
+            }
+            AutomationRuleFiring::create(pool, rule.id, task_id, &results).await?;
+        }
+        Ok(())
+    }
+
+    async fn run_action(
+        &self,
+        rule: &AutomationRule,
+        task_id: Uuid,
+        action: &AutomationAction,
+    ) -> Result<(), ActionError> {
+        let pool = &self.db.pool;
+        // Reload so each action sees what the previous ones did
+        let task = Task::find_by_id(pool, task_id)
+            .await?
+            .ok_or(ActionError::Skipped("the task no longer exists"))?;
+        match action {
+            AutomationAction::MoveTo { status } => {
+                task_workflow::check_transition(pool, &task, status).await?;
+                if &task.status != status {
+                    Task::update_status(pool, task.id, status.clone()).await?;
+                }
+            }
+            AutomationAction::Assign { user_id } => {
+                let shared_task_id = task
+                    .shared_task_id
+                    .ok_or(ActionError::Skipped("the task is not shared"))?;
+                let publisher = self
+                    .container
+                    .share_publisher()
+                    .ok_or(ActionError::Skipped("sharing is not configured"))?;
+                publisher
+                    .assign_shared_task(shared_task_id, Some(user_id.clone()))
+                    .await?;
+            }
+            AutomationAction::Notify { message } => {
+                self.container
+                    .notification_service()
+                    .notify(CreateNotification {
+                        kind: NotificationKind::Automation,
+                        title: rule.name.clone(),
+                        message: format!("{}: {}", task.title, message),
+                        project_id: Some(task.project_id),
+                        task_id: Some(task.id),
+                    })
+                    .await;
+            }
+            AutomationAction::StartAttempt => {
+                if ExecutionProcess::has_running_non_dev_server_processes_for_task(pool, task.id)
+                    .await?
+                {
+                    return Err(ActionError::Skipped("an attempt is already running"));
+                }
+                let default_profile = self.config.read().await.executor_profile.clone();
+                self.container
+                    .start_default_attempt(&task, default_profile)
+                    .await?
+                    .ok_or(ActionError::Skipped("the project has no usable repository"))?;
+            }
+            AutomationAction::AddLabel { label } => {
+                add_label(pool, &self.entity_events, &task, label).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<C: ContainerService + Send + Sync + 'static> EntityEventSubscriber for AutomationEngine<C> {
+    fn name(&self) -> &'static str {
+        "automations"
+    }
+
+    async fn handle(&self, envelope: &EntityEventEnvelope) {
+        let Some((task_id, event)) = self.automation_event(&envelope.event) else {
+            return;
+        };
+        if let Err(e) = self.evaluate(task_id, &event).await {
+            tracing::error!("Failed to run automations for task {}: {}", task_id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_changed(from: TaskStatus, to: TaskStatus) -> AutomationEvent {
+        AutomationEvent::StatusChanged { from, to }
+    }
+
+    #[test]
+    fn status_triggers_match_any_side_left_open() {
+        let into_review = AutomationTrigger::StatusChanged {
+            from: None,
+            to: Some(TaskStatus::InReview),
+        };
+        assert!(trigger_matches(
+            &into_review,
+            &status_changed(TaskStatus::InProgress, TaskStatus::InReview)
+        ));
+        assert!(trigger_matches(
+            &into_review,
+            &status_changed(TaskStatus::Todo, TaskStatus::InReview)
+        ));
+        assert!(!trigger_matches(
+            &into_review,
+            &status_changed(TaskStatus::InReview, TaskStatus::Done)
+        ));
+
+        let reopened = AutomationTrigger::StatusChanged {
+            from: Some(TaskStatus::Done),
+            to: Some(TaskStatus::Todo),
+        };
+        assert!(!trigger_matches(
+            &reopened,
+            &status_changed(TaskStatus::Cancelled, TaskStatus::Todo)
+        ));
+    }
+
+    #[test]
+    fn label_and_failure_triggers_match_their_events_only() {
+        let any_label = AutomationTrigger::LabelAdded { label: None };
+        let bug = AutomationTrigger::LabelAdded {
+            label: Some("bug".to_string()),
+        };
+        let added = |label: &str| AutomationEvent::LabelAdded {
+            label: label.to_string(),
+        };
+
+        assert!(trigger_matches(&any_label, &added("docs")));
+        assert!(trigger_matches(&bug, &added("bug")));
+        assert!(!trigger_matches(&bug, &added("docs")));
+        assert!(trigger_matches(
+            &AutomationTrigger::AttemptFailed,
+            &AutomationEvent::AttemptFailed
+        ));
+        assert!(!trigger_matches(
+            &AutomationTrigger::AttemptFailed,
+            &AutomationEvent::QuestionTimedOut
+        ));
+        assert!(!trigger_matches(
+            &any_label,
+            &AutomationEvent::AttemptFailed
+        ));
+    }
+
+    #[test]
+    fn rules_round_trip_through_json() {
+        let actions = vec![
+            AutomationAction::MoveTo {
+                status: TaskStatus::InProgress,
+            },
+            AutomationAction::AddLabel {
+                label: "needs-triage".to_string(),
+            },
+        ];
+        let json = serde_json::to_value(&actions).unwrap();
+        assert_eq!(json[0]["type"], "move_to");
+        assert_eq!(json[0]["status"], "inprogress");
+        let parsed: Vec<AutomationAction> = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, actions);
+
+        let trigger: AutomationTrigger =
+            serde_json::from_str(r#"{"type":"status_changed","to":"done"}"#).unwrap();
+        assert_eq!(
+            trigger,
+            AutomationTrigger::StatusChanged {
+                from: None,
+                to: Some(TaskStatus::Done),
+            }
+        );
+    }
+}
@@ -0,0 +1,606 @@
+//! Online backups of the database and uploaded images, and staged restores.
+//!
+//! A backup is a `.tar.gz` holding `manifest.json`, a consistent `db.sqlite`
+//! snapshot taken with `VACUUM INTO`, and the `images/` directory. Restores are
+//! staged next to the live database and swapped in on the next start, since the
+//! running server holds the database open. Project secrets stay encrypted with
+//! the local `secret.key`, which is deliberately not part of the archive. Images kept in
+//! external blob storage (`VK_BLOB_STORAGE_URL`) are left to that storage's own backups.
+//!
+//! Scheduled backups can also be copied to an `s3://` URI. The copy goes through
+//! [`BlobStorage`], so it needs no `aws` binary, only the standard `AWS_*` variables.
+
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use chrono::{DateTime, Utc};
+use db::{DBService, database_path, pending_restore_path};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use ts_rs::TS;
+use utils::{assets::asset_dir, path::expand_tilde, version::APP_VERSION};
+use uuid::Uuid;
+
+use crate::services::{
+    blob_storage::{BlobStorage, BlobStorageError},
+    config::BackupScheduleConfig,
+};
+
+const FORMAT_VERSION: u32 = 1;
+const MANIFEST_FILE: &str = "manifest.json";
+const DB_FILE: &str = "db.sqlite";
+const IMAGES_DIR: &str = "images";
+const ARCHIVE_SUFFIX: &str = ".tar.gz";
+const MANUAL_PREFIX: &str = "vibe-kanban";
+const SCHEDULED_PREFIX: &str = "vibe-kanban-auto";
+
+#[derive(Debug, Error)]
+pub enum BackupError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("Invalid backup manifest: {0}")]
+    Manifest(#[from] serde_json::Error),
+    #[error("Backup not found")]
+    NotFound,
+    #[error("Backup storage error: {0}")]
+    Storage(#[from] BlobStorageError),
+    #[error("Invalid backup archive: {0}")]
+    InvalidArchive(String),
+    #[error(
+        "Backup was made with a newer schema (version {backup}) than this server supports ({current}); upgrade before restoring"
+    )]
+    IncompatibleVersion { backup: i64, current: i64 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    /// Latest applied database migration when the backup was taken
+    pub schema_version: i64,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct BackupInfo {
+    pub file_name: String,
+    pub size_bytes: u64,
+    pub manifest: BackupManifest,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct RestoreResult {
+    pub manifest: BackupManifest,
+    pub images_restored: usize,
+    /// The database is swapped in when the server next starts
+    pub restart_required: bool,
+}
+
+/// Outcome of scheduled backups, reported on `/health/backup`.
+#[derive(Debug, Clone, Default, Serialize, TS)]
+pub struct BackupStatus {
+    pub schedule_enabled: bool,
+    #[ts(type = "Date | null")]
+    pub last_success_at: Option<DateTime<Utc>>,
+    /// Most recent scheduled backup taken since the server started
+    pub last_backup: Option<BackupInfo>,
+    #[ts(type = "Date | null")]
+    pub last_failure_at: Option<DateTime<Utc>>,
+    /// Error from the last scheduled run, cleared by the next success
+    pub last_error: Option<String>,
+    #[ts(type = "Date | null")]
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone)]
+pub struct BackupService {
+    db: DBService,
+    db_path: PathBuf,
+    images_dir: PathBuf,
+    backups_dir: PathBuf,
+    status: Arc<RwLock<BackupStatus>>,
+}
+
+impl BackupService {
+    pub fn new(db: DBService) -> Self {
+        Self {
+            db,
+            db_path: database_path(),
+            images_dir: utils::cache_dir().join(IMAGES_DIR),
+            backups_dir: asset_dir().join("backups"),
+            status: Arc::new(RwLock::new(BackupStatus::default())),
+        }
+    }
+
+    pub fn backups_dir(&self) -> &Path {
+        &self.backups_dir
+    }
+
+    pub async fn status(&self) -> BackupStatus {
+        self.status.read().await.clone()
+    }
+
+    async fn create_archive(&self, dir: &Path, prefix: &str) -> Result<BackupInfo, BackupError> {
+        tokio::fs::create_dir_all(dir).await?;
+
+        let created_at = Utc::now();
+        let file_name = format!(
+            "{prefix}-{}{ARCHIVE_SUFFIX}",
+            created_at.format("%Y%m%d-%H%M%S")
+        );
+        let staging = tempfile::Builder::new()
+            .prefix(".backup-")
+            .tempdir_in(dir)?;
+        let snapshot = staging.path().join(DB_FILE);
+
+        // VACUUM INTO writes a transactionally consistent copy while the server keeps running
+        sqlx::query("VACUUM INTO $1")
+            .bind(snapshot.to_string_lossy().to_string())
+            .execute(&self.db.pool)
+            .await?;
+
+        let manifest = BackupManifest {
+            format_version: FORMAT_VERSION,
+            app_version: APP_VERSION.to_string(),
+            schema_version: DBService::schema_version(),
+            created_at,
+        };
+        let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+        let archive_path = dir.join(&file_name);
+        let images_dir = self.images_dir.clone();
+        tokio::task::spawn_blocking(move || {
+            write_archive(&archive_path, &manifest_json, &snapshot, &images_dir)
+        })
+        .await
+        .map_err(io::Error::other)??;
+        drop(staging);
+
+        let size_bytes = tokio::fs::metadata(dir.join(&file_name)).await?.len();
+        tracing::info!("Created backup {} ({} bytes)", file_name, size_bytes);
+        Ok(BackupInfo {
+            file_name,
+            size_bytes,
+            manifest,
+        })
+    }
+
+    /// Snapshot the database and images into a new archive in the backups directory.
+    pub async fn create_backup(&self) -> Result<BackupInfo, BackupError> {
+        self.create_archive(&self.backups_dir, MANUAL_PREFIX).await
+    }
+
+    /// Directory scheduled backups are written to for `settings`.
+    pub fn scheduled_dir(&self, settings: &BackupScheduleConfig) -> PathBuf {
+        settings
+            .destination_dir
+            .as_deref()
+            .map(expand_tilde)
+            .unwrap_or_else(|| self.backups_dir.clone())
+    }
+
+    /// Creation time of the newest scheduled backup in the destination directory.
+    pub async fn latest_scheduled_backup(
+        &self,
+        settings: &BackupScheduleConfig,
+    ) -> Option<DateTime<Utc>> {
+        let dir = self.scheduled_dir(settings);
+        tokio::task::spawn_blocking(move || list_archives(&dir))
+            .await
+            .ok()?
+            .ok()?
+            .into_iter()
+            .find(|backup| backup.file_name.starts_with(SCHEDULED_PREFIX))
+            .map(|backup| backup.manifest.created_at)
+    }
+
+    /// Storage for the configured `s3_uri`, if any. Listing it up front surfaces bad
+    /// credentials or a missing bucket before a backup is taken.
+    pub async fn remote_storage(
+        settings: &BackupScheduleConfig,
+    ) -> Result<Option<BlobStorage>, BackupError> {
+        let Some(uri) = settings
+            .s3_uri
+            .as_deref()
+            .map(str::trim)
+            .filter(|uri| !uri.is_empty())
+        else {
+            return Ok(None);
+        };
+        let storage = BlobStorage::from_url(uri)?;
+        storage.list("").await?;
+        Ok(Some(storage))
+    }
+
+    /// Take a scheduled backup, copy it to S3 if configured and prune old backups.
+    /// The outcome is recorded in [`BackupService::status`].
+    pub async fn run_scheduled(
+        &self,
+        settings: &BackupScheduleConfig,
+    ) -> Result<BackupInfo, BackupError> {
+        let result = self.run_scheduled_inner(settings).await;
+        let mut status = self.status.write().await;
+        match &result {
+            Ok(backup) => {
+                status.last_success_at = Some(backup.manifest.created_at);
+                status.last_backup = Some(backup.clone());
+                status.last_error = None;
+            }
+            Err(e) => {
+                status.last_failure_at = Some(Utc::now());
+                status.last_error = Some(e.to_string());
+            }
+        }
+        result
+    }
+
+    async fn run_scheduled_inner(
+        &self,
+        settings: &BackupScheduleConfig,
+    ) -> Result<BackupInfo, BackupError> {
+        let remote = Self::remote_storage(settings).await?;
+        let dir = self.scheduled_dir(settings);
+        let backup = self.create_archive(&dir, SCHEDULED_PREFIX).await?;
+        let keep = settings.keep.max(1) as usize;
+
+        let pruned = tokio::task::spawn_blocking({
+            let dir = dir.clone();
+            move || prune_scheduled(&dir, keep)
+        })
+        .await
+        .map_err(io::Error::other)??;
+        if pruned > 0 {
+            tracing::info!("Pruned {} old scheduled backups", pruned);
+        }
+
+        if let Some(remote) = remote {
+            remote
+                .put_file(&backup.file_name, &dir.join(&backup.file_name))
+                .await?;
+            tracing::info!(
+                "Uploaded backup {} to {}",
+                backup.file_name,
+                remote.description()
+            );
+            prune_remote(&remote, keep).await?;
+        }
+        Ok(backup)
+    }
+
+    /// Record the scheduler's view of the schedule for status reporting.
+    pub async fn set_schedule(
+        &self,
+        enabled: bool,
+        last_success_at: Option<DateTime<Utc>>,
+        next_run_at: Option<DateTime<Utc>>,
+    ) {
+        let mut status = self.status.write().await;
+        status.schedule_enabled = enabled;
+        status.next_run_at = next_run_at;
+        status.last_success_at = status.last_success_at.or(last_success_at);
+    }
+
+    /// Backups in the backups directory, newest first.
+    pub async fn list_backups(&self) -> Result<Vec<BackupInfo>, BackupError> {
+        let dir = self.backups_dir.clone();
+        tokio::task::spawn_blocking(move || list_archives(&dir))
+            .await
+            .map_err(io::Error::other)?
+    }
+
+    /// Resolve a backup file name to its path, rejecting anything outside the backups directory.
+    pub fn backup_path(&self, file_name: &str) -> Result<PathBuf, BackupError> {
+        let valid = file_name.ends_with(ARCHIVE_SUFFIX)
+            && !file_name.starts_with('.')
+            && !file_name.contains(['/', '\\']);
+        let path = self.backups_dir.join(file_name);
+        if !valid || !path.is_file() {
+            return Err(BackupError::NotFound);
+        }
+        Ok(path)
+    }
+
+    pub async fn delete_backup(&self, file_name: &str) -> Result<(), BackupError> {
+        tokio::fs::remove_file(self.backup_path(file_name)?).await?;
+        Ok(())
+    }
+
+    /// A fresh path in the backups directory for receiving an uploaded archive.
+    pub async fn upload_path(&self) -> Result<PathBuf, BackupError> {
+        tokio::fs::create_dir_all(&self.backups_dir).await?;
+        Ok(self
+            .backups_dir
+            .join(format!(".upload-{}{ARCHIVE_SUFFIX}", Uuid::new_v4())))
+    }
+
+    /// Validate an archive and stage it to replace the database on the next start.
+    /// Images are restored immediately; existing files are left untouched.
+    pub async fn restore(&self, archive: &Path) -> Result<RestoreResult, BackupError> {
+        let db_path = self.db_path.clone();
+        let archive = archive.to_path_buf();
+        let backups_dir = self.backups_dir.clone();
+        let images_dir = self.images_dir.clone();
+
+        let result = tokio::task::spawn_blocking(move || {
+            stage_restore(&archive, &backups_dir, &db_path, &images_dir)
+        })
+        .await
+        .map_err(io::Error::other)??;
+
+        tracing::info!(
+            "Staged restore of backup from {} (schema {}); restart to apply",
+            result.manifest.created_at,
+            result.manifest.schema_version
+        );
+        Ok(result)
+    }
+}
+
+fn write_archive(
+    archive_path: &Path,
+    manifest_json: &[u8],
+    snapshot: &Path,
+    images_dir: &Path,
+) -> Result<(), BackupError> {
+    let mut partial = archive_path.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+
+    let encoder = GzEncoder::new(File::create(&partial)?, Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, MANIFEST_FILE, manifest_json)?;
+    builder.append_path_with_name(snapshot, DB_FILE)?;
+    if images_dir.is_dir() {
+        builder.append_dir_all(IMAGES_DIR, images_dir)?;
+    }
+    builder.into_inner()?.finish()?;
+
+    std::fs::rename(partial, archive_path)?;
+    Ok(())
+}
+
+fn read_manifest(archive: &Path) -> Result<BackupManifest, BackupError> {
+    let mut entries = tar::Archive::new(GzDecoder::new(File::open(archive)?));
+    for entry in entries.entries()? {
+        let mut entry = entry?;
+        if entry.path()?.as_ref() == Path::new(MANIFEST_FILE) {
+            let mut json = String::new();
+            entry.read_to_string(&mut json)?;
+            return Ok(serde_json::from_str(&json)?);
+        }
+    }
+    Err(BackupError::InvalidArchive(
+        "missing manifest.json".to_string(),
+    ))
+}
+
+fn list_archives(dir: &Path) -> Result<Vec<BackupInfo>, BackupError> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut backups = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if file_name.starts_with('.') || !file_name.ends_with(ARCHIVE_SUFFIX) {
+            continue;
+        }
+        match read_manifest(&entry.path()) {
+            Ok(manifest) => backups.push(BackupInfo {
+                file_name,
+                size_bytes: entry.metadata()?.len(),
+                manifest,
+            }),
+            Err(e) => tracing::warn!("Skipping unreadable backup {}: {}", file_name, e),
+        }
+    }
+    backups.sort_by(|a, b| b.manifest.created_at.cmp(&a.manifest.created_at));
+    Ok(backups)
+}
+
+/// Delete all but the newest `keep` scheduled backups in `dir`.
+fn prune_scheduled(dir: &Path, keep: usize) -> Result<usize, BackupError> {
+    let mut scheduled: Vec<_> = list_archives(dir)?
+        .into_iter()
+        .filter(|backup| backup.file_name.starts_with(SCHEDULED_PREFIX))
+        .collect();
+    let stale = scheduled.split_off(keep.min(scheduled.len()));
+    for backup in &stale {
+        std::fs::remove_file(dir.join(&backup.file_name))?;
+    }
+    Ok(stale.len())
+}
+
+/// Delete all but the newest `keep` scheduled backups in remote storage.
+async fn prune_remote(remote: &BlobStorage, keep: usize) -> Result<(), BackupError> {
+    // File names embed a sortable timestamp, so name order is age order
+    let mut scheduled: Vec<String> = remote
+        .list("")
+        .await?
+        .into_iter()
+        .map(|blob| blob.key)
+        .filter(|key| key.starts_with(SCHEDULED_PREFIX) && key.ends_with(ARCHIVE_SUFFIX))
+        .collect();
+    scheduled.sort_unstable_by(|a, b| b.cmp(a));
+    for key in scheduled.into_iter().skip(keep) {
+        remote.delete(&key).await?;
+    }
+    Ok(())
+}
+
+fn stage_restore(
+    archive: &Path,
+    backups_dir: &Path,
+    db_path: &Path,
+    images_dir: &Path,
+) -> Result<RestoreResult, BackupError> {
+    let manifest = read_manifest(archive)?;
+    if manifest.format_version != FORMAT_VERSION {
+        return Err(BackupError::InvalidArchive(format!(
+            "unsupported format version {}",
+            manifest.format_version
+        )));
+    }
+    let current = DBService::schema_version();
+    if manifest.schema_version > current {
+        return Err(BackupError::IncompatibleVersion {
+            backup: manifest.schema_version,
+            current,
+        });
+    }
+
+    std::fs::create_dir_all(backups_dir)?;
+    let staging = tempfile::Builder::new()
+        .prefix(".restore-")
+        .tempdir_in(backups_dir)?;
+    // `unpack` refuses entries that would escape the target directory
+    tar::Archive::new(GzDecoder::new(File::open(archive)?)).unpack(staging.path())?;
+
+    let snapshot = staging.path().join(DB_FILE);
+    if !snapshot.is_file() {
+        return Err(BackupError::InvalidArchive("missing db.sqlite".to_string()));
+    }
+    let staged_db = pending_restore_path(db_path);
+    if std::fs::rename(&snapshot, &staged_db).is_err() {
+        // Different filesystems; fall back to copying
+        std::fs::copy(&snapshot, &staged_db)?;
+    }
+
+    let mut images_restored = 0;
+    let restored_images = staging.path().join(IMAGES_DIR);
+    if restored_images.is_dir() {
+        std::fs::create_dir_all(images_dir)?;
+        for entry in std::fs::read_dir(restored_images)? {
+            let entry = entry?;
+            let target = images_dir.join(entry.file_name());
+            if entry.file_type()?.is_file() && !target.exists() {
+                std::fs::copy(entry.path(), target)?;
+                images_restored += 1;
+            }
+        }
+    }
+
+    Ok(RestoreResult {
+        manifest,
+        images_restored,
+        restart_required: true,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archive_round_trip_preserves_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot = dir.path().join("snapshot.sqlite");
+        std::fs::write(&snapshot, b"sqlite").unwrap();
+        let images = dir.path().join("images");
+        std::fs::create_dir_all(&images).unwrap();
+        std::fs::write(images.join("a.png"), b"png").unwrap();
+
+        let manifest = BackupManifest {
+            format_version: FORMAT_VERSION,
+            app_version: APP_VERSION.to_string(),
+            schema_version: 1,
+            created_at: Utc::now(),
+        };
+        let archive = dir.path().join("backup.tar.gz");
+        write_archive(
+            &archive,
+            &serde_json::to_vec(&manifest).unwrap(),
+            &snapshot,
+            &images,
+        )
+        .unwrap();
+
+        let listed = list_archives(dir.path()).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].manifest.schema_version, 1);
+
+        let restored_images = dir.path().join("restored-images");
+        let db_path = dir.path().join("db.sqlite");
+        let result = stage_restore(&archive, dir.path(), &db_path, &restored_images).unwrap();
+        assert_eq!(result.images_restored, 1);
+        assert!(pending_restore_path(&db_path).is_file());
+    }
+
+    #[test]
+    fn prunes_only_old_scheduled_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot = dir.path().join("snapshot.sqlite");
+        std::fs::write(&snapshot, b"sqlite").unwrap();
+        let names = [
+            "vibe-kanban-auto-20260101-000000.tar.gz",
+            "vibe-kanban-auto-20260102-000000.tar.gz",
+            "vibe-kanban-auto-20260103-000000.tar.gz",
+            "vibe-kanban-20260101-000000.tar.gz",
+        ];
+        for (i, name) in names.iter().enumerate() {
+            let manifest = BackupManifest {
+                format_version: FORMAT_VERSION,
+                app_version: APP_VERSION.to_string(),
+                schema_version: 1,
+                created_at: DateTime::from_timestamp(1_700_000_000 + i as i64, 0).unwrap(),
+            };
+            write_archive(
+                &dir.path().join(name),
+                &serde_json::to_vec(&manifest).unwrap(),
+                &snapshot,
+                &dir.path().join("missing"),
+            )
+            .unwrap();
+        }
+
+        assert_eq!(prune_scheduled(dir.path(), 2).unwrap(), 1);
+        assert!(!dir.path().join(names[0]).exists());
+        assert!(dir.path().join(names[2]).exists());
+        assert!(dir.path().join(names[3]).exists());
+    }
+
+    #[test]
+    fn rejects_backups_from_newer_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot = dir.path().join("snapshot.sqlite");
+        std::fs::write(&snapshot, b"sqlite").unwrap();
+        let manifest = BackupManifest {
+            format_version: FORMAT_VERSION,
+            app_version: APP_VERSION.to_string(),
+            schema_version: i64::MAX,
+            created_at: Utc::now(),
+        };
+        let archive = dir.path().join("backup.tar.gz");
+        write_archive(
+            &archive,
+            &serde_json::to_vec(&manifest).unwrap(),
+            &snapshot,
+            &dir.path().join("missing"),
+        )
+        .unwrap();
+
+        let err = stage_restore(
+            &archive,
+            dir.path(),
+            &dir.path().join("db.sqlite"),
+            &dir.path().join("images"),
+        )
+        .unwrap_err();
+        assert!(matches!(err, BackupError::IncompatibleVersion { .. }));
+    }
+}
@@ -0,0 +1,84 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use tokio::{sync::RwLock, time::interval};
+use tracing::{error, info};
+
+use crate::services::{backup::BackupService, config::Config};
+
+/// Service that takes scheduled backups according to the `backups` settings
+pub struct BackupSchedulerService {
+    backups: BackupService,
+    config: Arc<RwLock<Config>>,
+    poll_interval: Duration,
+}
+
+impl BackupSchedulerService {
+    pub async fn spawn(
+        backups: BackupService,
+        config: Arc<RwLock<Config>>,
+    ) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            backups,
+            config,
+            poll_interval: Duration::from_secs(15 * 60), // Check every 15 minutes
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting backup scheduler with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        // Survives restarts by looking at what is already in the destination
+        let mut last_success: Option<DateTime<Utc>> = None;
+        let mut seeded_from: Option<String> = None;
+        // Remote destination last checked, so a bad `s3_uri` is reported at startup and after
+        // each change rather than only when a backup is due
+        let mut checked_remote: Option<Option<String>> = None;
+
+        loop {
+            interval.tick().await;
+            let settings = self.config.read().await.backups.clone();
+            if !settings.enabled {
+                self.backups.set_schedule(false, None, None).await;
+                continue;
+            }
+
+            if checked_remote.as_ref() != Some(&settings.s3_uri) {
+                if let Err(e) = BackupService::remote_storage(&settings).await {
+                    error!("Backup destination is unusable: {}", e);
+                }
+                checked_remote = Some(settings.s3_uri.clone());
+            }
+
+            if seeded_from.as_ref() != settings.destination_dir.as_ref() || last_success.is_none() {
+                last_success = self.backups.latest_scheduled_backup(&settings).await;
+                seeded_from = settings.destination_dir.clone();
+            }
+
+            let period = settings.frequency.interval();
+            let now = Utc::now();
+            if last_success.is_none_or(|last| now - last >= period) {
+                match self.backups.run_scheduled(&settings).await {
+                    Ok(backup) => {
+                        info!("Scheduled backup {} completed", backup.file_name);
+                        last_success = Some(backup.manifest.created_at);
+                    }
+                    Err(e) => error!("Scheduled backup failed: {}", e),
+                }
+            }
+
+            // Failed runs are retried on the next tick
+            let next_run_at = last_success.map(|last| last + period).unwrap_or(now);
+            self.backups
+                .set_schedule(true, last_success, Some(next_run_at))
+                .await;
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! Watched attempts are rebased onto their base branches whenever new commits land there. A
+//! rebase that hits conflicts is left in progress for the usual conflict flow and announced;
+//! either way the agent can be asked to reconcile its work with the new commits.
+
+use std::{path::Path, time::Duration};
+
+use db::{
+    DBService,
+    models::{
+        execution_process::ExecutionProcess,
+        notification::{CreateNotification, NotificationKind},
+        workspace::Workspace,
+        workspace_base_watch::WorkspaceBaseWatch,
+        workspace_repo::WorkspaceRepo,
+    },
+};
+use tokio::{task::JoinHandle, time::interval};
+
+use crate::services::{
+    container::{ContainerError, ContainerService},
+    git::{GitService, GitServiceError},
+};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// What happened to one repository of a watched attempt after its base moved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaseUpdate {
+    pub repo_name: String,
+    pub base_branch: String,
+    /// Commits that landed on the base since it was last handled
+    pub new_commits: usize,
+    /// Files left conflicted by the rebase, empty when it went through
+    pub conflicts: Vec<String>,
+}
+
+impl BaseUpdate {
+    fn conflicted(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+fn describe(update: &BaseUpdate) -> String {
+    let commits = if update.new_commits == 1 {
+        "1 new commit".to_string()
+    } else {
+        format!("{} new commits", update.new_commits)
+    };
+    format!(
+        "`{}` ({commits} on `{}`)",
+        update.repo_name, update.base_branch
+    )
+}
+
+/// Prompt asking the agent to reconcile its work with what landed on the base.
+pub fn follow_up_prompt(updates: &[BaseUpdate]) -> String {
+    let mut sections = Vec::new();
+    let rebased: Vec<String> = updates
+        .iter()
+        .filter(|update| !update.conflicted())
+        .map(describe)
+        .collect();
+    if !rebased.is_empty() {
+        sections.push(format!(
+            "Your branch was rebased onto new commits of its base branch in {}. Review what \
+changed upstream, make sure your work still fits with it and still builds and passes its tests, \
+and fix anything that no longer does.",
+            rebased.join(", ")
+        ));
+    }
+    for update in updates.iter().filter(|update| update.conflicted()) {
+        sections.push(format!(
+            "Rebasing {} onto its base stopped on conflicts in: {}. Resolve them keeping both \
+your changes and the upstream ones, then run `git rebase --continue` until the rebase is done.",
+            describe(update),
+            update.conflicts.join(", ")
+        ));
+    }
+    sections.join("\n\n")
+}
+
+/// Notification body listing the conflicts, `None` when every rebase went through.
+pub fn conflict_message(task_title: &str, updates: &[BaseUpdate]) -> Option<String> {
+    let lines: Vec<String> = updates
+        .iter()
+        .filter(|update| update.conflicted())
+        .map(|update| format!("{}: {}", describe(update), update.conflicts.join(", ")))
+        .collect();
+    (!lines.is_empty()).then(|| {
+        format!(
+            "⚠️ Rebasing '{task_title}' onto its base hit conflicts\n{}",
+            lines.join("\n")
+        )
+    })
+}
+
+/// Checks watched attempts every few minutes and rebases those whose base branch moved.
+pub struct BaseWatchMonitor<C> {
+    db: DBService,
+    git: GitService,
+    container: C,
+}
+
+impl<C: ContainerService + Send + Sync + 'static> BaseWatchMonitor<C> {
+    pub fn new(db: DBService, git: GitService, container: C) -> Self {
+        Self { db, git, container }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = interval(CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.check().await {
+                    tracing::error!("Failed to check watched base branches: {}", e);
+                }
+            }
+        })
+    }
+
+    async fn check(&self) -> Result<(), sqlx::Error> {
+        for watch in WorkspaceBaseWatch::find_all(&self.db.pool).await? {
+            if let Err(e) = self.check_watch(&watch).await {
+                tracing::warn!(
+                    "Failed to follow the base branch of workspace {}: {}",
+                    watch.workspace_id,
+                    e
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn check_watch(&self, watch: &WorkspaceBaseWatch) -> Result<(), ContainerError> {
+        let pool = &self.db.pool;
+        let Some(workspace) = Workspace::find_by_id(pool, watch.workspace_id).await? else {
+            return Ok(());
+        };
+        // Never rebase under a running agent; the next check picks the commits up
+        if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+            .await?
+        {
+            return Ok(());
+        }
+        let Some(container_ref) = workspace.container_ref.as_deref().map(Path::new) else {
+            return Ok(());
+        };
+        if !container_ref.exists() {
+            return Ok(());
+        }
+
+        let mut seen = watch.seen_base_oids.0.clone();
+        let mut updates = Vec::new();
+        for repo in
+            WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, workspace.id).await?
+        {
+            let base_oid = match self
+                .git
+                .fetch_branch_oid(&repo.repo.path, &repo.target_branch)
+            {
+                Ok(oid) => oid,
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to read base branch {} of {}: {}",
+                        repo.target_branch,
+                        repo.repo.name,
+                        e
+                    );
+                    continue;
+                }
+            };
+            // The first check only records where the base is
+            let Some(previous) = seen.get(&repo.repo.id).cloned() else {
+                seen.insert(repo.repo.id, base_oid);
+                continue;
+            };
+            if previous == base_oid {
+                continue;
+            }
+
+            let worktree = container_ref.join(&repo.repo.name);
+            let conflicts = match self.git.rebase_branch(
+                &repo.repo.path,
+                &worktree,
+                &repo.target_branch,
+                &repo.target_branch,
+                &workspace.branch,
+            ) {
+                Ok(_) => Vec::new(),
+                Err(GitServiceError::MergeConflicts(_)) => {
+                    self.git.get_conflicted_files(&worktree).unwrap_or_default()
+                }
+                // Dirty worktrees and rebases already in progress are retried on the next check
+                Err(e) => {
+                    tracing::info!(
+                        "Not rebasing {} of workspace {} yet: {}",
+                        repo.repo.name,
+                        workspace.id,
+                        e
+                    );
+                    continue;
+                }
+            };
+            let new_commits = self
+                .git
+                .ahead_behind_commits_by_oid(&repo.repo.path, &base_oid, &previous)
+                .map(|(ahead, _)| ahead)
+                .unwrap_or_default();
+            seen.insert(repo.repo.id, base_oid);
+            updates.push(BaseUpdate {
+                repo_name: repo.repo.name.clone(),
+                base_branch: repo.target_branch.clone(),
+                new_commits,
+                conflicts,
+            });
+        }
+        WorkspaceBaseWatch::set_seen_base_oids(pool, workspace.id, &seen).await?;
+        if updates.is_empty() {
+            return Ok(());
+        }
+        tracing::info!(
+            "Followed base branch updates of workspace {}: {:?}",
+            workspace.id,
+            updates
+        );
+
+        let Some(task) = workspace.parent_task(pool).await? else {
+            return Ok(());
+        };
+        if let Some(message) = conflict_message(&task.title, &updates) {
+            self.container
+                .notification_service()
+                .notify(CreateNotification {
+                    kind: NotificationKind::RebaseConflict,
+                    title: format!("Rebase Conflict: {}", task.title),
+                    message,
+                    project_id: Some(task.project_id),
+                    task_id: Some(task.id),
+                })
+                .await;
+        }
+        if watch.follow_up {
+            self.container
+                .start_prompt_follow_up(&workspace, follow_up_prompt(&updates))
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(repo_name: &str, new_commits: usize, conflicts: &[&str]) -> BaseUpdate {
+        BaseUpdate {
+            repo_name: repo_name.to_string(),
+            base_branch: "main".to_string(),
+            new_commits,
+            conflicts: conflicts.iter().map(|file| file.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn follow_up_covers_rebased_and_conflicted_repos() {
+        let prompt = follow_up_prompt(&[
+            update("api", 3, &[]),
+            update("web", 1, &["src/app.ts", "package.json"]),
+        ]);
+        assert!(prompt.starts_with(
+            "Your branch was rebased onto new commits of its base branch in `api` (3 new \
+commits on `main`)."
+        ));
+        assert!(prompt.contains(
+            "Rebasing `web` (1 new commit on `main`) onto its base stopped on conflicts in: \
+src/app.ts, package.json."
+        ));
+    }
+
+    #[test]
+    fn conflict_message_only_for_conflicts() {
+        assert!(conflict_message("Fix login", &[update("api", 2, &[])]).is_none());
+        assert_eq!(
+            conflict_message("Fix login", &[update("api", 2, &["src/lib.rs"])]).unwrap(),
+            "⚠️ Rebasing 'Fix login' onto its base hit conflicts\n`api` (2 new commits on \
+`main`): src/lib.rs"
+        );
+    }
+}
@@ -0,0 +1,264 @@
+//! Pluggable storage for uploaded files.
+//!
+//! Blobs live under the local cache directory by default. Setting `VK_BLOB_STORAGE_URL` to an
+//! `s3://bucket/prefix` URL stores them in S3 or an S3-compatible service instead; credentials,
+//! region and a custom endpoint (e.g. MinIO) are read from the standard `AWS_*` variables.
+
+use std::{path::Path, sync::Arc};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, TryStreamExt, stream::BoxStream};
+use object_store::{
+    GetResult, ObjectStore, PutPayload, WriteMultipart, aws::AmazonS3Builder,
+    local::LocalFileSystem, path::Path as ObjectPath,
+};
+use thiserror::Error;
+use tokio::io::AsyncReadExt;
+use url::Url;
+
+#[derive(Debug, Error)]
+pub enum BlobStorageError {
+    #[error(transparent)]
+    Store(#[from] object_store::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Invalid blob storage URL: {0}")]
+    InvalidUrl(String),
+    #[error("Blob not found: {0}")]
+    NotFound(String),
+}
+
+/// A blob opened for streaming.
+pub struct BlobReader {
+    pub size: u64,
+    pub stream: BoxStream<'static, Result<Bytes, std::io::Error>>,
+}
+
+/// A stored blob, as returned by [`BlobStorage::list`].
+#[derive(Debug, Clone)]
+pub struct BlobMeta {
+    /// Key relative to the storage root, as passed to [`BlobStorage::put`].
+    pub key: String,
+    pub size: u64,
+    pub last_modified: DateTime<Utc>,
+}
+
+/// Chunk size used when streaming a file into storage.
+const UPLOAD_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+#[derive(Clone)]
+pub struct BlobStorage {
+    store: Arc<dyn ObjectStore>,
+    prefix: ObjectPath,
+    description: String,
+}
+
+impl BlobStorage {
+    /// Storage selected by `VK_BLOB_STORAGE_URL`, defaulting to the local cache directory.
+    pub fn from_env() -> Result<Self, BlobStorageError> {
+        match std::env::var("VK_BLOB_STORAGE_URL") {
+            Ok(url) if !url.trim().is_empty() => Self::from_url(url.trim()),
+            _ => Self::local(&utils::cache_dir()),
+        }
+    }
+
+    pub fn local(root: &Path) -> Result<Self, BlobStorageError> {
+        std::fs::create_dir_all(root)?;
+        Ok(Self {
+            store: Arc::new(LocalFileSystem::new_with_prefix(root)?),
+            prefix: ObjectPath::default(),
+            description: root.display().to_string(),
+        })
+    }
+
+    pub fn from_url(raw: &str) -> Result<Self, BlobStorageError> {
+        let url = Url::parse(raw).map_err(|e| BlobStorageError::InvalidUrl(e.to_string()))?;
+        match url.scheme() {
+            "s3" => {
+                let bucket = url
+                    .host_str()
+                    .ok_or_else(|| BlobStorageError::InvalidUrl("missing bucket".to_string()))?;
+                let store = AmazonS3Builder::from_env()
+                    .with_bucket_name(bucket)
+                    .build()?;
+                Ok(Self {
+                    store: Arc::new(store),
+                    prefix: ObjectPath::from(url.path().trim_matches('/')),
+                    description: raw.to_string(),
+                })
+            }
+            "file" => {
+                let path = url
+                    .to_file_path()
+                    .map_err(|_| BlobStorageError::InvalidUrl(raw.to_string()))?;
+                Self::local(&path)
+            }
+            other => Err(BlobStorageError::InvalidUrl(format!(
+                "unsupported scheme `{other}`"
+            ))),
+        }
+    }
+
+    /// Where blobs are stored, for logging.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn path(&self, key: &str) -> ObjectPath {
+        key.split('/')
+            .filter(|part| !part.is_empty())
+            .fold(self.prefix.clone(), |path, part| path.child(part))
+    }
+
+    pub async fn put(&self, key: &str, data: impl Into<Bytes>) -> Result<(), BlobStorageError> {
+        self.store
+            .put(&self.path(key), PutPayload::from(data.into()))
+            .await?;
+        Ok(())
+    }
+
+    /// Stream a file into storage without reading it into memory; large files are sent as a
+    /// multipart upload.
+    pub async fn put_file(&self, key: &str, file: &Path) -> Result<(), BlobStorageError> {
+        let mut source = tokio::fs::File::open(file).await?;
+        let upload = self.store.put_multipart(&self.path(key)).await?;
+        let mut writer = WriteMultipart::new_with_chunk_size(upload, UPLOAD_CHUNK_SIZE);
+        let mut buf = vec![0u8; UPLOAD_CHUNK_SIZE];
+        loop {
+            let read = match source.read(&mut buf).await {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) => {
+                    let _ = writer.abort().await;
+                    return Err(e.into());
+                }
+            };
+            if let Err(e) = writer.wait_for_capacity(4).await {
+                let _ = writer.abort().await;
+                return Err(e.into());
+            }
+            writer.write(&buf[..read]);
+        }
+        writer.finish().await?;
+        Ok(())
+    }
+
+    async fn fetch(&self, key: &str) -> Result<GetResult, BlobStorageError> {
+        self.store.get(&self.path(key)).await.map_err(|e| match e {
+            object_store::Error::NotFound { .. } => BlobStorageError::NotFound(key.to_string()),
+            e => e.into(),
+        })
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Bytes, BlobStorageError> {
+        Ok(self.fetch(key).await?.bytes().await?)
+    }
+
+    /// Open a blob for streaming, e.g. as an HTTP response body.
+    pub async fn open(&self, key: &str) -> Result<BlobReader, BlobStorageError> {
+        let result = self.fetch(key).await?;
+        Ok(BlobReader {
+            size: result.meta.size as u64,
+            stream: result.into_stream().map_err(std::io::Error::other).boxed(),
+        })
+    }
+
+    pub async fn exists(&self, key: &str) -> Result<bool, BlobStorageError> {
+        match self.store.head(&self.path(key)).await {
+            Ok(_) => Ok(true),
+            Err(object_store::Error::NotFound { .. }) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Every blob whose key starts with the `prefix` directory, in no particular order.
+    pub async fn list(&self, prefix: &str) -> Result<Vec<BlobMeta>, BlobStorageError> {
+        let metas: Vec<_> = self
+            .store
+            .list(Some(&self.path(prefix)))
+            .try_collect()
+            .await?;
+        Ok(metas
+            .into_iter()
+            .filter_map(|meta| {
+                let key = meta
+                    .location
+                    .prefix_match(&self.prefix)?
+                    .map(|part| part.as_ref().to_string())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                Some(BlobMeta {
+                    key,
+                    size: meta.size as u64,
+                    last_modified: meta.last_modified,
+                })
+            })
+            .collect())
+    }
+
+    /// Delete a blob; missing blobs are not an error.
+    pub async fn delete(&self, key: &str) -> Result<(), BlobStorageError> {
+        match self.store.delete(&self.path(key)).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn local_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = BlobStorage::local(dir.path()).unwrap();
+
+        storage.put("images/a.png", b"png".to_vec()).await.unwrap();
+        assert!(dir.path().join("images/a.png").is_file());
+        assert_eq!(&storage.get("images/a.png").await.unwrap()[..], b"png");
+        assert!(storage.exists("images/a.png").await.unwrap());
+
+        storage.delete("images/a.png").await.unwrap();
+        storage.delete("images/a.png").await.unwrap();
+        assert!(!storage.exists("images/a.png").await.unwrap());
+        assert!(matches!(
+            storage.open("images/a.png").await,
+            Err(BlobStorageError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn lists_and_uploads_files_under_a_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = BlobStorage::local(&dir.path().join("store")).unwrap();
+        let source = dir.path().join("source.bin");
+        std::fs::write(&source, vec![7u8; 1024]).unwrap();
+
+        storage.put_file("backups/a.bin", &source).await.unwrap();
+        storage.put("backups/b.bin", b"b".to_vec()).await.unwrap();
+        storage.put("images/c.png", b"c".to_vec()).await.unwrap();
+
+        assert_eq!(
+            &storage.get("backups/a.bin").await.unwrap()[..],
+            &[7u8; 1024]
+        );
+        let mut listed: Vec<_> = storage
+            .list("backups")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|meta| (meta.key, meta.size))
+            .collect();
+        listed.sort();
+        assert_eq!(
+            listed,
+            vec![
+                ("backups/a.bin".to_string(), 1024),
+                ("backups/b.bin".to_string(), 1)
+            ]
+        );
+        assert!(storage.list("missing").await.unwrap().is_empty());
+    }
+}
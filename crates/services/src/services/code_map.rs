@@ -0,0 +1,589 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use ignore::WalkBuilder;
+use moka::future::Cache;
+use thiserror::Error;
+use tree_sitter::{Language, Node, Parser};
+use uuid::Uuid;
+
+use super::{
+    code_search::ChunkIndex,
+    config::EmbeddingProvider,
+    git::{GitService, GitServiceError},
+};
+
+/// Files listed per repository before the walk stops
+const MAX_FILES: usize = 20_000;
+/// Larger source files are listed without parsing them
+const MAX_PARSED_FILE_BYTES: u64 = 256 * 1024;
+/// Symbols shown per file in the prompt
+const MAX_PROMPT_SYMBOLS_PER_FILE: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Function,
+    Method,
+    Struct,
+    Enum,
+    Trait,
+    Interface,
+    Class,
+    Type,
+    Constant,
+    Module,
+}
+
+impl SymbolKind {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            SymbolKind::Function | SymbolKind::Method => "fn",
+            SymbolKind::Struct => "struct",
+            SymbolKind::Enum => "enum",
+            SymbolKind::Trait => "trait",
+            SymbolKind::Interface => "interface",
+            SymbolKind::Class => "class",
+            SymbolKind::Type => "type",
+            SymbolKind::Constant => "const",
+            SymbolKind::Module => "mod",
+        }
+    }
+}
+
+/// A public item defined in a source file. Lines are 1-based and inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct CodeMapFile {
+    /// Relative to the repository root, with `/` separators
+    pub path: String,
+    pub symbols: Vec<CodeSymbol>,
+}
+
+/// Files of a repository at a commit and the public symbols each one defines.
+#[derive(Debug)]
+pub struct CodeMap {
+    /// Checkout the map was built from
+    pub root: PathBuf,
+    pub head_sha: String,
+    pub files: Vec<CodeMapFile>,
+}
+
+#[derive(Debug, Error)]
+pub enum CodeMapError {
+    #[error(transparent)]
+    Git(#[from] GitServiceError),
+    #[error("Indexing task failed: {0}")]
+    Join(#[from] tokio::task::JoinError),
+}
+
+/// Builds and caches code maps per repository, keyed by repo id.
+#[derive(Clone)]
+pub struct CodeMapService {
+    cache: Cache<Uuid, Arc<CodeMap>>,
+    chunk_indexes: Cache<(Uuid, EmbeddingProvider), Arc<ChunkIndex>>,
+    git: GitService,
+}
+
+impl CodeMapService {
+    pub fn new(git: GitService) -> Self {
+        let cache = Cache::builder()
+            .max_capacity(50)
+            .time_to_live(Duration::from_secs(24 * 3600))
+            .build();
+        let chunk_indexes = Cache::builder()
+            .max_capacity(50)
+            .time_to_live(Duration::from_secs(24 * 3600))
+            .build();
+        Self {
+            cache,
+            chunk_indexes,
+            git,
+        }
+    }
+
+    /// The latest map of a repository, which may come from an attempt's worktree. Builds one from
+    /// `path` when nothing is cached or the checkout it came from is gone.
+    pub async fn get_or_build(
+        &self,
+        repo_id: Uuid,
+        path: &Path,
+    ) -> Result<Arc<CodeMap>, CodeMapError> {
+        if let Some(map) = self.cache.get(&repo_id).await
+            && map.root.exists()
+        {
+            return Ok(map);
+        }
+        self.refresh(repo_id, path).await
+    }
+
+    pub(crate) fn chunk_indexes(&self) -> &Cache<(Uuid, EmbeddingProvider), Arc<ChunkIndex>> {
+        &self.chunk_indexes
+    }
+
+    /// The map of the repository checked out at `path`, rebuilt when HEAD has moved since the
+    /// last build. Uncommitted changes are picked up only on a rebuild.
+    pub async fn refresh(&self, repo_id: Uuid, path: &Path) -> Result<Arc<CodeMap>, CodeMapError> {
+        let head_sha = self.git.get_head_info(path)?.oid;
+        if let Some(map) = self.cache.get(&repo_id).await
+            && map.head_sha == head_sha
+        {
+            return Ok(map);
+        }
+
+        let root = path.to_path_buf();
+        let files = {
+            let root = root.clone();
+            tokio::task::spawn_blocking(move || index_repo(&root)).await?
+        };
+        let map = Arc::new(CodeMap {
+            root,
+            head_sha,
+            files,
+        });
+        self.cache.insert(repo_id, map.clone()).await;
+        Ok(map)
+    }
+}
+
+fn index_repo(root: &Path) -> Vec<CodeMapFile> {
+    let mut parser = Parser::new();
+    let mut files = Vec::new();
+    for entry in WalkBuilder::new(root).build().flatten() {
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let symbols = SourceLanguage::from_path(entry.path())
+            .filter(|_| {
+                entry
+                    .metadata()
+                    .is_ok_and(|m| m.len() <= MAX_PARSED_FILE_BYTES)
+            })
+            .and_then(|language| {
+                let source = std::fs::read_to_string(entry.path()).ok()?;
+                Some(extract_symbols(&mut parser, language, &source))
+            })
+            .unwrap_or_default();
+        files.push(CodeMapFile {
+            path: relative.to_string_lossy().replace('\\', "/"),
+            symbols,
+        });
+        if files.len() >= MAX_FILES {
+            break;
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SourceLanguage {
+    Rust,
+    TypeScript,
+    Tsx,
+    JavaScript,
+    Python,
+    Go,
+}
+
+impl SourceLanguage {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "rs" => Some(Self::Rust),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "py" => Some(Self::Python),
+            "go" => Some(Self::Go),
+            _ => None,
+        }
+    }
+
+    fn grammar(self) -> Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+        }
+    }
+}
+
+/// Public symbols of a source file: `pub` items in Rust, exports in TypeScript and JavaScript,
+/// names without a leading underscore in Python and capitalized names in Go.
+fn extract_symbols(parser: &mut Parser, language: SourceLanguage, source: &str) -> Vec<CodeSymbol> {
+    if parser.set_language(&language.grammar()).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return Vec::new();
+    };
+
+    let src = source.as_bytes();
+    let root = tree.root_node();
+    let mut symbols = Vec::new();
+    match language {
+        SourceLanguage::Rust => rust_symbols(root, src, None, &mut symbols),
+        SourceLanguage::TypeScript | SourceLanguage::Tsx | SourceLanguage::JavaScript => {
+            js_symbols(root, src, &mut symbols)
+        }
+        SourceLanguage::Python => python_symbols(root, src, None, &mut symbols),
+        SourceLanguage::Go => go_symbols(root, src, &mut symbols),
+    }
+    symbols
+}
+
+fn symbol(node: Node, name: String, kind: SymbolKind) -> CodeSymbol {
+    CodeSymbol {
+        name,
+        kind,
+        start_line: node.start_position().row + 1,
+        end_line: node.end_position().row + 1,
+    }
+}
+
+fn field_text<'a>(node: Node, field: &str, src: &'a [u8]) -> Option<&'a str> {
+    node.child_by_field_name(field)?.utf8_text(src).ok()
+}
+
+fn qualified(owner: Option<&str>, separator: &str, name: &str) -> String {
+    match owner {
+        Some(owner) => format!("{owner}{separator}{name}"),
+        None => name.to_string(),
+    }
+}
+
+fn rust_symbols(parent: Node, src: &[u8], owner: Option<&str>, out: &mut Vec<CodeSymbol>) {
+    let mut cursor = parent.walk();
+    for node in parent.named_children(&mut cursor) {
+        let mut children = node.walk();
+        let public = node
+            .named_children(&mut children)
+            .any(|child| child.kind() == "visibility_modifier");
+
+        let kind = match node.kind() {
+            // Methods of trait impls are covered by the trait
+            "impl_item" if node.child_by_field_name("trait").is_none() => {
+                if let (Some(ty), Some(body)) = (
+                    field_text(node, "type", src),
+                    node.child_by_field_name("body"),
+                ) {
+                    let ty = ty.split('<').next().unwrap_or(ty).trim();
+                    rust_symbols(body, src, Some(ty), out);
+                }
+                continue;
+            }
+            "mod_item" if public => {
+                if let Some(body) = node.child_by_field_name("body") {
+                    rust_symbols(body, src, None, out);
+                }
+                SymbolKind::Module
+            }
+            _ if !public => continue,
+            "function_item" if owner.is_some() => SymbolKind::Method,
+            "function_item" => SymbolKind::Function,
+            "struct_item" | "union_item" => SymbolKind::Struct,
+            "enum_item" => SymbolKind::Enum,
+            "trait_item" => SymbolKind::Trait,
+            "type_item" => SymbolKind::Type,
+            "const_item" | "static_item" => SymbolKind::Constant,
+            _ => continue,
+        };
+        if let Some(name) = field_text(node, "name", src) {
+            out.push(symbol(node, qualified(owner, "::", name), kind));
+        }
+    }
+}
+
+fn js_symbols(root: Node, src: &[u8], out: &mut Vec<CodeSymbol>) {
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        if node.kind() != "export_statement" {
+            continue;
+        }
+        let Some(declaration) = node.child_by_field_name("declaration") else {
+            continue;
+        };
+        let kind = match declaration.kind() {
+            "function_declaration" | "generator_function_declaration" | "function_signature" => {
+                SymbolKind::Function
+            }
+            "class_declaration" | "abstract_class_declaration" => SymbolKind::Class,
+            "interface_declaration" => SymbolKind::Interface,
+            "type_alias_declaration" => SymbolKind::Type,
+            "enum_declaration" => SymbolKind::Enum,
+            "internal_module" => SymbolKind::Module,
+            "lexical_declaration" | "variable_declaration" => {
+                let mut declarators = declaration.walk();
+                for declarator in declaration.named_children(&mut declarators) {
+                    if declarator.kind() != "variable_declarator" {
+                        continue;
+                    }
+                    let Some(name) = declarator
+                        .child_by_field_name("name")
+                        .filter(|name| name.kind() == "identifier")
+                        .and_then(|name| name.utf8_text(src).ok())
+                    else {
+                        continue;
+                    };
+                    let kind = match declarator.child_by_field_name("value").map(|v| v.kind()) {
+                        Some("arrow_function" | "function_expression" | "function") => {
+                            SymbolKind::Function
+                        }
+                        _ => SymbolKind::Constant,
+                    };
+                    out.push(symbol(declaration, name.to_string(), kind));
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        if let Some(name) = field_text(declaration, "name", src) {
+            out.push(symbol(declaration, name.to_string(), kind));
+        }
+    }
+}
+
+fn python_symbols(parent: Node, src: &[u8], owner: Option<&str>, out: &mut Vec<CodeSymbol>) {
+    let mut cursor = parent.walk();
+    for node in parent.named_children(&mut cursor) {
+        // Report decorated definitions with their decorators
+        let definition = match node.kind() {
+            "decorated_definition" => match node.child_by_field_name("definition") {
+                Some(definition) => definition,
+                None => continue,
+            },
+            _ => node,
+        };
+        let Some(name) = field_text(definition, "name", src).filter(|n| !n.starts_with('_')) else {
+            continue;
+        };
+        match definition.kind() {
+            "function_definition" => {
+                let kind = match owner {
+                    Some(_) => SymbolKind::Method,
+                    None => SymbolKind::Function,
+                };
+                out.push(symbol(node, qualified(owner, ".", name), kind));
+            }
+            "class_definition" if owner.is_none() => {
+                out.push(symbol(node, name.to_string(), SymbolKind::Class));
+                if let Some(body) = definition.child_by_field_name("body") {
+                    python_symbols(body, src, Some(name), out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn go_symbols(root: Node, src: &[u8], out: &mut Vec<CodeSymbol>) {
+    let exported = |name: &str| name.starts_with(|c: char| c.is_uppercase());
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        match node.kind() {
+            "function_declaration" => {
+                if let Some(name) = field_text(node, "name", src).filter(|n| exported(n)) {
+                    out.push(symbol(node, name.to_string(), SymbolKind::Function));
+                }
+            }
+            "method_declaration" => {
+                let Some(name) = field_text(node, "name", src).filter(|n| exported(n)) else {
+                    continue;
+                };
+                let receiver = node
+                    .child_by_field_name("receiver")
+                    .and_then(|receiver| receiver.named_child(0))
+                    .and_then(|parameter| field_text(parameter, "type", src))
+                    .map(|ty| ty.trim_start_matches('*'));
+                out.push(symbol(
+                    node,
+                    qualified(receiver, ".", name),
+                    SymbolKind::Method,
+                ));
+            }
+            "type_declaration" | "const_declaration" => {
+                let mut specs = node.walk();
+                for spec in node.named_children(&mut specs) {
+                    let Some(name) = field_text(spec, "name", src).filter(|n| exported(n)) else {
+                        continue;
+                    };
+                    let kind = match (
+                        spec.kind(),
+                        spec.child_by_field_name("type").map(|t| t.kind()),
+                    ) {
+                        ("const_spec", _) => SymbolKind::Constant,
+                        (_, Some("struct_type")) => SymbolKind::Struct,
+                        (_, Some("interface_type")) => SymbolKind::Interface,
+                        _ => SymbolKind::Type,
+                    };
+                    out.push(symbol(spec, name.to_string(), kind));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Render the maps of a workspace's repositories for an agent's prompt, within `max_bytes`.
+/// Files that define public symbols come first, then the remaining file paths, so trimming drops
+/// the least informative lines.
+pub fn render_for_prompt(maps: &[(String, Arc<CodeMap>)], max_bytes: usize) -> Option<String> {
+    let header = "Repository map (files and their public symbols at the start of this attempt; \
+                  may be incomplete):";
+    let mut out = header.to_string();
+    let mut omitted = 0;
+
+    for (repo_name, map) in maps {
+        let heading = format!("\n\n## {repo_name}");
+        if out.len() + heading.len() > max_bytes {
+            omitted += map.files.len();
+            continue;
+        }
+        out.push_str(&heading);
+
+        let (with_symbols, without_symbols): (Vec<_>, Vec<_>) =
+            map.files.iter().partition(|f| !f.symbols.is_empty());
+        for file in with_symbols.into_iter().chain(without_symbols) {
+            let line = format!("\n{}", render_file(file));
+            if out.len() + line.len() > max_bytes {
+                omitted += 1;
+                continue;
+            }
+            out.push_str(&line);
+        }
+    }
+
+    if out.len() == header.len() {
+        return None;
+    }
+    if omitted > 0 {
+        out.push_str(&format!("\n({omitted} more files not shown)"));
+    }
+    Some(out)
+}
+
+fn render_file(file: &CodeMapFile) -> String {
+    if file.symbols.is_empty() {
+        return file.path.clone();
+    }
+    let mut symbols = file
+        .symbols
+        .iter()
+        .take(MAX_PROMPT_SYMBOLS_PER_FILE)
+        .map(|s| format!("{} {}", s.kind.label(), s.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if file.symbols.len() > MAX_PROMPT_SYMBOLS_PER_FILE {
+        symbols.push_str(&format!(
+            ", +{} more",
+            file.symbols.len() - MAX_PROMPT_SYMBOLS_PER_FILE
+        ));
+    }
+    format!("{}: {symbols}", file.path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(language: SourceLanguage, source: &str) -> Vec<(String, SymbolKind)> {
+        extract_symbols(&mut Parser::new(), language, source)
+            .into_iter()
+            .map(|s| (s.name, s.kind))
+            .collect()
+    }
+
+    #[test]
+    fn extracts_public_rust_items() {
+        let source = r#"
+pub struct Task;
+struct Hidden;
+impl Task {
+    pub fn new() -> Self { Task }
+    fn helper(&self) {}
+}
+impl Default for Task {
+    fn default() -> Self { Task }
+}
+pub mod models {
+    pub enum Status { Todo }
+}
+"#;
+        assert_eq!(
+            names(SourceLanguage::Rust, source),
+            vec![
+                ("Task".to_string(), SymbolKind::Struct),
+                ("Task::new".to_string(), SymbolKind::Method),
+                ("Status".to_string(), SymbolKind::Enum),
+                ("models".to_string(), SymbolKind::Module),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_typescript_exports() {
+        let source = r#"
+export interface Props { id: string }
+export const useTasks = () => [];
+export const LIMIT = 10;
+function internal() {}
+export function TaskCard(props: Props) { return null; }
+"#;
+        assert_eq!(
+            names(SourceLanguage::Tsx, source),
+            vec![
+                ("Props".to_string(), SymbolKind::Interface),
+                ("useTasks".to_string(), SymbolKind::Function),
+                ("LIMIT".to_string(), SymbolKind::Constant),
+                ("TaskCard".to_string(), SymbolKind::Function),
+            ]
+        );
+    }
+
+    #[test]
+    fn prompt_map_lists_files_with_symbols_first_and_trims() {
+        let map = Arc::new(CodeMap {
+            root: PathBuf::from("/repo"),
+            head_sha: "abc".to_string(),
+            files: vec![
+                CodeMapFile {
+                    path: "README.md".to_string(),
+                    symbols: Vec::new(),
+                },
+                CodeMapFile {
+                    path: "src/lib.rs".to_string(),
+                    symbols: vec![CodeSymbol {
+                        name: "run".to_string(),
+                        kind: SymbolKind::Function,
+                        start_line: 1,
+                        end_line: 3,
+                    }],
+                },
+            ],
+        });
+        let maps = vec![("app".to_string(), map)];
+
+        let full = render_for_prompt(&maps, 10_000).unwrap();
+        assert!(full.ends_with("## app\nsrc/lib.rs: fn run\nREADME.md"));
+
+        let trimmed = render_for_prompt(&maps, full.len() - 1).unwrap();
+        assert!(trimmed.ends_with("## app\nsrc/lib.rs: fn run\n(1 more files not shown)"));
+
+        assert!(render_for_prompt(&maps, 10).is_none());
+    }
+}
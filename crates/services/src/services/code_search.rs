@@ -0,0 +1,384 @@
+use std::{collections::HashSet, path::Path, sync::Arc};
+
+use db::models::repo::Repo;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::{
+    code_map::{CodeMap, CodeMapFile, CodeMapService},
+    config::{EmbeddingConfig, EmbeddingProvider},
+    llm::LlmService,
+    task_similarity::{cosine_similarity, local_embedding},
+};
+
+const DEFAULT_LIMIT: usize = 20;
+const MAX_LIMIT: usize = 100;
+/// Lines shown around a keyword match
+const SNIPPET_CONTEXT_LINES: usize = 2;
+/// Lines of a symbol's body embedded with it, and shown as its snippet
+const MAX_CHUNK_LINES: usize = 30;
+const MAX_SEARCHED_FILE_BYTES: u64 = 256 * 1024;
+/// Symbols embedded per repository when embeddings come from an API
+const MAX_API_CHUNKS: usize = 5_000;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeSearchMode {
+    #[default]
+    All,
+    Keyword,
+    Semantic,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CodeSearchQuery {
+    pub q: String,
+    #[serde(default)]
+    pub mode: CodeSearchMode,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeSearchMatch {
+    Keyword,
+    Semantic,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct CodeSearchResult {
+    pub repo_name: String,
+    /// Relative to the repository root
+    pub file: String,
+    /// 1-based, inclusive
+    pub start_line: usize,
+    pub end_line: usize,
+    pub snippet: String,
+    /// Public symbol enclosing the span, if any
+    pub symbol: Option<String>,
+    pub match_type: CodeSearchMatch,
+    /// From 0 to 1: the share of query words found for keyword matches, cosine similarity for
+    /// semantic ones
+    pub score: f32,
+}
+
+/// A public symbol and the start of its body, embedded for semantic search.
+#[derive(Debug, Clone)]
+struct Chunk {
+    file: String,
+    symbol: String,
+    start_line: usize,
+    end_line: usize,
+    text: String,
+}
+
+/// Embedded chunks of one code map.
+pub(crate) struct ChunkIndex {
+    map: Arc<CodeMap>,
+    chunks: Vec<Chunk>,
+    vectors: Vec<Vec<f32>>,
+}
+
+/// Search the code maps of a project's repositories by keyword, by embedding similarity or
+/// both, best matches first.
+pub async fn search(
+    code_map: &CodeMapService,
+    llm: &LlmService,
+    config: &EmbeddingConfig,
+    repos: &[Repo],
+    query: &CodeSearchQuery,
+) -> Vec<CodeSearchResult> {
+    let q = query.q.trim();
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let mut results = Vec::new();
+
+    for repo in repos {
+        let map = match code_map.get_or_build(repo.id, &repo.path).await {
+            Ok(map) => map,
+            Err(e) => {
+                tracing::warn!("Failed to build code map for {}: {}", repo.name, e);
+                continue;
+            }
+        };
+
+        if query.mode != CodeSearchMode::Semantic {
+            let (map, repo_name, q) = (map.clone(), repo.name.clone(), q.to_string());
+            match tokio::task::spawn_blocking(move || keyword_search(&map, &repo_name, &q)).await {
+                Ok(found) => results.extend(found),
+                Err(e) => tracing::warn!("Keyword search failed for {}: {}", repo.name, e),
+            }
+        }
+        if query.mode != CodeSearchMode::Keyword {
+            results.extend(semantic_search(code_map, llm, config, repo, map, q).await);
+        }
+    }
+
+    // Keep the best match per span when both searches found it
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    let mut seen = HashSet::new();
+    results.retain(|r| seen.insert((r.repo_name.clone(), r.file.clone(), r.start_line)));
+    results.truncate(limit);
+    results
+}
+
+fn read_source(root: &Path, file: &CodeMapFile) -> Option<String> {
+    let path = root.join(&file.path);
+    if std::fs::metadata(&path).ok()?.len() > MAX_SEARCHED_FILE_BYTES {
+        return None;
+    }
+    std::fs::read_to_string(path).ok()
+}
+
+fn enclosing_symbol(file: &CodeMapFile, line: usize) -> Option<String> {
+    file.symbols
+        .iter()
+        .filter(|s| s.start_line <= line && line <= s.end_line)
+        .min_by_key(|s| s.end_line - s.start_line)
+        .map(|s| s.name.clone())
+}
+
+/// The line of each file matching the most query words, with a few lines of context.
+fn keyword_search(map: &CodeMap, repo_name: &str, q: &str) -> Vec<CodeSearchResult> {
+    let terms: Vec<String> = q.split_whitespace().map(str::to_lowercase).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    map.files
+        .iter()
+        .filter_map(|file| {
+            let content = read_source(&map.root, file)?;
+            let lines: Vec<&str> = content.lines().collect();
+            let (index, matched) = lines
+                .iter()
+                .enumerate()
+                .map(|(i, line)| {
+                    let line = line.to_lowercase();
+                    (
+                        i,
+                        terms.iter().filter(|t| line.contains(t.as_str())).count(),
+                    )
+                })
+                // Earliest line among equally good ones
+                .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))?;
+            if matched == 0 {
+                return None;
+            }
+
+            let start = index.saturating_sub(SNIPPET_CONTEXT_LINES);
+            let end = (index + SNIPPET_CONTEXT_LINES).min(lines.len() - 1);
+            Some(CodeSearchResult {
+                repo_name: repo_name.to_string(),
+                file: file.path.clone(),
+                start_line: start + 1,
+                end_line: end + 1,
+                snippet: lines[start..=end].join("\n"),
+                symbol: enclosing_symbol(file, index + 1),
+                match_type: CodeSearchMatch::Keyword,
+                score: matched as f32 / terms.len() as f32,
+            })
+        })
+        .collect()
+}
+
+fn build_chunks(map: &CodeMap) -> Vec<Chunk> {
+    map.files
+        .iter()
+        .filter(|file| !file.symbols.is_empty())
+        .filter_map(|file| Some((file, read_source(&map.root, file)?)))
+        .flat_map(|(file, content)| {
+            let lines: Vec<&str> = content.lines().collect();
+            file.symbols
+                .iter()
+                .filter(|s| s.start_line <= lines.len())
+                .map(|s| {
+                    let end_line = s
+                        .end_line
+                        .min(s.start_line + MAX_CHUNK_LINES - 1)
+                        .min(lines.len());
+                    Chunk {
+                        file: file.path.clone(),
+                        symbol: s.name.clone(),
+                        start_line: s.start_line,
+                        end_line,
+                        text: format!(
+                            "{} {} {}\n{}",
+                            file.path,
+                            s.kind.label(),
+                            s.name,
+                            lines[s.start_line - 1..end_line].join("\n")
+                        ),
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// The embedded chunks of a map, built on first use and whenever the map is rebuilt. Falls back
+/// to local embeddings when the API is unavailable.
+async fn chunk_index(
+    code_map: &CodeMapService,
+    llm: &LlmService,
+    config: &EmbeddingConfig,
+    repo: &Repo,
+    map: Arc<CodeMap>,
+) -> Option<(Arc<ChunkIndex>, EmbeddingProvider)> {
+    let mut provider = config.provider;
+    if provider == EmbeddingProvider::OpenAi {
+        if let Some(index) = code_map.chunk_indexes().get(&(repo.id, provider)).await
+            && Arc::ptr_eq(&index.map, &map)
+        {
+            return Some((index, provider));
+        }
+
+        let mut chunks = {
+            let map = map.clone();
+            tokio::task::spawn_blocking(move || build_chunks(&map))
+                .await
+                .ok()?
+        };
+        chunks.truncate(MAX_API_CHUNKS);
+        let texts: Vec<String> = chunks.iter().map(|c| c.text.clone()).collect();
+        match llm.embed(config, &texts).await {
+            Ok(vectors) => {
+                let index = Arc::new(ChunkIndex {
+                    map,
+                    chunks,
+                    vectors,
+                });
+                code_map
+                    .chunk_indexes()
+                    .insert((repo.id, provider), index.clone())
+                    .await;
+                return Some((index, provider));
+            }
+            Err(e) => {
+                tracing::warn!("Embedding request failed, using local embeddings: {}", e);
+                provider = EmbeddingProvider::Local;
+            }
+        }
+    }
+
+    if let Some(index) = code_map.chunk_indexes().get(&(repo.id, provider)).await
+        && Arc::ptr_eq(&index.map, &map)
+    {
+        return Some((index, provider));
+    }
+    let index = {
+        let map = map.clone();
+        tokio::task::spawn_blocking(move || {
+            let chunks = build_chunks(&map);
+            let vectors = chunks.iter().map(|c| local_embedding(&c.text)).collect();
+            ChunkIndex {
+                map,
+                chunks,
+                vectors,
+            }
+        })
+        .await
+        .ok()?
+    };
+    let index = Arc::new(index);
+    code_map
+        .chunk_indexes()
+        .insert((repo.id, provider), index.clone())
+        .await;
+    Some((index, provider))
+}
+
+async fn semantic_search(
+    code_map: &CodeMapService,
+    llm: &LlmService,
+    config: &EmbeddingConfig,
+    repo: &Repo,
+    map: Arc<CodeMap>,
+    q: &str,
+) -> Vec<CodeSearchResult> {
+    if q.is_empty() {
+        return Vec::new();
+    }
+    let Some((index, provider)) = chunk_index(code_map, llm, config, repo, map).await else {
+        return Vec::new();
+    };
+    let query_vector = match provider {
+        EmbeddingProvider::Local => local_embedding(q),
+        EmbeddingProvider::OpenAi => match llm.embed(config, &[q.to_string()]).await {
+            Ok(mut vectors) if !vectors.is_empty() => vectors.swap_remove(0),
+            Ok(_) => return Vec::new(),
+            Err(e) => {
+                tracing::warn!("Failed to embed code search query: {}", e);
+                return Vec::new();
+            }
+        },
+    };
+
+    let mut scored: Vec<(f32, &Chunk)> = index
+        .chunks
+        .iter()
+        .zip(&index.vectors)
+        .map(|(chunk, vector)| (cosine_similarity(&query_vector, vector), chunk))
+        .filter(|(score, _)| *score > 0.0)
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.truncate(MAX_LIMIT);
+
+    scored
+        .into_iter()
+        .map(|(score, chunk)| CodeSearchResult {
+            repo_name: repo.name.clone(),
+            file: chunk.file.clone(),
+            start_line: chunk.start_line,
+            end_line: chunk.end_line,
+            snippet: chunk
+                .text
+                .split_once('\n')
+                .map(|(_, body)| body.to_string())
+                .unwrap_or_default(),
+            symbol: Some(chunk.symbol.clone()),
+            match_type: CodeSearchMatch::Semantic,
+            score,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::services::code_map::{CodeSymbol, SymbolKind};
+
+    #[test]
+    fn keyword_search_reports_best_line_with_context() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(
+            root.path().join("lib.rs"),
+            "pub fn parse_config() {\n    let path = config_path();\n    read(path)\n}\n",
+        )
+        .unwrap();
+        let map = CodeMap {
+            root: PathBuf::from(root.path()),
+            head_sha: "abc".to_string(),
+            files: vec![CodeMapFile {
+                path: "lib.rs".to_string(),
+                symbols: vec![CodeSymbol {
+                    name: "parse_config".to_string(),
+                    kind: SymbolKind::Function,
+                    start_line: 1,
+                    end_line: 4,
+                }],
+            }],
+        };
+
+        let results = keyword_search(&map, "app", "read PATH");
+        assert_eq!(results.len(), 1);
+        let result = &results[0];
+        assert_eq!((result.start_line, result.end_line), (1, 4));
+        assert_eq!(result.symbol.as_deref(), Some("parse_config"));
+        assert_eq!(result.score, 1.0);
+
+        assert!(keyword_search(&map, "app", "missing").is_empty());
+    }
+}
@@ -26,6 +26,19 @@ pub type EditorType = versions::v8::EditorType;
 pub type GitHubConfig = versions::v8::GitHubConfig;
 pub type UiLanguage = versions::v8::UiLanguage;
 pub type ShowcaseState = versions::v8::ShowcaseState;
+pub type BackupScheduleConfig = versions::v8::BackupScheduleConfig;
+pub type ScheduleFrequency = versions::v8::ScheduleFrequency;
+pub type MaintenanceScheduleConfig = versions::v8::MaintenanceScheduleConfig;
+pub type MobilePushConfig = versions::v8::MobilePushConfig;
+pub type LlmConfig = versions::v8::LlmConfig;
+pub type LlmProvider = versions::v8::LlmProvider;
+pub type EmbeddingConfig = versions::v8::EmbeddingConfig;
+pub type EmbeddingProvider = versions::v8::EmbeddingProvider;
+pub type CodeReviewConfig = versions::v8::CodeReviewConfig;
+pub type CodeMapConfig = versions::v8::CodeMapConfig;
+pub type ReviewSlaConfig = versions::v8::ReviewSlaConfig;
+pub type StaleTaskConfig = versions::v8::StaleTaskConfig;
+pub type ExecutionLimitsConfig = versions::v8::ExecutionLimitsConfig;
 
 /// Will always return config, trying old schemas or eventually returning default
 pub async fn load_config_from_file(config_path: &PathBuf) -> Config {
@@ -1,5 +1,6 @@
 use anyhow::Error;
-use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use db::models::task::TaskStatus;
+use executors::{executors::BaseCodingAgent, limits::ResourceLimits, profile::ExecutorProfileId};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 pub use v7::{
@@ -17,6 +18,317 @@ fn default_pr_auto_description_enabled() -> bool {
     true
 }
 
+fn default_backup_keep() -> u32 {
+    7
+}
+
+fn default_ntfy_server() -> String {
+    "https://ntfy.sh".to_string()
+}
+
+fn default_llm_model() -> String {
+    "claude-haiku-4-5".to_string()
+}
+
+fn default_summarize_attempts() -> bool {
+    true
+}
+
+fn default_restart_on_context_overflow() -> bool {
+    true
+}
+
+fn default_embedding_model() -> String {
+    "text-embedding-3-small".to_string()
+}
+
+fn default_code_map_in_prompt() -> bool {
+    true
+}
+
+fn default_code_map_max_prompt_bytes() -> usize {
+    6_000
+}
+
+fn default_review_sla_reminders_enabled() -> bool {
+    true
+}
+
+fn default_review_sla_thresholds_hours() -> Vec<u32> {
+    vec![24, 72, 168]
+}
+
+fn default_stale_after_days() -> u32 {
+    30
+}
+
+fn default_stale_statuses() -> Vec<TaskStatus> {
+    vec![TaskStatus::Todo]
+}
+
+fn default_stale_grace_period_days() -> u32 {
+    7
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleFrequency {
+    #[default]
+    Daily,
+    Weekly,
+}
+
+impl ScheduleFrequency {
+    pub fn interval(self) -> chrono::Duration {
+        match self {
+            Self::Daily => chrono::Duration::days(1),
+            Self::Weekly => chrono::Duration::weeks(1),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct BackupScheduleConfig {
+    pub enabled: bool,
+    pub frequency: ScheduleFrequency,
+    /// Number of scheduled backups to keep; older ones are deleted
+    #[serde(default = "default_backup_keep")]
+    pub keep: u32,
+    /// Directory for scheduled backups, defaults to the `backups` folder in the data directory
+    #[serde(default)]
+    pub destination_dir: Option<String>,
+    /// Optional `s3://bucket/prefix` to copy each backup to, using the standard `AWS_*` credentials
+    #[serde(default)]
+    pub s3_uri: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+pub struct MaintenanceScheduleConfig {
+    /// Periodically run VACUUM, ANALYZE and an integrity check on the database
+    pub enabled: bool,
+    pub frequency: ScheduleFrequency,
+}
+
+/// Phone notifications through ntfy and Pushover. Topics and user keys set here receive every
+/// notification; in multi-user mode each user can also register their own.
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct MobilePushConfig {
+    pub enabled: bool,
+    /// ntfy server, e.g. a self-hosted instance
+    #[serde(default = "default_ntfy_server")]
+    pub ntfy_server: String,
+    #[serde(default)]
+    pub ntfy_topic: Option<String>,
+    /// Access token for protected ntfy topics
+    #[serde(default)]
+    pub ntfy_token: Option<String>,
+    /// Pushover application API token, required for Pushover delivery
+    #[serde(default)]
+    pub pushover_app_token: Option<String>,
+    #[serde(default)]
+    pub pushover_user_key: Option<String>,
+}
+
+impl Default for MobilePushConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ntfy_server: default_ntfy_server(),
+            ntfy_topic: None,
+            ntfy_token: None,
+            pushover_app_token: None,
+            pushover_user_key: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmProvider {
+    #[default]
+    Anthropic,
+    OpenAi,
+}
+
+/// Language model used by assistant features such as task breakdown.
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct LlmConfig {
+    /// `openai` also covers any server implementing the OpenAI chat completions API
+    pub provider: LlmProvider,
+    #[serde(default = "default_llm_model")]
+    pub model: String,
+    /// Falls back to `ANTHROPIC_API_KEY` or `OPENAI_API_KEY` from the environment
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// API base URL override, e.g. `http://localhost:11434/v1` for a local server
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Summarize finished attempts from their log and diff for review
+    #[serde(default = "default_summarize_attempts")]
+    pub summarize_attempts: bool,
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        Self {
+            provider: LlmProvider::default(),
+            model: default_llm_model(),
+            api_key: None,
+            base_url: None,
+            summarize_attempts: default_summarize_attempts(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, TS, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbeddingProvider {
+    #[default]
+    Local,
+    OpenAi,
+}
+
+/// Text embeddings used to flag likely duplicate tasks.
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct EmbeddingConfig {
+    /// `local` hashes words and character n-grams without any API calls; `openai` also covers
+    /// any server implementing the OpenAI embeddings API
+    pub provider: EmbeddingProvider,
+    #[serde(default = "default_embedding_model")]
+    pub model: String,
+    /// Falls back to `OPENAI_API_KEY` from the environment
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// API base URL override, e.g. `http://localhost:11434/v1` for a local server
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Cosine similarity from 0 to 1 above which tasks count as duplicates. Defaults to a value
+    /// suited to the provider.
+    #[serde(default)]
+    pub similarity_threshold: Option<f32>,
+}
+
+impl Default for EmbeddingConfig {
+    fn default() -> Self {
+        Self {
+            provider: EmbeddingProvider::default(),
+            model: default_embedding_model(),
+            api_key: None,
+            base_url: None,
+            similarity_threshold: None,
+        }
+    }
+}
+
+/// Second-agent review of finished attempts.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+pub struct CodeReviewConfig {
+    /// Start a review whenever a coding agent finishes an attempt successfully
+    #[serde(default)]
+    pub auto_review: bool,
+    /// Executor that performs reviews. Defaults to the one that ran the attempt.
+    #[serde(default)]
+    pub executor_profile: Option<ExecutorProfileId>,
+    /// Refuse to move a task to Done while its latest review has unresolved blocking findings
+    #[serde(default)]
+    pub block_done_on_findings: bool,
+}
+
+/// Map of each repository's files and public symbols, shared with coding agents.
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct CodeMapConfig {
+    /// Add the map to the prompt of new attempts so agents spend fewer turns exploring
+    #[serde(default = "default_code_map_in_prompt")]
+    pub include_in_prompt: bool,
+    /// Budget for the map in the prompt; the rest of the map is left out
+    #[serde(default = "default_code_map_max_prompt_bytes")]
+    pub max_prompt_bytes: usize,
+}
+
+impl Default for CodeMapConfig {
+    fn default() -> Self {
+        Self {
+            include_in_prompt: default_code_map_in_prompt(),
+            max_prompt_bytes: default_code_map_max_prompt_bytes(),
+        }
+    }
+}
+
+/// How long tasks may wait in review for a human before reminders are sent.
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct ReviewSlaConfig {
+    #[serde(default = "default_review_sla_reminders_enabled")]
+    pub reminders_enabled: bool,
+    /// Hours in review after which a reminder is sent, each one more urgent than the last.
+    /// The first threshold is the SLA itself.
+    #[serde(default = "default_review_sla_thresholds_hours")]
+    pub thresholds_hours: Vec<u32>,
+}
+
+impl Default for ReviewSlaConfig {
+    fn default() -> Self {
+        Self {
+            reminders_enabled: default_review_sla_reminders_enabled(),
+            thresholds_hours: default_review_sla_thresholds_hours(),
+        }
+    }
+}
+
+/// Policy for tasks nobody has touched in a while.
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct StaleTaskConfig {
+    /// Flag stale tasks and warn about them in the notification center
+    #[serde(default)]
+    pub enabled: bool,
+    /// Days without changes to the task or runs of its attempts before it is flagged
+    #[serde(default = "default_stale_after_days")]
+    pub stale_after_days: u32,
+    /// Columns the policy applies to
+    #[serde(default = "default_stale_statuses")]
+    pub statuses: Vec<TaskStatus>,
+    /// Move flagged tasks to Cancelled once the grace period passes without activity
+    #[serde(default)]
+    pub auto_close: bool,
+    #[serde(default = "default_stale_grace_period_days")]
+    pub grace_period_days: u32,
+}
+
+/// Limits for execution processes running on this machine. Processes sent to a remote host, a
+/// cluster or a sandbox only get the wall-clock limit.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+pub struct ExecutionLimitsConfig {
+    /// Coding agent runs
+    #[serde(default)]
+    pub coding_agent: ResourceLimits,
+    /// Setup, cleanup and dev server scripts
+    #[serde(default)]
+    pub scripts: ResourceLimits,
+}
+
+impl Default for StaleTaskConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            stale_after_days: default_stale_after_days(),
+            statuses: default_stale_statuses(),
+            auto_close: false,
+            grace_period_days: default_stale_grace_period_days(),
+        }
+    }
+}
+
+impl Default for BackupScheduleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            frequency: ScheduleFrequency::default(),
+            keep: default_backup_keep(),
+            destination_dir: None,
+            s3_uri: None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, TS)]
 pub struct Config {
     pub config_version: String,
@@ -41,6 +353,34 @@ pub struct Config {
     pub pr_auto_description_enabled: bool,
     #[serde(default)]
     pub pr_auto_description_prompt: Option<String>,
+    #[serde(default)]
+    pub backups: BackupScheduleConfig,
+    #[serde(default)]
+    pub maintenance: MaintenanceScheduleConfig,
+    #[serde(default)]
+    pub mobile_push: MobilePushConfig,
+    #[serde(default)]
+    pub llm: LlmConfig,
+    #[serde(default)]
+    pub embeddings: EmbeddingConfig,
+    #[serde(default)]
+    pub code_review: CodeReviewConfig,
+    #[serde(default)]
+    pub code_map: CodeMapConfig,
+    #[serde(default)]
+    pub review_sla: ReviewSlaConfig,
+    #[serde(default)]
+    pub stale_tasks: StaleTaskConfig,
+    #[serde(default)]
+    pub execution_limits: ExecutionLimitsConfig,
+    /// Seconds an answer to an agent's question can still be amended or withdrawn before the
+    /// agent receives it. 0 delivers answers straight away.
+    #[serde(default)]
+    pub question_answer_grace_secs: u64,
+    /// Restart a coding agent that overflowed its context in a fresh session seeded with a
+    /// summary of the conversation and the diff, instead of failing the attempt
+    #[serde(default = "default_restart_on_context_overflow")]
+    pub restart_on_context_overflow: bool,
 }
 
 impl Config {
@@ -66,6 +406,18 @@ impl Config {
             showcases: old_config.showcases,
             pr_auto_description_enabled: true,
             pr_auto_description_prompt: None,
+            backups: BackupScheduleConfig::default(),
+            maintenance: MaintenanceScheduleConfig::default(),
+            mobile_push: MobilePushConfig::default(),
+            llm: LlmConfig::default(),
+            embeddings: EmbeddingConfig::default(),
+            code_review: CodeReviewConfig::default(),
+            code_map: CodeMapConfig::default(),
+            review_sla: ReviewSlaConfig::default(),
+            stale_tasks: StaleTaskConfig::default(),
+            execution_limits: ExecutionLimitsConfig::default(),
+            question_answer_grace_secs: 0,
+            restart_on_context_overflow: default_restart_on_context_overflow(),
         }
     }
 
@@ -116,6 +468,18 @@ impl Default for Config {
             showcases: ShowcaseState::default(),
             pr_auto_description_enabled: true,
             pr_auto_description_prompt: None,
+            backups: BackupScheduleConfig::default(),
+            maintenance: MaintenanceScheduleConfig::default(),
+            mobile_push: MobilePushConfig::default(),
+            llm: LlmConfig::default(),
+            embeddings: EmbeddingConfig::default(),
+            code_review: CodeReviewConfig::default(),
+            code_map: CodeMapConfig::default(),
+            review_sla: ReviewSlaConfig::default(),
+            stale_tasks: StaleTaskConfig::default(),
+            execution_limits: ExecutionLimitsConfig::default(),
+            question_answer_grace_secs: 0,
+            restart_on_context_overflow: default_restart_on_context_overflow(),
         }
     }
 }
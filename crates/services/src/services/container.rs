@@ -1,7 +1,8 @@
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, atomic::AtomicUsize},
+    time::Duration,
 };
 
 use anyhow::{Error as AnyhowError, anyhow};
@@ -9,32 +10,54 @@ use async_trait::async_trait;
 use db::{
     DBService,
     models::{
+        attempt_checkpoint::{AttemptCheckpoint, CheckpointRepoState, CheckpointTrigger},
+        attempt_e2e_run::{AttemptE2eRun, CreateAttemptE2eRun, E2eRunStatus},
+        attempt_lint_annotation::{AttemptLintAnnotation, LintSeverity},
+        attempt_screenshot::AttemptScreenshot,
         coding_agent_turn::{CodingAgentTurn, CreateCodingAgentTurn},
         execution_process::{
             CreateExecutionProcess, ExecutionContext, ExecutionProcess, ExecutionProcessRunReason,
             ExecutionProcessStatus,
         },
+        execution_process_environment::{ExecutionProcessEnvironment, RepoEnvironment},
+        execution_process_failure::ExecutionProcessFailure,
+        execution_process_heartbeat::RecordHeartbeat,
         execution_process_logs::ExecutionProcessLogs,
         execution_process_repo_state::{
             CreateExecutionProcessRepoState, ExecutionProcessRepoState,
         },
+        feature_flag::FeatureFlag,
+        notification::{CreateNotification, NotificationKind},
         project::{Project, UpdateProject},
+        project_lifecycle_hook::{LifecycleHook, ProjectLifecycleHook},
+        project_linter::ProjectLinter,
+        project_permission_policy::ProjectPermissionPolicy,
         project_repo::{ProjectRepo, ProjectRepoWithName},
+        prompt_template::PromptTemplate,
         repo::Repo,
+        review::{Review, ReviewFinding, ReviewSeverity, ReviewStatus},
         session::{CreateSession, Session, SessionError},
         task::{Task, TaskStatus},
-        workspace::{Workspace, WorkspaceError},
-        workspace_repo::WorkspaceRepo,
+        task_attachment::TaskAttachment,
+        task_prompt::TaskPrompt,
+        team::Team,
+        workspace::{CreateWorkspace, Workspace, WorkspaceError},
+        workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+        workspace_summary::WorkspaceSummary,
     },
 };
 use executors::{
     actions::{
         ExecutorAction, ExecutorActionType,
+        coding_agent_follow_up::CodingAgentFollowUpRequest,
         coding_agent_initial::CodingAgentInitialRequest,
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
     executors::{ExecutorError, StandardCodingAgentExecutor},
-    logs::{NormalizedEntry, NormalizedEntryError, NormalizedEntryType, utils::ConversationPatch},
+    logs::{
+        NormalizedEntry, NormalizedEntryError, NormalizedEntryType, human_handoff::HumanHandoff,
+        utils::ConversationPatch,
+    },
     profile::{ExecutorConfigs, ExecutorProfileId},
 };
 use futures::{StreamExt, future};
@@ -42,6 +65,7 @@ use sqlx::Error as SqlxError;
 use thiserror::Error;
 use tokio::{sync::RwLock, task::JoinHandle};
 use utils::{
+    diff::Diff,
     log_msg::LogMsg,
     msg_store::MsgStore,
     text::{git_branch_id, short_uuid},
@@ -49,9 +73,25 @@ use utils::{
 use uuid::Uuid;
 
 use crate::services::{
-    git::{GitService, GitServiceError},
+    attachment::AttachmentService,
+    attempt_summary,
+    code_map::{self, CodeMapService},
+    config::{CodeMapConfig, CodeReviewConfig},
+    diff_stream::{apply_stream_omit_policy, prefix_path},
+    e2e_verification, environment_snapshot,
+    git::{DiffTarget, GitService, GitServiceError},
+    human_handoff,
+    image::ImageService,
+    lifecycle_hooks::{self, LifecycleHookError},
+    lint,
+    llm::{LlmError, LlmService},
     notification::NotificationService,
+    prompt_references::{self, ResolvedPrompt},
+    prompt_templates::{self, PromptVariables},
+    repo_instructions::{self, RepoInstructions},
+    review, screenshot,
     share::SharePublisher,
+    task_workflow,
     workspace_manager::WorkspaceError as WorkspaceManagerError,
     worktree_manager::WorktreeError,
 };
@@ -77,6 +117,8 @@ pub enum ContainerError {
     Io(#[from] std::io::Error),
     #[error("Failed to kill process: {0}")]
     KillFailed(std::io::Error),
+    #[error("Server is shutting down and not starting new executions")]
+    Draining,
     #[error(transparent)]
     Other(#[from] AnyhowError), // Catches any unclassified errors
 }
@@ -93,12 +135,31 @@ pub trait ContainerService {
 
     fn notification_service(&self) -> &NotificationService;
 
+    fn image_service(&self) -> &ImageService;
+
+    fn attachment_service(&self) -> &AttachmentService;
+
+    fn llm(&self) -> &LlmService;
+
+    async fn code_review_config(&self) -> CodeReviewConfig;
+
+    fn code_map(&self) -> &CodeMapService;
+
+    async fn code_map_config(&self) -> CodeMapConfig;
+
     fn workspace_to_current_dir(&self, workspace: &Workspace) -> PathBuf;
 
     async fn create(&self, workspace: &Workspace) -> Result<ContainerRef, ContainerError>;
 
     async fn kill_all_running_processes(&self) -> Result<(), ContainerError>;
 
+    /// Whether the server is shutting down and refusing new executions
+    fn is_draining(&self) -> bool;
+
+    /// Stop accepting new executions, give running coding agents up to `timeout` to checkpoint
+    /// and exit after an interrupt, then stop everything still running, dev servers included.
+    async fn drain(&self, timeout: Duration) -> Result<(), ContainerError>;
+
     async fn delete(&self, workspace: &Workspace) -> Result<(), ContainerError>;
 
     /// Check if a task has any running execution processes
@@ -161,13 +222,53 @@ pub trait ContainerService {
         action.next_action.is_none()
     }
 
+    /// In place of finalizing, move the task to Blocked and tell the owner what the agent
+    /// handed back
+    async fn block_task(
+        &self,
+        share_publisher: Option<&SharePublisher>,
+        ctx: &ExecutionContext,
+        handoff: &HumanHandoff,
+    ) {
+        match task_workflow::update_task_status(&self.db().pool, ctx.task.id, TaskStatus::Blocked)
+            .await
+        {
+            Ok(_) => {
+                if let Some(publisher) = share_publisher
+                    && let Err(err) = publisher.update_shared_task_by_id(ctx.task.id).await
+                {
+                    tracing::warn!(
+                        ?err,
+                        "Failed to propagate shared task update for {}",
+                        ctx.task.id
+                    );
+                }
+            }
+            Err(e) => {
+                tracing::error!("Failed to update task status to Blocked: {e}");
+            }
+        }
+
+        self.notification_service()
+            .notify(CreateNotification {
+                kind: NotificationKind::AgentBlocked,
+                title: format!("Agent Blocked: {}", ctx.task.title),
+                message: human_handoff::notification_message(&ctx.task.title, handoff),
+                project_id: Some(ctx.task.project_id),
+                task_id: Some(ctx.task.id),
+            })
+            .await;
+    }
+
     /// Finalize task execution by updating status to InReview and sending notifications
     async fn finalize_task(
         &self,
         share_publisher: Option<&SharePublisher>,
         ctx: &ExecutionContext,
     ) {
-        match Task::update_status(&self.db().pool, ctx.task.id, TaskStatus::InReview).await {
+        match task_workflow::update_task_status(&self.db().pool, ctx.task.id, TaskStatus::InReview)
+            .await
+        {
             Ok(_) => {
                 if let Some(publisher) = share_publisher
                     && let Err(err) = publisher.update_shared_task_by_id(ctx.task.id).await
@@ -184,20 +285,40 @@ pub trait ContainerService {
             }
         }
 
+        // Review sessions report their findings instead of the usual notification
+        match Review::find_by_session_id(&self.db().pool, ctx.session.id).await {
+            Ok(Some(review)) => {
+                self.complete_review(&review, ctx).await;
+                return;
+            }
+            Ok(None) => {}
+            Err(e) => tracing::error!(
+                "Failed to look up review for session {}: {e}",
+                ctx.session.id
+            ),
+        }
+
         // Skip notification if process was intentionally killed by user
         if matches!(ctx.execution_process.status, ExecutionProcessStatus::Killed) {
             return;
         }
 
         let title = format!("Task Complete: {}", ctx.task.title);
-        let message = match ctx.execution_process.status {
-            ExecutionProcessStatus::Completed => format!(
-                "✅ '{}' completed successfully\nBranch: {:?}\nExecutor: {:?}",
-                ctx.task.title, ctx.workspace.branch, ctx.session.executor
+        let summary = self.summarize_attempt(ctx).await;
+        let (kind, mut message) = match ctx.execution_process.status {
+            ExecutionProcessStatus::Completed => (
+                NotificationKind::ExecutionCompleted,
+                format!(
+                    "✅ '{}' completed successfully\nBranch: {:?}\nExecutor: {:?}",
+                    ctx.task.title, ctx.workspace.branch, ctx.session.executor
+                ),
             ),
-            ExecutionProcessStatus::Failed => format!(
-                "❌ '{}' execution failed\nBranch: {:?}\nExecutor: {:?}",
-                ctx.task.title, ctx.workspace.branch, ctx.session.executor
+            ExecutionProcessStatus::Failed => (
+                NotificationKind::ExecutionFailed,
+                format!(
+                    "❌ '{}' execution failed\nBranch: {:?}\nExecutor: {:?}",
+                    ctx.task.title, ctx.workspace.branch, ctx.session.executor
+                ),
             ),
             _ => {
                 tracing::warn!(
@@ -207,7 +328,253 @@ pub trait ContainerService {
                 return;
             }
         };
-        self.notification_service().notify(&title, &message).await;
+        if kind == NotificationKind::ExecutionFailed
+            && let Ok(Some(failure)) = ExecutionProcessFailure::find_by_execution_process_id(
+                &self.db().pool,
+                ctx.execution_process.id,
+            )
+            .await
+        {
+            message.push_str(&format!(
+                "\nCause: {}\n{}",
+                failure.kind,
+                failure.kind.remediation()
+            ));
+        }
+        if kind == NotificationKind::ExecutionCompleted
+            && FeatureFlag::DevServerScreenshots
+                .is_enabled(&self.db().pool, Some(ctx.task.project_id))
+                .await
+        {
+            match self.capture_dev_server_screenshot(&ctx.workspace).await {
+                Ok(Some(shot)) => message.push_str(&format!(
+                    "\nScreenshot: {}",
+                    screenshot::image_path(shot.image_id)
+                )),
+                Ok(None) => {}
+                Err(e) => tracing::warn!(
+                    "Failed to screenshot the dev server of workspace {}: {}",
+                    ctx.workspace.id,
+                    e
+                ),
+            }
+        }
+        if kind == NotificationKind::ExecutionCompleted {
+            self.spawn_e2e_verification(&ctx.workspace);
+            self.spawn_linters(&ctx.workspace);
+        }
+        if let Some(summary) = summary {
+            message.push_str(&format!("\n\n{summary}"));
+        }
+        self.notification_service()
+            .notify(CreateNotification {
+                kind,
+                title,
+                message,
+                project_id: Some(ctx.task.project_id),
+                task_id: Some(ctx.task.id),
+            })
+            .await;
+
+        let review_config = self.code_review_config().await;
+        if review_config.auto_review
+            && kind == NotificationKind::ExecutionCompleted
+            && FeatureFlag::AutoReview
+                .is_enabled(&self.db().pool, Some(ctx.task.project_id))
+                .await
+        {
+            let executor_profile_id = match review_config.executor_profile {
+                Some(profile) => profile,
+                None => match ExecutionProcess::latest_executor_profile_for_session(
+                    &self.db().pool,
+                    ctx.session.id,
+                )
+                .await
+                {
+                    Ok(profile) => profile,
+                    Err(e) => {
+                        tracing::warn!("No executor to review workspace {}: {e}", ctx.workspace.id);
+                        return;
+                    }
+                },
+            };
+            if let Err(e) = self
+                .start_review(&ctx.workspace, &ctx.task, executor_profile_id)
+                .await
+            {
+                tracing::error!(
+                    "Failed to start review for workspace {}: {e}",
+                    ctx.workspace.id
+                );
+            }
+        }
+    }
+
+    /// Start a second coding agent in a new session that reviews the attempt's diff.
+    async fn start_review(
+        &self,
+        workspace: &Workspace,
+        task: &Task,
+        executor_profile_id: ExecutorProfileId,
+    ) -> Result<Review, ContainerError> {
+        let pool = &self.db().pool;
+        let diffs = self.diff_snapshot(workspace, false).await?;
+        let prompt = review::review_prompt(task, &attempt_summary::render_diffs(&diffs));
+
+        let executor = executor_profile_id.executor.to_string();
+        let session = Session::create(
+            pool,
+            &CreateSession {
+                executor: Some(executor.clone()),
+            },
+            Uuid::new_v4(),
+            workspace.id,
+        )
+        .await?;
+        let review = Review::create(pool, workspace.id, session.id, &executor).await?;
+
+        let action = ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt,
+                executor_profile_id,
+                working_dir: workspace
+                    .agent_working_dir
+                    .clone()
+                    .filter(|dir| !dir.is_empty()),
+                model: None,
+            }),
+            None,
+        );
+        if let Err(e) = self
+            .start_execution(
+                workspace,
+                &session,
+                &action,
+                &ExecutionProcessRunReason::CodingAgent,
+            )
+            .await
+        {
+            Review::finish(pool, review.id, ReviewStatus::Failed, Some(&e.to_string())).await?;
+            return Err(e);
+        }
+        Ok(review)
+    }
+
+    /// Store the findings from a finished review session and notify about them.
+    async fn complete_review(&self, review: &Review, ctx: &ExecutionContext) {
+        let pool = &self.db().pool;
+        let findings = match ctx.execution_process.status {
+            ExecutionProcessStatus::Completed => self
+                .get_msg_store_by_id(&ctx.execution_process.id)
+                .await
+                .and_then(|store| review::last_assistant_message(&store.get_history()))
+                .ok_or_else(|| "The reviewer did not reply".to_string())
+                .and_then(|reply| review::parse_findings(&reply)),
+            ExecutionProcessStatus::Killed => Err("The review was stopped".to_string()),
+            _ => Err("The reviewer failed to run".to_string()),
+        };
+
+        let (kind, title, message) = match findings {
+            Ok(findings) => {
+                if let Err(e) = ReviewFinding::create_many(pool, review.id, &findings).await {
+                    tracing::error!("Failed to store findings of review {}: {e}", review.id);
+                    return;
+                }
+                if let Err(e) = Review::finish(pool, review.id, ReviewStatus::Completed, None).await
+                {
+                    tracing::error!("Failed to complete review {}: {e}", review.id);
+                }
+                let blocking = findings
+                    .iter()
+                    .filter(|f| f.severity == ReviewSeverity::Blocking)
+                    .count();
+                (
+                    NotificationKind::ExecutionCompleted,
+                    format!("Review Complete: {}", ctx.task.title),
+                    format!("{} findings, {blocking} blocking", findings.len()),
+                )
+            }
+            Err(error) => {
+                if let Err(e) =
+                    Review::finish(pool, review.id, ReviewStatus::Failed, Some(&error)).await
+                {
+                    tracing::error!("Failed to mark review {} as failed: {e}", review.id);
+                }
+                if matches!(ctx.execution_process.status, ExecutionProcessStatus::Killed) {
+                    return;
+                }
+                (
+                    NotificationKind::ExecutionFailed,
+                    format!("Review Failed: {}", ctx.task.title),
+                    error,
+                )
+            }
+        };
+        self.notification_service()
+            .notify(CreateNotification {
+                kind,
+                title,
+                message,
+                project_id: Some(ctx.task.project_id),
+                task_id: Some(ctx.task.id),
+            })
+            .await;
+    }
+
+    /// Summarize what the attempt changed and why from its log and diff, storing the result on
+    /// the workspace. Returns `None` when disabled, when no model is configured or on failure.
+    async fn summarize_attempt(&self, ctx: &ExecutionContext) -> Option<String> {
+        if !self.llm().settings().await.summarize_attempts {
+            return None;
+        }
+        let pool = &self.db().pool;
+
+        // The full log is only kept in memory; older turns fall back to prompts and final messages
+        let history = match ctx.execution_process.run_reason {
+            ExecutionProcessRunReason::CodingAgent => self
+                .get_msg_store_by_id(&ctx.execution_process.id)
+                .await
+                .map(|store| store.get_history())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        let mut transcript = attempt_summary::transcript_from_history(&history);
+        if transcript.is_empty() {
+            let turns = CodingAgentTurn::find_by_workspace_id(pool, ctx.workspace.id)
+                .await
+                .inspect_err(|e| tracing::warn!("Failed to load turns for summary: {}", e))
+                .unwrap_or_default();
+            transcript = attempt_summary::transcript_from_turns(&turns);
+        }
+        let diffs = self
+            .diff_snapshot(&ctx.workspace, false)
+            .await
+            .inspect_err(|e| tracing::warn!("Failed to compute diff for summary: {}", e))
+            .unwrap_or_default();
+        if transcript.is_empty() && diffs.is_empty() {
+            return None;
+        }
+
+        let diff = attempt_summary::render_diffs(&diffs);
+        let summary =
+            match attempt_summary::summarize_attempt(self.llm(), &ctx.task, &transcript, &diff)
+                .await
+            {
+                Ok(summary) => summary,
+                Err(LlmError::MissingApiKey) => return None,
+                Err(e) => {
+                    tracing::warn!("Failed to summarize workspace {}: {}", ctx.workspace.id, e);
+                    return None;
+                }
+            };
+        if let Err(e) = WorkspaceSummary::upsert(pool, ctx.workspace.id, &summary).await {
+            tracing::error!(
+                "Failed to store summary for workspace {}: {}",
+                ctx.workspace.id,
+                e
+            );
+        }
+        Some(summary)
     }
 
     /// Cleanup executions marked as running in the db, call at startup
@@ -274,7 +641,13 @@ pub trait ContainerService {
                     Workspace::find_by_id(&self.db().pool, session.workspace_id).await
                 && let Ok(Some(task)) = workspace.parent_task(&self.db().pool).await
             {
-                match Task::update_status(&self.db().pool, task.id, TaskStatus::InReview).await {
+                match task_workflow::update_task_status(
+                    &self.db().pool,
+                    task.id,
+                    TaskStatus::InReview,
+                )
+                .await
+                {
                     Ok(_) => {
                         if let Some(publisher) = self.share_publisher()
                             && let Err(err) = publisher.update_shared_task_by_id(task.id).await
@@ -461,6 +834,84 @@ pub trait ContainerService {
         Some(root_action)
     }
 
+    /// Actions run after each coding agent turn: the cleanup scripts, then the project's
+    /// attempt_finished hook.
+    async fn finish_actions(
+        &self,
+        project_id: Uuid,
+        repos: &[ProjectRepoWithName],
+    ) -> Result<Option<ExecutorAction>, ContainerError> {
+        let cleanup_action = self.cleanup_actions_for_repos(repos);
+        let pool = &self.db().pool;
+        if !FeatureFlag::LifecycleHooks
+            .is_enabled(pool, Some(project_id))
+            .await
+        {
+            return Ok(cleanup_action);
+        }
+        let Some(hook) =
+            ProjectLifecycleHook::find(pool, project_id, LifecycleHook::AttemptFinished).await?
+        else {
+            return Ok(cleanup_action);
+        };
+        let hook_action = lifecycle_hooks::hook_action(&hook, None);
+        Ok(Some(match cleanup_action {
+            Some(cleanup_action) => cleanup_action.append_action(hook_action),
+            None => hook_action,
+        }))
+    }
+
+    /// Run a merge hook in the workspace directory. Output goes to the latest coding agent
+    /// process so it shows up in the attempt's logs.
+    async fn run_merge_hook(
+        &self,
+        workspace: &Workspace,
+        workspace_dir: &Path,
+        project_id: Uuid,
+        hook: LifecycleHook,
+    ) -> Result<(), LifecycleHookError> {
+        let pool = &self.db().pool;
+        if !FeatureFlag::LifecycleHooks
+            .is_enabled(pool, Some(project_id))
+            .await
+        {
+            return Ok(());
+        }
+        let Some(hook) = ProjectLifecycleHook::find(pool, project_id, hook).await? else {
+            return Ok(());
+        };
+        let process = ExecutionProcess::find_latest_by_workspace_and_run_reason(
+            pool,
+            workspace.id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+        let live_store = match &process {
+            Some(process) => self.get_msg_store_by_id(&process.id).await,
+            None => None,
+        };
+        let msg_store = live_store.clone().unwrap_or_default();
+
+        let result = lifecycle_hooks::run_hook(&hook, workspace_dir, &msg_store).await;
+
+        // A finished process no longer streams its store to the database
+        if live_store.is_none()
+            && let Some(process) = process
+        {
+            for msg in msg_store.get_history() {
+                if let Ok(json_line) = serde_json::to_string(&msg) {
+                    ExecutionProcessLogs::append_log_line(
+                        pool,
+                        process.id,
+                        &format!("{json_line}\n"),
+                    )
+                    .await?;
+                }
+            }
+        }
+        result
+    }
+
     fn setup_actions_for_repos(&self, repos: &[ProjectRepoWithName]) -> Option<ExecutorAction> {
         let repos_with_setup: Vec<_> = repos.iter().filter(|r| r.setup_script.is_some()).collect();
 
@@ -585,6 +1036,12 @@ pub trait ContainerService {
         status: ExecutionProcessStatus,
     ) -> Result<(), ContainerError>;
 
+    /// What the OS reports about the execution's child process right now, if this instance runs it.
+    /// Default implementation reports nothing (for non-local deployments).
+    async fn sample_child(&self, _exec_id: &Uuid) -> Option<RecordHeartbeat> {
+        None
+    }
+
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError>;
 
     async fn copy_project_files(
@@ -620,6 +1077,99 @@ pub trait ContainerService {
         }
     }
 
+    /// Snapshot the agent, platform and repo state an execution process starts with
+    async fn record_environment(
+        &self,
+        workspace: &Workspace,
+        workspace_root: &Path,
+        execution_process: &ExecutionProcess,
+        executor_action: &ExecutorAction,
+    ) -> Result<(), ContainerError> {
+        let repos = WorkspaceRepo::find_repos_with_target_branch_for_workspace(
+            &self.db().pool,
+            workspace.id,
+        )
+        .await?
+        .into_iter()
+        .map(|repo| {
+            let worktree_path = workspace_root.join(&repo.repo.name);
+            RepoEnvironment {
+                repo_id: repo.repo.id,
+                base_commit: self
+                    .git()
+                    .get_base_commit(&repo.repo.path, &workspace.branch, &repo.target_branch)
+                    .ok()
+                    .map(|commit| commit.to_string()),
+                head_commit: self.git().get_head_info(&worktree_path).ok().map(|h| h.oid),
+                lockfiles: environment_snapshot::lockfile_hashes(&worktree_path),
+                repo_name: repo.repo.name,
+                target_branch: repo.target_branch,
+            }
+        })
+        .collect();
+        ExecutionProcessEnvironment::create(
+            &self.db().pool,
+            execution_process.id,
+            &environment_snapshot::snapshot(executor_action, repos),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Compute the workspace's current diff against each repo's target branch in one shot.
+    async fn diff_snapshot(
+        &self,
+        workspace: &Workspace,
+        stats_only: bool,
+    ) -> Result<Vec<Diff>, ContainerError> {
+        let pool = &self.db().pool;
+        let workspace_repos = WorkspaceRepo::find_by_workspace_id(pool, workspace.id).await?;
+        let repositories = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+        let workspace_root = PathBuf::from(self.ensure_container_exists(workspace).await?);
+        let sent_bytes = Arc::new(AtomicUsize::new(0));
+
+        let mut diffs = Vec::new();
+        for repo in repositories {
+            let Some(target_branch) = workspace_repos
+                .iter()
+                .find(|wr| wr.repo_id == repo.id)
+                .map(|wr| wr.target_branch.clone())
+            else {
+                continue;
+            };
+            let base_commit =
+                self.git()
+                    .get_base_commit(&repo.path, &workspace.branch, &target_branch)?;
+
+            let git = self.git().clone();
+            let worktree_path = workspace_root.join(&repo.name);
+            let repo_diffs = tokio::task::spawn_blocking(move || {
+                git.get_diffs(
+                    DiffTarget::Worktree {
+                        worktree_path: &worktree_path,
+                        base_commit: &base_commit,
+                    },
+                    None,
+                )
+            })
+            .await
+            .map_err(|e| ContainerError::Other(anyhow!("Diff task failed: {e}")))??;
+
+            for mut diff in repo_diffs {
+                apply_stream_omit_policy(&mut diff, &sent_bytes, stats_only);
+                diff.old_path = diff
+                    .old_path
+                    .map(|path| prefix_path(path, Some(&repo.name)));
+                diff.new_path = diff
+                    .new_path
+                    .map(|path| prefix_path(path, Some(&repo.name)));
+                diffs.push(diff);
+            }
+        }
+
+        Ok(diffs)
+    }
+
     async fn stream_raw_logs(
         &self,
         id: &Uuid,
@@ -878,6 +1428,452 @@ pub trait ContainerService {
         })
     }
 
+    /// Expand `@path` and `@task:ID` references in a prompt against the workspace's checkout.
+    async fn resolve_prompt_references(
+        &self,
+        workspace: &Workspace,
+        prompt: String,
+    ) -> Result<ResolvedPrompt, ContainerError> {
+        if prompt_references::parse_references(&prompt).is_empty() {
+            return Ok(ResolvedPrompt {
+                prompt,
+                missing_files: Vec::new(),
+                missing_tasks: Vec::new(),
+            });
+        }
+        let workspace_root = PathBuf::from(self.ensure_container_exists(workspace).await?);
+        let repos = WorkspaceRepo::find_repos_for_workspace(&self.db().pool, workspace.id).await?;
+        Ok(
+            prompt_references::resolve_references(&self.db().pool, &workspace_root, &repos, prompt)
+                .await?,
+        )
+    }
+
+    /// Refresh the code maps of the workspace's repositories and render them trimmed to the
+    /// prompt budget. `None` when the workspace has no checkout or nothing could be mapped.
+    async fn render_code_map(&self, workspace: &Workspace) -> Option<String> {
+        let container_ref = workspace.container_ref.as_ref()?;
+        let repos =
+            match WorkspaceRepo::find_repos_for_workspace(&self.db().pool, workspace.id).await {
+                Ok(repos) => repos,
+                Err(e) => {
+                    tracing::warn!("Failed to load repos for code map: {}", e);
+                    return None;
+                }
+            };
+
+        let workspace_root = PathBuf::from(container_ref);
+        let mut maps = Vec::new();
+        for repo in repos {
+            match self
+                .code_map()
+                .refresh(repo.id, &workspace_root.join(&repo.name))
+                .await
+            {
+                Ok(map) => maps.push((repo.name, map)),
+                Err(e) => tracing::warn!("Failed to build code map for {}: {}", repo.name, e),
+            }
+        }
+
+        let config = self.code_map_config().await;
+        code_map::render_for_prompt(&maps, config.max_prompt_bytes)
+    }
+
+    /// Refresh the code maps of the workspace's repositories and, if enabled, append a trimmed
+    /// rendering to the prompt so the agent starts with an overview of the tree.
+    async fn append_code_map(&self, workspace: &Workspace, prompt: String) -> String {
+        let map = self.render_code_map(workspace).await;
+        if !self.code_map_config().await.include_in_prompt {
+            return prompt;
+        }
+        match map {
+            Some(map) => format!("{prompt}\n\n{map}"),
+            None => prompt,
+        }
+    }
+
+    /// Fill in the templates' variables from the attempt and append them to the prompt.
+    async fn apply_prompt_templates(
+        &self,
+        workspace: &Workspace,
+        task: &Task,
+        templates: &[PromptTemplate],
+        prompt: String,
+    ) -> Result<String, ContainerError> {
+        if templates.is_empty() {
+            return Ok(prompt);
+        }
+        let pool = &self.db().pool;
+        let project = task
+            .parent_project(pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let repos =
+            WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, workspace.id).await?;
+        let code_map = if templates
+            .iter()
+            .any(|template| prompt_templates::uses_variable(&template.content, "code_map"))
+        {
+            self.render_code_map(workspace).await.unwrap_or_default()
+        } else {
+            String::new()
+        };
+
+        let variables = PromptVariables {
+            task_title: task.title.clone(),
+            task_description: task.description.clone().unwrap_or_default(),
+            project_name: project.name,
+            branch: workspace.branch.clone(),
+            base_branch: prompt_templates::base_branch(&repos),
+            code_map,
+        };
+        let rendered = templates
+            .iter()
+            .map(|template| prompt_templates::render(&template.content, &variables))
+            .collect::<Vec<_>>();
+        Ok(prompt_templates::append_rendered(prompt, &rendered))
+    }
+
+    /// Repository instructions files of the workspace's checkouts, when the project has the
+    /// feature on.
+    async fn repo_instructions(
+        &self,
+        workspace: &Workspace,
+        project_id: Uuid,
+    ) -> Result<Vec<RepoInstructions>, ContainerError> {
+        let pool = &self.db().pool;
+        if !FeatureFlag::RepoInstructions
+            .is_enabled(pool, Some(project_id))
+            .await
+        {
+            return Ok(Vec::new());
+        }
+        let Some(container_ref) = &workspace.container_ref else {
+            return Ok(Vec::new());
+        };
+        let workspace_root = PathBuf::from(container_ref);
+        let mut found = Vec::new();
+        for repo in WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await? {
+            if let Some(instructions) =
+                repo_instructions::read(&repo.name, &workspace_root.join(&repo.name)).await
+            {
+                found.push(instructions);
+            }
+        }
+        Ok(found)
+    }
+
+    /// The prompt an attempt's first run starts with: the task with its references expanded,
+    /// its attachments, the code map, the templates picked at attempt start and the
+    /// repositories' instructions files.
+    async fn compose_initial_prompt(
+        &self,
+        workspace: &Workspace,
+        task: &Task,
+    ) -> Result<String, ContainerError> {
+        let pool = &self.db().pool;
+        // Tasks with generated details still run on the prompt they were created from
+        let prompt = match TaskPrompt::find_by_task_id(pool, task.id).await? {
+            Some(task_prompt) => task_prompt.original_prompt,
+            None => task.to_prompt(),
+        };
+        let prompt = self
+            .resolve_prompt_references(workspace, prompt)
+            .await?
+            .into_prompt_with_notes();
+        // Attachments were copied into the workspace when it was created
+        let attachments = TaskAttachment::find_by_task_id(pool, task.id).await?;
+        let prompt = TaskAttachment::append_to_prompt(prompt, &attachments);
+        // Templates picked at attempt start follow the prompt; one placing the code map itself
+        // replaces the automatic one
+        let templates = PromptTemplate::find_by_workspace_id(pool, workspace.id).await?;
+        let prompt = if templates
+            .iter()
+            .any(|template| prompt_templates::uses_variable(&template.content, "code_map"))
+        {
+            prompt
+        } else {
+            self.append_code_map(workspace, prompt).await
+        };
+        let prompt = self
+            .apply_prompt_templates(workspace, task, &templates, prompt)
+            .await?;
+        let instructions = self.repo_instructions(workspace, task.project_id).await?;
+        let prompt = match repo_instructions::render(&instructions) {
+            Some(section) => format!("{prompt}\n\n{section}"),
+            None => prompt,
+        };
+        Ok(
+            if FeatureFlag::HumanHandoff
+                .is_enabled(pool, Some(task.project_id))
+                .await
+            {
+                format!("{prompt}\n\n{}", human_handoff::instructions())
+            } else {
+                prompt
+            },
+        )
+    }
+
+    /// Send the attempt's agent a follow-up prompt on behalf of the user, in the attempt's latest
+    /// session with the executor it last ran.
+    async fn start_prompt_follow_up(
+        &self,
+        workspace: &Workspace,
+        prompt: String,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        let pool = &self.db().pool;
+        self.ensure_container_exists(workspace).await?;
+        let session = match Session::find_latest_by_workspace_id(pool, workspace.id).await? {
+            Some(session) => session,
+            None => {
+                Session::create(
+                    pool,
+                    &CreateSession { executor: None },
+                    Uuid::new_v4(),
+                    workspace.id,
+                )
+                .await?
+            }
+        };
+        let executor_profile_id =
+            ExecutionProcess::latest_executor_profile_for_session(pool, session.id)
+                .await
+                .map_err(|e| anyhow!("Failed to get executor profile: {e}"))?;
+        let latest_agent_session_id =
+            ExecutionProcess::find_latest_coding_agent_turn_session_id(pool, session.id).await?;
+        let working_dir = workspace
+            .agent_working_dir
+            .as_ref()
+            .filter(|dir| !dir.is_empty())
+            .cloned();
+
+        let action_type = match latest_agent_session_id {
+            Some(agent_session_id) => {
+                ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                    prompt,
+                    session_id: agent_session_id,
+                    executor_profile_id,
+                    working_dir,
+                    model: None,
+                })
+            }
+            None => ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt,
+                executor_profile_id,
+                working_dir,
+                model: None,
+            }),
+        };
+        self.start_execution(
+            workspace,
+            &session,
+            &ExecutorAction::new(action_type, None),
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await
+    }
+
+    /// Snapshot every repository of the attempt, uncommitted changes included. `None` when
+    /// nothing changed since the attempt's latest checkpoint.
+    async fn create_checkpoint(
+        &self,
+        workspace: &Workspace,
+        execution_process_id: Uuid,
+        trigger: CheckpointTrigger,
+    ) -> Result<Option<AttemptCheckpoint>, ContainerError> {
+        let pool = &self.db().pool;
+        let workspace_root = self.workspace_to_current_dir(workspace);
+        let message = format!("Checkpoint of workspace {}", workspace.id);
+        let mut snapshots = Vec::new();
+        for repo in WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await? {
+            let worktree_path = workspace_root.join(&repo.name);
+            let head_oid = self.git().get_head_info(&worktree_path)?.oid;
+            let (snapshot_oid, tree_oid) =
+                self.git().snapshot_worktree(&worktree_path, &message)?;
+            snapshots.push((
+                worktree_path,
+                CheckpointRepoState {
+                    repo_id: repo.id,
+                    head_oid,
+                    snapshot_oid,
+                    tree_oid,
+                },
+            ));
+        }
+
+        let unchanged = AttemptCheckpoint::find_latest_by_workspace_id(pool, workspace.id)
+            .await?
+            .is_some_and(|latest| {
+                latest.repo_states.0.len() == snapshots.len()
+                    && snapshots.iter().all(|(_, state)| {
+                        latest.repo_states.0.iter().any(|seen| {
+                            seen.repo_id == state.repo_id
+                                && seen.head_oid == state.head_oid
+                                && seen.tree_oid == state.tree_oid
+                        })
+                    })
+            });
+        if unchanged {
+            return Ok(None);
+        }
+
+        for (worktree_path, state) in &snapshots {
+            self.git().pin_commit(
+                worktree_path,
+                &format!(
+                    "refs/vibe-kanban/checkpoints/{}/{}",
+                    workspace.id, state.snapshot_oid
+                ),
+                &state.snapshot_oid,
+            )?;
+        }
+        let repo_states: Vec<CheckpointRepoState> =
+            snapshots.into_iter().map(|(_, state)| state).collect();
+        let checkpoint = AttemptCheckpoint::create(
+            pool,
+            workspace.id,
+            execution_process_id,
+            trigger,
+            &repo_states,
+        )
+        .await?;
+        Ok(Some(checkpoint))
+    }
+
+    /// Stop the attempt's agent and put its worktrees back to a checkpoint, after checkpointing
+    /// the state being replaced so the rollback can be undone. With `truncate_conversation`,
+    /// the runs that followed the checkpoint's run in its session are dropped so the next
+    /// follow-up continues from that point. Returns the checkpoint of the replaced state.
+    async fn rollback_to_checkpoint(
+        &self,
+        workspace: &Workspace,
+        checkpoint: &AttemptCheckpoint,
+        truncate_conversation: bool,
+    ) -> Result<Option<AttemptCheckpoint>, ContainerError> {
+        let pool = &self.db().pool;
+        self.try_stop(workspace, false).await;
+
+        let replaced = match ExecutionProcess::find_latest_by_workspace_and_run_reason(
+            pool,
+            workspace.id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?
+        {
+            Some(process) => {
+                self.create_checkpoint(workspace, process.id, CheckpointTrigger::BeforeRollback)
+                    .await?
+            }
+            None => None,
+        };
+
+        let workspace_root = self.workspace_to_current_dir(workspace);
+        let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+        for state in &checkpoint.repo_states.0 {
+            let Some(repo) = repos.iter().find(|repo| repo.id == state.repo_id) else {
+                tracing::warn!(
+                    "Repository {} of checkpoint {} is no longer part of the attempt",
+                    state.repo_id,
+                    checkpoint.id
+                );
+                continue;
+            };
+            self.git().restore_worktree_snapshot(
+                &workspace_root.join(&repo.name),
+                &state.head_oid,
+                &state.snapshot_oid,
+            )?;
+        }
+
+        if truncate_conversation
+            && let Some(process) =
+                ExecutionProcess::find_by_id(pool, checkpoint.execution_process_id).await?
+        {
+            ExecutionProcess::drop_after(pool, process.session_id, process.id).await?;
+        }
+        Ok(replaced)
+    }
+
+    /// Start an attempt on every repository of the project, from its checked out branch, with
+    /// the team's executor or `default_profile` when the team has none. `None` when the project
+    /// has no usable repository.
+    async fn start_default_attempt(
+        &self,
+        task: &Task,
+        default_profile: ExecutorProfileId,
+    ) -> Result<Option<Workspace>, ContainerError> {
+        let pool = &self.db().pool;
+        let repos = ProjectRepo::find_repos_for_project(pool, task.project_id).await?;
+        let workspace_repos: Vec<CreateWorkspaceRepo> = repos
+            .iter()
+            .filter_map(|repo| {
+                let target_branch = self
+                    .git()
+                    .get_current_branch(&repo.path)
+                    .inspect_err(|e| tracing::warn!("Skipping repo {}: {}", repo.name, e))
+                    .ok()?;
+                Some(CreateWorkspaceRepo {
+                    repo_id: repo.id,
+                    target_branch,
+                })
+            })
+            .collect();
+        if workspace_repos.is_empty() {
+            tracing::warn!(
+                "Not starting an attempt for task {}: project has no usable repository",
+                task.id
+            );
+            return Ok(None);
+        }
+
+        let project = task
+            .parent_project(pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let agent_working_dir = project
+            .default_agent_working_dir
+            .filter(|dir| !dir.is_empty());
+        // The team's shared executor takes precedence over the instance default
+        let executor_profile_id = match Team::find_by_project_id(pool, task.project_id).await? {
+            Some(Team {
+                executor_profile: Some(profile),
+                ..
+            }) => profile.0,
+            _ => default_profile,
+        };
+        // Nobody picked a mode, so the project's cap applies if the profile allows more
+        let policy = ProjectPermissionPolicy::find_by_project_id(pool, task.project_id).await?;
+        let profile_mode = ExecutorConfigs::get_cached()
+            .get_coding_agent(&executor_profile_id)
+            .and_then(|agent| agent.default_permission_mode());
+        let permission_mode =
+            ProjectPermissionPolicy::resolve(policy.as_ref(), None, profile_mode).unwrap_or(None);
+
+        let attempt_id = Uuid::new_v4();
+        let branch = self
+            .git_branch_from_workspace(&attempt_id, &task.title)
+            .await;
+        let workspace = Workspace::create(
+            pool,
+            &CreateWorkspace {
+                branch,
+                agent_working_dir,
+                read_only: false,
+                dry_run: false,
+                permission_mode,
+            },
+            attempt_id,
+            task.id,
+        )
+        .await?;
+        WorkspaceRepo::create_many(pool, workspace.id, &workspace_repos).await?;
+        if let Err(err) = self.start_workspace(&workspace, executor_profile_id).await {
+            tracing::error!("Failed to start attempt for task {}: {}", task.id, err);
+        }
+        Ok(Some(workspace))
+    }
+
     async fn start_workspace(
         &self,
         workspace: &Workspace,
@@ -916,7 +1912,7 @@ pub trait ContainerService {
         )
         .await?;
 
-        let prompt = task.to_prompt();
+        let prompt = self.compose_initial_prompt(&workspace, &task).await?;
 
         let repos_with_setup: Vec<_> = project_repos
             .iter()
@@ -925,7 +1921,16 @@ pub trait ContainerService {
 
         let all_parallel = repos_with_setup.iter().all(|pr| pr.parallel_setup_script);
 
-        let cleanup_action = self.cleanup_actions_for_repos(&project_repos);
+        let hooks = if FeatureFlag::LifecycleHooks
+            .is_enabled(&self.db().pool, Some(project.id))
+            .await
+        {
+            ProjectLifecycleHook::find_by_project_id(&self.db().pool, project.id).await?
+        } else {
+            Vec::new()
+        };
+        let find_hook = |kind: LifecycleHook| hooks.iter().find(|hook| hook.hook == kind);
+        let finish_action = self.finish_actions(project.id, &project_repos).await?;
 
         let working_dir = workspace
             .agent_working_dir
@@ -938,9 +1943,19 @@ pub trait ContainerService {
                 prompt,
                 executor_profile_id: executor_profile_id.clone(),
                 working_dir,
+                model: None,
             }),
-            cleanup_action.map(Box::new),
+            finish_action.map(Box::new),
         );
+        let coding_action = match find_hook(LifecycleHook::AttemptStarted) {
+            Some(hook) => lifecycle_hooks::hook_action(hook, Some(Box::new(coding_action))),
+            None => coding_action,
+        };
+        let with_worktree_hook =
+            |action: ExecutorAction| match find_hook(LifecycleHook::WorktreeCreated) {
+                Some(hook) => lifecycle_hooks::hook_action(hook, Some(Box::new(action))),
+                None => action,
+            };
 
         let execution_process = if all_parallel {
             // All parallel: start each setup independently, then start coding agent
@@ -958,16 +1973,19 @@ pub trait ContainerService {
                     tracing::warn!(?e, "Failed to start setup script in parallel mode");
                 }
             }
-            self.start_execution(
-                &workspace,
-                &session,
-                &coding_action,
-                &ExecutionProcessRunReason::CodingAgent,
-            )
-            .await?
+            let main_action = with_worktree_hook(coding_action);
+            let run_reason = match main_action.typ() {
+                ExecutorActionType::ScriptRequest(_) => ExecutionProcessRunReason::SetupScript,
+                _ => ExecutionProcessRunReason::CodingAgent,
+            };
+            self.start_execution(&workspace, &session, &main_action, &run_reason)
+                .await?
         } else {
             // Any sequential: chain ALL setups → coding agent via next_action
-            let main_action = Self::build_sequential_setup_chain(&repos_with_setup, coding_action);
+            let main_action = with_worktree_hook(Self::build_sequential_setup_chain(
+                &repos_with_setup,
+                coding_action,
+            ));
             self.start_execution(
                 &workspace,
                 &session,
@@ -980,6 +1998,11 @@ pub trait ContainerService {
         Ok(execution_process)
     }
 
+    #[tracing::instrument(
+        name = "execution_process.start",
+        skip_all,
+        fields(workspace_id = %workspace.id, session_id = %session.id, run_reason = ?run_reason)
+    )]
     async fn start_execution(
         &self,
         workspace: &Workspace,
@@ -987,6 +2010,10 @@ pub trait ContainerService {
         executor_action: &ExecutorAction,
         run_reason: &ExecutionProcessRunReason,
     ) -> Result<ExecutionProcess, ContainerError> {
+        if self.is_draining() {
+            return Err(ContainerError::Draining);
+        }
+
         // Update task status to InProgress when starting an execution
         let task = workspace
             .parent_task(&self.db().pool)
@@ -995,7 +2022,8 @@ pub trait ContainerService {
         if task.status != TaskStatus::InProgress
             && run_reason != &ExecutionProcessRunReason::DevServer
         {
-            Task::update_status(&self.db().pool, task.id, TaskStatus::InProgress).await?;
+            task_workflow::update_task_status(&self.db().pool, task.id, TaskStatus::InProgress)
+                .await?;
 
             if let Some(publisher) = self.share_publisher()
                 && let Err(err) = publisher.update_shared_task_by_id(task.id).await
@@ -1048,6 +2076,23 @@ pub trait ContainerService {
         )
         .await?;
 
+        // Only needed to reproduce results later, so never blocks the run
+        if let Err(e) = self
+            .record_environment(
+                workspace,
+                &workspace_root,
+                &execution_process,
+                executor_action,
+            )
+            .await
+        {
+            tracing::warn!(
+                "Failed to record environment of execution process {}: {}",
+                execution_process.id,
+                e
+            );
+        }
+
         if let Some(prompt) = match executor_action.typ() {
             ExecutorActionType::CodingAgentInitialRequest(coding_agent_request) => {
                 Some(coding_agent_request.prompt.clone())
@@ -1091,7 +2136,8 @@ pub trait ContainerService {
                     update_error
                 );
             }
-            Task::update_status(&self.db().pool, task.id, TaskStatus::InReview).await?;
+            task_workflow::update_task_status(&self.db().pool, task.id, TaskStatus::InReview)
+                .await?;
 
             // Emit stderr error message
             let log_message = LogMsg::Stderr(format!("Failed to start execution: {start_error}"));
@@ -1169,6 +2215,14 @@ pub trait ContainerService {
 
         // Determine the run reason of the next action
         let next_run_reason = match (action.typ(), next_action.typ()) {
+            // Scripts chained after a cleanup script belong to the cleanup phase
+            (ExecutorActionType::ScriptRequest(_), ExecutorActionType::ScriptRequest(next))
+                if next.context == ScriptContext::CleanupScript
+                    || ctx.execution_process.run_reason
+                        == ExecutionProcessRunReason::CleanupScript =>
+            {
+                ExecutionProcessRunReason::CleanupScript
+            }
             (ExecutorActionType::ScriptRequest(_), ExecutorActionType::ScriptRequest(_)) => {
                 ExecutionProcessRunReason::SetupScript
             }
@@ -1203,6 +2257,271 @@ pub trait ContainerService {
         None
     }
 
+    /// URL a dev server is reachable at: its devctl2 route, or the local address it printed on
+    /// startup.
+    async fn dev_server_url(&self, exec_id: &Uuid) -> Option<String> {
+        match self.get_devctl2_url(exec_id).await {
+            Some(url) => Some(url),
+            None => self
+                .get_msg_store_by_id(exec_id)
+                .await
+                .and_then(|store| screenshot::detect_local_url(&store.get_history())),
+        }
+    }
+
+    /// Screenshot the attempt's running dev server and keep it with the attempt. `None` when no
+    /// dev server is running or its URL is unknown.
+    async fn capture_dev_server_screenshot(
+        &self,
+        workspace: &Workspace,
+    ) -> Result<Option<AttemptScreenshot>, ContainerError> {
+        let pool = &self.db().pool;
+        let dev_servers =
+            ExecutionProcess::find_running_dev_servers_by_workspace(pool, workspace.id).await?;
+        let Some(dev_server) = dev_servers.first() else {
+            return Ok(None);
+        };
+        let Some(url) = self.dev_server_url(&dev_server.id).await else {
+            return Ok(None);
+        };
+        let png = screenshot::capture(&url)
+            .await
+            .map_err(|e| ContainerError::Other(anyhow!(e)))?;
+        let image = self
+            .image_service()
+            .store_image(&png, "dev-server-screenshot.png")
+            .await
+            .map_err(|e| ContainerError::Other(anyhow!(e)))?;
+        let shot = AttemptScreenshot::create(pool, workspace.id, image.id, &url).await?;
+        Ok(Some(shot))
+    }
+
+    /// Run the project's E2E verification against the attempt's running dev server and keep
+    /// the results, screenshots and traces with the attempt. `None` when the project has no
+    /// verification configured or no dev server is running with a known URL.
+    async fn run_e2e_verification(
+        &self,
+        workspace: &Workspace,
+    ) -> Result<Option<AttemptE2eRun>, ContainerError> {
+        let pool = &self.db().pool;
+        let task = workspace
+            .parent_task(pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        if !FeatureFlag::LifecycleHooks
+            .is_enabled(pool, Some(task.project_id))
+            .await
+        {
+            return Ok(None);
+        }
+        let Some(hook) =
+            ProjectLifecycleHook::find(pool, task.project_id, LifecycleHook::E2eVerification)
+                .await?
+        else {
+            return Ok(None);
+        };
+        let dev_servers =
+            ExecutionProcess::find_running_dev_servers_by_workspace(pool, workspace.id).await?;
+        let Some(dev_server) = dev_servers.first() else {
+            return Ok(None);
+        };
+        let Some(url) = self.dev_server_url(&dev_server.id).await else {
+            return Ok(None);
+        };
+
+        let current_dir = self.workspace_to_current_dir(workspace);
+        let (status, report, output) =
+            match e2e_verification::run(&hook.command, &current_dir, &url).await {
+                Ok(outcome) => (
+                    outcome.status(),
+                    outcome.report.unwrap_or_default(),
+                    outcome.output,
+                ),
+                Err(e) => (E2eRunStatus::Error, Default::default(), e.to_string()),
+            };
+        let data = CreateAttemptE2eRun {
+            url,
+            command: hook.command,
+            status,
+            passed: report.passed,
+            failed: report.failed,
+            flaky: report.flaky,
+            skipped: report.skipped,
+            failures: report.failures,
+            output,
+        };
+        let run = AttemptE2eRun::create(pool, workspace.id, &data).await?;
+
+        for (kind, path) in report.artifacts {
+            let name = e2e_verification::artifact_name(&path, &current_dir);
+            let stored = match tokio::fs::read(&path).await {
+                Ok(bytes) => self
+                    .attachment_service()
+                    .store_e2e_artifact(run.id, kind, &bytes, &name)
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(e) = stored {
+                tracing::warn!(
+                    "Failed to keep E2E artifact {} of run {}: {}",
+                    name,
+                    run.id,
+                    e
+                );
+            }
+        }
+        Ok(Some(run))
+    }
+
+    /// Start the attempt's E2E verification in the background.
+    /// Default implementation does nothing (for non-local deployments).
+    fn spawn_e2e_verification(&self, _workspace: &Workspace) {
+        // No-op by default
+    }
+
+    /// A blocking E2E verification stops the merge until the attempt's latest run passed.
+    async fn require_e2e_verification(
+        &self,
+        workspace: &Workspace,
+        project_id: Uuid,
+    ) -> Result<(), LifecycleHookError> {
+        let pool = &self.db().pool;
+        if !FeatureFlag::LifecycleHooks
+            .is_enabled(pool, Some(project_id))
+            .await
+        {
+            return Ok(());
+        }
+        match ProjectLifecycleHook::find(pool, project_id, LifecycleHook::E2eVerification).await? {
+            Some(hook) if hook.blocking => {}
+            _ => return Ok(()),
+        }
+        match AttemptE2eRun::find_latest_by_workspace_id(pool, workspace.id).await? {
+            Some(run) if run.status == E2eRunStatus::Passed => Ok(()),
+            Some(run) if run.status == E2eRunStatus::Error => Err(
+                LifecycleHookError::E2eNotPassed("the latest run could not start".to_string()),
+            ),
+            Some(run) => Err(LifecycleHookError::E2eNotPassed(format!(
+                "{} tests failed in the latest run",
+                run.failed
+            ))),
+            None => Err(LifecycleHookError::E2eNotPassed(
+                "it has not run yet".to_string(),
+            )),
+        }
+    }
+
+    /// Run the project's linters against the files the attempt changed and keep their findings
+    /// on the attempt. Errors the previous run did not report are sent to the agent as a
+    /// follow-up when the linter has fix follow-ups enabled. Returns the attempt's annotations.
+    async fn run_linters(
+        &self,
+        workspace: &Workspace,
+    ) -> Result<Vec<AttemptLintAnnotation>, ContainerError> {
+        let pool = &self.db().pool;
+        let task = workspace
+            .parent_task(pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let linters = ProjectLinter::find_by_project_id(pool, task.project_id).await?;
+        if linters.is_empty() {
+            return Ok(AttemptLintAnnotation::find_by_workspace_id(pool, workspace.id).await?);
+        }
+        let previous = AttemptLintAnnotation::find_by_workspace_id(pool, workspace.id).await?;
+        let changed: Vec<String> = self
+            .diff_snapshot(workspace, true)
+            .await?
+            .into_iter()
+            .filter_map(|diff| diff.new_path)
+            .collect();
+        let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+        let workspace_root = self.workspace_to_current_dir(workspace);
+
+        for linter in &linters {
+            let mut annotations = Vec::new();
+            let mut failed = false;
+            for repo in &repos {
+                let prefix = format!("{}/", repo.name);
+                let files: Vec<String> = changed
+                    .iter()
+                    .filter_map(|path| path.strip_prefix(&prefix))
+                    .filter(|path| lint::handles(linter.linter, path))
+                    .map(str::to_string)
+                    .collect();
+                if files.is_empty() {
+                    continue;
+                }
+                let repo_dir = workspace_root.join(&repo.name);
+                match lint::run(linter.linter, &linter.command, &repo_dir, &files).await {
+                    Ok(found) => annotations.extend(
+                        found
+                            .into_iter()
+                            .filter(|annotation| files.contains(&annotation.file))
+                            .map(|mut annotation| {
+                                annotation.file = format!("{prefix}{}", annotation.file);
+                                annotation
+                            }),
+                    ),
+                    Err(e) => {
+                        tracing::warn!("Failed to lint workspace {}: {}", workspace.id, e);
+                        failed = true;
+                    }
+                }
+            }
+            // Keep the previous findings rather than clearing them when the linter broke
+            if !failed {
+                AttemptLintAnnotation::replace_for_linter(
+                    pool,
+                    workspace.id,
+                    linter.linter,
+                    &annotations,
+                )
+                .await?;
+            }
+        }
+
+        let annotations = AttemptLintAnnotation::find_by_workspace_id(pool, workspace.id).await?;
+        let is_new = |annotation: &AttemptLintAnnotation| {
+            !previous.iter().any(|seen| {
+                seen.linter == annotation.linter
+                    && seen.file == annotation.file
+                    && seen.line == annotation.line
+                    && seen.col == annotation.col
+                    && seen.rule == annotation.rule
+                    && seen.message == annotation.message
+            })
+        };
+        let fix_errors: Vec<AttemptLintAnnotation> = annotations
+            .iter()
+            .filter(|annotation| annotation.severity == LintSeverity::Error)
+            .filter(|annotation| {
+                linters
+                    .iter()
+                    .any(|linter| linter.linter == annotation.linter && linter.fix_follow_up)
+            })
+            .cloned()
+            .collect();
+        // Errors the agent already failed to fix are not sent again, so follow-ups cannot loop
+        if fix_errors.iter().any(is_new)
+            && !ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+                pool,
+                workspace.id,
+            )
+            .await?
+        {
+            self.start_prompt_follow_up(workspace, lint::fix_prompt(&fix_errors))
+                .await?;
+        }
+        Ok(annotations)
+    }
+
+    /// Lint the attempt in the background.
+    /// Default implementation does nothing (for non-local deployments).
+    fn spawn_linters(&self, _workspace: &Workspace) {
+        // No-op by default
+    }
+
     /// Remove the devctl2 subdomain URL for an execution process.
     /// Default implementation returns None (for non-local deployments).
     async fn remove_devctl2_url(&self, _exec_id: &Uuid) -> Option<String> {
@@ -0,0 +1,164 @@
+//! A coding agent that overflows its context window can't go on with its session. Instead of
+//! failing the attempt, the conversation so far is summarized and a fresh session picks the
+//! work up from the summary and the workspace's diff.
+
+use executors::actions::{
+    ExecutorAction, ExecutorActionType, coding_agent_initial::CodingAgentInitialRequest,
+};
+use utils::text::truncate_to_char_boundary;
+
+use crate::services::{
+    attempt_summary::tail,
+    llm::{LlmError, LlmService},
+};
+
+/// Budget for the conversation sent to the model for summarizing, in bytes; the end is kept
+const MAX_TRANSCRIPT_BYTES: usize = 48_000;
+/// Conversation kept in the seed when no model is configured to summarize it, in bytes
+const MAX_UNSUMMARIZED_BYTES: usize = 8_000;
+/// Budget for the diff included in the seed, in bytes
+const MAX_DIFF_BYTES: usize = 16_000;
+
+/// First line of every seeded prompt, also how a handoff run is told apart from the first one
+pub const HANDOFF_HEADER: &str =
+    "You are continuing work from an earlier session that ran out of context.";
+
+const HANDOFF_SYSTEM_PROMPT: &str = "You condense the conversation of a coding agent that ran \
+out of context so that a fresh session can carry on. From the task and the conversation, write \
+at most 400 words of Markdown covering what has been done, decisions made and why, what was in \
+progress when the session stopped, and what remains. Keep file paths, commands and error \
+messages that matter verbatim. Reply with the summary only.";
+
+/// Whether the action already runs a session seeded after an overflow. A handoff that overflows
+/// again is left to fail rather than summarized in a loop.
+pub fn is_handoff(action: &ExecutorAction) -> bool {
+    matches!(
+        action.typ(),
+        ExecutorActionType::CodingAgentInitialRequest(request)
+            if request.prompt.starts_with(HANDOFF_HEADER)
+    )
+}
+
+/// Ask the model to condense the conversation for the next session.
+pub async fn summarize_conversation(
+    llm: &LlmService,
+    task_prompt: &str,
+    transcript: &str,
+) -> Result<String, LlmError> {
+    let transcript = tail(transcript.trim(), MAX_TRANSCRIPT_BYTES);
+    let prompt = format!("Task:\n{task_prompt}\n\nConversation (oldest first):\n{transcript}");
+
+    let summary = llm.complete(HANDOFF_SYSTEM_PROMPT, &prompt).await?;
+    let summary = summary.trim();
+    if summary.is_empty() {
+        return Err(LlmError::InvalidResponse("summary is empty".to_string()));
+    }
+    Ok(summary.to_string())
+}
+
+/// Stand-in for the summary when no model is configured: the end of the conversation.
+pub fn unsummarized(transcript: &str) -> String {
+    match transcript.trim() {
+        "" => "(no conversation was recorded)".to_string(),
+        t if t.len() > MAX_UNSUMMARIZED_BYTES => {
+            format!(
+                "[earlier conversation omitted]\n{}",
+                tail(t, MAX_UNSUMMARIZED_BYTES)
+            )
+        }
+        t => t.to_string(),
+    }
+}
+
+/// Prompt starting the fresh session from the task, the summary and the diff so far.
+pub fn seed_prompt(task_prompt: &str, summary: &str, diff: &str) -> String {
+    let diff = match diff.trim() {
+        "" => "(no changes yet)".to_string(),
+        d if d.len() > MAX_DIFF_BYTES => format!(
+            "{}\n[diff truncated, inspect the working tree for the rest]",
+            truncate_to_char_boundary(d, MAX_DIFF_BYTES)
+        ),
+        d => d.to_string(),
+    };
+    format!(
+        "{HANDOFF_HEADER} The changes made so far are already in the working tree.\n\n\
+         Task:\n{task_prompt}\n\n\
+         Summary of the earlier session:\n{summary}\n\n\
+         Changes so far:\n{diff}\n\n\
+         Continue the task from where the earlier session left off."
+    )
+}
+
+/// Action starting a fresh session with `prompt` in place of the overflowed run, keeping its
+/// profile, working directory, model and what runs after it. `None` for scripts.
+pub fn handoff_action(failed: &ExecutorAction, prompt: String) -> Option<ExecutorAction> {
+    let (executor_profile_id, working_dir, model) = match failed.typ() {
+        ExecutorActionType::CodingAgentInitialRequest(request) => (
+            &request.executor_profile_id,
+            &request.working_dir,
+            &request.model,
+        ),
+        ExecutorActionType::CodingAgentFollowUpRequest(request) => (
+            &request.executor_profile_id,
+            &request.working_dir,
+            &request.model,
+        ),
+        ExecutorActionType::ScriptRequest(_) => return None,
+    };
+    Some(ExecutorAction::new(
+        ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt,
+            executor_profile_id: executor_profile_id.clone(),
+            working_dir: working_dir.clone(),
+            model: model.clone(),
+        }),
+        failed.next_action.clone(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::{
+        actions::coding_agent_follow_up::CodingAgentFollowUpRequest, executors::BaseCodingAgent,
+        profile::ExecutorProfileId,
+    };
+
+    use super::*;
+
+    fn follow_up() -> ExecutorAction {
+        ExecutorAction::new(
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt: "Also cover the edge cases".to_string(),
+                session_id: "agent-session".to_string(),
+                executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+                working_dir: Some("app".to_string()),
+                model: Some("sonnet".to_string()),
+            }),
+            None,
+        )
+    }
+
+    #[test]
+    fn handoff_starts_fresh_session_with_seed() {
+        let prompt = seed_prompt("Fix the bug", "Found the cause in auth.rs", "");
+        let action = handoff_action(&follow_up(), prompt).unwrap();
+        let ExecutorActionType::CodingAgentInitialRequest(request) = action.typ() else {
+            panic!("expected a fresh session");
+        };
+        assert!(request.prompt.contains("Found the cause in auth.rs"));
+        assert!(request.prompt.contains("(no changes yet)"));
+        assert_eq!(request.working_dir.as_deref(), Some("app"));
+        assert_eq!(request.model.as_deref(), Some("sonnet"));
+        assert!(is_handoff(&action));
+        assert!(!is_handoff(&follow_up()));
+    }
+
+    #[test]
+    fn unsummarized_keeps_end_of_conversation() {
+        let transcript = format!("User: start\n{}Agent: latest", "x".repeat(10_000));
+        let kept = unsummarized(&transcript);
+        assert!(kept.starts_with("[earlier conversation omitted]"));
+        assert!(kept.ends_with("Agent: latest"));
+        assert!(!kept.contains("User: start"));
+    }
+}
@@ -279,7 +279,7 @@ pub async fn create(
     ))
 }
 
-fn prefix_path(path: String, prefix: Option<&str>) -> String {
+pub(crate) fn prefix_path(path: String, prefix: Option<&str>) -> String {
     match prefix {
         Some(p) => format!("{p}/{path}"),
         None => path,
@@ -0,0 +1,351 @@
+//! Browser E2E verification: a project's Playwright command run against an attempt's dev server,
+//! with its JSON report parsed into per-test results.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+
+use db::models::attempt_e2e_run::{E2eArtifactKind, E2eRunStatus, E2eTestFailure};
+use serde::Deserialize;
+use thiserror::Error;
+use tokio::process::Command;
+use utils::shell::get_shell_command;
+
+const RUN_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+/// Output kept with a run; Playwright's list reporter is verbose on large suites
+const OUTPUT_TAIL_BYTES: usize = 16 * 1024;
+/// Screenshots and traces kept per run
+const MAX_ARTIFACTS: usize = 20;
+const REPORT_FILE: &str = "report.json";
+
+#[derive(Debug, Error)]
+pub enum E2eVerificationError {
+    #[error("Failed to run the E2E command: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("E2E command did not finish within {0:?}")]
+    Timeout(Duration),
+}
+
+/// Test counts and failures from a Playwright JSON report.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct E2eReport {
+    pub passed: i64,
+    pub failed: i64,
+    pub flaky: i64,
+    pub skipped: i64,
+    pub failures: Vec<E2eTestFailure>,
+    /// Screenshots and traces attached to test results
+    pub artifacts: Vec<(E2eArtifactKind, PathBuf)>,
+}
+
+#[derive(Debug)]
+pub struct E2eOutcome {
+    pub exit_code: Option<i32>,
+    /// Tail of stdout and stderr
+    pub output: String,
+    pub report: Option<E2eReport>,
+}
+
+impl E2eOutcome {
+    pub fn status(&self) -> E2eRunStatus {
+        let tests_failed = self.report.as_ref().is_some_and(|r| r.failed > 0);
+        if self.exit_code == Some(0) && !tests_failed {
+            E2eRunStatus::Passed
+        } else {
+            E2eRunStatus::Failed
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Report {
+    #[serde(default)]
+    suites: Vec<Suite>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Suite {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    file: String,
+    #[serde(default)]
+    specs: Vec<Spec>,
+    #[serde(default)]
+    suites: Vec<Suite>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Spec {
+    title: String,
+    #[serde(default)]
+    file: String,
+    #[serde(default)]
+    tests: Vec<Test>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Test {
+    /// expected, unexpected, flaky or skipped
+    status: String,
+    #[serde(default)]
+    results: Vec<TestResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TestResult {
+    #[serde(default)]
+    error: Option<TestError>,
+    #[serde(default)]
+    attachments: Vec<Attachment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TestError {
+    #[serde(default)]
+    message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Attachment {
+    name: String,
+    #[serde(rename = "contentType")]
+    content_type: String,
+    path: Option<PathBuf>,
+}
+
+fn artifact_kind(attachment: &Attachment) -> Option<E2eArtifactKind> {
+    if attachment.name == "trace" {
+        Some(E2eArtifactKind::Trace)
+    } else if attachment.content_type.starts_with("image/") {
+        Some(E2eArtifactKind::Screenshot)
+    } else {
+        None
+    }
+}
+
+fn collect_suite(suite: &Suite, titles: &[&str], report: &mut E2eReport) {
+    for spec in &suite.specs {
+        let title = titles
+            .iter()
+            .copied()
+            .chain([spec.title.as_str()])
+            .collect::<Vec<_>>()
+            .join(" › ");
+        for test in &spec.tests {
+            match test.status.as_str() {
+                "expected" => report.passed += 1,
+                "flaky" => report.flaky += 1,
+                "skipped" => report.skipped += 1,
+                _ => {
+                    report.failed += 1;
+                    let error = test
+                        .results
+                        .iter()
+                        .find_map(|r| r.error.as_ref()?.message.as_deref())
+                        .map(|message| strip_ansi_escapes::strip_str(message).trim().to_string());
+                    report.failures.push(E2eTestFailure {
+                        title: title.clone(),
+                        file: if spec.file.is_empty() {
+                            suite.file.clone()
+                        } else {
+                            spec.file.clone()
+                        },
+                        error,
+                    });
+                }
+            }
+            for attachment in test.results.iter().flat_map(|r| &r.attachments) {
+                if let (Some(kind), Some(path)) = (artifact_kind(attachment), &attachment.path) {
+                    report.artifacts.push((kind, path.clone()));
+                }
+            }
+        }
+    }
+    for child in &suite.suites {
+        let mut child_titles = titles.to_vec();
+        if !child.title.is_empty() {
+            child_titles.push(&child.title);
+        }
+        collect_suite(child, &child_titles, report);
+    }
+}
+
+/// Parse Playwright's JSON reporter output. Top level suites are spec files, so only nested
+/// describe blocks prefix test titles.
+pub fn parse_report(json: &str) -> Option<E2eReport> {
+    let parsed: Report = serde_json::from_str(json.trim()).ok()?;
+    let mut report = E2eReport::default();
+    for suite in &parsed.suites {
+        collect_suite(suite, &[], &mut report);
+    }
+    report.artifacts.truncate(MAX_ARTIFACTS);
+    Some(report)
+}
+
+/// Name an artifact is kept under: its path relative to the directory the tests ran in.
+pub fn artifact_name(path: &Path, current_dir: &Path) -> String {
+    path.strip_prefix(current_dir)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+fn output_tail(stdout: &[u8], stderr: &[u8]) -> String {
+    let output = format!(
+        "{}{}",
+        String::from_utf8_lossy(stdout),
+        String::from_utf8_lossy(stderr)
+    );
+    let output = strip_ansi_escapes::strip_str(&output);
+    if output.len() <= OUTPUT_TAIL_BYTES {
+        return output;
+    }
+    let mut start = output.len() - OUTPUT_TAIL_BYTES;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    output[start..].to_string()
+}
+
+/// Run `command` in `current_dir` with the dev server `url` exported as `BASE_URL` and
+/// `PLAYWRIGHT_BASE_URL`. The JSON reporter writes to a temporary file when the command enables
+/// it; otherwise its report is read from stdout.
+pub async fn run(
+    command: &str,
+    current_dir: &Path,
+    url: &str,
+) -> Result<E2eOutcome, E2eVerificationError> {
+    let dir = tempfile::TempDir::new()?;
+    let report_path = dir.path().join(REPORT_FILE);
+    let (shell_cmd, shell_arg) = get_shell_command();
+    let mut cmd = Command::new(shell_cmd);
+    cmd.arg(shell_arg)
+        .arg(command)
+        .current_dir(current_dir)
+        .env("BASE_URL", url)
+        .env("PLAYWRIGHT_BASE_URL", url)
+        .env("PLAYWRIGHT_JSON_OUTPUT_FILE", &report_path)
+        .env("PLAYWRIGHT_JSON_OUTPUT_NAME", &report_path)
+        .env("CI", "1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let result = tokio::time::timeout(RUN_TIMEOUT, cmd.output())
+        .await
+        .map_err(|_| E2eVerificationError::Timeout(RUN_TIMEOUT))??;
+
+    let report = match tokio::fs::read_to_string(&report_path).await {
+        Ok(json) => parse_report(&json),
+        Err(_) => parse_report(&String::from_utf8_lossy(&result.stdout)),
+    };
+    Ok(E2eOutcome {
+        exit_code: result.status.code(),
+        output: output_tail(&result.stdout, &result.stderr),
+        report,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = r#"{
+        "config": {},
+        "suites": [{
+            "title": "home.spec.ts",
+            "file": "home.spec.ts",
+            "specs": [{
+                "title": "has title",
+                "file": "home.spec.ts",
+                "tests": [{ "status": "expected", "results": [{ "status": "passed" }] }]
+            }],
+            "suites": [{
+                "title": "header",
+                "file": "home.spec.ts",
+                "specs": [{
+                    "title": "shows the logo",
+                    "file": "home.spec.ts",
+                    "tests": [{
+                        "status": "unexpected",
+                        "results": [{
+                            "status": "failed",
+                            "error": { "message": "\u001b[31mExpected visible\u001b[39m" },
+                            "attachments": [
+                                { "name": "screenshot", "contentType": "image/png", "path": "/w/test-results/logo/test-failed-1.png" },
+                                { "name": "trace", "contentType": "application/zip", "path": "/w/test-results/logo/trace.zip" },
+                                { "name": "stdout", "contentType": "text/plain", "body": "hi" }
+                            ]
+                        }]
+                    }]
+                }, {
+                    "title": "retries",
+                    "file": "home.spec.ts",
+                    "tests": [{ "status": "flaky", "results": [] }, { "status": "skipped", "results": [] }]
+                }]
+            }]
+        }],
+        "errors": []
+    }"#;
+
+    #[test]
+    fn parses_counts_failures_and_artifacts() {
+        let report = parse_report(REPORT).unwrap();
+        assert_eq!(
+            (report.passed, report.failed, report.flaky, report.skipped),
+            (1, 1, 1, 1)
+        );
+        assert_eq!(
+            report.failures,
+            vec![E2eTestFailure {
+                title: "header › shows the logo".to_string(),
+                file: "home.spec.ts".to_string(),
+                error: Some("Expected visible".to_string()),
+            }]
+        );
+        assert_eq!(
+            report.artifacts,
+            vec![
+                (
+                    E2eArtifactKind::Screenshot,
+                    PathBuf::from("/w/test-results/logo/test-failed-1.png")
+                ),
+                (
+                    E2eArtifactKind::Trace,
+                    PathBuf::from("/w/test-results/logo/trace.zip")
+                ),
+            ]
+        );
+        assert_eq!(
+            artifact_name(&report.artifacts[1].1, Path::new("/w")),
+            "test-results/logo/trace.zip"
+        );
+        assert!(parse_report("Running 3 tests using 1 worker").is_none());
+    }
+
+    #[test]
+    fn failing_tests_fail_the_run_even_on_a_zero_exit() {
+        let outcome = |exit_code, failed| E2eOutcome {
+            exit_code: Some(exit_code),
+            output: String::new(),
+            report: Some(E2eReport {
+                failed,
+                ..Default::default()
+            }),
+        };
+        assert_eq!(outcome(0, 0).status(), E2eRunStatus::Passed);
+        assert_eq!(outcome(0, 2).status(), E2eRunStatus::Failed);
+        assert_eq!(outcome(1, 0).status(), E2eRunStatus::Failed);
+    }
+
+    #[test]
+    fn keeps_the_end_of_long_output() {
+        let stdout = "x".repeat(OUTPUT_TAIL_BYTES) + "last line";
+        let tail = output_tail(stdout.as_bytes(), b"");
+        assert_eq!(tail.len(), OUTPUT_TAIL_BYTES);
+        assert!(tail.ends_with("last line"));
+    }
+}
@@ -0,0 +1,90 @@
+//! Snapshot of what an execution process runs with: the agent's CLI and model, the platform, and
+//! each repo's commits and dependency lockfiles. Recorded when the process starts, so a result
+//! can be reproduced and a regression traced to a tool version change.
+
+use std::path::Path;
+
+use db::models::execution_process_environment::{
+    CreateExecutionProcessEnvironment, LockfileHash, RepoEnvironment,
+};
+use executors::{actions::ExecutorAction, executors::pinned_cli_version, profile::ExecutorConfigs};
+use sha2::{Digest, Sha256};
+
+/// Lockfiles looked for at the root of each repo
+pub const LOCKFILES: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+    "bun.lock",
+    "bun.lockb",
+    "poetry.lock",
+    "uv.lock",
+    "Pipfile.lock",
+    "Gemfile.lock",
+    "go.sum",
+    "composer.lock",
+];
+
+/// SHA-256 of the lockfiles present at the root of `repo_dir`
+pub fn lockfile_hashes(repo_dir: &Path) -> Vec<LockfileHash> {
+    LOCKFILES
+        .iter()
+        .filter_map(|name| {
+            let contents = std::fs::read(repo_dir.join(name)).ok()?;
+            Some(LockfileHash {
+                path: name.to_string(),
+                sha256: hex::encode(Sha256::digest(&contents)),
+            })
+        })
+        .collect()
+}
+
+/// Environment of a process about to run `action`, given the state of the attempt's repos
+pub fn snapshot(
+    action: &ExecutorAction,
+    repos: Vec<RepoEnvironment>,
+) -> CreateExecutionProcessEnvironment {
+    let agent = action
+        .executor_profile_id()
+        .and_then(|profile_id| ExecutorConfigs::get_cached().get_coding_agent(profile_id));
+    let cli_command = agent.as_ref().map(|agent| agent.cli_command());
+    CreateExecutionProcessEnvironment {
+        executor: action.executor_profile_id().map(|id| id.to_string()),
+        cli_version: cli_command
+            .as_deref()
+            .and_then(pinned_cli_version)
+            .map(str::to_string),
+        cli_command,
+        model: agent
+            .as_ref()
+            .and_then(|agent| agent.model())
+            .map(str::to_string),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        repos,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_lockfiles_at_repo_root() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.lock"), "version = 4\n").unwrap();
+        std::fs::write(dir.path().join("README.md"), "not a lockfile").unwrap();
+        std::fs::create_dir(dir.path().join("web")).unwrap();
+        std::fs::write(dir.path().join("web/package-lock.json"), "{}").unwrap();
+
+        let hashes = lockfile_hashes(dir.path());
+        assert_eq!(hashes.len(), 1);
+        assert_eq!(hashes[0].path, "Cargo.lock");
+        assert_eq!(
+            hashes[0].sha256,
+            hex::encode(Sha256::digest(b"version = 4\n"))
+        );
+    }
+}
@@ -0,0 +1,390 @@
+use chrono::{Duration, Utc};
+use db::models::{
+    error_tracker::{ErrorTrackerIntegration, ErrorTrackerIssue},
+    task::{CreateTask, Task},
+    task_prompt::TaskPrompt,
+};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use subtle::ConstantTimeEq;
+use ts_rs::TS;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Frames beyond this are left out of task descriptions
+const MAX_FRAMES: usize = 50;
+
+/// Verify the `Sentry-Hook-Signature` header, the hex HMAC-SHA256 of the body keyed with the
+/// integration's client secret.
+pub fn verify_sentry_signature(secret: &[u8], signature: &str, payload: &[u8]) -> bool {
+    let Ok(expected) = hex::decode(signature.trim()) else {
+        return false;
+    };
+    let Ok(mut mac) = HmacSha256::new_from_slice(secret) else {
+        return false;
+    };
+    mac.update(payload);
+    mac.finalize().into_bytes()[..].ct_eq(&expected).into()
+}
+
+/// Compare a secret presented as a bearer token without leaking timing.
+pub fn secret_matches(secret: &str, presented: &str) -> bool {
+    secret.as_bytes().ct_eq(presented.as_bytes()).into()
+}
+
+/// An error reported by Sentry or another error tracker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorReport {
+    /// Identifies the issue across events; repeated reports update the same task
+    pub fingerprint: String,
+    pub title: String,
+    pub message: Option<String>,
+    pub stack_trace: Option<String>,
+    pub url: Option<String>,
+}
+
+/// Payload of trackers without a dedicated format.
+#[derive(Debug, Deserialize)]
+struct GenericErrorReport {
+    fingerprint: Option<String>,
+    title: String,
+    message: Option<String>,
+    stack_trace: Option<String>,
+    url: Option<String>,
+}
+
+/// Sentry `issue` and `event_alert` webhooks.
+#[derive(Debug, Deserialize)]
+struct SentryWebhook {
+    data: SentryData,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentryData {
+    event: Option<SentryEvent>,
+    issue: Option<SentryIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentryEvent {
+    title: String,
+    message: Option<String>,
+    culprit: Option<String>,
+    web_url: Option<String>,
+    issue_id: Option<Value>,
+    #[serde(default)]
+    fingerprint: Vec<String>,
+    exception: Option<SentryExceptions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentryIssue {
+    id: Value,
+    title: String,
+    culprit: Option<String>,
+    #[serde(alias = "web_url")]
+    permalink: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentryExceptions {
+    #[serde(default)]
+    values: Vec<SentryException>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentryException {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    value: Option<String>,
+    stacktrace: Option<SentryStacktrace>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentryStacktrace {
+    #[serde(default)]
+    frames: Vec<SentryFrame>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentryFrame {
+    function: Option<String>,
+    filename: Option<String>,
+    module: Option<String>,
+    lineno: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorReportOutcome {
+    Created,
+    /// The fingerprint already had a task; its event count was increased
+    Duplicate,
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn id_string(id: &Value) -> Option<String> {
+    match id {
+        Value::String(id) => Some(id.clone()),
+        Value::Number(id) => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+fn title_fingerprint(title: &str) -> String {
+    hex::encode(Sha256::digest(title.trim().as_bytes()))
+}
+
+/// Render frames most recent call first, the order Sentry shows them in.
+fn format_stack_trace(exceptions: &[SentryException]) -> Option<String> {
+    let mut lines = Vec::new();
+    for exception in exceptions.iter().rev() {
+        let header = match (exception.kind.as_deref(), exception.value.as_deref()) {
+            (Some(kind), Some(value)) => format!("{kind}: {value}"),
+            (Some(kind), None) => kind.to_string(),
+            (None, Some(value)) => value.to_string(),
+            (None, None) => continue,
+        };
+        lines.push(header);
+        let frames = exception
+            .stacktrace
+            .as_ref()
+            .map(|s| s.frames.as_slice())
+            .unwrap_or_default();
+        for frame in frames.iter().rev().take(MAX_FRAMES) {
+            let function = frame.function.as_deref().unwrap_or("<unknown>");
+            let location = frame
+                .filename
+                .as_deref()
+                .or(frame.module.as_deref())
+                .unwrap_or("<unknown>");
+            match frame.lineno {
+                Some(line) => lines.push(format!("  at {function} ({location}:{line})")),
+                None => lines.push(format!("  at {function} ({location})")),
+            }
+        }
+    }
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+impl ErrorReport {
+    /// Parse a Sentry webhook, or a generic `{fingerprint, title, message, stack_trace, url}`
+    /// payload when the body is not one.
+    pub fn parse(body: &[u8]) -> Result<Self, serde_json::Error> {
+        let value: Value = serde_json::from_slice(body)?;
+        if value.get("data").is_none() {
+            let report: GenericErrorReport = serde_json::from_value(value)?;
+            let fingerprint =
+                non_empty(report.fingerprint).unwrap_or_else(|| title_fingerprint(&report.title));
+            return Ok(Self {
+                fingerprint,
+                title: report.title.trim().to_string(),
+                message: non_empty(report.message),
+                stack_trace: non_empty(report.stack_trace),
+                url: non_empty(report.url),
+            });
+        }
+
+        let webhook: SentryWebhook = serde_json::from_value(value)?;
+        match (webhook.data.event, webhook.data.issue) {
+            (Some(event), _) => {
+                let fingerprint = event
+                    .issue_id
+                    .as_ref()
+                    .and_then(id_string)
+                    .map(|id| format!("sentry:{id}"))
+                    .or_else(|| {
+                        (!event.fingerprint.is_empty()).then(|| event.fingerprint.join(":"))
+                    })
+                    .unwrap_or_else(|| title_fingerprint(&event.title));
+                let stack_trace = event
+                    .exception
+                    .as_ref()
+                    .and_then(|e| format_stack_trace(&e.values));
+                Ok(Self {
+                    fingerprint,
+                    title: event.title.trim().to_string(),
+                    message: non_empty(event.message).or(non_empty(event.culprit)),
+                    stack_trace,
+                    url: non_empty(event.web_url),
+                })
+            }
+            (None, Some(issue)) => Ok(Self {
+                fingerprint: id_string(&issue.id)
+                    .map(|id| format!("sentry:{id}"))
+                    .unwrap_or_else(|| title_fingerprint(&issue.title)),
+                title: issue.title.trim().to_string(),
+                message: non_empty(issue.culprit),
+                stack_trace: None,
+                url: non_empty(issue.permalink),
+            }),
+            (None, None) => Err(serde::de::Error::custom(
+                "Sentry payload has neither an event nor an issue",
+            )),
+        }
+    }
+
+    pub fn task_description(&self) -> String {
+        let mut sections = Vec::new();
+        if let Some(message) = &self.message {
+            sections.push(message.clone());
+        }
+        if let Some(stack_trace) = &self.stack_trace {
+            sections.push(format!("```\n{stack_trace}\n```"));
+        }
+        if let Some(url) = &self.url {
+            sections.push(format!("Error tracker issue: {url}"));
+        }
+        sections.join("\n\n")
+    }
+
+    /// Prompt of auto-started attempts.
+    pub fn fix_prompt(&self) -> String {
+        format!(
+            "Diagnose and fix the following error reported by our error tracker. Use the stack \
+             trace to find the root cause, fix it, and add a regression test where practical.\n\n\
+             {}\n\n{}",
+            self.title,
+            self.task_description()
+        )
+    }
+}
+
+/// Create a task for a new fingerprint, or count another event of a known one.
+pub async fn record_report(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    report: &ErrorReport,
+) -> Result<(ErrorReportOutcome, Uuid), sqlx::Error> {
+    if let Some(issue) =
+        ErrorTrackerIssue::find_by_fingerprint(pool, project_id, &report.fingerprint).await?
+    {
+        ErrorTrackerIssue::record_event(pool, project_id, &report.fingerprint).await?;
+        return Ok((ErrorReportOutcome::Duplicate, issue.task_id));
+    }
+
+    let description = report.task_description();
+    let create_task = CreateTask {
+        project_id,
+        title: report.title.clone(),
+        description: (!description.is_empty()).then_some(description),
+        status: None,
+        parent_workspace_id: None,
+        image_ids: None,
+        shared_task_id: None,
+        generate_details: None,
+    };
+    let task = Task::create(pool, &create_task, Uuid::new_v4()).await?;
+    ErrorTrackerIssue::create(pool, project_id, &report.fingerprint, task.id).await?;
+    tracing::info!(
+        "Created task {} for error tracker issue {}",
+        task.id,
+        report.fingerprint
+    );
+    Ok((ErrorReportOutcome::Created, task.id))
+}
+
+/// Whether a fix attempt may be started for a new issue without exceeding the daily cost cap.
+pub async fn can_auto_start(
+    pool: &SqlitePool,
+    integration: &ErrorTrackerIntegration,
+) -> Result<bool, sqlx::Error> {
+    if !integration.auto_start {
+        return Ok(false);
+    }
+    let Some(cap) = integration.daily_cost_cap_usd else {
+        return Ok(true);
+    };
+    let spent = ErrorTrackerIssue::auto_start_cost_since(
+        pool,
+        integration.project_id,
+        Utc::now() - Duration::hours(24),
+    )
+    .await?;
+    if spent >= cap {
+        tracing::info!(
+            "Not auto-starting a fix for project {}: ${:.2} of ${:.2} daily cap spent",
+            integration.project_id,
+            spent,
+            cap
+        );
+    }
+    Ok(spent < cap)
+}
+
+/// Store the fix prompt so the attempt runs on it instead of the task description.
+pub async fn prepare_fix_attempt(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    task_id: Uuid,
+    report: &ErrorReport,
+) -> Result<(), sqlx::Error> {
+    TaskPrompt::create(pool, task_id, &report.fix_prompt()).await?;
+    ErrorTrackerIssue::mark_auto_started(pool, project_id, &report.fingerprint).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sentry_event_alerts() {
+        let body = br#"{
+            "action": "triggered",
+            "data": {
+                "event": {
+                    "title": "TypeError: x is undefined",
+                    "culprit": "app/save",
+                    "web_url": "https://sentry.io/issues/42/events/1/",
+                    "issue_id": "42",
+                    "exception": {"values": [{
+                        "type": "TypeError",
+                        "value": "x is undefined",
+                        "stacktrace": {"frames": [
+                            {"function": "main", "filename": "app.js", "lineno": 1},
+                            {"function": "save", "filename": "save.js", "lineno": 12}
+                        ]}
+                    }]}
+                }
+            }
+        }"#;
+        let report = ErrorReport::parse(body).unwrap();
+
+        assert_eq!(report.fingerprint, "sentry:42");
+        assert_eq!(report.message.as_deref(), Some("app/save"));
+        assert_eq!(
+            report.stack_trace.as_deref(),
+            Some("TypeError: x is undefined\n  at save (save.js:12)\n  at main (app.js:1)")
+        );
+    }
+
+    #[test]
+    fn generic_reports_fall_back_to_title_fingerprint() {
+        let report = ErrorReport::parse(br#"{"title": "Disk full"}"#).unwrap();
+        let again = ErrorReport::parse(br#"{"title": " Disk full ", "message": ""}"#).unwrap();
+
+        assert_eq!(report.fingerprint, again.fingerprint);
+        assert_eq!(report.message, None);
+        assert!(ErrorReport::parse(br#"{"data": {}}"#).is_err());
+    }
+
+    #[test]
+    fn verifies_sentry_signatures() {
+        let payload = br#"{"action":"created"}"#;
+        let mut mac = HmacSha256::new_from_slice(b"secret").unwrap();
+        mac.update(payload);
+        let signature = hex::encode(mac.finalize().into_bytes());
+
+        assert!(verify_sentry_signature(b"secret", &signature, payload));
+        assert!(!verify_sentry_signature(b"other", &signature, payload));
+    }
+}
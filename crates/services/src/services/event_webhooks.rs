@@ -0,0 +1,279 @@
+//! Outgoing webhooks for approvals and questions, so external systems such as paging tools or
+//! chat bots can alert someone and drive the response through the API.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use db::{
+    DBService,
+    models::{
+        execution_process::ExecutionProcess,
+        project_event_webhook::{EventWebhookKind, ProjectEventWebhook},
+    },
+};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use utils::{
+    approvals::{ApprovalRequest, ApprovalStatus},
+    user_questions::UserQuestionRequest,
+};
+use uuid::Uuid;
+
+use crate::services::{
+    approvals::Approvals,
+    events::{EntityEvent, EntityEventEnvelope, EntityEventSubscriber},
+    secret_cipher::SecretCipher,
+    user_questions::UserQuestions,
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// `sha256=<hex>` HMAC of the request body keyed with the webhook's secret
+pub const SIGNATURE_HEADER: &str = "X-Vibe-Signature";
+pub const EVENT_HEADER: &str = "X-Vibe-Event";
+
+/// Body posted to an outgoing webhook.
+#[derive(Debug, Clone, Serialize)]
+pub struct EventWebhookPayload {
+    pub event: EventWebhookKind,
+    pub project_id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: Option<String>,
+    pub execution_process_id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    /// Approval waiting for a decision, with the tool call to render
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval: Option<ApprovalRequest>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_status: Option<ApprovalStatus>,
+    /// Question waiting for an answer, with its options
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub question: Option<UserQuestionRequest>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub question_id: Option<String>,
+    /// False when the question timed out without an answer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answered: Option<bool>,
+    /// API path that resolves the approval or question
+    pub respond_path: String,
+}
+
+pub fn sign(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Calls the project webhooks subscribed to approval and question events.
+pub struct EventWebhookSubscriber {
+    db: DBService,
+    cipher: SecretCipher,
+    approvals: Approvals,
+    questions: UserQuestions,
+    http: reqwest::Client,
+}
+
+impl EventWebhookSubscriber {
+    pub fn new(
+        db: DBService,
+        cipher: SecretCipher,
+        approvals: Approvals,
+        questions: UserQuestions,
+    ) -> Self {
+        let http = utils::http::client_builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self {
+            db,
+            cipher,
+            approvals,
+            questions,
+            http,
+        }
+    }
+
+    fn payload(&self, envelope: &EntityEventEnvelope) -> Option<EventWebhookPayload> {
+        let project_id = envelope.project_id?;
+        let payload = |event, task_id, execution_process_id, respond_path| EventWebhookPayload {
+            event,
+            project_id,
+            task_id,
+            task_title: None,
+            execution_process_id,
+            timestamp: envelope.timestamp,
+            approval: None,
+            approval_id: None,
+            approval_status: None,
+            question: None,
+            question_id: None,
+            answered: None,
+            respond_path,
+        };
+        let payload = match &envelope.event {
+            EntityEvent::ApprovalPending {
+                approval_id,
+                execution_process_id,
+                task_id,
+                ..
+            } => EventWebhookPayload {
+                approval: self
+                    .approvals
+                    .list_pending()
+                    .into_iter()
+                    .find(|approval| &approval.id == approval_id),
+                approval_id: Some(approval_id.clone()),
+                ..payload(
+                    EventWebhookKind::ApprovalPending,
+                    *task_id,
+                    *execution_process_id,
+                    format!("/api/approvals/{approval_id}/respond"),
+                )
+            },
+            EntityEvent::ApprovalResolved {
+                approval_id,
+                execution_process_id,
+                task_id,
+                status,
+            } => EventWebhookPayload {
+                approval_id: Some(approval_id.clone()),
+                approval_status: Some(status.clone()),
+                ..payload(
+                    EventWebhookKind::ApprovalResolved,
+                    *task_id,
+                    *execution_process_id,
+                    format!("/api/approvals/{approval_id}/respond"),
+                )
+            },
+            EntityEvent::QuestionPending {
+                question_id,
+                execution_process_id,
+                task_id,
+            } => EventWebhookPayload {
+                question: self
+                    .questions
+                    .list_pending()
+                    .into_iter()
+                    .find(|question| &question.id == question_id),
+                question_id: Some(question_id.clone()),
+                ..payload(
+                    EventWebhookKind::QuestionPending,
+                    *task_id,
+                    *execution_process_id,
+                    format!("/api/questions/{question_id}/respond"),
+                )
+            },
+            EntityEvent::QuestionAnswered {
+                question_id,
+                execution_process_id,
+                task_id,
+                answered,
+            } => EventWebhookPayload {
+                question_id: Some(question_id.clone()),
+                answered: Some(*answered),
+                ..payload(
+                    EventWebhookKind::QuestionResolved,
+                    *task_id,
+                    *execution_process_id,
+                    format!("/api/questions/{question_id}/respond"),
+                )
+            },
+            _ => return None,
+        };
+        Some(payload)
+    }
+
+    async fn deliver(
+        &self,
+        webhook: &ProjectEventWebhook,
+        kind: EventWebhookKind,
+        body: &[u8],
+    ) -> anyhow::Result<()> {
+        let mut request = self
+            .http
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(
+                EVENT_HEADER,
+                serde_json::to_string(&kind)?.trim_matches('"'),
+            );
+        if let Some(secret) = &webhook.secret {
+            let secret = self.cipher.decrypt(secret)?;
+            request = request.header(SIGNATURE_HEADER, sign(secret.as_bytes(), body));
+        }
+        request
+            .body(body.to_vec())
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EntityEventSubscriber for EventWebhookSubscriber {
+    fn name(&self) -> &'static str {
+        "event_webhooks"
+    }
+
+    async fn handle(&self, envelope: &EntityEventEnvelope) {
+        let Some(mut payload) = self.payload(envelope) else {
+            return;
+        };
+        let pool = &self.db.pool;
+        let webhooks = match ProjectEventWebhook::find_by_project_id(pool, payload.project_id).await
+        {
+            Ok(webhooks) => webhooks
+                .into_iter()
+                .filter(|webhook| webhook.subscribes_to(payload.event))
+                .collect::<Vec<_>>(),
+            Err(e) => {
+                tracing::warn!("Failed to load event webhooks: {}", e);
+                return;
+            }
+        };
+        if webhooks.is_empty() {
+            return;
+        }
+
+        payload.task_title = ExecutionProcess::load_context(pool, payload.execution_process_id)
+            .await
+            .ok()
+            .map(|ctx| ctx.task.title);
+        let body = match serde_json::to_vec(&payload) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!("Failed to serialize event webhook payload: {}", e);
+                return;
+            }
+        };
+        for webhook in &webhooks {
+            if let Err(e) = self.deliver(webhook, payload.event, &body).await {
+                tracing::warn!(
+                    "Event webhook {} to {} failed: {}",
+                    webhook.id,
+                    webhook.url,
+                    e
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signs_the_body_with_the_secret() {
+        let signature = sign(b"secret", b"{}");
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature, sign(b"secret", b"{}"));
+        assert_ne!(signature, sign(b"other", b"{}"));
+        assert_ne!(signature, sign(b"secret", b"[]"));
+    }
+}
@@ -3,7 +3,11 @@ use std::{str::FromStr, sync::Arc};
 use db::{
     DBService,
     models::{
-        execution_process::ExecutionProcess, project::Project, scratch::Scratch, task::Task,
+        execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+        project::Project,
+        scratch::Scratch,
+        session::Session,
+        task::Task,
         workspace::Workspace,
     },
 };
@@ -13,16 +17,24 @@ use tokio::sync::RwLock;
 use utils::msg_store::MsgStore;
 use uuid::Uuid;
 
+#[path = "events/entity_stream.rs"]
+pub mod entity_stream;
 #[path = "events/patches.rs"]
 pub mod patches;
 #[path = "events/streams.rs"]
 mod streams;
+#[path = "events/subscribers.rs"]
+pub mod subscribers;
 #[path = "events/types.rs"]
 pub mod types;
 
+pub use entity_stream::{
+    EntityEvent, EntityEventBus, EntityEventEnvelope, EntityEventSubscription,
+};
 pub use patches::{
     execution_process_patch, project_patch, scratch_patch, task_patch, workspace_patch,
 };
+pub use subscribers::{EntityEventSubscriber, spawn_subscriber};
 pub use types::{EventError, EventPatch, EventPatchInner, HookTables, RecordTypes};
 
 #[derive(Clone)]
@@ -31,15 +43,22 @@ pub struct EventService {
     db: DBService,
     #[allow(dead_code)]
     entry_count: Arc<RwLock<usize>>,
+    entity_events: EntityEventBus,
 }
 
 impl EventService {
     /// Creates a new EventService that will work with a DBService configured with hooks
-    pub fn new(db: DBService, msg_store: Arc<MsgStore>, entry_count: Arc<RwLock<usize>>) -> Self {
+    pub fn new(
+        db: DBService,
+        msg_store: Arc<MsgStore>,
+        entry_count: Arc<RwLock<usize>>,
+        entity_events: EntityEventBus,
+    ) -> Self {
         Self {
             msg_store,
             db,
             entry_count,
+            entity_events,
         }
     }
 
@@ -67,7 +86,6 @@ impl EventService {
         msg_store: Arc<MsgStore>,
         session_id: Uuid,
     ) -> Result<(), SqlxError> {
-        use db::models::session::Session;
         if let Some(session) = Session::find_by_id(pool, session_id).await?
             && let Some(workspace) = Workspace::find_by_id(pool, session.workspace_id).await?
         {
@@ -77,10 +95,54 @@ impl EventService {
         Ok(())
     }
 
+    /// Publish an execution process change as an attempt or dev server status event.
+    async fn publish_execution_process_event(
+        pool: &SqlitePool,
+        entity_events: &EntityEventBus,
+        process: &ExecutionProcess,
+        operation: &SqliteOperation,
+    ) -> Result<(), SqlxError> {
+        // Updates that leave the process running (e.g. commit bookkeeping) aren't state changes
+        if matches!(operation, SqliteOperation::Update)
+            && process.status == ExecutionProcessStatus::Running
+        {
+            return Ok(());
+        }
+
+        let Some(session) = Session::find_by_id(pool, process.session_id).await? else {
+            return Ok(());
+        };
+        let Some(workspace) = Workspace::find_by_id(pool, session.workspace_id).await? else {
+            return Ok(());
+        };
+        let project_id = Task::find_by_id(pool, workspace.task_id)
+            .await?
+            .map(|task| task.project_id);
+
+        let event = if process.run_reason == ExecutionProcessRunReason::DevServer {
+            EntityEvent::DevServerStatusChanged {
+                execution_process_id: process.id,
+                workspace_id: workspace.id,
+                status: process.status.clone(),
+            }
+        } else {
+            EntityEvent::AttemptStatusChanged {
+                workspace_id: workspace.id,
+                task_id: workspace.task_id,
+                execution_process_id: process.id,
+                run_reason: process.run_reason.clone(),
+                status: process.status.clone(),
+            }
+        };
+        entity_events.publish(project_id, event);
+        Ok(())
+    }
+
     /// Creates the hook function that should be used with DBService::new_with_after_connect
     pub fn create_hook(
         msg_store: Arc<MsgStore>,
         entry_count: Arc<RwLock<usize>>,
+        entity_events: EntityEventBus,
         db_service: DBService,
     ) -> impl for<'a> Fn(
         &'a mut sqlx::sqlite::SqliteConnection,
@@ -92,12 +154,14 @@ impl EventService {
         move |conn: &mut sqlx::sqlite::SqliteConnection| {
             let msg_store_for_hook = msg_store.clone();
             let entry_count_for_hook = entry_count.clone();
+            let entity_events_for_hook = entity_events.clone();
             let db_for_hook = db_service.clone();
             Box::pin(async move {
                 let mut handle = conn.lock_handle().await?;
                 let runtime_handle = tokio::runtime::Handle::current();
                 handle.set_preupdate_hook({
                     let msg_store_for_preupdate = msg_store_for_hook.clone();
+                    let entity_events_for_preupdate = entity_events_for_hook.clone();
                     move |preupdate: sqlx::sqlite::PreupdateHookResult<'_>| {
                         if preupdate.operation != SqliteOperation::Delete {
                             return;
@@ -110,6 +174,13 @@ impl EventService {
                                 {
                                     let patch = task_patch::remove(task_id);
                                     msg_store_for_preupdate.push_patch(patch);
+
+                                    let project_id =
+                                        preupdate.get_old_column_value(1).ok().and_then(|value| {
+                                            <Uuid as Decode<Sqlite>>::decode(value).ok()
+                                        });
+                                    entity_events_for_preupdate
+                                        .publish(project_id, EntityEvent::TaskDeleted { task_id });
                                 }
                             }
                             "projects" => {
@@ -158,6 +229,7 @@ impl EventService {
                     let runtime_handle = runtime_handle.clone();
                     let entry_count_for_hook = entry_count_for_hook.clone();
                     let msg_store_for_hook = msg_store_for_hook.clone();
+                    let entity_events_for_hook = entity_events_for_hook.clone();
                     let db = db_for_hook.clone();
 
                     if let Ok(table) = HookTables::from_str(hook.table) {
@@ -278,6 +350,17 @@ impl EventService {
                                             _ => task_patch::replace(&task_with_status), // fallback
                                         };
                                         msg_store_for_hook.push_patch(patch);
+
+                                        let project_id = task_with_status.project_id;
+                                        let event = match hook.operation {
+                                            SqliteOperation::Insert => EntityEvent::TaskCreated {
+                                                task: task_with_status,
+                                            },
+                                            _ => EntityEvent::TaskUpdated {
+                                                task: task_with_status,
+                                            },
+                                        };
+                                        entity_events_for_hook.publish(Some(project_id), event);
                                         return;
                                     }
                                 }
@@ -367,6 +450,20 @@ impl EventService {
                                     };
                                     msg_store_for_hook.push_patch(patch);
 
+                                    if let Err(err) = EventService::publish_execution_process_event(
+                                        &db.pool,
+                                        &entity_events_for_hook,
+                                        process,
+                                        &hook.operation,
+                                    )
+                                    .await
+                                    {
+                                        tracing::error!(
+                                            "Failed to publish execution process event: {:?}",
+                                            err
+                                        );
+                                    }
+
                                     if let Err(err) = EventService::push_task_update_for_session(
                                         &db.pool,
                                         msg_store_for_hook.clone(),
@@ -445,4 +542,8 @@ impl EventService {
     pub fn msg_store(&self) -> &Arc<MsgStore> {
         &self.msg_store
     }
+
+    pub fn entity_events(&self) -> &EntityEventBus {
+        &self.entity_events
+    }
 }
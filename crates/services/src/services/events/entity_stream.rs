@@ -0,0 +1,252 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    execution_process::{ExecutionProcessRunReason, ExecutionProcessStatus},
+    notification::Notification,
+    task::TaskWithAttemptStatus,
+};
+use serde::Serialize;
+use tokio::sync::broadcast;
+use ts_rs::TS;
+use utils::approvals::ApprovalStatus;
+use uuid::Uuid;
+
+use crate::services::presence::TaskPresence;
+
+/// Number of recent events kept for clients resuming with `Last-Event-ID`
+const REPLAY_BUFFER_SIZE: usize = 1024;
+
+/// A change to a domain entity, published on the unified `/events/stream` endpoint.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EntityEvent {
+    TaskCreated {
+        task: TaskWithAttemptStatus,
+    },
+    TaskUpdated {
+        task: TaskWithAttemptStatus,
+    },
+    TaskDeleted {
+        task_id: Uuid,
+    },
+    AttemptStatusChanged {
+        workspace_id: Uuid,
+        task_id: Uuid,
+        execution_process_id: Uuid,
+        run_reason: ExecutionProcessRunReason,
+        status: ExecutionProcessStatus,
+    },
+    QuestionPending {
+        question_id: String,
+        execution_process_id: Uuid,
+        task_id: Uuid,
+    },
+    QuestionAnswered {
+        question_id: String,
+        execution_process_id: Uuid,
+        task_id: Uuid,
+        /// False when the question timed out without an answer
+        answered: bool,
+    },
+    /// A coding agent run of an attempt exited
+    AttemptFinished {
+        workspace_id: Uuid,
+        task_id: Uuid,
+        session_id: Uuid,
+        execution_process_id: Uuid,
+        status: ExecutionProcessStatus,
+        #[ts(type = "number | null")]
+        exit_code: Option<i64>,
+    },
+    ApprovalPending {
+        approval_id: String,
+        execution_process_id: Uuid,
+        task_id: Uuid,
+        tool_name: String,
+    },
+    ApprovalResolved {
+        approval_id: String,
+        execution_process_id: Uuid,
+        task_id: Uuid,
+        status: ApprovalStatus,
+    },
+    DevServerStatusChanged {
+        execution_process_id: Uuid,
+        workspace_id: Uuid,
+        status: ExecutionProcessStatus,
+    },
+    NotificationCreated {
+        notification: Notification,
+        #[ts(type = "number")]
+        unread_count: i64,
+    },
+    /// Notifications were marked as read or cleared
+    NotificationsUpdated {
+        #[ts(type = "number")]
+        unread_count: i64,
+    },
+    TaskLabelAdded {
+        task_id: Uuid,
+        label: String,
+    },
+    /// Someone opened or left a task, or changed what they are doing in it
+    PresenceChanged {
+        task_id: Uuid,
+        viewers: Vec<TaskPresence>,
+    },
+}
+
+impl EntityEvent {
+    /// Events delivered to every subscriber regardless of their project filter.
+    pub fn is_global(&self) -> bool {
+        matches!(
+            self,
+            Self::NotificationCreated { .. } | Self::NotificationsUpdated { .. }
+        )
+    }
+}
+
+/// An [`EntityEvent`] together with its sequence id and owning project.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct EntityEventEnvelope {
+    /// Monotonically increasing id, usable as `Last-Event-ID` to resume the stream
+    #[ts(type = "number")]
+    pub id: u64,
+    /// `null` when the project could not be resolved (e.g. the row was already deleted)
+    pub project_id: Option<Uuid>,
+    #[ts(type = "Date")]
+    pub timestamp: DateTime<Utc>,
+    pub event: EntityEvent,
+}
+
+/// Result of subscribing to the entity event stream.
+pub struct EntityEventSubscription {
+    /// Buffered events newer than the requested id
+    pub replay: Vec<Arc<EntityEventEnvelope>>,
+    /// True when events after the requested id are no longer available and clients should refetch
+    pub truncated: bool,
+    pub live: broadcast::Receiver<Arc<EntityEventEnvelope>>,
+}
+
+#[derive(Debug)]
+struct BusState {
+    next_id: u64,
+    history: VecDeque<Arc<EntityEventEnvelope>>,
+}
+
+/// Fan-out of entity change events with a bounded replay buffer.
+#[derive(Debug, Clone)]
+pub struct EntityEventBus {
+    state: Arc<Mutex<BusState>>,
+    sender: broadcast::Sender<Arc<EntityEventEnvelope>>,
+}
+
+impl Default for EntityEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EntityEventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(REPLAY_BUFFER_SIZE);
+        Self {
+            state: Arc::new(Mutex::new(BusState {
+                next_id: 1,
+                history: VecDeque::with_capacity(REPLAY_BUFFER_SIZE),
+            })),
+            sender,
+        }
+    }
+
+    pub fn publish(&self, project_id: Option<Uuid>, event: EntityEvent) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let envelope = Arc::new(EntityEventEnvelope {
+            id: state.next_id,
+            project_id,
+            timestamp: Utc::now(),
+            event,
+        });
+        state.next_id += 1;
+        if state.history.len() == REPLAY_BUFFER_SIZE {
+            state.history.pop_front();
+        }
+        state.history.push_back(envelope.clone());
+        // Sent while holding the lock so subscribers never see gaps or duplicates
+        let _ = self.sender.send(envelope);
+    }
+
+    /// Subscribe to live events, replaying buffered events after `last_event_id`.
+    pub fn subscribe(&self, last_event_id: Option<u64>) -> EntityEventSubscription {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let live = self.sender.subscribe();
+
+        let Some(last_event_id) = last_event_id else {
+            return EntityEventSubscription {
+                replay: Vec::new(),
+                truncated: false,
+                live,
+            };
+        };
+
+        let oldest = state.history.front().map(|e| e.id).unwrap_or(state.next_id);
+        let replay = state
+            .history
+            .iter()
+            .filter(|e| e.id > last_event_id)
+            .cloned()
+            .collect();
+        EntityEventSubscription {
+            replay,
+            // An id from before a server restart is also unusable for resuming
+            truncated: last_event_id + 1 < oldest || last_event_id >= state.next_id,
+            live,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deleted(bus: &EntityEventBus) {
+        bus.publish(
+            None,
+            EntityEvent::TaskDeleted {
+                task_id: Uuid::new_v4(),
+            },
+        );
+    }
+
+    #[test]
+    fn replays_events_after_last_id() {
+        let bus = EntityEventBus::new();
+        for _ in 0..3 {
+            deleted(&bus);
+        }
+
+        let sub = bus.subscribe(Some(1));
+        assert_eq!(
+            sub.replay.iter().map(|e| e.id).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert!(!sub.truncated);
+        assert!(bus.subscribe(None).replay.is_empty());
+    }
+
+    #[test]
+    fn reports_truncated_replay() {
+        let bus = EntityEventBus::new();
+        for _ in 0..REPLAY_BUFFER_SIZE + 5 {
+            deleted(&bus);
+        }
+
+        let sub = bus.subscribe(Some(2));
+        assert!(sub.truncated);
+        assert_eq!(sub.replay.len(), REPLAY_BUFFER_SIZE);
+    }
+}
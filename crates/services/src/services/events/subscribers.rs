@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::{sync::broadcast::error::RecvError, task::JoinHandle};
+
+use super::entity_stream::{EntityEventBus, EntityEventEnvelope};
+
+/// Integration consuming the entity event stream, such as analytics or outgoing webhooks.
+#[async_trait]
+pub trait EntityEventSubscriber: Send + Sync + 'static {
+    /// Identifies the subscriber in logs
+    fn name(&self) -> &'static str;
+
+    async fn handle(&self, envelope: &EntityEventEnvelope);
+}
+
+/// Deliver every event published after this call to `subscriber`, on its own task so a slow
+/// subscriber never holds up publishers or other subscribers.
+pub fn spawn_subscriber(
+    bus: &EntityEventBus,
+    subscriber: Arc<dyn EntityEventSubscriber>,
+) -> JoinHandle<()> {
+    let mut live = bus.subscribe(None).live;
+    tokio::spawn(async move {
+        loop {
+            match live.recv().await {
+                Ok(envelope) => subscriber.handle(&envelope).await,
+                Err(RecvError::Lagged(skipped)) => tracing::warn!(
+                    subscriber = subscriber.name(),
+                    skipped,
+                    "Entity event subscriber fell behind"
+                ),
+                Err(RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::services::events::EntityEvent;
+
+    struct Forward(mpsc::UnboundedSender<u64>);
+
+    #[async_trait]
+    impl EntityEventSubscriber for Forward {
+        fn name(&self) -> &'static str {
+            "forward"
+        }
+
+        async fn handle(&self, envelope: &EntityEventEnvelope) {
+            let _ = self.0.send(envelope.id);
+        }
+    }
+
+    #[tokio::test]
+    async fn delivers_published_events_in_order() {
+        let bus = EntityEventBus::new();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let handle = spawn_subscriber(&bus, Arc::new(Forward(tx)));
+
+        for _ in 0..3 {
+            bus.publish(
+                None,
+                EntityEvent::TaskDeleted {
+                    task_id: Uuid::new_v4(),
+                },
+            );
+        }
+
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            ids.push(rx.recv().await.unwrap());
+        }
+        assert_eq!(ids, vec![1, 2, 3]);
+        handle.abort();
+    }
+}
@@ -0,0 +1,268 @@
+//! Executor CLIs that crash usually explain why only on stderr. The end of that output is kept
+//! with the failed execution process and matched against the errors users hit most, so
+//! notifications can say what went wrong and retry policies can tell transient failures apart.
+
+use db::models::execution_process_failure::ExecutionProcessFailureKind;
+use executors::limits::ResourceLimit;
+use utils::log_msg::LogMsg;
+
+/// Lines of stderr kept for a failed process
+pub const STDERR_TAIL_LINES: usize = 50;
+
+const RATE_LIMITED_PATTERNS: &[&str] = &[
+    "rate limit",
+    "rate_limit",
+    "too many requests",
+    "status 429",
+    "usage limit",
+    "quota exceeded",
+    "overloaded_error",
+];
+
+const CONTEXT_OVERFLOW_PATTERNS: &[&str] = &[
+    "prompt is too long",
+    "context length",
+    "context window",
+    "context_length_exceeded",
+    "maximum context length",
+    "input is too long",
+];
+
+const BUDGET_EXHAUSTED_PATTERNS: &[&str] = &[
+    "credit balance is too low",
+    "insufficient credit",
+    "insufficient_quota",
+    "spend limit",
+    "spending limit",
+    "billing",
+];
+
+const OUT_OF_MEMORY_PATTERNS: &[&str] = &[
+    "out of memory",
+    "heap out of memory",
+    "cannot allocate memory",
+    "memory allocation failed",
+    "exceeded memory limit",
+    "oom-kill",
+];
+
+const MERGE_CONFLICT_PATTERNS: &[&str] = &[
+    "conflict (content)",
+    "conflict (add/add)",
+    "conflict (modify/delete)",
+    "automatic merge failed",
+    "merge conflict",
+    "could not apply",
+];
+
+const TEST_FAILURE_PATTERNS: &[&str] = &[
+    "test result: failed",
+    "tests failed",
+    "test failed",
+    "failing tests",
+    "assertionerror",
+    "assertion failed",
+    "error: test failed",
+];
+
+const CLI_CRASH_PATTERNS: &[&str] = &[
+    "panicked at",
+    "segmentation fault",
+    "unhandled exception",
+    "unhandledpromiserejection",
+    "uncaught exception",
+    "uncaught error",
+    "traceback (most recent call last)",
+    "fatal error",
+];
+
+const AUTH_FAILURE_PATTERNS: &[&str] = &[
+    "not logged in",
+    "please run /login",
+    "invalid api key",
+    "invalid x-api-key",
+    "incorrect api key",
+    "api key not found",
+    "authentication failed",
+    "authentication_error",
+    "401 unauthorized",
+    "status 401",
+    "token has expired",
+];
+
+const CLI_MISSING_PATTERNS: &[&str] = &[
+    "command not found",
+    "is not recognized as an internal or external command",
+    "enoent",
+    "cannot find module",
+    "could not determine executable to run",
+    "npx: not found",
+];
+
+const VERSION_MISMATCH_PATTERNS: &[&str] = &[
+    "unknown option",
+    "unrecognized option",
+    "unexpected argument",
+    "unknown argument",
+    "unsupported engine",
+    "requires node",
+    "please upgrade",
+    "version mismatch",
+    "minimum required version",
+];
+
+/// Last `STDERR_TAIL_LINES` lines the process wrote to stderr, oldest first.
+pub fn stderr_tail(history: &[LogMsg]) -> String {
+    // Stderr arrives in arbitrary chunks, so join them before splitting into lines
+    let stderr: String = history
+        .iter()
+        .filter_map(|msg| match msg {
+            LogMsg::Stderr(chunk) => Some(chunk.as_str()),
+            _ => None,
+        })
+        .collect();
+    let lines: Vec<&str> = stderr
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .collect();
+    lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n")
+}
+
+/// Match a failed process's stderr against the failures users run into most. A process killed
+/// for exceeding its memory limit is out of memory whatever it printed.
+pub fn classify(stderr: &str, exceeded: Option<ResourceLimit>) -> ExecutionProcessFailureKind {
+    if exceeded == Some(ResourceLimit::Memory) {
+        return ExecutionProcessFailureKind::OutOfMemory;
+    }
+    let stderr = stderr.to_lowercase();
+    let matches = |patterns: &[&str]| patterns.iter().any(|pattern| stderr.contains(pattern));
+    // Most specific first: a crash report often mentions the error that caused it
+    [
+        (
+            MERGE_CONFLICT_PATTERNS,
+            ExecutionProcessFailureKind::MergeConflict,
+        ),
+        (
+            OUT_OF_MEMORY_PATTERNS,
+            ExecutionProcessFailureKind::OutOfMemory,
+        ),
+        (
+            CONTEXT_OVERFLOW_PATTERNS,
+            ExecutionProcessFailureKind::ContextOverflow,
+        ),
+        (
+            BUDGET_EXHAUSTED_PATTERNS,
+            ExecutionProcessFailureKind::BudgetExhausted,
+        ),
+        (
+            RATE_LIMITED_PATTERNS,
+            ExecutionProcessFailureKind::RateLimited,
+        ),
+        (
+            AUTH_FAILURE_PATTERNS,
+            ExecutionProcessFailureKind::AuthFailure,
+        ),
+        (
+            CLI_MISSING_PATTERNS,
+            ExecutionProcessFailureKind::CliMissing,
+        ),
+        (
+            VERSION_MISMATCH_PATTERNS,
+            ExecutionProcessFailureKind::VersionMismatch,
+        ),
+        (
+            TEST_FAILURE_PATTERNS,
+            ExecutionProcessFailureKind::TestFailure,
+        ),
+        (CLI_CRASH_PATTERNS, ExecutionProcessFailureKind::CliCrash),
+    ]
+    .into_iter()
+    .find(|(patterns, _)| matches(patterns))
+    .map(|(_, kind)| kind)
+    .unwrap_or(ExecutionProcessFailureKind::Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_last_stderr_lines() {
+        let mut history = vec![LogMsg::Stdout("ignored\n".to_string())];
+        for i in 0..STDERR_TAIL_LINES + 5 {
+            history.push(LogMsg::Stderr(format!("line {i}\n")));
+        }
+        history.push(LogMsg::Stderr("split ".to_string()));
+        history.push(LogMsg::Stderr("line\n\n".to_string()));
+
+        let tail = stderr_tail(&history);
+        let lines: Vec<&str> = tail.lines().collect();
+        assert_eq!(lines.len(), STDERR_TAIL_LINES);
+        assert_eq!(lines.last(), Some(&"split line"));
+        assert!(!tail.contains("ignored"));
+        assert!(!tail.contains("line 5\n"));
+    }
+
+    #[test]
+    fn classifies_common_failures() {
+        let cases = [
+            (
+                "Invalid API key · Please run /login",
+                ExecutionProcessFailureKind::AuthFailure,
+            ),
+            (
+                "sh: 1: codex: command not found",
+                ExecutionProcessFailureKind::CliMissing,
+            ),
+            (
+                "error: unknown option '--output-format'",
+                ExecutionProcessFailureKind::VersionMismatch,
+            ),
+            (
+                "API Error: 429 Too Many Requests",
+                ExecutionProcessFailureKind::RateLimited,
+            ),
+            (
+                "FATAL ERROR: Reached heap limit Allocation failed - JavaScript heap out of memory",
+                ExecutionProcessFailureKind::OutOfMemory,
+            ),
+            (
+                "test result: FAILED. 3 passed; 1 failed",
+                ExecutionProcessFailureKind::TestFailure,
+            ),
+            (
+                "CONFLICT (content): Merge conflict in src/lib.rs",
+                ExecutionProcessFailureKind::MergeConflict,
+            ),
+            (
+                "API Error: 400 prompt is too long: 210000 tokens > 200000 maximum",
+                ExecutionProcessFailureKind::ContextOverflow,
+            ),
+            (
+                "Credit balance is too low",
+                ExecutionProcessFailureKind::BudgetExhausted,
+            ),
+            (
+                "thread 'main' panicked at src/main.rs:10:5",
+                ExecutionProcessFailureKind::CliCrash,
+            ),
+            ("exited", ExecutionProcessFailureKind::Unknown),
+        ];
+        for (stderr, kind) in cases {
+            assert_eq!(classify(stderr, None), kind, "{stderr}");
+        }
+    }
+
+    #[test]
+    fn memory_limit_means_out_of_memory() {
+        assert_eq!(
+            classify("", Some(ResourceLimit::Memory)),
+            ExecutionProcessFailureKind::OutOfMemory
+        );
+        assert_eq!(
+            classify("", Some(ResourceLimit::WallClock)),
+            ExecutionProcessFailureKind::Unknown
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use db::models::execution_process::{CodingAgentRun, ExecutionProcessStatus};
+use executors::profile::ExecutorProfileId;
+use serde::Serialize;
+use ts_rs::TS;
+use utils::log_msg::LogMsg;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AttemptOutcome {
+    Succeeded,
+    NeededFollowUp,
+    Failed,
+    Cancelled,
+}
+
+/// How attempts started with one executor profile turned out.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ExecutorStats {
+    pub executor_profile_id: ExecutorProfileId,
+    /// Finished attempts; attempts with an agent still running are left out
+    pub attempts: usize,
+    /// Completed on the first agent run
+    pub succeeded: usize,
+    /// Completed after one or more follow-ups
+    pub needed_follow_up: usize,
+    pub failed: usize,
+    /// Stopped by the user
+    pub cancelled: usize,
+    /// Share of attempts that succeeded without follow-ups, from 0 to 1
+    pub success_rate: f64,
+    /// Median over attempts of the total time their agents ran
+    pub median_duration_seconds: Option<f64>,
+    /// Median over attempts whose agent reports its cost
+    pub median_cost_usd: Option<f64>,
+    pub total_cost_usd: Option<f64>,
+}
+
+struct AttemptSummary {
+    outcome: AttemptOutcome,
+    duration_seconds: f64,
+    cost_usd: Option<f64>,
+}
+
+/// Summarize one attempt's runs, oldest first, or `None` while an agent is still running.
+fn summarize_attempt(runs: &[&CodingAgentRun]) -> Option<AttemptSummary> {
+    let last = runs.last()?;
+    let outcome = match last.status {
+        ExecutionProcessStatus::Running => return None,
+        ExecutionProcessStatus::Completed if runs.len() == 1 => AttemptOutcome::Succeeded,
+        ExecutionProcessStatus::Completed => AttemptOutcome::NeededFollowUp,
+        ExecutionProcessStatus::Failed => AttemptOutcome::Failed,
+        ExecutionProcessStatus::Killed => AttemptOutcome::Cancelled,
+    };
+    let duration_seconds = runs
+        .iter()
+        .filter_map(|run| Some((run.completed_at? - run.started_at).num_milliseconds()))
+        .map(|ms| ms.max(0) as f64 / 1000.0)
+        .sum();
+    let costs: Vec<f64> = runs.iter().filter_map(|run| run.cost_usd).collect();
+
+    Some(AttemptSummary {
+        outcome,
+        duration_seconds,
+        cost_usd: (!costs.is_empty()).then(|| costs.iter().sum()),
+    })
+}
+
+pub(crate) fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    })
+}
+
+/// Group coding agent runs into attempts and aggregate their outcomes per executor profile,
+/// most used profile first. Attempts count towards the profile that started them.
+pub fn aggregate(runs: &[CodingAgentRun]) -> Vec<ExecutorStats> {
+    let mut order: Vec<Uuid> = Vec::new();
+    let mut by_workspace: HashMap<Uuid, Vec<&CodingAgentRun>> = HashMap::new();
+    for run in runs {
+        by_workspace
+            .entry(run.workspace_id)
+            .or_insert_with(|| {
+                order.push(run.workspace_id);
+                Vec::new()
+            })
+            .push(run);
+    }
+
+    let mut by_profile: HashMap<ExecutorProfileId, Vec<AttemptSummary>> = HashMap::new();
+    for workspace_id in order {
+        let attempt_runs = &by_workspace[&workspace_id];
+        let Some(profile) = attempt_runs
+            .iter()
+            .find_map(|run| run.executor_profile_id())
+        else {
+            continue;
+        };
+        if let Some(summary) = summarize_attempt(attempt_runs) {
+            by_profile.entry(profile.clone()).or_default().push(summary);
+        }
+    }
+
+    let mut stats: Vec<ExecutorStats> = by_profile
+        .into_iter()
+        .map(|(executor_profile_id, attempts)| {
+            let count = |outcome| attempts.iter().filter(|a| a.outcome == outcome).count();
+            let succeeded = count(AttemptOutcome::Succeeded);
+            let costs: Vec<f64> = attempts.iter().filter_map(|a| a.cost_usd).collect();
+            ExecutorStats {
+                executor_profile_id,
+                attempts: attempts.len(),
+                succeeded,
+                needed_follow_up: count(AttemptOutcome::NeededFollowUp),
+                failed: count(AttemptOutcome::Failed),
+                cancelled: count(AttemptOutcome::Cancelled),
+                success_rate: succeeded as f64 / attempts.len() as f64,
+                median_duration_seconds: median(
+                    attempts.iter().map(|a| a.duration_seconds).collect(),
+                ),
+                total_cost_usd: (!costs.is_empty()).then(|| costs.iter().sum()),
+                median_cost_usd: median(costs),
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| {
+        b.attempts.cmp(&a.attempts).then_with(|| {
+            a.executor_profile_id
+                .to_string()
+                .cmp(&b.executor_profile_id.to_string())
+        })
+    });
+    stats
+}
+
+/// The cost an agent printed in its final result line, such as Claude Code's `total_cost_usd`.
+pub fn reported_cost_usd(history: &[LogMsg]) -> Option<f64> {
+    history.iter().rev().find_map(|msg| match msg {
+        LogMsg::Stdout(chunk) => chunk.lines().rev().find_map(|line| {
+            let line = line.trim();
+            if !line.starts_with('{') || !line.contains("cost_usd") {
+                return None;
+            }
+            let value: serde_json::Value = serde_json::from_str(line).ok()?;
+            value
+                .get("total_cost_usd")
+                .or_else(|| value.get("cost_usd"))
+                .and_then(serde_json::Value::as_f64)
+        }),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use db::models::execution_process::ExecutorActionField;
+    use executors::{
+        actions::{
+            ExecutorAction, ExecutorActionType, coding_agent_initial::CodingAgentInitialRequest,
+        },
+        executors::BaseCodingAgent,
+    };
+
+    use super::*;
+
+    fn run(
+        workspace_id: Uuid,
+        executor: BaseCodingAgent,
+        status: ExecutionProcessStatus,
+        seconds: i64,
+        cost_usd: Option<f64>,
+    ) -> CodingAgentRun {
+        let started_at = Utc::now();
+        let action = ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt: "Fix it".to_string(),
+                executor_profile_id: ExecutorProfileId::new(executor),
+                working_dir: None,
+                model: None,
+            }),
+            None,
+        );
+        CodingAgentRun {
+            workspace_id,
+            executor_action: sqlx::types::Json(ExecutorActionField::ExecutorAction(action)),
+            status,
+            started_at,
+            completed_at: Some(started_at + Duration::seconds(seconds)),
+            cost_usd,
+        }
+    }
+
+    #[test]
+    fn aggregates_attempt_outcomes_per_executor() {
+        let (a, b, c, d) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let claude = BaseCodingAgent::ClaudeCode;
+        let runs = vec![
+            run(a, claude, ExecutionProcessStatus::Completed, 60, Some(0.5)),
+            run(b, claude, ExecutionProcessStatus::Completed, 30, Some(0.25)),
+            run(b, claude, ExecutionProcessStatus::Completed, 90, Some(0.25)),
+            run(c, claude, ExecutionProcessStatus::Killed, 10, None),
+            run(
+                d,
+                BaseCodingAgent::Codex,
+                ExecutionProcessStatus::Running,
+                0,
+                None,
+            ),
+        ];
+
+        let stats = aggregate(&runs);
+        assert_eq!(stats.len(), 1);
+        let claude_stats = &stats[0];
+        assert_eq!(claude_stats.attempts, 3);
+        assert_eq!(claude_stats.succeeded, 1);
+        assert_eq!(claude_stats.needed_follow_up, 1);
+        assert_eq!(claude_stats.cancelled, 1);
+        assert_eq!(claude_stats.median_duration_seconds, Some(60.0));
+        assert_eq!(claude_stats.median_cost_usd, Some(0.5));
+        assert_eq!(claude_stats.total_cost_usd, Some(1.0));
+    }
+
+    #[test]
+    fn reads_cost_from_result_line() {
+        let history = vec![
+            LogMsg::Stdout("{\"type\":\"assistant\"}\n".to_string()),
+            LogMsg::Stdout(
+                "{\"type\":\"result\",\"total_cost_usd\":0.1234,\"num_turns\":3}\n".to_string(),
+            ),
+            LogMsg::Finished,
+        ];
+        assert_eq!(reported_cost_usd(&history), Some(0.1234));
+        assert_eq!(reported_cost_usd(&history[..1]), None);
+    }
+}
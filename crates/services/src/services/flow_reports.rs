@@ -0,0 +1,413 @@
+//! Cycle time and burndown series of a project over a date range, for charts and external BI.
+//!
+//! Both are computed from the status changes recorded for every task. Tasks that existed before
+//! status changes were recorded only have their current column, so their early history is
+//! missing from the reports.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use db::models::{
+    task::{Task, TaskStatus},
+    task_label::TaskLabel,
+    task_status_change::TaskStatusChange,
+};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{
+    executor_stats::median,
+    project_stats::{COLUMNS, average, seconds_between},
+};
+
+pub const DEFAULT_RANGE_DAYS: i64 = 30;
+pub const MAX_RANGE_DAYS: i64 = 366;
+
+/// Days reported, both ends included.
+#[derive(Debug, Clone, Copy)]
+pub struct ReportRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+impl ReportRange {
+    /// The `DEFAULT_RANGE_DAYS` days up to and including today when no bound is given.
+    pub fn resolve(from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<Self, String> {
+        let to = to.unwrap_or_else(|| Utc::now().date_naive());
+        let from = from.unwrap_or(to - Duration::days(DEFAULT_RANGE_DAYS - 1));
+        if from > to {
+            return Err("The range must not end before it starts".to_string());
+        }
+        if (to - from).num_days() >= MAX_RANGE_DAYS {
+            return Err(format!("The range can span at most {MAX_RANGE_DAYS} days"));
+        }
+        Ok(Self { from, to })
+    }
+
+    fn contains(&self, at: DateTime<Utc>) -> bool {
+        (self.from..=self.to).contains(&at.date_naive())
+    }
+
+    fn days(self) -> impl Iterator<Item = NaiveDate> {
+        (0..=(self.to - self.from).num_days()).map(move |offset| self.from + Duration::days(offset))
+    }
+}
+
+fn end_of(date: NaiveDate) -> DateTime<Utc> {
+    (date + Duration::days(1))
+        .and_hms_opt(0, 0, 0)
+        .map(|at| at.and_utc())
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct CycleTimeReport {
+    /// Tasks that first reached Done within the range, in completion order
+    pub tasks: Vec<TaskCycleTime>,
+    pub average_seconds: Option<f64>,
+    pub median_seconds: Option<f64>,
+    /// One point per day of the range
+    pub series: Vec<CycleTimePoint>,
+    /// Time the completed tasks spent in each column before reaching Done
+    pub column_lead_times: Vec<ColumnLeadTime>,
+}
+
+/// Time from creating a task to it first reaching Done.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct TaskCycleTime {
+    pub task_id: Uuid,
+    pub title: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub done_at: DateTime<Utc>,
+    pub cycle_seconds: f64,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct CycleTimePoint {
+    #[ts(type = "string")]
+    pub date: NaiveDate,
+    pub completed: usize,
+    /// Average cycle time of the tasks completed that day
+    pub average_cycle_seconds: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ColumnLeadTime {
+    pub status: TaskStatus,
+    pub average_seconds: Option<f64>,
+    pub median_seconds: Option<f64>,
+    /// Completed tasks that passed through the column
+    pub tasks: usize,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct BurndownReport {
+    /// Label the tasks were scoped to, if any
+    pub label: Option<String>,
+    /// One point per day of the range, counted at the end of the day
+    pub series: Vec<BurndownPoint>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct BurndownPoint {
+    #[ts(type = "string")]
+    pub date: NaiveDate,
+    /// Tasks that existed and were not cancelled
+    pub scope: usize,
+    pub completed: usize,
+    pub remaining: usize,
+}
+
+/// Changes of each task, relying on them being sorted by task and time.
+fn changes_by_task(changes: &[TaskStatusChange]) -> HashMap<Uuid, &[TaskStatusChange]> {
+    changes
+        .chunk_by(|a, b| a.task_id == b.task_id)
+        .map(|task_changes| (task_changes[0].task_id, task_changes))
+        .collect()
+}
+
+fn cycle_times(
+    tasks: &[Task],
+    changes: &[TaskStatusChange],
+    range: ReportRange,
+) -> CycleTimeReport {
+    let by_task = changes_by_task(changes);
+    let mut completed = Vec::new();
+    let mut column_seconds: Vec<Vec<f64>> = vec![Vec::new(); COLUMNS.len()];
+
+    for task in tasks {
+        let task_changes = by_task.get(&task.id).copied().unwrap_or_default();
+        let Some(done_index) = task_changes
+            .iter()
+            .position(|c| c.status == TaskStatus::Done)
+        else {
+            continue;
+        };
+        let done_at = task_changes[done_index].changed_at;
+        if !range.contains(done_at) {
+            continue;
+        }
+
+        let mut per_column = [0.0; COLUMNS.len()];
+        let mut visited = [false; COLUMNS.len()];
+        for pair in task_changes[..=done_index].windows(2) {
+            if let Some(column) = COLUMNS.iter().position(|s| *s == pair[0].status) {
+                per_column[column] += seconds_between(pair[0].changed_at, pair[1].changed_at);
+                visited[column] = true;
+            }
+        }
+        for column in (0..COLUMNS.len()).filter(|c| visited[*c]) {
+            column_seconds[column].push(per_column[column]);
+        }
+
+        completed.push(TaskCycleTime {
+            task_id: task.id,
+            title: task.title.clone(),
+            created_at: task.created_at,
+            done_at,
+            cycle_seconds: seconds_between(task.created_at, done_at),
+        });
+    }
+    completed.sort_by_key(|t| t.done_at);
+
+    let series = range
+        .days()
+        .map(|date| {
+            let seconds: Vec<f64> = completed
+                .iter()
+                .filter(|t| t.done_at.date_naive() == date)
+                .map(|t| t.cycle_seconds)
+                .collect();
+            CycleTimePoint {
+                date,
+                completed: seconds.len(),
+                average_cycle_seconds: average(&seconds),
+            }
+        })
+        .collect();
+    let column_lead_times = COLUMNS
+        .into_iter()
+        .zip(column_seconds)
+        .filter(|(status, _)| *status != TaskStatus::Done)
+        .map(|(status, seconds)| ColumnLeadTime {
+            status,
+            average_seconds: average(&seconds),
+            tasks: seconds.len(),
+            median_seconds: median(seconds),
+        })
+        .collect();
+    let cycle_seconds: Vec<f64> = completed.iter().map(|t| t.cycle_seconds).collect();
+
+    CycleTimeReport {
+        average_seconds: average(&cycle_seconds),
+        median_seconds: median(cycle_seconds),
+        tasks: completed,
+        series,
+        column_lead_times,
+    }
+}
+
+fn burndown(
+    tasks: &[&Task],
+    changes: &[TaskStatusChange],
+    range: ReportRange,
+) -> Vec<BurndownPoint> {
+    let by_task = changes_by_task(changes);
+    range
+        .days()
+        .map(|date| {
+            let end = end_of(date);
+            let (mut scope, mut completed) = (0, 0);
+            for task in tasks.iter().filter(|t| t.created_at < end) {
+                let status = by_task
+                    .get(&task.id)
+                    .and_then(|c| c.iter().rev().find(|c| c.changed_at < end))
+                    .map(|c| &c.status)
+                    .unwrap_or(&TaskStatus::Todo);
+                match status {
+                    TaskStatus::Cancelled => {}
+                    TaskStatus::Done => {
+                        scope += 1;
+                        completed += 1;
+                    }
+                    _ => scope += 1,
+                }
+            }
+            BurndownPoint {
+                date,
+                scope,
+                completed,
+                remaining: scope - completed,
+            }
+        })
+        .collect()
+}
+
+pub async fn cycle_time_report(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    range: ReportRange,
+) -> Result<CycleTimeReport, sqlx::Error> {
+    let tasks = Task::find_by_project_id(pool, project_id).await?;
+    let changes = TaskStatusChange::find_by_project_id(pool, project_id).await?;
+    Ok(cycle_times(&tasks, &changes, range))
+}
+
+/// Burndown of the project's tasks, or only of those carrying `label`, such as a milestone's.
+pub async fn burndown_report(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    label: Option<String>,
+    range: ReportRange,
+) -> Result<BurndownReport, sqlx::Error> {
+    let tasks = Task::find_by_project_id(pool, project_id).await?;
+    let changes = TaskStatusChange::find_by_project_id(pool, project_id).await?;
+    let labelled: Option<HashSet<Uuid>> = match &label {
+        Some(label) => Some(
+            TaskLabel::find_task_ids_by_label(pool, project_id, label)
+                .await?
+                .into_iter()
+                .collect(),
+        ),
+        None => None,
+    };
+    let scoped: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| labelled.as_ref().is_none_or(|ids| ids.contains(&t.id)))
+        .collect();
+    Ok(BurndownReport {
+        series: burndown(&scoped, &changes, range),
+        label,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn task(id: u128, created_at: &str) -> Task {
+        Task {
+            id: Uuid::from_u128(id),
+            project_id: Uuid::nil(),
+            title: format!("Task {id}"),
+            description: None,
+            status: TaskStatus::Todo,
+            parent_workspace_id: None,
+            shared_task_id: None,
+            created_at: at(created_at),
+            updated_at: at(created_at),
+        }
+    }
+
+    fn change(id: u128, status: TaskStatus, changed_at: &str) -> TaskStatusChange {
+        TaskStatusChange {
+            task_id: Uuid::from_u128(id),
+            status,
+            changed_at: at(changed_at),
+        }
+    }
+
+    fn range(from: &str, to: &str) -> ReportRange {
+        ReportRange {
+            from: from.parse().unwrap(),
+            to: to.parse().unwrap(),
+        }
+    }
+
+    fn history() -> (Vec<Task>, Vec<TaskStatusChange>) {
+        let tasks = vec![
+            task(1, "2026-03-02T09:00:00Z"),
+            task(2, "2026-03-02T09:00:00Z"),
+            task(3, "2026-03-03T09:00:00Z"),
+        ];
+        let changes = vec![
+            change(1, TaskStatus::Todo, "2026-03-02T09:00:00Z"),
+            change(1, TaskStatus::InProgress, "2026-03-02T10:00:00Z"),
+            change(1, TaskStatus::Done, "2026-03-03T10:00:00Z"),
+            change(2, TaskStatus::Todo, "2026-03-02T09:00:00Z"),
+            change(2, TaskStatus::Cancelled, "2026-03-04T09:00:00Z"),
+            change(3, TaskStatus::Todo, "2026-03-03T09:00:00Z"),
+            change(3, TaskStatus::InProgress, "2026-03-03T09:00:00Z"),
+            change(3, TaskStatus::InReview, "2026-03-03T11:00:00Z"),
+            change(3, TaskStatus::Done, "2026-03-04T09:00:00Z"),
+        ];
+        (tasks, changes)
+    }
+
+    #[test]
+    fn measures_cycle_and_column_lead_times() {
+        let (tasks, changes) = history();
+        let report = cycle_times(&tasks, &changes, range("2026-03-02", "2026-03-04"));
+
+        assert_eq!(
+            report
+                .tasks
+                .iter()
+                .map(|t| (t.task_id, t.cycle_seconds))
+                .collect::<Vec<_>>(),
+            vec![(Uuid::from_u128(1), 90000.0), (Uuid::from_u128(3), 86400.0)]
+        );
+        assert_eq!(report.median_seconds, Some(88200.0));
+        assert_eq!(
+            report
+                .series
+                .iter()
+                .map(|p| p.completed)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 1]
+        );
+
+        let lead = |status: TaskStatus| {
+            report
+                .column_lead_times
+                .iter()
+                .find(|c| c.status == status)
+                .map(|c| (c.tasks, c.average_seconds))
+                .unwrap()
+        };
+        assert_eq!(lead(TaskStatus::Todo), (2, Some(1800.0)));
+        assert_eq!(lead(TaskStatus::InProgress), (2, Some(46800.0)));
+        assert_eq!(lead(TaskStatus::InReview), (1, Some(79200.0)));
+        assert!(
+            report
+                .column_lead_times
+                .iter()
+                .all(|c| c.status != TaskStatus::Done)
+        );
+
+        let later = cycle_times(&tasks, &changes, range("2026-03-04", "2026-03-04"));
+        assert_eq!(later.tasks.len(), 1);
+    }
+
+    #[test]
+    fn burns_down_scope_at_the_end_of_each_day() {
+        let (tasks, changes) = history();
+        let scoped: Vec<&Task> = tasks.iter().collect();
+        let series = burndown(&scoped, &changes, range("2026-03-01", "2026-03-04"));
+
+        assert_eq!(
+            series
+                .iter()
+                .map(|p| (p.scope, p.completed, p.remaining))
+                .collect::<Vec<_>>(),
+            vec![(0, 0, 0), (2, 0, 2), (3, 1, 2), (2, 2, 0)]
+        );
+    }
+
+    #[test]
+    fn rejects_inverted_and_oversized_ranges() {
+        let date = |s: &str| s.parse::<NaiveDate>().unwrap();
+        assert!(ReportRange::resolve(Some(date("2026-03-05")), Some(date("2026-03-04"))).is_err());
+        assert!(ReportRange::resolve(Some(date("2025-01-01")), Some(date("2026-03-04"))).is_err());
+        let range = ReportRange::resolve(None, Some(date("2026-03-30"))).unwrap();
+        assert_eq!(range.from, date("2026-03-01"));
+    }
+}
@@ -1055,6 +1055,21 @@ impl GitService {
         Ok(oid)
     }
 
+    /// Commit OID of a base branch, fetching it first when it is a remote branch so commits
+    /// pushed upstream are seen
+    pub fn fetch_branch_oid(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+    ) -> Result<String, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let branch = Self::find_branch(&repo, branch_name)?.into_reference();
+        if branch.is_remote() {
+            self.fetch_branch_from_remote(&repo, &branch)?;
+        }
+        self.get_branch_oid(repo_path, branch_name)
+    }
+
     /// Get the subject/summary line for a given commit OID
     pub fn get_commit_subject(
         &self,
@@ -1161,6 +1176,47 @@ impl GitService {
         Ok(())
     }
 
+    /// Snapshot the worktree including uncommitted changes without touching it. Returns the
+    /// snapshot commit and its tree.
+    pub fn snapshot_worktree(
+        &self,
+        worktree_path: &Path,
+        message: &str,
+    ) -> Result<(String, String), GitServiceError> {
+        self.ensure_cli_commit_identity(worktree_path)?;
+        GitCli::new()
+            .snapshot_worktree(worktree_path, message)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git snapshot failed: {e}")))
+    }
+
+    /// Point `refname` at a commit no branch reaches, so it survives garbage collection.
+    pub fn pin_commit(
+        &self,
+        repo_path: &Path,
+        refname: &str,
+        sha: &str,
+    ) -> Result<(), GitServiceError> {
+        GitCli::new()
+            .update_ref(repo_path, refname, sha)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git update-ref failed: {e}")))
+    }
+
+    /// Put the worktree back to a snapshot: the branch is reset to `head_sha` and the
+    /// snapshot's uncommitted changes are restored on top of it.
+    pub fn restore_worktree_snapshot(
+        &self,
+        worktree_path: &Path,
+        head_sha: &str,
+        snapshot_sha: &str,
+    ) -> Result<(), GitServiceError> {
+        self.reset_worktree_to_commit(worktree_path, head_sha, true)?;
+        GitCli::new()
+            .restore_snapshot(worktree_path, snapshot_sha)
+            .map_err(|e| {
+                GitServiceError::InvalidRepository(format!("git snapshot restore failed: {e}"))
+            })
+    }
+
     /// Add a worktree for a branch, optionally creating the branch
     pub fn add_worktree(
         &self,
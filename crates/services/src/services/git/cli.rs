@@ -605,6 +605,56 @@ impl GitCli {
             .map(|_| ())
     }
 
+    /// Record the working tree, untracked files included, as a commit on top of HEAD without
+    /// touching HEAD, the index or the working tree. Returns the commit and its tree.
+    pub fn snapshot_worktree(
+        &self,
+        worktree_path: &Path,
+        message: &str,
+    ) -> Result<(String, String), GitCliError> {
+        let tmp_dir = tempfile::TempDir::new()
+            .map_err(|e| GitCliError::CommandFailed(format!("temp dir create failed: {e}")))?;
+        let tmp_index = tmp_dir.path().join("index");
+        let envs = vec![(
+            OsString::from("GIT_INDEX_FILE"),
+            tmp_index.as_os_str().to_os_string(),
+        )];
+        self.git_with_env(worktree_path, ["read-tree", "HEAD"], &envs)?;
+        self.git_with_env(
+            worktree_path,
+            Self::apply_default_excludes(vec!["add", "-A"]),
+            &envs,
+        )?;
+        let tree = self
+            .git_with_env(worktree_path, ["write-tree"], &envs)?
+            .trim()
+            .to_string();
+        let commit = self
+            .git(
+                worktree_path,
+                ["commit-tree", tree.as_str(), "-p", "HEAD", "-m", message],
+            )?
+            .trim()
+            .to_string();
+        Ok((commit, tree))
+    }
+
+    /// Make the working tree match a snapshot while HEAD and the index stay put, so the
+    /// snapshot's differences from HEAD show up as uncommitted changes again.
+    pub fn restore_snapshot(
+        &self,
+        worktree_path: &Path,
+        snapshot: &str,
+    ) -> Result<(), GitCliError> {
+        self.git(
+            worktree_path,
+            Self::apply_default_excludes(vec!["clean", "-fd"]),
+        )?;
+        self.git(worktree_path, ["read-tree", "-u", "--reset", snapshot])?;
+        self.git(worktree_path, ["reset", "-q"])?;
+        Ok(())
+    }
+
     pub fn abort_merge(&self, worktree_path: &Path) -> Result<(), GitCliError> {
         if !self.is_merge_in_progress(worktree_path)? {
             return Ok(());
@@ -100,6 +100,11 @@ impl GitHubServiceError {
                 | GitHubServiceError::GhCliNotInstalled(_)
         )
     }
+
+    /// Whether GitHub couldn't be reached at all, so the same call may succeed later
+    pub fn is_connectivity_error(&self) -> bool {
+        self.should_retry() && crate::services::outbox::is_connectivity_error(&self.to_string())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -322,6 +327,26 @@ impl GitHubService {
         .await
     }
 
+    /// Close or reopen an issue
+    pub async fn set_issue_state(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        issue_number: i64,
+        open: bool,
+    ) -> Result<(), GitHubServiceError> {
+        let cli = self.gh_cli.clone();
+        let owner = repo_info.owner.clone();
+        let repo = repo_info.repo_name.clone();
+        task::spawn_blocking(move || cli.set_issue_state(&owner, &repo, issue_number, open))
+            .await
+            .map_err(|err| {
+                GitHubServiceError::Repository(format!(
+                    "Failed to execute GitHub CLI for issue #{issue_number}: {err}"
+                ))
+            })?
+            .map_err(GitHubServiceError::from)
+    }
+
     /// Fetch all comments (both general and review) for a pull request
     pub async fn get_pr_comments(
         &self,
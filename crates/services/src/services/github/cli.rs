@@ -213,6 +213,24 @@ impl GhCli {
         Self::parse_pr_list(&raw)
     }
 
+    /// Close or reopen an issue.
+    pub fn set_issue_state(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue_number: i64,
+        open: bool,
+    ) -> Result<(), GhCliError> {
+        self.run([
+            "issue",
+            if open { "reopen" } else { "close" },
+            &issue_number.to_string(),
+            "--repo",
+            &format!("{owner}/{repo}"),
+        ])?;
+        Ok(())
+    }
+
     /// Fetch comments for a pull request.
     pub fn get_pr_comments(
         &self,
@@ -0,0 +1,316 @@
+use std::time::Duration;
+
+use db::{
+    DBService,
+    models::{
+        github_issue::{GitHubIssueLink, GitHubIssueSync},
+        integration_outbox::IntegrationOutboxEntry,
+        task::{CreateTask, Task, TaskStatus},
+    },
+};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sqlx::SqlitePool;
+use subtle::ConstantTimeEq;
+use tokio::time::interval;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::services::{
+    github::{GitHubRepoInfo, GitHubService},
+    outbox::{self, OutboxAction},
+    task_workflow,
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+const SYNC_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Verify the `X-Hub-Signature-256` header (`sha256=<hex>`) GitHub signs webhook payloads with.
+pub fn verify_signature(secret: &[u8], signature_header: &str, payload: &[u8]) -> bool {
+    let Some(expected) = signature_header
+        .strip_prefix("sha256=")
+        .and_then(|hex_signature| hex::decode(hex_signature).ok())
+    else {
+        return false;
+    };
+    let Ok(mut mac) = HmacSha256::new_from_slice(secret) else {
+        return false;
+    };
+    mac.update(payload);
+    mac.finalize().into_bytes()[..].ct_eq(&expected).into()
+}
+
+/// Payload of an `issues` webhook event.
+#[derive(Debug, Deserialize)]
+pub struct IssuesEvent {
+    pub action: String,
+    pub issue: Issue,
+    pub repository: Repository,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Issue {
+    pub number: i64,
+    pub title: String,
+    pub body: Option<String>,
+    pub html_url: String,
+    pub state: String,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Label {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Repository {
+    pub full_name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueEventOutcome {
+    Created,
+    Updated,
+    /// The event did not concern a synced issue
+    Ignored,
+}
+
+fn matches_filter(issue: &Issue, label_filter: Option<&str>) -> bool {
+    match label_filter.map(str::trim).filter(|l| !l.is_empty()) {
+        Some(filter) => issue
+            .labels
+            .iter()
+            .any(|label| label.name.eq_ignore_ascii_case(filter)),
+        None => true,
+    }
+}
+
+fn task_description(issue: &Issue) -> String {
+    match issue
+        .body
+        .as_deref()
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+    {
+        Some(body) => format!("{body}\n\nGitHub issue: {}", issue.html_url),
+        None => format!("GitHub issue: {}", issue.html_url),
+    }
+}
+
+fn is_closed(status: &TaskStatus) -> bool {
+    matches!(status, TaskStatus::Done | TaskStatus::Cancelled)
+}
+
+/// Create or update the task of an issue. Issues that lose the filter label keep their task.
+pub async fn apply_issues_event(
+    pool: &SqlitePool,
+    sync: &GitHubIssueSync,
+    event: &IssuesEvent,
+) -> Result<(IssueEventOutcome, Option<Uuid>), sqlx::Error> {
+    if !event
+        .repository
+        .full_name
+        .eq_ignore_ascii_case(&sync.repo_full_name)
+    {
+        return Ok((IssueEventOutcome::Ignored, None));
+    }
+
+    let issue = &event.issue;
+    let link =
+        GitHubIssueLink::find_by_issue(pool, sync.project_id, &sync.repo_full_name, issue.number)
+            .await?;
+    let task = match &link {
+        Some(link) => Task::find_by_id(pool, link.task_id).await?,
+        None => None,
+    };
+
+    let Some(task) = task else {
+        let importable = matches!(
+            event.action.as_str(),
+            "opened" | "edited" | "labeled" | "reopened"
+        ) && issue.state == "open"
+            && matches_filter(issue, sync.label_filter.as_deref());
+        if !importable {
+            return Ok((IssueEventOutcome::Ignored, None));
+        }
+        let task_id = Uuid::new_v4();
+        let create_task = CreateTask {
+            project_id: sync.project_id,
+            title: issue.title.clone(),
+            description: Some(task_description(issue)),
+            status: None,
+            parent_workspace_id: None,
+            image_ids: None,
+            shared_task_id: None,
+            generate_details: None,
+        };
+        Task::create(pool, &create_task, task_id).await?;
+        GitHubIssueLink::create(
+            pool,
+            task_id,
+            sync.project_id,
+            &sync.repo_full_name,
+            issue.number,
+            &issue.html_url,
+            true,
+        )
+        .await?;
+        tracing::info!(
+            "Created task {} from GitHub issue {}#{}",
+            task_id,
+            sync.repo_full_name,
+            issue.number
+        );
+        return Ok((IssueEventOutcome::Created, Some(task_id)));
+    };
+
+    match event.action.as_str() {
+        "edited" => {
+            Task::update(
+                pool,
+                task.id,
+                task.project_id,
+                issue.title.clone(),
+                Some(task_description(issue)),
+                task.status.clone(),
+                task.parent_workspace_id,
+            )
+            .await?;
+        }
+        "closed" => {
+            if !is_closed(&task.status) {
+                task_workflow::update_task_status(pool, task.id, TaskStatus::Done).await?;
+            }
+            GitHubIssueLink::set_issue_open(pool, task.id, false).await?;
+        }
+        "reopened" => {
+            if is_closed(&task.status) {
+                task_workflow::update_task_status(pool, task.id, TaskStatus::Todo).await?;
+            }
+            GitHubIssueLink::set_issue_open(pool, task.id, true).await?;
+        }
+        _ => return Ok((IssueEventOutcome::Ignored, Some(task.id))),
+    }
+    Ok((IssueEventOutcome::Updated, Some(task.id)))
+}
+
+/// Closes the issues of tasks moved to done or cancelled, and reopens them when the task is
+/// picked up again.
+pub struct GitHubIssueSyncMonitor {
+    db: DBService,
+}
+
+impl GitHubIssueSyncMonitor {
+    pub async fn spawn(db: DBService) -> tokio::task::JoinHandle<()> {
+        let monitor = Self { db };
+        tokio::spawn(async move {
+            let mut interval = interval(SYNC_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = monitor.sync().await {
+                    tracing::error!("Failed to sync GitHub issue states: {}", e);
+                }
+            }
+        })
+    }
+
+    async fn sync(&self) -> Result<(), sqlx::Error> {
+        let pool = &self.db.pool;
+        let links = GitHubIssueLink::find_out_of_sync(pool).await?;
+        if links.is_empty() {
+            return Ok(());
+        }
+        // GitHubService uses the gh CLI, so no token is needed
+        let github = match GitHubService::new() {
+            Ok(github) => github,
+            Err(e) => {
+                tracing::warn!("Skipping GitHub issue sync: {}", e);
+                return Ok(());
+            }
+        };
+        for link in links {
+            // Left to the outbox worker while GitHub is unreachable
+            if IntegrationOutboxEntry::exists_for_key(pool, &outbox::issue_state_key(link.task_id))
+                .await?
+            {
+                continue;
+            }
+            let open = !link.issue_open;
+            let result = match GitHubRepoInfo::from_remote_url(&link.issue_url) {
+                Ok(repo_info) => {
+                    github
+                        .set_issue_state(&repo_info, link.issue_number, open)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => GitHubIssueLink::set_issue_open(pool, link.task_id, open).await?,
+                Err(e) if e.is_connectivity_error() => {
+                    let action = OutboxAction::GithubIssueState {
+                        task_id: link.task_id,
+                        issue_url: link.issue_url.clone(),
+                        issue_number: link.issue_number,
+                        open,
+                    };
+                    if let Err(e) = outbox::enqueue(pool, &action, &e.to_string()).await {
+                        tracing::error!("Failed to queue GitHub issue sync: {}", e);
+                    }
+                }
+                Err(e) => tracing::warn!(
+                    "Failed to {} GitHub issue {}#{}: {}",
+                    if open { "reopen" } else { "close" },
+                    link.repo_full_name,
+                    link.issue_number,
+                    e
+                ),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(labels: &[&str]) -> Issue {
+        Issue {
+            number: 7,
+            title: "Crash on save".to_string(),
+            body: None,
+            html_url: "https://github.com/acme/app/issues/7".to_string(),
+            state: "open".to_string(),
+            labels: labels
+                .iter()
+                .map(|name| Label {
+                    name: name.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn verifies_signatures() {
+        let payload = br#"{"action":"opened"}"#;
+        let mut mac = HmacSha256::new_from_slice(b"secret").unwrap();
+        mac.update(payload);
+        let header = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+
+        assert!(verify_signature(b"secret", &header, payload));
+        assert!(!verify_signature(b"other", &header, payload));
+        assert!(!verify_signature(b"secret", "sha256=zz", payload));
+    }
+
+    #[test]
+    fn label_filter_is_case_insensitive() {
+        assert!(matches_filter(&issue(&[]), None));
+        assert!(matches_filter(&issue(&["Vibe"]), Some("vibe")));
+        assert!(!matches_filter(&issue(&["bug"]), Some("vibe")));
+    }
+}
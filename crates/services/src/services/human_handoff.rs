@@ -0,0 +1,114 @@
+//! Coding agent runs ending with a handoff block (see [`executors::logs::human_handoff`]) leave
+//! the attempt blocked on a person: the note is stored, the task moves to the blocked column and
+//! the owner is notified instead of the attempt going to review.
+
+use std::collections::BTreeMap;
+
+use executors::logs::{
+    NormalizedEntryType,
+    human_handoff::{CLOSE_TAG, HumanHandoff, OPEN_TAG},
+    utils::patch::extract_normalized_entry_from_patch,
+};
+use utils::log_msg::LogMsg;
+
+/// Section appended to attempt prompts when the project has the feature on.
+pub fn instructions() -> String {
+    format!(
+        "If you cannot finish the task without a person, for example because you need access, a \
+decision or information you do not have, stop and end your last message with:\n\n{OPEN_TAG}\n\
+Done: what you completed\nBlocked: what stops you\nNext steps:\n- what a person should do or \
+decide\n{CLOSE_TAG}\n\nDo not use it for questions you can answer yourself."
+    )
+}
+
+/// The handoff the run ended with, if its final agent message declared one.
+pub fn declared(history: &[LogMsg]) -> Option<HumanHandoff> {
+    // Entries are replaced in place as they update, so keep the last version of each
+    let mut entries = BTreeMap::new();
+    for msg in history {
+        if let LogMsg::JsonPatch(patch) = msg
+            && let Some((index, entry)) = extract_normalized_entry_from_patch(patch)
+        {
+            entries.insert(index, entry);
+        }
+    }
+
+    entries
+        .into_values()
+        .rev()
+        .find_map(|entry| match entry.entry_type {
+            NormalizedEntryType::HumanHandoff { handoff } => Some(Some(handoff)),
+            NormalizedEntryType::AssistantMessage => Some(None),
+            _ => None,
+        })
+        .flatten()
+}
+
+/// Body of the notification telling the owner the attempt needs them.
+pub fn notification_message(task_title: &str, handoff: &HumanHandoff) -> String {
+    let mut message = format!("✋ '{task_title}' needs you\nBlocked: {}", handoff.blocked);
+    if !handoff.done.is_empty() {
+        message.push_str(&format!("\nDone: {}", handoff.done));
+    }
+    if !handoff.next_steps.is_empty() {
+        message.push_str("\nNext steps:");
+        for step in &handoff.next_steps {
+            message.push_str(&format!("\n- {step}"));
+        }
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::logs::{NormalizedEntry, utils::ConversationPatch};
+
+    use super::*;
+
+    fn message(index: usize, content: &str) -> LogMsg {
+        LogMsg::JsonPatch(ConversationPatch::add_normalized_entry(
+            index,
+            NormalizedEntry {
+                timestamp: None,
+                entry_type: NormalizedEntryType::AssistantMessage,
+                content: content.to_string(),
+                metadata: None,
+            },
+        ))
+    }
+
+    #[test]
+    fn declared_uses_the_final_agent_message() {
+        let handoff = "<needs_human>\nBlocked: no staging access\n</needs_human>";
+        let history = vec![message(0, "Looking around"), message(1, handoff)];
+        assert_eq!(declared(&history).unwrap().blocked, "no staging access");
+
+        // Working on after the handoff withdraws it
+        let history = vec![message(0, handoff), message(1, "Found a way, done.")];
+        assert!(declared(&history).is_none());
+    }
+
+    #[test]
+    fn instructions_describe_the_parsed_format() {
+        let example = instructions();
+        let handoff = executors::logs::human_handoff::parse(&example).unwrap();
+        assert_eq!(handoff.blocked, "what stops you");
+        assert_eq!(handoff.next_steps.len(), 1);
+    }
+
+    #[test]
+    fn notification_lists_next_steps() {
+        let message = notification_message(
+            "Fix login",
+            &HumanHandoff {
+                done: String::new(),
+                blocked: "Missing API key".to_string(),
+                next_steps: vec!["Add the key".to_string(), "Restart".to_string()],
+            },
+        );
+        assert_eq!(
+            message,
+            "✋ 'Fix login' needs you\nBlocked: Missing API key\nNext steps:\n- Add the key\n- Restart"
+        );
+    }
+}
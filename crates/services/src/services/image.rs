@@ -1,13 +1,14 @@
-use std::{
-    fs,
-    path::{Path, PathBuf},
-};
+use std::path::Path;
 
 use db::models::image::{CreateImage, Image};
 use sha2::{Digest, Sha256};
 use sqlx::SqlitePool;
 use uuid::Uuid;
 
+use crate::services::blob_storage::{BlobReader, BlobStorage, BlobStorageError};
+
+const IMAGES_PREFIX: &str = "images";
+
 #[derive(Debug, thiserror::Error)]
 pub enum ImageError {
     #[error("IO error: {0}")]
@@ -16,6 +17,9 @@ pub enum ImageError {
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
 
+    #[error("Storage error: {0}")]
+    Storage(#[from] BlobStorageError),
+
     #[error("Invalid image format")]
     InvalidFormat,
 
@@ -31,20 +35,22 @@ pub enum ImageError {
 
 #[derive(Clone)]
 pub struct ImageService {
-    cache_dir: PathBuf,
+    storage: BlobStorage,
     pool: SqlitePool,
     max_size_bytes: u64,
 }
 
+fn storage_key(file_path: &str) -> String {
+    format!("{IMAGES_PREFIX}/{file_path}")
+}
+
 impl ImageService {
-    pub fn new(pool: SqlitePool) -> Result<Self, ImageError> {
-        let cache_dir = utils::cache_dir().join("images");
-        fs::create_dir_all(&cache_dir)?;
-        Ok(Self {
-            cache_dir,
+    pub fn new(pool: SqlitePool, storage: BlobStorage) -> Self {
+        Self {
+            storage,
             pool,
             max_size_bytes: 20 * 1024 * 1024, // 20MB default
-        })
+        }
     }
 
     pub async fn store_image(
@@ -88,8 +94,9 @@ impl ImageService {
         }
 
         let new_filename = format!("{}.{}", Uuid::new_v4(), extension);
-        let cached_path = self.cache_dir.join(&new_filename);
-        fs::write(&cached_path, data)?;
+        self.storage
+            .put(&storage_key(&new_filename), data.to_vec())
+            .await?;
 
         let image = Image::create(
             &self.pool,
@@ -141,8 +148,30 @@ impl ImageService {
         Ok(())
     }
 
-    pub fn get_absolute_path(&self, image: &Image) -> PathBuf {
-        self.cache_dir.join(&image.file_path)
+    /// Store an image file from a worktree, e.g. a screenshot an agent wants the user to see.
+    pub async fn store_worktree_image(
+        &self,
+        worktree_path: &Path,
+        relative_path: &str,
+    ) -> Result<Image, ImageError> {
+        let relative = Path::new(relative_path);
+        if !relative
+            .components()
+            .all(|c| matches!(c, std::path::Component::Normal(_)))
+        {
+            return Err(ImageError::NotFound);
+        }
+        let data = tokio::fs::read(worktree_path.join(relative)).await?;
+        let file_name = relative
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| relative_path.to_string());
+        self.store_image(&data, &file_name).await
+    }
+
+    /// Open the stored image file for streaming.
+    pub async fn open_image(&self, image: &Image) -> Result<BlobReader, ImageError> {
+        Ok(self.storage.open(&storage_key(&image.file_path)).await?)
     }
 
     pub async fn get_image(&self, id: Uuid) -> Result<Option<Image>, ImageError> {
@@ -151,10 +180,7 @@ impl ImageService {
 
     pub async fn delete_image(&self, id: Uuid) -> Result<(), ImageError> {
         if let Some(image) = Image::find_by_id(&self.pool, id).await? {
-            let file_path = self.cache_dir.join(&image.file_path);
-            if file_path.exists() {
-                fs::remove_file(file_path)?;
-            }
+            self.storage.delete(&storage_key(&image.file_path)).await?;
 
             Image::delete(&self.pool, id).await?;
         }
@@ -168,7 +194,7 @@ impl ImageService {
         task_id: Uuid,
     ) -> Result<(), ImageError> {
         let images = Image::find_by_task_id(&self.pool, task_id).await?;
-        self.copy_images(worktree_path, images)
+        self.copy_images(worktree_path, images).await
     }
 
     pub async fn copy_images_by_ids_to_worktree(
@@ -182,11 +208,15 @@ impl ImageService {
                 images.push(image);
             }
         }
-        self.copy_images(worktree_path, images)
+        self.copy_images(worktree_path, images).await
     }
 
     /// Copy images to the worktree. Skips images that already exist at target.
-    fn copy_images(&self, worktree_path: &Path, images: Vec<Image>) -> Result<(), ImageError> {
+    async fn copy_images(
+        &self,
+        worktree_path: &Path,
+        images: Vec<Image>,
+    ) -> Result<(), ImageError> {
         if images.is_empty() {
             return Ok(());
         }
@@ -210,21 +240,24 @@ impl ImageService {
         }
 
         for image in images {
-            let src = self.cache_dir.join(&image.file_path);
             let dst = images_dir.join(&image.file_path);
 
             if dst.exists() {
                 continue;
             }
 
-            if src.exists() {
-                if let Err(e) = std::fs::copy(&src, &dst) {
-                    tracing::error!("Failed to copy {}: {}", image.file_path, e);
-                } else {
-                    tracing::debug!("Copied {}", image.file_path);
+            match self.storage.get(&storage_key(&image.file_path)).await {
+                Ok(data) => {
+                    if let Err(e) = tokio::fs::write(&dst, data).await {
+                        tracing::error!("Failed to copy {}: {}", image.file_path, e);
+                    } else {
+                        tracing::debug!("Copied {}", image.file_path);
+                    }
+                }
+                Err(BlobStorageError::NotFound(key)) => {
+                    tracing::warn!("Missing stored image: {}", key);
                 }
-            } else {
-                tracing::warn!("Missing cache file: {}", src.display());
+                Err(e) => tracing::error!("Failed to read {}: {}", image.file_path, e),
             }
         }
 
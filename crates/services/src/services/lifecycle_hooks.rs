@@ -0,0 +1,164 @@
+use std::{path::Path, process::Stdio};
+
+use db::models::project_lifecycle_hook::{LifecycleHook, ProjectLifecycleHook};
+use executors::actions::{
+    ExecutorAction, ExecutorActionType,
+    script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+};
+use thiserror::Error;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, BufReader},
+    process::Command,
+};
+use utils::{msg_store::MsgStore, shell::get_shell_command};
+
+#[derive(Debug, Error)]
+pub enum LifecycleHookError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("The {hook} hook failed with exit code {exit_code}")]
+    Failed { hook: LifecycleHook, exit_code: i32 },
+    #[error("The blocking E2E verification has not passed: {0}")]
+    E2eNotPassed(String),
+}
+
+/// Script run for a hook. Advisory hooks always exit successfully so a failure is reported in
+/// their output without stopping the actions chained after them.
+pub fn hook_script(hook: &ProjectLifecycleHook) -> String {
+    if hook.blocking {
+        return hook.command.clone();
+    }
+    format!(
+        "(\n{command}\n)\nstatus=$?\nif [ \"$status\" -ne 0 ]; then\n  \
+         echo \"Advisory {name} hook failed with exit code $status\" >&2\nfi\nexit 0\n",
+        command = hook.command,
+        name = hook.hook,
+    )
+}
+
+/// Script action running a hook in the workspace root, followed by `next_action`.
+pub fn hook_action(
+    hook: &ProjectLifecycleHook,
+    next_action: Option<Box<ExecutorAction>>,
+) -> ExecutorAction {
+    ExecutorAction::new(
+        ExecutorActionType::ScriptRequest(ScriptRequest {
+            script: hook_script(hook),
+            language: ScriptRequestLanguage::Bash,
+            context: ScriptContext::LifecycleHook,
+            working_dir: None,
+        }),
+        next_action,
+    )
+}
+
+/// Run a hook outside of an execution chain, streaming its output into `msg_store`. Only
+/// blocking hooks return an error when the command fails.
+pub async fn run_hook(
+    hook: &ProjectLifecycleHook,
+    current_dir: &Path,
+    msg_store: &MsgStore,
+) -> Result<(), LifecycleHookError> {
+    let (shell_cmd, shell_arg) = get_shell_command();
+    let mut child = Command::new(shell_cmd)
+        .kill_on_drop(true)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .arg(shell_arg)
+        .arg(&hook.command)
+        .current_dir(current_dir)
+        .spawn()?;
+
+    msg_store.push_stdout(format!("Running {} hook: {}\n", hook.hook, hook.command));
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    tokio::join!(
+        forward_lines(stdout, |line| msg_store.push_stdout(line)),
+        forward_lines(stderr, |line| msg_store.push_stderr(line)),
+    );
+    let status = child.wait().await?;
+
+    if status.success() {
+        return Ok(());
+    }
+    let error = LifecycleHookError::Failed {
+        hook: hook.hook,
+        exit_code: status.code().unwrap_or(-1),
+    };
+    if hook.blocking {
+        return Err(error);
+    }
+    msg_store.push_stderr(format!("Advisory hook ignored: {error}\n"));
+    tracing::warn!(project_id = %hook.project_id, "{error}");
+    Ok(())
+}
+
+async fn forward_lines<R: AsyncRead + Unpin>(reader: Option<R>, push: impl Fn(String)) {
+    let Some(reader) = reader else {
+        return;
+    };
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        push(format!("{line}\n"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use utils::log_msg::LogMsg;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn hook(command: &str, blocking: bool) -> ProjectLifecycleHook {
+        ProjectLifecycleHook {
+            project_id: Uuid::new_v4(),
+            hook: LifecycleHook::BeforeMerge,
+            command: command.to_string(),
+            blocking,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn advisory_scripts_always_succeed() {
+        assert_eq!(hook_script(&hook("make lint", true)), "make lint");
+        let script = hook_script(&hook("make lint", false));
+        assert!(script.starts_with("(\nmake lint\n)"));
+        assert!(script.contains("Advisory before_merge hook failed"));
+        assert!(script.ends_with("exit 0\n"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn streams_output_and_reports_blocking_failures() {
+        let dir = std::env::temp_dir();
+        let store = MsgStore::new();
+        run_hook(&hook("echo out; echo err >&2", true), &dir, &store)
+            .await
+            .unwrap();
+        let history = store.get_history();
+        assert!(
+            history
+                .iter()
+                .any(|msg| matches!(msg, LogMsg::Stdout(line) if line == "out\n"))
+        );
+        assert!(
+            history
+                .iter()
+                .any(|msg| matches!(msg, LogMsg::Stderr(line) if line == "err\n"))
+        );
+
+        let failed = run_hook(&hook("exit 3", true), &dir, &store).await;
+        assert!(matches!(
+            failed,
+            Err(LifecycleHookError::Failed { exit_code: 3, .. })
+        ));
+        assert!(run_hook(&hook("exit 3", false), &dir, &store).await.is_ok());
+    }
+}
@@ -0,0 +1,359 @@
+//! Linters run against the files an attempt changed, with their JSON output parsed into
+//! annotations.
+
+use std::{path::Path, process::Stdio, time::Duration};
+
+use db::models::{
+    attempt_lint_annotation::{AttemptLintAnnotation, CreateLintAnnotation, LintSeverity},
+    project_linter::Linter,
+};
+use serde::Deserialize;
+use thiserror::Error;
+use tokio::process::Command;
+use utils::shell::get_shell_command;
+
+const RUN_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+/// Annotations listed in a fix follow-up; the agent reruns the linter for the rest
+const MAX_PROMPT_ANNOTATIONS: usize = 50;
+const ESLINT_EXTENSIONS: [&str; 6] = ["js", "jsx", "mjs", "cjs", "ts", "tsx"];
+
+#[derive(Debug, Error)]
+pub enum LintError {
+    #[error("Failed to run {0}: {1}")]
+    Io(Linter, std::io::Error),
+    #[error("{0} did not finish within {1:?}")]
+    Timeout(Linter, Duration),
+    #[error("{linter} printed no JSON output: {stderr}")]
+    InvalidOutput { linter: Linter, stderr: String },
+}
+
+pub fn default_command(linter: Linter) -> &'static str {
+    match linter {
+        Linter::Clippy => "cargo clippy --all-targets --message-format=json",
+        Linter::Eslint => "npx --no-install eslint --format json",
+    }
+}
+
+/// Whether the linter checks the file at `path`.
+pub fn handles(linter: Linter, path: &str) -> bool {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    match linter {
+        Linter::Clippy => extension == "rs",
+        Linter::Eslint => ESLINT_EXTENSIONS.contains(&extension),
+    }
+}
+
+/// ESLint is given the changed files; clippy checks whole crates and is filtered afterwards.
+pub fn command_with_files(linter: Linter, command: &str, files: &[String]) -> String {
+    match linter {
+        Linter::Clippy => command.to_string(),
+        Linter::Eslint => files.iter().fold(command.to_string(), |command, file| {
+            let quoted = shlex::try_quote(file)
+                .map(|quoted| quoted.to_string())
+                .unwrap_or_else(|_| file.clone());
+            format!("{command} {quoted}")
+        }),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<Diagnostic>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Diagnostic {
+    message: String,
+    level: String,
+    code: Option<DiagnosticCode>,
+    #[serde(default)]
+    spans: Vec<DiagnosticSpan>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiagnosticCode {
+    code: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiagnosticSpan {
+    file_name: String,
+    line_start: i64,
+    column_start: i64,
+    is_primary: bool,
+}
+
+fn push_unique(annotations: &mut Vec<CreateLintAnnotation>, annotation: CreateLintAnnotation) {
+    if !annotations.contains(&annotation) {
+        annotations.push(annotation);
+    }
+}
+
+/// Parse `cargo clippy --message-format=json` output. `None` when it printed no cargo messages.
+pub fn parse_clippy(stdout: &str) -> Option<Vec<CreateLintAnnotation>> {
+    let messages: Vec<CargoMessage> = stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    if messages.is_empty() {
+        return None;
+    }
+
+    let mut annotations = Vec::new();
+    for diagnostic in messages
+        .into_iter()
+        .filter(|m| m.reason == "compiler-message")
+        .filter_map(|m| m.message)
+    {
+        let severity = match diagnostic.level.as_str() {
+            "warning" => LintSeverity::Warning,
+            level if level.starts_with("error") => LintSeverity::Error,
+            _ => continue,
+        };
+        // Summaries such as "3 warnings emitted" have no location
+        let Some(span) = diagnostic.spans.iter().find(|span| span.is_primary) else {
+            continue;
+        };
+        push_unique(
+            &mut annotations,
+            CreateLintAnnotation {
+                file: span.file_name.clone(),
+                line: span.line_start,
+                col: span.column_start,
+                rule: diagnostic.code.map(|code| code.code),
+                severity,
+                message: diagnostic.message,
+            },
+        );
+    }
+    Some(annotations)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EslintFile {
+    file_path: String,
+    #[serde(default)]
+    messages: Vec<EslintMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EslintMessage {
+    rule_id: Option<String>,
+    severity: u8,
+    message: String,
+    #[serde(default)]
+    line: i64,
+    #[serde(default)]
+    column: i64,
+}
+
+/// Parse `eslint --format json` output, with file paths made relative to `repo_dir`.
+pub fn parse_eslint(stdout: &str, repo_dir: &Path) -> Option<Vec<CreateLintAnnotation>> {
+    let files: Vec<EslintFile> = serde_json::from_str(stdout.trim()).ok()?;
+    let mut annotations = Vec::new();
+    for file in files {
+        let path = Path::new(&file.file_path);
+        let path = path
+            .strip_prefix(repo_dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string();
+        for message in file.messages {
+            let severity = match message.severity {
+                2 => LintSeverity::Error,
+                1 => LintSeverity::Warning,
+                _ => continue,
+            };
+            push_unique(
+                &mut annotations,
+                CreateLintAnnotation {
+                    file: path.clone(),
+                    line: message.line,
+                    col: message.column,
+                    rule: message.rule_id,
+                    severity,
+                    message: message.message,
+                },
+            );
+        }
+    }
+    Some(annotations)
+}
+
+/// Run a linter in `repo_dir` against the changed `files`, relative to it. Annotations keep the
+/// paths the linter printed, relative to the repository.
+pub async fn run(
+    linter: Linter,
+    command: &str,
+    repo_dir: &Path,
+    files: &[String],
+) -> Result<Vec<CreateLintAnnotation>, LintError> {
+    let (shell_cmd, shell_arg) = get_shell_command();
+    let mut cmd = Command::new(shell_cmd);
+    cmd.arg(shell_arg)
+        .arg(command_with_files(linter, command, files))
+        .current_dir(repo_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    // Linters exit non-zero when they report errors, so only their output is checked
+    let output = tokio::time::timeout(RUN_TIMEOUT, cmd.output())
+        .await
+        .map_err(|_| LintError::Timeout(linter, RUN_TIMEOUT))?
+        .map_err(|e| LintError::Io(linter, e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let annotations = match linter {
+        Linter::Clippy => parse_clippy(&stdout),
+        Linter::Eslint => parse_eslint(&stdout, repo_dir),
+    };
+    annotations.ok_or_else(|| LintError::InvalidOutput {
+        linter,
+        stderr: strip_ansi_escapes::strip_str(String::from_utf8_lossy(&output.stderr))
+            .trim()
+            .lines()
+            .last()
+            .unwrap_or_default()
+            .to_string(),
+    })
+}
+
+/// Follow-up asking the agent to fix the error annotations.
+pub fn fix_prompt(annotations: &[AttemptLintAnnotation]) -> String {
+    let errors: Vec<_> = annotations
+        .iter()
+        .filter(|a| a.severity == LintSeverity::Error)
+        .collect();
+    let mut prompt =
+        "The linters reported these errors in the files you changed. Please fix them:\n"
+            .to_string();
+    for annotation in errors.iter().take(MAX_PROMPT_ANNOTATIONS) {
+        prompt.push_str(&format!(
+            "\n- {}:{}:{} [{}] {}",
+            annotation.file,
+            annotation.line,
+            annotation.col,
+            annotation
+                .rule
+                .as_deref()
+                .unwrap_or(&annotation.linter.to_string()),
+            annotation.message
+        ));
+    }
+    if errors.len() > MAX_PROMPT_ANNOTATIONS {
+        prompt.push_str(&format!(
+            "\n\n{} more errors are not listed; rerun the linters to see them.",
+            errors.len() - MAX_PROMPT_ANNOTATIONS
+        ));
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn parses_clippy_messages() {
+        let stdout = [
+            r#"{"reason":"compiler-artifact","package_id":"app"}"#,
+            r#"{"reason":"compiler-message","message":{"message":"unneeded `return` statement","level":"warning","code":{"code":"clippy::needless_return","explanation":null},"spans":[{"file_name":"src/lib.rs","line_start":3,"column_start":5,"is_primary":true}]}}"#,
+            r#"{"reason":"compiler-message","message":{"message":"unneeded `return` statement","level":"warning","code":{"code":"clippy::needless_return","explanation":null},"spans":[{"file_name":"src/lib.rs","line_start":3,"column_start":5,"is_primary":true}]}}"#,
+            r#"{"reason":"compiler-message","message":{"message":"mismatched types","level":"error","code":{"code":"E0308","explanation":"..."},"spans":[{"file_name":"src/main.rs","line_start":9,"column_start":1,"is_primary":false},{"file_name":"src/main.rs","line_start":10,"column_start":13,"is_primary":true}]}}"#,
+            r#"{"reason":"compiler-message","message":{"message":"1 warning emitted","level":"warning","code":null,"spans":[]}}"#,
+            r#"{"reason":"build-finished","success":false}"#,
+        ]
+        .join("\n");
+        let annotations = parse_clippy(&stdout).unwrap();
+        assert_eq!(
+            annotations,
+            vec![
+                CreateLintAnnotation {
+                    file: "src/lib.rs".to_string(),
+                    line: 3,
+                    col: 5,
+                    rule: Some("clippy::needless_return".to_string()),
+                    severity: LintSeverity::Warning,
+                    message: "unneeded `return` statement".to_string(),
+                },
+                CreateLintAnnotation {
+                    file: "src/main.rs".to_string(),
+                    line: 10,
+                    col: 13,
+                    rule: Some("E0308".to_string()),
+                    severity: LintSeverity::Error,
+                    message: "mismatched types".to_string(),
+                },
+            ]
+        );
+        assert!(parse_clippy("error: could not find `Cargo.toml`").is_none());
+    }
+
+    #[test]
+    fn parses_eslint_results_relative_to_the_repo() {
+        let stdout = r#"[
+            {"filePath":"/w/app/src/a.ts","messages":[
+                {"ruleId":"no-unused-vars","severity":2,"message":"'x' is unused","line":1,"column":7},
+                {"ruleId":"eqeqeq","severity":1,"message":"Expected '==='","line":4,"column":9}
+            ],"errorCount":1,"warningCount":1},
+            {"filePath":"/w/app/src/b.ts","messages":[
+                {"ruleId":null,"fatal":true,"severity":2,"message":"Parsing error: ';' expected","line":2,"column":1}
+            ]}
+        ]"#;
+        let annotations = parse_eslint(stdout, Path::new("/w/app")).unwrap();
+        assert_eq!(annotations.len(), 3);
+        assert_eq!(annotations[0].file, "src/a.ts");
+        assert_eq!(annotations[0].severity, LintSeverity::Error);
+        assert_eq!(annotations[1].severity, LintSeverity::Warning);
+        assert_eq!(annotations[2].rule, None);
+        assert!(parse_eslint("Oops! Something went wrong!", Path::new("/w")).is_none());
+    }
+
+    #[test]
+    fn only_eslint_is_given_the_changed_files() {
+        let files = vec!["src/a.ts".to_string(), "src/my file.ts".to_string()];
+        assert_eq!(
+            command_with_files(Linter::Eslint, "npx eslint --format json", &files),
+            "npx eslint --format json src/a.ts 'src/my file.ts'"
+        );
+        assert_eq!(
+            command_with_files(Linter::Clippy, "cargo clippy", &files),
+            "cargo clippy"
+        );
+        assert!(handles(Linter::Eslint, "app/src/a.tsx"));
+        assert!(!handles(Linter::Eslint, "app/src/lib.rs"));
+        assert!(handles(Linter::Clippy, "app/src/lib.rs"));
+    }
+
+    #[test]
+    fn fix_prompt_lists_only_errors() {
+        let annotation = |severity, message: &str| AttemptLintAnnotation {
+            id: Uuid::new_v4(),
+            workspace_id: Uuid::nil(),
+            linter: Linter::Eslint,
+            file: "app/src/a.ts".to_string(),
+            line: 1,
+            col: 7,
+            rule: None,
+            severity,
+            message: message.to_string(),
+            created_at: Utc::now(),
+        };
+        let prompt = fix_prompt(&[
+            annotation(LintSeverity::Error, "'x' is unused"),
+            annotation(LintSeverity::Warning, "Expected '==='"),
+        ]);
+        assert!(prompt.contains("\n- app/src/a.ts:1:7 [eslint] 'x' is unused"));
+        assert!(!prompt.contains("Expected"));
+    }
+}
@@ -0,0 +1,260 @@
+use std::{sync::Arc, time::Duration};
+
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use crate::services::config::{Config, EmbeddingConfig, LlmConfig, LlmProvider};
+
+const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const OPENAI_API_URL: &str = "https://api.openai.com/v1";
+const MAX_OUTPUT_TOKENS: u32 = 4096;
+/// Inputs sent per embeddings request
+const EMBEDDING_BATCH_SIZE: usize = 256;
+
+#[derive(Debug, Error)]
+pub enum LlmError {
+    #[error("No API key configured for the language model")]
+    MissingApiKey,
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error("Language model request failed ({status}): {body}")]
+    Api { status: u16, body: String },
+    #[error("Unexpected language model response: {0}")]
+    InvalidResponse(String),
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingsReply {
+    data: Vec<EmbeddingItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingItem {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+/// Client for the language model configured under `llm` in the config.
+#[derive(Debug, Clone)]
+pub struct LlmService {
+    config: Arc<RwLock<Config>>,
+    http: reqwest::Client,
+}
+
+impl LlmService {
+    pub fn new(config: Arc<RwLock<Config>>) -> Self {
+        let http = utils::http::client_builder()
+            .timeout(Duration::from_secs(120))
+            .build()
+            .unwrap_or_default();
+        Self { config, http }
+    }
+
+    /// The current `llm` section of the config.
+    pub async fn settings(&self) -> LlmConfig {
+        self.config.read().await.llm.clone()
+    }
+
+    /// Send a single-turn prompt and return the model's text reply.
+    pub async fn complete(&self, system: &str, prompt: &str) -> Result<String, LlmError> {
+        let config = self.settings().await;
+        let api_key = config
+            .api_key
+            .clone()
+            .filter(|k| !k.trim().is_empty())
+            .or_else(|| std::env::var(api_key_env(config.provider)).ok());
+        match config.provider {
+            LlmProvider::Anthropic => {
+                let api_key = api_key.ok_or(LlmError::MissingApiKey)?;
+                self.complete_anthropic(&config, &api_key, system, prompt)
+                    .await
+            }
+            // Local OpenAI-compatible servers usually run without a key
+            LlmProvider::OpenAi if api_key.is_none() && config.base_url.is_none() => {
+                Err(LlmError::MissingApiKey)
+            }
+            LlmProvider::OpenAi => {
+                self.complete_openai(&config, api_key.as_deref(), system, prompt)
+                    .await
+            }
+        }
+    }
+
+    /// Like [`Self::complete`], parsing the first JSON value in the reply.
+    pub async fn complete_json<T: DeserializeOwned>(
+        &self,
+        system: &str,
+        prompt: &str,
+    ) -> Result<T, LlmError> {
+        let reply = self.complete(system, prompt).await?;
+        parse_json_reply(&reply)
+    }
+
+    /// Embed texts with an OpenAI-compatible embeddings API, returning vectors in input order.
+    pub async fn embed(
+        &self,
+        config: &EmbeddingConfig,
+        texts: &[String],
+    ) -> Result<Vec<Vec<f32>>, LlmError> {
+        let api_key = config
+            .api_key
+            .clone()
+            .filter(|k| !k.trim().is_empty())
+            .or_else(|| std::env::var("OPENAI_API_KEY").ok());
+        if api_key.is_none() && config.base_url.is_none() {
+            return Err(LlmError::MissingApiKey);
+        }
+        let base_url = config.base_url.as_deref().unwrap_or(OPENAI_API_URL);
+
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(EMBEDDING_BATCH_SIZE) {
+            let mut request = self
+                .http
+                .post(format!("{}/embeddings", base_url.trim_end_matches('/')))
+                .json(&json!({ "model": config.model, "input": batch }));
+            if let Some(api_key) = &api_key {
+                request = request.bearer_auth(api_key);
+            }
+            let body = read_body(request.send().await?).await?;
+
+            let mut reply: EmbeddingsReply = serde_json::from_value(body)
+                .map_err(|e| LlmError::InvalidResponse(e.to_string()))?;
+            if reply.data.len() != batch.len() {
+                return Err(LlmError::InvalidResponse(format!(
+                    "expected {} embeddings, got {}",
+                    batch.len(),
+                    reply.data.len()
+                )));
+            }
+            reply.data.sort_by_key(|item| item.index);
+            embeddings.extend(reply.data.into_iter().map(|item| item.embedding));
+        }
+        Ok(embeddings)
+    }
+
+    async fn complete_anthropic(
+        &self,
+        config: &LlmConfig,
+        api_key: &str,
+        system: &str,
+        prompt: &str,
+    ) -> Result<String, LlmError> {
+        let base_url = config.base_url.as_deref().unwrap_or(ANTHROPIC_API_URL);
+        let response = self
+            .http
+            .post(format!("{}/messages", base_url.trim_end_matches('/')))
+            .header("x-api-key", api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .json(&json!({
+                "model": config.model,
+                "max_tokens": MAX_OUTPUT_TOKENS,
+                "system": system,
+                "messages": [{ "role": "user", "content": prompt }],
+            }))
+            .send()
+            .await?;
+        let body = read_body(response).await?;
+
+        let text = body["content"]
+            .as_array()
+            .map(|blocks| {
+                blocks
+                    .iter()
+                    .filter_map(|b| b["text"].as_str())
+                    .collect::<String>()
+            })
+            .unwrap_or_default();
+        if text.is_empty() {
+            return Err(LlmError::InvalidResponse("reply has no text".to_string()));
+        }
+        Ok(text)
+    }
+
+    async fn complete_openai(
+        &self,
+        config: &LlmConfig,
+        api_key: Option<&str>,
+        system: &str,
+        prompt: &str,
+    ) -> Result<String, LlmError> {
+        let base_url = config.base_url.as_deref().unwrap_or(OPENAI_API_URL);
+        let mut request = self
+            .http
+            .post(format!(
+                "{}/chat/completions",
+                base_url.trim_end_matches('/')
+            ))
+            .json(&json!({
+                "model": config.model,
+                "max_tokens": MAX_OUTPUT_TOKENS,
+                "messages": [
+                    { "role": "system", "content": system },
+                    { "role": "user", "content": prompt },
+                ],
+            }));
+        if let Some(api_key) = api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let body = read_body(request.send().await?).await?;
+
+        body["choices"][0]["message"]["content"]
+            .as_str()
+            .filter(|text| !text.is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| LlmError::InvalidResponse("reply has no text".to_string()))
+    }
+}
+
+fn api_key_env(provider: LlmProvider) -> &'static str {
+    match provider {
+        LlmProvider::Anthropic => "ANTHROPIC_API_KEY",
+        LlmProvider::OpenAi => "OPENAI_API_KEY",
+    }
+}
+
+async fn read_body(response: reqwest::Response) -> Result<Value, LlmError> {
+    let status = response.status();
+    if !status.is_success() {
+        return Err(LlmError::Api {
+            status: status.as_u16(),
+            body: response.text().await.unwrap_or_default(),
+        });
+    }
+    Ok(response.json().await?)
+}
+
+/// Parse a reply that should be JSON, tolerating code fences and prose around it.
+pub fn parse_json_reply<T: DeserializeOwned>(reply: &str) -> Result<T, LlmError> {
+    let start = reply.find(['{', '[']);
+    let end = reply.rfind(['}', ']']);
+    let json = match (start, end) {
+        (Some(start), Some(end)) if start < end => &reply[start..=end],
+        _ => reply,
+    };
+    serde_json::from_str(json).map_err(|e| LlmError::InvalidResponse(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Reply {
+        items: Vec<String>,
+    }
+
+    #[test]
+    fn parses_json_wrapped_in_prose_and_fences() {
+        let reply = "Here you go:\n```json\n{\"items\": [\"a\", \"b\"]}\n```\nLet me know!";
+        assert_eq!(
+            parse_json_reply::<Reply>(reply).unwrap(),
+            Reply {
+                items: vec!["a".to_string(), "b".to_string()],
+            }
+        );
+        assert!(parse_json_reply::<Reply>("no json here").is_err());
+    }
+}
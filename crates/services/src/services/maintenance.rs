@@ -0,0 +1,201 @@
+//! Database maintenance: integrity check, VACUUM and ANALYZE.
+//!
+//! Execution logs are stored in SQLite and deleted rows leave free pages behind, so long-running
+//! instances grow well beyond their live data until the file is vacuumed. VACUUM blocks writers
+//! for its duration, which is why runs happen in the background with step-level progress.
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use db::DBService;
+use serde::Serialize;
+use thiserror::Error;
+use tokio::{sync::RwLock, time::interval};
+use ts_rs::TS;
+
+use crate::services::config::Config;
+
+#[derive(Debug, Error)]
+pub enum MaintenanceError {
+    #[error("Database maintenance is already running")]
+    AlreadyRunning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum MaintenanceStep {
+    IntegrityCheck,
+    Checkpoint,
+    Vacuum,
+    Analyze,
+}
+
+const STEPS: [MaintenanceStep; 4] = [
+    MaintenanceStep::IntegrityCheck,
+    MaintenanceStep::Checkpoint,
+    MaintenanceStep::Vacuum,
+    MaintenanceStep::Analyze,
+];
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct MaintenanceReport {
+    #[ts(type = "Date")]
+    pub started_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub finished_at: DateTime<Utc>,
+    pub integrity_ok: bool,
+    /// Problems reported by `PRAGMA integrity_check`, empty when the database is healthy
+    pub integrity_errors: Vec<String>,
+    pub size_before_bytes: i64,
+    pub size_after_bytes: i64,
+    /// Set when a step failed; later steps are skipped
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, TS)]
+pub struct MaintenanceStatus {
+    pub running: bool,
+    /// Step currently running
+    pub current_step: Option<MaintenanceStep>,
+    pub steps_completed: usize,
+    pub total_steps: usize,
+    pub last_report: Option<MaintenanceReport>,
+}
+
+#[derive(Clone)]
+pub struct MaintenanceService {
+    db: DBService,
+    status: Arc<RwLock<MaintenanceStatus>>,
+}
+
+impl MaintenanceService {
+    pub fn new(db: DBService) -> Self {
+        Self {
+            db,
+            status: Arc::new(RwLock::new(MaintenanceStatus {
+                total_steps: STEPS.len(),
+                ..Default::default()
+            })),
+        }
+    }
+
+    pub async fn status(&self) -> MaintenanceStatus {
+        self.status.read().await.clone()
+    }
+
+    /// Start a maintenance run in the background; poll [`MaintenanceService::status`] for progress.
+    pub async fn start(&self) -> Result<MaintenanceStatus, MaintenanceError> {
+        {
+            let mut status = self.status.write().await;
+            if status.running {
+                return Err(MaintenanceError::AlreadyRunning);
+            }
+            status.running = true;
+            status.current_step = None;
+            status.steps_completed = 0;
+        }
+
+        let service = self.clone();
+        tokio::spawn(async move {
+            service.run().await;
+        });
+        Ok(self.status().await)
+    }
+
+    async fn run(&self) {
+        let started_at = Utc::now();
+        let size_before_bytes = self.database_size().await.unwrap_or_default();
+        let mut integrity_errors = Vec::new();
+        let mut error = None;
+
+        for step in STEPS {
+            self.status.write().await.current_step = Some(step);
+            tracing::info!("Database maintenance: {:?}", step);
+            let result = match step {
+                MaintenanceStep::IntegrityCheck => self.integrity_check().await.map(|errors| {
+                    integrity_errors = errors;
+                }),
+                MaintenanceStep::Checkpoint => {
+                    self.execute("PRAGMA wal_checkpoint(TRUNCATE)").await
+                }
+                MaintenanceStep::Vacuum => self.execute("VACUUM").await,
+                MaintenanceStep::Analyze => self.execute("ANALYZE").await,
+            };
+            if let Err(e) = result {
+                tracing::error!("Database maintenance failed during {:?}: {}", step, e);
+                error = Some(e.to_string());
+                break;
+            }
+            // Compacting a corrupt database can make things worse
+            if !integrity_errors.is_empty() {
+                error = Some("Integrity check failed; skipped VACUUM and ANALYZE".to_string());
+                break;
+            }
+            self.status.write().await.steps_completed += 1;
+        }
+
+        let report = MaintenanceReport {
+            started_at,
+            finished_at: Utc::now(),
+            integrity_ok: integrity_errors.is_empty(),
+            integrity_errors,
+            size_before_bytes,
+            size_after_bytes: self.database_size().await.unwrap_or_default(),
+            error,
+        };
+        tracing::info!(
+            "Database maintenance finished: {} -> {} bytes",
+            report.size_before_bytes,
+            report.size_after_bytes
+        );
+
+        let mut status = self.status.write().await;
+        status.running = false;
+        status.current_step = None;
+        status.last_report = Some(report);
+    }
+
+    async fn execute(&self, sql: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(sql).execute(&self.db.pool).await?;
+        Ok(())
+    }
+
+    async fn integrity_check(&self) -> Result<Vec<String>, sqlx::Error> {
+        let rows: Vec<String> = sqlx::query_scalar("PRAGMA integrity_check")
+            .fetch_all(&self.db.pool)
+            .await?;
+        Ok(rows.into_iter().filter(|row| row != "ok").collect())
+    }
+
+    async fn database_size(&self) -> Result<i64, sqlx::Error> {
+        let page_count: i64 = sqlx::query_scalar("PRAGMA page_count")
+            .fetch_one(&self.db.pool)
+            .await?;
+        let page_size: i64 = sqlx::query_scalar("PRAGMA page_size")
+            .fetch_one(&self.db.pool)
+            .await?;
+        Ok(page_count * page_size)
+    }
+
+    /// Run maintenance according to the `maintenance` settings, checking every hour.
+    pub fn spawn_scheduler(&self, config: Arc<RwLock<Config>>) -> tokio::task::JoinHandle<()> {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut interval = interval(Duration::from_secs(60 * 60));
+            // Measured from startup so a restart doesn't immediately block writers with VACUUM
+            let mut last_run = Utc::now();
+            loop {
+                interval.tick().await;
+                let settings = config.read().await.maintenance.clone();
+                if !settings.enabled || Utc::now() - last_run < settings.frequency.interval() {
+                    continue;
+                }
+                last_run = Utc::now();
+                match service.start().await {
+                    Ok(_) => tracing::info!("Started scheduled database maintenance"),
+                    Err(e) => tracing::debug!("Skipped scheduled database maintenance: {}", e),
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,132 @@
+//! Progress of milestones: how many of their tasks are done and what finishing the rest with
+//! agents is expected to cost.
+
+use db::models::{
+    milestone::{Milestone, MilestoneTask},
+    task::TaskStatus,
+};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct MilestoneProgress {
+    /// Tasks in the milestone, cancelled ones excluded
+    pub total: usize,
+    pub done: usize,
+    pub cancelled: usize,
+    /// Agent cost of the milestone's tasks so far
+    pub spent_usd: f64,
+    /// What the open tasks are expected to cost, from the average cost of the project's done
+    /// tasks; `null` until a done task reported a cost
+    pub estimated_remaining_usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct MilestoneSummary {
+    pub milestone: Milestone,
+    pub progress: MilestoneProgress,
+}
+
+/// A milestone with its tasks, for planning a release.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct MilestoneView {
+    pub milestone: Milestone,
+    pub progress: MilestoneProgress,
+    pub tasks: Vec<MilestoneTask>,
+}
+
+fn progress(tasks: &[MilestoneTask], average_task_cost: Option<f64>) -> MilestoneProgress {
+    let cancelled = tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Cancelled)
+        .count();
+    let done = tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Done)
+        .count();
+    // An open task that already cost more than average is expected to need nothing more
+    let estimated_remaining_usd = average_task_cost.map(|average| {
+        tasks
+            .iter()
+            .filter(|t| !matches!(t.status, TaskStatus::Done | TaskStatus::Cancelled))
+            .map(|t| (average - t.cost_usd).max(0.0))
+            .sum()
+    });
+    MilestoneProgress {
+        total: tasks.len() - cancelled,
+        done,
+        cancelled,
+        spent_usd: tasks.iter().map(|t| t.cost_usd).sum(),
+        estimated_remaining_usd,
+    }
+}
+
+pub async fn milestone_view(
+    pool: &SqlitePool,
+    milestone: Milestone,
+) -> Result<MilestoneView, sqlx::Error> {
+    let tasks = Milestone::find_tasks(pool, milestone.id).await?;
+    let average = Milestone::average_done_task_cost(pool, milestone.project_id).await?;
+    Ok(MilestoneView {
+        progress: progress(&tasks, average),
+        milestone,
+        tasks,
+    })
+}
+
+pub async fn milestone_summaries(
+    pool: &SqlitePool,
+    project_id: Uuid,
+) -> Result<Vec<MilestoneSummary>, sqlx::Error> {
+    let average = Milestone::average_done_task_cost(pool, project_id).await?;
+    let mut summaries = Vec::new();
+    for milestone in Milestone::find_by_project_id(pool, project_id).await? {
+        let tasks = Milestone::find_tasks(pool, milestone.id).await?;
+        summaries.push(MilestoneSummary {
+            progress: progress(&tasks, average),
+            milestone,
+        });
+    }
+    Ok(summaries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(status: TaskStatus, cost_usd: f64) -> MilestoneTask {
+        MilestoneTask {
+            task_id: Uuid::new_v4(),
+            title: "Task".to_string(),
+            status,
+            cost_usd,
+        }
+    }
+
+    #[test]
+    fn estimates_what_open_tasks_still_cost() {
+        let tasks = vec![
+            task(TaskStatus::Done, 2.0),
+            task(TaskStatus::Todo, 0.0),
+            task(TaskStatus::InProgress, 0.5),
+            task(TaskStatus::InReview, 3.0),
+            task(TaskStatus::Cancelled, 0.25),
+        ];
+
+        let with_history = progress(&tasks, Some(1.5));
+        assert_eq!(
+            (
+                with_history.total,
+                with_history.done,
+                with_history.cancelled
+            ),
+            (4, 1, 1)
+        );
+        assert_eq!(with_history.spent_usd, 5.75);
+        assert_eq!(with_history.estimated_remaining_usd, Some(2.5));
+
+        assert_eq!(progress(&tasks, None).estimated_remaining_usd, None);
+    }
+}
@@ -1,25 +1,80 @@
+pub mod accounts;
 pub mod analytics;
+pub mod analytics_export;
 pub mod approvals;
+pub mod attachment;
+pub mod attempt_compare;
+pub mod attempt_review;
+pub mod attempt_summary;
 pub mod auth;
+pub mod automation;
+pub mod backup;
+pub mod backup_scheduler;
+pub mod base_watch;
+pub mod blob_storage;
+pub mod code_map;
+pub mod code_search;
 pub mod user_questions;
 pub mod config;
 pub mod container;
+pub mod context_handoff;
 pub mod diff_stream;
+pub mod environment_snapshot;
+pub mod e2e_verification;
+pub mod error_tracker;
+pub mod event_webhooks;
 pub mod events;
+pub mod execution_failure;
+pub mod executor_stats;
 pub mod file_ranker;
 pub mod file_search_cache;
 pub mod filesystem;
 pub mod filesystem_watcher;
+pub mod flow_reports;
 pub mod git;
 pub mod github;
+pub mod github_issues;
+pub mod human_handoff;
 pub mod image;
+pub mod lifecycle_hooks;
+pub mod lint;
+pub mod llm;
+pub mod maintenance;
+pub mod milestones;
+pub mod model_fallback;
 pub mod notification;
 pub mod oauth_credentials;
+pub mod oidc;
+pub mod outbox;
 pub mod pr_monitor;
+pub mod presence;
 pub mod project;
+pub mod project_env;
+pub mod project_stats;
+pub mod prompt_references;
+pub mod prompt_templates;
+pub mod public_share;
+pub mod question_stats;
 pub mod queued_message;
 pub mod remote_client;
 pub mod repo;
+pub mod repo_instructions;
+pub mod review;
+pub mod review_checklist;
+pub mod review_sla;
+pub mod screenshot;
+pub mod secret_cipher;
+pub mod secret_vault;
+pub mod server_config;
 pub mod share;
+pub mod slack;
+pub mod stale_tasks;
+pub mod task_assist;
+pub mod task_similarity;
+pub mod task_sources;
+pub mod task_workflow;
+pub mod time_tracking;
+pub mod tool_stats;
+pub mod tunnel;
 pub mod workspace_manager;
 pub mod worktree_manager;
@@ -0,0 +1,221 @@
+//! Profiles can list models to fall back to. When a coding agent run fails on a provider error,
+//! a context overflow or an exhausted budget, the attempt continues with the next model of the
+//! chain, and the model is kept with each run's action so every segment of the conversation
+//! can be traced to the model that produced it.
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    execution_process_failure::{ExecutionProcessFailure, ExecutionProcessFailureKind},
+};
+use executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
+        coding_agent_initial::CodingAgentInitialRequest,
+    },
+    executors::CodingAgent,
+    profile::ExecutorConfigs,
+};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Sent when the next model resumes the agent's session
+pub const RESUME_PROMPT: &str =
+    "The previous model stopped with an error. Continue the task from where it left off.";
+
+/// Action continuing a failed coding agent run with the next model of the profile's chain.
+/// Resumes the agent's session when there is one, and otherwise runs the same prompt again.
+/// `None` when the failure isn't one a different model helps with or the chain is exhausted.
+pub fn fallback_action(
+    failed: &ExecutorAction,
+    agent: &CodingAgent,
+    kind: ExecutionProcessFailureKind,
+    agent_session_id: Option<String>,
+) -> Option<ExecutorAction> {
+    if !kind.falls_back_to_next_model() {
+        return None;
+    }
+    let (prompt, executor_profile_id, working_dir) = match failed.typ() {
+        ExecutorActionType::CodingAgentInitialRequest(request) => (
+            &request.prompt,
+            &request.executor_profile_id,
+            &request.working_dir,
+        ),
+        ExecutorActionType::CodingAgentFollowUpRequest(request) => (
+            &request.prompt,
+            &request.executor_profile_id,
+            &request.working_dir,
+        ),
+        ExecutorActionType::ScriptRequest(_) => return None,
+    };
+    let model = agent.next_fallback_model(failed.model_override())?;
+
+    let typ = match agent_session_id {
+        Some(session_id) => {
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt: RESUME_PROMPT.to_string(),
+                session_id,
+                executor_profile_id: executor_profile_id.clone(),
+                working_dir: working_dir.clone(),
+                model: Some(model),
+            })
+        }
+        None => ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt: prompt.clone(),
+            executor_profile_id: executor_profile_id.clone(),
+            working_dir: working_dir.clone(),
+            model: Some(model),
+        }),
+    };
+    Some(ExecutorAction::new(typ, failed.next_action.clone()))
+}
+
+/// One coding agent run of a session and the model it ran with.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ModelSegment {
+    pub execution_process_id: Uuid,
+    /// `None` when neither the profile nor a fallback picked a model, so the CLI's default ran
+    pub model: Option<String>,
+    /// Why the previous run gave up its model, when this run is a fallback
+    pub fell_back_after: Option<ExecutionProcessFailureKind>,
+    pub status: ExecutionProcessStatus,
+    #[ts(type = "Date")]
+    pub started_at: DateTime<Utc>,
+    #[ts(type = "Date | null")]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Coding agent runs of the session, oldest first, with the model each one ran with.
+pub async fn model_segments(
+    pool: &SqlitePool,
+    session_id: Uuid,
+) -> Result<Vec<ModelSegment>, sqlx::Error> {
+    let configs = ExecutorConfigs::get_cached();
+    let processes = ExecutionProcess::find_by_session_id(pool, session_id, false).await?;
+    let mut segments = Vec::new();
+    let mut previous_failure = None;
+    for process in processes {
+        if process.run_reason != ExecutionProcessRunReason::CodingAgent {
+            continue;
+        }
+        let Ok(action) = process.executor_action() else {
+            continue;
+        };
+        let model = match action.model_override() {
+            Some(model) => Some(model.to_string()),
+            None => action
+                .executor_profile_id()
+                .and_then(|profile| configs.get_coding_agent(profile))
+                .and_then(|agent| agent.model().map(str::to_string)),
+        };
+        let fell_back_after = action
+            .model_override()
+            .and(previous_failure)
+            .filter(|kind: &ExecutionProcessFailureKind| kind.falls_back_to_next_model());
+        previous_failure = ExecutionProcessFailure::find_by_execution_process_id(pool, process.id)
+            .await?
+            .map(|failure| failure.kind);
+        segments.push(ModelSegment {
+            execution_process_id: process.id,
+            model,
+            fell_back_after,
+            status: process.status,
+            started_at: process.started_at,
+            completed_at: process.completed_at,
+        });
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::{
+        actions::script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+        executors::BaseCodingAgent,
+        profile::ExecutorProfileId,
+    };
+
+    use super::*;
+
+    fn agent() -> CodingAgent {
+        CodingAgent::ClaudeCode(
+            serde_json::from_value(serde_json::json!({
+                "model": "opus",
+                "model_fallbacks": ["sonnet", "haiku"],
+            }))
+            .unwrap(),
+        )
+    }
+
+    fn initial(model: Option<&str>) -> ExecutorAction {
+        let cleanup = ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script: "make fmt".to_string(),
+                language: ScriptRequestLanguage::Bash,
+                context: ScriptContext::CleanupScript,
+                working_dir: None,
+            }),
+            None,
+        );
+        ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt: "Fix the bug".to_string(),
+                executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+                working_dir: Some("app".to_string()),
+                model: model.map(str::to_string),
+            }),
+            Some(Box::new(cleanup)),
+        )
+    }
+
+    #[test]
+    fn resumes_session_with_next_model() {
+        let action = fallback_action(
+            &initial(None),
+            &agent(),
+            ExecutionProcessFailureKind::RateLimited,
+            Some("agent-session".to_string()),
+        )
+        .unwrap();
+        let ExecutorActionType::CodingAgentFollowUpRequest(request) = action.typ() else {
+            panic!("expected a follow-up");
+        };
+        assert_eq!(request.session_id, "agent-session");
+        assert_eq!(request.model.as_deref(), Some("sonnet"));
+        assert_eq!(request.working_dir.as_deref(), Some("app"));
+        assert!(action.next_action().is_some());
+    }
+
+    #[test]
+    fn reruns_prompt_without_session() {
+        let action = fallback_action(
+            &initial(Some("sonnet")),
+            &agent(),
+            ExecutionProcessFailureKind::ContextOverflow,
+            None,
+        )
+        .unwrap();
+        let ExecutorActionType::CodingAgentInitialRequest(request) = action.typ() else {
+            panic!("expected an initial request");
+        };
+        assert_eq!(request.prompt, "Fix the bug");
+        assert_eq!(request.model.as_deref(), Some("haiku"));
+    }
+
+    #[test]
+    fn stops_at_end_of_chain_or_unrelated_failure() {
+        let kind = ExecutionProcessFailureKind::BudgetExhausted;
+        assert!(fallback_action(&initial(Some("haiku")), &agent(), kind, None).is_none());
+        assert!(
+            fallback_action(
+                &initial(None),
+                &agent(),
+                ExecutionProcessFailureKind::TestFailure,
+                None
+            )
+            .is_none()
+        );
+    }
+}
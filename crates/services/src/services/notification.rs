@@ -1,28 +1,305 @@
-use std::sync::{Arc, OnceLock};
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 
+use db::models::{
+    notification::{CreateNotification, Notification},
+    team::{Team, TeamNotificationDefaults},
+    user_push_settings::UserPushSettings,
+};
+use serde_json::json;
+use sqlx::SqlitePool;
+use thiserror::Error;
 use tokio::sync::RwLock;
 use utils;
+use uuid::Uuid;
 
-use crate::services::config::{Config, NotificationConfig, SoundFile};
+use crate::services::{
+    config::{Config, MobilePushConfig, NotificationConfig, SoundFile},
+    events::{EntityEvent, EntityEventBus},
+};
+
+const PUSHOVER_API_URL: &str = "https://api.pushover.net/1/messages.json";
+/// ntfy "high" priority, which makes phones vibrate and show a pop-over
+const NTFY_PRIORITY_HIGH: u8 = 4;
+
+#[derive(Debug, Error)]
+pub enum NotificationError {
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Pushover requires an application token in the mobile push settings")]
+    MissingPushoverToken,
+    #[error("No ntfy topic or Pushover user key configured")]
+    NoDestination,
+}
 
 /// Service for handling cross-platform notifications including sound alerts and push notifications
 #[derive(Debug, Clone)]
 pub struct NotificationService {
     config: Arc<RwLock<Config>>,
+    pool: SqlitePool,
+    entity_events: EntityEventBus,
+    http: reqwest::Client,
 }
 
 /// Cache for WSL root path from PowerShell
 static WSL_ROOT_PATH_CACHE: OnceLock<Option<String>> = OnceLock::new();
 
 impl NotificationService {
-    pub fn new(config: Arc<RwLock<Config>>) -> Self {
-        Self { config }
+    pub fn new(
+        config: Arc<RwLock<Config>>,
+        pool: SqlitePool,
+        entity_events: EntityEventBus,
+    ) -> Self {
+        let http = utils::http::client_builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self {
+            config,
+            pool,
+            entity_events,
+            http,
+        }
+    }
+
+    /// Record the notification in the notification center, then send sound, desktop and mobile
+    /// push notifications if enabled
+    pub async fn notify(&self, notification: CreateNotification) {
+        self.record(&notification).await;
+
+        let (config, mobile_push) = {
+            let config = self.config.read().await;
+            (config.notifications.clone(), config.mobile_push.clone())
+        };
+        let team_defaults = self.team_defaults(&notification).await;
+        let CreateNotification { title, message, .. } = notification;
+        Self::send_notification(&config, &title, &message).await;
+
+        if mobile_push.enabled || team_defaults.is_some() {
+            let service = self.clone();
+            tokio::spawn(async move {
+                service
+                    .send_mobile_push(&mobile_push, team_defaults.as_ref(), &title, &message)
+                    .await;
+            });
+        }
+    }
+
+    /// Notification defaults of the team owning the notification's project, when they push
+    /// this kind of notification.
+    async fn team_defaults(
+        &self,
+        notification: &CreateNotification,
+    ) -> Option<TeamNotificationDefaults> {
+        let project_id = notification.project_id?;
+        match Team::find_by_project_id(&self.pool, project_id).await {
+            Ok(team) => team
+                .map(|team| team.notification_defaults.0)
+                .filter(|defaults| defaults.pushes(notification.kind)),
+            Err(e) => {
+                tracing::error!("Failed to load team notification defaults: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn record(&self, data: &CreateNotification) {
+        let notification = match Notification::create(&self.pool, data).await {
+            Ok(notification) => notification,
+            Err(e) => {
+                tracing::error!("Failed to store notification: {}", e);
+                return;
+            }
+        };
+        let unread_count = Notification::count_unread(&self.pool)
+            .await
+            .unwrap_or_default();
+        self.entity_events.publish(
+            notification.project_id,
+            EntityEvent::NotificationCreated {
+                notification,
+                unread_count,
+            },
+        );
+    }
+
+    /// Tell event stream subscribers about read state changes.
+    async fn publish_unread_count(&self) -> Result<i64, sqlx::Error> {
+        let unread_count = Notification::count_unread(&self.pool).await?;
+        self.entity_events
+            .publish(None, EntityEvent::NotificationsUpdated { unread_count });
+        Ok(unread_count)
+    }
+
+    /// Returns `None` when the notification doesn't exist.
+    pub async fn mark_read(&self, id: Uuid) -> Result<Option<i64>, sqlx::Error> {
+        if !Notification::mark_read(&self.pool, id).await? {
+            return Ok(None);
+        }
+        Ok(Some(self.publish_unread_count().await?))
+    }
+
+    pub async fn mark_all_read(&self) -> Result<i64, sqlx::Error> {
+        Notification::mark_all_read(&self.pool).await?;
+        self.publish_unread_count().await
+    }
+
+    pub async fn clear(&self) -> Result<i64, sqlx::Error> {
+        Notification::delete_all(&self.pool).await?;
+        self.publish_unread_count().await
+    }
+
+    /// Deliver to the team's shared destinations and, when mobile push is enabled, to the
+    /// server-wide destinations and every user that registered their own.
+    async fn send_mobile_push(
+        &self,
+        config: &MobilePushConfig,
+        team_defaults: Option<&TeamNotificationDefaults>,
+        title: &str,
+        message: &str,
+    ) {
+        let mut ntfy_topics = BTreeSet::new();
+        let mut pushover_users = BTreeSet::new();
+        if let Some(defaults) = team_defaults {
+            ntfy_topics.extend(defaults.ntfy_topic.clone());
+            pushover_users.extend(defaults.pushover_user_key.clone());
+        }
+        if config.enabled {
+            ntfy_topics.extend(config.ntfy_topic.clone());
+            pushover_users.extend(config.pushover_user_key.clone());
+            match UserPushSettings::find_active(&self.pool).await {
+                Ok(settings) => {
+                    for s in settings {
+                        ntfy_topics.extend(s.ntfy_topic);
+                        pushover_users.extend(s.pushover_user_key);
+                    }
+                }
+                Err(e) => tracing::error!("Failed to load user push settings: {}", e),
+            }
+        }
+
+        for topic in ntfy_topics.iter().filter(|t| !t.trim().is_empty()) {
+            if let Err(e) = self.send_ntfy(config, topic, title, message).await {
+                tracing::warn!("Failed to send ntfy notification: {}", e);
+            }
+        }
+        for user_key in pushover_users.iter().filter(|k| !k.trim().is_empty()) {
+            if let Err(e) = self.send_pushover(config, user_key, title, message).await {
+                tracing::warn!("Failed to send Pushover notification: {}", e);
+            }
+        }
+    }
+
+    /// Send a test message to the given destinations, reporting the first failure.
+    pub async fn send_test_push(
+        &self,
+        ntfy_topic: Option<&str>,
+        pushover_user_key: Option<&str>,
+    ) -> Result<(), NotificationError> {
+        let config = self.config.read().await.mobile_push.clone();
+        let ntfy_topic = ntfy_topic.filter(|t| !t.trim().is_empty());
+        let pushover_user_key = pushover_user_key.filter(|k| !k.trim().is_empty());
+        if ntfy_topic.is_none() && pushover_user_key.is_none() {
+            return Err(NotificationError::NoDestination);
+        }
+
+        let title = "Vibe Kanban";
+        let message = "Test notification: mobile push is working.";
+        if let Some(topic) = ntfy_topic {
+            self.send_ntfy(&config, topic, title, message).await?;
+        }
+        if let Some(user_key) = pushover_user_key {
+            self.send_pushover(&config, user_key, title, message)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Push to one user's own ntfy topic and Pushover key, whether or not mobile push is
+    /// enabled server-wide.
+    pub async fn push_to_user(
+        &self,
+        user_id: Uuid,
+        title: &str,
+        message: &str,
+    ) -> Result<(), NotificationError> {
+        let config = self.config.read().await.mobile_push.clone();
+        let settings = UserPushSettings::find_by_user_id(&self.pool, user_id).await?;
+        let ntfy_topic = settings
+            .as_ref()
+            .and_then(|s| s.ntfy_topic.as_deref())
+            .filter(|t| !t.trim().is_empty());
+        let pushover_user_key = settings
+            .as_ref()
+            .and_then(|s| s.pushover_user_key.as_deref())
+            .filter(|k| !k.trim().is_empty());
+        if ntfy_topic.is_none() && pushover_user_key.is_none() {
+            return Err(NotificationError::NoDestination);
+        }
+
+        if let Some(topic) = ntfy_topic {
+            self.send_ntfy(&config, topic, title, message).await?;
+        }
+        if let Some(user_key) = pushover_user_key {
+            self.send_pushover(&config, user_key, title, message)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Publish to an ntfy topic using the JSON API, which handles non-ASCII titles
+    async fn send_ntfy(
+        &self,
+        config: &MobilePushConfig,
+        topic: &str,
+        title: &str,
+        message: &str,
+    ) -> Result<(), NotificationError> {
+        let mut request = self
+            .http
+            .post(config.ntfy_server.trim_end_matches('/'))
+            .json(&json!({
+                "topic": topic.trim(),
+                "title": title,
+                "message": message,
+                "priority": NTFY_PRIORITY_HIGH,
+            }));
+        if let Some(token) = config.ntfy_token.as_deref().filter(|t| !t.is_empty()) {
+            request = request.bearer_auth(token);
+        }
+        request.send().await?.error_for_status()?;
+        Ok(())
     }
 
-    /// Send both sound and push notifications if enabled
-    pub async fn notify(&self, title: &str, message: &str) {
-        let config = self.config.read().await.notifications.clone();
-        Self::send_notification(&config, title, message).await;
+    async fn send_pushover(
+        &self,
+        config: &MobilePushConfig,
+        user_key: &str,
+        title: &str,
+        message: &str,
+    ) -> Result<(), NotificationError> {
+        let token = config
+            .pushover_app_token
+            .as_deref()
+            .filter(|t| !t.is_empty())
+            .ok_or(NotificationError::MissingPushoverToken)?;
+        self.http
+            .post(PUSHOVER_API_URL)
+            .form(&[
+                ("token", token),
+                ("user", user_key.trim()),
+                ("title", title),
+                ("message", message),
+            ])
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
     }
 
     /// Internal method to send notifications with a given config
@@ -0,0 +1,694 @@
+//! OpenID Connect single sign-on for team deployments.
+//!
+//! Users sign in through the authorization code flow with PKCE against the issuer in
+//! `VK_OIDC_ISSUER`. The first sign-in links the provider account to a new local user; claims in
+//! the ID token keep the user's profile and role in sync on every sign-in after that.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::Utc;
+use db::models::{
+    user::{CreateUser, UpdateUser, User, UserRole},
+    user_identity::UserIdentity,
+};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::sync::OnceCell;
+use url::Url;
+
+use super::accounts::{AccountError, AccountService, generate_token};
+
+const DEFAULT_SCOPES: &str = "openid profile email";
+const DEFAULT_ROLE_CLAIM: &str = "groups";
+/// How long a user has to finish signing in at the identity provider
+const PENDING_LOGIN_TTL: Duration = Duration::from_secs(10 * 60);
+/// Allowed clock skew when checking ID token expiry
+const CLOCK_SKEW_SECS: i64 = 60;
+/// Attempts at a free username before giving up on a new user
+const MAX_USERNAME_SUFFIX: usize = 100;
+
+#[derive(Debug, Error)]
+pub enum OidcError {
+    #[error("Single sign-on is not configured")]
+    NotConfigured,
+    #[error("Identity provider request failed: {0}")]
+    Provider(String),
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error("Sign-in request expired or was not started on this server")]
+    InvalidState,
+    #[error("Invalid ID token: {0}")]
+    InvalidIdToken(String),
+    #[error("Your identity provider account is not allowed to use this server")]
+    AccessDenied,
+    #[error(transparent)]
+    Account(#[from] AccountError),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// Maps the values of an ID token claim, such as the user's groups, onto local roles.
+#[derive(Debug, Clone)]
+pub struct RoleMapping {
+    /// Claim holding a string or array of strings; dots select nested claims
+    pub claim: String,
+    pub admin: Vec<String>,
+    pub member: Vec<String>,
+    pub reviewer: Vec<String>,
+    /// Role for users none of whose claim values are mapped; `None` denies them access
+    pub default_role: Option<UserRole>,
+}
+
+impl RoleMapping {
+    /// Parse `VK_OIDC_DEFAULT_ROLE`. Unmapped users are denied unless a role is set explicitly.
+    fn parse_default_role(value: Option<&str>) -> Option<UserRole> {
+        match value? {
+            "none" => None,
+            role => match role.parse() {
+                Ok(role) => Some(role),
+                Err(_) => {
+                    tracing::warn!("Unknown VK_OIDC_DEFAULT_ROLE '{role}', denying unmapped users");
+                    None
+                }
+            },
+        }
+    }
+
+    /// The role the claims grant: the mapped one, else the default. `None` denies access.
+    pub fn granted_role(&self, claims: &Map<String, Value>) -> Option<UserRole> {
+        self.mapped_role(claims).or(self.default_role)
+    }
+
+    /// The role the claims explicitly map to. Admin wins over member, and member over reviewer.
+    pub fn mapped_role(&self, claims: &Map<String, Value>) -> Option<UserRole> {
+        let mut path = self.claim.split('.');
+        let first = path.next()?;
+        let value = path.try_fold(claims.get(first)?, |value, key| value.get(key))?;
+        let values: Vec<&str> = match value {
+            Value::String(s) => vec![s.as_str()],
+            Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+            _ => return None,
+        };
+        let matches = |mapped: &[String]| values.iter().any(|v| mapped.iter().any(|m| m == v));
+
+        if matches(&self.admin) {
+            Some(UserRole::Admin)
+        } else if matches(&self.member) {
+            Some(UserRole::Member)
+        } else if matches(&self.reviewer) {
+            Some(UserRole::Reviewer)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OidcConfig {
+    pub issuer: String,
+    pub client_id: String,
+    /// Unset for public clients, which rely on PKCE alone
+    pub client_secret: Option<String>,
+    pub scopes: String,
+    /// Overrides the callback URL derived from the request, e.g. behind a rewriting proxy
+    pub redirect_url: Option<String>,
+    pub roles: RoleMapping,
+}
+
+impl OidcConfig {
+    /// Read the configuration from `VK_OIDC_*` variables. `None` unless both the issuer and
+    /// client ID are set.
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let list = |name: &str| {
+            var(name)
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        Some(Self {
+            issuer: var("VK_OIDC_ISSUER")?,
+            client_id: var("VK_OIDC_CLIENT_ID")?,
+            client_secret: var("VK_OIDC_CLIENT_SECRET"),
+            scopes: var("VK_OIDC_SCOPES").unwrap_or_else(|| DEFAULT_SCOPES.to_string()),
+            redirect_url: var("VK_OIDC_REDIRECT_URL"),
+            roles: RoleMapping {
+                claim: var("VK_OIDC_ROLE_CLAIM").unwrap_or_else(|| DEFAULT_ROLE_CLAIM.to_string()),
+                admin: list("VK_OIDC_ADMIN_VALUES"),
+                member: list("VK_OIDC_MEMBER_VALUES"),
+                reviewer: list("VK_OIDC_REVIEWER_VALUES"),
+                default_role: RoleMapping::parse_default_role(
+                    var("VK_OIDC_DEFAULT_ROLE").as_deref(),
+                ),
+            },
+        })
+    }
+}
+
+/// The parts of the provider's discovery document the flow uses.
+#[derive(Debug, Clone, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    userinfo_endpoint: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    id_token: String,
+}
+
+#[derive(Debug)]
+struct PendingLogin {
+    code_verifier: String,
+    nonce: String,
+    redirect_url: String,
+    started_at: Instant,
+}
+
+#[derive(Clone)]
+pub struct OidcService {
+    config: Option<Arc<OidcConfig>>,
+    http: reqwest::Client,
+    metadata: Arc<OnceCell<ProviderMetadata>>,
+    /// Sign-ins waiting on the provider's callback, keyed by `state`
+    pending: Arc<Mutex<HashMap<String, PendingLogin>>>,
+}
+
+impl OidcService {
+    pub fn new(config: Option<OidcConfig>) -> Self {
+        Self {
+            config: config.map(Arc::new),
+            http: reqwest::Client::new(),
+            metadata: Arc::new(OnceCell::new()),
+            pending: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(OidcConfig::from_env())
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.is_some()
+    }
+
+    fn config(&self) -> Result<&OidcConfig, OidcError> {
+        self.config.as_deref().ok_or(OidcError::NotConfigured)
+    }
+
+    /// The callback URL registered with the provider: the configured one, or the callback route
+    /// under the server's public base URL.
+    pub fn redirect_url(&self, base_url: Option<&str>) -> Result<String, OidcError> {
+        let config = self.config()?;
+        config
+            .redirect_url
+            .clone()
+            .or_else(|| base_url.map(|base| format!("{base}/api/accounts/oidc/callback")))
+            .ok_or_else(|| {
+                OidcError::Provider(
+                    "Cannot determine the callback URL; set VK_OIDC_REDIRECT_URL".to_string(),
+                )
+            })
+    }
+
+    async fn metadata(&self) -> Result<&ProviderMetadata, OidcError> {
+        let config = self.config()?;
+        self.metadata
+            .get_or_try_init(|| async {
+                let url = format!(
+                    "{}/.well-known/openid-configuration",
+                    config.issuer.trim_end_matches('/')
+                );
+                let response = self.http.get(&url).send().await?;
+                if !response.status().is_success() {
+                    return Err(OidcError::Provider(format!(
+                        "discovery at {url} returned {}",
+                        response.status()
+                    )));
+                }
+                Ok(response.json::<ProviderMetadata>().await?)
+            })
+            .await
+    }
+
+    /// Start a sign-in, returning the provider URL to send the browser to.
+    pub async fn begin_login(&self, redirect_url: &str) -> Result<String, OidcError> {
+        let config = self.config()?;
+        let metadata = self.metadata().await?;
+
+        let state = generate_token();
+        let nonce = generate_token();
+        let code_verifier = generate_token();
+
+        let mut url = Url::parse(&metadata.authorization_endpoint)
+            .map_err(|e| OidcError::Provider(format!("invalid authorization endpoint: {e}")))?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &config.client_id)
+            .append_pair("redirect_uri", redirect_url)
+            .append_pair("scope", &config.scopes)
+            .append_pair("state", &state)
+            .append_pair("nonce", &nonce)
+            .append_pair("code_challenge", &pkce_challenge(&code_verifier))
+            .append_pair("code_challenge_method", "S256");
+
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|_, login| login.started_at.elapsed() < PENDING_LOGIN_TTL);
+        pending.insert(
+            state,
+            PendingLogin {
+                code_verifier,
+                nonce,
+                redirect_url: redirect_url.to_string(),
+                started_at: Instant::now(),
+            },
+        );
+        Ok(url.into())
+    }
+
+    /// Finish a sign-in from the provider's callback, returning the local user it signs in as.
+    pub async fn complete_login(
+        &self,
+        pool: &SqlitePool,
+        accounts: &AccountService,
+        code: &str,
+        state: &str,
+    ) -> Result<User, OidcError> {
+        let config = self.config()?;
+        let login = self
+            .pending
+            .lock()
+            .unwrap()
+            .remove(state)
+            .filter(|login| login.started_at.elapsed() < PENDING_LOGIN_TTL)
+            .ok_or(OidcError::InvalidState)?;
+        let metadata = self.metadata().await?;
+
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", login.redirect_url.as_str()),
+            ("client_id", config.client_id.as_str()),
+            ("code_verifier", login.code_verifier.as_str()),
+        ];
+        if let Some(secret) = &config.client_secret {
+            form.push(("client_secret", secret.as_str()));
+        }
+        let response = self
+            .http
+            .post(&metadata.token_endpoint)
+            .form(&form)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(OidcError::Provider(format!(
+                "token exchange returned {status}: {body}"
+            )));
+        }
+        let tokens: TokenResponse = response.json().await?;
+
+        // The ID token came straight from the token endpoint over TLS, which OIDC Core 3.1.3.7
+        // accepts in place of checking its signature
+        let mut claims = id_token_claims(&tokens.id_token)?;
+        validate_claims(
+            &claims,
+            &metadata.issuer,
+            &config.client_id,
+            &login.nonce,
+            Utc::now().timestamp(),
+        )?;
+        let subject = claims
+            .get("sub")
+            .and_then(Value::as_str)
+            .ok_or_else(|| OidcError::InvalidIdToken("missing sub".to_string()))?
+            .to_string();
+
+        // Providers often leave profile and group claims out of the ID token
+        if let Some(endpoint) = &metadata.userinfo_endpoint {
+            let userinfo: Map<String, Value> = self
+                .http
+                .get(endpoint)
+                .bearer_auth(&tokens.access_token)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            if userinfo.get("sub").and_then(Value::as_str) == Some(subject.as_str()) {
+                for (key, value) in userinfo {
+                    claims.entry(key).or_insert(value);
+                }
+            }
+        }
+
+        self.sign_in_user(pool, accounts, &metadata.issuer, &subject, &claims)
+            .await
+    }
+
+    /// Find or create the local user linked to the provider account and sync it with the claims.
+    async fn sign_in_user(
+        &self,
+        pool: &SqlitePool,
+        accounts: &AccountService,
+        issuer: &str,
+        subject: &str,
+        claims: &Map<String, Value>,
+    ) -> Result<User, OidcError> {
+        let config = self.config()?;
+        let mapped_role = config.roles.mapped_role(claims);
+        let display_name = claim_str(claims, "name");
+        let email = claim_str(claims, "email");
+
+        let linked = match UserIdentity::find(pool, issuer, subject).await? {
+            Some(identity) => User::find_by_id(pool, identity.user_id).await?,
+            None => None,
+        };
+
+        let Some(user) = linked else {
+            // The first account on a fresh server has to be able to administer it
+            let role = if User::count(pool).await? == 0 {
+                UserRole::Admin
+            } else {
+                config
+                    .roles
+                    .granted_role(claims)
+                    .ok_or(OidcError::AccessDenied)?
+            };
+            let user = self
+                .create_user(pool, accounts, claims, subject, role)
+                .await?;
+            UserIdentity::create(pool, issuer, subject, user.id).await?;
+            tracing::info!("Created user {} from {issuer} sign-in", user.username);
+            return Ok(user);
+        };
+
+        if user.disabled {
+            return Err(AccountError::Disabled.into());
+        }
+        if config.roles.granted_role(claims).is_none() {
+            return Err(OidcError::AccessDenied);
+        }
+
+        // Unmapped users keep whatever role an admin gave them
+        let role = mapped_role.filter(|role| *role != user.role);
+        let profile_changed = display_name
+            .is_some_and(|name| user.display_name.as_deref() != Some(name))
+            || email.is_some_and(|email| user.email.as_deref() != Some(email));
+        if role.is_none() && !profile_changed {
+            return Ok(user);
+        }
+        let update = UpdateUser {
+            display_name: display_name.map(str::to_string),
+            email: email.map(str::to_string),
+            role,
+            disabled: None,
+            password: None,
+        };
+        match accounts.update_user(pool, &user, &update).await {
+            Ok(user) => Ok(user),
+            // Never demote the last admin because the provider's groups changed
+            Err(AccountError::LastAdmin) => Ok(accounts
+                .update_user(
+                    pool,
+                    &user,
+                    &UpdateUser {
+                        role: None,
+                        ..update
+                    },
+                )
+                .await?),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn create_user(
+        &self,
+        pool: &SqlitePool,
+        accounts: &AccountService,
+        claims: &Map<String, Value>,
+        subject: &str,
+        role: UserRole,
+    ) -> Result<User, OidcError> {
+        let base = username_from_claims(claims, subject);
+        for attempt in 0..MAX_USERNAME_SUFFIX {
+            let username = if attempt == 0 {
+                base.clone()
+            } else {
+                format!("{base}-{}", attempt + 1)
+            };
+            let data = CreateUser {
+                username,
+                password: None,
+                display_name: claim_str(claims, "name").map(str::to_string),
+                email: claim_str(claims, "email").map(str::to_string),
+                role,
+            };
+            match accounts.create_user(pool, &data).await {
+                Err(AccountError::UsernameTaken) => continue,
+                result => return Ok(result?),
+            }
+        }
+        Err(AccountError::UsernameTaken.into())
+    }
+}
+
+fn claim_str<'a>(claims: &'a Map<String, Value>, name: &str) -> Option<&'a str> {
+    claims
+        .get(name)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+/// S256 code challenge for a PKCE code verifier (RFC 7636).
+pub fn pkce_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+/// Decode the claims of a compact-serialized JWT without verifying its signature.
+fn id_token_claims(id_token: &str) -> Result<Map<String, Value>, OidcError> {
+    let invalid = |reason: &str| OidcError::InvalidIdToken(reason.to_string());
+    let payload = id_token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| invalid("not a JWT"))?;
+    let bytes = URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|_| invalid("payload is not base64url"))?;
+    serde_json::from_slice(&bytes).map_err(|_| invalid("payload is not a JSON object"))
+}
+
+fn validate_claims(
+    claims: &Map<String, Value>,
+    issuer: &str,
+    client_id: &str,
+    nonce: &str,
+    now: i64,
+) -> Result<(), OidcError> {
+    let invalid = |reason: &str| Err(OidcError::InvalidIdToken(reason.to_string()));
+
+    if claims.get("iss").and_then(Value::as_str) != Some(issuer) {
+        return invalid("issuer mismatch");
+    }
+    let audience_ok = match claims.get("aud") {
+        Some(Value::String(aud)) => aud == client_id,
+        Some(Value::Array(auds)) => auds.iter().any(|aud| aud.as_str() == Some(client_id)),
+        _ => false,
+    };
+    if !audience_ok {
+        return invalid("audience mismatch");
+    }
+    match claims.get("exp").and_then(Value::as_i64) {
+        Some(exp) if exp + CLOCK_SKEW_SECS > now => {}
+        _ => return invalid("expired"),
+    }
+    if claims.get("nonce").and_then(Value::as_str) != Some(nonce) {
+        return invalid("nonce mismatch");
+    }
+    Ok(())
+}
+
+/// A valid local username from `preferred_username`, the email's local part, or the subject.
+fn username_from_claims(claims: &Map<String, Value>, subject: &str) -> String {
+    let candidate = claim_str(claims, "preferred_username")
+        .or_else(|| claim_str(claims, "email").and_then(|email| email.split('@').next()))
+        .unwrap_or(subject);
+    let username: String = candidate
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '@') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(56)
+        .collect();
+    if username.is_empty() {
+        "user".to_string()
+    } else {
+        username
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn claims(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    fn mapping() -> RoleMapping {
+        RoleMapping {
+            claim: "groups".to_string(),
+            admin: vec!["vk-admins".to_string()],
+            member: vec!["engineering".to_string()],
+            reviewer: vec!["qa".to_string()],
+            default_role: None,
+        }
+    }
+
+    #[test]
+    fn pkce_challenge_matches_rfc_7636_example() {
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mJ92K9n8hRV2SuqYPQpCsKrjWsnMDW5"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn maps_the_most_privileged_matching_role() {
+        let roles = mapping();
+        let role = |value| roles.mapped_role(&claims(value));
+
+        assert_eq!(
+            role(json!({ "groups": ["qa", "vk-admins"] })),
+            Some(UserRole::Admin)
+        );
+        assert_eq!(
+            role(json!({ "groups": ["qa", "engineering"] })),
+            Some(UserRole::Member)
+        );
+        assert_eq!(role(json!({ "groups": "qa" })), Some(UserRole::Reviewer));
+        assert_eq!(role(json!({ "groups": ["sales"] })), None);
+        assert_eq!(role(json!({})), None);
+    }
+
+    #[test]
+    fn denies_unmapped_users_without_an_explicit_default_role() {
+        let unmapped = claims(json!({ "groups": ["sales"] }));
+        let roles = RoleMapping {
+            default_role: RoleMapping::parse_default_role(None),
+            ..mapping()
+        };
+        assert_eq!(roles.granted_role(&unmapped), None);
+        assert_eq!(
+            roles.granted_role(&claims(json!({ "groups": ["qa"] }))),
+            Some(UserRole::Reviewer)
+        );
+
+        let roles = RoleMapping {
+            default_role: RoleMapping::parse_default_role(Some("member")),
+            ..mapping()
+        };
+        assert_eq!(roles.granted_role(&unmapped), Some(UserRole::Member));
+        assert_eq!(RoleMapping::parse_default_role(Some("none")), None);
+    }
+
+    #[test]
+    fn maps_nested_claims() {
+        let roles = RoleMapping {
+            claim: "realm_access.roles".to_string(),
+            ..mapping()
+        };
+        let claims = claims(json!({ "realm_access": { "roles": ["offline", "vk-admins"] } }));
+        assert_eq!(roles.mapped_role(&claims), Some(UserRole::Admin));
+    }
+
+    #[test]
+    fn decodes_and_validates_id_token_claims() {
+        let payload = URL_SAFE_NO_PAD.encode(
+            json!({
+                "iss": "https://idp.example.com",
+                "aud": ["other", "vk"],
+                "sub": "248289761001",
+                "exp": 1_000,
+                "nonce": "n-0S6_WzA2Mj",
+            })
+            .to_string(),
+        );
+        let claims = id_token_claims(&format!("eyJhbGciOiJSUzI1NiJ9.{payload}.sig")).unwrap();
+        let validate = |issuer, client_id, nonce, now| {
+            validate_claims(&claims, issuer, client_id, nonce, now).is_ok()
+        };
+
+        assert!(validate(
+            "https://idp.example.com",
+            "vk",
+            "n-0S6_WzA2Mj",
+            900
+        ));
+        assert!(!validate(
+            "https://evil.example.com",
+            "vk",
+            "n-0S6_WzA2Mj",
+            900
+        ));
+        assert!(!validate(
+            "https://idp.example.com",
+            "other-app",
+            "n-0S6_WzA2Mj",
+            900
+        ));
+        assert!(!validate("https://idp.example.com", "vk", "replayed", 900));
+        assert!(!validate(
+            "https://idp.example.com",
+            "vk",
+            "n-0S6_WzA2Mj",
+            2_000
+        ));
+        assert!(id_token_claims("not-a-jwt").is_err());
+    }
+
+    #[test]
+    fn derives_valid_usernames() {
+        assert_eq!(
+            username_from_claims(&claims(json!({ "preferred_username": "Jane Doe" })), "s"),
+            "Jane_Doe"
+        );
+        assert_eq!(
+            username_from_claims(&claims(json!({ "email": "jane@example.com" })), "s"),
+            "jane"
+        );
+        assert_eq!(
+            username_from_claims(&claims(json!({})), "auth0|123"),
+            "auth0_123"
+        );
+    }
+}
@@ -0,0 +1,365 @@
+//! Deferred delivery of integration actions while their service is unreachable.
+//!
+//! Status syncs, pull request creation and Slack announcements that fail because GitHub or
+//! Slack can't be reached are stored in the `integration_outbox` table instead of being dropped.
+//! [`OutboxWorker`] retries them with backoff until they go through; errors that retrying won't
+//! fix, such as a missing login, still reach the caller straight away.
+
+use std::{path::PathBuf, time::Duration};
+
+use chrono::Utc;
+use db::{
+    DBService,
+    models::{
+        github_issue::GitHubIssueLink,
+        integration_outbox::{IntegrationOutboxEntry, OutboxIntegration},
+        merge::Merge,
+        slack::{SlackIntegration, SlackTaskThread},
+    },
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::time::interval;
+use uuid::Uuid;
+
+use crate::services::{
+    git::{GitService, GitServiceError},
+    github::{CreatePrRequest, GitHubRepoInfo, GitHubService, GitHubServiceError},
+    secret_cipher::{SecretCipher, SecretCipherError},
+    slack::{SlackClient, SlackError},
+};
+
+/// Entries that failed this many times are kept for inspection but no longer retried
+pub const MAX_ATTEMPTS: i64 = 20;
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+/// Delay before the second attempt, doubled after each failure up to `MAX_BACKOFF`
+const INITIAL_BACKOFF: Duration = Duration::from_secs(30);
+const MAX_BACKOFF: Duration = Duration::from_secs(60 * 60);
+const BATCH_SIZE: i64 = 20;
+
+/// Fragments of the errors `git`, `gh` and HTTP clients report when the other side can't be
+/// reached
+const CONNECTIVITY_ERRORS: &[&str] = &[
+    "could not resolve host",
+    "could not resolve hostname",
+    "temporary failure in name resolution",
+    "name or service not known",
+    "no such host",
+    "connection refused",
+    "connection reset",
+    "connection timed out",
+    "operation timed out",
+    "network is unreachable",
+    "no route to host",
+    "error connecting to",
+    "failed to connect",
+];
+
+/// Whether an error message says a service couldn't be reached, as opposed to it answering
+/// with an error.
+pub fn is_connectivity_error(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    CONNECTIVITY_ERRORS
+        .iter()
+        .any(|fragment| message.contains(fragment))
+}
+
+#[derive(Debug, Error)]
+pub enum OutboxError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    GitHub(#[from] GitHubServiceError),
+    #[error(transparent)]
+    Git(#[from] GitServiceError),
+    #[error(transparent)]
+    Slack(#[from] SlackError),
+    #[error(transparent)]
+    Cipher(#[from] SecretCipherError),
+    #[error("{0}")]
+    Gone(String),
+}
+
+/// An integration action that can be stored and replayed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum OutboxAction {
+    /// Close or reopen the GitHub issue linked to a task
+    GithubIssueState {
+        task_id: Uuid,
+        issue_url: String,
+        issue_number: i64,
+        open: bool,
+    },
+    /// Push an attempt's branch and open a pull request for it
+    GithubPullRequest {
+        workspace_id: Uuid,
+        repo_id: Uuid,
+        worktree_path: PathBuf,
+        title: String,
+        body: Option<String>,
+        head_branch: String,
+        base_branch: String,
+        draft: Option<bool>,
+    },
+    /// Announce a task created from Slack in the channel it was created from
+    SlackTaskAnnouncement {
+        project_id: Uuid,
+        task_id: Uuid,
+        channel_id: String,
+        text: String,
+    },
+}
+
+impl OutboxAction {
+    pub fn integration(&self) -> OutboxIntegration {
+        match self {
+            OutboxAction::GithubIssueState { .. } | OutboxAction::GithubPullRequest { .. } => {
+                OutboxIntegration::Github
+            }
+            OutboxAction::SlackTaskAnnouncement { .. } => OutboxIntegration::Slack,
+        }
+    }
+
+    /// Key of the thing the action is about; a newer action for it replaces a queued one
+    pub fn dedupe_key(&self) -> String {
+        match self {
+            OutboxAction::GithubIssueState { task_id, .. } => issue_state_key(*task_id),
+            OutboxAction::GithubPullRequest {
+                workspace_id,
+                repo_id,
+                ..
+            } => format!("github_pull_request:{workspace_id}:{repo_id}"),
+            OutboxAction::SlackTaskAnnouncement { task_id, .. } => {
+                format!("slack_task_announcement:{task_id}")
+            }
+        }
+    }
+}
+
+/// Outbox key of a task's GitHub issue state sync
+pub fn issue_state_key(task_id: Uuid) -> String {
+    format!("github_issue_state:{task_id}")
+}
+
+fn backoff(attempts: i64) -> Duration {
+    let doublings = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    INITIAL_BACKOFF
+        .saturating_mul(2u32.saturating_pow(doublings))
+        .min(MAX_BACKOFF)
+}
+
+fn next_attempt_at(attempts: i64) -> chrono::DateTime<Utc> {
+    Utc::now() + chrono::Duration::from_std(backoff(attempts)).unwrap_or_default()
+}
+
+/// Queue an action that just failed with `error` for a later retry.
+pub async fn enqueue(
+    pool: &SqlitePool,
+    action: &OutboxAction,
+    error: &str,
+) -> Result<IntegrationOutboxEntry, OutboxError> {
+    tracing::warn!(
+        "Queued {} for retry while it is unreachable: {}",
+        action.dedupe_key(),
+        error
+    );
+    Ok(IntegrationOutboxEntry::enqueue(
+        pool,
+        action.integration(),
+        &action.dedupe_key(),
+        &serde_json::to_value(action)?,
+        error,
+        next_attempt_at(1),
+    )
+    .await?)
+}
+
+/// Retries queued integration actions.
+#[derive(Clone)]
+pub struct OutboxWorker {
+    db: DBService,
+    cipher: SecretCipher,
+}
+
+impl OutboxWorker {
+    pub async fn spawn(db: DBService, cipher: SecretCipher) -> tokio::task::JoinHandle<()> {
+        let worker = Self { db, cipher };
+        tokio::spawn(async move {
+            let mut interval = interval(POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = worker.run_due().await {
+                    tracing::error!("Failed to process integration outbox: {}", e);
+                }
+            }
+        })
+    }
+
+    async fn run_due(&self) -> Result<(), sqlx::Error> {
+        let pool = &self.db.pool;
+        for entry in IntegrationOutboxEntry::find_due(pool, MAX_ATTEMPTS, BATCH_SIZE).await? {
+            match self.deliver_entry(&entry).await {
+                Ok(()) => {
+                    tracing::info!("Delivered queued {}", entry.dedupe_key);
+                    IntegrationOutboxEntry::delete(pool, entry.id).await?;
+                }
+                Err(OutboxError::Gone(reason)) => {
+                    tracing::info!("Dropped queued {}: {}", entry.dedupe_key, reason);
+                    IntegrationOutboxEntry::delete(pool, entry.id).await?;
+                }
+                Err(e) => {
+                    let attempts = entry.attempts + 1;
+                    tracing::warn!(
+                        "Queued {} failed again (attempt {}): {}",
+                        entry.dedupe_key,
+                        attempts,
+                        e
+                    );
+                    IntegrationOutboxEntry::record_failure(
+                        pool,
+                        entry.id,
+                        &e.to_string(),
+                        next_attempt_at(attempts),
+                    )
+                    .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn deliver_entry(&self, entry: &IntegrationOutboxEntry) -> Result<(), OutboxError> {
+        let action: OutboxAction = serde_json::from_value(entry.payload.0.clone())?;
+        self.deliver(&action).await
+    }
+
+    /// Perform an action now
+    pub async fn deliver(&self, action: &OutboxAction) -> Result<(), OutboxError> {
+        let pool = &self.db.pool;
+        match action {
+            OutboxAction::GithubIssueState {
+                task_id,
+                issue_url,
+                issue_number,
+                open,
+            } => {
+                let repo_info = GitHubRepoInfo::from_remote_url(issue_url)?;
+                GitHubService::new()?
+                    .set_issue_state(&repo_info, *issue_number, *open)
+                    .await?;
+                GitHubIssueLink::set_issue_open(pool, *task_id, *open).await?;
+            }
+            OutboxAction::GithubPullRequest {
+                workspace_id,
+                repo_id,
+                worktree_path,
+                title,
+                body,
+                head_branch,
+                base_branch,
+                draft,
+            } => {
+                if !Merge::find_by_workspace_and_repo_id(pool, *workspace_id, *repo_id)
+                    .await?
+                    .is_empty()
+                {
+                    return Err(OutboxError::Gone(
+                        "a pull request was attached in the meantime".to_string(),
+                    ));
+                }
+                if !worktree_path.exists() {
+                    return Err(OutboxError::Gone(
+                        "the attempt's worktree no longer exists".to_string(),
+                    ));
+                }
+                GitService::new().push_to_github(worktree_path, head_branch, false)?;
+
+                let repo_info = GitService::new().get_github_repo_info(worktree_path)?;
+                let request = CreatePrRequest {
+                    title: title.clone(),
+                    body: body.clone(),
+                    head_branch: head_branch.clone(),
+                    base_branch: base_branch.clone(),
+                    draft: *draft,
+                };
+                let pr_info = GitHubService::new()?
+                    .create_pr(&repo_info, &request)
+                    .await?;
+                Merge::create_pr(
+                    pool,
+                    *workspace_id,
+                    *repo_id,
+                    base_branch,
+                    pr_info.number,
+                    &pr_info.url,
+                )
+                .await?;
+            }
+            OutboxAction::SlackTaskAnnouncement {
+                project_id,
+                task_id,
+                channel_id,
+                text,
+            } => {
+                let integration = SlackIntegration::find_by_project_id(pool, *project_id)
+                    .await?
+                    .ok_or_else(|| {
+                        OutboxError::Gone("the project's Slack app was removed".to_string())
+                    })?;
+                let bot_token = self.cipher.decrypt(&integration.bot_token)?;
+                let message_ts = SlackClient::new(bot_token)
+                    .post_message(channel_id, text)
+                    .await?;
+                SlackTaskThread::create(pool, *task_id, *project_id, channel_id, &message_ts)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognises_connectivity_errors() {
+        assert!(is_connectivity_error(
+            "GitHub CLI command failed: error connecting to api.github.com"
+        ));
+        assert!(is_connectivity_error(
+            "git command failed: fatal: unable to access 'https://github.com/acme/app.git/': \
+             Could not resolve host: github.com"
+        ));
+        assert!(!is_connectivity_error(
+            "GitHub CLI command failed: HTTP 422: A pull request already exists"
+        ));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        assert_eq!(backoff(1), INITIAL_BACKOFF);
+        assert_eq!(backoff(3), INITIAL_BACKOFF * 4);
+        assert_eq!(backoff(MAX_ATTEMPTS), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn actions_round_trip_through_json() {
+        let action = OutboxAction::GithubIssueState {
+            task_id: Uuid::new_v4(),
+            issue_url: "https://github.com/acme/app/issues/7".to_string(),
+            issue_number: 7,
+            open: false,
+        };
+        let value = serde_json::to_value(&action).unwrap();
+        assert_eq!(value["action"], "github_issue_state");
+        assert_eq!(
+            serde_json::from_value::<OutboxAction>(value).unwrap(),
+            action
+        );
+    }
+}
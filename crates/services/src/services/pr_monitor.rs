@@ -18,6 +18,7 @@ use crate::services::{
     analytics::AnalyticsContext,
     github::{GitHubRepoInfo, GitHubService, GitHubServiceError},
     share::SharePublisher,
+    task_workflow,
 };
 
 #[derive(Debug, Error)]
@@ -128,7 +129,12 @@ impl PrMonitorService {
                     "PR #{} was merged, updating task {} to done",
                     pr_merge.pr_info.number, workspace.task_id
                 );
-                Task::update_status(&self.db.pool, workspace.task_id, TaskStatus::Done).await?;
+                task_workflow::update_task_status(
+                    &self.db.pool,
+                    workspace.task_id,
+                    TaskStatus::Done,
+                )
+                .await?;
 
                 // Track analytics event
                 if let Some(analytics) = &self.analytics
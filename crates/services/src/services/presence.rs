@@ -0,0 +1,365 @@
+//! Who currently has a task open and what they are doing in it.
+//!
+//! Presence lives in memory only. Clients refresh their entry while the task is
+//! open and it expires after [`PRESENCE_TTL`] without a refresh, so a closed tab
+//! disappears on its own. Starting an attempt and answering a question are
+//! claims: only one client may hold them for a task at a time.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::services::events::{EntityEvent, EntityEventBus};
+
+/// How long an entry stays visible without being refreshed
+pub const PRESENCE_TTL: Duration = Duration::from_secs(30);
+const SWEEP_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PresenceActivity {
+    Viewing,
+    TypingFollowUp,
+    StartingAttempt,
+    AnsweringQuestion { question_id: String },
+}
+
+impl PresenceActivity {
+    /// Activities that only one client may perform on a task at a time.
+    pub fn is_claim(&self) -> bool {
+        matches!(self, Self::StartingAttempt | Self::AnsweringQuestion { .. })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct TaskPresence {
+    /// Identifies a browser tab, so one user can have the task open twice
+    pub client_id: String,
+    /// `null` in single-user mode
+    pub user_id: Option<Uuid>,
+    pub name: String,
+    pub activity: PresenceActivity,
+    #[ts(type = "Date")]
+    pub last_seen_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpdatePresence {
+    pub client_id: String,
+    pub user_id: Option<Uuid>,
+    pub name: String,
+    pub activity: PresenceActivity,
+}
+
+#[derive(Debug, Error)]
+pub enum PresenceError {
+    #[error("{} is already doing this on the task", .0.name)]
+    Claimed(Box<TaskPresence>),
+}
+
+#[derive(Debug)]
+struct TaskViewers {
+    project_id: Uuid,
+    viewers: Vec<TaskPresence>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PresenceService {
+    tasks: Arc<Mutex<HashMap<Uuid, TaskViewers>>>,
+    entity_events: EntityEventBus,
+}
+
+impl PresenceService {
+    pub fn new(entity_events: EntityEventBus) -> Self {
+        Self {
+            tasks: Arc::new(Mutex::new(HashMap::new())),
+            entity_events,
+        }
+    }
+
+    /// Live viewers of a task.
+    pub fn list(&self, task_id: Uuid) -> Vec<TaskPresence> {
+        self.list_at(task_id, Utc::now())
+    }
+
+    /// Join a task or refresh an existing entry, failing when the activity is a claim
+    /// already held by another client.
+    pub fn update(
+        &self,
+        project_id: Uuid,
+        task_id: Uuid,
+        update: UpdatePresence,
+    ) -> Result<Vec<TaskPresence>, PresenceError> {
+        self.update_at(project_id, task_id, update, Utc::now())
+    }
+
+    pub fn leave(&self, task_id: Uuid, client_id: &str) -> Vec<TaskPresence> {
+        self.leave_at(task_id, client_id, Utc::now())
+    }
+
+    /// Periodically drop expired entries so other viewers see them leave.
+    pub fn spawn_sweeper(&self) -> JoinHandle<()> {
+        let presence = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+            loop {
+                interval.tick().await;
+                presence.sweep_at(Utc::now());
+            }
+        })
+    }
+
+    fn list_at(&self, task_id: Uuid, now: DateTime<Utc>) -> Vec<TaskPresence> {
+        let tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        tasks
+            .get(&task_id)
+            .map(|entry| {
+                entry
+                    .viewers
+                    .iter()
+                    .filter(|p| !is_expired(p, now))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn update_at(
+        &self,
+        project_id: Uuid,
+        task_id: Uuid,
+        update: UpdatePresence,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<TaskPresence>, PresenceError> {
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = tasks.entry(task_id).or_insert_with(|| TaskViewers {
+            project_id,
+            viewers: Vec::new(),
+        });
+        let mut changed = prune(&mut entry.viewers, now);
+
+        if update.activity.is_claim()
+            && let Some(holder) = entry
+                .viewers
+                .iter()
+                .find(|p| p.client_id != update.client_id && p.activity == update.activity)
+        {
+            let holder = Box::new(holder.clone());
+            if changed {
+                self.publish(project_id, task_id, entry.viewers.clone());
+            }
+            return Err(PresenceError::Claimed(holder));
+        }
+
+        match entry
+            .viewers
+            .iter_mut()
+            .find(|p| p.client_id == update.client_id)
+        {
+            Some(existing) => {
+                // A plain refresh is not worth an event
+                changed |= existing.activity != update.activity || existing.name != update.name;
+                existing.user_id = update.user_id;
+                existing.name = update.name;
+                existing.activity = update.activity;
+                existing.last_seen_at = now;
+            }
+            None => {
+                changed = true;
+                entry.viewers.push(TaskPresence {
+                    client_id: update.client_id,
+                    user_id: update.user_id,
+                    name: update.name,
+                    activity: update.activity,
+                    last_seen_at: now,
+                });
+            }
+        }
+
+        let viewers = entry.viewers.clone();
+        if changed {
+            self.publish(project_id, task_id, viewers.clone());
+        }
+        Ok(viewers)
+    }
+
+    fn leave_at(&self, task_id: Uuid, client_id: &str, now: DateTime<Utc>) -> Vec<TaskPresence> {
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(entry) = tasks.get_mut(&task_id) else {
+            return Vec::new();
+        };
+        let before = entry.viewers.len();
+        entry.viewers.retain(|p| p.client_id != client_id);
+        let changed = prune(&mut entry.viewers, now) || entry.viewers.len() != before;
+
+        let (project_id, viewers) = (entry.project_id, entry.viewers.clone());
+        if viewers.is_empty() {
+            tasks.remove(&task_id);
+        }
+        if changed {
+            self.publish(project_id, task_id, viewers.clone());
+        }
+        viewers
+    }
+
+    fn sweep_at(&self, now: DateTime<Utc>) {
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        for (task_id, entry) in tasks.iter_mut() {
+            if prune(&mut entry.viewers, now) {
+                self.publish(entry.project_id, *task_id, entry.viewers.clone());
+            }
+        }
+        tasks.retain(|_, entry| !entry.viewers.is_empty());
+    }
+
+    fn publish(&self, project_id: Uuid, task_id: Uuid, viewers: Vec<TaskPresence>) {
+        self.entity_events.publish(
+            Some(project_id),
+            EntityEvent::PresenceChanged { task_id, viewers },
+        );
+    }
+}
+
+fn is_expired(presence: &TaskPresence, now: DateTime<Utc>) -> bool {
+    now.signed_duration_since(presence.last_seen_at)
+        .to_std()
+        .is_ok_and(|age| age > PRESENCE_TTL)
+}
+
+/// Remove expired entries, returning whether any were removed.
+fn prune(viewers: &mut Vec<TaskPresence>, now: DateTime<Utc>) -> bool {
+    let before = viewers.len();
+    viewers.retain(|p| !is_expired(p, now));
+    viewers.len() != before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(client_id: &str, activity: PresenceActivity) -> UpdatePresence {
+        UpdatePresence {
+            client_id: client_id.to_string(),
+            user_id: None,
+            name: client_id.to_string(),
+            activity,
+        }
+    }
+
+    #[test]
+    fn claims_are_exclusive_per_task() {
+        let presence = PresenceService::new(EntityEventBus::new());
+        let (project_id, task_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let now = Utc::now();
+
+        presence
+            .update_at(
+                project_id,
+                task_id,
+                update("a", PresenceActivity::StartingAttempt),
+                now,
+            )
+            .unwrap();
+        assert!(matches!(
+            presence.update_at(project_id, task_id, update("b", PresenceActivity::StartingAttempt), now),
+            Err(PresenceError::Claimed(holder)) if holder.client_id == "a"
+        ));
+
+        let question = |id: &str| PresenceActivity::AnsweringQuestion {
+            question_id: id.to_string(),
+        };
+        presence
+            .update_at(project_id, task_id, update("b", question("q1")), now)
+            .unwrap();
+        presence
+            .update_at(project_id, task_id, update("c", question("q2")), now)
+            .unwrap();
+        assert!(
+            presence
+                .update_at(project_id, task_id, update("c", question("q1")), now)
+                .is_err()
+        );
+        assert_eq!(presence.list_at(task_id, now).len(), 3);
+    }
+
+    #[test]
+    fn expired_entries_are_swept_and_announced() {
+        let bus = EntityEventBus::new();
+        let presence = PresenceService::new(bus.clone());
+        let (project_id, task_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let now = Utc::now();
+
+        presence
+            .update_at(
+                project_id,
+                task_id,
+                update("a", PresenceActivity::StartingAttempt),
+                now,
+            )
+            .unwrap();
+        // Refreshing with the same activity does not publish
+        presence
+            .update_at(
+                project_id,
+                task_id,
+                update("a", PresenceActivity::StartingAttempt),
+                now,
+            )
+            .unwrap();
+        let later = now + chrono::Duration::seconds(PRESENCE_TTL.as_secs() as i64 + 1);
+        presence.sweep_at(later);
+
+        assert!(presence.list_at(task_id, later).is_empty());
+        presence
+            .update_at(
+                project_id,
+                task_id,
+                update("b", PresenceActivity::StartingAttempt),
+                later,
+            )
+            .unwrap();
+
+        let sub = bus.subscribe(Some(0));
+        let sizes: Vec<_> = sub
+            .replay
+            .iter()
+            .map(|e| match &e.event {
+                EntityEvent::PresenceChanged { viewers, .. } => viewers.len(),
+                other => panic!("unexpected event {other:?}"),
+            })
+            .collect();
+        assert_eq!(sizes, vec![1, 0, 1]);
+    }
+
+    #[test]
+    fn leaving_removes_the_client() {
+        let presence = PresenceService::new(EntityEventBus::new());
+        let (project_id, task_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        presence
+            .update(project_id, task_id, update("a", PresenceActivity::Viewing))
+            .unwrap();
+        presence
+            .update(
+                project_id,
+                task_id,
+                update("b", PresenceActivity::TypingFollowUp),
+            )
+            .unwrap();
+        let remaining = presence.leave(task_id, "a");
+
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].client_id, "b");
+        assert!(presence.leave(task_id, "b").is_empty());
+        assert!(presence.list(task_id).is_empty());
+    }
+}
@@ -5,9 +5,15 @@ use std::{
 
 use db::models::{
     project::{CreateProject, Project, ProjectError, SearchMatchType, SearchResult, UpdateProject},
-    project_repo::{CreateProjectRepo, ProjectRepo},
+    project_env_var::ProjectEnvVar,
+    project_mcp_server::{CreateProjectMcpServer, ProjectMcpServer},
+    project_repo::{CreateProjectRepo, ProjectRepo, ProjectRepoError, UpdateProjectRepo},
+    project_template::{
+        ProjectTemplateConfig, ProjectTemplateEnvVar, ProjectTemplateMcpServer, ProjectTemplateRepo,
+    },
     repo::Repo,
     task::Task,
+    team::Team,
 };
 use ignore::WalkBuilder;
 use sqlx::SqlitePool;
@@ -151,6 +157,133 @@ impl ProjectService {
         Ok(project)
     }
 
+    /// Capture a project's configuration for a template. Env var values are left out.
+    pub async fn template_config(
+        &self,
+        pool: &SqlitePool,
+        project: &Project,
+    ) -> Result<ProjectTemplateConfig> {
+        let repositories = ProjectRepo::find_by_project_id_with_names(pool, project.id)
+            .await?
+            .into_iter()
+            .map(|repo| ProjectTemplateRepo {
+                name: repo.repo_name,
+                setup_script: repo.setup_script,
+                cleanup_script: repo.cleanup_script,
+                copy_files: repo.copy_files,
+                parallel_setup_script: repo.parallel_setup_script,
+            })
+            .collect();
+        let env_vars = ProjectEnvVar::find_by_project_id(pool, project.id)
+            .await?
+            .into_iter()
+            .map(|var| ProjectTemplateEnvVar {
+                key: var.key,
+                is_secret: var.is_secret,
+            })
+            .collect();
+        let mcp_servers = ProjectMcpServer::find_by_project_id(pool, project.id)
+            .await?
+            .into_iter()
+            .map(|server| {
+                let mut env_keys: Vec<String> = server.env.0.into_keys().collect();
+                env_keys.sort();
+                ProjectTemplateMcpServer {
+                    name: server.name,
+                    transport: server.transport.0,
+                    env_keys,
+                    auto_approve: server.auto_approve,
+                }
+            })
+            .collect();
+        let team_id = Team::find_by_project_id(pool, project.id)
+            .await?
+            .map(|team| team.id);
+
+        Ok(ProjectTemplateConfig {
+            dev_script: project.dev_script.clone(),
+            dev_script_working_dir: project.dev_script_working_dir.clone(),
+            default_agent_working_dir: project.default_agent_working_dir.clone(),
+            repositories,
+            env_vars,
+            mcp_servers,
+            team_id,
+        })
+    }
+
+    /// Apply a template's scripts, MCP servers and team to a newly created project. Env vars
+    /// are created by the caller since secrets must be encrypted.
+    pub async fn apply_template(
+        &self,
+        pool: &SqlitePool,
+        project: &Project,
+        config: &ProjectTemplateConfig,
+    ) -> Result<Project> {
+        let project = Project::update(
+            pool,
+            project.id,
+            &UpdateProject {
+                name: None,
+                dev_script: config.dev_script.clone(),
+                dev_script_working_dir: config.dev_script_working_dir.clone(),
+                default_agent_working_dir: config
+                    .default_agent_working_dir
+                    .clone()
+                    .or_else(|| project.default_agent_working_dir.clone()),
+            },
+        )
+        .await?;
+
+        for repo in ProjectRepo::find_by_project_id_with_names(pool, project.id).await? {
+            let Some(scripts) = config.repo_scripts(&repo.repo_name) else {
+                continue;
+            };
+            ProjectRepo::update(
+                pool,
+                project.id,
+                repo.repo_id,
+                &UpdateProjectRepo {
+                    setup_script: scripts.setup_script.clone(),
+                    cleanup_script: scripts.cleanup_script.clone(),
+                    copy_files: scripts.copy_files.clone(),
+                    parallel_setup_script: Some(scripts.parallel_setup_script),
+                },
+            )
+            .await
+            .map_err(|e| match e {
+                ProjectRepoError::Database(e) => ProjectServiceError::Database(e),
+                _ => ProjectServiceError::RepositoryNotFound,
+            })?;
+        }
+
+        for server in &config.mcp_servers {
+            let env = server
+                .env_keys
+                .iter()
+                .map(|key| (key.clone(), String::new()))
+                .collect();
+            ProjectMcpServer::create(
+                pool,
+                project.id,
+                &CreateProjectMcpServer {
+                    name: server.name.clone(),
+                    transport: server.transport.clone(),
+                    env,
+                    auto_approve: server.auto_approve,
+                },
+            )
+            .await?;
+        }
+
+        if let Some(team_id) = config.team_id
+            && Team::find_by_id(pool, team_id).await?.is_some()
+        {
+            Team::add_project(pool, team_id, project.id).await?;
+        }
+
+        Ok(project)
+    }
+
     /// Link a project to a remote project and sync shared tasks
     pub async fn link_to_remote(
         &self,
@@ -0,0 +1,274 @@
+use std::{collections::HashMap, sync::Arc};
+
+use db::models::project_env_var::{CreateProjectEnvVar, ProjectEnvVar, UpdateProjectEnvVar};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::secret_cipher::{SecretCipher, SecretCipherError};
+
+/// Placeholder substituted for secret values in process output.
+pub const REDACTED: &str = "[REDACTED]";
+
+#[derive(Debug, Error)]
+pub enum ProjectEnvError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Cipher(#[from] SecretCipherError),
+    #[error("Invalid environment variable name: {0}")]
+    InvalidKey(String),
+}
+
+/// Resolved env vars for a project along with the plaintext secrets that must
+/// be scrubbed from any captured output.
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedProjectEnv {
+    pub vars: HashMap<String, String>,
+    pub secrets: Vec<String>,
+}
+
+#[derive(Clone)]
+pub struct ProjectEnvService {
+    cipher: SecretCipher,
+}
+
+impl ProjectEnvService {
+    pub fn new(cipher: SecretCipher) -> Self {
+        Self { cipher }
+    }
+
+    pub fn cipher(&self) -> &SecretCipher {
+        &self.cipher
+    }
+
+    pub async fn create(
+        &self,
+        pool: &SqlitePool,
+        project_id: Uuid,
+        payload: &CreateProjectEnvVar,
+    ) -> Result<ProjectEnvVar, ProjectEnvError> {
+        validate_key(&payload.key)?;
+        let value = self.seal(&payload.value, payload.is_secret)?;
+        let var = ProjectEnvVar::create(pool, project_id, &payload.key, &value, payload.is_secret)
+            .await?;
+        Ok(var)
+    }
+
+    pub async fn update(
+        &self,
+        pool: &SqlitePool,
+        existing: &ProjectEnvVar,
+        payload: &UpdateProjectEnvVar,
+    ) -> Result<ProjectEnvVar, ProjectEnvError> {
+        let key = payload.key.as_deref().unwrap_or(&existing.key);
+        validate_key(key)?;
+        let is_secret = payload.is_secret.unwrap_or(existing.is_secret);
+
+        let value = match &payload.value {
+            Some(value) => self.seal(value, is_secret)?,
+            None => {
+                // Re-seal the stored value if the secret flag changed
+                let plaintext = self.open(existing)?;
+                self.seal(&plaintext, is_secret)?
+            }
+        };
+
+        Ok(ProjectEnvVar::update(pool, existing.id, key, &value, is_secret).await?)
+    }
+
+    /// Load and decrypt all env vars configured for a project.
+    pub async fn resolve(
+        &self,
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<ResolvedProjectEnv, ProjectEnvError> {
+        let mut resolved = ResolvedProjectEnv::default();
+        for var in ProjectEnvVar::find_by_project_id(pool, project_id).await? {
+            let value = self.open(&var)?;
+            if var.is_secret && !value.is_empty() {
+                resolved.secrets.push(value.clone());
+            }
+            resolved.vars.insert(var.key, value);
+        }
+        Ok(resolved)
+    }
+
+    fn seal(&self, value: &str, is_secret: bool) -> Result<String, ProjectEnvError> {
+        if is_secret {
+            Ok(self.cipher.encrypt(value)?)
+        } else {
+            Ok(value.to_string())
+        }
+    }
+
+    fn open(&self, var: &ProjectEnvVar) -> Result<String, ProjectEnvError> {
+        if var.is_secret {
+            Ok(self.cipher.decrypt(&var.value)?)
+        } else {
+            Ok(var.value.clone())
+        }
+    }
+}
+
+/// Replace every occurrence of a secret value in `text` with [`REDACTED`].
+pub fn redact_secrets(text: &str, secrets: &[String]) -> String {
+    secrets
+        .iter()
+        .filter(|secret| !secret.is_empty())
+        .fold(text.to_string(), |acc, secret| {
+            acc.replace(secret.as_str(), REDACTED)
+        })
+}
+
+/// Redacts secrets from output that arrives in arbitrary chunks.
+///
+/// A secret can be split across two reads, so output that could still be the
+/// start of a secret is held back unredacted and prepended to the next chunk.
+/// Anything still held when the stream ends is returned by
+/// [`SecretRedactor::finish`].
+#[derive(Debug)]
+pub struct SecretRedactor {
+    secrets: Arc<Vec<String>>,
+    tail: String,
+}
+
+impl SecretRedactor {
+    pub fn new(secrets: Arc<Vec<String>>) -> Self {
+        Self {
+            secrets,
+            tail: String::new(),
+        }
+    }
+
+    /// Redact `chunk` and return the part that can no longer be the start of
+    /// a secret.
+    pub fn push(&mut self, chunk: &str) -> String {
+        self.tail.push_str(chunk);
+        self.drain(false)
+    }
+
+    /// Whether output is being held back in case it starts a secret.
+    pub fn is_holding(&self) -> bool {
+        !self.tail.is_empty()
+    }
+
+    /// Redact and return whatever output is still held back.
+    pub fn finish(&mut self) -> String {
+        self.drain(true)
+    }
+
+    fn drain(&mut self, at_end: bool) -> String {
+        let text = std::mem::take(&mut self.tail);
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text.as_str();
+        while let Some(c) = rest.chars().next() {
+            if !at_end
+                && self
+                    .secrets
+                    .iter()
+                    .any(|secret| secret.len() > rest.len() && secret.starts_with(rest))
+            {
+                break;
+            }
+            match self
+                .secrets
+                .iter()
+                .filter(|secret| !secret.is_empty() && rest.starts_with(secret.as_str()))
+                .max_by_key(|secret| secret.len())
+            {
+                Some(secret) => {
+                    out.push_str(REDACTED);
+                    rest = &rest[secret.len()..];
+                }
+                None => {
+                    out.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+        self.tail = rest.to_string();
+        out
+    }
+}
+
+pub(crate) fn validate_key(key: &str) -> Result<(), ProjectEnvError> {
+    let mut chars = key.chars();
+    let valid = matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(ProjectEnvError::InvalidKey(key.to_string()));
+    }
+    if key.starts_with("VK_") {
+        return Err(ProjectEnvError::InvalidKey(format!(
+            "{key} (the VK_ prefix is reserved)"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_keys() {
+        assert!(validate_key("DATABASE_URL").is_ok());
+        assert!(validate_key("_private").is_ok());
+        assert!(validate_key("1BAD").is_err());
+        assert!(validate_key("HAS-DASH").is_err());
+        assert!(validate_key("").is_err());
+        assert!(validate_key("VK_TASK_ID").is_err());
+    }
+
+    #[test]
+    fn redacts_all_occurrences() {
+        let secrets = vec!["s3cret".to_string(), String::new()];
+        assert_eq!(
+            redact_secrets("token=s3cret, again s3cret", &secrets),
+            "token=[REDACTED], again [REDACTED]"
+        );
+        assert_eq!(redact_secrets("nothing here", &secrets), "nothing here");
+    }
+
+    #[test]
+    fn redacts_secrets_split_across_chunks() {
+        let mut redactor = SecretRedactor::new(Arc::new(vec!["s3cret".to_string()]));
+        let mut out = String::new();
+        for chunk in ["token=s3", "cr", "et done s3c", "ret"] {
+            out.push_str(&redactor.push(chunk));
+        }
+        out.push_str(&redactor.finish());
+        assert_eq!(out, "token=[REDACTED] done [REDACTED]");
+    }
+
+    #[test]
+    fn passes_output_through_without_secrets() {
+        let mut redactor = SecretRedactor::new(Arc::new(vec![String::new()]));
+        assert_eq!(redactor.push("héllo"), "héllo");
+        assert!(!redactor.is_holding());
+        assert_eq!(redactor.finish(), "");
+    }
+
+    #[test]
+    fn holds_back_only_possible_secret_starts() {
+        let mut redactor = SecretRedactor::new(Arc::new(vec!["s3cret".to_string()]));
+        assert_eq!(redactor.push("Password: "), "Password: ");
+        assert!(!redactor.is_holding());
+
+        assert_eq!(redactor.push("is s3c"), "is ");
+        assert!(redactor.is_holding());
+        assert_eq!(redactor.finish(), "s3c");
+    }
+
+    #[test]
+    fn does_not_redact_the_marker_again() {
+        let mut redactor = SecretRedactor::new(Arc::new(vec!["RED".to_string()]));
+        let mut out = String::new();
+        for chunk in ["a RE", "D b", " RED"] {
+            out.push_str(&redactor.push(chunk));
+        }
+        out.push_str(&redactor.finish());
+        assert_eq!(out, "a [REDACTED] b [REDACTED]");
+    }
+}
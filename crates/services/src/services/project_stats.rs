@@ -0,0 +1,372 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use db::models::{
+    agent_request_response::{AgentRequestKind, AgentRequestResponse},
+    execution_process::{AgentRunTime, ExecutionProcess},
+    task::TaskStatus,
+    task_status_change::TaskStatusChange,
+    task_time_entry::TaskTimeEntry,
+    workspace::Workspace,
+};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::executor_stats::median;
+
+pub const DEFAULT_WEEKS: u32 = 12;
+pub const MAX_WEEKS: u32 = 104;
+
+pub(crate) const COLUMNS: [TaskStatus; 6] = [
+    TaskStatus::Todo,
+    TaskStatus::InProgress,
+    TaskStatus::Blocked,
+    TaskStatus::InReview,
+    TaskStatus::Done,
+    TaskStatus::Cancelled,
+];
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ProjectStats {
+    /// Tasks completed in each of the last weeks, oldest week first
+    pub completed_per_week: Vec<WeeklyThroughput>,
+    pub column_times: Vec<ColumnTime>,
+    pub attempts_per_task: AttemptsPerTask,
+    pub question_response: ResponseLatency,
+    pub approval_response: ResponseLatency,
+    pub time_spent: TimeSpent,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct WeeklyThroughput {
+    /// Monday the week starts on, in UTC
+    #[ts(type = "string")]
+    pub week_start: NaiveDate,
+    /// Tasks that first reached Done that week
+    pub completed: usize,
+}
+
+/// Agent and logged human time over the reported period.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct TimeSpent {
+    /// Wall-clock time of finished coding agent runs
+    pub agent_seconds: f64,
+    /// Time logged by hand on the project's tasks
+    #[ts(type = "number")]
+    pub human_seconds: i64,
+    /// Oldest week first
+    pub per_week: Vec<WeeklyTime>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct WeeklyTime {
+    /// Monday the week starts on, in UTC
+    #[ts(type = "string")]
+    pub week_start: NaiveDate,
+    pub agent_seconds: f64,
+    #[ts(type = "number")]
+    pub human_seconds: i64,
+}
+
+/// How long tasks stayed in a column before moving on. Only stays that ended in the reported
+/// period count, so tasks still sitting in the column are left out.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ColumnTime {
+    pub status: TaskStatus,
+    pub average_seconds: Option<f64>,
+    /// Number of stays averaged
+    pub stays: usize,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct AttemptsPerTask {
+    pub tasks: usize,
+    pub average: f64,
+    #[ts(type = "number")]
+    pub max: i64,
+    /// Tasks never attempted
+    pub without_attempts: usize,
+}
+
+/// Time from an agent asking to the user answering, over the reported period.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ResponseLatency {
+    pub responses: usize,
+    pub average_seconds: Option<f64>,
+    pub median_seconds: Option<f64>,
+}
+
+pub(crate) fn seconds_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_milliseconds().max(0) as f64 / 1000.0
+}
+
+fn week_start(at: DateTime<Utc>) -> NaiveDate {
+    let date = at.date_naive();
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Changes grouped per task, relying on them being sorted by task.
+fn per_task(changes: &[TaskStatusChange]) -> impl Iterator<Item = &[TaskStatusChange]> {
+    changes.chunk_by(|a, b| a.task_id == b.task_id)
+}
+
+/// Tasks that first reached Done in each of the `weeks` weeks up to `now`.
+fn completed_per_week(
+    changes: &[TaskStatusChange],
+    now: DateTime<Utc>,
+    weeks: u32,
+) -> Vec<WeeklyThroughput> {
+    let current = week_start(now);
+    let mut counts: Vec<WeeklyThroughput> = (0..weeks as i64)
+        .rev()
+        .map(|ago| WeeklyThroughput {
+            week_start: current - Duration::weeks(ago),
+            completed: 0,
+        })
+        .collect();
+
+    for task_changes in per_task(changes) {
+        let Some(done) = task_changes.iter().find(|c| c.status == TaskStatus::Done) else {
+            continue;
+        };
+        let week = week_start(done.changed_at);
+        if let Some(bucket) = counts.iter_mut().find(|w| w.week_start == week) {
+            bucket.completed += 1;
+        }
+    }
+    counts
+}
+
+/// Average stay in each column, over stays that ended since `since`.
+fn column_times(changes: &[TaskStatusChange], since: DateTime<Utc>) -> Vec<ColumnTime> {
+    let mut stays: Vec<(TaskStatus, f64)> = Vec::new();
+    for task_changes in per_task(changes) {
+        for pair in task_changes.windows(2) {
+            if pair[1].changed_at >= since {
+                stays.push((
+                    pair[0].status.clone(),
+                    seconds_between(pair[0].changed_at, pair[1].changed_at),
+                ));
+            }
+        }
+    }
+
+    COLUMNS
+        .into_iter()
+        .map(|status| {
+            let durations: Vec<f64> = stays
+                .iter()
+                .filter(|(s, _)| *s == status)
+                .map(|(_, seconds)| *seconds)
+                .collect();
+            ColumnTime {
+                average_seconds: average(&durations),
+                stays: durations.len(),
+                status,
+            }
+        })
+        .collect()
+}
+
+/// Agent runs and time entries bucketed into the `weeks` weeks up to `now`, by when the run
+/// started or the work was done.
+fn time_spent(
+    runs: &[AgentRunTime],
+    entries: &[TaskTimeEntry],
+    now: DateTime<Utc>,
+    weeks: u32,
+) -> TimeSpent {
+    let current = week_start(now);
+    let mut per_week: Vec<WeeklyTime> = (0..weeks as i64)
+        .rev()
+        .map(|ago| WeeklyTime {
+            week_start: current - Duration::weeks(ago),
+            agent_seconds: 0.0,
+            human_seconds: 0,
+        })
+        .collect();
+
+    for run in runs {
+        let week = week_start(run.started_at);
+        if let Some(bucket) = per_week.iter_mut().find(|w| w.week_start == week) {
+            bucket.agent_seconds += run.seconds();
+        }
+    }
+    for entry in entries {
+        let week = week_start(entry.worked_at);
+        if let Some(bucket) = per_week.iter_mut().find(|w| w.week_start == week) {
+            bucket.human_seconds += entry.seconds;
+        }
+    }
+    TimeSpent {
+        agent_seconds: per_week.iter().map(|w| w.agent_seconds).sum(),
+        human_seconds: per_week.iter().map(|w| w.human_seconds).sum(),
+        per_week,
+    }
+}
+
+fn attempts_per_task(counts: &[i64]) -> AttemptsPerTask {
+    AttemptsPerTask {
+        tasks: counts.len(),
+        average: if counts.is_empty() {
+            0.0
+        } else {
+            counts.iter().sum::<i64>() as f64 / counts.len() as f64
+        },
+        max: counts.iter().copied().max().unwrap_or(0),
+        without_attempts: counts.iter().filter(|c| **c == 0).count(),
+    }
+}
+
+fn response_latency(responses: &[AgentRequestResponse], kind: AgentRequestKind) -> ResponseLatency {
+    let seconds: Vec<f64> = responses
+        .iter()
+        .filter(|r| r.kind == kind)
+        .map(|r| seconds_between(r.requested_at, r.responded_at))
+        .collect();
+    ResponseLatency {
+        responses: seconds.len(),
+        average_seconds: average(&seconds),
+        median_seconds: median(seconds),
+    }
+}
+
+pub(crate) fn average(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Throughput, flow and responsiveness of a project over the last `weeks` weeks.
+pub async fn project_stats(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    weeks: u32,
+) -> Result<ProjectStats, sqlx::Error> {
+    let weeks = weeks.clamp(1, MAX_WEEKS);
+    let now = Utc::now();
+    let since = week_start(now)
+        .checked_sub_signed(Duration::weeks(weeks as i64 - 1))
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc())
+        .unwrap_or(now);
+
+    let changes = TaskStatusChange::find_by_project_id(pool, project_id).await?;
+    let attempt_counts = Workspace::count_per_task_for_project(pool, project_id).await?;
+    let responses = AgentRequestResponse::find_by_project_id(pool, project_id, since).await?;
+    let runs = ExecutionProcess::find_agent_run_times_for_project(pool, project_id, since).await?;
+    let entries = TaskTimeEntry::find_by_project_id(pool, project_id, since).await?;
+
+    Ok(ProjectStats {
+        completed_per_week: completed_per_week(&changes, now, weeks),
+        column_times: column_times(&changes, since),
+        attempts_per_task: attempts_per_task(&attempt_counts),
+        question_response: response_latency(&responses, AgentRequestKind::Question),
+        approval_response: response_latency(&responses, AgentRequestKind::Approval),
+        time_spent: time_spent(&runs, &entries, now, weeks),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(task_id: Uuid, status: TaskStatus, at: &str) -> TaskStatusChange {
+        TaskStatusChange {
+            task_id,
+            status,
+            changed_at: DateTime::parse_from_rfc3339(at)
+                .unwrap()
+                .with_timezone(&Utc),
+        }
+    }
+
+    #[test]
+    fn counts_completions_per_week_and_time_in_columns() {
+        let (a, b) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        let changes = vec![
+            change(a, TaskStatus::Todo, "2026-01-05T09:00:00Z"),
+            change(a, TaskStatus::InProgress, "2026-01-05T10:00:00Z"),
+            change(a, TaskStatus::Done, "2026-01-05T12:00:00Z"),
+            change(a, TaskStatus::InProgress, "2026-01-13T12:00:00Z"),
+            change(a, TaskStatus::Done, "2026-01-13T13:00:00Z"),
+            change(b, TaskStatus::Todo, "2026-01-12T09:00:00Z"),
+            change(b, TaskStatus::InProgress, "2026-01-12T12:00:00Z"),
+        ];
+        let now = DateTime::parse_from_rfc3339("2026-01-14T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let weeks = completed_per_week(&changes, now, 2);
+        assert_eq!(
+            weeks
+                .iter()
+                .map(|w| (w.week_start.to_string(), w.completed))
+                .collect::<Vec<_>>(),
+            vec![("2026-01-05".to_string(), 1), ("2026-01-12".to_string(), 0)]
+        );
+
+        let times = column_times(&changes, now - Duration::weeks(2));
+        let todo = times.iter().find(|t| t.status == TaskStatus::Todo).unwrap();
+        assert_eq!((todo.stays, todo.average_seconds), (2, Some(7200.0)));
+        let in_progress = times
+            .iter()
+            .find(|t| t.status == TaskStatus::InProgress)
+            .unwrap();
+        assert_eq!(
+            (in_progress.stays, in_progress.average_seconds),
+            (2, Some(5400.0))
+        );
+    }
+
+    #[test]
+    fn rolls_up_agent_and_human_time_per_week() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let run = |start: &str, end: &str| AgentRunTime {
+            task_id: Uuid::from_u128(1),
+            workspace_id: Uuid::from_u128(2),
+            started_at: at(start),
+            completed_at: at(end),
+        };
+        let entry = |seconds: i64, worked_at: &str| TaskTimeEntry {
+            id: Uuid::new_v4(),
+            task_id: Uuid::from_u128(1),
+            user_id: None,
+            seconds,
+            note: None,
+            worked_at: at(worked_at),
+            created_at: at(worked_at),
+        };
+        let runs = vec![
+            run("2026-01-06T10:00:00Z", "2026-01-06T10:30:00Z"),
+            run("2026-01-13T09:00:00Z", "2026-01-13T09:01:30Z"),
+            // Before the reported weeks
+            run("2025-12-01T09:00:00Z", "2025-12-01T10:00:00Z"),
+        ];
+        let entries = vec![
+            entry(3600, "2026-01-07T12:00:00Z"),
+            entry(600, "2026-01-13T12:00:00Z"),
+        ];
+
+        let spent = time_spent(&runs, &entries, at("2026-01-14T00:00:00Z"), 2);
+        assert_eq!(
+            spent
+                .per_week
+                .iter()
+                .map(|w| (w.week_start.to_string(), w.agent_seconds, w.human_seconds))
+                .collect::<Vec<_>>(),
+            vec![
+                ("2026-01-05".to_string(), 1800.0, 3600),
+                ("2026-01-12".to_string(), 90.0, 600),
+            ]
+        );
+        assert_eq!((spent.agent_seconds, spent.human_seconds), (1890.0, 4200));
+    }
+
+    #[test]
+    fn summarizes_attempts_per_task() {
+        let attempts = attempts_per_task(&[0, 1, 3, 2]);
+        assert_eq!(attempts.tasks, 4);
+        assert_eq!(attempts.average, 1.5);
+        assert_eq!(attempts.max, 3);
+        assert_eq!(attempts.without_attempts, 1);
+    }
+}
@@ -0,0 +1,267 @@
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::LazyLock,
+};
+
+use db::models::{repo::Repo, task::Task, workspace_summary::WorkspaceSummary};
+use regex::Regex;
+use sqlx::SqlitePool;
+use utils::text::truncate_to_char_boundary;
+use uuid::Uuid;
+
+/// Budget per embedded file, in bytes
+const MAX_FILE_BYTES: usize = 32_000;
+/// Budget for all embedded files of a prompt, in bytes
+const MAX_TOTAL_FILE_BYTES: usize = 96_000;
+/// Entries listed for a referenced directory
+const MAX_DIRECTORY_ENTRIES: usize = 200;
+
+/// `@task:<uuid>` or `@path`, at the start of the prompt or after whitespace or an opening
+/// bracket so that email addresses are left alone.
+static REFERENCE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[\s(\[])@(task:[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}|[\w.\-/]+)")
+        .expect("valid reference regex")
+});
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptReference {
+    File(String),
+    Task(Uuid),
+}
+
+/// A prompt with its references expanded, and the references that could not be resolved.
+#[derive(Debug, Clone)]
+pub struct ResolvedPrompt {
+    pub prompt: String,
+    pub missing_files: Vec<String>,
+    pub missing_tasks: Vec<Uuid>,
+}
+
+impl ResolvedPrompt {
+    pub fn has_missing(&self) -> bool {
+        !self.missing_files.is_empty() || !self.missing_tasks.is_empty()
+    }
+
+    /// Human readable list of the unresolved references.
+    pub fn missing_message(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.missing_files.is_empty() {
+            parts.push(format!(
+                "Referenced paths not found: {}",
+                self.missing_files.join(", ")
+            ));
+        }
+        if !self.missing_tasks.is_empty() {
+            parts.push(format!(
+                "Referenced tasks not found: {}",
+                self.missing_tasks
+                    .iter()
+                    .map(Uuid::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        parts.join(". ")
+    }
+
+    /// The expanded prompt, telling the agent about references that could not be resolved.
+    pub fn into_prompt_with_notes(self) -> String {
+        if !self.has_missing() {
+            return self.prompt;
+        }
+        let message = self.missing_message();
+        format!("{}\n\nNote: {message}.", self.prompt)
+    }
+}
+
+/// `@path/to/file` and `@task:ID` mentions in a prompt, in order and without duplicates. A path
+/// must contain a `/` or `.` so that plain `@name` mentions are not taken for files.
+pub fn parse_references(prompt: &str) -> Vec<PromptReference> {
+    let mut references = Vec::new();
+    for captures in REFERENCE_RE.captures_iter(prompt) {
+        let raw = &captures[1];
+        let reference = match raw.strip_prefix("task:") {
+            Some(id) => match Uuid::parse_str(id) {
+                Ok(id) => PromptReference::Task(id),
+                Err(_) => continue,
+            },
+            None => {
+                // Sentence punctuation right after a path is not part of it
+                let path = raw.trim_end_matches(['.', ',', ':', ';']);
+                if !path.contains(['/', '.']) || path.chars().all(|c| c == '.' || c == '/') {
+                    continue;
+                }
+                PromptReference::File(path.to_string())
+            }
+        };
+        if !references.contains(&reference) {
+            references.push(reference);
+        }
+    }
+    references
+}
+
+/// Find a referenced path in the workspace: first relative to the workspace root, where each
+/// repository is a directory, then relative to each repository.
+fn locate(workspace_root: &Path, repos: &[Repo], reference: &str) -> Option<PathBuf> {
+    let relative = Path::new(reference.trim_start_matches("./"));
+    if relative
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return None;
+    }
+
+    std::iter::once(workspace_root.join(relative))
+        .chain(
+            repos
+                .iter()
+                .map(|repo| workspace_root.join(&repo.name).join(relative)),
+        )
+        .find(|candidate| candidate.exists())
+}
+
+fn render_file(reference: &str, path: &Path, budget: &mut usize) -> String {
+    if path.is_dir() {
+        let mut entries: Vec<String> = std::fs::read_dir(path)
+            .map(|dir| {
+                dir.flatten()
+                    .map(|entry| {
+                        let name = entry.file_name().to_string_lossy().to_string();
+                        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                            format!("{name}/")
+                        } else {
+                            name
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        entries.sort();
+        entries.truncate(MAX_DIRECTORY_ENTRIES);
+        return format!("### {reference} (directory)\n{}", entries.join("\n"));
+    }
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return format!("### {reference}\n(binary or unreadable file, not embedded)"),
+    };
+    if *budget == 0 {
+        return format!("### {reference}\n(not embedded to keep the prompt short; read the file)");
+    }
+    let limit = MAX_FILE_BYTES.min(*budget);
+    let truncated = content.len() > limit;
+    let content = truncate_to_char_boundary(&content, limit);
+    *budget -= content.len();
+    format!(
+        "### {reference}\n```\n{content}\n```{}",
+        if truncated {
+            "\n(truncated; read the file for the rest)"
+        } else {
+            ""
+        }
+    )
+}
+
+async fn render_task(pool: &SqlitePool, task: &Task) -> Result<String, sqlx::Error> {
+    let mut out = format!("### {} ({})", task.title, task.status);
+    if let Some(description) = task.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        out.push_str(&format!("\n{}", description.trim()));
+    }
+    if let Some(summary) = WorkspaceSummary::find_latest_by_task_id(pool, task.id).await? {
+        out.push_str(&format!(
+            "\n\nSummary of the latest attempt:\n{}",
+            summary.summary
+        ));
+    }
+    Ok(out)
+}
+
+/// Expand the references in a prompt: file contents are embedded from the workspace, and linked
+/// tasks are described with the summary of their latest attempt.
+pub async fn resolve_references(
+    pool: &SqlitePool,
+    workspace_root: &Path,
+    repos: &[Repo],
+    prompt: String,
+) -> Result<ResolvedPrompt, sqlx::Error> {
+    let mut resolved = ResolvedPrompt {
+        prompt,
+        missing_files: Vec::new(),
+        missing_tasks: Vec::new(),
+    };
+    let references = parse_references(&resolved.prompt);
+    if references.is_empty() {
+        return Ok(resolved);
+    }
+
+    let mut files = Vec::new();
+    let mut tasks = Vec::new();
+    let mut budget = MAX_TOTAL_FILE_BYTES;
+    for reference in references {
+        match reference {
+            PromptReference::File(reference) => match locate(workspace_root, repos, &reference) {
+                Some(path) => files.push(render_file(&reference, &path, &mut budget)),
+                None => resolved.missing_files.push(reference),
+            },
+            PromptReference::Task(id) => match Task::find_by_id(pool, id).await? {
+                Some(task) => tasks.push(render_task(pool, &task).await?),
+                None => resolved.missing_tasks.push(id),
+            },
+        }
+    }
+
+    if !files.is_empty() {
+        resolved
+            .prompt
+            .push_str(&format!("\n\nReferenced files:\n\n{}", files.join("\n\n")));
+    }
+    if !tasks.is_empty() {
+        resolved
+            .prompt
+            .push_str(&format!("\n\nReferenced tasks:\n\n{}", tasks.join("\n\n")));
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_file_and_task_references() {
+        let id = Uuid::new_v4();
+        let prompt = format!(
+            "Update @src/lib.rs and @README.md, following @task:{id}. Ask @alice, not bob@example.com. See (@docs/guide.md) and @src/lib.rs again."
+        );
+        assert_eq!(
+            parse_references(&prompt),
+            vec![
+                PromptReference::File("src/lib.rs".to_string()),
+                PromptReference::File("README.md".to_string()),
+                PromptReference::Task(id),
+                PromptReference::File("docs/guide.md".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn locate_rejects_paths_outside_the_workspace() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join("app/src")).unwrap();
+        std::fs::write(root.path().join("app/src/main.rs"), "fn main() {}").unwrap();
+        let repos = vec![Repo {
+            id: Uuid::new_v4(),
+            path: root.path().join("app"),
+            name: "app".to_string(),
+            display_name: "app".to_string(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }];
+
+        assert!(locate(root.path(), &repos, "src/main.rs").is_some());
+        assert!(locate(root.path(), &repos, "app/src/main.rs").is_some());
+        assert!(locate(root.path(), &repos, "../etc/passwd").is_none());
+        assert!(locate(root.path(), &repos, "/etc/passwd").is_none());
+    }
+}
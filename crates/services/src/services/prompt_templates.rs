@@ -0,0 +1,149 @@
+use std::sync::LazyLock;
+
+use db::models::workspace_repo::RepoWithTargetBranch;
+use regex::Regex;
+use serde::Serialize;
+use ts_rs::TS;
+
+/// `{{name}}`, with optional spaces inside the braces.
+static VARIABLE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*([a-z_]+)\s*\}\}").expect("valid variable regex"));
+
+/// Placeholders a template can use, with what they are replaced by.
+pub const VARIABLES: &[(&str, &str)] = &[
+    ("task_title", "Title of the attempt's task"),
+    ("task_description", "Description of the attempt's task"),
+    ("project_name", "Name of the task's project"),
+    ("branch", "Branch the attempt works on"),
+    (
+        "base_branch",
+        "Branch the attempt merges into, per repository when there are several",
+    ),
+    (
+        "code_map",
+        "Overview of the repositories' files and symbols",
+    ),
+];
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct PromptTemplateVariable {
+    pub name: String,
+    pub description: String,
+}
+
+/// The variables as listed to users writing templates.
+pub fn variables() -> Vec<PromptTemplateVariable> {
+    VARIABLES
+        .iter()
+        .map(|(name, description)| PromptTemplateVariable {
+            name: name.to_string(),
+            description: description.to_string(),
+        })
+        .collect()
+}
+
+/// Values of the variables for one attempt.
+#[derive(Debug, Clone, Default)]
+pub struct PromptVariables {
+    pub task_title: String,
+    pub task_description: String,
+    pub project_name: String,
+    pub branch: String,
+    pub base_branch: String,
+    pub code_map: String,
+}
+
+impl PromptVariables {
+    fn get(&self, name: &str) -> Option<&str> {
+        match name {
+            "task_title" => Some(&self.task_title),
+            "task_description" => Some(&self.task_description),
+            "project_name" => Some(&self.project_name),
+            "branch" => Some(&self.branch),
+            "base_branch" => Some(&self.base_branch),
+            "code_map" => Some(&self.code_map),
+            _ => None,
+        }
+    }
+}
+
+/// The single target branch, or `repo: branch` pairs when the attempt spans several repos.
+pub fn base_branch(repos: &[RepoWithTargetBranch]) -> String {
+    match repos {
+        [only] => only.target_branch.clone(),
+        repos => repos
+            .iter()
+            .map(|repo| format!("{}: {}", repo.repo.name, repo.target_branch))
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+/// Whether the template uses the variable, to skip computing values nobody asked for.
+pub fn uses_variable(content: &str, name: &str) -> bool {
+    VARIABLE_RE
+        .captures_iter(content)
+        .any(|caps| &caps[1] == name)
+}
+
+/// Fill in the template's variables. Unknown names are left as written.
+pub fn render(content: &str, variables: &PromptVariables) -> String {
+    VARIABLE_RE
+        .replace_all(content, |caps: &regex::Captures| {
+            variables
+                .get(&caps[1])
+                .map(str::to_string)
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .trim()
+        .to_string()
+}
+
+/// The prompt followed by each non-empty rendered template.
+pub fn append_rendered(prompt: String, rendered: &[String]) -> String {
+    rendered
+        .iter()
+        .filter(|section| !section.is_empty())
+        .fold(prompt, |prompt, section| format!("{prompt}\n\n{section}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt_variables() -> PromptVariables {
+        PromptVariables {
+            task_title: "Fix login".to_string(),
+            base_branch: "main".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn render_fills_known_variables_and_keeps_unknown() {
+        let rendered = render(
+            "Work on {{task_title}} against {{ base_branch }}. Ask {{ reviewer }}.\n",
+            &attempt_variables(),
+        );
+        assert_eq!(
+            rendered,
+            "Work on Fix login against main. Ask {{ reviewer }}."
+        );
+    }
+
+    #[test]
+    fn uses_variable_matches_whole_names() {
+        assert!(uses_variable("Map:\n{{ code_map }}", "code_map"));
+        assert!(!uses_variable("{{code_map_extra}}", "code_map"));
+        assert!(!uses_variable("code_map", "code_map"));
+    }
+
+    #[test]
+    fn append_rendered_skips_empty_sections() {
+        let prompt = append_rendered(
+            "Fix login".to_string(),
+            &["Run the tests first.".to_string(), String::new()],
+        );
+        assert_eq!(prompt, "Fix login\n\nRun the tests first.");
+    }
+}
@@ -0,0 +1,245 @@
+use chrono::{DateTime, Utc};
+use db::models::{
+    public_share_link::PublicShareLink,
+    review_checklist::{ChecklistItemState, ReviewChecklistItem},
+    task::{Task, TaskStatus},
+    workspace::Workspace,
+    workspace_summary::WorkspaceSummary,
+};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs::TS;
+use utils::diff::Diff;
+use uuid::Uuid;
+
+use crate::services::secret_cipher::SecretCipher;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PublicShareError {
+    #[error("Invalid share link")]
+    Invalid,
+    #[error("Share link has expired")]
+    Expired,
+}
+
+fn signed_part(link_id: Uuid, expires_at: i64) -> String {
+    format!("{}.{expires_at}", link_id.simple())
+}
+
+/// Token of a share link: its id and expiry, signed with the local signing key.
+pub fn link_token(cipher: &SecretCipher, link: &PublicShareLink) -> String {
+    let signed = signed_part(link.id, link.expires_at.timestamp());
+    format!("{signed}.{}", cipher.sign(signed.as_bytes()))
+}
+
+/// The link id of a token that is correctly signed and not yet expired.
+pub fn verify_link_token(
+    cipher: &SecretCipher,
+    token: &str,
+    now: DateTime<Utc>,
+) -> Result<Uuid, PublicShareError> {
+    let mut parts = token.splitn(3, '.');
+    let (Some(id), Some(expires_at), Some(signature)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(PublicShareError::Invalid);
+    };
+    let link_id = Uuid::try_parse(id).map_err(|_| PublicShareError::Invalid)?;
+    let expires_at: i64 = expires_at.parse().map_err(|_| PublicShareError::Invalid)?;
+    if !cipher.verify(signed_part(link_id, expires_at).as_bytes(), signature) {
+        return Err(PublicShareError::Invalid);
+    }
+    if now.timestamp() >= expires_at {
+        return Err(PublicShareError::Expired);
+    }
+    Ok(link_id)
+}
+
+/// Keep the diffs of the chosen files, matched on their new or old path.
+pub fn select_diffs(diffs: Vec<Diff>, paths: &[String]) -> Vec<Diff> {
+    diffs
+        .into_iter()
+        .filter(|diff| {
+            [&diff.new_path, &diff.old_path]
+                .into_iter()
+                .flatten()
+                .any(|path| paths.contains(path))
+        })
+        .collect()
+}
+
+/// What a share link shows.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct PublicTaskView {
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    pub attempt: Option<PublicAttemptView>,
+    #[ts(type = "Date")]
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct PublicAttemptView {
+    pub branch: String,
+    pub summary: Option<String>,
+    /// Unified diff of the files chosen when the link was created
+    pub diff: Option<String>,
+    pub checklist: Vec<ChecklistItemState>,
+}
+
+/// The current task and attempt summary, with the diff captured when the link was created.
+pub async fn load_view(
+    pool: &SqlitePool,
+    link: &PublicShareLink,
+) -> Result<Option<PublicTaskView>, sqlx::Error> {
+    let Some(task) = Task::find_by_id(pool, link.task_id).await? else {
+        return Ok(None);
+    };
+    let workspace = match link.workspace_id {
+        Some(workspace_id) => Workspace::find_by_id(pool, workspace_id).await?,
+        None => None,
+    };
+    let attempt = match workspace {
+        Some(workspace) => Some(PublicAttemptView {
+            summary: WorkspaceSummary::find_by_workspace_id(pool, workspace.id)
+                .await?
+                .map(|s| s.summary),
+            checklist: ReviewChecklistItem::find_states_for_workspace(pool, workspace.id).await?,
+            branch: workspace.branch,
+            diff: link.diff.clone(),
+        }),
+        None => None,
+    };
+    Ok(Some(PublicTaskView {
+        title: task.title,
+        description: task.description,
+        status: task.status,
+        attempt,
+        expires_at: link.expires_at,
+    }))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Standalone read-only page for people opening the link in a browser.
+pub fn render_page(view: &PublicTaskView) -> String {
+    let mut body = format!(
+        "<h1>{}</h1>\n<p class=\"status\">Status: {}</p>\n",
+        escape_html(&view.title),
+        escape_html(&view.status.to_string())
+    );
+    if let Some(description) = &view.description {
+        body.push_str(&format!("<pre>{}</pre>\n", escape_html(description)));
+    }
+    if let Some(attempt) = &view.attempt {
+        body.push_str(&format!(
+            "<h2>Attempt <code>{}</code></h2>\n",
+            escape_html(&attempt.branch)
+        ));
+        if let Some(summary) = &attempt.summary {
+            body.push_str(&format!("<pre>{}</pre>\n", escape_html(summary)));
+        }
+        if !attempt.checklist.is_empty() {
+            body.push_str("<h2>Review checklist</h2>\n<ul class=\"checklist\">\n");
+            for item in &attempt.checklist {
+                body.push_str(&format!(
+                    "<li>{} {}</li>\n",
+                    if item.checked() { "&#9745;" } else { "&#9744;" },
+                    escape_html(&item.label)
+                ));
+            }
+            body.push_str("</ul>\n");
+        }
+        if let Some(diff) = &attempt.diff {
+            body.push_str(&format!(
+                "<h2>Changes</h2>\n<pre class=\"diff\">{}</pre>\n",
+                escape_html(diff)
+            ));
+        }
+    }
+    body.push_str(&format!(
+        "<p class=\"expiry\">This link expires {}.</p>\n",
+        view.expires_at.format("%Y-%m-%d %H:%M UTC")
+    ));
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"robots\" content=\"noindex\">\n<title>{}</title>\n<style>\
+         body{{font-family:system-ui,sans-serif;max-width:960px;margin:2rem auto;padding:0 1rem}}\
+         pre{{white-space:pre-wrap;background:#f6f8fa;padding:1rem;border-radius:6px}}\
+         .status,.expiry{{color:#57606a}}</style>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape_html(&view.title)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use sqlx::types::Json;
+    use utils::diff::DiffChangeKind;
+
+    use super::*;
+
+    fn link(expires_at: DateTime<Utc>) -> PublicShareLink {
+        PublicShareLink {
+            id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            workspace_id: None,
+            diff_paths: Json(Vec::new()),
+            diff: None,
+            expires_at,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn tokens_are_signed_and_expire() {
+        let cipher = SecretCipher::from_key_bytes(&[7u8; 32]).unwrap();
+        let link = link(Utc::now() + Duration::hours(1));
+        let token = link_token(&cipher, &link);
+
+        assert_eq!(verify_link_token(&cipher, &token, Utc::now()), Ok(link.id));
+        assert_eq!(
+            verify_link_token(&cipher, &token, Utc::now() + Duration::hours(2)),
+            Err(PublicShareError::Expired)
+        );
+        let forged = token.replacen('.', ".9", 1);
+        assert_eq!(
+            verify_link_token(&cipher, &forged, Utc::now()),
+            Err(PublicShareError::Invalid)
+        );
+    }
+
+    #[test]
+    fn selects_diffs_by_path_and_escapes_page() {
+        let diff = |path: &str| Diff {
+            change: DiffChangeKind::Modified,
+            old_path: Some(path.to_string()),
+            new_path: Some(path.to_string()),
+            old_content: None,
+            new_content: None,
+            content_omitted: true,
+            additions: None,
+            deletions: None,
+        };
+        let selected = select_diffs(vec![diff("a.rs"), diff("b.rs")], &["b.rs".to_string()]);
+        assert_eq!(selected.len(), 1);
+
+        let page = render_page(&PublicTaskView {
+            title: "<script>".to_string(),
+            description: None,
+            status: TaskStatus::Todo,
+            attempt: None,
+            expires_at: Utc::now(),
+        });
+        assert!(page.contains("&lt;script&gt;"));
+        assert!(!page.contains("<script>"));
+    }
+}
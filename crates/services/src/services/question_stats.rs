@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use db::models::agent_question::{AgentQuestion, AgentQuestionOutcome};
+use executors::profile::ExecutorProfileId;
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::services::executor_stats::median;
+
+/// How often agents stopped to ask questions and how quickly they got answers.
+#[derive(Debug, Clone, Default, Serialize, TS)]
+pub struct QuestionStats {
+    /// Question requests; an agent may ask several questions in one request
+    pub requests: usize,
+    pub questions: usize,
+    pub answered: usize,
+    pub timed_out: usize,
+    /// Answered by a project's auto-answer rules without interrupting anyone
+    pub auto_answered: usize,
+    /// Still waiting, or abandoned when the agent stopped
+    pub pending: usize,
+    /// Share of resolved requests that timed out, from 0 to 1
+    pub timeout_rate: Option<f64>,
+    /// Median time from the request to a reviewer's answer
+    pub median_time_to_answer_seconds: Option<f64>,
+    /// Most interrupting executor first
+    pub by_executor: Vec<ExecutorQuestionStats>,
+}
+
+/// Question statistics of one executor profile.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ExecutorQuestionStats {
+    pub executor_profile_id: ExecutorProfileId,
+    pub requests: usize,
+    pub questions: usize,
+    pub timed_out: usize,
+    pub auto_answered: usize,
+    pub median_time_to_answer_seconds: Option<f64>,
+}
+
+fn seconds_to_answer(question: &AgentQuestion) -> Option<f64> {
+    if question.outcome != AgentQuestionOutcome::Answered {
+        return None;
+    }
+    let ms = (question.resolved_at? - question.requested_at).num_milliseconds();
+    Some(ms.max(0) as f64 / 1000.0)
+}
+
+fn summarize<'a>(questions: impl Iterator<Item = &'a AgentQuestion> + Clone) -> QuestionStats {
+    let count = |outcome| questions.clone().filter(|q| q.outcome == outcome).count();
+    let answered = count(AgentQuestionOutcome::Answered);
+    let timed_out = count(AgentQuestionOutcome::TimedOut);
+    let auto_answered = count(AgentQuestionOutcome::AutoAnswered);
+    let resolved = answered + timed_out + auto_answered;
+    QuestionStats {
+        requests: questions.clone().count(),
+        questions: questions
+            .clone()
+            .map(|q| q.question_count.max(0) as usize)
+            .sum(),
+        answered,
+        timed_out,
+        auto_answered,
+        pending: count(AgentQuestionOutcome::Pending),
+        timeout_rate: (resolved > 0).then(|| timed_out as f64 / resolved as f64),
+        median_time_to_answer_seconds: median(questions.filter_map(seconds_to_answer).collect()),
+        by_executor: Vec::new(),
+    }
+}
+
+/// Aggregate question requests overall and per executor profile. Requests whose execution
+/// process isn't a coding agent run only count towards the totals.
+pub fn aggregate(questions: &[AgentQuestion]) -> QuestionStats {
+    let mut by_profile: HashMap<&ExecutorProfileId, Vec<&AgentQuestion>> = HashMap::new();
+    for question in questions {
+        if let Some(profile) = question.executor_action.0.executor_profile_id() {
+            by_profile.entry(profile).or_default().push(question);
+        }
+    }
+
+    let mut by_executor: Vec<ExecutorQuestionStats> = by_profile
+        .into_iter()
+        .map(|(profile, questions)| {
+            let stats = summarize(questions.iter().copied());
+            ExecutorQuestionStats {
+                executor_profile_id: profile.clone(),
+                requests: stats.requests,
+                questions: stats.questions,
+                timed_out: stats.timed_out,
+                auto_answered: stats.auto_answered,
+                median_time_to_answer_seconds: stats.median_time_to_answer_seconds,
+            }
+        })
+        .collect();
+    by_executor.sort_by(|a, b| {
+        b.requests.cmp(&a.requests).then_with(|| {
+            a.executor_profile_id
+                .to_string()
+                .cmp(&b.executor_profile_id.to_string())
+        })
+    });
+
+    QuestionStats {
+        by_executor,
+        ..summarize(questions.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use db::models::execution_process::ExecutorActionField;
+    use executors::{
+        actions::{
+            ExecutorAction, ExecutorActionType, coding_agent_initial::CodingAgentInitialRequest,
+        },
+        executors::BaseCodingAgent,
+    };
+
+    use super::*;
+
+    fn question(
+        executor: BaseCodingAgent,
+        outcome: AgentQuestionOutcome,
+        seconds: i64,
+    ) -> AgentQuestion {
+        let requested_at = Utc::now();
+        let action = ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt: "Fix it".to_string(),
+                executor_profile_id: ExecutorProfileId::new(executor),
+                working_dir: None,
+                model: None,
+            }),
+            None,
+        );
+        AgentQuestion {
+            executor_action: sqlx::types::Json(ExecutorActionField::ExecutorAction(action)),
+            question_count: 2,
+            outcome,
+            requested_at,
+            resolved_at: (outcome != AgentQuestionOutcome::Pending)
+                .then(|| requested_at + Duration::seconds(seconds)),
+        }
+    }
+
+    #[test]
+    fn aggregates_outcomes_and_answer_times() {
+        let questions = vec![
+            question(
+                BaseCodingAgent::ClaudeCode,
+                AgentQuestionOutcome::Answered,
+                10,
+            ),
+            question(
+                BaseCodingAgent::ClaudeCode,
+                AgentQuestionOutcome::Answered,
+                30,
+            ),
+            question(
+                BaseCodingAgent::ClaudeCode,
+                AgentQuestionOutcome::TimedOut,
+                3600,
+            ),
+            question(
+                BaseCodingAgent::Codex,
+                AgentQuestionOutcome::AutoAnswered,
+                0,
+            ),
+            question(BaseCodingAgent::Codex, AgentQuestionOutcome::Pending, 0),
+        ];
+
+        let stats = aggregate(&questions);
+        assert_eq!(stats.requests, 5);
+        assert_eq!(stats.questions, 10);
+        assert_eq!(stats.answered, 2);
+        assert_eq!(stats.pending, 1);
+        assert_eq!(stats.timeout_rate, Some(0.25));
+        assert_eq!(stats.median_time_to_answer_seconds, Some(20.0));
+
+        assert_eq!(stats.by_executor.len(), 2);
+        let claude = &stats.by_executor[0];
+        assert_eq!(
+            claude.executor_profile_id.executor,
+            BaseCodingAgent::ClaudeCode
+        );
+        assert_eq!(claude.requests, 3);
+        assert_eq!(claude.timed_out, 1);
+        assert_eq!(stats.by_executor[1].auto_answered, 1);
+    }
+}
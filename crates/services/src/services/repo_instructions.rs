@@ -0,0 +1,121 @@
+//! Repositories can carry standing instructions for coding agents in `.vibe/instructions.md`,
+//! or in an `AGENTS.md` at their root. When the project has the feature on, the file of each
+//! repository is appended to the prompt every attempt starts with.
+
+use std::path::Path;
+
+use serde::Serialize;
+use ts_rs::TS;
+use utils::text::truncate_to_char_boundary;
+
+/// Looked up in order; the first non-empty file of a repository is used.
+pub const INSTRUCTION_FILES: [&str; 2] = [".vibe/instructions.md", "AGENTS.md"];
+/// Budget per instructions file, in bytes
+const MAX_INSTRUCTIONS_BYTES: usize = 16_000;
+
+/// Instructions found in one repository.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct RepoInstructions {
+    pub repo_name: String,
+    /// Path of the file relative to the repository root
+    pub file: String,
+    pub content: String,
+}
+
+/// Instructions of the repository checked out at `repo_dir`, if it has any.
+pub async fn read(repo_name: &str, repo_dir: &Path) -> Option<RepoInstructions> {
+    for file in INSTRUCTION_FILES {
+        let Ok(content) = tokio::fs::read_to_string(repo_dir.join(file)).await else {
+            continue;
+        };
+        let content = content.trim();
+        if content.is_empty() {
+            continue;
+        }
+        let content = if content.len() > MAX_INSTRUCTIONS_BYTES {
+            format!(
+                "{}\n[instructions truncated]",
+                truncate_to_char_boundary(content, MAX_INSTRUCTIONS_BYTES)
+            )
+        } else {
+            content.to_string()
+        };
+        return Some(RepoInstructions {
+            repo_name: repo_name.to_string(),
+            file: file.to_string(),
+            content,
+        });
+    }
+    None
+}
+
+/// Section appended to the prompt, one block per repository. `None` when there are none.
+pub fn render(instructions: &[RepoInstructions]) -> Option<String> {
+    if instructions.is_empty() {
+        return None;
+    }
+    let blocks = instructions
+        .iter()
+        .map(|found| {
+            format!(
+                "From `{}/{}`:\n{}",
+                found.repo_name, found.file, found.content
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Some(format!(
+        "Project instructions, follow them throughout the task:\n\n{blocks}"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn read_prefers_vibe_instructions_over_agents_md() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("AGENTS.md"), "Use tabs.").unwrap();
+        assert_eq!(read("api", dir.path()).await.unwrap().file, "AGENTS.md");
+
+        std::fs::create_dir(dir.path().join(".vibe")).unwrap();
+        std::fs::write(dir.path().join(".vibe/instructions.md"), "  \n").unwrap();
+        assert_eq!(read("api", dir.path()).await.unwrap().file, "AGENTS.md");
+
+        std::fs::write(
+            dir.path().join(".vibe/instructions.md"),
+            "Run make check.\n",
+        )
+        .unwrap();
+        let found = read("api", dir.path()).await.unwrap();
+        assert_eq!(found.file, ".vibe/instructions.md");
+        assert_eq!(found.content, "Run make check.");
+    }
+
+    #[tokio::test]
+    async fn read_without_instructions_is_none() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read("api", dir.path()).await.is_none());
+        assert!(render(&[]).is_none());
+    }
+
+    #[test]
+    fn render_labels_each_repository() {
+        let rendered = render(&[
+            RepoInstructions {
+                repo_name: "api".to_string(),
+                file: "AGENTS.md".to_string(),
+                content: "Use tabs.".to_string(),
+            },
+            RepoInstructions {
+                repo_name: "web".to_string(),
+                file: ".vibe/instructions.md".to_string(),
+                content: "Run pnpm lint.".to_string(),
+            },
+        ])
+        .unwrap();
+        assert!(rendered.contains("From `api/AGENTS.md`:\nUse tabs."));
+        assert!(rendered.ends_with("From `web/.vibe/instructions.md`:\nRun pnpm lint."));
+    }
+}
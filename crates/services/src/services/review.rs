@@ -0,0 +1,139 @@
+use db::models::{
+    review::{CreateReviewFinding, ReviewSeverity},
+    task::Task,
+};
+use executors::logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch};
+use serde::Deserialize;
+use utils::{log_msg::LogMsg, text::truncate_to_char_boundary};
+
+use crate::services::llm::parse_json_reply;
+
+/// Budget for the diff included in the review prompt, in bytes
+const MAX_REVIEW_DIFF_BYTES: usize = 48_000;
+/// Upper bound on findings kept from a review
+const MAX_FINDINGS: usize = 50;
+
+#[derive(Debug, Deserialize)]
+struct ReviewReply {
+    #[serde(default)]
+    findings: Vec<RawFinding>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawFinding {
+    #[serde(default, alias = "file_path", alias = "path")]
+    file: Option<String>,
+    #[serde(default)]
+    line: Option<i64>,
+    #[serde(default)]
+    severity: Option<String>,
+    #[serde(default)]
+    comment: String,
+}
+
+/// Instructions for the reviewing agent, including the attempt's diff.
+pub fn review_prompt(task: &Task, diff: &str) -> String {
+    let diff = match diff.trim() {
+        "" => "(no changes)".to_string(),
+        d if d.len() > MAX_REVIEW_DIFF_BYTES => format!(
+            "{}\n[diff truncated; inspect the repository for the rest]",
+            truncate_to_char_boundary(d, MAX_REVIEW_DIFF_BYTES)
+        ),
+        d => d.to_string(),
+    };
+    format!(
+        r#"Review the changes another coding agent made for the task below. Do not modify any files.
+
+Task:
+{task}
+
+Changes against the target branch:
+{diff}
+
+Inspect the repository as needed. Finish your reply with a JSON object in this form, with nothing after it:
+{{"findings": [{{"file": "path as shown in the diff", "line": 42, "severity": "blocking", "comment": "what is wrong and how to fix it"}}]}}
+
+Severity is "blocking" for problems that must be fixed before merging, such as bugs, security issues or unmet requirements, "warning" for likely problems and "suggestion" for optional improvements. Leave out "file" and "line" for comments about the change as a whole. Reply with an empty findings list if the changes look good."#,
+        task = task.to_prompt()
+    )
+}
+
+/// The latest assistant message in an execution's normalized log.
+pub fn last_assistant_message(history: &[LogMsg]) -> Option<String> {
+    history.iter().rev().find_map(|msg| match msg {
+        LogMsg::JsonPatch(patch) => extract_normalized_entry_from_patch(patch)
+            .map(|(_, entry)| entry)
+            .filter(|entry| matches!(entry.entry_type, NormalizedEntryType::AssistantMessage))
+            .map(|entry| entry.content.trim().to_string())
+            .filter(|content| !content.is_empty()),
+        _ => None,
+    })
+}
+
+/// Parse the findings object at the end of the reviewer's reply. Unknown severities are kept as
+/// warnings.
+pub fn parse_findings(reply: &str) -> Result<Vec<CreateReviewFinding>, String> {
+    // Prose before the object may contain braces, so start from the one opening it
+    let json = reply
+        .rfind("\"findings\"")
+        .and_then(|key| reply[..key].rfind('{'))
+        .map_or(reply, |start| &reply[start..]);
+    let parsed: ReviewReply = parse_json_reply(json).map_err(|e| e.to_string())?;
+
+    Ok(parsed
+        .findings
+        .into_iter()
+        .filter(|f| !f.comment.trim().is_empty())
+        .take(MAX_FINDINGS)
+        .map(|f| CreateReviewFinding {
+            file_path: f
+                .file
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty()),
+            line: f.line.filter(|line| *line > 0),
+            severity: match f.severity.unwrap_or_default().to_lowercase().as_str() {
+                "blocking" | "blocker" | "critical" | "error" => ReviewSeverity::Blocking,
+                "suggestion" | "nit" | "info" => ReviewSeverity::Suggestion,
+                _ => ReviewSeverity::Warning,
+            },
+            comment: f.comment.trim().to_string(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_findings_after_prose() {
+        let reply = r#"I checked `fn main() { .. }` and the tests.
+
+{"findings": [
+  {"file": "src/lib.rs", "line": 12, "severity": "Blocking", "comment": "Unwrap on user input panics"},
+  {"file": "", "line": 0, "severity": "nit", "comment": "Typo in docs"},
+  {"severity": "blocking", "comment": "  "}
+]}"#;
+        let findings = parse_findings(reply).unwrap();
+        assert_eq!(
+            findings,
+            vec![
+                CreateReviewFinding {
+                    file_path: Some("src/lib.rs".to_string()),
+                    line: Some(12),
+                    severity: ReviewSeverity::Blocking,
+                    comment: "Unwrap on user input panics".to_string(),
+                },
+                CreateReviewFinding {
+                    file_path: None,
+                    line: None,
+                    severity: ReviewSeverity::Suggestion,
+                    comment: "Typo in docs".to_string(),
+                },
+            ]
+        );
+
+        assert!(parse_findings(r#"{"findings": []}"#).unwrap().is_empty());
+        assert!(parse_findings("Looks good to me").is_err());
+    }
+}
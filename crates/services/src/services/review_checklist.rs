@@ -0,0 +1,62 @@
+use db::models::review_checklist::ChecklistItemState;
+
+/// The attempt's checklist as a Markdown task list for PR bodies. `None` without a checklist.
+pub fn render_markdown(items: &[ChecklistItemState]) -> Option<String> {
+    if items.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = items
+        .iter()
+        .map(|item| {
+            format!(
+                "- [{}] {}",
+                if item.checked() { "x" } else { " " },
+                item.label.trim()
+            )
+        })
+        .collect();
+    Some(format!("## Review checklist\n\n{}", lines.join("\n")))
+}
+
+/// The PR body with the checklist appended, if there is one
+pub fn append_to_body(body: Option<String>, items: &[ChecklistItemState]) -> Option<String> {
+    match (body, render_markdown(items)) {
+        (Some(body), Some(checklist)) => Some(format!("{}\n\n{checklist}", body.trim_end())),
+        (body, checklist) => body.or(checklist),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn item(label: &str, checked: bool) -> ChecklistItemState {
+        ChecklistItemState {
+            item_id: Uuid::new_v4(),
+            label: label.to_string(),
+            required: true,
+            checked_at: checked.then(Utc::now),
+        }
+    }
+
+    #[test]
+    fn renders_a_task_list_after_the_body() {
+        let items = vec![item("Tests added", true), item("Docs updated", false)];
+        assert_eq!(
+            append_to_body(Some("Fixes the login bug\n".to_string()), &items).unwrap(),
+            "Fixes the login bug\n\n## Review checklist\n\n- [x] Tests added\n- [ ] Docs updated"
+        );
+        assert_eq!(
+            append_to_body(Some("Body".to_string()), &[]).as_deref(),
+            Some("Body")
+        );
+        assert!(
+            append_to_body(None, &items)
+                .unwrap()
+                .starts_with("## Review checklist")
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use db::{
+    DBService,
+    models::{
+        execution_process::ExecutionProcess,
+        notification::{CreateNotification, NotificationKind},
+        review_sla_reminder::ReviewSlaReminder,
+        task_status_change::InReviewTask,
+    },
+};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tokio::{sync::RwLock, time::interval};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::services::{
+    approvals::Approvals,
+    config::{Config, ReviewSlaConfig},
+    notification::NotificationService,
+    user_questions::UserQuestions,
+};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewWaitingOn {
+    /// An agent asked a question
+    Answer,
+    /// An agent asked to run a tool
+    Approval,
+    /// The agent finished and the changes await review and merge
+    Merge,
+}
+
+impl ReviewWaitingOn {
+    fn describe(self) -> &'static str {
+        match self {
+            ReviewWaitingOn::Answer => "an answer",
+            ReviewWaitingOn::Approval => "an approval",
+            ReviewWaitingOn::Merge => "review and merge",
+        }
+    }
+}
+
+/// A task that has waited in review longer than the SLA.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ReviewSlaBreach {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub task_title: String,
+    #[ts(type = "Date")]
+    pub in_review_since: DateTime<Utc>,
+    #[ts(type = "number")]
+    pub waiting_seconds: i64,
+    pub waiting_on: ReviewWaitingOn,
+    /// Number of thresholds crossed, starting at 1 for the SLA itself
+    pub level: usize,
+    /// Highest threshold crossed
+    pub threshold_hours: u32,
+}
+
+/// Thresholds in ascending order, ignoring zeros and duplicates.
+fn thresholds(config: &ReviewSlaConfig) -> Vec<u32> {
+    let mut thresholds: Vec<u32> = config
+        .thresholds_hours
+        .iter()
+        .copied()
+        .filter(|h| *h > 0)
+        .collect();
+    thresholds.sort_unstable();
+    thresholds.dedup();
+    thresholds
+}
+
+/// The highest threshold crossed after waiting `waiting_seconds`, as `(level, hours)`.
+fn crossed_level(thresholds: &[u32], waiting_seconds: i64) -> Option<(usize, u32)> {
+    thresholds
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, hours)| waiting_seconds >= **hours as i64 * 3600)
+        .map(|(index, hours)| (index + 1, *hours))
+}
+
+/// Tasks of the given execution processes.
+async fn task_ids(pool: &SqlitePool, execution_process_ids: Vec<Uuid>) -> HashSet<Uuid> {
+    let mut task_ids = HashSet::new();
+    for id in execution_process_ids {
+        if let Ok(ctx) = ExecutionProcess::load_context(pool, id).await {
+            task_ids.insert(ctx.task.id);
+        }
+    }
+    task_ids
+}
+
+/// Tasks in review past the first threshold, longest waiting first.
+pub async fn find_breaches(
+    pool: &SqlitePool,
+    approvals: &Approvals,
+    questions: &UserQuestions,
+    config: &ReviewSlaConfig,
+    project_id: Option<Uuid>,
+) -> Result<Vec<ReviewSlaBreach>, sqlx::Error> {
+    let thresholds = thresholds(config);
+    if thresholds.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let now = Utc::now();
+    let mut breaches = Vec::new();
+    let mut pending_answers = None;
+    let mut pending_approvals = None;
+    for task in InReviewTask::find_all(pool, project_id).await? {
+        let waiting_seconds = (now - task.in_review_since).num_seconds();
+        let Some((level, threshold_hours)) = crossed_level(&thresholds, waiting_seconds) else {
+            continue;
+        };
+
+        // Pending requests only matter once something breached, so look them up lazily
+        if pending_answers.is_none() {
+            let ids = questions
+                .list_pending()
+                .into_iter()
+                .map(|q| q.execution_process_id)
+                .collect();
+            pending_answers = Some(task_ids(pool, ids).await);
+            let ids = approvals
+                .list_pending()
+                .into_iter()
+                .map(|a| a.execution_process_id)
+                .collect();
+            pending_approvals = Some(task_ids(pool, ids).await);
+        }
+        let waiting_on = if pending_answers
+            .as_ref()
+            .is_some_and(|ids| ids.contains(&task.task_id))
+        {
+            ReviewWaitingOn::Answer
+        } else if pending_approvals
+            .as_ref()
+            .is_some_and(|ids| ids.contains(&task.task_id))
+        {
+            ReviewWaitingOn::Approval
+        } else {
+            ReviewWaitingOn::Merge
+        };
+
+        breaches.push(ReviewSlaBreach {
+            task_id: task.task_id,
+            project_id: task.project_id,
+            task_title: task.title,
+            in_review_since: task.in_review_since,
+            waiting_seconds,
+            waiting_on,
+            level,
+            threshold_hours,
+        });
+    }
+    Ok(breaches)
+}
+
+fn reminder(breach: &ReviewSlaBreach) -> CreateNotification {
+    let title = if breach.level == 1 {
+        format!("Review overdue: {}", breach.task_title)
+    } else {
+        format!("Still waiting for review: {}", breach.task_title)
+    };
+    CreateNotification {
+        kind: NotificationKind::ReviewSlaBreached,
+        title,
+        message: format!(
+            "'{}' has waited in review for more than {} hours for {}",
+            breach.task_title,
+            breach.threshold_hours,
+            breach.waiting_on.describe()
+        ),
+        project_id: Some(breach.project_id),
+        task_id: Some(breach.task_id),
+    }
+}
+
+/// Sends a reminder each time a task in review crosses another threshold.
+pub struct ReviewSlaMonitor {
+    db: DBService,
+    config: Arc<RwLock<Config>>,
+    notifications: NotificationService,
+    approvals: Approvals,
+    questions: UserQuestions,
+}
+
+impl ReviewSlaMonitor {
+    pub async fn spawn(
+        db: DBService,
+        config: Arc<RwLock<Config>>,
+        notifications: NotificationService,
+        approvals: Approvals,
+        questions: UserQuestions,
+    ) -> tokio::task::JoinHandle<()> {
+        let monitor = Self {
+            db,
+            config,
+            notifications,
+            approvals,
+            questions,
+        };
+        tokio::spawn(async move {
+            let mut interval = interval(CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = monitor.check().await {
+                    tracing::error!("Failed to check review SLAs: {}", e);
+                }
+            }
+        })
+    }
+
+    async fn check(&self) -> Result<(), sqlx::Error> {
+        let config = self.config.read().await.review_sla.clone();
+        if !config.reminders_enabled {
+            return Ok(());
+        }
+
+        let pool = &self.db.pool;
+        for breach in find_breaches(pool, &self.approvals, &self.questions, &config, None).await? {
+            // Only the most urgent reminder is sent when several thresholds passed at once
+            if ReviewSlaReminder::record(
+                pool,
+                breach.task_id,
+                breach.in_review_since,
+                breach.level as i64,
+            )
+            .await?
+            {
+                self.notifications.notify(reminder(&breach)).await;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crossed_level_picks_highest_threshold() {
+        let config = ReviewSlaConfig {
+            reminders_enabled: true,
+            thresholds_hours: vec![72, 24, 0, 24],
+        };
+        let thresholds = thresholds(&config);
+        assert_eq!(thresholds, vec![24, 72]);
+
+        assert_eq!(crossed_level(&thresholds, 23 * 3600), None);
+        assert_eq!(crossed_level(&thresholds, 24 * 3600), Some((1, 24)));
+        assert_eq!(crossed_level(&thresholds, 100 * 3600), Some((2, 72)));
+    }
+}
@@ -0,0 +1,160 @@
+//! Screenshots of dev servers taken with a headless Chromium, so reviewers can see UI changes
+//! without starting the attempt themselves.
+
+use std::{process::Stdio, sync::LazyLock, time::Duration};
+
+use regex::Regex;
+use thiserror::Error;
+use tokio::process::Command;
+use utils::{log_msg::LogMsg, shell::resolve_executable_path};
+use uuid::Uuid;
+
+/// Browsers tried in order; all accept Chromium's headless screenshot flags
+const BROWSERS: [&str; 5] = [
+    "chromium",
+    "chromium-browser",
+    "google-chrome",
+    "google-chrome-stable",
+    "microsoft-edge",
+];
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(30);
+const WINDOW_SIZE: &str = "1280,800";
+/// Time the page gets to render its client side before the capture
+const VIRTUAL_TIME_BUDGET_MS: u32 = 5000;
+
+/// Matches local URLs printed by common dev servers (vite, next, webpack, ...)
+static LOCAL_URL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"https?://(?:localhost|127\.0\.0\.1|0\.0\.0\.0|\[::1?\]):\d{2,5}")
+        .expect("valid regex")
+});
+
+#[derive(Debug, Error)]
+pub enum ScreenshotError {
+    #[error("No Chromium based browser found in PATH (tried {})", BROWSERS.join(", "))]
+    BrowserUnavailable,
+    #[error("Failed to run the browser: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Browser did not finish the screenshot within {0:?}")]
+    Timeout(Duration),
+    #[error("Browser failed to take the screenshot: {0}")]
+    Failed(String),
+}
+
+/// Find the last local URL a dev server printed to its output.
+pub fn detect_local_url(history: &[LogMsg]) -> Option<String> {
+    history.iter().rev().find_map(|msg| match msg {
+        LogMsg::Stdout(content) | LogMsg::Stderr(content) => {
+            let stripped = strip_ansi_escapes::strip_str(content);
+            LOCAL_URL_RE
+                .find_iter(&stripped)
+                .last()
+                .map(|m| m.as_str().replace("0.0.0.0", "localhost"))
+        }
+        _ => None,
+    })
+}
+
+/// Path the server serves a stored image at.
+pub fn image_path(image_id: Uuid) -> String {
+    format!("/api/images/{image_id}/file")
+}
+
+/// Add the screenshot to a PR body, linked from the server's base URL.
+pub fn append_to_body(body: Option<String>, base_url: &str, image_id: Uuid) -> Option<String> {
+    let section = format!(
+        "## Screenshot\n\n![Dev server screenshot]({}{})",
+        base_url.trim_end_matches('/'),
+        image_path(image_id)
+    );
+    Some(match body {
+        Some(body) => format!("{}\n\n{section}", body.trim_end()),
+        None => section,
+    })
+}
+
+async fn find_browser() -> Option<std::path::PathBuf> {
+    for browser in BROWSERS {
+        if let Some(path) = resolve_executable_path(browser).await {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// Take a PNG screenshot of `url`.
+pub async fn capture(url: &str) -> Result<Vec<u8>, ScreenshotError> {
+    let browser = find_browser()
+        .await
+        .ok_or(ScreenshotError::BrowserUnavailable)?;
+    let dir = tempfile::TempDir::new()?;
+    let output = dir.path().join("screenshot.png");
+
+    let mut command = Command::new(browser);
+    command
+        .arg("--headless=new")
+        .arg("--disable-gpu")
+        .arg("--hide-scrollbars")
+        .arg("--no-first-run")
+        .arg(format!(
+            "--user-data-dir={}",
+            dir.path().join("profile").display()
+        ))
+        .arg(format!("--window-size={WINDOW_SIZE}"))
+        .arg(format!("--virtual-time-budget={VIRTUAL_TIME_BUDGET_MS}"))
+        .arg(format!("--screenshot={}", output.display()))
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let result = tokio::time::timeout(CAPTURE_TIMEOUT, command.output())
+        .await
+        .map_err(|_| ScreenshotError::Timeout(CAPTURE_TIMEOUT))??;
+
+    match tokio::fs::read(&output).await {
+        Ok(png) if !png.is_empty() => Ok(png),
+        _ => Err(ScreenshotError::Failed(
+            String::from_utf8_lossy(&result.stderr).trim().to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_the_last_printed_local_url() {
+        let history = vec![
+            LogMsg::Stdout("  ➜  Local:   \u{1b}[36mhttp://localhost:5173/\u{1b}[39m".to_string()),
+            LogMsg::Stderr("ready on http://0.0.0.0:3000".to_string()),
+            LogMsg::Stdout("compiled successfully".to_string()),
+        ];
+        assert_eq!(
+            detect_local_url(&history).as_deref(),
+            Some("http://localhost:3000")
+        );
+        assert_eq!(detect_local_url(&[]), None);
+    }
+
+    #[test]
+    fn screenshot_section_follows_the_body() {
+        let image_id = Uuid::nil();
+        assert_eq!(
+            append_to_body(
+                Some("Fixes the header\n".to_string()),
+                "https://vk.example/",
+                image_id
+            )
+            .unwrap(),
+            format!(
+                "Fixes the header\n\n## Screenshot\n\n![Dev server screenshot](https://vk.example/api/images/{image_id}/file)"
+            )
+        );
+        assert!(
+            append_to_body(None, "https://vk.example", image_id)
+                .unwrap()
+                .starts_with("## Screenshot")
+        );
+    }
+}
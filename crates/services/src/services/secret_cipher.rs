@@ -0,0 +1,219 @@
+//! Symmetric encryption for secrets persisted in the local database.
+//!
+//! The 256-bit key is read from `VK_SECRET_KEY` (base64) when set, derived with Argon2 from
+//! `VK_SECRET_PASSPHRASE` and a salt stored next to the key file, or otherwise loaded from
+//! (or generated into) `secret.key` in the asset directory. Switching between these sources
+//! makes previously stored secrets unreadable. A separate signing key is derived from it for
+//! values that must be verifiable but not secret.
+
+use std::{path::Path, sync::Arc};
+
+use aes_gcm::{
+    Aes256Gcm, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng, rand_core::RngCore},
+};
+use argon2::Argon2;
+use base64::{
+    Engine,
+    engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD},
+};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const SIGNING_KEY_CONTEXT: &[u8] = b"vibe-kanban signing key:";
+
+#[derive(Debug, Error)]
+pub enum SecretCipherError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Invalid secret key: {0}")]
+    InvalidKey(String),
+    #[error("Failed to encrypt secret")]
+    Encrypt,
+    #[error("Failed to decrypt secret")]
+    Decrypt,
+}
+
+#[derive(Clone)]
+pub struct SecretCipher {
+    cipher: Arc<Aes256Gcm>,
+    signing_key: Arc<[u8]>,
+}
+
+impl SecretCipher {
+    pub fn from_key_bytes(bytes: &[u8]) -> Result<Self, SecretCipherError> {
+        if bytes.len() != KEY_LEN {
+            return Err(SecretCipherError::InvalidKey(format!(
+                "expected {KEY_LEN} bytes, got {}",
+                bytes.len()
+            )));
+        }
+        let cipher = Aes256Gcm::new_from_slice(bytes)
+            .map_err(|e| SecretCipherError::InvalidKey(e.to_string()))?;
+        let signing_key = Sha256::new()
+            .chain_update(SIGNING_KEY_CONTEXT)
+            .chain_update(bytes)
+            .finalize();
+        Ok(Self {
+            cipher: Arc::new(cipher),
+            signing_key: Arc::from(&signing_key[..]),
+        })
+    }
+
+    /// Derive the key from a master passphrase and salt.
+    pub fn from_passphrase(passphrase: &str, salt: &[u8]) -> Result<Self, SecretCipherError> {
+        let mut key = [0u8; KEY_LEN];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| SecretCipherError::InvalidKey(e.to_string()))?;
+        Self::from_key_bytes(&key)
+    }
+
+    /// Load the key from `VK_SECRET_KEY`, `VK_SECRET_PASSPHRASE` or the key file, generating
+    /// the key file (or the passphrase salt) on first use.
+    pub fn load_or_create(key_path: &Path) -> Result<Self, SecretCipherError> {
+        if let Ok(encoded) = std::env::var("VK_SECRET_KEY") {
+            let bytes = BASE64
+                .decode(encoded.trim())
+                .map_err(|e| SecretCipherError::InvalidKey(e.to_string()))?;
+            return Self::from_key_bytes(&bytes);
+        }
+
+        if let Ok(passphrase) = std::env::var("VK_SECRET_PASSPHRASE")
+            && !passphrase.is_empty()
+        {
+            let salt_path = key_path.with_extension("salt");
+            let salt = if salt_path.exists() {
+                BASE64
+                    .decode(std::fs::read_to_string(&salt_path)?.trim())
+                    .map_err(|e| SecretCipherError::InvalidKey(e.to_string()))?
+            } else {
+                let mut salt = vec![0u8; SALT_LEN];
+                OsRng.fill_bytes(&mut salt);
+                std::fs::write(&salt_path, BASE64.encode(&salt))?;
+                salt
+            };
+            return Self::from_passphrase(&passphrase, &salt);
+        }
+
+        if key_path.exists() {
+            let encoded = std::fs::read_to_string(key_path)?;
+            let bytes = BASE64
+                .decode(encoded.trim())
+                .map_err(|e| SecretCipherError::InvalidKey(e.to_string()))?;
+            return Self::from_key_bytes(&bytes);
+        }
+
+        let key = Aes256Gcm::generate_key(OsRng);
+        std::fs::write(key_path, BASE64.encode(key))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(key_path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        tracing::info!("Generated new secret key at {}", key_path.display());
+        Self::from_key_bytes(&key)
+    }
+
+    /// Encrypt a plaintext value, returning base64(nonce || ciphertext).
+    pub fn encrypt(&self, plaintext: &str) -> Result<String, SecretCipherError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| SecretCipherError::Encrypt)?;
+
+        let mut payload = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        payload.extend_from_slice(&nonce);
+        payload.extend_from_slice(&ciphertext);
+        Ok(BASE64.encode(payload))
+    }
+
+    /// Decrypt a value produced by [`SecretCipher::encrypt`].
+    pub fn decrypt(&self, encoded: &str) -> Result<String, SecretCipherError> {
+        let payload = BASE64
+            .decode(encoded)
+            .map_err(|_| SecretCipherError::Decrypt)?;
+        if payload.len() < NONCE_LEN {
+            return Err(SecretCipherError::Decrypt);
+        }
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        let nonce: [u8; NONCE_LEN] = nonce.try_into().map_err(|_| SecretCipherError::Decrypt)?;
+        let plaintext = self
+            .cipher
+            .decrypt(&Nonce::from(nonce), ciphertext)
+            .map_err(|_| SecretCipherError::Decrypt)?;
+        String::from_utf8(plaintext).map_err(|_| SecretCipherError::Decrypt)
+    }
+
+    fn mac(&self, message: &[u8]) -> Hmac<Sha256> {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.signing_key)
+            .expect("HMAC accepts any key size");
+        mac.update(message);
+        mac
+    }
+
+    /// Sign a message, returning the URL-safe base64 HMAC-SHA256.
+    pub fn sign(&self, message: &[u8]) -> String {
+        URL_SAFE_NO_PAD.encode(self.mac(message).finalize().into_bytes())
+    }
+
+    /// Check a signature produced by [`SecretCipher::sign`].
+    pub fn verify(&self, message: &[u8], signature: &str) -> bool {
+        let Ok(signature) = URL_SAFE_NO_PAD.decode(signature) else {
+            return false;
+        };
+        self.mac(message).finalize().into_bytes()[..]
+            .ct_eq(&signature)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_uses_fresh_nonces() {
+        let cipher = SecretCipher::from_key_bytes(&[7u8; KEY_LEN]).unwrap();
+        let a = cipher.encrypt("hunter2").unwrap();
+        let b = cipher.encrypt("hunter2").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(cipher.decrypt(&a).unwrap(), "hunter2");
+        assert_eq!(cipher.decrypt(&b).unwrap(), "hunter2");
+    }
+
+    #[test]
+    fn rejects_tampered_payload() {
+        let cipher = SecretCipher::from_key_bytes(&[7u8; KEY_LEN]).unwrap();
+        let other = SecretCipher::from_key_bytes(&[8u8; KEY_LEN]).unwrap();
+        let encrypted = cipher.encrypt("hunter2").unwrap();
+        assert!(other.decrypt(&encrypted).is_err());
+        assert!(cipher.decrypt("not-base64!").is_err());
+    }
+
+    #[test]
+    fn passphrase_keys_depend_on_salt() {
+        let a = SecretCipher::from_passphrase("correct horse", &[1u8; SALT_LEN]).unwrap();
+        let b = SecretCipher::from_passphrase("correct horse", &[1u8; SALT_LEN]).unwrap();
+        let c = SecretCipher::from_passphrase("correct horse", &[2u8; SALT_LEN]).unwrap();
+        let encrypted = a.encrypt("hunter2").unwrap();
+        assert_eq!(b.decrypt(&encrypted).unwrap(), "hunter2");
+        assert!(c.decrypt(&encrypted).is_err());
+    }
+
+    #[test]
+    fn signatures_depend_on_key_and_message() {
+        let cipher = SecretCipher::from_key_bytes(&[7u8; KEY_LEN]).unwrap();
+        let other = SecretCipher::from_key_bytes(&[8u8; KEY_LEN]).unwrap();
+        let signature = cipher.sign(b"link");
+        assert!(cipher.verify(b"link", &signature));
+        assert!(!cipher.verify(b"link2", &signature));
+        assert!(!other.verify(b"link", &signature));
+    }
+}
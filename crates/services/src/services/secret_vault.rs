@@ -0,0 +1,132 @@
+//! Encrypted-at-rest store for provider credentials such as `ANTHROPIC_API_KEY`.
+//!
+//! Global secrets are injected into every executor process; a project secret with the same
+//! name overrides the global one for that project. Values are encrypted with the same
+//! [`SecretCipher`] as secret project env vars and are never returned by the API.
+//!
+//! Secrets are decrypted each time an executor process starts, so updating a value rotates
+//! it for every later process (including follow-ups of running attempts) without a restart,
+//! while processes already running keep the value they were started with.
+
+use std::collections::HashMap;
+
+use db::models::secret::{CreateSecret, Secret};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{
+    project_env::{ProjectEnvError, validate_key},
+    secret_cipher::{SecretCipher, SecretCipherError},
+};
+
+#[derive(Debug, Error)]
+pub enum SecretVaultError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Cipher(#[from] SecretCipherError),
+    #[error("Invalid secret name: {0}")]
+    InvalidName(String),
+}
+
+/// Decrypted secrets for a project, keyed by environment variable name.
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedSecrets {
+    pub vars: HashMap<String, String>,
+}
+
+impl ResolvedSecrets {
+    /// Plaintext values that must be scrubbed from captured output.
+    pub fn values(&self) -> impl Iterator<Item = &String> {
+        self.vars.values().filter(|value| !value.is_empty())
+    }
+}
+
+#[derive(Clone)]
+pub struct SecretVault {
+    cipher: SecretCipher,
+}
+
+impl SecretVault {
+    pub fn new(cipher: SecretCipher) -> Self {
+        Self { cipher }
+    }
+
+    /// Store a secret, globally when `project_id` is `None`.
+    pub async fn create(
+        &self,
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+        payload: &CreateSecret,
+    ) -> Result<Secret, SecretVaultError> {
+        if let Err(ProjectEnvError::InvalidKey(name)) = validate_key(&payload.name) {
+            return Err(SecretVaultError::InvalidName(name));
+        }
+        let value = self.cipher.encrypt(&payload.value)?;
+        Ok(Secret::create(pool, project_id, &payload.name, &value).await?)
+    }
+
+    pub async fn update(
+        &self,
+        pool: &SqlitePool,
+        id: Uuid,
+        value: &str,
+    ) -> Result<Secret, SecretVaultError> {
+        let value = self.cipher.encrypt(value)?;
+        Ok(Secret::update_value(pool, id, &value).await?)
+    }
+
+    /// Decrypt the secrets that apply to a project, with project overrides taking precedence.
+    pub async fn resolve(
+        &self,
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<ResolvedSecrets, SecretVaultError> {
+        Ok(ResolvedSecrets {
+            vars: self.decrypt_all(Secret::find_for_project(pool, project_id).await?)?,
+        })
+    }
+
+    fn decrypt_all(
+        &self,
+        secrets: Vec<Secret>,
+    ) -> Result<HashMap<String, String>, SecretVaultError> {
+        let mut vars = HashMap::new();
+        for secret in secrets {
+            let value = self.cipher.decrypt(&secret.value)?;
+            vars.insert(secret.name, value);
+        }
+        Ok(vars)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn secret(cipher: &SecretCipher, project_id: Option<Uuid>, value: &str) -> Secret {
+        Secret {
+            id: Uuid::new_v4(),
+            project_id,
+            name: "ANTHROPIC_API_KEY".to_string(),
+            value: cipher.encrypt(value).unwrap(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn project_secrets_override_global_ones() {
+        let cipher = SecretCipher::from_key_bytes(&[7u8; 32]).unwrap();
+        let vault = SecretVault::new(cipher.clone());
+        let secrets = vec![
+            secret(&cipher, None, "global"),
+            secret(&cipher, Some(Uuid::new_v4()), "project"),
+        ];
+        let vars = vault.decrypt_all(secrets).unwrap();
+        assert_eq!(vars["ANTHROPIC_API_KEY"], "project");
+    }
+}
@@ -0,0 +1,479 @@
+//! Server settings file, `vibe-kanban.toml`.
+//!
+//! Read from `VK_CONFIG_FILE` or `vibe-kanban.toml` in the data directory at startup and
+//! re-read whenever it changes. Settings that also exist as environment variables (bind
+//! address, shutdown drain, Redis, limits, remote runner, Kubernetes) are looked up through
+//! [`setting`], where the environment wins; they are read when the owning subsystem starts, so
+//! changing them needs a restart. Notification, executor and retention settings override the
+//! user config and apply as soon as the file is saved.
+//!
+//! ```toml
+//! [server]
+//! host = "0.0.0.0"
+//! port = 8080
+//! shutdown_drain_secs = 60
+//!
+//! [redis]
+//! url = "redis://cache.internal:6379"
+//!
+//! [notifications]
+//! sound_enabled = false
+//!
+//! [executor]
+//! profile = { executor = "CLAUDE_CODE", variant = "PLAN" }
+//!
+//! [retention]
+//! backup_keep = 30
+//!
+//! [limits]
+//! max_attachment_bytes = 52428800
+//! rate_limit_rpm = 600
+//!
+//! [remote_runner]
+//! host = "builder@build-01"
+//! root = "/srv/vibe-kanban/worktrees"
+//!
+//! [kubernetes]
+//! namespace = "agents"
+//! workspace_claim = "vibe-kanban-workspaces"
+//! ```
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock, RwLock as StdRwLock},
+    time::Duration,
+};
+
+use executors::profile::ExecutorProfileId;
+use notify::RecursiveMode;
+use notify_debouncer_full::{DebounceEventResult, new_debouncer};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::{RwLock, mpsc};
+use ts_rs::TS;
+
+use crate::services::config::{Config, MobilePushConfig};
+
+const REDACTED: &str = "[REDACTED]";
+
+#[derive(Debug, Error)]
+pub enum ServerConfigError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Invalid {path}: {source}")]
+    Parse {
+        path: String,
+        source: toml::de::Error,
+    },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSection {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    /// Seconds running coding agents get to checkpoint and exit on shutdown
+    pub shutdown_drain_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RedisSection {
+    pub url: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Coordinate with other instances through this Redis. Refused for now: instances would need
+    /// a shared database, which the SQLite backend can't provide
+    pub multi_instance: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationsSection {
+    pub sound_enabled: Option<bool>,
+    pub push_enabled: Option<bool>,
+    pub mobile_push: Option<MobilePushConfig>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExecutorSection {
+    /// Default executor profile for new attempts
+    pub profile: Option<ExecutorProfileId>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetentionSection {
+    /// Number of scheduled backups to keep
+    pub backup_keep: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitsSection {
+    pub max_attachment_bytes: Option<u64>,
+    pub max_body_bytes: Option<u64>,
+    pub max_upload_bytes: Option<u64>,
+    pub rate_limit_rpm: Option<u32>,
+    pub rate_limit_burst: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RemoteRunnerSection {
+    /// SSH destination that coding agents and scripts run on
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub identity_file: Option<String>,
+    /// Directory on the remote host that mirrors local worktrees
+    pub root: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KubernetesSection {
+    pub namespace: Option<String>,
+    pub context: Option<String>,
+    /// Shared claim holding the workspace directory; setting it runs attempts as Jobs
+    pub workspace_claim: Option<String>,
+    pub workspace_mount: Option<String>,
+    pub storage_class: Option<String>,
+    pub home_size: Option<String>,
+    pub default_image: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfigFile {
+    pub server: ServerSection,
+    pub redis: RedisSection,
+    pub notifications: NotificationsSection,
+    pub executor: ExecutorSection,
+    pub retention: RetentionSection,
+    pub limits: LimitsSection,
+    pub remote_runner: RemoteRunnerSection,
+    pub kubernetes: KubernetesSection,
+}
+
+impl ServerConfigFile {
+    /// Parse the file at `path`; a missing file yields the empty config.
+    pub fn load(path: &Path) -> Result<Self, ServerConfigError> {
+        let raw = match std::fs::read_to_string(path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        toml::from_str(&raw).map_err(|source| ServerConfigError::Parse {
+            path: path.display().to_string(),
+            source,
+        })
+    }
+
+    /// The file's value for a setting that can also be given as an environment variable.
+    fn env_value(&self, name: &str) -> Option<String> {
+        let limits = &self.limits;
+        let remote = &self.remote_runner;
+        let kubernetes = &self.kubernetes;
+        match name {
+            "HOST" => self.server.host.clone(),
+            "BACKEND_PORT" | "PORT" => self.server.port.map(|v| v.to_string()),
+            "VK_SHUTDOWN_DRAIN_SECS" => self.server.shutdown_drain_secs.map(|v| v.to_string()),
+            "REDIS_URL" => self.redis.url.clone(),
+            "REDIS_USERNAME" => self.redis.username.clone(),
+            "REDIS_PASSWORD" => self.redis.password.clone(),
+            "VK_MULTI_INSTANCE" => self.redis.multi_instance.map(|v| v.to_string()),
+            "VK_MAX_ATTACHMENT_BYTES" => limits.max_attachment_bytes.map(|v| v.to_string()),
+            "VK_MAX_BODY_BYTES" => limits.max_body_bytes.map(|v| v.to_string()),
+            "VK_MAX_UPLOAD_BYTES" => limits.max_upload_bytes.map(|v| v.to_string()),
+            "VK_RATE_LIMIT_RPM" => limits.rate_limit_rpm.map(|v| v.to_string()),
+            "VK_RATE_LIMIT_BURST" => limits.rate_limit_burst.map(|v| v.to_string()),
+            "VK_REMOTE_RUNNER_HOST" => remote.host.clone(),
+            "VK_REMOTE_RUNNER_PORT" => remote.port.map(|v| v.to_string()),
+            "VK_REMOTE_RUNNER_IDENTITY" => remote.identity_file.clone(),
+            "VK_REMOTE_RUNNER_ROOT" => remote.root.clone(),
+            "VK_KUBERNETES_NAMESPACE" => kubernetes.namespace.clone(),
+            "VK_KUBERNETES_CONTEXT" => kubernetes.context.clone(),
+            "VK_KUBERNETES_WORKSPACE_CLAIM" => kubernetes.workspace_claim.clone(),
+            "VK_KUBERNETES_WORKSPACE_MOUNT" => kubernetes.workspace_mount.clone(),
+            "VK_KUBERNETES_STORAGE_CLASS" => kubernetes.storage_class.clone(),
+            "VK_KUBERNETES_HOME_SIZE" => kubernetes.home_size.clone(),
+            "VK_KUBERNETES_DEFAULT_IMAGE" => kubernetes.default_image.clone(),
+            _ => None,
+        }
+    }
+
+    /// Override user config values with the ones set in this file.
+    pub fn apply_to(&self, config: &mut Config) {
+        let notifications = &self.notifications;
+        if let Some(sound_enabled) = notifications.sound_enabled {
+            config.notifications.sound_enabled = sound_enabled;
+        }
+        if let Some(push_enabled) = notifications.push_enabled {
+            config.notifications.push_enabled = push_enabled;
+        }
+        if let Some(mobile_push) = &notifications.mobile_push {
+            config.mobile_push = mobile_push.clone();
+        }
+        if let Some(profile) = &self.executor.profile {
+            config.executor_profile = profile.clone();
+        }
+        if let Some(keep) = self.retention.backup_keep {
+            config.backups.keep = keep;
+        }
+    }
+}
+
+static CURRENT: LazyLock<StdRwLock<Arc<ServerConfigFile>>> =
+    LazyLock::new(|| StdRwLock::new(Arc::new(ServerConfigFile::default())));
+
+/// The most recently loaded settings file.
+pub fn current() -> Arc<ServerConfigFile> {
+    CURRENT.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn set_current(file: ServerConfigFile) {
+    *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(file);
+}
+
+/// Look up a setting by its environment variable name, falling back to the settings file.
+pub fn setting(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .or_else(|| current().env_value(name))
+}
+
+/// Location of the settings file, `VK_CONFIG_FILE` or `vibe-kanban.toml` in the data directory.
+pub fn server_config_path() -> PathBuf {
+    std::env::var("VK_CONFIG_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| utils::assets::asset_dir().join("vibe-kanban.toml"))
+}
+
+/// Effective server settings, with credentials redacted.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct EffectiveServerConfig {
+    pub path: String,
+    pub file_exists: bool,
+    /// Error from the last attempt to load the file; the previous settings stay in effect
+    pub last_error: Option<String>,
+    pub host: String,
+    pub port: Option<u16>,
+    pub redis_url: Option<String>,
+    pub redis_username: Option<String>,
+    pub redis_password: Option<String>,
+    pub sound_enabled: bool,
+    pub push_enabled: bool,
+    pub mobile_push: MobilePushConfig,
+    pub executor_profile: ExecutorProfileId,
+    pub backup_keep: u32,
+    pub max_attachment_bytes: Option<u64>,
+    pub max_body_bytes: Option<u64>,
+    pub max_upload_bytes: Option<u64>,
+    pub rate_limit_rpm: Option<u32>,
+    pub rate_limit_burst: Option<u32>,
+}
+
+fn redact_url(raw: String) -> String {
+    match url::Url::parse(&raw) {
+        Ok(mut url) if url.password().is_some() => {
+            let _ = url.set_password(Some(REDACTED));
+            url.to_string()
+        }
+        _ => raw,
+    }
+}
+
+fn redact(value: &mut Option<String>) {
+    if value.is_some() {
+        *value = Some(REDACTED.to_string());
+    }
+}
+
+fn number<T: std::str::FromStr>(name: &str) -> Option<T> {
+    setting(name).and_then(|v| v.trim().parse().ok())
+}
+
+#[derive(Clone)]
+pub struct ServerConfigService {
+    path: PathBuf,
+    config: Arc<RwLock<Config>>,
+    last_error: Arc<StdRwLock<Option<String>>>,
+}
+
+impl ServerConfigService {
+    /// Load the settings file and apply it to the user config.
+    pub async fn load(path: PathBuf, config: Arc<RwLock<Config>>) -> Self {
+        let service = Self {
+            path,
+            config,
+            last_error: Arc::new(StdRwLock::new(None)),
+        };
+        if service.path.exists() {
+            tracing::info!("Loading server settings from {}", service.path.display());
+        }
+        service.reload().await;
+        service
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Re-read the file. On error the previous settings stay in effect.
+    pub async fn reload(&self) {
+        let result = ServerConfigFile::load(&self.path);
+        let error = match result {
+            Ok(file) => {
+                file.apply_to(&mut *self.config.write().await);
+                set_current(file);
+                None
+            }
+            Err(e) => {
+                tracing::error!("Failed to load server settings: {}", e);
+                Some(e.to_string())
+            }
+        };
+        *self.last_error.write().unwrap_or_else(|e| e.into_inner()) = error;
+    }
+
+    /// Watch the settings file and reload it when it changes.
+    pub fn spawn_watcher(&self) {
+        let Some(dir) = self.path.parent().map(Path::to_path_buf) else {
+            return;
+        };
+        let Some(file_name) = self.path.file_name().map(|name| name.to_os_string()) else {
+            return;
+        };
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let debouncer = new_debouncer(
+            Duration::from_millis(500),
+            None,
+            move |res: DebounceEventResult| {
+                let touches_file = res.is_ok_and(|events| {
+                    events.iter().any(|event| {
+                        event
+                            .event
+                            .paths
+                            .iter()
+                            .any(|path| path.file_name() == Some(file_name.as_os_str()))
+                    })
+                });
+                if touches_file {
+                    let _ = tx.send(());
+                }
+            },
+        );
+        let mut debouncer = match debouncer {
+            Ok(debouncer) => debouncer,
+            Err(e) => {
+                tracing::warn!("Failed to watch server settings: {}", e);
+                return;
+            }
+        };
+        // Watch the directory so the file can be created or replaced atomically by editors
+        if let Err(e) = debouncer.watch(&dir, RecursiveMode::NonRecursive) {
+            tracing::warn!("Failed to watch {}: {}", dir.display(), e);
+            return;
+        }
+
+        let service = self.clone();
+        tokio::spawn(async move {
+            let _debouncer = debouncer;
+            while rx.recv().await.is_some() {
+                tracing::info!("Reloading server settings from {}", service.path.display());
+                service.reload().await;
+            }
+        });
+    }
+
+    pub async fn effective(&self) -> EffectiveServerConfig {
+        let config = self.config.read().await;
+        let mut mobile_push = config.mobile_push.clone();
+        redact(&mut mobile_push.ntfy_token);
+        redact(&mut mobile_push.pushover_app_token);
+        redact(&mut mobile_push.pushover_user_key);
+        let mut redis_password = setting("REDIS_PASSWORD");
+        redact(&mut redis_password);
+
+        EffectiveServerConfig {
+            path: self.path.display().to_string(),
+            file_exists: self.path.exists(),
+            last_error: self
+                .last_error
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+            host: setting("HOST").unwrap_or_else(|| "127.0.0.1".to_string()),
+            port: number("BACKEND_PORT").or_else(|| number("PORT")),
+            redis_url: setting("REDIS_URL").map(redact_url),
+            redis_username: setting("REDIS_USERNAME"),
+            redis_password,
+            sound_enabled: config.notifications.sound_enabled,
+            push_enabled: config.notifications.push_enabled,
+            mobile_push,
+            executor_profile: config.executor_profile.clone(),
+            backup_keep: config.backups.keep,
+            max_attachment_bytes: number("VK_MAX_ATTACHMENT_BYTES"),
+            max_body_bytes: number("VK_MAX_BODY_BYTES"),
+            max_upload_bytes: number("VK_MAX_UPLOAD_BYTES"),
+            rate_limit_rpm: number("VK_RATE_LIMIT_RPM"),
+            rate_limit_burst: number("VK_RATE_LIMIT_BURST"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sections_and_overlays_user_config() {
+        let file: ServerConfigFile = toml::from_str(
+            r#"
+            [server]
+            port = 8080
+
+            [notifications]
+            sound_enabled = false
+
+            [executor]
+            profile = { executor = "CLAUDE_CODE", variant = "PLAN" }
+
+            [retention]
+            backup_keep = 3
+
+            [limits]
+            rate_limit_rpm = 600
+            "#,
+        )
+        .unwrap();
+        assert_eq!(file.env_value("BACKEND_PORT").as_deref(), Some("8080"));
+        assert_eq!(file.env_value("VK_RATE_LIMIT_RPM").as_deref(), Some("600"));
+        assert_eq!(file.env_value("REDIS_URL"), None);
+
+        let mut config = Config::default();
+        file.apply_to(&mut config);
+        assert!(!config.notifications.sound_enabled);
+        assert_eq!(config.executor_profile.variant.as_deref(), Some("PLAN"));
+        assert_eq!(config.backups.keep, 3);
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(toml::from_str::<ServerConfigFile>("[server]\nhots = \"x\"").is_err());
+    }
+
+    #[test]
+    fn redacts_url_passwords() {
+        assert_eq!(
+            redact_url("redis://:hunter2@cache:6379".to_string()),
+            "redis://:%5BREDACTED%5D@cache:6379"
+        );
+        assert_eq!(
+            redact_url("redis://cache:6379".to_string()),
+            "redis://cache:6379"
+        );
+    }
+}
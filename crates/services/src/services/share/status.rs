@@ -5,6 +5,8 @@ pub(super) fn to_remote(status: &TaskStatus) -> RemoteTaskStatus {
     match status {
         TaskStatus::Todo => RemoteTaskStatus::Todo,
         TaskStatus::InProgress => RemoteTaskStatus::InProgress,
+        // Shared boards have no blocked column; the task is still being worked on
+        TaskStatus::Blocked => RemoteTaskStatus::InProgress,
         TaskStatus::InReview => RemoteTaskStatus::InReview,
         TaskStatus::Done => RemoteTaskStatus::Done,
         TaskStatus::Cancelled => RemoteTaskStatus::Cancelled,
@@ -0,0 +1,364 @@
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const API_BASE: &str = "https://slack.com/api";
+/// Requests signed longer ago than this are rejected as replays
+const MAX_SIGNATURE_AGE_SECS: i64 = 5 * 60;
+/// `callback_id` of the task creation modal
+pub const CREATE_TASK_CALLBACK_ID: &str = "vibe_create_task";
+const TITLE_BLOCK: &str = "title";
+const DESCRIPTION_BLOCK: &str = "description";
+
+#[derive(Debug, Error)]
+pub enum SlackError {
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error("Slack API error: {0}")]
+    Api(String),
+}
+
+impl SlackError {
+    /// Whether Slack couldn't be reached at all, as opposed to rejecting the request
+    pub fn is_connectivity_error(&self) -> bool {
+        matches!(self, SlackError::Http(e) if e.is_connect() || e.is_timeout())
+    }
+}
+
+/// Verify the `X-Slack-Signature` header, `v0=` followed by the hex HMAC-SHA256 of
+/// `v0:<timestamp>:<body>` keyed with the app's signing secret.
+pub fn verify_signature(secret: &[u8], timestamp: &str, signature: &str, body: &[u8]) -> bool {
+    let Ok(sent_at) = timestamp.parse::<i64>() else {
+        return false;
+    };
+    if (Utc::now().timestamp() - sent_at).abs() > MAX_SIGNATURE_AGE_SECS {
+        return false;
+    }
+    let Some(expected) = signature
+        .strip_prefix("v0=")
+        .and_then(|hex_signature| hex::decode(hex_signature).ok())
+    else {
+        return false;
+    };
+    let Ok(mut mac) = HmacSha256::new_from_slice(secret) else {
+        return false;
+    };
+    mac.update(format!("v0:{timestamp}:").as_bytes());
+    mac.update(body);
+    mac.finalize().into_bytes()[..].ct_eq(&expected).into()
+}
+
+/// Form Slack posts when `/vibe` is invoked.
+#[derive(Debug, Deserialize)]
+pub struct SlashCommand {
+    #[serde(default)]
+    pub text: String,
+    pub channel_id: String,
+    pub trigger_id: String,
+}
+
+impl SlashCommand {
+    pub fn parse(body: &[u8]) -> Result<Self, serde_urlencoded::de::Error> {
+        serde_urlencoded::from_bytes(body)
+    }
+
+    /// Title given to `/vibe create`, or `None` for any other subcommand.
+    pub fn create_title(&self) -> Option<&str> {
+        let text = self.text.trim();
+        let rest = text.strip_prefix("create")?;
+        (rest.is_empty() || rest.starts_with(char::is_whitespace)).then(|| rest.trim())
+    }
+}
+
+/// A submitted task creation modal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskSubmission {
+    pub channel_id: String,
+    pub user_id: String,
+    pub title: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InteractionForm {
+    payload: String,
+}
+
+/// Parse an interactivity request, returning the task of a submitted creation modal and
+/// `None` for any other interaction.
+pub fn parse_submission(body: &[u8]) -> Result<Option<TaskSubmission>, SlackError> {
+    let form: InteractionForm = serde_urlencoded::from_bytes(body)
+        .map_err(|e| SlackError::Api(format!("invalid interaction form: {e}")))?;
+    let payload: Value = serde_json::from_str(&form.payload)
+        .map_err(|e| SlackError::Api(format!("invalid interaction payload: {e}")))?;
+    if payload["type"] != "view_submission"
+        || payload["view"]["callback_id"] != CREATE_TASK_CALLBACK_ID
+    {
+        return Ok(None);
+    }
+    let input = |block: &str| {
+        payload["view"]["state"]["values"][block][block]["value"]
+            .as_str()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    Ok(Some(TaskSubmission {
+        channel_id: payload["view"]["private_metadata"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        user_id: payload["user"]["id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        title: input(TITLE_BLOCK).unwrap_or_default(),
+        description: input(DESCRIPTION_BLOCK),
+    }))
+}
+
+/// Envelope of Events API requests.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EventEnvelope {
+    /// Sent once when the request URL is saved in the app settings
+    UrlVerification {
+        challenge: String,
+    },
+    EventCallback {
+        event: MessageEvent,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// A `message` event. Other event types deserialize with `kind` set to their type.
+#[derive(Debug, Deserialize)]
+pub struct MessageEvent {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub channel: Option<String>,
+    pub user: Option<String>,
+    #[serde(default)]
+    pub text: String,
+    pub ts: Option<String>,
+    pub thread_ts: Option<String>,
+    pub bot_id: Option<String>,
+    pub subtype: Option<String>,
+}
+
+impl MessageEvent {
+    /// Channel and parent message of a reply written by a person in a thread.
+    pub fn thread_reply(&self) -> Option<(&str, &str)> {
+        if self.kind != "message" || self.bot_id.is_some() || self.subtype.is_some() {
+            return None;
+        }
+        let thread_ts = self.thread_ts.as_deref()?;
+        if self.ts.as_deref() == Some(thread_ts) || self.text.trim().is_empty() {
+            return None;
+        }
+        Some((self.channel.as_deref()?, thread_ts))
+    }
+}
+
+/// Modal collecting the title and description of a new task.
+pub fn create_task_modal(title: &str, channel_id: &str) -> Value {
+    json!({
+        "type": "modal",
+        "callback_id": CREATE_TASK_CALLBACK_ID,
+        "private_metadata": channel_id,
+        "title": { "type": "plain_text", "text": "Create task" },
+        "submit": { "type": "plain_text", "text": "Create" },
+        "close": { "type": "plain_text", "text": "Cancel" },
+        "blocks": [
+            {
+                "type": "input",
+                "block_id": TITLE_BLOCK,
+                "label": { "type": "plain_text", "text": "Title" },
+                "element": {
+                    "type": "plain_text_input",
+                    "action_id": TITLE_BLOCK,
+                    "initial_value": title,
+                },
+            },
+            {
+                "type": "input",
+                "block_id": DESCRIPTION_BLOCK,
+                "optional": true,
+                "label": { "type": "plain_text", "text": "Description" },
+                "element": {
+                    "type": "plain_text_input",
+                    "action_id": DESCRIPTION_BLOCK,
+                    "multiline": true,
+                },
+            },
+        ],
+    })
+}
+
+pub fn task_url(app_url: &str, project_id: Uuid, task_id: Uuid) -> String {
+    format!(
+        "{}/projects/{project_id}/tasks/{task_id}",
+        app_url.trim_end_matches('/')
+    )
+}
+
+/// Tasks have no comment thread of their own, so Slack replies are appended to the
+/// description where both reviewers and agents see them.
+pub fn append_comment(description: Option<&str>, user_id: &str, text: &str) -> String {
+    let comment = format!("**<@{user_id}> on Slack:** {}", text.trim());
+    match description.map(str::trim_end).filter(|d| !d.is_empty()) {
+        Some(description) => format!("{description}\n\n{comment}"),
+        None => comment,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiReply<T> {
+    ok: bool,
+    error: Option<String>,
+    #[serde(flatten)]
+    data: Option<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PostMessageReply {
+    ts: String,
+}
+
+/// Web API client authenticated with the app's bot token.
+pub struct SlackClient {
+    http: reqwest::Client,
+    bot_token: String,
+}
+
+impl SlackClient {
+    pub fn new(bot_token: String) -> Self {
+        Self {
+            http: utils::http::client_builder().build().unwrap_or_default(),
+            bot_token,
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, body: Value) -> Result<T, SlackError> {
+        let reply: ApiReply<T> = self
+            .http
+            .post(format!("{API_BASE}/{method}"))
+            .bearer_auth(&self.bot_token)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        match reply {
+            ApiReply {
+                ok: true,
+                data: Some(data),
+                ..
+            } => Ok(data),
+            ApiReply { error, .. } => Err(SlackError::Api(
+                error.unwrap_or_else(|| format!("{method} failed")),
+            )),
+        }
+    }
+
+    pub async fn open_view(&self, trigger_id: &str, view: Value) -> Result<(), SlackError> {
+        self.call::<Value>(
+            "views.open",
+            json!({ "trigger_id": trigger_id, "view": view }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Post a message and return its `ts`, which identifies the thread of its replies.
+    pub async fn post_message(&self, channel_id: &str, text: &str) -> Result<String, SlackError> {
+        let reply: PostMessageReply = self
+            .call(
+                "chat.postMessage",
+                json!({ "channel": channel_id, "text": text }),
+            )
+            .await?;
+        Ok(reply.ts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign(secret: &[u8], timestamp: &str, body: &[u8]) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret).unwrap();
+        mac.update(format!("v0:{timestamp}:").as_bytes());
+        mac.update(body);
+        format!("v0={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    #[test]
+    fn verifies_recent_signatures() {
+        let body = b"command=%2Fvibe&text=create";
+        let now = Utc::now().timestamp().to_string();
+        let stale = (Utc::now().timestamp() - 600).to_string();
+
+        assert!(verify_signature(
+            b"secret",
+            &now,
+            &sign(b"secret", &now, body),
+            body
+        ));
+        assert!(!verify_signature(
+            b"other",
+            &now,
+            &sign(b"secret", &now, body),
+            body
+        ));
+        assert!(!verify_signature(
+            b"secret",
+            &stale,
+            &sign(b"secret", &stale, body),
+            body
+        ));
+    }
+
+    #[test]
+    fn parses_create_subcommand() {
+        let command = SlashCommand::parse(
+            b"text=create+Fix+login&channel_id=C1&trigger_id=T1&command=%2Fvibe",
+        )
+        .unwrap();
+        assert_eq!(command.create_title(), Some("Fix login"));
+
+        let command = SlashCommand::parse(b"text=created&channel_id=C1&trigger_id=T1").unwrap();
+        assert_eq!(command.create_title(), None);
+    }
+
+    #[test]
+    fn only_human_thread_replies_become_comments() {
+        let event = |ts: &str, bot_id: Option<&str>| MessageEvent {
+            kind: "message".to_string(),
+            channel: Some("C1".to_string()),
+            user: Some("U1".to_string()),
+            text: "Seen on staging too".to_string(),
+            ts: Some(ts.to_string()),
+            thread_ts: Some("1.0".to_string()),
+            bot_id: bot_id.map(str::to_string),
+            subtype: None,
+        };
+
+        assert_eq!(event("2.0", None).thread_reply(), Some(("C1", "1.0")));
+        assert_eq!(event("1.0", None).thread_reply(), None);
+        assert_eq!(event("2.0", Some("B1")).thread_reply(), None);
+        assert_eq!(
+            append_comment(Some("Steps"), "U1", "Seen on staging too"),
+            "Steps\n\n**<@U1> on Slack:** Seen on staging too"
+        );
+    }
+}
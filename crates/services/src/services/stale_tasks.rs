@@ -0,0 +1,281 @@
+use std::{sync::Arc, time::Duration as StdDuration};
+
+use chrono::{DateTime, Duration, Utc};
+use db::{
+    DBService,
+    models::{
+        notification::{CreateNotification, NotificationKind},
+        stale_task_flag::{StaleTaskFlag, TaskActivity},
+        task::TaskStatus,
+    },
+};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tokio::{sync::RwLock, time::interval};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::services::{
+    config::{Config, StaleTaskConfig},
+    notification::NotificationService,
+    share::SharePublisher,
+    task_workflow,
+};
+
+const CHECK_INTERVAL: StdDuration = StdDuration::from_secs(60 * 60);
+
+/// A task flagged by the stale task policy.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct StaleTask {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub title: String,
+    pub status: TaskStatus,
+    #[ts(type = "Date")]
+    pub last_activity_at: DateTime<Utc>,
+    #[ts(type = "Date")]
+    pub flagged_at: DateTime<Utc>,
+    /// When the task will be moved to Cancelled, if auto-close is on
+    #[ts(type = "Date | null")]
+    pub closes_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PolicyAction {
+    None,
+    Flag,
+    Unflag,
+    Close,
+}
+
+fn closes_at(config: &StaleTaskConfig, flagged_at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    config
+        .auto_close
+        .then(|| flagged_at + Duration::days(config.grace_period_days as i64))
+}
+
+fn is_stale(task: &TaskActivity, config: &StaleTaskConfig, now: DateTime<Utc>) -> bool {
+    config.statuses.contains(&task.status)
+        && task.last_activity_at <= now - Duration::days(config.stale_after_days as i64)
+}
+
+fn decide(task: &TaskActivity, config: &StaleTaskConfig, now: DateTime<Utc>) -> PolicyAction {
+    let stale = is_stale(task, config, now);
+    let Some(flagged_at) = task.flagged_at else {
+        return if stale {
+            PolicyAction::Flag
+        } else {
+            PolicyAction::None
+        };
+    };
+
+    let touched_since_flagged = task
+        .flagged_activity_at
+        .is_some_and(|seen| task.last_activity_at > seen);
+    if !stale || touched_since_flagged {
+        PolicyAction::Unflag
+    } else if closes_at(config, flagged_at).is_some_and(|at| at <= now) {
+        PolicyAction::Close
+    } else {
+        PolicyAction::None
+    }
+}
+
+/// Flagged tasks that are still stale, least recently active first.
+pub async fn find_stale(
+    pool: &SqlitePool,
+    config: &StaleTaskConfig,
+    project_id: Option<Uuid>,
+) -> Result<Vec<StaleTask>, sqlx::Error> {
+    let now = Utc::now();
+    Ok(TaskActivity::find_open(pool, project_id)
+        .await?
+        .into_iter()
+        .filter(|task| {
+            matches!(
+                decide(task, config, now),
+                PolicyAction::None | PolicyAction::Close
+            )
+        })
+        .filter_map(|task| {
+            let flagged_at = task.flagged_at?;
+            Some(StaleTask {
+                task_id: task.task_id,
+                project_id: task.project_id,
+                title: task.title,
+                status: task.status,
+                last_activity_at: task.last_activity_at,
+                flagged_at,
+                closes_at: closes_at(config, flagged_at),
+            })
+        })
+        .collect())
+}
+
+/// Applies the stale task policy every hour: flags and warns about untouched tasks, clears flags
+/// once tasks see activity and closes flagged tasks after the grace period when enabled.
+pub struct StaleTaskMonitor {
+    db: DBService,
+    config: Arc<RwLock<Config>>,
+    notifications: NotificationService,
+    publisher: Option<SharePublisher>,
+}
+
+impl StaleTaskMonitor {
+    pub async fn spawn(
+        db: DBService,
+        config: Arc<RwLock<Config>>,
+        notifications: NotificationService,
+        publisher: Option<SharePublisher>,
+    ) -> tokio::task::JoinHandle<()> {
+        let monitor = Self {
+            db,
+            config,
+            notifications,
+            publisher,
+        };
+        tokio::spawn(async move {
+            let mut interval = interval(CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = monitor.check().await {
+                    tracing::error!("Failed to apply stale task policy: {}", e);
+                }
+            }
+        })
+    }
+
+    async fn check(&self) -> Result<(), sqlx::Error> {
+        let config = self.config.read().await.stale_tasks.clone();
+        if !config.enabled {
+            return Ok(());
+        }
+
+        let pool = &self.db.pool;
+        let now = Utc::now();
+        for task in TaskActivity::find_open(pool, None).await? {
+            match decide(&task, &config, now) {
+                PolicyAction::None => {}
+                PolicyAction::Flag => {
+                    StaleTaskFlag::create(pool, task.task_id, task.last_activity_at).await?;
+                    self.notifications
+                        .notify(flagged_notification(&task, &config, now))
+                        .await;
+                }
+                PolicyAction::Unflag => StaleTaskFlag::delete(pool, task.task_id).await?,
+                PolicyAction::Close => {
+                    task_workflow::update_task_status(pool, task.task_id, TaskStatus::Cancelled)
+                        .await?;
+                    StaleTaskFlag::delete(pool, task.task_id).await?;
+                    if let Some(publisher) = &self.publisher
+                        && let Err(e) = publisher.update_shared_task_by_id(task.task_id).await
+                    {
+                        tracing::warn!("Failed to update shared task {}: {}", task.task_id, e);
+                    }
+                    tracing::info!("Closed stale task {}", task.task_id);
+                    self.notifications
+                        .notify(CreateNotification {
+                            kind: NotificationKind::TaskStale,
+                            title: format!("Closed stale task: {}", task.title),
+                            message: format!(
+                                "'{}' saw no activity for {} days and was moved to Cancelled",
+                                task.title,
+                                config.stale_after_days + config.grace_period_days
+                            ),
+                            project_id: Some(task.project_id),
+                            task_id: Some(task.task_id),
+                        })
+                        .await;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn flagged_notification(
+    task: &TaskActivity,
+    config: &StaleTaskConfig,
+    now: DateTime<Utc>,
+) -> CreateNotification {
+    let mut message = format!(
+        "'{}' has not been touched in {} days",
+        task.title, config.stale_after_days
+    );
+    if let Some(at) = closes_at(config, now) {
+        message.push_str(&format!(
+            " and will be closed on {} unless it sees activity",
+            at.format("%Y-%m-%d")
+        ));
+    }
+    CreateNotification {
+        kind: NotificationKind::TaskStale,
+        title: format!("Stale task: {}", task.title),
+        message,
+        project_id: Some(task.project_id),
+        task_id: Some(task.task_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(
+        status: TaskStatus,
+        idle_days: i64,
+        flagged_days_ago: Option<i64>,
+        now: DateTime<Utc>,
+    ) -> TaskActivity {
+        let last_activity_at = now - Duration::days(idle_days);
+        TaskActivity {
+            task_id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            title: "Old idea".to_string(),
+            status,
+            last_activity_at,
+            flagged_at: flagged_days_ago.map(|days| now - Duration::days(days)),
+            flagged_activity_at: flagged_days_ago.map(|_| last_activity_at),
+        }
+    }
+
+    #[test]
+    fn policy_flags_closes_and_unflags() {
+        let now = Utc::now();
+        let mut config = StaleTaskConfig {
+            enabled: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            decide(&task(TaskStatus::Todo, 31, None, now), &config, now),
+            PolicyAction::Flag
+        );
+        assert_eq!(
+            decide(&task(TaskStatus::Todo, 10, None, now), &config, now),
+            PolicyAction::None
+        );
+        assert_eq!(
+            decide(&task(TaskStatus::InProgress, 60, None, now), &config, now),
+            PolicyAction::None
+        );
+        assert_eq!(
+            decide(&task(TaskStatus::Todo, 40, Some(8), now), &config, now),
+            PolicyAction::None
+        );
+
+        config.auto_close = true;
+        assert_eq!(
+            decide(&task(TaskStatus::Todo, 40, Some(8), now), &config, now),
+            PolicyAction::Close
+        );
+        assert_eq!(
+            decide(&task(TaskStatus::Todo, 40, Some(3), now), &config, now),
+            PolicyAction::None
+        );
+
+        let mut touched = task(TaskStatus::Todo, 40, Some(8), now);
+        touched.last_activity_at = now - Duration::days(1);
+        assert_eq!(decide(&touched, &config, now), PolicyAction::Unflag);
+    }
+}
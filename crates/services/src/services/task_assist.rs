@@ -0,0 +1,206 @@
+use db::models::task::Task;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::services::llm::{LlmError, LlmService};
+
+/// Upper bound on subtasks kept from a breakdown
+const MAX_SUBTASKS: usize = 12;
+
+const BREAKDOWN_SYSTEM_PROMPT: &str = "You break software development tasks into smaller \
+subtasks that a coding agent can complete one at a time. Reply with JSON only, in the form \
+{\"subtasks\": [{\"title\": string, \"description\": string, \"order\": number}]}. Propose 2 to 8 \
+subtasks, ordered so that each one builds on the previous ones. Titles are short imperatives. \
+Descriptions say what to change and how to check that it works.";
+
+/// Longest generated title kept, in characters
+const MAX_TITLE_CHARS: usize = 100;
+
+const DETAILS_SYSTEM_PROMPT: &str = "You turn rough requests written for a coding agent into \
+tidy task cards. Reply with JSON only, in the form {\"title\": string, \"description\": string, \
+\"acceptance_criteria\": [string]}. The title is a short imperative. The description states the \
+goal and the relevant context in a few sentences or bullet points, in Markdown. Acceptance \
+criteria are concrete, checkable outcomes. Do not add requirements the request does not imply.";
+
+/// A title and description generated from a raw prompt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedTaskDetails {
+    pub title: String,
+    /// Markdown description ending with the acceptance criteria as a checklist
+    pub description: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DetailsReply {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    acceptance_criteria: Vec<String>,
+}
+
+/// A subtask suggested by the model.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProposedSubtask {
+    pub title: String,
+    pub description: Option<String>,
+    /// 1-based position in the suggested order of work
+    pub order: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct BreakdownReply {
+    subtasks: Vec<RawSubtask>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawSubtask {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    order: Option<u32>,
+}
+
+/// Ask the model to split a task into ordered subtasks. Nothing is created; the caller decides
+/// which proposals to keep.
+pub async fn propose_subtasks(
+    llm: &LlmService,
+    task: &Task,
+) -> Result<Vec<ProposedSubtask>, LlmError> {
+    let prompt = format!("Break down this task:\n\n{}", task.to_prompt());
+    let reply: BreakdownReply = llm.complete_json(BREAKDOWN_SYSTEM_PROMPT, &prompt).await?;
+    let subtasks = normalize_subtasks(reply.subtasks);
+    if subtasks.is_empty() {
+        return Err(LlmError::InvalidResponse(
+            "no subtasks were proposed".to_string(),
+        ));
+    }
+    Ok(subtasks)
+}
+
+/// Drop untitled entries, keep the model's ordering where given and renumber from 1.
+fn normalize_subtasks(raw: Vec<RawSubtask>) -> Vec<ProposedSubtask> {
+    let mut raw: Vec<(usize, RawSubtask)> = raw
+        .into_iter()
+        .filter(|s| !s.title.trim().is_empty())
+        .enumerate()
+        .collect();
+    raw.sort_by_key(|(index, s)| (s.order.unwrap_or(u32::MAX), *index));
+
+    raw.into_iter()
+        .take(MAX_SUBTASKS)
+        .enumerate()
+        .map(|(position, (_, s))| ProposedSubtask {
+            title: s.title.trim().to_string(),
+            description: s
+                .description
+                .map(|d| d.trim().to_string())
+                .filter(|d| !d.is_empty()),
+            order: position as u32 + 1,
+        })
+        .collect()
+}
+
+/// Ask the model for a concise title, structured description and acceptance criteria for a
+/// prompt pasted as a task.
+pub async fn generate_task_details(
+    llm: &LlmService,
+    raw_prompt: &str,
+) -> Result<GeneratedTaskDetails, LlmError> {
+    let prompt = format!("Write a task card for this request:\n\n{raw_prompt}");
+    let reply: DetailsReply = llm.complete_json(DETAILS_SYSTEM_PROMPT, &prompt).await?;
+    normalize_details(reply)
+        .ok_or_else(|| LlmError::InvalidResponse("no task title was generated".to_string()))
+}
+
+/// Trim the reply, cap the title length and fold the acceptance criteria into the description.
+fn normalize_details(reply: DetailsReply) -> Option<GeneratedTaskDetails> {
+    let title = reply.title.trim();
+    if title.is_empty() {
+        return None;
+    }
+    let title = match title.char_indices().nth(MAX_TITLE_CHARS) {
+        Some((end, _)) => format!("{}…", title[..end].trim_end()),
+        None => title.to_string(),
+    };
+
+    let mut description = reply.description.trim().to_string();
+    let criteria: Vec<&str> = reply
+        .acceptance_criteria
+        .iter()
+        .map(|c| c.trim().trim_start_matches(['-', '*']).trim())
+        .filter(|c| !c.is_empty())
+        .collect();
+    if !criteria.is_empty() {
+        if !description.is_empty() {
+            description.push_str("\n\n");
+        }
+        description.push_str("## Acceptance criteria\n");
+        for criterion in criteria {
+            description.push_str(&format!("\n- [ ] {criterion}"));
+        }
+    }
+
+    Some(GeneratedTaskDetails { title, description })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(title: &str, order: Option<u32>) -> RawSubtask {
+        RawSubtask {
+            title: title.to_string(),
+            description: Some(" details ".to_string()),
+            order,
+        }
+    }
+
+    #[test]
+    fn normalizes_order_and_drops_untitled() {
+        let subtasks = normalize_subtasks(vec![
+            raw("second", Some(5)),
+            raw("  ", Some(1)),
+            raw("first", Some(2)),
+            raw("last", None),
+        ]);
+        let titles: Vec<_> = subtasks.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["first", "second", "last"]);
+        assert_eq!(
+            subtasks.iter().map(|s| s.order).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(subtasks[0].description.as_deref(), Some("details"));
+    }
+
+    #[test]
+    fn appends_acceptance_criteria_to_description() {
+        let details = normalize_details(DetailsReply {
+            title: " Add dark mode ".to_string(),
+            description: "Support a dark theme.\n".to_string(),
+            acceptance_criteria: vec![
+                "- Theme toggle in settings".to_string(),
+                " ".to_string(),
+                "Choice persists across reloads".to_string(),
+            ],
+        })
+        .unwrap();
+        assert_eq!(details.title, "Add dark mode");
+        assert_eq!(
+            details.description,
+            "Support a dark theme.\n\n## Acceptance criteria\n\n- [ ] Theme toggle in settings\n- [ ] Choice persists across reloads"
+        );
+
+        assert!(
+            normalize_details(DetailsReply {
+                title: "  ".to_string(),
+                description: String::new(),
+                acceptance_criteria: Vec::new(),
+            })
+            .is_none()
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use db::models::task::Task;
+use serde::Serialize;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::services::{
+    config::{EmbeddingConfig, EmbeddingProvider},
+    llm::LlmService,
+};
+
+/// Size of the locally computed embedding vectors
+const LOCAL_DIMENSIONS: usize = 512;
+/// Most similar tasks reported per candidate
+const MAX_SIMILAR_TASKS: usize = 5;
+
+/// An existing task that looks like the same work as a new one.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct SimilarTask {
+    pub task_id: Uuid,
+    pub title: String,
+    /// Cosine similarity of the two tasks' embeddings, from 0 to 1
+    pub similarity: f32,
+}
+
+/// Text embedded for a task: its title followed by its description.
+pub fn task_text(title: &str, description: Option<&str>) -> String {
+    match description.map(str::trim).filter(|d| !d.is_empty()) {
+        Some(description) => format!("{}\n\n{}", title.trim(), description),
+        None => title.trim().to_string(),
+    }
+}
+
+fn default_threshold(provider: EmbeddingProvider) -> f32 {
+    match provider {
+        EmbeddingProvider::Local => 0.75,
+        EmbeddingProvider::OpenAi => 0.85,
+    }
+}
+
+/// For each candidate text, the existing tasks whose similarity is above the configured
+/// threshold, most similar first. Falls back to local embeddings when the API is unavailable.
+pub async fn find_similar(
+    llm: &LlmService,
+    config: &EmbeddingConfig,
+    existing: &[Task],
+    candidates: &[String],
+) -> Vec<Vec<SimilarTask>> {
+    if existing.is_empty() || candidates.is_empty() {
+        return vec![Vec::new(); candidates.len()];
+    }
+
+    let texts: Vec<String> = existing
+        .iter()
+        .map(|t| task_text(&t.title, t.description.as_deref()))
+        .chain(candidates.iter().cloned())
+        .collect();
+
+    let local = || texts.iter().map(|t| local_embedding(t)).collect::<Vec<_>>();
+    let (vectors, provider) = match config.provider {
+        EmbeddingProvider::Local => (local(), EmbeddingProvider::Local),
+        EmbeddingProvider::OpenAi => match llm.embed(config, &texts).await {
+            Ok(vectors) => (vectors, EmbeddingProvider::OpenAi),
+            Err(e) => {
+                tracing::warn!("Embedding request failed, using local embeddings: {}", e);
+                (local(), EmbeddingProvider::Local)
+            }
+        },
+    };
+    // A configured threshold is tuned for the configured provider, not the fallback
+    let threshold = config
+        .similarity_threshold
+        .filter(|_| provider == config.provider)
+        .unwrap_or_else(|| default_threshold(provider));
+
+    let (existing_vectors, candidate_vectors) = vectors.split_at(existing.len());
+    candidate_vectors
+        .iter()
+        .map(|candidate| {
+            let mut similar: Vec<SimilarTask> = existing
+                .iter()
+                .zip(existing_vectors)
+                .map(|(task, vector)| SimilarTask {
+                    task_id: task.id,
+                    title: task.title.clone(),
+                    similarity: cosine_similarity(candidate, vector),
+                })
+                .filter(|s| s.similarity >= threshold)
+                .collect();
+            similar.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+            similar.truncate(MAX_SIMILAR_TASKS);
+            similar
+        })
+        .collect()
+}
+
+/// Hashed bag of lowercase words and their character trigrams, normalized to unit length.
+pub(crate) fn local_embedding(text: &str) -> Vec<f32> {
+    let mut vector = vec![0.0f32; LOCAL_DIMENSIONS];
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        let word = word.to_lowercase();
+        vector[bucket(&word)] += 1.0;
+
+        let padded: Vec<char> = format!(" {word} ").chars().collect();
+        for gram in padded.windows(3) {
+            vector[bucket(&gram.iter().collect::<String>())] += 0.5;
+        }
+    }
+
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+/// FNV-1a hash of a feature, reduced to a vector index.
+fn bucket(feature: &str) -> usize {
+    let hash = feature.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    (hash % LOCAL_DIMENSIONS as u64) as usize
+}
+
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    (dot / (norm_a * norm_b)).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn similarity(a: &str, b: &str) -> f32 {
+        cosine_similarity(&local_embedding(a), &local_embedding(b))
+    }
+
+    #[test]
+    fn local_embeddings_rank_rewordings_above_unrelated_tasks() {
+        let threshold = default_threshold(EmbeddingProvider::Local);
+        assert!((similarity("Fix login bug", "fix LOGIN bug") - 1.0).abs() < 1e-5);
+        assert!(similarity("Add dark mode to settings", "Add dark-mode setting") > threshold);
+        assert!(
+            similarity("Add dark mode to settings", "Migrate database to Postgres") < threshold
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use db::models::{
+    redis_task_source::{RedisTaskSource, TaskSourceFieldMapping},
+    task::{CreateTask, Task, TaskStatus},
+};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::services::{
+    config::EmbeddingConfig,
+    llm::LlmService,
+    task_similarity::{self, SimilarTask},
+};
+
+/// A task read from an external source, ready for the import pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceTask {
+    /// Identifies the task in its source
+    pub external_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    pub url: Option<String>,
+}
+
+impl SourceTask {
+    pub fn to_create_task(&self, project_id: Uuid) -> CreateTask {
+        CreateTask {
+            project_id,
+            title: self.title.clone(),
+            description: self.description.clone(),
+            status: Some(self.status.clone()),
+            parent_workspace_id: None,
+            image_ids: None,
+            shared_task_id: None,
+            generate_details: None,
+        }
+    }
+}
+
+/// For each task, the most similar existing task of the project when it looks like a duplicate.
+pub async fn find_duplicates(
+    pool: &SqlitePool,
+    llm: &LlmService,
+    config: &EmbeddingConfig,
+    project_id: Uuid,
+    tasks: &[SourceTask],
+) -> Result<Vec<Option<SimilarTask>>, sqlx::Error> {
+    let existing: Vec<Task> = Task::find_by_project_id_with_attempt_status(pool, project_id)
+        .await?
+        .into_iter()
+        .map(|t| t.task)
+        .collect();
+    let candidates: Vec<String> = tasks
+        .iter()
+        .map(|t| task_similarity::task_text(&t.title, t.description.as_deref()))
+        .collect();
+    Ok(
+        task_similarity::find_similar(llm, config, &existing, &candidates)
+            .await
+            .into_iter()
+            .map(|similar| similar.into_iter().next())
+            .collect(),
+    )
+}
+
+/// Why a task is not imported by default when it looks like a duplicate.
+pub fn duplicate_reason(similar: &SimilarTask) -> String {
+    format!(
+        "Similar to existing task \"{}\" ({:.0}% match)",
+        similar.title,
+        similar.similarity * 100.0
+    )
+}
+
+/// The value at a dot-separated path, where numeric segments index into arrays.
+fn lookup<'a>(item: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(item, |value, segment| match value {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        })
+}
+
+/// The text at `path`, treating nulls and blank strings as missing.
+fn text_at(item: &Value, path: &str) -> Option<String> {
+    let text = match lookup(item, path)? {
+        Value::Null => return None,
+        Value::String(s) => s.trim().to_string(),
+        other => other.to_string(),
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// Map a raw item onto a task, or `None` when it has no id or title.
+pub fn map_item(
+    item: &Value,
+    fields: &TaskSourceFieldMapping,
+    statuses: &HashMap<String, TaskStatus>,
+) -> Option<SourceTask> {
+    let optional = |path: &Option<String>| path.as_deref().and_then(|p| text_at(item, p));
+    let status = optional(&fields.status)
+        .and_then(|raw| statuses.get(&raw).cloned())
+        .unwrap_or(TaskStatus::Todo);
+    Some(SourceTask {
+        external_id: text_at(item, &fields.id)?,
+        title: text_at(item, &fields.title)?,
+        description: optional(&fields.description),
+        status,
+        url: optional(&fields.url),
+    })
+}
+
+/// Map every item of a source, returning the tasks and the number of items skipped.
+pub fn map_items(source: &RedisTaskSource, items: &[Value]) -> (Vec<SourceTask>, usize) {
+    let tasks: Vec<SourceTask> = items
+        .iter()
+        .filter_map(|item| map_item(item, &source.field_mapping.0, &source.status_mapping.0))
+        .collect();
+    let skipped = items.len() - tasks.len();
+    if skipped > 0 {
+        tracing::warn!(
+            "Skipped {} items of task source '{}' without an id or title",
+            skipped,
+            source.name
+        );
+    }
+    (tasks, skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn maps_nested_fields_and_statuses() {
+        let fields = TaskSourceFieldMapping {
+            id: "key".to_string(),
+            title: "fields.summary".to_string(),
+            description: Some("fields.body".to_string()),
+            status: Some("fields.state".to_string()),
+            url: Some("links.0".to_string()),
+        };
+        let statuses = HashMap::from([("Doing".to_string(), TaskStatus::InProgress)]);
+
+        let item = json!({
+            "key": 42,
+            "fields": { "summary": " Fix login ", "body": null, "state": "Doing" },
+            "links": ["https://tracker/42"]
+        });
+        assert_eq!(
+            map_item(&item, &fields, &statuses),
+            Some(SourceTask {
+                external_id: "42".to_string(),
+                title: "Fix login".to_string(),
+                description: None,
+                status: TaskStatus::InProgress,
+                url: Some("https://tracker/42".to_string()),
+            })
+        );
+
+        let unmapped = json!({ "key": "a", "fields": { "summary": "x", "state": "Blocked" } });
+        assert_eq!(
+            map_item(&unmapped, &fields, &statuses).map(|t| t.status),
+            Some(TaskStatus::Todo)
+        );
+        assert_eq!(map_item(&json!({ "key": "b" }), &fields, &statuses), None);
+    }
+}
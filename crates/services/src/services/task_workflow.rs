@@ -0,0 +1,82 @@
+//! Per-project rules on which status moves a task may make.
+//!
+//! Rules are checked both when a user moves a task and when the system does so
+//! on its own (an attempt starting, finishing or being merged). User moves are
+//! rejected with the unmet requirements; automatic moves are skipped and the task
+//! keeps its status.
+
+use db::models::{
+    task::{Task, TaskStatus},
+    task_transition_rule::{TaskTransitionRule, TransitionRequirement},
+};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum TransitionError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Cannot move the task from {from} to {to}: {}", describe(.unmet))]
+    Blocked {
+        from: TaskStatus,
+        to: TaskStatus,
+        unmet: Vec<TransitionRequirement>,
+    },
+}
+
+fn describe(unmet: &[TransitionRequirement]) -> String {
+    unmet
+        .iter()
+        .map(|requirement| requirement.remediation())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Fail with the unmet requirements when the project's rules forbid moving `task` to `to`.
+pub async fn check_transition(
+    pool: &SqlitePool,
+    task: &Task,
+    to: &TaskStatus,
+) -> Result<(), TransitionError> {
+    if &task.status == to {
+        return Ok(());
+    }
+    let rules =
+        TaskTransitionRule::find_for_transition(pool, task.project_id, &task.status, to).await?;
+    let mut unmet = Vec::new();
+    for rule in rules {
+        if !unmet.contains(&rule.requirement) && !rule.requirement.is_met(pool, task.id).await? {
+            unmet.push(rule.requirement);
+        }
+    }
+    if unmet.is_empty() {
+        Ok(())
+    } else {
+        Err(TransitionError::Blocked {
+            from: task.status.clone(),
+            to: to.clone(),
+            unmet,
+        })
+    }
+}
+
+/// [`Task::update_status`] for moves the system makes on its own. A move the project's rules
+/// forbid is skipped, leaving the task where it is.
+pub async fn update_task_status(
+    pool: &SqlitePool,
+    task_id: Uuid,
+    status: TaskStatus,
+) -> Result<(), sqlx::Error> {
+    let Some(task) = Task::find_by_id(pool, task_id).await? else {
+        return Ok(());
+    };
+    match check_transition(pool, &task, &status).await {
+        Ok(()) => Task::update_status(pool, task_id, status).await,
+        Err(TransitionError::Database(e)) => Err(e),
+        Err(e @ TransitionError::Blocked { .. }) => {
+            tracing::info!("Skipping status change of task {task_id}: {e}");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Time spent on a task: wall-clock time of its coding agent runs, per attempt, and time people
+//! logged by hand.
+
+use db::models::{
+    execution_process::{AgentRunTime, ExecutionProcess},
+    task_time_entry::TaskTimeEntry,
+};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct TaskTime {
+    pub agent_seconds: f64,
+    #[ts(type = "number")]
+    pub human_seconds: i64,
+    /// Agent time of each attempt, oldest attempt first
+    pub attempts: Vec<AttemptTime>,
+    pub entries: Vec<TaskTimeEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct AttemptTime {
+    pub workspace_id: Uuid,
+    pub agent_seconds: f64,
+    pub runs: usize,
+}
+
+fn summarize(runs: &[AgentRunTime], entries: Vec<TaskTimeEntry>) -> TaskTime {
+    let mut attempts: Vec<AttemptTime> = Vec::new();
+    for run in runs {
+        match attempts
+            .iter_mut()
+            .find(|a| a.workspace_id == run.workspace_id)
+        {
+            Some(attempt) => {
+                attempt.agent_seconds += run.seconds();
+                attempt.runs += 1;
+            }
+            None => attempts.push(AttemptTime {
+                workspace_id: run.workspace_id,
+                agent_seconds: run.seconds(),
+                runs: 1,
+            }),
+        }
+    }
+    TaskTime {
+        agent_seconds: attempts.iter().map(|a| a.agent_seconds).sum(),
+        human_seconds: entries.iter().map(|e| e.seconds).sum(),
+        attempts,
+        entries,
+    }
+}
+
+pub async fn task_time(pool: &SqlitePool, task_id: Uuid) -> Result<TaskTime, sqlx::Error> {
+    let runs = ExecutionProcess::find_agent_run_times_for_task(pool, task_id).await?;
+    let entries = TaskTimeEntry::find_by_task_id(pool, task_id).await?;
+    Ok(summarize(&runs, entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use super::*;
+
+    #[test]
+    fn sums_agent_time_per_attempt() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let run = |workspace: u128, start: &str, end: &str| AgentRunTime {
+            task_id: Uuid::from_u128(1),
+            workspace_id: Uuid::from_u128(workspace),
+            started_at: at(start),
+            completed_at: at(end),
+        };
+        let runs = vec![
+            run(2, "2026-01-06T10:00:00Z", "2026-01-06T10:10:00Z"),
+            run(3, "2026-01-06T11:00:00Z", "2026-01-06T11:00:30Z"),
+            run(2, "2026-01-06T12:00:00Z", "2026-01-06T12:05:00Z"),
+        ];
+        let entries = vec![TaskTimeEntry {
+            id: Uuid::new_v4(),
+            task_id: Uuid::from_u128(1),
+            user_id: None,
+            seconds: 1200,
+            note: Some("Reviewed the diff".to_string()),
+            worked_at: at("2026-01-06T13:00:00Z"),
+            created_at: at("2026-01-06T13:00:00Z"),
+        }];
+
+        let time = summarize(&runs, entries);
+        assert_eq!(
+            time.attempts
+                .iter()
+                .map(|a| (a.workspace_id, a.agent_seconds, a.runs))
+                .collect::<Vec<_>>(),
+            vec![
+                (Uuid::from_u128(2), 900.0, 2),
+                (Uuid::from_u128(3), 30.0, 1)
+            ]
+        );
+        assert_eq!(time.agent_seconds, 930.0);
+        assert_eq!(time.human_seconds, 1200);
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::{BTreeMap, HashMap};
+
+use db::models::tool_usage::{CreateToolUsage, ToolUsage, ToolUsageStatus};
+use executors::logs::{
+    NormalizedEntryType, ToolStatus, utils::patch::extract_normalized_entry_from_patch,
+};
+use serde::Serialize;
+use ts_rs::TS;
+use utils::log_msg::LogMsg;
+
+/// How often agents used a tool, how often it failed and how long approvals held it up.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ToolStats {
+    pub tool_name: String,
+    pub calls: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Rejected by a reviewer or by the attempt's tool restrictions
+    pub denied: usize,
+    /// Approval requests nobody answered in time
+    pub timed_out: usize,
+    /// Share of finished calls that failed, from 0 to 1
+    pub failure_rate: Option<f64>,
+    /// Calls that waited for a reviewer's decision
+    pub approvals: usize,
+    pub avg_approval_latency_seconds: Option<f64>,
+}
+
+fn usage_status(status: &ToolStatus) -> ToolUsageStatus {
+    match status {
+        ToolStatus::Success => ToolUsageStatus::Succeeded,
+        ToolStatus::Failed => ToolUsageStatus::Failed,
+        ToolStatus::Denied { .. } => ToolUsageStatus::Denied,
+        ToolStatus::TimedOut => ToolUsageStatus::TimedOut,
+        ToolStatus::Created
+        | ToolStatus::PendingApproval { .. }
+        | ToolStatus::PendingQuestion { .. } => ToolUsageStatus::Unfinished,
+    }
+}
+
+/// Tool calls in a run's normalized log, in the order they were made. `approval_waits` maps
+/// log entry indexes to how long the call waited for approval, in milliseconds.
+pub fn usages_from_history(
+    history: &[LogMsg],
+    approval_waits: &HashMap<usize, i64>,
+) -> Vec<CreateToolUsage> {
+    // Entries are replaced as the call progresses, the last version has the final status
+    let mut entries = BTreeMap::new();
+    for msg in history {
+        if let LogMsg::JsonPatch(patch) = msg
+            && let Some((index, entry)) = extract_normalized_entry_from_patch(patch)
+        {
+            entries.insert(index, entry);
+        }
+    }
+
+    entries
+        .into_iter()
+        .filter_map(|(index, entry)| match entry.entry_type {
+            NormalizedEntryType::ToolUse {
+                tool_name, status, ..
+            } => Some(CreateToolUsage {
+                tool_name,
+                status: usage_status(&status),
+                approval_wait_ms: approval_waits.get(&index).copied(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Aggregate recorded tool calls per tool, most used first.
+pub fn aggregate(usages: &[ToolUsage]) -> Vec<ToolStats> {
+    let mut by_tool: HashMap<&str, Vec<&ToolUsage>> = HashMap::new();
+    for usage in usages {
+        by_tool.entry(&usage.tool_name).or_default().push(usage);
+    }
+
+    let mut stats: Vec<ToolStats> = by_tool
+        .into_iter()
+        .map(|(tool_name, usages)| {
+            let count = |status| usages.iter().filter(|u| u.status == status).count();
+            let succeeded = count(ToolUsageStatus::Succeeded);
+            let failed = count(ToolUsageStatus::Failed);
+            let waits: Vec<i64> = usages
+                .iter()
+                .filter_map(|u| u.approval_wait_ms)
+                .map(|ms| ms.max(0))
+                .collect();
+            ToolStats {
+                tool_name: tool_name.to_string(),
+                calls: usages.len(),
+                succeeded,
+                failed,
+                denied: count(ToolUsageStatus::Denied),
+                timed_out: count(ToolUsageStatus::TimedOut),
+                failure_rate: (succeeded + failed > 0)
+                    .then(|| failed as f64 / (succeeded + failed) as f64),
+                approvals: waits.len(),
+                avg_approval_latency_seconds: (!waits.is_empty())
+                    .then(|| waits.iter().sum::<i64>() as f64 / waits.len() as f64 / 1000.0),
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| {
+        b.calls
+            .cmp(&a.calls)
+            .then_with(|| a.tool_name.cmp(&b.tool_name))
+    });
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::logs::{ActionType, NormalizedEntry, utils::patch::ConversationPatch};
+
+    use super::*;
+
+    fn tool_use(tool_name: &str, status: ToolStatus) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::ToolUse {
+                tool_name: tool_name.to_string(),
+                action_type: ActionType::Other {
+                    description: tool_name.to_string(),
+                },
+                status,
+            },
+            content: tool_name.to_string(),
+            metadata: None,
+        }
+    }
+
+    fn usage(tool_name: &str, status: ToolUsageStatus, approval_wait_ms: Option<i64>) -> ToolUsage {
+        ToolUsage {
+            tool_name: tool_name.to_string(),
+            status,
+            approval_wait_ms,
+        }
+    }
+
+    #[test]
+    fn takes_final_status_of_each_call() {
+        let history: Vec<LogMsg> = [
+            ConversationPatch::add_normalized_entry(0, tool_use("Bash", ToolStatus::Created)),
+            ConversationPatch::add_normalized_entry(1, tool_use("Read", ToolStatus::Created)),
+            ConversationPatch::replace(0, tool_use("Bash", ToolStatus::Failed)),
+            ConversationPatch::replace(1, tool_use("Read", ToolStatus::Success)),
+            ConversationPatch::add_normalized_entry(2, tool_use("Edit", ToolStatus::Created)),
+        ]
+        .into_iter()
+        .map(LogMsg::JsonPatch)
+        .collect();
+
+        let usages = usages_from_history(&history, &HashMap::from([(0, 1500)]));
+        let summary: Vec<_> = usages
+            .iter()
+            .map(|u| (u.tool_name.as_str(), u.status, u.approval_wait_ms))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Bash", ToolUsageStatus::Failed, Some(1500)),
+                ("Read", ToolUsageStatus::Succeeded, None),
+                ("Edit", ToolUsageStatus::Unfinished, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn aggregates_failures_and_approval_latency() {
+        let usages = vec![
+            usage("Bash", ToolUsageStatus::Succeeded, Some(2000)),
+            usage("Bash", ToolUsageStatus::Failed, Some(4000)),
+            usage("Bash", ToolUsageStatus::Denied, Some(6000)),
+            usage("Bash", ToolUsageStatus::Succeeded, None),
+            usage("Read", ToolUsageStatus::Succeeded, None),
+        ];
+
+        let stats = aggregate(&usages);
+        assert_eq!(stats.len(), 2);
+        let bash = &stats[0];
+        assert_eq!(bash.tool_name, "Bash");
+        assert_eq!(bash.calls, 4);
+        assert_eq!(bash.denied, 1);
+        assert_eq!(bash.failure_rate, Some(1.0 / 3.0));
+        assert_eq!(bash.approvals, 3);
+        assert_eq!(bash.avg_approval_latency_seconds, Some(4.0));
+
+        let read = &stats[1];
+        assert_eq!(read.failure_rate, Some(0.0));
+        assert_eq!(read.avg_approval_latency_seconds, None);
+    }
+}
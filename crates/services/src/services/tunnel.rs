@@ -0,0 +1,300 @@
+//! Public tunnels for sharing a running dev server with stakeholders.
+//!
+//! Tunnels are backed by an external CLI (cloudflared, ngrok, ...) spawned per
+//! dev server. New providers only need to describe how to launch the CLI and how
+//! to spot the public URL in its output.
+
+use std::{collections::HashMap, process::Stdio, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    process::{Child, Command},
+    sync::{RwLock, mpsc},
+};
+use ts_rs::TS;
+use uuid::Uuid;
+
+const TUNNEL_STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Error)]
+pub enum TunnelError {
+    #[error("{0} is not installed or not in PATH")]
+    ProviderUnavailable(&'static str),
+    #[error("Failed to start tunnel: {0}")]
+    Spawn(#[from] std::io::Error),
+    #[error("Tunnel did not report a public URL within {0:?}")]
+    Timeout(Duration),
+    #[error("Tunnel process exited before reporting a public URL")]
+    Exited,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum TunnelProviderKind {
+    #[default]
+    Cloudflared,
+    Ngrok,
+}
+
+impl TunnelProviderKind {
+    fn provider(self) -> Box<dyn TunnelProvider> {
+        match self {
+            TunnelProviderKind::Cloudflared => Box::new(CloudflaredProvider::new()),
+            TunnelProviderKind::Ngrok => Box::new(NgrokProvider::new()),
+        }
+    }
+}
+
+/// Describes how to run a tunnel CLI and extract the public URL it allocates.
+pub trait TunnelProvider: Send + Sync {
+    fn binary(&self) -> &'static str;
+
+    fn command(&self, local_url: &str) -> Command;
+
+    /// Return the public URL if this output line announces it.
+    fn parse_public_url(&self, line: &str) -> Option<String>;
+}
+
+pub struct CloudflaredProvider {
+    url_re: Regex,
+}
+
+impl CloudflaredProvider {
+    pub fn new() -> Self {
+        Self {
+            url_re: Regex::new(r"https://[a-z0-9-]+\.trycloudflare\.com").expect("valid regex"),
+        }
+    }
+}
+
+impl Default for CloudflaredProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TunnelProvider for CloudflaredProvider {
+    fn binary(&self) -> &'static str {
+        "cloudflared"
+    }
+
+    fn command(&self, local_url: &str) -> Command {
+        let mut cmd = Command::new(self.binary());
+        cmd.args(["tunnel", "--no-autoupdate", "--url", local_url]);
+        cmd
+    }
+
+    fn parse_public_url(&self, line: &str) -> Option<String> {
+        self.url_re.find(line).map(|m| m.as_str().to_string())
+    }
+}
+
+pub struct NgrokProvider {
+    url_re: Regex,
+}
+
+impl NgrokProvider {
+    pub fn new() -> Self {
+        Self {
+            url_re: Regex::new(r#"url=(https://[^\s"]+)"#).expect("valid regex"),
+        }
+    }
+}
+
+impl Default for NgrokProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TunnelProvider for NgrokProvider {
+    fn binary(&self) -> &'static str {
+        "ngrok"
+    }
+
+    fn command(&self, local_url: &str) -> Command {
+        let mut cmd = Command::new(self.binary());
+        cmd.args(["http", local_url, "--log", "stdout"]);
+        cmd
+    }
+
+    fn parse_public_url(&self, line: &str) -> Option<String> {
+        self.url_re
+            .captures(line)
+            .and_then(|caps| caps.get(1))
+            .map(|m| m.as_str().to_string())
+    }
+}
+
+/// A tunnel currently exposing a dev server.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ActiveTunnel {
+    pub execution_process_id: Uuid,
+    pub provider: TunnelProviderKind,
+    pub local_url: String,
+    pub public_url: String,
+    #[ts(type = "Date")]
+    pub created_at: DateTime<Utc>,
+}
+
+struct TunnelEntry {
+    info: ActiveTunnel,
+    child: Child,
+}
+
+/// Tracks tunnels keyed by the dev server execution process they expose.
+#[derive(Clone, Default)]
+pub struct TunnelService {
+    tunnels: Arc<RwLock<HashMap<Uuid, TunnelEntry>>>,
+}
+
+impl TunnelService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn get(&self, execution_process_id: &Uuid) -> Option<ActiveTunnel> {
+        self.tunnels
+            .read()
+            .await
+            .get(execution_process_id)
+            .map(|entry| entry.info.clone())
+    }
+
+    /// Open a tunnel to `local_url`, replacing any existing tunnel for the process.
+    pub async fn open(
+        &self,
+        execution_process_id: Uuid,
+        local_url: &str,
+        kind: TunnelProviderKind,
+    ) -> Result<ActiveTunnel, TunnelError> {
+        self.close(&execution_process_id).await;
+
+        let provider = kind.provider();
+        let mut child = provider
+            .command(local_url)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => TunnelError::ProviderUnavailable(provider.binary()),
+                _ => TunnelError::Spawn(e),
+            })?;
+
+        // Both cloudflared and ngrok may log the URL to either stream
+        let (line_tx, mut line_rx) = mpsc::unbounded_channel::<String>();
+        if let Some(stdout) = child.stdout.take() {
+            spawn_line_forwarder(stdout, line_tx.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            spawn_line_forwarder(stderr, line_tx);
+        }
+
+        let public_url = tokio::time::timeout(TUNNEL_STARTUP_TIMEOUT, async {
+            while let Some(line) = line_rx.recv().await {
+                if let Some(url) = provider.parse_public_url(&line) {
+                    return Ok(url);
+                }
+            }
+            Err(TunnelError::Exited)
+        })
+        .await
+        .unwrap_or(Err(TunnelError::Timeout(TUNNEL_STARTUP_TIMEOUT)));
+
+        let public_url = match public_url {
+            Ok(url) => url,
+            Err(e) => {
+                let _ = child.kill().await;
+                return Err(e);
+            }
+        };
+
+        // Keep draining output so the tunnel process never blocks on a full pipe
+        tokio::spawn(async move { while line_rx.recv().await.is_some() {} });
+
+        let info = ActiveTunnel {
+            execution_process_id,
+            provider: kind,
+            local_url: local_url.to_string(),
+            public_url,
+            created_at: Utc::now(),
+        };
+        tracing::info!(
+            "Opened {} tunnel {} -> {}",
+            provider.binary(),
+            info.public_url,
+            info.local_url
+        );
+
+        self.tunnels.write().await.insert(
+            execution_process_id,
+            TunnelEntry {
+                info: info.clone(),
+                child,
+            },
+        );
+        Ok(info)
+    }
+
+    /// Tear down the tunnel for a process, if one is open.
+    pub async fn close(&self, execution_process_id: &Uuid) -> Option<ActiveTunnel> {
+        let mut entry = self.tunnels.write().await.remove(execution_process_id)?;
+        if let Err(e) = entry.child.kill().await {
+            tracing::warn!(
+                "Failed to kill tunnel process for {}: {}",
+                execution_process_id,
+                e
+            );
+        }
+        tracing::info!("Closed tunnel {}", entry.info.public_url);
+        Some(entry.info)
+    }
+}
+
+fn spawn_line_forwarder<R>(reader: R, tx: mpsc::UnboundedSender<String>)
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_provider_urls() {
+        let cloudflared = CloudflaredProvider::new();
+        assert_eq!(
+            cloudflared
+                .parse_public_url("INF |  https://quiet-river-1234.trycloudflare.com  |")
+                .as_deref(),
+            Some("https://quiet-river-1234.trycloudflare.com")
+        );
+        assert_eq!(cloudflared.parse_public_url("INF Starting tunnel"), None);
+
+        let ngrok = NgrokProvider::new();
+        assert_eq!(
+            ngrok
+                .parse_public_url(
+                    r#"t=2024 lvl=info msg="started tunnel" name=command_line addr=http://localhost:5173 url=https://ab12.ngrok-free.app"#
+                )
+                .as_deref(),
+            Some("https://ab12.ngrok-free.app")
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Canned answers for questions agents keep asking, configured per project as rules that pick
+//! an option when the question text or header matches.
+
+use db::{
+    DBService,
+    models::{
+        execution_process::ExecutionProcess, question_auto_answer_rule::QuestionAutoAnswerRule,
+    },
+};
+use regex::Regex;
+use utils::user_questions::{QuestionAnswer, UserQuestion, UserQuestionRequest};
+
+/// Answers questions from the rules of the question's project.
+#[derive(Clone)]
+pub struct QuestionAutoAnswers {
+    db: DBService,
+}
+
+/// Answers for every question of a request, with a line per question saying which rule chose
+/// what
+#[derive(Debug, Clone)]
+pub struct AutoAnswer {
+    pub answers: Vec<QuestionAnswer>,
+    pub summary: String,
+}
+
+impl QuestionAutoAnswers {
+    pub fn new(db: DBService) -> Self {
+        Self { db }
+    }
+
+    /// Answer the request if the project's rules cover all of its questions
+    pub(super) async fn answer(&self, request: &UserQuestionRequest) -> Option<AutoAnswer> {
+        let pool = &self.db.pool;
+        let ctx = ExecutionProcess::load_context(pool, request.execution_process_id)
+            .await
+            .ok()?;
+        let rules = match QuestionAutoAnswerRule::find_by_project_id(pool, ctx.project.id).await {
+            Ok(rules) => rules,
+            Err(e) => {
+                tracing::warn!("Failed to load question auto-answer rules: {}", e);
+                return None;
+            }
+        };
+        match_rules(&rules, &request.questions)
+    }
+}
+
+fn matches(pattern: &str, text: &str) -> bool {
+    match Regex::new(pattern) {
+        Ok(regex) => regex.is_match(text),
+        Err(e) => {
+            tracing::warn!("Ignoring invalid auto-answer pattern '{}': {}", pattern, e);
+            false
+        }
+    }
+}
+
+/// Option the rule selects for the question, if the rule applies to it
+fn select(rule: &QuestionAutoAnswerRule, question: &UserQuestion) -> Option<usize> {
+    if !rule.enabled || !matches(&rule.question_pattern, &question.question) {
+        return None;
+    }
+    if let Some(header_pattern) = &rule.header_pattern
+        && !question
+            .header
+            .as_deref()
+            .is_some_and(|header| matches(header_pattern, header))
+    {
+        return None;
+    }
+    question.options.iter().position(|option| {
+        option
+            .label
+            .trim()
+            .eq_ignore_ascii_case(rule.option_label.trim())
+    })
+}
+
+/// Answer every question with the first rule that applies to it. A request with a question no
+/// rule covers is left for a reviewer entirely.
+pub fn match_rules(
+    rules: &[QuestionAutoAnswerRule],
+    questions: &[UserQuestion],
+) -> Option<AutoAnswer> {
+    if questions.is_empty() {
+        return None;
+    }
+    let mut answers = Vec::with_capacity(questions.len());
+    let mut lines = Vec::with_capacity(questions.len());
+    for (question_index, question) in questions.iter().enumerate() {
+        let (rule, option) = rules
+            .iter()
+            .find_map(|rule| select(rule, question).map(|option| (rule, option)))?;
+        answers.push(QuestionAnswer {
+            question_index,
+            selected_options: vec![option],
+            custom_text: None,
+            attachments: None,
+        });
+        lines.push(format!(
+            "Auto-answered \"{}\" with \"{}\" (rule \"{}\")",
+            question.question, question.options[option].label, rule.name
+        ));
+    }
+    Some(AutoAnswer {
+        answers,
+        summary: lines.join("\n"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use utils::user_questions::QuestionOption;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn rule(
+        question_pattern: &str,
+        header_pattern: Option<&str>,
+        label: &str,
+    ) -> QuestionAutoAnswerRule {
+        QuestionAutoAnswerRule {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            name: label.to_string(),
+            question_pattern: question_pattern.to_string(),
+            header_pattern: header_pattern.map(str::to_string),
+            option_label: label.to_string(),
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn question(text: &str, header: Option<&str>, labels: &[&str]) -> UserQuestion {
+        UserQuestion {
+            question: text.to_string(),
+            header: header.map(str::to_string),
+            options: labels
+                .iter()
+                .map(|label| QuestionOption {
+                    label: label.to_string(),
+                    description: None,
+                    attachments: None,
+                })
+                .collect(),
+            multi_select: false,
+            attachments: None,
+        }
+    }
+
+    #[test]
+    fn selects_the_option_of_the_first_matching_rule() {
+        let rules = vec![
+            rule("(?i)run the tests", Some("^Tests$"), "yes"),
+            rule("(?i)run the tests", None, "No"),
+        ];
+        let questions = vec![question(
+            "Should I run the tests?",
+            Some("Tests"),
+            &["Yes", "No"],
+        )];
+
+        let answer = match_rules(&rules, &questions).unwrap();
+        assert_eq!(answer.answers[0].selected_options, vec![0]);
+
+        let questions = vec![question(
+            "Should I run the tests?",
+            Some("Checks"),
+            &["Yes", "No"],
+        )];
+        let answer = match_rules(&rules, &questions).unwrap();
+        assert_eq!(answer.answers[0].selected_options, vec![1]);
+    }
+
+    #[test]
+    fn leaves_requests_with_uncovered_questions_alone() {
+        let mut disabled = rule("deploy", None, "Yes");
+        disabled.enabled = false;
+        let rules = vec![rule("tests", None, "Yes"), disabled];
+
+        let questions = vec![
+            question("Run the tests?", None, &["Yes", "No"]),
+            question("Deploy now?", None, &["Yes", "No"]),
+        ];
+        assert!(match_rules(&rules, &questions).is_none());
+
+        let questions = vec![question("Run the tests?", None, &["Sure", "No"])];
+        assert!(match_rules(&rules, &questions).is_none());
+    }
+}
@@ -0,0 +1,126 @@
+//! Reminders for agent questions nobody has answered yet, following the escalation chain of
+//! the question's project.
+
+use db::{
+    DBService,
+    models::{
+        execution_process::ExecutionProcess,
+        notification::{CreateNotification, NotificationKind},
+        question_escalation::{EscalationChannel, EscalationStep, ProjectQuestionEscalation},
+        slack::SlackIntegration,
+    },
+};
+use uuid::Uuid;
+
+use crate::services::{
+    notification::NotificationService,
+    secret_cipher::SecretCipher,
+    slack::{SlackClient, task_url},
+};
+
+/// Sends the escalation reminders of unanswered questions.
+#[derive(Clone)]
+pub struct QuestionEscalation {
+    db: DBService,
+    notifications: NotificationService,
+    cipher: SecretCipher,
+}
+
+/// Escalation chain that applies to one question
+#[derive(Debug, Clone)]
+pub(super) struct EscalationPlan {
+    project_id: Uuid,
+    task_id: Uuid,
+    task_title: String,
+    pub steps: Vec<EscalationStep>,
+}
+
+impl QuestionEscalation {
+    pub fn new(db: DBService, notifications: NotificationService, cipher: SecretCipher) -> Self {
+        Self {
+            db,
+            notifications,
+            cipher,
+        }
+    }
+
+    /// The chain configured for the project the execution process belongs to, if any
+    pub(super) async fn plan(&self, execution_process_id: Uuid) -> Option<EscalationPlan> {
+        let pool = &self.db.pool;
+        let ctx = ExecutionProcess::load_context(pool, execution_process_id)
+            .await
+            .ok()?;
+        let escalation =
+            match ProjectQuestionEscalation::find_by_project_id(pool, ctx.project.id).await {
+                Ok(escalation) => escalation?,
+                Err(e) => {
+                    tracing::warn!("Failed to load question escalation chain: {}", e);
+                    return None;
+                }
+            };
+        let mut steps = escalation.steps.0;
+        if steps.is_empty() {
+            return None;
+        }
+        steps.sort_by_key(|step| step.after_minutes);
+        Some(EscalationPlan {
+            project_id: ctx.project.id,
+            task_id: ctx.task.id,
+            task_title: ctx.task.title,
+            steps,
+        })
+    }
+
+    pub(super) async fn escalate(&self, plan: &EscalationPlan, step: &EscalationStep) {
+        let title = "Question still unanswered";
+        let message = format!(
+            "The agent working on \"{}\" has waited {} minute{} for an answer",
+            plan.task_title,
+            step.after_minutes,
+            if step.after_minutes == 1 { "" } else { "s" }
+        );
+        match &step.channel {
+            EscalationChannel::Notify => {
+                self.notifications
+                    .notify(CreateNotification {
+                        kind: NotificationKind::QuestionPending,
+                        title: title.to_string(),
+                        message,
+                        project_id: Some(plan.project_id),
+                        task_id: Some(plan.task_id),
+                    })
+                    .await;
+            }
+            EscalationChannel::User { user_id } => {
+                if let Err(e) = self
+                    .notifications
+                    .push_to_user(*user_id, title, &message)
+                    .await
+                {
+                    tracing::warn!("Failed to escalate question to user {}: {}", user_id, e);
+                }
+            }
+            EscalationChannel::Slack { channel_id } => {
+                if let Err(e) = self.post_to_slack(plan, channel_id).await {
+                    tracing::warn!("Failed to escalate question to Slack: {}", e);
+                }
+            }
+        }
+    }
+
+    async fn post_to_slack(&self, plan: &EscalationPlan, channel_id: &str) -> anyhow::Result<()> {
+        let integration = SlackIntegration::find_by_project_id(&self.db.pool, plan.project_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("the project has no Slack app"))?;
+        let bot_token = self.cipher.decrypt(&integration.bot_token)?;
+        let text = format!(
+            "An agent is waiting for an answer on <{}|{}>",
+            task_url(&integration.app_url, plan.project_id, plan.task_id),
+            plan.task_title
+        );
+        SlackClient::new(bot_token)
+            .post_message(channel_id, &text)
+            .await?;
+        Ok(())
+    }
+}
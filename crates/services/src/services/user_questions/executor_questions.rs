@@ -1,7 +1,15 @@
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 use async_trait::async_trait;
-use db::{self, DBService};
+use db::{
+    self, DBService,
+    models::{
+        agent_question::{AgentQuestion, AgentQuestionOutcome},
+        execution_process::{ExecutionContext, ExecutionProcess},
+        image::TaskImage,
+        notification::{CreateNotification, NotificationKind},
+    },
+};
 use executors::approvals::{ExecutorQuestionError, ExecutorQuestionService};
 use utils::user_questions::{
     CreateUserQuestionRequest, UserQuestion, UserQuestionRequest, UserQuestionResponse,
@@ -9,12 +17,19 @@ use utils::user_questions::{
 use uuid::Uuid;
 
 use super::UserQuestions;
-use crate::services::{approvals::ensure_task_in_review, notification::NotificationService};
+use crate::services::{
+    approvals::ensure_task_in_review,
+    events::{EntityEvent, EntityEventBus},
+    image::ImageService,
+    notification::NotificationService,
+};
 
 pub struct ExecutorQuestionBridge {
     questions: UserQuestions,
     db: DBService,
     notification_service: NotificationService,
+    image_service: ImageService,
+    entity_events: EntityEventBus,
     execution_process_id: Uuid,
 }
 
@@ -23,15 +38,89 @@ impl ExecutorQuestionBridge {
         questions: UserQuestions,
         db: DBService,
         notification_service: NotificationService,
+        image_service: ImageService,
+        entity_events: EntityEventBus,
         execution_process_id: Uuid,
     ) -> Arc<Self> {
         Arc::new(Self {
             questions,
             db,
             notification_service,
+            image_service,
+            entity_events,
             execution_process_id,
         })
     }
+
+    /// Store worktree images referenced by the agent's questions so the UI can display them.
+    async fn store_attachments(&self, questions: &mut [UserQuestion], ctx: &ExecutionContext) {
+        let Some(worktree) = ctx.workspace.container_ref.as_deref().map(Path::new) else {
+            return;
+        };
+
+        let mut attachments = Vec::new();
+        for question in questions.iter_mut() {
+            attachments.extend(question.attachments.iter_mut().flatten());
+            for option in question.options.iter_mut() {
+                attachments.extend(option.attachments.iter_mut().flatten());
+            }
+        }
+
+        let mut stored = Vec::new();
+        for attachment in attachments {
+            let Some(path) = attachment.path.as_deref() else {
+                continue;
+            };
+            if attachment.image_id.is_some() {
+                continue;
+            }
+            match self
+                .image_service
+                .store_worktree_image(worktree, path)
+                .await
+            {
+                Ok(image) => {
+                    attachment.image_id = Some(image.id);
+                    stored.push(image.id);
+                }
+                // Non-image files stay as plain path references
+                Err(e) => tracing::debug!("Not storing question attachment {}: {}", path, e),
+            }
+        }
+
+        // Linking to the task keeps the orphaned image cleanup from removing them
+        if !stored.is_empty()
+            && let Err(e) =
+                TaskImage::associate_many_dedup(&self.db.pool, ctx.task.id, &stored).await
+        {
+            tracing::warn!("Failed to link question attachments to task: {}", e);
+        }
+    }
+
+    /// Keep a record of the request for question statistics
+    async fn record(&self, request: &UserQuestionRequest) {
+        if let Err(e) = AgentQuestion::create(
+            &self.db.pool,
+            &request.id,
+            self.execution_process_id,
+            request.questions.len() as i64,
+            request.created_at,
+        )
+        .await
+        {
+            tracing::warn!("Failed to record agent question {}: {}", request.id, e);
+        }
+    }
+
+    async fn record_outcome(&self, question_id: &str, outcome: AgentQuestionOutcome) {
+        if let Err(e) = AgentQuestion::resolve(&self.db.pool, question_id, outcome).await {
+            tracing::warn!(
+                "Failed to record outcome of question {}: {}",
+                question_id,
+                e
+            );
+        }
+    }
 }
 
 #[async_trait]
@@ -39,9 +128,14 @@ impl ExecutorQuestionService for ExecutorQuestionBridge {
     async fn request_user_question(
         &self,
         tool_call_id: &str,
-        questions: Vec<UserQuestion>,
+        mut questions: Vec<UserQuestion>,
     ) -> Result<UserQuestionResponse, ExecutorQuestionError> {
-        ensure_task_in_review(&self.db.pool, self.execution_process_id).await;
+        let context = ExecutionProcess::load_context(&self.db.pool, self.execution_process_id)
+            .await
+            .ok();
+        if let Some(ctx) = &context {
+            self.store_attachments(&mut questions, ctx).await;
+        }
 
         let request = UserQuestionRequest::from_create(
             CreateUserQuestionRequest {
@@ -51,26 +145,71 @@ impl ExecutorQuestionService for ExecutorQuestionBridge {
             self.execution_process_id,
         );
 
-        let (_, waiter) = self
+        let (request, waiter) = self
             .questions
             .create_with_waiter(request)
             .await
             .map_err(|e| ExecutorQuestionError::request_failed(e.to_string()))?;
+        self.record(&request).await;
+
+        // Answered by one of the project's auto-answer rules, nobody needs to be told
+        if self.questions.is_completed(&request.id) {
+            self.record_outcome(&request.id, AgentQuestionOutcome::AutoAnswered)
+                .await;
+            return waiter.await.ok_or(ExecutorQuestionError::TimedOut);
+        }
+
+        ensure_task_in_review(&self.db.pool, self.execution_process_id).await;
+
+        if let Some(ctx) = &context {
+            self.entity_events.publish(
+                Some(ctx.task.project_id),
+                EntityEvent::QuestionPending {
+                    question_id: request.id.clone(),
+                    execution_process_id: self.execution_process_id,
+                    task_id: ctx.task.id,
+                },
+            );
+        }
 
         // Play notification sound when question needs answering
         let question_count = questions.len();
         self.notification_service
-            .notify(
-                "Question from Agent",
-                &format!(
+            .notify(CreateNotification {
+                kind: NotificationKind::QuestionPending,
+                title: "Question from Agent".to_string(),
+                message: format!(
                     "Agent is asking {} question{}",
                     question_count,
                     if question_count == 1 { "" } else { "s" }
                 ),
-            )
+                project_id: context.as_ref().map(|ctx| ctx.task.project_id),
+                task_id: context.as_ref().map(|ctx| ctx.task.id),
+            })
             .await;
 
         let response = waiter.clone().await;
+        self.record_outcome(
+            &request.id,
+            if response.is_some() {
+                AgentQuestionOutcome::Answered
+            } else {
+                AgentQuestionOutcome::TimedOut
+            },
+        )
+        .await;
+
+        if let Some(ctx) = &context {
+            self.entity_events.publish(
+                Some(ctx.task.project_id),
+                EntityEvent::QuestionAnswered {
+                    question_id: request.id.clone(),
+                    execution_process_id: self.execution_process_id,
+                    task_id: ctx.task.id,
+                    answered: response.is_some(),
+                },
+            );
+        }
 
         match response {
             Some(r) => Ok(r),
@@ -1,11 +1,21 @@
+pub mod auto_answer;
+pub mod escalation;
 pub mod executor_questions;
 
-use std::{collections::HashMap, sync::Arc, time::Duration as StdDuration};
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration as StdDuration, Instant},
+};
 
 use dashmap::DashMap;
 use db::models::{
+    agent_request_response::{AgentRequestKind, AgentRequestResponse},
     execution_process::ExecutionProcess,
-    task::{Task, TaskStatus},
+    task::TaskStatus,
 };
 use executors::{
     approvals::ToolCallMetadata,
@@ -21,27 +31,48 @@ use tokio::sync::{RwLock, oneshot};
 use utils::{
     log_msg::LogMsg,
     msg_store::MsgStore,
-    user_questions::{UserQuestion, UserQuestionRequest, UserQuestionResponse},
+    user_questions::{QuestionClaim, UserQuestionRequest, UserQuestionResponse},
 };
 use uuid::Uuid;
 
+use self::{auto_answer::QuestionAutoAnswers, escalation::QuestionEscalation};
+use crate::services::task_workflow;
+
 #[derive(Debug)]
 struct PendingQuestion {
     entry_index: usize,
     entry: NormalizedEntry,
-    execution_process_id: Uuid,
-    #[allow(dead_code)]
-    questions: Vec<UserQuestion>,
+    request: UserQuestionRequest,
     response_tx: oneshot::Sender<UserQuestionResponse>,
 }
 
+/// An answer waiting out its grace period before it reaches the agent
+#[derive(Debug)]
+struct HeldAnswer {
+    pending: PendingQuestion,
+    response: UserQuestionResponse,
+    responder: Option<Uuid>,
+    /// Identifies the grace timer that may deliver this answer, so a timer left over from a
+    /// withdrawn answer can't deliver a later one early
+    generation: u64,
+}
+
 type QuestionWaiter = Shared<BoxFuture<'static, Option<UserQuestionResponse>>>;
 
+/// How long a response is replayed to retries carrying the same idempotency key
+const IDEMPOTENCY_KEY_TTL: StdDuration = StdDuration::from_secs(24 * 60 * 60);
+
 #[derive(Clone)]
 pub struct UserQuestions {
     pending: Arc<DashMap<String, PendingQuestion>>,
+    held: Arc<DashMap<String, HeldAnswer>>,
+    held_generation: Arc<AtomicU64>,
     completed: Arc<DashMap<String, UserQuestionResponse>>,
+    /// Responses by question id, caller and idempotency key
+    idempotent: Arc<DashMap<(String, Option<Uuid>, String), (UserQuestionResponse, Instant)>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+    escalation: Option<QuestionEscalation>,
+    auto_answers: Option<QuestionAutoAnswers>,
 }
 
 #[derive(Debug, Error)]
@@ -50,6 +81,10 @@ pub enum QuestionError {
     NotFound,
     #[error("question request already completed")]
     AlreadyCompleted,
+    #[error("question is being answered by {}", .0.name)]
+    Claimed(QuestionClaim),
+    #[error("only the reviewer who answered can change the answer")]
+    NotResponder,
     #[error("no executor session found for session_id: {0}")]
     NoExecutorSession(String),
     #[error("corresponding tool use entry not found for question request")]
@@ -64,11 +99,28 @@ impl UserQuestions {
     pub fn new(msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>) -> Self {
         Self {
             pending: Arc::new(DashMap::new()),
+            held: Arc::new(DashMap::new()),
+            held_generation: Arc::new(AtomicU64::new(0)),
             completed: Arc::new(DashMap::new()),
+            idempotent: Arc::new(DashMap::new()),
             msg_stores,
+            escalation: None,
+            auto_answers: None,
         }
     }
 
+    /// Escalate questions that stay unanswered along their project's escalation chain
+    pub fn with_escalation(mut self, escalation: QuestionEscalation) -> Self {
+        self.escalation = Some(escalation);
+        self
+    }
+
+    /// Answer questions matching their project's auto-answer rules without asking anyone
+    pub fn with_auto_answers(mut self, auto_answers: QuestionAutoAnswers) -> Self {
+        self.auto_answers = Some(auto_answers);
+        self
+    }
+
     pub async fn create_with_waiter(
         &self,
         request: UserQuestionRequest,
@@ -82,13 +134,30 @@ impl UserQuestions {
             let matching_tool = find_matching_tool_use(store.clone(), &request.tool_call_id);
 
             if let Some((idx, matching_tool)) = matching_tool {
+                if let Some(auto_answers) = &self.auto_answers
+                    && let Some(answer) = auto_answers.answer(&request).await
+                {
+                    let answered_entry = NormalizedEntry {
+                        content: answer.summary,
+                        ..matching_tool
+                    }
+                    .with_tool_status(ToolStatus::Success)
+                    .ok_or(QuestionError::NoToolUseEntry)?;
+                    store.push_patch(ConversationPatch::replace(idx, answered_entry));
+
+                    let response = UserQuestionResponse {
+                        execution_process_id: request.execution_process_id,
+                        answers: answer.answers,
+                        amendable_until: None,
+                    };
+                    self.completed.insert(req_id.clone(), response.clone());
+                    let _ = tx.send(response);
+                    tracing::debug!("Auto-answered question {} from project rules", req_id);
+                    return Ok((request, waiter));
+                }
+
                 let question_entry = matching_tool
-                    .with_tool_status(ToolStatus::PendingQuestion {
-                        question_id: req_id.clone(),
-                        requested_at: request.created_at,
-                        timeout_at: request.timeout_at,
-                        questions: request.questions.clone(),
-                    })
+                    .with_tool_status(pending_status(&request))
                     .ok_or(QuestionError::NoToolUseEntry)?;
                 store.push_patch(ConversationPatch::replace(idx, question_entry));
 
@@ -97,8 +166,7 @@ impl UserQuestions {
                     PendingQuestion {
                         entry_index: idx,
                         entry: matching_tool,
-                        execution_process_id: request.execution_process_id,
-                        questions: request.questions.clone(),
+                        request: request.clone(),
                         response_tx: tx,
                     },
                 );
@@ -121,77 +189,344 @@ impl UserQuestions {
             );
         }
 
-        self.spawn_timeout_watcher(req_id.clone(), request.timeout_at, waiter.clone());
+        self.spawn_timeout_watcher(&request, waiter.clone());
         Ok((request, waiter))
     }
 
-    #[tracing::instrument(skip(self, id, response))]
+    /// Response an earlier request from the same caller with the same idempotency key got
+    pub fn replay(
+        &self,
+        id: &str,
+        caller: Option<Uuid>,
+        idempotency_key: &str,
+    ) -> Option<UserQuestionResponse> {
+        self.idempotent
+            .get(&(id.to_string(), caller, idempotency_key.to_string()))
+            .filter(|entry| entry.1.elapsed() < IDEMPOTENCY_KEY_TTL)
+            .map(|entry| entry.0.clone())
+    }
+
+    /// Remember the response so the caller's retries with the same idempotency key get it again
+    pub fn remember(
+        &self,
+        id: &str,
+        caller: Option<Uuid>,
+        idempotency_key: &str,
+        response: &UserQuestionResponse,
+    ) {
+        self.idempotent
+            .retain(|_, (_, at)| at.elapsed() < IDEMPOTENCY_KEY_TTL);
+        self.idempotent.insert(
+            (id.to_string(), caller, idempotency_key.to_string()),
+            (response.clone(), Instant::now()),
+        );
+    }
+
+    /// Whether the question already has an answer the agent received
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.completed.contains_key(id)
+    }
+
+    /// Execution process waiting on a pending question, including one whose answer is still in
+    /// its grace period.
+    pub fn pending_execution_process_id(&self, id: &str) -> Option<Uuid> {
+        self.pending
+            .get(id)
+            .map(|p| p.request.execution_process_id)
+            .or_else(|| {
+                self.held
+                    .get(id)
+                    .map(|held| held.pending.request.execution_process_id)
+            })
+    }
+
+    /// Execution process that asked the question, whether or not it has been answered yet
+    pub fn execution_process_id(&self, id: &str) -> Option<Uuid> {
+        self.pending_execution_process_id(id)
+            .or_else(|| self.completed.get(id).map(|r| r.execution_process_id))
+    }
+
+    /// Check that `responder` could answer the question right now, without answering it.
+    pub fn check_respondable(
+        &self,
+        id: &str,
+        responder: Option<Uuid>,
+    ) -> Result<(), QuestionError> {
+        let Some(pending) = self.pending.get(id) else {
+            return Err(self.missing(id));
+        };
+        match pending.request.claimed_by_other(responder) {
+            Some(claim) => Err(QuestionError::Claimed(claim.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Questions still waiting for an answer, oldest first.
+    pub fn list_pending(&self) -> Vec<UserQuestionRequest> {
+        let mut requests: Vec<_> = self.pending.iter().map(|p| p.request.clone()).collect();
+        requests.sort_by_key(|r| r.created_at);
+        requests
+    }
+
+    /// Mark a pending question as being answered by `user_id`, or renew their claim. Fails
+    /// while someone else holds an active claim.
+    pub async fn claim(
+        &self,
+        id: &str,
+        user_id: Option<Uuid>,
+        name: String,
+    ) -> Result<QuestionClaim, QuestionError> {
+        let claim = {
+            let Some(mut pending) = self.pending.get_mut(id) else {
+                return Err(self.missing(id));
+            };
+            if let Some(claim) = pending.request.claimed_by_other(user_id) {
+                return Err(QuestionError::Claimed(claim.clone()));
+            }
+            let mut claim = QuestionClaim::new(user_id, name);
+            if let Some(existing) = pending.request.claimed_by.as_ref()
+                && existing.is_active()
+            {
+                claim.claimed_at = existing.claimed_at;
+            }
+            pending.request.claimed_by = Some(claim.clone());
+            claim
+        };
+        self.push_pending_status(id).await;
+        Ok(claim)
+    }
+
+    /// Give up a claim so others can answer. Releasing a question nobody holds is a no-op.
+    pub async fn release(&self, id: &str, user_id: Option<Uuid>) -> Result<(), QuestionError> {
+        {
+            let Some(mut pending) = self.pending.get_mut(id) else {
+                return Err(self.missing(id));
+            };
+            if let Some(claim) = pending.request.claimed_by_other(user_id) {
+                return Err(QuestionError::Claimed(claim.clone()));
+            }
+            if pending.request.claimed_by.take().is_none() {
+                return Ok(());
+            }
+        }
+        self.push_pending_status(id).await;
+        Ok(())
+    }
+
+    /// Answer a pending question as `responder`, who must hold the claim if there is one. With
+    /// a non-zero `grace`, the answer reaches the agent only once the grace period is over and
+    /// can be amended or withdrawn until then.
+    #[tracing::instrument(skip(self, pool, id, response))]
     pub async fn respond(
         &self,
         pool: &SqlitePool,
         id: &str,
-        response: UserQuestionResponse,
+        mut response: UserQuestionResponse,
+        responder: Option<Uuid>,
+        grace: StdDuration,
     ) -> Result<UserQuestionResponse, QuestionError> {
-        if let Some((_, p)) = self.pending.remove(id) {
-            self.completed.insert(id.to_string(), response.clone());
-            let _ = p.response_tx.send(response.clone());
-
-            if let Some(store) = self.msg_store_by_id(&p.execution_process_id).await {
-                // Mark the tool as successful after question is answered
-                let updated_entry = p
-                    .entry
-                    .with_tool_status(ToolStatus::Success)
-                    .ok_or(QuestionError::NoToolUseEntry)?;
+        let removed = self
+            .pending
+            .remove_if(id, |_, p| p.request.claimed_by_other(responder).is_none());
+        if removed.is_none()
+            && let Some(pending) = self.pending.get(id)
+            && let Some(claim) = pending.request.claimed_by_other(responder)
+        {
+            return Err(QuestionError::Claimed(claim.clone()));
+        }
+        let Some((_, p)) = removed else {
+            return Err(self.missing(id));
+        };
+        if grace.is_zero() {
+            return self.deliver(pool, id, p, response).await;
+        }
 
-                store.push_patch(ConversationPatch::replace(p.entry_index, updated_entry));
-            } else {
-                tracing::warn!(
-                    "No msg_store found for execution_process_id: {}",
-                    p.execution_process_id
-                );
-            }
+        response.amendable_until =
+            Some(chrono::Utc::now() + chrono::Duration::from_std(grace).unwrap_or_default());
+        let generation = self.held_generation.fetch_add(1, Ordering::Relaxed);
+        self.held.insert(
+            id.to_string(),
+            HeldAnswer {
+                pending: p,
+                response: response.clone(),
+                responder,
+                generation,
+            },
+        );
 
-            // Move task back to InProgress if in InReview
-            if let Ok(ctx) = ExecutionProcess::load_context(pool, p.execution_process_id).await
-                && ctx.task.status == TaskStatus::InReview
-                && let Err(e) = Task::update_status(pool, ctx.task.id, TaskStatus::InProgress).await
+        let questions = self.clone();
+        let pool = pool.clone();
+        let id = id.to_string();
+        tokio::spawn(async move {
+            tokio::time::sleep(grace).await;
+            if let Some((_, held)) = questions
+                .held
+                .remove_if(&id, |_, held| held.generation == generation)
+                && let Err(e) = questions
+                    .deliver(&pool, &id, held.pending, held.response)
+                    .await
             {
-                tracing::warn!(
-                    "Failed to update task status to InProgress after question response: {}",
-                    e
-                );
+                tracing::warn!("Failed to deliver answer to question {}: {}", id, e);
             }
+        });
+        Ok(response)
+    }
 
-            Ok(response)
-        } else if self.completed.contains_key(id) {
-            Err(QuestionError::AlreadyCompleted)
-        } else {
-            Err(QuestionError::NotFound)
+    /// Replace an answer still in its grace period. Only whoever answered may amend it.
+    pub fn amend(
+        &self,
+        id: &str,
+        mut response: UserQuestionResponse,
+        responder: Option<Uuid>,
+    ) -> Result<UserQuestionResponse, QuestionError> {
+        let Some(mut held) = self.held.get_mut(id) else {
+            return Err(self.missing(id));
+        };
+        if held.responder != responder {
+            return Err(QuestionError::NotResponder);
         }
+        response.execution_process_id = held.pending.request.execution_process_id;
+        response.amendable_until = held.response.amendable_until;
+        held.response = response.clone();
+        Ok(response)
+    }
+
+    /// Withdraw an answer still in its grace period, putting the question back up for an
+    /// answer.
+    pub async fn withdraw(&self, id: &str, responder: Option<Uuid>) -> Result<(), QuestionError> {
+        let Some((_, held)) = self
+            .held
+            .remove_if(id, |_, held| held.responder == responder)
+        else {
+            return Err(if self.held.contains_key(id) {
+                QuestionError::NotResponder
+            } else {
+                self.missing(id)
+            });
+        };
+        self.pending.insert(id.to_string(), held.pending);
+        self.push_pending_status(id).await;
+        Ok(())
     }
 
-    #[tracing::instrument(skip(self, id, timeout_at, waiter))]
-    fn spawn_timeout_watcher(
+    /// Hand the answer to the waiting agent and record it
+    async fn deliver(
         &self,
-        id: String,
-        timeout_at: chrono::DateTime<chrono::Utc>,
-        waiter: QuestionWaiter,
-    ) {
+        pool: &SqlitePool,
+        id: &str,
+        p: PendingQuestion,
+        mut response: UserQuestionResponse,
+    ) -> Result<UserQuestionResponse, QuestionError> {
+        response.amendable_until = None;
+        self.completed.insert(id.to_string(), response.clone());
+        let _ = p.response_tx.send(response.clone());
+
+        if let Some(store) = self.msg_store_by_id(&p.request.execution_process_id).await {
+            // Mark the tool as successful after question is answered
+            let updated_entry = p
+                .entry
+                .with_tool_status(ToolStatus::Success)
+                .ok_or(QuestionError::NoToolUseEntry)?;
+
+            store.push_patch(ConversationPatch::replace(p.entry_index, updated_entry));
+        } else {
+            tracing::warn!(
+                "No msg_store found for execution_process_id: {}",
+                p.request.execution_process_id
+            );
+        }
+
+        if let Err(e) = AgentRequestResponse::create(
+            pool,
+            AgentRequestKind::Question,
+            p.request.execution_process_id,
+            p.request.created_at,
+        )
+        .await
+        {
+            tracing::warn!("Failed to record question response: {}", e);
+        }
+
+        // Move task back to InProgress if in InReview
+        if let Ok(ctx) = ExecutionProcess::load_context(pool, p.request.execution_process_id).await
+            && ctx.task.status == TaskStatus::InReview
+            && let Err(e) =
+                task_workflow::update_task_status(pool, ctx.task.id, TaskStatus::InProgress).await
+        {
+            tracing::warn!(
+                "Failed to update task status to InProgress after question response: {}",
+                e
+            );
+        }
+
+        Ok(response)
+    }
+
+    fn missing(&self, id: &str) -> QuestionError {
+        if self.completed.contains_key(id) || self.held.contains_key(id) {
+            QuestionError::AlreadyCompleted
+        } else {
+            QuestionError::NotFound
+        }
+    }
+
+    /// Replace a pending question's tool entry so streams pick up a claim change
+    async fn push_pending_status(&self, id: &str) {
+        let Some((execution_process_id, entry_index, entry)) = self.pending.get(id).and_then(|p| {
+            let entry = p.entry.with_tool_status(pending_status(&p.request))?;
+            Some((p.request.execution_process_id, p.entry_index, entry))
+        }) else {
+            return;
+        };
+        if let Some(store) = self.msg_store_by_id(&execution_process_id).await {
+            store.push_patch(ConversationPatch::replace(entry_index, entry));
+        }
+    }
+
+    /// Time the question out at its deadline, escalating it along the project's chain while
+    /// nobody has answered or claimed it.
+    #[tracing::instrument(skip(self, request, waiter))]
+    fn spawn_timeout_watcher(&self, request: &UserQuestionRequest, waiter: QuestionWaiter) {
         let pending = self.pending.clone();
         let msg_stores = self.msg_stores.clone();
+        let escalation = self.escalation.clone();
+        let id = request.id.clone();
+        let execution_process_id = request.execution_process_id;
+        let created_at = request.created_at;
 
-        let now = chrono::Utc::now();
-        let to_wait = (timeout_at - now)
-            .to_std()
-            .unwrap_or_else(|_| StdDuration::from_secs(0));
-        let deadline = tokio::time::Instant::now() + to_wait;
+        let deadline = instant_at(request.timeout_at);
 
         tokio::spawn(async move {
-            let result = tokio::select! {
-                biased;
+            let chain = match &escalation {
+                Some(escalation) => escalation
+                    .plan(execution_process_id)
+                    .await
+                    .map(|plan| (escalation, plan)),
+                None => None,
+            };
+            let steps = chain.as_ref().map_or(&[][..], |(_, plan)| &plan.steps[..]);
+            let mut next_step = 0;
+
+            let result = loop {
+                let escalate_at = steps.get(next_step).map_or(deadline, |step| {
+                    instant_at(created_at + chrono::Duration::minutes(step.after_minutes.into()))
+                });
+                tokio::select! {
+                    biased;
 
-                resolved = waiter.clone() => resolved,
-                _ = tokio::time::sleep_until(deadline) => None,
+                    resolved = waiter.clone() => break resolved,
+                    _ = tokio::time::sleep_until(deadline) => break None,
+                    _ = tokio::time::sleep_until(escalate_at), if next_step < steps.len() => {
+                        // Only while nobody has answered or is answering it
+                        let unattended = pending.get(&id).is_some_and(|p| {
+                            p.request.claimed_by.as_ref().is_none_or(|c| !c.is_active())
+                        });
+                        if unattended && let Some((escalation, plan)) = &chain {
+                            escalation.escalate(plan, &steps[next_step]).await;
+                        }
+                        next_step += 1;
+                    }
+                }
             };
 
             let is_timeout = result.is_none();
@@ -199,7 +534,8 @@ impl UserQuestions {
             if is_timeout && let Some((_, pending_question)) = pending.remove(&id) {
                 let store = {
                     let map = msg_stores.read().await;
-                    map.get(&pending_question.execution_process_id).cloned()
+                    map.get(&pending_question.request.execution_process_id)
+                        .cloned()
                 };
 
                 if let Some(store) = store {
@@ -220,7 +556,7 @@ impl UserQuestions {
                 } else {
                     tracing::warn!(
                         "No msg_store found for execution_process_id: {}",
-                        pending_question.execution_process_id
+                        pending_question.request.execution_process_id
                     );
                 }
             }
@@ -233,6 +569,23 @@ impl UserQuestions {
     }
 }
 
+fn instant_at(at: chrono::DateTime<chrono::Utc>) -> tokio::time::Instant {
+    let to_wait = (at - chrono::Utc::now())
+        .to_std()
+        .unwrap_or_else(|_| StdDuration::from_secs(0));
+    tokio::time::Instant::now() + to_wait
+}
+
+fn pending_status(request: &UserQuestionRequest) -> ToolStatus {
+    ToolStatus::PendingQuestion {
+        question_id: request.id.clone(),
+        requested_at: request.created_at,
+        timeout_at: request.timeout_at,
+        questions: request.questions.clone(),
+        claimed_by: request.claimed_by.clone(),
+    }
+}
+
 /// Find a matching tool use entry that hasn't been assigned to a question yet
 /// Matches by tool call id from tool metadata
 fn find_matching_tool_use(
@@ -269,3 +622,138 @@ fn find_matching_tool_use(
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use utils::user_questions::{CreateUserQuestionRequest, QuestionAnswer};
+
+    use super::*;
+
+    const GRACE: StdDuration = StdDuration::from_millis(300);
+
+    fn questions() -> UserQuestions {
+        UserQuestions::new(Arc::new(RwLock::new(HashMap::new())))
+    }
+
+    fn pool() -> SqlitePool {
+        SqlitePool::connect_lazy("sqlite::memory:").unwrap()
+    }
+
+    /// Register a pending question, returning its id, the agent's end and its execution process
+    fn ask(questions: &UserQuestions) -> (String, oneshot::Receiver<UserQuestionResponse>, Uuid) {
+        let execution_process_id = Uuid::new_v4();
+        let request = UserQuestionRequest::from_create(
+            CreateUserQuestionRequest {
+                tool_call_id: "call-1".to_string(),
+                questions: vec![],
+            },
+            execution_process_id,
+        );
+        let (tx, rx) = oneshot::channel();
+        let id = request.id.clone();
+        questions.pending.insert(
+            id.clone(),
+            PendingQuestion {
+                entry_index: 0,
+                entry: NormalizedEntry {
+                    timestamp: None,
+                    entry_type: NormalizedEntryType::AssistantMessage,
+                    content: String::new(),
+                    metadata: None,
+                },
+                request,
+                response_tx: tx,
+            },
+        );
+        (id, rx, execution_process_id)
+    }
+
+    fn answer(execution_process_id: Uuid, option: usize) -> UserQuestionResponse {
+        UserQuestionResponse {
+            execution_process_id,
+            answers: vec![QuestionAnswer {
+                question_index: 0,
+                selected_options: vec![option],
+                custom_text: None,
+                attachments: None,
+            }],
+            amendable_until: None,
+        }
+    }
+
+    fn selected(response: &UserQuestionResponse) -> usize {
+        response.answers[0].selected_options[0]
+    }
+
+    #[tokio::test]
+    async fn amending_within_grace_delivers_the_amended_answer() {
+        let questions = questions();
+        let (id, mut rx, ep) = ask(&questions);
+        let responder = Some(Uuid::new_v4());
+
+        questions
+            .respond(&pool(), &id, answer(ep, 0), responder, GRACE)
+            .await
+            .unwrap();
+        assert!(matches!(
+            questions.amend(&id, answer(ep, 2), Some(Uuid::new_v4())),
+            Err(QuestionError::NotResponder)
+        ));
+        questions.amend(&id, answer(ep, 1), responder).unwrap();
+        assert!(rx.try_recv().is_err());
+
+        let delivered = tokio::time::timeout(GRACE * 4, rx).await.unwrap().unwrap();
+        assert_eq!(selected(&delivered), 1);
+        assert!(delivered.amendable_until.is_none());
+        assert!(questions.is_completed(&id));
+    }
+
+    #[tokio::test]
+    async fn withdrawing_puts_the_question_back() {
+        let questions = questions();
+        let (id, mut rx, ep) = ask(&questions);
+        let responder = Some(Uuid::new_v4());
+
+        questions
+            .respond(&pool(), &id, answer(ep, 0), responder, GRACE)
+            .await
+            .unwrap();
+        assert!(matches!(
+            questions.withdraw(&id, Some(Uuid::new_v4())).await,
+            Err(QuestionError::NotResponder)
+        ));
+        questions.withdraw(&id, responder).await.unwrap();
+
+        tokio::time::sleep(GRACE * 2).await;
+        assert!(rx.try_recv().is_err());
+        assert!(!questions.is_completed(&id));
+        assert_eq!(questions.list_pending().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn re_answering_after_withdraw_gets_a_full_grace_period() {
+        let questions = questions();
+        let (id, mut rx, ep) = ask(&questions);
+        let responder = Some(Uuid::new_v4());
+        let pool = pool();
+
+        questions
+            .respond(&pool, &id, answer(ep, 0), responder, GRACE)
+            .await
+            .unwrap();
+        questions.withdraw(&id, responder).await.unwrap();
+        tokio::time::sleep(GRACE / 2).await;
+        questions
+            .respond(&pool, &id, answer(ep, 1), responder, GRACE)
+            .await
+            .unwrap();
+
+        // The first answer's timer fires here and must leave the second answer alone
+        tokio::time::sleep(GRACE * 3 / 4).await;
+        assert!(rx.try_recv().is_err());
+        questions.amend(&id, answer(ep, 2), responder).unwrap();
+
+        let delivered = tokio::time::timeout(GRACE * 4, rx).await.unwrap().unwrap();
+        assert_eq!(selected(&delivered), 2);
+    }
+}
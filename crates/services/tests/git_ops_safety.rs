@@ -1406,3 +1406,58 @@ fn merge_base_ahead_of_task_should_error() {
         "Merge should error when base branch is ahead of task branch"
     );
 }
+
+#[test]
+fn worktree_snapshot_restores_uncommitted_changes() {
+    let td = TempDir::new().unwrap();
+    let (_repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let service = GitService::new();
+    let git = GitCli::new();
+
+    // Uncommitted edit and untracked file at checkpoint time
+    write_file(&worktree_path, "feat.txt", "checkpointed\n");
+    write_file(&worktree_path, "notes.txt", "untracked\n");
+    let head = service.get_head_info(&worktree_path).unwrap().oid;
+    let (snapshot, _tree) = service
+        .snapshot_worktree(&worktree_path, "checkpoint")
+        .expect("snapshot");
+
+    // Taking the snapshot leaves the index alone
+    let status = git.get_worktree_status(&worktree_path).unwrap();
+    assert!(
+        status
+            .entries
+            .iter()
+            .all(|e| e.staged == ' ' || e.staged == '?')
+    );
+
+    // Work moves on: a commit, more edits and another untracked file
+    let wt_repo = Repository::open(&worktree_path).unwrap();
+    commit_all(&wt_repo, "after checkpoint");
+    write_file(&worktree_path, "feat.txt", "later\n");
+    write_file(&worktree_path, "scratch.txt", "later\n");
+
+    service
+        .restore_worktree_snapshot(&worktree_path, &head, &snapshot)
+        .expect("restore");
+
+    assert_eq!(service.get_head_info(&worktree_path).unwrap().oid, head);
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("feat.txt")).unwrap(),
+        "checkpointed\n"
+    );
+    assert!(!worktree_path.join("scratch.txt").exists());
+    let status = git.get_worktree_status(&worktree_path).unwrap();
+    let notes = status
+        .entries
+        .iter()
+        .find(|e| e.path == b"notes.txt")
+        .expect("notes.txt restored");
+    assert!(notes.is_untracked);
+    let feat = status
+        .entries
+        .iter()
+        .find(|e| e.path == b"feat.txt")
+        .expect("feat.txt modified");
+    assert_eq!((feat.staged, feat.unstaged), (' ', 'M'));
+}
@@ -0,0 +1,114 @@
+//! ETag and `If-None-Match` support so polling clients can get cheap 304 responses.
+
+use axum::{
+    Json,
+    body::Body,
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::response::ApiResponse;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ETag(String);
+
+fn digest(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .take(16)
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+impl ETag {
+    /// Weak validator derived from model version data such as `updated_at` and row counts.
+    pub fn weak(version: impl AsRef<[u8]>) -> Self {
+        Self(format!("W/\"{}\"", digest(version.as_ref())))
+    }
+
+    /// Strong validator derived from the exact response body.
+    pub fn strong(body: &[u8]) -> Self {
+        Self(format!("\"{}\"", digest(body)))
+    }
+
+    fn opaque(&self) -> &str {
+        self.0.strip_prefix("W/").unwrap_or(&self.0)
+    }
+
+    /// Whether the request's `If-None-Match` header matches this tag (weak comparison).
+    pub fn matches(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get_all(header::IF_NONE_MATCH)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(str::trim)
+            .any(|candidate| {
+                candidate == "*"
+                    || candidate.strip_prefix("W/").unwrap_or(candidate) == self.opaque()
+            })
+    }
+
+    pub fn not_modified(&self) -> Response {
+        self.attach(StatusCode::NOT_MODIFIED)
+    }
+
+    /// Add this tag as the `ETag` header of `response`.
+    pub fn attach(&self, response: impl IntoResponse) -> Response {
+        let mut response = response.into_response();
+        if let Ok(value) = HeaderValue::from_str(&self.0) {
+            response.headers_mut().insert(header::ETAG, value);
+        }
+        response
+    }
+}
+
+/// Serialize `data` as a successful `ApiResponse`, answering 304 when the client already has it.
+pub fn json_with_etag<T: Serialize>(headers: &HeaderMap, data: T) -> Response {
+    let body = match serde_json::to_vec(&ApiResponse::<T>::success(data)) {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!("Failed to serialize response: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error("Failed to serialize response")),
+            )
+                .into_response();
+        }
+    };
+
+    let etag = ETag::strong(&body);
+    if etag.matches(headers) {
+        return etag.not_modified();
+    }
+    etag.attach((
+        [(header::CONTENT_TYPE, "application/json")],
+        Body::from(body),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn if_none_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn matches_weak_and_strong_forms() {
+        let etag = ETag::weak("tasks:3:2025-01-01");
+        let opaque = etag.opaque().to_string();
+
+        assert!(etag.matches(&if_none_match(&etag.0)));
+        assert!(etag.matches(&if_none_match(&opaque)));
+        assert!(etag.matches(&if_none_match(&format!("\"other\", {opaque}"))));
+        assert!(etag.matches(&if_none_match("*")));
+        assert!(!etag.matches(&if_none_match("\"other\"")));
+        assert!(!etag.matches(&HeaderMap::new()));
+    }
+}
@@ -0,0 +1,142 @@
+//! Outbound HTTP settings for corporate networks.
+//!
+//! `VK_HTTP_PROXY` routes every outbound request through a proxy, with `VK_NO_PROXY` listing
+//! hosts to reach directly (loopback addresses are always direct). `VK_CA_CERTS` points at a
+//! PEM bundle of extra root certificates trusted in addition to the system roots. The same
+//! settings are handed to executor subprocesses through [`subprocess_env`].
+
+use std::{path::PathBuf, sync::OnceLock};
+
+use reqwest::{Certificate, ClientBuilder, NoProxy, Proxy};
+
+/// Hosts that never go through the proxy, so local services stay reachable.
+const LOOPBACK_HOSTS: &str = "localhost,127.0.0.1,::1";
+
+#[derive(Debug, Clone, Default)]
+pub struct OutboundConfig {
+    pub proxy: Option<String>,
+    pub no_proxy: Option<String>,
+    pub ca_certs: Option<PathBuf>,
+}
+
+impl OutboundConfig {
+    pub fn from_env() -> Self {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        Self {
+            proxy: var("VK_HTTP_PROXY"),
+            no_proxy: var("VK_NO_PROXY"),
+            ca_certs: var("VK_CA_CERTS").map(PathBuf::from),
+        }
+    }
+
+    /// Comma-separated hosts that bypass the proxy, including loopback addresses.
+    pub fn no_proxy_list(&self) -> String {
+        match &self.no_proxy {
+            Some(hosts) => format!("{hosts},{LOOPBACK_HOSTS}"),
+            None => LOOPBACK_HOSTS.to_string(),
+        }
+    }
+
+    /// Environment variables that make subprocesses use the same proxy and CA bundle.
+    pub fn subprocess_env(&self) -> Vec<(String, String)> {
+        let mut vars = Vec::new();
+        if let Some(proxy) = &self.proxy {
+            let no_proxy = self.no_proxy_list();
+            for name in ["HTTP_PROXY", "HTTPS_PROXY", "ALL_PROXY"] {
+                vars.push((name.to_string(), proxy.clone()));
+                vars.push((name.to_lowercase(), proxy.clone()));
+            }
+            vars.push(("NO_PROXY".to_string(), no_proxy.clone()));
+            vars.push(("no_proxy".to_string(), no_proxy));
+        }
+        if let Some(ca_certs) = &self.ca_certs {
+            // Additive for Node-based agents; unlike SSL_CERT_FILE it keeps the system roots
+            vars.push((
+                "NODE_EXTRA_CA_CERTS".to_string(),
+                ca_certs.to_string_lossy().into_owned(),
+            ));
+        }
+        vars
+    }
+}
+
+static OUTBOUND_CONFIG: OnceLock<OutboundConfig> = OnceLock::new();
+static EXTRA_ROOTS: OnceLock<Vec<Certificate>> = OnceLock::new();
+
+/// Outbound settings read from the environment on first use.
+pub fn outbound_config() -> &'static OutboundConfig {
+    OUTBOUND_CONFIG.get_or_init(OutboundConfig::from_env)
+}
+
+fn extra_roots() -> &'static [Certificate] {
+    EXTRA_ROOTS.get_or_init(|| {
+        let Some(path) = &outbound_config().ca_certs else {
+            return Vec::new();
+        };
+        match std::fs::read(path).map(|pem| Certificate::from_pem_bundle(&pem)) {
+            Ok(Ok(certs)) => certs,
+            Ok(Err(e)) => {
+                tracing::error!("Failed to parse CA bundle {}: {}", path.display(), e);
+                Vec::new()
+            }
+            Err(e) => {
+                tracing::error!("Failed to read CA bundle {}: {}", path.display(), e);
+                Vec::new()
+            }
+        }
+    })
+}
+
+/// A `reqwest` client builder configured with the outbound proxy and extra root certificates.
+/// Use this instead of `reqwest::Client::builder()` for every outbound client.
+pub fn client_builder() -> ClientBuilder {
+    let config = outbound_config();
+    let mut builder = reqwest::Client::builder();
+    if let Some(proxy) = &config.proxy {
+        match Proxy::all(proxy) {
+            Ok(proxy) => {
+                let no_proxy = NoProxy::from_string(&config.no_proxy_list());
+                builder = builder.proxy(proxy.no_proxy(no_proxy));
+            }
+            Err(e) => tracing::error!("Ignoring invalid VK_HTTP_PROXY {}: {}", proxy, e),
+        }
+    }
+    for cert in extra_roots() {
+        builder = builder.add_root_certificate(cert.clone());
+    }
+    builder
+}
+
+/// Environment variables passing the outbound settings to executor subprocesses.
+pub fn subprocess_env() -> Vec<(String, String)> {
+    outbound_config().subprocess_env()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subprocess_env_includes_proxy_and_ca() {
+        let config = OutboundConfig {
+            proxy: Some("http://proxy.corp:3128".to_string()),
+            no_proxy: Some(".corp".to_string()),
+            ca_certs: Some(PathBuf::from("/etc/corp-ca.pem")),
+        };
+        let env: std::collections::HashMap<_, _> = config.subprocess_env().into_iter().collect();
+        assert_eq!(env["HTTPS_PROXY"], "http://proxy.corp:3128");
+        assert_eq!(env["https_proxy"], "http://proxy.corp:3128");
+        assert_eq!(env["NO_PROXY"], ".corp,localhost,127.0.0.1,::1");
+        assert_eq!(env["NODE_EXTRA_CA_CERTS"], "/etc/corp-ca.pem");
+    }
+
+    #[test]
+    fn empty_config_sets_nothing() {
+        assert!(OutboundConfig::default().subprocess_env().is_empty());
+    }
+}
@@ -8,16 +8,20 @@ pub mod assets;
 pub mod user_questions;
 pub mod browser;
 pub mod diff;
+pub mod etag;
 pub mod git;
+pub mod http;
 pub mod jwt;
 pub mod log_msg;
 pub mod msg_store;
+pub mod pagination;
 pub mod path;
 pub mod port_file;
 pub mod response;
 pub mod sentry;
 pub mod shell;
 pub mod stream_lines;
+pub mod telemetry;
 pub mod text;
 pub mod tokio;
 pub mod version;
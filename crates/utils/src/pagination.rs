@@ -0,0 +1,296 @@
+//! Cursor pagination for listing endpoints.
+//!
+//! Listings keep returning a plain array in `ApiResponse::data` so existing clients are
+//! unaffected; paging metadata travels in the `X-Total-Count` and `X-Next-Cursor` headers.
+
+use axum::{
+    Json,
+    http::HeaderValue,
+    response::{IntoResponse, Response},
+};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::response::ApiResponse;
+
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+pub const NEXT_CURSOR_HEADER: &str = "x-next-cursor";
+pub const MAX_PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Error)]
+pub enum PaginationError {
+    #[error("Invalid pagination cursor")]
+    InvalidCursor,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum SortField {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+/// Records that can be listed with [`PageQuery`].
+pub trait Paginate {
+    fn page_id(&self) -> Uuid;
+    fn sort_value(&self, field: SortField) -> DateTime<Utc>;
+}
+
+/// Query parameters accepted by paginated listing endpoints.
+///
+/// Without `limit` or `cursor` the full listing is returned, preserving the old behaviour.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, TS)]
+pub struct PageQuery {
+    /// Maximum number of items to return (capped at 500)
+    pub limit: Option<usize>,
+    /// Opaque cursor from a previous response's `X-Next-Cursor` header
+    pub cursor: Option<String>,
+    pub sort: Option<SortField>,
+    pub order: Option<SortOrder>,
+}
+
+#[derive(Debug, Clone)]
+struct Cursor {
+    value: DateTime<Utc>,
+    id: Uuid,
+}
+
+impl Cursor {
+    fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!("{}:{}", self.value.timestamp_micros(), self.id))
+    }
+
+    fn decode(raw: &str) -> Result<Self, PaginationError> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(raw)
+            .map_err(|_| PaginationError::InvalidCursor)?;
+        let text = String::from_utf8(bytes).map_err(|_| PaginationError::InvalidCursor)?;
+        let (micros, id) = text.split_once(':').ok_or(PaginationError::InvalidCursor)?;
+        let value = micros
+            .parse()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or(PaginationError::InvalidCursor)?;
+        let id = id.parse().map_err(|_| PaginationError::InvalidCursor)?;
+        Ok(Self { value, id })
+    }
+}
+
+/// One page of a listing.
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub total_count: usize,
+}
+
+/// Where a page starts and how the listing is ordered, for queries that paginate in SQL.
+///
+/// Queries sort on `updated_at` when [`Keyset::by_updated_at`] is set and `created_at`
+/// otherwise, breaking ties on `id`. They keep only rows past `(after_value, after_id)` in that
+/// order and read [`Keyset::fetch_limit`] rows, which [`Keyset::page`] turns into a [`Page`].
+#[derive(Debug, Clone)]
+pub struct Keyset {
+    field: SortField,
+    order: SortOrder,
+    after: Option<Cursor>,
+    limit: Option<usize>,
+}
+
+impl Keyset {
+    /// The whole listing, by creation time.
+    pub fn all(order: SortOrder) -> Self {
+        Self {
+            field: SortField::CreatedAt,
+            order,
+            after: None,
+            limit: None,
+        }
+    }
+
+    pub fn by_updated_at(&self) -> bool {
+        self.field == SortField::UpdatedAt
+    }
+
+    pub fn descending(&self) -> bool {
+        self.order == SortOrder::Desc
+    }
+
+    /// Sort value of the last item on the previous page
+    pub fn after_value(&self) -> Option<DateTime<Utc>> {
+        self.after.as_ref().map(|cursor| cursor.value)
+    }
+
+    /// Id of the last item on the previous page
+    pub fn after_id(&self) -> Option<Uuid> {
+        self.after.as_ref().map(|cursor| cursor.id)
+    }
+
+    /// Rows to read: one past the page, so we know whether another follows, or `-1` (no limit in
+    /// SQLite) for the full listing.
+    pub fn fetch_limit(&self) -> i64 {
+        self.limit.map_or(-1, |limit| limit as i64 + 1)
+    }
+
+    /// Build the page from the rows read with this keyset.
+    pub fn page<T: Paginate>(&self, mut items: Vec<T>, total_count: usize) -> Page<T> {
+        let next_cursor = match self.limit {
+            Some(limit) if items.len() > limit => {
+                items.truncate(limit);
+                items.last().map(|last| {
+                    Cursor {
+                        value: last.sort_value(self.field),
+                        id: last.page_id(),
+                    }
+                    .encode()
+                })
+            }
+            _ => None,
+        };
+
+        Page {
+            items,
+            next_cursor,
+            total_count,
+        }
+    }
+}
+
+impl PageQuery {
+    /// The keyset selecting the page this query asks for.
+    pub fn keyset(&self, default_order: SortOrder) -> Result<Keyset, PaginationError> {
+        let after = self.cursor.as_deref().map(Cursor::decode).transpose()?;
+        let limit = match (self.limit, &self.cursor) {
+            (None, None) => None,
+            (limit, _) => Some(limit.unwrap_or(MAX_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)),
+        };
+        Ok(Keyset {
+            field: self.sort.unwrap_or_default(),
+            order: self.order.unwrap_or(default_order),
+            after,
+            limit,
+        })
+    }
+}
+
+impl<T: Serialize> IntoResponse for Page<T> {
+    fn into_response(self) -> Response {
+        let mut response = Json(ApiResponse::<Vec<T>>::success(self.items)).into_response();
+        let headers = response.headers_mut();
+        headers.insert(TOTAL_COUNT_HEADER, HeaderValue::from(self.total_count));
+        if let Some(cursor) = self.next_cursor
+            && let Ok(value) = HeaderValue::from_str(&cursor)
+        {
+            headers.insert(NEXT_CURSOR_HEADER, value);
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone)]
+    struct Item {
+        id: Uuid,
+        created_at: DateTime<Utc>,
+    }
+
+    impl Paginate for Item {
+        fn page_id(&self) -> Uuid {
+            self.id
+        }
+
+        fn sort_value(&self, _: SortField) -> DateTime<Utc> {
+            self.created_at
+        }
+    }
+
+    fn items(n: i64) -> Vec<Item> {
+        (0..n)
+            .map(|i| Item {
+                id: Uuid::new_v4(),
+                created_at: DateTime::from_timestamp(1_700_000_000 + i, 0).unwrap(),
+            })
+            .collect()
+    }
+
+    /// What the listing queries do in SQL
+    fn fetch(items: &[Item], keyset: &Keyset) -> Vec<Item> {
+        let key = |item: &Item| (item.created_at, item.id);
+        let mut rows: Vec<Item> = items
+            .iter()
+            .filter(|item| match (keyset.after_value(), keyset.after_id()) {
+                (Some(value), Some(id)) if keyset.descending() => key(item) < (value, id),
+                (Some(value), Some(id)) => key(item) > (value, id),
+                _ => true,
+            })
+            .cloned()
+            .collect();
+        rows.sort_by_key(key);
+        if keyset.descending() {
+            rows.reverse();
+        }
+        match keyset.fetch_limit() {
+            -1 => rows,
+            limit => rows.into_iter().take(limit as usize).collect(),
+        }
+    }
+
+    #[test]
+    fn walks_all_pages_without_gaps() {
+        let all = items(5);
+        let mut query = PageQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let mut seen = Vec::new();
+        loop {
+            let keyset = query.keyset(SortOrder::Asc).unwrap();
+            assert_eq!(keyset.fetch_limit(), 3);
+            let page = keyset.page(fetch(&all, &keyset), all.len());
+            assert_eq!(page.total_count, 5);
+            seen.extend(page.items.iter().map(|item| item.created_at.timestamp()));
+            match page.next_cursor {
+                Some(cursor) => query.cursor = Some(cursor),
+                None => break,
+            }
+        }
+        assert_eq!(seen, (0..5).map(|i| 1_700_000_000 + i).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn returns_everything_without_limit() {
+        let all = items(3);
+        let keyset = PageQuery::default().keyset(SortOrder::Desc).unwrap();
+        assert_eq!(keyset.fetch_limit(), -1);
+        assert!(keyset.descending());
+
+        let page = keyset.page(fetch(&all, &keyset), all.len());
+        assert_eq!(page.items.len(), 3);
+        assert!(page.next_cursor.is_none());
+        assert!(page.items[0].created_at > page.items[2].created_at);
+    }
+
+    #[test]
+    fn rejects_garbage_cursor() {
+        let query = PageQuery {
+            cursor: Some("not a cursor".to_string()),
+            ..Default::default()
+        };
+        assert!(query.keyset(SortOrder::Asc).is_err());
+    }
+}
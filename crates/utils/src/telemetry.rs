@@ -0,0 +1,116 @@
+//! OpenTelemetry trace export over OTLP/gRPC.
+//!
+//! Disabled unless `OTEL_EXPORTER_OTLP_ENDPOINT` (or `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`) is
+//! set, e.g. `http://localhost:4317` for a local Jaeger or Tempo. SQL statements logged by sqlx
+//! are exported as events on the surrounding request or execution span.
+
+use std::sync::OnceLock;
+
+use opentelemetry::{KeyValue, trace::TracerProvider as _};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    Resource, runtime,
+    trace::{Sampler, TracerProvider},
+};
+use tracing_subscriber::{EnvFilter, Layer, registry::LookupSpan};
+
+const DEFAULT_SERVICE_NAME: &str = "vibe-kanban";
+const DEFAULT_FILTER: &str = "info,sqlx::query=debug";
+
+static PROVIDER: OnceLock<TracerProvider> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+pub struct TelemetrySettings {
+    pub endpoint: String,
+    pub service_name: String,
+    /// Fraction of new traces to sample, between 0.0 and 1.0
+    pub sample_ratio: f64,
+    /// `EnvFilter` directives selecting the spans and events to export
+    pub filter: String,
+}
+
+impl TelemetrySettings {
+    pub fn from_env() -> Option<Self> {
+        let endpoint = std::env::var("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT")
+            .or_else(|_| std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT"))
+            .ok()
+            .filter(|value| !value.trim().is_empty())?;
+
+        let sample_ratio = std::env::var("VK_OTEL_SAMPLE_RATIO")
+            .or_else(|_| std::env::var("OTEL_TRACES_SAMPLER_ARG"))
+            .ok()
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .map(|ratio| ratio.clamp(0.0, 1.0))
+            .unwrap_or(1.0);
+
+        Some(Self {
+            endpoint,
+            service_name: std::env::var("OTEL_SERVICE_NAME")
+                .unwrap_or_else(|_| DEFAULT_SERVICE_NAME.to_string()),
+            sample_ratio,
+            filter: std::env::var("VK_OTEL_FILTER").unwrap_or_else(|_| DEFAULT_FILTER.to_string()),
+        })
+    }
+}
+
+/// Tracing layer exporting spans to the configured OTLP endpoint, or `None` when disabled.
+/// Must be called from within a Tokio runtime.
+pub fn otel_layer<S>() -> Option<impl Layer<S>>
+where
+    S: tracing::Subscriber,
+    S: for<'a> LookupSpan<'a>,
+{
+    let settings = TelemetrySettings::from_env()?;
+
+    let exporter = match SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(&settings.endpoint)
+        .build()
+    {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            // The subscriber isn't installed yet, so this can't go through tracing
+            eprintln!(
+                "Failed to create OTLP exporter for {}: {e}",
+                settings.endpoint
+            );
+            return None;
+        }
+    };
+    let filter = match EnvFilter::try_new(&settings.filter) {
+        Ok(filter) => filter,
+        Err(e) => {
+            eprintln!("Invalid VK_OTEL_FILTER {:?}: {e}", settings.filter);
+            EnvFilter::new(DEFAULT_FILTER)
+        }
+    };
+
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            settings.sample_ratio,
+        ))))
+        .with_resource(Resource::new(vec![
+            KeyValue::new("service.name", settings.service_name),
+            KeyValue::new("service.version", crate::version::APP_VERSION),
+        ]))
+        .build();
+    let tracer = provider.tracer(DEFAULT_SERVICE_NAME);
+    opentelemetry::global::set_tracer_provider(provider.clone());
+    let _ = PROVIDER.set(provider);
+
+    Some(
+        tracing_opentelemetry::layer()
+            .with_tracer(tracer)
+            .with_filter(filter),
+    )
+}
+
+/// Flush buffered spans; call before the process exits.
+pub fn shutdown() {
+    if let Some(provider) = PROVIDER.get()
+        && let Err(e) = provider.shutdown()
+    {
+        tracing::warn!("Failed to flush OpenTelemetry spans: {}", e);
+    }
+}
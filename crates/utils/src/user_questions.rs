@@ -4,6 +4,24 @@ use ts_rs::TS;
 use uuid::Uuid;
 
 pub const QUESTION_TIMEOUT_SECONDS: i64 = 3600; // 1 hour
+pub const QUESTION_CLAIM_SECONDS: i64 = 300; // 5 minutes
+
+/// An image or file shown alongside a question, option or answer
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq)]
+#[ts(export)]
+pub struct QuestionAttachment {
+    /// Image stored by the image service, served at `/api/images/{id}/file`
+    #[serde(default)]
+    #[ts(optional)]
+    pub image_id: Option<Uuid>,
+    /// File path relative to the attempt's worktree
+    #[serde(default)]
+    #[ts(optional)]
+    pub path: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub name: Option<String>,
+}
 
 /// A single question option with label and optional description
 #[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq)]
@@ -13,6 +31,9 @@ pub struct QuestionOption {
     #[serde(default)]
     #[ts(optional)]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub attachments: Option<Vec<QuestionAttachment>>,
 }
 
 /// A user question with options for selection
@@ -27,6 +48,9 @@ pub struct UserQuestion {
     pub options: Vec<QuestionOption>,
     #[serde(default, rename = "multiSelect")]
     pub multi_select: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub attachments: Option<Vec<QuestionAttachment>>,
 }
 
 /// Input format from Claude Code's AskUserQuestion tool
@@ -45,6 +69,10 @@ pub struct QuestionAnswer {
     #[serde(default)]
     #[ts(optional)]
     pub custom_text: Option<String>,
+    /// Images or files the user attached to the answer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub attachments: Option<Vec<QuestionAttachment>>,
 }
 
 /// Request to create a pending user question
@@ -64,6 +92,9 @@ pub struct UserQuestionRequest {
     pub execution_process_id: Uuid,
     pub created_at: DateTime<Utc>,
     pub timeout_at: DateTime<Utc>,
+    /// Reviewer currently answering the question
+    #[serde(default)]
+    pub claimed_by: Option<QuestionClaim>,
 }
 
 impl UserQuestionRequest {
@@ -76,8 +107,44 @@ impl UserQuestionRequest {
             execution_process_id,
             created_at: now,
             timeout_at: now + Duration::seconds(QUESTION_TIMEOUT_SECONDS),
+            claimed_by: None,
+        }
+    }
+
+    /// Active claim held by someone other than `user_id`
+    pub fn claimed_by_other(&self, user_id: Option<Uuid>) -> Option<&QuestionClaim> {
+        self.claimed_by
+            .as_ref()
+            .filter(|claim| claim.is_active() && claim.user_id != user_id)
+    }
+}
+
+/// A reviewer's lock on a pending question, so only the first responder answers it
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq)]
+#[ts(export)]
+pub struct QuestionClaim {
+    /// Account holding the claim; `None` in single-user mode
+    pub user_id: Option<Uuid>,
+    pub name: String,
+    pub claimed_at: DateTime<Utc>,
+    /// The claim lapses after this unless renewed, letting others answer
+    pub expires_at: DateTime<Utc>,
+}
+
+impl QuestionClaim {
+    pub fn new(user_id: Option<Uuid>, name: String) -> Self {
+        let now = Utc::now();
+        Self {
+            user_id,
+            name,
+            claimed_at: now,
+            expires_at: now + Duration::seconds(QUESTION_CLAIM_SECONDS),
         }
     }
+
+    pub fn is_active(&self) -> bool {
+        self.expires_at > Utc::now()
+    }
 }
 
 /// Status of a user question
@@ -96,4 +163,35 @@ pub enum UserQuestionStatus {
 pub struct UserQuestionResponse {
     pub execution_process_id: Uuid,
     pub answers: Vec<QuestionAnswer>,
+    /// Until when the answer can still be amended or withdrawn before the agent receives it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub amendable_until: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_active_claims_by_others_block() {
+        let owner = Some(Uuid::new_v4());
+        let mut request = UserQuestionRequest::from_create(
+            CreateUserQuestionRequest {
+                tool_call_id: "call-1".to_string(),
+                questions: vec![],
+            },
+            Uuid::new_v4(),
+        );
+        request.claimed_by = Some(QuestionClaim::new(owner, "Ada".to_string()));
+
+        assert!(request.claimed_by_other(owner).is_none());
+        assert!(request.claimed_by_other(Some(Uuid::new_v4())).is_some());
+        assert!(request.claimed_by_other(None).is_some());
+
+        if let Some(claim) = request.claimed_by.as_mut() {
+            claim.expires_at = Utc::now() - Duration::seconds(1);
+        }
+        assert!(request.claimed_by_other(Some(Uuid::new_v4())).is_none());
+    }
 }
@@ -45,6 +45,8 @@ const statusLabel = (status: TaskStatus): string => {
       return 'To Do';
     case 'inprogress':
       return 'In Progress';
+    case 'blocked':
+      return 'Blocked';
     case 'inreview':
       return 'In Review';
     case 'done':
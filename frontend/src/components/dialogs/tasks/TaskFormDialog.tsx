@@ -486,6 +486,9 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
                         <SelectItem value="inprogress">
                           {t('taskFormDialog.statusOptions.inprogress')}
                         </SelectItem>
+                        <SelectItem value="blocked">
+                          {t('taskFormDialog.statusOptions.blocked')}
+                        </SelectItem>
                         <SelectItem value="inreview">
                           {t('taskFormDialog.statusOptions.inreview')}
                         </SelectItem>
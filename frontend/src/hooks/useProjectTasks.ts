@@ -120,6 +120,7 @@ export const useProjectTasks = (projectId: string): UseProjectTasksResult => {
     const byStatus: Record<TaskStatus, TaskWithAttemptStatus[]> = {
       todo: [],
       inprogress: [],
+      blocked: [],
       inreview: [],
       done: [],
       cancelled: [],
@@ -150,6 +151,7 @@ export const useProjectTasks = (projectId: string): UseProjectTasksResult => {
     const grouped: Record<TaskStatus, SharedTaskRecord[]> = {
       todo: [],
       inprogress: [],
+      blocked: [],
       inreview: [],
       done: [],
       cancelled: [],
@@ -78,6 +78,7 @@ type Task = TaskWithAttemptStatus;
 const TASK_STATUSES = [
   'todo',
   'inprogress',
+  'blocked',
   'inreview',
   'done',
   'cancelled',
@@ -362,6 +363,7 @@ export function ProjectTasks() {
     const columns: Record<TaskStatus, KanbanColumnItem[]> = {
       todo: [],
       inprogress: [],
+      blocked: [],
       inreview: [],
       done: [],
       cancelled: [],
@@ -454,6 +456,7 @@ export function ProjectTasks() {
     const map: Record<TaskStatus, Task[]> = {
       todo: [],
       inprogress: [],
+      blocked: [],
       inreview: [],
       done: [],
       cancelled: [],
@@ -3,6 +3,7 @@ import { TaskStatus } from 'shared/types';
 export const statusLabels: Record<TaskStatus, string> = {
   todo: 'To Do',
   inprogress: 'In Progress',
+  blocked: 'Blocked',
   inreview: 'In Review',
   done: 'Done',
   cancelled: 'Cancelled',
@@ -11,6 +12,7 @@ export const statusLabels: Record<TaskStatus, string> = {
 export const statusBoardColors: Record<TaskStatus, string> = {
   todo: '--neutral-foreground',
   inprogress: '--info',
+  blocked: '--console-error',
   inreview: '--warning',
   done: '--success',
   cancelled: '--destructive',
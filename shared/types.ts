@@ -22,13 +22,830 @@ export type SearchResult = { path: string, is_file: boolean, match_type: SearchM
 
 export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
 
+export type CodeSearchMode = "all" | "keyword" | "semantic";
+
+export type CodeSearchResult = { repo_name: string, 
+/**
+ * Relative to the repository root
+ */
+file: string, 
+/**
+ * 1-based, inclusive
+ */
+start_line: number, end_line: number, snippet: string, 
+/**
+ * Public symbol enclosing the span, if any
+ */
+symbol: string | null, match_type: CodeSearchMatch, 
+/**
+ * From 0 to 1: the share of query words found for keyword matches, cosine similarity for
+ * semantic ones
+ */
+score: number, };
+
+/**
+ * How attempts started with one executor profile turned out.
+ */
+export type ExecutorStats = { executor_profile_id: ExecutorProfileId, 
+/**
+ * Finished attempts; attempts with an agent still running are left out
+ */
+attempts: number, 
+/**
+ * Completed on the first agent run
+ */
+succeeded: number, 
+/**
+ * Completed after one or more follow-ups
+ */
+needed_follow_up: number, failed: number, 
+/**
+ * Stopped by the user
+ */
+cancelled: number, 
+/**
+ * Share of attempts that succeeded without follow-ups, from 0 to 1
+ */
+success_rate: number, 
+/**
+ * Median over attempts of the total time their agents ran
+ */
+median_duration_seconds: number | null, 
+/**
+ * Median over attempts whose agent reports its cost
+ */
+median_cost_usd: number | null, total_cost_usd: number | null, };
+
+/**
+ * How often agents stopped to ask questions and how quickly they got answers.
+ */
+export type QuestionStats = { 
+/**
+ * Question requests; an agent may ask several questions in one request
+ */
+requests: number, questions: number, answered: number, timed_out: number, 
+/**
+ * Answered by a project's auto-answer rules without interrupting anyone
+ */
+auto_answered: number, 
+/**
+ * Still waiting, or abandoned when the agent stopped
+ */
+pending: number, 
+/**
+ * Share of resolved requests that timed out, from 0 to 1
+ */
+timeout_rate: number | null, 
+/**
+ * Median time from the request to a reviewer's answer
+ */
+median_time_to_answer_seconds: number | null, 
+/**
+ * Most interrupting executor first
+ */
+by_executor: Array<ExecutorQuestionStats>, };
+
+/**
+ * Question statistics of one executor profile.
+ */
+export type ExecutorQuestionStats = { executor_profile_id: ExecutorProfileId, requests: number, questions: number, timed_out: number, auto_answered: number, median_time_to_answer_seconds: number | null, };
+
+export type ProjectStats = { 
+/**
+ * Tasks completed in each of the last weeks, oldest week first
+ */
+completed_per_week: Array<WeeklyThroughput>, column_times: Array<ColumnTime>, attempts_per_task: AttemptsPerTask, question_response: ResponseLatency, approval_response: ResponseLatency, time_spent: TimeSpent, };
+
+export type WeeklyThroughput = { 
+/**
+ * Monday the week starts on, in UTC
+ */
+week_start: string, 
+/**
+ * Tasks that first reached Done that week
+ */
+completed: number, };
+
+/**
+ * How long tasks stayed in a column before moving on. Only stays that ended in the reported
+ * period count, so tasks still sitting in the column are left out.
+ */
+export type ColumnTime = { status: TaskStatus, average_seconds: number | null, 
+/**
+ * Number of stays averaged
+ */
+stays: number, };
+
+export type AttemptsPerTask = { tasks: number, average: number, max: number, 
+/**
+ * Tasks never attempted
+ */
+without_attempts: number, };
+
+/**
+ * Time from an agent asking to the user answering, over the reported period.
+ */
+export type ResponseLatency = { responses: number, average_seconds: number | null, median_seconds: number | null, };
+
+/**
+ * Agent and logged human time over the reported period.
+ */
+export type TimeSpent = { 
+/**
+ * Wall-clock time of finished coding agent runs
+ */
+agent_seconds: number, 
+/**
+ * Time logged by hand on the project's tasks
+ */
+human_seconds: number, 
+/**
+ * Oldest week first
+ */
+per_week: Array<WeeklyTime>, };
+
+export type WeeklyTime = { 
+/**
+ * Monday the week starts on, in UTC
+ */
+week_start: string, agent_seconds: number, human_seconds: number, };
+
+export type CycleTimeReport = { 
+/**
+ * Tasks that first reached Done within the range, in completion order
+ */
+tasks: Array<TaskCycleTime>, average_seconds: number | null, median_seconds: number | null, 
+/**
+ * One point per day of the range
+ */
+series: Array<CycleTimePoint>, 
+/**
+ * Time the completed tasks spent in each column before reaching Done
+ */
+column_lead_times: Array<ColumnLeadTime>, };
+
+/**
+ * Time from creating a task to it first reaching Done.
+ */
+export type TaskCycleTime = { task_id: string, title: string, created_at: Date, done_at: Date, cycle_seconds: number, };
+
+export type CycleTimePoint = { date: string, completed: number, 
+/**
+ * Average cycle time of the tasks completed that day
+ */
+average_cycle_seconds: number | null, };
+
+export type ColumnLeadTime = { status: TaskStatus, average_seconds: number | null, median_seconds: number | null, 
+/**
+ * Completed tasks that passed through the column
+ */
+tasks: number, };
+
+export type BurndownReport = { 
+/**
+ * Label the tasks were scoped to, if any
+ */
+label: string | null, 
+/**
+ * One point per day of the range, counted at the end of the day
+ */
+series: Array<BurndownPoint>, };
+
+export type BurndownPoint = { date: string, 
+/**
+ * Tasks that existed and were not cancelled
+ */
+scope: number, completed: number, remaining: number, };
+
+/**
+ * A release or other goal grouping tasks of a project.
+ */
+export type Milestone = { id: string, project_id: string, title: string, description: string | null, target_date: string | null, created_at: Date, updated_at: Date, };
+
+/**
+ * Used both to create a milestone and to replace every field of one.
+ */
+export type CreateMilestone = { title: string, description: string | null, target_date: string | null, };
+
+/**
+ * A task of a milestone with what its agents cost so far.
+ */
+export type MilestoneTask = { task_id: string, title: string, status: TaskStatus, 
+/**
+ * Cost reported by the coding agents of all the task's attempts
+ */
+cost_usd: number, };
+
+export type MilestoneProgress = { 
+/**
+ * Tasks in the milestone, cancelled ones excluded
+ */
+total: number, done: number, cancelled: number, 
+/**
+ * Agent cost of the milestone's tasks so far
+ */
+spent_usd: number, 
+/**
+ * What the open tasks are expected to cost, from the average cost of the project's done
+ * tasks; `null` until a done task reported a cost
+ */
+estimated_remaining_usd: number | null, };
+
+export type MilestoneSummary = { milestone: Milestone, progress: MilestoneProgress, };
+
+/**
+ * A milestone with its tasks, for planning a release.
+ */
+export type MilestoneView = { milestone: Milestone, progress: MilestoneProgress, tasks: Array<MilestoneTask>, };
+
+export type TaskTime = { agent_seconds: number, human_seconds: number, 
+/**
+ * Agent time of each attempt, oldest attempt first
+ */
+attempts: Array<AttemptTime>, entries: Array<TaskTimeEntry>, };
+
+export type AttemptTime = { workspace_id: string, agent_seconds: number, runs: number, };
+
+/**
+ * Time a person spent on a task, logged by hand.
+ */
+export type TaskTimeEntry = { id: string, task_id: string, 
+/**
+ * `null` in single-user mode
+ */
+user_id: string | null, seconds: number, note: string | null, worked_at: Date, created_at: Date, };
+
+export type CreateTaskTimeEntry = { seconds: number, note: string | null, 
+/**
+ * Defaults to now
+ */
+worked_at: Date | null, };
+
+/**
+ * How often agents used a tool, how often it failed and how long approvals held it up.
+ */
+export type ToolStats = { tool_name: string, calls: number, succeeded: number, failed: number, 
+/**
+ * Rejected by a reviewer or by the attempt's tool restrictions
+ */
+denied: number, 
+/**
+ * Approval requests nobody answered in time
+ */
+timed_out: number, 
+/**
+ * Share of finished calls that failed, from 0 to 1
+ */
+failure_rate: number | null, 
+/**
+ * Calls that waited for a reviewer's decision
+ */
+approvals: number, avg_approval_latency_seconds: number | null, };
+
+export type ReviewWaitingOn = "answer" | "approval" | "merge";
+
+/**
+ * A task that has waited in review longer than the SLA.
+ */
+export type ReviewSlaBreach = { task_id: string, project_id: string, task_title: string, in_review_since: Date, waiting_seconds: number, waiting_on: ReviewWaitingOn, 
+/**
+ * Number of thresholds crossed, starting at 1 for the SLA itself
+ */
+level: number, 
+/**
+ * Highest threshold crossed
+ */
+threshold_hours: number, };
+
+/**
+ * A task flagged by the stale task policy.
+ */
+export type StaleTask = { task_id: string, project_id: string, title: string, status: TaskStatus, last_activity_at: Date, flagged_at: Date, 
+/**
+ * When the task will be moved to Cancelled, if auto-close is on
+ */
+closes_at: Date | null, };
+
+export type CodeSearchMatch = "keyword" | "semantic";
+
 export type Repo = { id: string, path: string, name: string, display_name: string, created_at: Date, updated_at: Date, };
 
-export type ProjectRepo = { id: string, project_id: string, repo_id: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, };
+/**
+ * A review of an attempt's diff by a second coding agent, run in its own session.
+ */
+export type Review = { id: string, workspace_id: string, session_id: string, status: ReviewStatus, executor: string, error: string | null, created_at: Date, completed_at: Date | null, };
+
+export type ReviewStatus = "running" | "completed" | "failed";
+
+/**
+ * A review comment on an attempt, optionally pointing at a file and line.
+ */
+export type ReviewFinding = { id: string, review_id: string, file_path: string | null, line: number | null, severity: ReviewSeverity, comment: string, 
+/**
+ * Set by the user once the finding has been addressed or dismissed
+ */
+resolved: boolean, created_at: Date, };
+
+export type ReviewSeverity = "blocking" | "warning" | "suggestion";
+
+/**
+ * A reviewer's overall verdict on an attempt. Each submission adds one; the latest counts.
+ */
+export type AttemptReview = { id: string, workspace_id: string, verdict: AttemptReviewVerdict, summary: string | null, created_at: Date, };
+
+export type AttemptReviewVerdict = "approved" | "changes_requested";
+
+export type CreateAttemptReview = { verdict: AttemptReviewVerdict, summary: string | null, };
+
+/**
+ * A reviewer's comment on a line of an attempt's diff.
+ */
+export type AttemptReviewComment = { id: string, workspace_id: string, 
+/**
+ * Path as shown in the attempt diff
+ */
+file_path: string, 
+/**
+ * Line in the new version of the file
+ */
+line: number, body: string, resolved: boolean, 
+/**
+ * When the comment was last sent to the agent as feedback
+ */
+sent_at: Date | null, created_at: Date, updated_at: Date, };
+
+export type CreateAttemptReviewComment = { file_path: string, line: number, body: string, };
+
+export type UpdateAttemptReviewComment = { body: string | null, resolved: boolean | null, };
+
+/**
+ * An item of a project's review checklist, ticked off separately on each attempt.
+ */
+export type ReviewChecklistItem = { id: string, project_id: string, label: string, 
+/**
+ * Tasks can't be moved to Done until the item is checked on their latest attempt
+ */
+required: boolean, position: number, created_at: Date, updated_at: Date, };
+
+export type CreateReviewChecklistItem = { label: string, required: boolean, };
+
+export type UpdateReviewChecklistItem = { label: string | null, required: boolean | null, position: number | null, };
+
+/**
+ * A checklist item and whether it's been checked on an attempt.
+ */
+export type ChecklistItemState = { item_id: string, label: string, required: boolean, checked_at: Date | null, };
+
+export type ProjectRepo = { id: string, project_id: string, repo_id: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, };
+
+export type CreateProjectRepo = { display_name: string, git_repo_path: string, };
+
+export type UpdateProjectRepo = { setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean | null, };
+
+export type ProjectEnvVarResponse = { id: string, project_id: string, key: string, value: string | null, is_secret: boolean, created_at: Date, updated_at: Date, };
+
+export type CreateProjectEnvVar = { key: string, value: string, is_secret: boolean, };
+
+export type UpdateProjectEnvVar = { key: string | null, value: string | null, is_secret: boolean | null, };
+
+export type SecretResponse = { id: string, project_id: string | null, name: string, created_at: Date, updated_at: Date, };
+
+export type CreateSecret = { 
+/**
+ * Environment variable name, e.g. `ANTHROPIC_API_KEY`
+ */
+name: string, value: string, };
+
+export type UpdateSecret = { value: string, };
+
+export type FeatureFlag = "auto_review" | "lifecycle_hooks" | "public_share_links" | "repo_instructions" | "human_handoff" | "dev_server_screenshots";
+
+export type FeatureFlagOverride = { flag: string, project_id: string | null, enabled: boolean, created_at: Date, updated_at: Date, };
+
+export type SetFeatureFlag = { enabled: boolean, 
+/**
+ * Override for a single project instead of the whole instance
+ */
+project_id: string | null, };
+
+/**
+ * An MCP server made available to coding agents running in a project's workspaces.
+ */
+export type ProjectMcpServer = { id: string, project_id: string, name: string, transport: McpServerTransport, env: Record<string, string>, auto_approve: boolean, created_at: Date, updated_at: Date, };
+
+export type CreateProjectMcpServer = { name: string, transport: McpServerTransport, env: { [key in string]?: string }, auto_approve: boolean, };
+
+export type UpdateProjectMcpServer = { name: string | null, transport: McpServerTransport | null, env: { [key in string]?: string } | null, auto_approve: boolean | null, };
+
+export type LifecycleHook = "worktree_created" | "attempt_started" | "attempt_finished" | "before_merge" | "after_merge" | "e2e_verification";
+
+export type ProjectLifecycleHook = { project_id: string, hook: LifecycleHook, command: string, 
+/**
+ * Whether a failure stops the attempt or merge; advisory failures are only reported
+ */
+blocking: boolean, created_at: Date, updated_at: Date, };
+
+export type UpsertProjectLifecycleHook = { command: string, blocking: boolean, };
+
+export type Linter = "clippy" | "eslint";
+
+/**
+ * Linter a project runs against the files an attempt changed.
+ */
+export type ProjectLinter = { project_id: string, linter: Linter, 
+/**
+ * Must print the linter's JSON output to stdout
+ */
+command: string, 
+/**
+ * Send error annotations back to the attempt's agent as a follow-up to fix
+ */
+fix_follow_up: boolean, created_at: Date, updated_at: Date, };
+
+export type UpsertProjectLinter = { 
+/**
+ * Defaults to the linter's standard JSON command
+ */
+command: string | null, fix_follow_up: boolean, };
+
+export type SandboxNetwork = "none" | "bridge" | "host";
+
+export type ProjectSandbox = { project_id: string, enabled: boolean, 
+/**
+ * Image the container is started from; it must provide the coding agent CLI
+ */
+image: string, network: SandboxNetwork, cpus: number | null, memory_mb: bigint | null, pids_limit: bigint | null, created_at: Date, updated_at: Date, };
+
+export type UpsertProjectSandbox = { enabled: boolean, image: string, network: SandboxNetwork, cpus: number | null, memory_mb: bigint | null, pids_limit: bigint | null, };
+
+/**
+ * Caps the permission mode a project's attempts may run with.
+ */
+export type ProjectPermissionPolicy = { project_id: string, 
+/**
+ * Least strict mode allowed; attempts may always pick a stricter one
+ */
+max_permission_mode: PermissionMode, created_at: Date, updated_at: Date, };
+
+export type UpsertProjectPermissionPolicy = { max_permission_mode: PermissionMode, };
+
+export type ProjectTemplateRepo = { name: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, };
+
+export type ProjectTemplateEnvVar = { key: string, is_secret: boolean, };
+
+export type ProjectTemplateMcpServer = { name: string, transport: McpServerTransport, env_keys: Array<string>, auto_approve: boolean, };
+
+export type ProjectTemplateConfig = { dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, repositories: Array<ProjectTemplateRepo>, env_vars: Array<ProjectTemplateEnvVar>, mcp_servers: Array<ProjectTemplateMcpServer>, 
+/**
+ * Team new projects join, sharing its executor and notification defaults
+ */
+team_id: string | null, };
+
+export type ProjectTemplate = { id: string, name: string, description: string | null, config: ProjectTemplateConfig, created_at: Date, updated_at: Date, };
+
+export type CreateProjectTemplate = { name: string, description: string | null, };
+
+export type CreateProjectFromTemplate = { template_id: string, name: string, repositories: Array<CreateProjectRepo>, };
+
+export type PromptTemplateKind = "system_prompt" | "follow_up_snippet";
+
+/**
+ * Reusable prompt text with `{{variable}}` placeholders, filled in from the attempt.
+ */
+export type PromptTemplate = { id: string, 
+/**
+ * `None` shares the template with every project
+ */
+project_id: string | null, name: string, description: string | null, kind: PromptTemplateKind, content: string, created_at: Date, updated_at: Date, };
+
+/**
+ * Used both to create a template and to replace every field of one.
+ */
+export type CreatePromptTemplate = { project_id: string | null, name: string, description: string | null, kind: PromptTemplateKind, content: string, };
+
+export type PromptTemplateVariable = { name: string, description: string, };
+
+/**
+ * A Redis key that an external daemon fills with a JSON array of tasks for a project to import.
+ */
+export type RedisTaskSource = { id: string, project_id: string, name: string, redis_key: string, field_mapping: TaskSourceFieldMapping, 
+/**
+ * Source status values mapped to task statuses; unmapped values import as todo
+ */
+status_mapping: Record<string, TaskStatus>, created_at: Date, updated_at: Date, };
+
+/**
+ * Where each task field is found in the items of a task source, as dot-separated paths such as
+ * `fields.summary` or `labels.0`.
+ */
+export type TaskSourceFieldMapping = { 
+/**
+ * Identifies an item across syncs
+ */
+id: string, title: string, description: string | null, status: string | null, url: string | null, };
+
+export type CreateRedisTaskSource = { name: string, redis_key: string, field_mapping: TaskSourceFieldMapping, status_mapping: { [key in string]?: TaskStatus }, };
+
+export type UpdateRedisTaskSource = { name: string | null, redis_key: string | null, field_mapping: TaskSourceFieldMapping | null, status_mapping: { [key in string]?: TaskStatus } | null, };
+
+/**
+ * Incoming webhook of a project. Only the SHA-256 of the secret is persisted.
+ */
+export type ProjectWebhook = { project_id: string, token: string, last_used_at: Date | null, created_at: Date, };
+
+/**
+ * Events an outgoing webhook can subscribe to.
+ */
+export type EventWebhookKind = "approval_pending" | "approval_resolved" | "question_pending" | "question_resolved";
+
+/**
+ * Outgoing webhook of a project, called when approvals and questions open or resolve.
+ */
+export type ProjectEventWebhook = { id: string, project_id: string, url: string, events: Array<EventWebhookKind>, enabled: boolean, created_at: Date, updated_at: Date, };
+
+export type CreateProjectEventWebhook = { url: string, 
+/**
+ * Deliveries carry an `X-Vibe-Signature` HMAC of the body when set
+ */
+secret: string | null, events: Array<EventWebhookKind>, };
+
+export type UpdateProjectEventWebhook = { url: string | null, 
+/**
+ * An empty string removes the secret
+ */
+secret: string | null, events: Array<EventWebhookKind> | null, enabled: boolean | null, };
+
+/**
+ * Expiring read-only link to a task for people without an account.
+ */
+export type PublicShareLink = { id: string, task_id: string, 
+/**
+ * Attempt whose summary and diff are shown
+ */
+workspace_id: string | null, 
+/**
+ * Files of the attempt's diff included in the link
+ */
+diff_paths: Array<string>, expires_at: Date, created_at: Date, };
+
+/**
+ * GitHub repository whose issues become tasks of a project.
+ */
+export type GitHubIssueSync = { project_id: string, 
+/**
+ * `owner/name` of the repository
+ */
+repo_full_name: string, 
+/**
+ * Only issues carrying this label become tasks; every issue when unset
+ */
+label_filter: string | null, created_at: Date, updated_at: Date, };
+
+/**
+ * Error tracker whose issues become tasks of a project.
+ */
+export type ErrorTrackerIntegration = { project_id: string, 
+/**
+ * Start a "diagnose and fix" attempt for every new issue
+ */
+auto_start: boolean, 
+/**
+ * Spend of auto-started attempts over the last 24 hours above which no more are started
+ */
+daily_cost_cap_usd: number | null, created_at: Date, updated_at: Date, };
 
-export type CreateProjectRepo = { display_name: string, git_repo_path: string, };
+/**
+ * Starts an attempt with the default executor when a task of the project enters a column.
+ */
+export type ProjectAutoStart = { project_id: string, 
+/**
+ * Column that starts an attempt when a task is dropped into it
+ */
+trigger_status: TaskStatus, 
+/**
+ * Attempts of the project that may run at once; further auto-starts are skipped
+ */
+max_concurrent_attempts: number, enabled: boolean, created_at: Date, updated_at: Date, };
 
-export type UpdateProjectRepo = { setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean | null, };
+export type UpsertProjectAutoStart = { trigger_status: TaskStatus, max_concurrent_attempts: number, enabled: boolean, };
+
+/**
+ * Slack app whose `/vibe` command creates tasks in a project.
+ */
+export type SlackIntegration = { project_id: string, 
+/**
+ * Address of this instance used in task links posted to Slack
+ */
+app_url: string, created_at: Date, updated_at: Date, };
+
+/**
+ * Where an escalation step sends its reminder.
+ */
+export type EscalationChannel = { "type": "notify" } | { "type": "user", user_id: string, } | { "type": "slack", channel_id: string, };
+
+export type EscalationStep = { 
+/**
+ * Minutes after the question was asked
+ */
+after_minutes: number, channel: EscalationChannel, };
+
+/**
+ * Escalation chain for a project's unanswered agent questions.
+ */
+export type ProjectQuestionEscalation = { project_id: string, steps: Array<EscalationStep>, created_at: Date, updated_at: Date, };
+
+export type UpdateQuestionEscalation = { steps: Array<EscalationStep>, };
+
+/**
+ * Answers a recurring agent question in a project without waiting for a reviewer.
+ */
+export type QuestionAutoAnswerRule = { id: string, project_id: string, name: string, 
+/**
+ * Regex the question text has to match
+ */
+question_pattern: string, 
+/**
+ * Regex the question header has to match, if set
+ */
+header_pattern: string | null, 
+/**
+ * Label of the option to select
+ */
+option_label: string, enabled: boolean, created_at: Date, updated_at: Date, };
+
+export type CreateQuestionAutoAnswerRule = { name: string, question_pattern: string, header_pattern: string | null, option_label: string, };
+
+export type UpdateQuestionAutoAnswerRule = { name: string | null, question_pattern: string | null, 
+/**
+ * An empty string clears the header pattern
+ */
+header_pattern: string | null, option_label: string | null, enabled: boolean | null, };
+
+export type TransitionRequirement = "has_attempt" | "verification_passed" | "no_pending_questions";
+
+/**
+ * A condition a task has to meet before moving to `to_status` in a project.
+ */
+export type TaskTransitionRule = { id: string, project_id: string, 
+/**
+ * `null` applies the rule to moves from any status
+ */
+from_status: TaskStatus | null, to_status: TaskStatus, requirement: TransitionRequirement, created_at: Date, };
+
+export type CreateTaskTransitionRule = { from_status: TaskStatus | null, to_status: TaskStatus, requirement: TransitionRequirement, };
+
+export type TaskLabel = { task_id: string, label: string, created_at: Date, };
+
+export type TaskAssignee = { task_id: string, user_id: string, assigned_at: Date, };
+
+export type AssignTask = { user_id: string, };
+
+/**
+ * What has to happen to a task of the project for a rule to fire.
+ */
+export type AutomationTrigger = { "type": "status_changed", from: TaskStatus | null, to: TaskStatus | null, } | { "type": "label_added", label: string | null, } | { "type": "attempt_failed" } | { "type": "question_timed_out" };
+
+/**
+ * A step run, in order, when a rule fires.
+ */
+export type AutomationAction = { "type": "move_to", status: TaskStatus, } | { "type": "assign", user_id: string, } | { "type": "notify", message: string, } | { "type": "start_attempt" } | { "type": "add_label", label: string, };
+
+export type AutomationRule = { id: string, project_id: string, name: string, trigger: AutomationTrigger, actions: Array<AutomationAction>, enabled: boolean, created_at: Date, updated_at: Date, };
+
+export type CreateAutomationRule = { name: string, trigger: AutomationTrigger, actions: Array<AutomationAction>, };
+
+export type UpdateAutomationRule = { name: string | null, trigger: AutomationTrigger | null, actions: Array<AutomationAction> | null, enabled: boolean | null, };
+
+/**
+ * Outcome of one action of a firing.
+ */
+export type AutomationActionResult = { action: AutomationAction, ok: boolean, 
+/**
+ * Why the action failed or was skipped
+ */
+detail: string | null, };
+
+/**
+ * A rule firing on a task, kept as an audit trail.
+ */
+export type AutomationRuleFiring = { id: string, rule_id: string, task_id: string, results: Array<AutomationActionResult>, fired_at: Date, };
+
+/**
+ * Runs or rejects a shell command an agent asks approval for without waiting for a reviewer.
+ */
+export type BashCommandRule = { id: string, project_id: string, pattern: string, match_kind: CommandMatchKind, action: CommandRuleAction, enabled: boolean, created_at: Date, updated_at: Date, };
+
+export type CreateBashCommandRule = { pattern: string, match_kind: CommandMatchKind, action: CommandRuleAction, };
+
+export type UpdateBashCommandRule = { pattern: string | null, match_kind: CommandMatchKind | null, action: CommandRuleAction | null, enabled: boolean | null, };
+
+export type CommandMatchKind = "glob" | "regex";
+
+export type CommandRuleAction = "allow" | "deny";
+
+/**
+ * Approves, holds or rejects agent edits to the files matching a glob without waiting for a
+ * reviewer.
+ */
+export type FilePathRule = { id: string, project_id: string, 
+/**
+ * Glob such as `src/**` or `.env*`; patterns without a `/` match file names anywhere
+ */
+pattern: string, action: PathRuleAction, enabled: boolean, created_at: Date, updated_at: Date, };
+
+export type CreateFilePathRule = { pattern: string, action: PathRuleAction, };
+
+export type UpdateFilePathRule = { pattern: string | null, action: PathRuleAction | null, enabled: boolean | null, };
+
+export type PathRuleAction = "allow" | "ask" | "deny";
+
+export type CommandDecision = "allow" | "deny" | "ask";
+
+/**
+ * How a project's rules treat a command.
+ */
+export type CommandClassification = { decision: CommandDecision, 
+/**
+ * Rules behind the decision, empty when it's left to a reviewer
+ */
+rules: Array<BashCommandRule>, };
+
+/**
+ * How an agent reaches a project-declared MCP server.
+ */
+export type McpServerTransport = { "type": "stdio", command: string, args: Array<string>, } | { "type": "http", url: string, headers: { [key in string]?: string }, };
+
+export type UserRole = "admin" | "member" | "reviewer";
+
+export type User = { id: string, username: string, display_name: string | null, email: string | null, role: UserRole, disabled: boolean, created_at: Date, updated_at: Date, };
+
+export type CreateUser = { username: string, password: string | null, display_name: string | null, email: string | null, role: UserRole, };
+
+/**
+ * Token for automation that may only list and answer the questions and approvals of some
+ * projects. Only the SHA-256 of the token is persisted.
+ */
+export type ApiToken = { id: string, name: string, project_ids: Array<string>, created_by: string | null, last_used_at: Date | null, created_at: Date, };
+
+export type CreateApiToken = { name: string, project_ids: Array<string>, };
+
+export type UpdateUser = { display_name: string | null, email: string | null, role: UserRole | null, disabled: boolean | null, 
+/**
+ * Replace the user's password
+ */
+password: string | null, };
+
+/**
+ * Where a user's phone notifications are delivered.
+ */
+export type UserPushSettings = { user_id: string, ntfy_topic: string | null, pushover_user_key: string | null, created_at: Date, updated_at: Date, };
+
+export type UpdateUserPushSettings = { ntfy_topic: string | null, pushover_user_key: string | null, };
+
+export type TeamRole = "owner" | "member";
+
+/**
+ * Notification settings applied to every project of a team.
+ */
+export type TeamNotificationDefaults = { 
+/**
+ * Shared ntfy topic the team's notifications are also pushed to
+ */
+ntfy_topic: string | null, 
+/**
+ * Shared Pushover user or group key the team's notifications are also pushed to
+ */
+pushover_user_key: string | null, 
+/**
+ * Kinds that are not pushed to the team destinations
+ */
+muted_kinds: Array<NotificationKind>, };
+
+/**
+ * Group of users sharing a set of projects.
+ */
+export type Team = { id: string, name: string, 
+/**
+ * Executor the team's attempts default to; the instance default when unset
+ */
+executor_profile: ExecutorProfileId | null, notification_defaults: TeamNotificationDefaults, created_at: Date, updated_at: Date, };
+
+export type CreateTeam = { name: string, executor_profile: ExecutorProfileId | null, notification_defaults: TeamNotificationDefaults, };
+
+export type UpdateTeam = { name: string | null, 
+/**
+ * Replaces the shared executor; `null` falls back to the instance default
+ */
+executor_profile: ExecutorProfileId | null, notification_defaults: TeamNotificationDefaults | null, };
+
+export type TeamMember = { team_id: string, user_id: string, username: string, display_name: string | null, role: TeamRole, created_at: Date, };
+
+export type NotificationKind = "execution_completed" | "execution_failed" | "question_pending" | "approval_pending" | "review_sla_breached" | "task_stale" | "automation" | "agent_blocked" | "rebase_conflict";
+
+/**
+ * An entry in the in-app notification center.
+ */
+export type Notification = { id: string, kind: NotificationKind, title: string, message: string, project_id: string | null, task_id: string | null, read_at: Date | null, created_at: Date, };
 
 export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string, target_branch: string, created_at: Date, updated_at: Date, };
 
@@ -36,13 +853,109 @@ export type CreateWorkspaceRepo = { repo_id: string, target_branch: string, };
 
 export type RepoWithTargetBranch = { target_branch: string, id: string, path: string, name: string, display_name: string, created_at: Date, updated_at: Date, };
 
+/**
+ * Short summary of what an attempt changed and why, written for reviewers.
+ */
+export type WorkspaceSummary = { workspace_id: string, summary: string, created_at: string, updated_at: string, };
+
+export type CheckpointTrigger = "periodic" | "run_end" | "before_rollback";
+
+/**
+ * Where one repository of the attempt was when the checkpoint was taken.
+ */
+export type CheckpointRepoState = { repo_id: string, 
+/**
+ * Branch head, restored as is
+ */
+head_oid: string, 
+/**
+ * Commit on top of the head holding the uncommitted changes
+ */
+snapshot_oid: string, tree_oid: string, };
+
+/**
+ * Snapshot of an attempt's worktrees that the attempt can be rolled back to.
+ */
+export type AttemptCheckpoint = { id: string, workspace_id: string, execution_process_id: string, trigger: CheckpointTrigger, repo_states: Array<CheckpointRepoState>, created_at: Date, };
+
+/**
+ * Note a coding agent run left when it ended blocked on a person.
+ */
+export type AttemptHandoff = { id: string, workspace_id: string, execution_process_id: string, done: string, blocked: string, next_steps: Array<string>, created_at: Date, };
+
+/**
+ * Screenshot of an attempt's running dev server, stored as an image.
+ */
+export type AttemptScreenshot = { id: string, workspace_id: string, image_id: string, 
+/**
+ * Page the screenshot was taken of
+ */
+url: string, created_at: Date, };
+
+export type E2eRunStatus = "passed" | "failed" | "error";
+
+export type E2eArtifactKind = "screenshot" | "trace";
+
+/**
+ * A failing test and the first error it reported.
+ */
+export type E2eTestFailure = { 
+/**
+ * Spec title, prefixed with its describe blocks
+ */
+title: string, file: string, error: string | null, };
+
+/**
+ * Result of running a project's E2E verification against an attempt's dev server.
+ */
+export type AttemptE2eRun = { id: string, workspace_id: string, 
+/**
+ * Dev server URL the tests ran against
+ */
+url: string, command: string, status: E2eRunStatus, passed: bigint, failed: bigint, flaky: bigint, skipped: bigint, failures: Array<E2eTestFailure>, 
+/**
+ * Tail of the command's output
+ */
+output: string, created_at: Date, };
+
+/**
+ * Screenshot or trace left behind by an E2E run.
+ */
+export type AttemptE2eArtifact = { id: string, run_id: string, kind: E2eArtifactKind, 
+/**
+ * Path relative to the test output directory
+ */
+original_name: string, mime_type: string, size_bytes: bigint, created_at: Date, };
+
+export type LintSeverity = "error" | "warning";
+
+/**
+ * A linter finding on a file the attempt changed, from the linter's latest run.
+ */
+export type AttemptLintAnnotation = { id: string, workspace_id: string, linter: Linter, 
+/**
+ * Path within the workspace, prefixed with the repository name
+ */
+file: string, line: bigint, col: bigint, rule: string | null, severity: LintSeverity, message: string, created_at: Date, };
+
+/**
+ * An attempt kept rebased onto its base branches as new commits land there.
+ */
+export type WorkspaceBaseWatch = { workspace_id: string, 
+/**
+ * Ask the agent to reconcile its work with the new commits after each rebase
+ */
+follow_up: boolean, created_at: Date, updated_at: Date, };
+
+export type SetWorkspaceBaseWatch = { follow_up: boolean, };
+
 export type Tag = { id: string, tag_name: string, content: string, created_at: string, updated_at: string, };
 
 export type CreateTag = { tag_name: string, content: string, };
 
 export type UpdateTag = { tag_name: string | null, content: string | null, };
 
-export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
+export type TaskStatus = "todo" | "inprogress" | "blocked" | "inreview" | "done" | "cancelled";
 
 export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, shared_task_id: string | null, created_at: string, updated_at: string, };
 
@@ -50,7 +963,12 @@ export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, last_att
 
 export type TaskRelationships = { parent_task: Task | null, current_workspace: Workspace, children: Array<Task>, };
 
-export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, shared_task_id: string | null, };
+export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, shared_task_id: string | null, 
+/**
+ * Replace the title and description with ones generated by the configured model, keeping
+ * the submitted text as the prompt the executor receives
+ */
+generate_details?: boolean, };
 
 export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, };
 
@@ -68,25 +986,158 @@ export type UpdateScratch = { payload: ScratchPayload, };
 
 export type Image = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
+export type TaskPrompt = { task_id: string, original_prompt: string, created_at: string, };
+
 export type CreateImage = { file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, };
 
-export type Workspace = { id: string, task_id: string, container_ref: string | null, branch: string, agent_working_dir: string | null, setup_completed_at: string | null, created_at: string, updated_at: string, };
+/**
+ * A file of any type attached to a task and copied into its worktrees.
+ */
+export type TaskAttachment = { id: string, task_id: string, file_path: string, original_name: string, mime_type: string, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
+
+export type Workspace = { id: string, task_id: string, container_ref: string | null, branch: string, agent_working_dir: string | null, 
+/**
+ * Investigate-only attempt: the agent gets read-only tools and can't change the worktree
+ */
+read_only: boolean, 
+/**
+ * Dry-run attempt: tool calls that may change the worktree are logged instead of run
+ */
+dry_run: boolean, 
+/**
+ * Permission mode picked for the attempt; the executor profile's applies when unset
+ */
+permission_mode: PermissionMode | null, setup_completed_at: string | null, created_at: string, updated_at: string, };
+
+export type Session = { id: string, workspace_id: string, executor: string | null, created_at: string, updated_at: string, };
+
+export type ExecutionProcess = { id: string, session_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, status: ExecutionProcessStatus, exit_code: bigint | null, 
+/**
+ * dropped: true if this process is excluded from the current
+ * history view (due to restore/trimming). Hidden from logs/timeline;
+ * still listed in the Processes tab.
+ */
+dropped: boolean, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
+
+export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
+
+export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver";
+
+export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, created_at: Date, updated_at: Date, };
+
+/**
+ * What an execution process ran with.
+ */
+export type ExecutionProcessEnvironment = { execution_process_id: string, 
+/**
+ * Coding agent, CLI and model; unset for scripts
+ */
+executor: string | null, cli_command: string | null, 
+/**
+ * Version pinned in the CLI command, when it pins one
+ */
+cli_version: string | null, 
+/**
+ * Model the profile asks for; unset when left to the CLI's default
+ */
+model: string | null, os: string, arch: string, 
+/**
+ * Version of vibe-kanban that started the process
+ */
+app_version: string, repos: Array<RepoEnvironment>, created_at: Date, };
+
+/**
+ * State of one of the attempt's repos when the process started.
+ */
+export type RepoEnvironment = { repo_id: string, repo_name: string, target_branch: string, 
+/**
+ * Merge base of the attempt branch and the target branch
+ */
+base_commit: string | null, head_commit: string | null, lockfiles: Array<LockfileHash>, };
+
+/**
+ * Hash of a dependency lockfile at the root of a repo.
+ */
+export type LockfileHash = { path: string, sha256: string, };
+
+/**
+ * What a failed execution process wrote to stderr before it exited.
+ */
+export type ExecutionProcessFailure = { execution_process_id: string, kind: ExecutionProcessFailureKind, 
+/**
+ * Last lines written to stderr, oldest first
+ */
+stderr_tail: string, created_at: Date, };
+
+/**
+ * Why an execution process failed, as a machine-readable code.
+ */
+export type ExecutionProcessFailureKind = "rate_limited" | "context_overflow" | "budget_exhausted" | "auth_failure" | "out_of_memory" | "test_failure" | "merge_conflict" | "cli_missing" | "version_mismatch" | "cli_crash" | "unknown";
+
+/**
+ * One coding agent run of a session and the model it ran with.
+ */
+export type ModelSegment = { execution_process_id: string, 
+/**
+ * `None` when neither the profile nor a fallback picked a model, so the CLI's default ran
+ */
+model: string | null, 
+/**
+ * Why the previous run gave up its model, when this run is a fallback
+ */
+fell_back_after: ExecutionProcessFailureKind | null, status: ExecutionProcessStatus, started_at: Date, completed_at: Date | null, };
+
+/**
+ * Latest liveness sample of a running execution process.
+ */
+export type ExecutionProcessHeartbeat = { execution_process_id: string, pid: bigint | null, 
+/**
+ * Single-letter process state from the OS, e.g. R, S or Z
+ */
+state: string | null, 
+/**
+ * Resident memory of the whole process group
+ */
+rss_kb: bigint | null, 
+/**
+ * CPU time used by the process group so far
+ */
+cpu_seconds: number | null, last_seen_at: Date, };
+
+/**
+ * A bookmark on an entry of an execution process's normalized log, optionally with a note.
+ */
+export type LogEntryAnnotation = { id: string, execution_process_id: string, 
+/**
+ * Index of the entry in the normalized log
+ */
+entry_index: number, 
+/**
+ * Unset for plain bookmarks
+ */
+note: string | null, created_at: Date, updated_at: Date, };
 
-export type Session = { id: string, workspace_id: string, executor: string | null, created_at: string, updated_at: string, };
+export type CreateLogEntryAnnotation = { entry_index: number, note: string | null, };
 
-export type ExecutionProcess = { id: string, session_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, status: ExecutionProcessStatus, exit_code: bigint | null, 
+export type UpdateLogEntryAnnotation = { 
 /**
- * dropped: true if this process is excluded from the current
- * history view (due to restore/trimming). Hidden from logs/timeline;
- * still listed in the Processes tab.
+ * An empty note turns the annotation back into a plain bookmark
  */
-dropped: boolean, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
+note: string | null, };
 
-export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
+export type ExecutionProcessLimits = { execution_process_id: string, memory_mb: bigint | null, cpu_seconds: bigint | null, wall_clock_secs: bigint | null, 
+/**
+ * Limit the process was killed for exceeding
+ */
+exceeded: ResourceLimit | null, created_at: Date, updated_at: Date, };
 
-export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver";
+export type OutboxIntegration = "github" | "slack";
 
-export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, created_at: Date, updated_at: Date, };
+export type IntegrationOutboxEntry = { id: string, integration: OutboxIntegration, 
+/**
+ * Identifies what the action is about; a newer action for the same key replaces this one
+ */
+dedupe_key: string, payload: unknown, attempts: bigint, last_error: string | null, next_attempt_at: Date, created_at: Date, updated_at: Date, };
 
 export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
 
@@ -100,20 +1151,70 @@ export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus
 
 export type ApprovalStatus = { "status": "pending" } | { "status": "approved" } | { "status": "denied", reason?: string, } | { "status": "timed_out" };
 
+export type ApprovalRequest = { id: string, tool_name: string, tool_input: JsonValue, tool_call_id: string, execution_process_id: string, created_at: string, timeout_at: string, };
+
 export type CreateApprovalRequest = { tool_name: string, tool_input: JsonValue, tool_call_id: string, };
 
 export type ApprovalResponse = { execution_process_id: string, status: ApprovalStatus, };
 
-export type UserQuestion = { question: string, header?: string, options: Array<QuestionOption>, multiSelect: boolean, };
+export type UserQuestion = { question: string, header?: string, options: Array<QuestionOption>, multiSelect: boolean, attachments?: Array<QuestionAttachment>, };
+
+export type QuestionOption = { label: string, description?: string, attachments?: Array<QuestionAttachment>, };
+
+export type QuestionAttachment = { 
+/**
+ * Image stored by the image service, served at `/api/images/{id}/file`
+ */
+image_id?: string, 
+/**
+ * File path relative to the attempt's worktree
+ */
+path?: string, name?: string, };
+
+export type QuestionAnswer = { question_index: number, selected_options: Array<number>, custom_text?: string, 
+/**
+ * Images or files the user attached to the answer
+ */
+attachments?: Array<QuestionAttachment>, };
 
-export type QuestionOption = { label: string, description?: string, };
+export type UserQuestionRequest = { id: string, tool_call_id: string, questions: Array<UserQuestion>, execution_process_id: string, created_at: string, timeout_at: string, 
+/**
+ * Reviewer currently answering the question
+ */
+claimed_by: QuestionClaim | null, };
 
-export type QuestionAnswer = { question_index: number, selected_options: Array<number>, custom_text?: string, };
+export type QuestionClaim = { 
+/**
+ * Account holding the claim; `None` in single-user mode
+ */
+user_id: string | null, name: string, claimed_at: string, 
+/**
+ * The claim lapses after this unless renewed, letting others answer
+ */
+expires_at: string, };
 
-export type UserQuestionResponse = { execution_process_id: string, answers: Array<QuestionAnswer>, };
+export type UserQuestionResponse = { execution_process_id: string, answers: Array<QuestionAnswer>, 
+/**
+ * Until when the answer can still be amended or withdrawn before the agent receives it
+ */
+amendable_until?: string, };
 
 export type UserQuestionStatus = { "status": "pending" } | { "status": "answered" } | { "status": "timed_out" };
 
+export type SortField = "created_at" | "updated_at";
+
+export type SortOrder = "asc" | "desc";
+
+export type PageQuery = { 
+/**
+ * Maximum number of items to return (capped at 500)
+ */
+limit: number | null, 
+/**
+ * Opaque cursor from a previous response's `X-Next-Cursor` header
+ */
+cursor: string | null, sort: SortField | null, order: SortOrder | null, };
+
 export type Diff = { change: DiffChangeKind, oldPath: string | null, newPath: string | null, oldContent: string | null, newContent: string | null, 
 /**
  * True when file contents are intentionally omitted (e.g., too large)
@@ -188,77 +1289,545 @@ export type CreateRemoteProjectRequest = { organization_id: string, name: string
 
 export type LinkToExistingRequest = { remote_project_id: string, };
 
+export type CheckBashCommandRequest = { command: string, };
+
 export type RegisterRepoRequest = { path: string, display_name: string | null, };
 
 export type InitRepoRequest = { parent_path: string, folder_name: string, };
 
-export type TagSearchParams = { search: string | null, };
+export type TagSearchParams = { search: string | null, };
+
+export type TokenResponse = { access_token: string, expires_at: string | null, };
+
+export type UserSystemInfo = { config: Config, analytics_user_id: string, login_status: LoginStatus, environment: Environment, 
+/**
+ * Capabilities supported per executor (e.g., { "CLAUDE_CODE": ["SESSION_FORK"] })
+ */
+capabilities: { [key in string]?: Array<BaseAgentCapability> }, 
+/**
+ * Externally visible base URL of this server, as seen by the client
+ */
+server_url: string | null, executors: { [key in BaseCodingAgent]?: ExecutorConfig }, };
+
+export type Environment = { os_type: string, os_version: string, os_architecture: string, bitness: string, };
+
+export type McpServerQuery = { executor: BaseCodingAgent, };
+
+export type UpdateMcpServersBody = { servers: { [key in string]?: JsonValue }, };
+
+export type GetMcpServerResponse = { mcp_config: McpConfig, config_path: string, };
+
+export type CheckEditorAvailabilityQuery = { editor_type: EditorType, };
+
+export type CheckEditorAvailabilityResponse = { available: boolean, };
+
+export type CheckAgentAvailabilityQuery = { executor: BaseCodingAgent, };
+
+export type CurrentUserResponse = { user_id: string, };
+
+export type CreateFollowUpAttempt = { prompt: string, variant: string | null, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, 
+/**
+ * Follow-up snippet templates added after the message, in order
+ */
+prompt_template_ids?: Array<string>, };
+
+export type ChangeTargetBranchRequest = { repo_id: string, new_target_branch: string, };
+
+export type ChangeTargetBranchResponse = { repo_id: string, new_target_branch: string, status: [number, number], };
+
+export type MergeTaskAttemptRequest = { repo_id: string, };
+
+export type PushTaskAttemptRequest = { repo_id: string, };
+
+export type RenameBranchRequest = { new_branch_name: string, };
+
+export type RenameBranchResponse = { branch: string, };
+
+export type OpenEditorRequest = { editor_type: string | null, file_path: string | null, };
+
+export type OpenEditorResponse = { url: string | null, };
+
+export type AccountStatus = { 
+/**
+ * Whether the server enforces user accounts
+ */
+multi_user: boolean, 
+/**
+ * No users exist yet; the first account created becomes an admin
+ */
+setup_required: boolean, 
+/**
+ * Users can sign in through the configured OpenID Connect provider
+ */
+oidc_enabled: boolean, user: User | null, };
+
+export type LoginRequest = { username: string, password: string, };
+
+export type LoginResponse = { user: User, 
+/**
+ * Session token for non-browser clients; browsers use the session cookie
+ */
+token: string, };
+
+export type NotificationList = { notifications: Array<Notification>, unread_count: number, };
+
+export type UnreadNotificationCount = { unread_count: number, };
+
+export type DevServerHealth = "healthy" | "unhealthy" | "unknown";
+
+export type DevServerInfo = { execution_process_id: string, workspace_id: string, task_id: string, task_title: string, project_id: string, project_name: string, branch: string, 
+/**
+ * devctl2 subdomain URL if registered, otherwise the local URL detected in the logs
+ */
+url: string | null, 
+/**
+ * Public tunnel URL, if the dev server is currently shared
+ */
+public_url: string | null, started_at: Date, uptime_seconds: bigint, health: DevServerHealth, };
+
+export type OpenTunnelRequest = { provider: TunnelProviderKind | null, };
+
+export type TunnelProviderKind = "cloudflared" | "ngrok";
+
+export type ActiveTunnel = { execution_process_id: string, provider: TunnelProviderKind, local_url: string, public_url: string, created_at: Date, };
+
+export type PresenceActivity = { "kind": "viewing" } | { "kind": "typing_follow_up" } | { "kind": "starting_attempt" } | { "kind": "answering_question", question_id: string, };
+
+export type TaskPresence = { 
+/**
+ * Identifies a browser tab, so one user can have the task open twice
+ */
+client_id: string, 
+/**
+ * `null` in single-user mode
+ */
+user_id: string | null, name: string, activity: PresenceActivity, last_seen_at: Date, };
+
+export type EntityEvent = { "type": "task_created", task: TaskWithAttemptStatus, } | { "type": "task_updated", task: TaskWithAttemptStatus, } | { "type": "task_deleted", task_id: string, } | { "type": "attempt_status_changed", workspace_id: string, task_id: string, execution_process_id: string, run_reason: ExecutionProcessRunReason, status: ExecutionProcessStatus, } | { "type": "question_pending", question_id: string, execution_process_id: string, task_id: string, } | { "type": "question_answered", question_id: string, execution_process_id: string, task_id: string, 
+/**
+ * False when the question timed out without an answer
+ */
+answered: boolean, } | { "type": "attempt_finished", workspace_id: string, task_id: string, session_id: string, execution_process_id: string, status: ExecutionProcessStatus, exit_code: number | null, } | { "type": "approval_pending", approval_id: string, execution_process_id: string, task_id: string, tool_name: string, } | { "type": "approval_resolved", approval_id: string, execution_process_id: string, task_id: string, status: ApprovalStatus, } | { "type": "dev_server_status_changed", execution_process_id: string, workspace_id: string, status: ExecutionProcessStatus, } | { "type": "notification_created", notification: Notification, unread_count: number, } | { "type": "notifications_updated", unread_count: number, } | { "type": "task_label_added", task_id: string, label: string, } | { "type": "presence_changed", task_id: string, viewers: Array<TaskPresence>, };
+
+export type EntityEventEnvelope = { 
+/**
+ * Monotonically increasing id, usable as `Last-Event-ID` to resume the stream
+ */
+id: number, 
+/**
+ * `null` when the project could not be resolved (e.g. the row was already deleted)
+ */
+project_id: string | null, timestamp: Date, event: EntityEvent, };
+
+export type BackupManifest = { format_version: number, app_version: string, 
+/**
+ * Latest applied database migration when the backup was taken
+ */
+schema_version: bigint, created_at: Date, };
+
+export type BackupInfo = { file_name: string, size_bytes: bigint, manifest: BackupManifest, };
+
+export type RestoreResult = { manifest: BackupManifest, images_restored: number, 
+/**
+ * The database is swapped in when the server next starts
+ */
+restart_required: boolean, };
+
+export type BackupStatus = { schedule_enabled: boolean, last_success_at: Date | null, 
+/**
+ * Most recent scheduled backup taken since the server started
+ */
+last_backup: BackupInfo | null, last_failure_at: Date | null, 
+/**
+ * Error from the last scheduled run, cleared by the next success
+ */
+last_error: string | null, next_run_at: Date | null, };
+
+export type MaintenanceStep = "integrity_check" | "checkpoint" | "vacuum" | "analyze";
+
+export type MaintenanceReport = { started_at: Date, finished_at: Date, integrity_ok: boolean, 
+/**
+ * Problems reported by `PRAGMA integrity_check`, empty when the database is healthy
+ */
+integrity_errors: Array<string>, size_before_bytes: bigint, size_after_bytes: bigint, 
+/**
+ * Set when a step failed; later steps are skipped
+ */
+error: string | null, };
+
+export type MaintenanceStatus = { running: boolean, 
+/**
+ * Step currently running
+ */
+current_step: MaintenanceStep | null, steps_completed: number, total_steps: number, last_report: MaintenanceReport | null, };
+
+export type ExportedAnalyticsEvent = { timestamp: Date, event: string, user_id: string, properties: Record<string, unknown>, };
+
+export type EffectiveServerConfig = { path: string, file_exists: boolean, 
+/**
+ * Error from the last attempt to load the file; the previous settings stay in effect
+ */
+last_error: string | null, host: string, port: number | null, redis_url: string | null, redis_username: string | null, redis_password: string | null, sound_enabled: boolean, push_enabled: boolean, mobile_push: MobilePushConfig, executor_profile: ExecutorProfileId, backup_keep: number, max_attachment_bytes: bigint | null, max_body_bytes: bigint | null, max_upload_bytes: bigint | null, rate_limit_rpm: number | null, rate_limit_burst: number | null, };
+
+export type RawLogLine = { "stream": "stdout", "content": string } | { "stream": "stderr", "content": string };
+
+export type RawLogSnapshot = { lines: Array<RawLogLine>, 
+/**
+ * Whether the process has exited, i.e. no more lines will be appended
+ */
+finished: boolean, };
+
+export type ExecutionFailureDetail = { execution_process_id: string, kind: ExecutionProcessFailureKind, 
+/**
+ * Last lines written to stderr, oldest first
+ */
+stderr_tail: string, created_at: Date, 
+/**
+ * Whether retrying the attempt as-is is likely to get further
+ */
+retryable: boolean, remediation: string, };
+
+export type ExecutionProcessDetail = { id: string, session_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, status: ExecutionProcessStatus, exit_code: bigint | null, 
+/**
+ * dropped: true if this process is excluded from the current
+ * history view (due to restore/trimming). Hidden from logs/timeline;
+ * still listed in the Processes tab.
+ */
+dropped: boolean, started_at: string, completed_at: string | null, created_at: string, updated_at: string, 
+/**
+ * End of stderr and the likely cause, when the process failed
+ */
+failure_detail: ExecutionFailureDetail | null, 
+/**
+ * Agent CLI, model, platform and repo state the process started with
+ */
+environment: ExecutionProcessEnvironment | null, };
+
+/**
+ * Liveness of an execution process's child process.
+ */
+export type ProcessInspection = { 
+/**
+ * Sampled just now by this instance; otherwise from the last heartbeat
+ */
+live: boolean, pid: bigint | null, 
+/**
+ * Single-letter process state from the OS, e.g. R, S or Z
+ */
+state: string | null, 
+/**
+ * Resident memory of the whole process group
+ */
+rss_kb: bigint | null, 
+/**
+ * CPU time used by the process group so far
+ */
+cpu_seconds: number | null, last_heartbeat_at: Date | null, };
+
+export type FeatureFlagInfo = { flag: FeatureFlag, description: string, default_enabled: boolean, 
+/**
+ * Instance override first, then per-project overrides
+ */
+overrides: Array<FeatureFlagOverride>, };
+
+export type FeatureFlagState = { flag: FeatureFlag, enabled: boolean, };
+
+export type AssignSharedTaskRequest = { new_assignee_user_id: string | null, };
+
+export type ShareTaskResponse = { shared_task_id: string, };
+
+export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, 
+/**
+ * Investigate only: run the agent with read-only tools, for codebase Q&A and audits
+ */
+read_only?: boolean, 
+/**
+ * Dry run: log what the agent's writing tool calls would do instead of running them
+ */
+dry_run?: boolean, 
+/**
+ * Permission mode to run the agent with instead of the one implied by the profile
+ */
+permission_mode?: PermissionMode, 
+/**
+ * System prompt templates added to the agent's first prompt, in order
+ */
+prompt_template_ids?: Array<string>, };
+
+export type FindSimilarTasksRequest = { project_id: string, title: string, description: string | null, 
+/**
+ * Task being edited, left out of the results
+ */
+exclude_task_id: string | null, };
+
+export type UpdateTaskPresenceRequest = { client_id: string, activity: PresenceActivity, 
+/**
+ * Shown to other viewers in single-user mode; the account name is used otherwise
+ */
+name: string | null, };
+
+export type AddTaskLabelRequest = { label: string, };
+
+/**
+ * The task and project an agent request belongs to
+ */
+export type WorkItemContext = { task_id: string, project_id: string, task_title: string, project_name: string, };
+
+export type MyPendingQuestion = { question: UserQuestionRequest, context: WorkItemContext, };
+
+export type MyPendingApproval = { approval: ApprovalRequest, context: WorkItemContext, };
+
+/**
+ * A task whose attempt is in review, waiting for someone to look at it
+ */
+export type AwaitingReviewTask = { task_id: string, project_id: string, title: string, in_review_since: Date, };
+
+/**
+ * Everything waiting on the current user across the projects they can see
+ */
+export type MyWork = { 
+/**
+ * Open tasks assigned to the user
+ */
+assigned_tasks: Array<Task>, 
+/**
+ * Questions agents are waiting on, except those another reviewer has claimed
+ */
+questions: Array<MyPendingQuestion>, approvals: Array<MyPendingApproval>, awaiting_review: Array<AwaitingReviewTask>, stale: Array<StaleTask>, };
+
+/**
+ * An existing task that looks like the same work as a new one.
+ */
+export type SimilarTask = { task_id: string, title: string, 
+/**
+ * Cosine similarity of the two tasks' embeddings, from 0 to 1
+ */
+similarity: number, };
+
+/**
+ * A subtask suggested by the model.
+ */
+export type ProposedSubtask = { title: string, description: string | null, 
+/**
+ * 1-based position in the suggested order of work
+ */
+order: number, };
+
+export type AcceptSubtasksRequest = { 
+/**
+ * The proposals to create, usually a subset of what the breakdown returned
+ */
+subtasks: Array<ProposedSubtask>, 
+/**
+ * Attempt to attach the subtasks to. Defaults to the task's latest attempt; without any
+ * attempt the subtasks are created as regular tasks.
+ */
+parent_workspace_id: string | null, };
+
+export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
+
+export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
+
+export type ImageMetadata = { exists: boolean, file_name: string | null, path: string | null, size_bytes: bigint | null, format: string | null, proxy_url: string | null, };
+
+export type NotionImportPreviewItem = { notion_id: string, task_id: string, title: string, description: string | null, status: TaskStatus, url: string, will_import: boolean, skip_reason: string | null, 
+/**
+ * Most similar existing task when this one looks like a duplicate
+ */
+similar_task: SimilarTask | null, };
+
+export type NotionImportPreviewResponse = { tasks: Array<NotionImportPreviewItem>, total_count: number, importable_count: number, duplicate_count: number, 
+/**
+ * When the tasks were read from Redis
+ */
+synced_at: Date, 
+/**
+ * Set when Redis is unreachable and the last synced tasks are shown instead
+ */
+sync_error: string | null, };
+
+export type NotionImportRequest = { notion_ids: Array<string>, };
+
+export type NotionImportResponse = { imported_count: number, skipped_count: number, errors: Array<NotionImportError>, };
 
-export type TokenResponse = { access_token: string, expires_at: string | null, };
+export type NotionImportError = { notion_id: string, title: string, error: string, };
 
-export type UserSystemInfo = { config: Config, analytics_user_id: string, login_status: LoginStatus, environment: Environment, 
 /**
- * Capabilities supported per executor (e.g., { "CLAUDE_CODE": ["SESSION_FORK"] })
+ * Reachability of the Redis deployment that bridges Notion tasks
  */
-capabilities: { [key in string]?: Array<BaseAgentCapability> }, executors: { [key in BaseCodingAgent]?: ExecutorConfig }, };
-
-export type Environment = { os_type: string, os_version: string, os_architecture: string, bitness: string, };
+export type NotionRedisHealth = { configured: boolean, 
+/**
+ * "single", "sentinel" or "cluster"
+ */
+topology: string | null, connected: boolean, 
+/**
+ * Round trip time of a PING
+ */
+latency_ms: number | null, error: string | null, 
+/**
+ * Whether reads are paused after repeated failures
+ */
+circuit_open: boolean, 
+/**
+ * When Notion tasks were last read successfully
+ */
+last_synced_at: Date | null, };
 
-export type McpServerQuery = { executor: BaseCodingAgent, };
+/**
+ * Preview of a task read from a Redis task source
+ */
+export type TaskSourcePreviewItem = { external_id: string, title: string, description: string | null, status: TaskStatus, url: string | null, will_import: boolean, skip_reason: string | null, 
+/**
+ * Most similar existing task when this one looks like a duplicate
+ */
+similar_task: SimilarTask | null, };
 
-export type UpdateMcpServersBody = { servers: { [key in string]?: JsonValue }, };
+export type TaskSourcePreviewResponse = { tasks: Array<TaskSourcePreviewItem>, total_count: number, importable_count: number, duplicate_count: number, 
+/**
+ * Items left out because they have no id or title under the field mapping
+ */
+invalid_count: number, 
+/**
+ * When the tasks were read from Redis
+ */
+synced_at: Date, 
+/**
+ * Set when Redis is unreachable and the last synced tasks are shown instead
+ */
+sync_error: string | null, };
 
-export type GetMcpServerResponse = { mcp_config: McpConfig, config_path: string, };
+export type TaskSourceImportRequest = { external_ids: Array<string>, };
 
-export type CheckEditorAvailabilityQuery = { editor_type: EditorType, };
+export type TaskSourceImportResponse = { imported_count: number, skipped_count: number, errors: Array<TaskSourceImportError>, };
 
-export type CheckEditorAvailabilityResponse = { available: boolean, };
+export type TaskSourceImportError = { external_id: string, title: string, error: string, };
 
-export type CheckAgentAvailabilityQuery = { executor: BaseCodingAgent, };
+export type IncomingTaskPriority = "low" | "medium" | "high" | "urgent";
 
-export type CurrentUserResponse = { user_id: string, };
+/**
+ * Task opened through a project's incoming webhook
+ */
+export type IncomingTaskPayload = { title: string, description: string | null, labels: Array<string>, priority: IncomingTaskPriority | null, };
 
-export type CreateFollowUpAttempt = { prompt: string, variant: string | null, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, };
+export type IncomingTaskResponse = { task_id: string, };
 
-export type ChangeTargetBranchRequest = { repo_id: string, new_target_branch: string, };
+/**
+ * A newly created or rotated webhook. The secret is only ever returned here.
+ */
+export type ProjectWebhookSecret = { webhook: ProjectWebhook, secret: string, };
 
-export type ChangeTargetBranchResponse = { repo_id: string, new_target_branch: string, status: [number, number], };
+/**
+ * A newly created API token. The token is only ever returned here.
+ */
+export type ApiTokenSecret = { api_token: ApiToken, token: string, };
 
-export type MergeTaskAttemptRequest = { repo_id: string, };
+export type IssueEventOutcome = "created" | "updated" | "ignored";
 
-export type PushTaskAttemptRequest = { repo_id: string, };
+export type UpdateGitHubIssueSync = { 
+/**
+ * `owner/name` of the repository
+ */
+repo_full_name: string, label_filter: string | null, 
+/**
+ * Secret entered in the repository's webhook settings
+ */
+webhook_secret: string, };
 
-export type RenameBranchRequest = { new_branch_name: string, };
+export type GitHubWebhookResponse = { outcome: IssueEventOutcome, task_id: string | null, };
 
-export type RenameBranchResponse = { branch: string, };
+export type ErrorReportOutcome = "created" | "duplicate";
 
-export type OpenEditorRequest = { editor_type: string | null, file_path: string | null, };
+export type UpdateErrorTrackerIntegration = { 
+/**
+ * Sentry client secret, or the bearer secret of generic trackers
+ */
+webhook_secret: string, auto_start: boolean, daily_cost_cap_usd: number | null, };
 
-export type OpenEditorResponse = { url: string | null, };
+export type ErrorTrackerWebhookResponse = { outcome: ErrorReportOutcome, task_id: string, 
+/**
+ * Attempt started to diagnose and fix a new issue
+ */
+workspace_id: string | null, };
 
-export type AssignSharedTaskRequest = { new_assignee_user_id: string | null, };
+export type UpdateSlackIntegration = { 
+/**
+ * Signing secret from the app's Basic Information page
+ */
+signing_secret: string, 
+/**
+ * Bot token (`xoxb-...`) with the `commands`, `chat:write` and `channels:history` scopes
+ */
+bot_token: string, 
+/**
+ * Address of this instance used in task links posted to Slack
+ */
+app_url: string, };
 
-export type ShareTaskResponse = { shared_task_id: string, };
+/**
+ * What a share link shows.
+ */
+export type PublicTaskView = { title: string, description: string | null, status: TaskStatus, attempt: PublicAttemptView | null, expires_at: Date, };
 
-export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+export type PublicAttemptView = { branch: string, summary: string | null, 
+/**
+ * Unified diff of the files chosen when the link was created
+ */
+diff: string | null, checklist: Array<ChecklistItemState>, };
 
-export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
+export type CreatePublicShareLink = { 
+/**
+ * Attempt whose summary and diff are shown
+ */
+workspace_id: string | null, 
+/**
+ * Files of the attempt's diff to include; none shows the summary only
+ */
+diff_paths: Array<string>, 
+/**
+ * Defaults to a week; at most 30 days
+ */
+expires_in_hours: number | null, };
 
-export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
+/**
+ * A share link with its URL. The URL is only returned when the link is created.
+ */
+export type PublicShareLinkWithUrl = { link: PublicShareLink, url: string, };
 
-export type ImageMetadata = { exists: boolean, file_name: string | null, path: string | null, size_bytes: bigint | null, format: string | null, proxy_url: string | null, };
+export type SetTeamMember = { role: TeamRole, };
 
-export type NotionImportPreviewItem = { notion_id: string, task_id: string, title: string, description: string | null, status: TaskStatus, url: string, will_import: boolean, skip_reason: string | null, };
+export type TeamDetails = { team: Team, members: Array<TeamMember>, projects: Array<Project>, };
 
-export type NotionImportPreviewResponse = { tasks: Array<NotionImportPreviewItem>, total_count: number, importable_count: number, duplicate_count: number, };
+/**
+ * Where a pending question or approval comes from.
+ */
+export type PendingItemContext = { project_id: string, project_name: string, task_id: string, task_title: string, workspace_id: string, };
 
-export type NotionImportRequest = { notion_ids: Array<string>, };
+export type TeamPendingQuestion = { context: PendingItemContext, question: UserQuestionRequest, };
 
-export type NotionImportResponse = { imported_count: number, skipped_count: number, errors: Array<NotionImportError>, };
+export type TeamPendingApproval = { context: PendingItemContext, approval: ApprovalRequest, };
 
-export type NotionImportError = { notion_id: string, title: string, error: string, };
+/**
+ * Questions and approvals agents are waiting on across a set of projects.
+ */
+export type TeamPendingItems = { questions: Array<TeamPendingQuestion>, approvals: Array<TeamPendingApproval>, };
 
-export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, 
+/**
+ * Investigate only: run the agent with read-only tools, for codebase Q&A and audits
+ */
+read_only?: boolean, 
+/**
+ * Dry run: log what the agent's writing tool calls would do instead of running them
+ */
+dry_run?: boolean, 
+/**
+ * Permission mode to run the agent with instead of the one implied by the profile
+ */
+permission_mode?: PermissionMode, 
+/**
+ * System prompt templates added to the agent's first prompt, in order
+ */
+prompt_template_ids?: Array<string>, };
 
 export type WorkspaceRepoInput = { repo_id: string, target_branch: string, };
 
@@ -276,7 +1845,7 @@ export type GitOperationError = { "type": "merge_conflicts", message: string, op
 
 export type PushError = { "type": "force_push_required" };
 
-export type CreatePrError = { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, };
+export type CreatePrError = { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, } | { "type": "queued", outbox_id: string, };
 
 export type BranchStatus = { commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
 /**
@@ -298,6 +1867,78 @@ export type AttachPrResponse = { pr_attached: boolean, pr_url: string | null, pr
 
 export type AttachExistingPrRequest = { repo_id: string, };
 
+export type StartReviewRequest = { 
+/**
+ * Defaults to the configured review executor, then to the one that ran the attempt
+ */
+executor_profile_id: ExecutorProfileId | null, };
+
+export type ReviewWithFindings = { id: string, workspace_id: string, session_id: string, status: ReviewStatus, executor: string, error: string | null, created_at: Date, completed_at: Date | null, findings: Array<ReviewFinding>, };
+
+export type ResolveReviewFindingRequest = { resolved: boolean, };
+
+/**
+ * A reviewer's inline comments on the attempt and their latest verdict.
+ */
+export type AttemptReviewState = { latest_review: AttemptReview | null, comments: Array<AttemptReviewComment>, };
+
+/**
+ * Instructions found in one repository.
+ */
+export type RepoInstructions = { repo_name: string, 
+/**
+ * Path of the file relative to the repository root
+ */
+file: string, content: string, };
+
+/**
+ * The prompt the attempt's first run is given, composed from the attempt as it is now.
+ */
+export type PromptPreview = { executor_profile_id: ExecutorProfileId, 
+/**
+ * Includes the profile's appended text
+ */
+prompt: string, 
+/**
+ * Instructions files found in the attempt's repositories
+ */
+instructions: Array<RepoInstructions>, };
+
+export type FileComparisonStatus = "only_in_a" | "only_in_b" | "same" | "differs";
+
+export type FileChange = { change: DiffChangeKind, additions: number, deletions: number, };
+
+export type FileComparison = { path: string, status: FileComparisonStatus, a: FileChange | null, b: FileChange | null, };
+
+/**
+ * Result of the latest cleanup script, which is where projects run their tests.
+ */
+export type TestOutcome = "passed" | "failed" | "not_run";
+
+export type AttemptSide = { workspace_id: string, branch: string, created_at: Date, 
+/**
+ * Cost reported by the attempt's coding agent runs, `None` when none reported one
+ */
+cost_usd: number | null, 
+/**
+ * Wall-clock time of the attempt's finished coding agent runs
+ */
+agent_seconds: number, agent_runs: number, tests: TestOutcome, files_changed: number, additions: number, deletions: number, };
+
+export type AttemptComparison = { a: AttemptSide, b: AttemptSide, 
+/**
+ * Files changed by either attempt, by path
+ */
+files: Array<FileComparison>, };
+
+export type SetChecklistItemRequest = { checked: boolean, };
+
+export type RollbackCheckpointRequest = { 
+/**
+ * Also drop the runs that followed the checkpoint, so the next follow-up continues from it
+ */
+truncate_conversation: boolean, };
+
 export type PrCommentsResponse = { comments: Array<UnifiedPrComment>, };
 
 export type GetPrCommentsError = { "type": "no_pr_attached" } | { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" };
@@ -324,7 +1965,17 @@ export type DirectoryEntry = { name: string, path: string, is_directory: boolean
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, };
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, backups: BackupScheduleConfig, maintenance: MaintenanceScheduleConfig, mobile_push: MobilePushConfig, llm: LlmConfig, embeddings: EmbeddingConfig, code_review: CodeReviewConfig, code_map: CodeMapConfig, review_sla: ReviewSlaConfig, stale_tasks: StaleTaskConfig, execution_limits: ExecutionLimitsConfig, 
+/**
+ * Seconds an answer to an agent's question can still be amended or withdrawn before the
+ * agent receives it. 0 delivers answers straight away.
+ */
+question_answer_grace_secs: bigint, 
+/**
+ * Restart a coding agent that overflowed its context in a fresh session seeded with a
+ * summary of the conversation and the diff, instead of failing the attempt
+ */
+restart_on_context_overflow: boolean, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -344,6 +1995,165 @@ export type UiLanguage = "BROWSER" | "EN" | "JA" | "ES" | "KO" | "ZH_HANS";
 
 export type ShowcaseState = { seen_features: Array<string>, };
 
+export type BackupScheduleConfig = { enabled: boolean, frequency: ScheduleFrequency, 
+/**
+ * Number of scheduled backups to keep; older ones are deleted
+ */
+keep: number, 
+/**
+ * Directory for scheduled backups, defaults to the `backups` folder in the data directory
+ */
+destination_dir: string | null, 
+/**
+ * Optional `s3://bucket/prefix` to copy each backup to, using the standard `AWS_*` credentials
+ */
+s3_uri: string | null, };
+
+export type ScheduleFrequency = "daily" | "weekly";
+
+export type MaintenanceScheduleConfig = { 
+/**
+ * Periodically run VACUUM, ANALYZE and an integrity check on the database
+ */
+enabled: boolean, frequency: ScheduleFrequency, };
+
+/**
+ * Phone notifications through ntfy and Pushover. Topics and user keys set here receive every
+ * notification; in multi-user mode each user can also register their own.
+ */
+export type MobilePushConfig = { enabled: boolean, 
+/**
+ * ntfy server, e.g. a self-hosted instance
+ */
+ntfy_server: string, ntfy_topic: string | null, 
+/**
+ * Access token for protected ntfy topics
+ */
+ntfy_token: string | null, 
+/**
+ * Pushover application API token, required for Pushover delivery
+ */
+pushover_app_token: string | null, pushover_user_key: string | null, };
+
+/**
+ * Language model used by assistant features such as task breakdown.
+ */
+export type LlmConfig = { 
+/**
+ * `openai` also covers any server implementing the OpenAI chat completions API
+ */
+provider: LlmProvider, model: string, 
+/**
+ * Falls back to `ANTHROPIC_API_KEY` or `OPENAI_API_KEY` from the environment
+ */
+api_key: string | null, 
+/**
+ * API base URL override, e.g. `http://localhost:11434/v1` for a local server
+ */
+base_url: string | null, 
+/**
+ * Summarize finished attempts from their log and diff for review
+ */
+summarize_attempts: boolean, };
+
+export type LlmProvider = "anthropic" | "openai";
+
+/**
+ * Text embeddings used to flag likely duplicate tasks.
+ */
+export type EmbeddingConfig = { 
+/**
+ * `local` hashes words and character n-grams without any API calls; `openai` also covers
+ * any server implementing the OpenAI embeddings API
+ */
+provider: EmbeddingProvider, model: string, 
+/**
+ * Falls back to `OPENAI_API_KEY` from the environment
+ */
+api_key: string | null, 
+/**
+ * API base URL override, e.g. `http://localhost:11434/v1` for a local server
+ */
+base_url: string | null, 
+/**
+ * Cosine similarity from 0 to 1 above which tasks count as duplicates. Defaults to a value
+ * suited to the provider.
+ */
+similarity_threshold: number | null, };
+
+export type EmbeddingProvider = "local" | "openai";
+
+/**
+ * Second-agent review of finished attempts.
+ */
+export type CodeReviewConfig = { 
+/**
+ * Start a review whenever a coding agent finishes an attempt successfully
+ */
+auto_review: boolean, 
+/**
+ * Executor that performs reviews. Defaults to the one that ran the attempt.
+ */
+executor_profile: ExecutorProfileId | null, 
+/**
+ * Refuse to move a task to Done while its latest review has unresolved blocking findings
+ */
+block_done_on_findings: boolean, };
+
+/**
+ * Map of each repository's files and public symbols, shared with coding agents.
+ */
+export type CodeMapConfig = { 
+/**
+ * Add the map to the prompt of new attempts so agents spend fewer turns exploring
+ */
+include_in_prompt: boolean, 
+/**
+ * Budget for the map in the prompt; the rest of the map is left out
+ */
+max_prompt_bytes: number, };
+
+/**
+ * How long tasks may wait in review for a human before reminders are sent.
+ */
+export type ReviewSlaConfig = { reminders_enabled: boolean, 
+/**
+ * Hours in review after which a reminder is sent, each one more urgent than the last.
+ * The first threshold is the SLA itself.
+ */
+thresholds_hours: Array<number>, };
+
+/**
+ * Policy for tasks nobody has touched in a while.
+ */
+export type StaleTaskConfig = { 
+/**
+ * Flag stale tasks and warn about them in the notification center
+ */
+enabled: boolean, 
+/**
+ * Days without changes to the task or runs of its attempts before it is flagged
+ */
+stale_after_days: number, 
+/**
+ * Columns the policy applies to
+ */
+statuses: Array<TaskStatus>, 
+/**
+ * Move flagged tasks to Cancelled once the grace period passes without activity
+ */
+auto_close: boolean, grace_period_days: number, };
+
+export type ExecutionLimitsConfig = { 
+/**
+ * Coding agent runs
+ */
+coding_agent: ResourceLimits, 
+/**
+ * Setup, cleanup and dev server scripts
+ */
+scripts: ResourceLimits, };
+
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
 export type SharedTaskDetails = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, };
@@ -368,11 +2178,15 @@ export type ConflictOp = "rebase" | "merge" | "cherry_pick" | "revert";
 
 export type ExecutorAction = { typ: ExecutorActionType, next_action: ExecutorAction | null, };
 
+export type ResourceLimits = { memory_mb: bigint | null, cpu_seconds: bigint | null, wall_clock_secs: bigint | null, };
+
+export type ResourceLimit = "memory" | "cpu" | "wall_clock";
+
 export type McpConfig = { servers: { [key in string]?: JsonValue }, servers_path: Array<string>, template: JsonValue, preconfigured: JsonValue, is_toml_config: boolean, };
 
 export type ExecutorActionType = { "type": "CodingAgentInitialRequest" } & CodingAgentInitialRequest | { "type": "CodingAgentFollowUpRequest" } & CodingAgentFollowUpRequest | { "type": "ScriptRequest" } & ScriptRequest;
 
-export type ScriptContext = "SetupScript" | "CleanupScript" | "DevServer" | "ToolInstallScript";
+export type ScriptContext = "SetupScript" | "CleanupScript" | "DevServer" | "ToolInstallScript" | "LifecycleHook";
 
 export type ScriptRequest = { script: string, language: ScriptRequestLanguage, context: ScriptContext, 
 /**
@@ -413,15 +2227,42 @@ export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } |
 
 export type ExecutorConfigs = { executors: { [key in BaseCodingAgent]?: ExecutorConfig }, };
 
-export enum BaseAgentCapability { SESSION_FORK = "SESSION_FORK", SETUP_HELPER = "SETUP_HELPER" }
+export enum BaseAgentCapability { SESSION_FORK = "SESSION_FORK", SETUP_HELPER = "SETUP_HELPER", READ_ONLY = "READ_ONLY", DRY_RUN = "DRY_RUN", PERMISSION_MODES = "PERMISSION_MODES" }
+
+export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, 
+/**
+ * Models to try in order when a run fails on provider errors, a context overflow or an
+ * exhausted budget
+ */
+model_fallbacks?: Array<string> | null, dangerously_skip_permissions?: boolean | null, disable_api_key?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, 
+/**
+ * Environment variables to set when running the executor. Values may reference secrets with ${NAME}
+ */
+env?: { [key in string]?: string } | null, };
 
-export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, disable_api_key?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+/**
+ * How much Claude Code may do without asking. Chosen per attempt, or implied by the profile's
+ * `plan` and `approvals` settings when the attempt doesn't pick one.
+ */
+export type PermissionMode = "default" | "acceptEdits" | "plan" | "bypassPermissions";
 
-export type Gemini = { append_prompt: AppendPrompt, model?: string | null, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Gemini = { append_prompt: AppendPrompt, model?: string | null, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, 
+/**
+ * Environment variables to set when running the executor. Values may reference secrets with ${NAME}
+ */
+env?: { [key in string]?: string } | null, };
 
-export type Amp = { append_prompt: AppendPrompt, dangerously_allow_all?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Amp = { append_prompt: AppendPrompt, dangerously_allow_all?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, 
+/**
+ * Environment variables to set when running the executor. Values may reference secrets with ${NAME}
+ */
+env?: { [key in string]?: string } | null, };
 
-export type Codex = { append_prompt: AppendPrompt, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_apply_patch_tool?: boolean | null, model_provider?: string | null, compact_prompt?: string | null, developer_instructions?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Codex = { append_prompt: AppendPrompt, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_apply_patch_tool?: boolean | null, model_provider?: string | null, compact_prompt?: string | null, developer_instructions?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, 
+/**
+ * Environment variables to set when running the executor. Values may reference secrets with ${NAME}
+ */
+env?: { [key in string]?: string } | null, };
 
 export type SandboxMode = "auto" | "read-only" | "workspace-write" | "danger-full-access";
 
@@ -433,19 +2274,39 @@ export type ReasoningSummary = "auto" | "concise" | "detailed" | "none";
 
 export type ReasoningSummaryFormat = "none" | "experimental";
 
-export type CursorAgent = { append_prompt: AppendPrompt, force?: boolean | null, model?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type CursorAgent = { append_prompt: AppendPrompt, force?: boolean | null, model?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, 
+/**
+ * Environment variables to set when running the executor. Values may reference secrets with ${NAME}
+ */
+env?: { [key in string]?: string } | null, };
 
-export type Copilot = { append_prompt: AppendPrompt, model?: string | null, allow_all_tools?: boolean | null, allow_tool?: string | null, deny_tool?: string | null, add_dir?: Array<string> | null, disable_mcp_server?: Array<string> | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Copilot = { append_prompt: AppendPrompt, model?: string | null, allow_all_tools?: boolean | null, allow_tool?: string | null, deny_tool?: string | null, add_dir?: Array<string> | null, disable_mcp_server?: Array<string> | null, base_command_override?: string | null, additional_params?: Array<string> | null, 
+/**
+ * Environment variables to set when running the executor. Values may reference secrets with ${NAME}
+ */
+env?: { [key in string]?: string } | null, };
 
 export type Opencode = { append_prompt: AppendPrompt, model?: string | null, mode?: string | null, 
 /**
  * Auto-approve agent actions
  */
-auto_approve: boolean, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+auto_approve: boolean, base_command_override?: string | null, additional_params?: Array<string> | null, 
+/**
+ * Environment variables to set when running the executor. Values may reference secrets with ${NAME}
+ */
+env?: { [key in string]?: string } | null, };
 
-export type QwenCode = { append_prompt: AppendPrompt, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type QwenCode = { append_prompt: AppendPrompt, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, 
+/**
+ * Environment variables to set when running the executor. Values may reference secrets with ${NAME}
+ */
+env?: { [key in string]?: string } | null, };
 
-export type Droid = { append_prompt: AppendPrompt, autonomy: Autonomy, model?: string | null, reasoning_effort?: DroidReasoningEffort | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Droid = { append_prompt: AppendPrompt, autonomy: Autonomy, model?: string | null, reasoning_effort?: DroidReasoningEffort | null, base_command_override?: string | null, additional_params?: Array<string> | null, 
+/**
+ * Environment variables to set when running the executor. Values may reference secrets with ${NAME}
+ */
+env?: { [key in string]?: string } | null, };
 
 export type Autonomy = "normal" | "low" | "medium" | "high" | "skip-permissions-unsafe";
 
@@ -462,7 +2323,11 @@ executor_profile_id: ExecutorProfileId,
  * Optional relative path to execute the agent in (relative to container_ref).
  * If None, uses the container_ref directory directly.
  */
-working_dir: string | null, };
+working_dir: string | null, 
+/**
+ * Model to run instead of the profile's, set when falling back after a failed run
+ */
+model?: string | null, };
 
 export type CodingAgentFollowUpRequest = { prompt: string, session_id: string, 
 /**
@@ -473,7 +2338,11 @@ executor_profile_id: ExecutorProfileId,
  * Optional relative path to execute the agent in (relative to container_ref).
  * If None, uses the container_ref directory directly.
  */
-working_dir: string | null, };
+working_dir: string | null, 
+/**
+ * Model to run instead of the profile's, set when falling back after a failed run
+ */
+model?: string | null, };
 
 export type CommandExitStatus = { "type": "exit_code", code: number, } | { "type": "success", success: boolean, };
 
@@ -481,7 +2350,20 @@ export type CommandRunResult = { exit_status: CommandExitStatus | null, output:
 
 export type NormalizedEntry = { timestamp: string | null, entry_type: NormalizedEntryType, content: string, };
 
-export type NormalizedEntryType = { "type": "user_message" } | { "type": "user_feedback", denied_tool: string, } | { "type": "assistant_message" } | { "type": "tool_use", tool_name: string, action_type: ActionType, status: ToolStatus, } | { "type": "system_message" } | { "type": "error_message", error_type: NormalizedEntryError, } | { "type": "thinking" } | { "type": "loading" } | { "type": "next_action", failed: boolean, execution_processes: number, needs_setup: boolean, };
+export type NormalizedEntryType = { "type": "user_message" } | { "type": "user_feedback", denied_tool: string, } | { "type": "assistant_message" } | { "type": "tool_use", tool_name: string, action_type: ActionType, status: ToolStatus, } | { "type": "system_message" } | { "type": "error_message", error_type: NormalizedEntryError, } | { "type": "thinking" } | { "type": "loading" } | { "type": "next_action", failed: boolean, execution_processes: number, needs_setup: boolean, } | { "type": "human_handoff", handoff: HumanHandoff, };
+
+/**
+ * Note an agent leaves when it declares itself blocked.
+ */
+export type HumanHandoff = { 
+/**
+ * What the agent completed before getting stuck
+ */
+done: string, 
+/**
+ * What keeps the agent from going on
+ */
+blocked: string, next_steps: Array<string>, };
 
 export type FileChange = { "action": "write", content: string, } | { "action": "delete" } | { "action": "rename", new_path: string, } | { "action": "edit", 
 /**
@@ -507,7 +2389,11 @@ value: JsonValue, };
 
 export type ToolResultValueType = { "type": "markdown" } | { "type": "json" };
 
-export type ToolStatus = { "status": "created" } | { "status": "success" } | { "status": "failed" } | { "status": "denied", reason: string | null, } | { "status": "pending_approval", approval_id: string, requested_at: string, timeout_at: string, } | { "status": "pending_question", question_id: string, requested_at: string, timeout_at: string, questions: Array<UserQuestion>, } | { "status": "timed_out" };
+export type ToolStatus = { "status": "created" } | { "status": "success" } | { "status": "failed" } | { "status": "denied", reason: string | null, } | { "status": "pending_approval", approval_id: string, requested_at: string, timeout_at: string, } | { "status": "pending_question", question_id: string, requested_at: string, timeout_at: string, questions: Array<UserQuestion>, 
+/**
+ * Reviewer currently answering the question
+ */
+claimed_by: QuestionClaim | null, } | { "status": "timed_out" };
 
 export type PatchType = { "type": "NORMALIZED_ENTRY", "content": NormalizedEntry } | { "type": "STDOUT", "content": string } | { "type": "STDERR", "content": string } | { "type": "DIFF", "content": Diff };
 